@@ -21,6 +21,7 @@ pub struct InputStory {
     input_esc: Entity<InputState>,
     mask_input: Entity<InputState>,
     disabled_input: Entity<InputState>,
+    read_only_input: Entity<InputState>,
     prefix_input1: Entity<InputState>,
     suffix_input1: Entity<InputState>,
     both_input1: Entity<InputState>,
@@ -107,6 +108,8 @@ impl InputStory {
             mask_input,
             disabled_input: cx
                 .new(|cx| InputState::new(window, cx).default_value("This is disabled input")),
+            read_only_input: cx
+                .new(|cx| InputState::new(window, cx).default_value("This is read-only input")),
             large_input: cx.new(|cx| InputState::new(window, cx).placeholder("Large input")),
             small_input: cx.new(|cx| {
                 InputState::new(window, cx)
@@ -153,7 +156,7 @@ impl FocusableCycle for InputStory {
         [
             self.input1.focus_handle(cx),
             self.input2.focus_handle(cx),
-            self.disabled_input.focus_handle(cx),
+            self.read_only_input.focus_handle(cx),
             self.mask_input.focus_handle(cx),
             self.prefix_input1.focus_handle(cx),
             self.both_input1.focus_handle(cx),
@@ -194,6 +197,7 @@ impl Render for InputStory {
                 section("Input State")
                     .max_w_md()
                     .child(TextInput::new(&self.disabled_input).disabled(true))
+                    .child(TextInput::new(&self.read_only_input).read_only(true))
                     .child(TextInput::new(&self.mask_input).mask_toggle().cleanable()),
             )
             .child(
@@ -6,7 +6,7 @@ use gpui_component::{
     h_flex,
     input::{InputEvent, InputState, TextInput},
     v_flex,
-    webview::WebView,
+    webview::{WebView, WebViewEvent},
     wry, ActiveTheme,
 };
 
@@ -43,7 +43,7 @@ impl WebViewStory {
         let focus_handle = cx.focus_handle();
 
         let webview = cx.new(|cx| {
-            let builder = wry::WebViewBuilder::new();
+            let (builder, events) = WebView::bind_events(wry::WebViewBuilder::new());
             #[cfg(not(any(
                 target_os = "windows",
                 target_os = "macos",
@@ -73,7 +73,7 @@ impl WebViewStory {
                 builder.build_as_child(&window_handle).unwrap()
             };
 
-            WebView::new(webview, window, cx)
+            WebView::new(webview, window, cx).events(events, cx)
         });
 
         let address_input =
@@ -87,7 +87,7 @@ impl WebViewStory {
         cx.new(|cx| {
             let this = WebViewStory {
                 focus_handle,
-                webview,
+                webview: webview.clone(),
                 address_input: address_input.clone(),
             };
 
@@ -105,6 +105,20 @@ impl WebViewStory {
             )
             .detach();
 
+            cx.subscribe_in(
+                &webview,
+                window,
+                |this: &mut Self, _, event: &WebViewEvent, window, cx| match event {
+                    WebViewEvent::Loaded(url) => {
+                        this.address_input.update(cx, |input, cx| {
+                            input.set_value(url.clone(), window, cx);
+                        });
+                    }
+                    _ => {}
+                },
+            )
+            .detach();
+
             this
         })
     }
@@ -0,0 +1,339 @@
+use std::{collections::HashMap, rc::Rc};
+
+use gpui::{
+    div, prelude::FluentBuilder as _, App, Global, IntoElement, ParentElement as _, RenderOnce,
+    SharedString, StyleRefinement, Styled, Window, WindowId,
+};
+
+use crate::{text::Text, ActiveTheme as _, StyledExt as _};
+
+/// The role of an element, used to describe it to the assistive technologies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessibilityRole {
+    Button,
+    Checkbox,
+    Switch,
+    Radio,
+    RadioGroup,
+    Slider,
+    List,
+    ListItem,
+    Tab,
+    TabList,
+    Link,
+}
+
+/// The accessibility metadata of an element.
+///
+/// The `None` fields are not applicable to the element.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccessibilityProperties {
+    /// The role of the element.
+    pub role: Option<AccessibilityRole>,
+    /// The accessible name of the element.
+    pub label: Option<SharedString>,
+    /// The additional description of the element.
+    pub description: Option<SharedString>,
+    /// The human readable value of the element, e.g.: "50%" for a Slider.
+    pub value: Option<SharedString>,
+    pub checked: Option<bool>,
+    pub selected: Option<bool>,
+    pub expanded: Option<bool>,
+    pub disabled: bool,
+    pub read_only: bool,
+}
+
+impl AccessibilityProperties {
+    /// Create a new properties with the role.
+    pub fn new(role: AccessibilityRole) -> Self {
+        Self {
+            role: Some(role),
+            ..Default::default()
+        }
+    }
+
+    /// Set the label of the element.
+    pub fn label(mut self, label: Option<impl Into<SharedString>>) -> Self {
+        self.label = label.map(Into::into);
+        self
+    }
+
+    /// Set the value of the element.
+    pub fn value(mut self, value: impl Into<SharedString>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Set the checked state of the element.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    /// Set the selected state of the element.
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = Some(selected);
+        self
+    }
+
+    /// Set the expanded state of the element.
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = Some(expanded);
+        self
+    }
+
+    /// Set the disabled state of the element.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Set the read-only state of the element.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Merge the properties set by the user into the properties detected from the element.
+    ///
+    /// The user properties have higher priority, except the `disabled` and `read_only` states.
+    pub(crate) fn merge(mut self, other: &AccessibilityProperties) -> Self {
+        if other.role.is_some() {
+            self.role = other.role;
+        }
+        if other.label.is_some() {
+            self.label = other.label.clone();
+        }
+        if other.description.is_some() {
+            self.description = other.description.clone();
+        }
+        if other.value.is_some() {
+            self.value = other.value.clone();
+        }
+        if other.checked.is_some() {
+            self.checked = other.checked;
+        }
+        if other.selected.is_some() {
+            self.selected = other.selected;
+        }
+        if other.expanded.is_some() {
+            self.expanded = other.expanded;
+        }
+        self.disabled |= other.disabled;
+        self.read_only |= other.read_only;
+        self
+    }
+}
+
+/// Returns the plain text of the label to use as the accessible name.
+pub(crate) fn text_label(text: Option<&Text>) -> Option<SharedString> {
+    match text {
+        Some(Text::String(s)) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+/// The accessibility properties of the elements rendered in the windows, in the render order.
+#[derive(Default)]
+struct RenderedElements(HashMap<WindowId, Vec<AccessibilityProperties>>);
+
+impl Global for RenderedElements {}
+
+/// Clear the rendered elements of the window, called by the [`Root`](crate::Root) at the
+/// start of each frame.
+pub(crate) fn clear_rendered(window: &Window, cx: &mut App) {
+    if let Some(elements) = cx
+        .default_global::<RenderedElements>()
+        .0
+        .get_mut(&window.window_handle().window_id())
+    {
+        elements.clear();
+    }
+}
+
+/// Record the accessibility properties of the element rendered in the window.
+pub(crate) fn record_rendered(properties: AccessibilityProperties, window: &Window, cx: &mut App) {
+    cx.default_global::<RenderedElements>()
+        .0
+        .entry(window.window_handle().window_id())
+        .or_default()
+        .push(properties);
+}
+
+/// Returns the accessibility properties of the elements rendered in the last frame of
+/// the window, in the render order.
+///
+/// This is the tree to expose to the assistive technologies, or to query in the tests,
+/// only the elements in the [`Root`](crate::Root) are collected.
+pub fn rendered_accessibility<'a>(window: &Window, cx: &'a App) -> &'a [AccessibilityProperties] {
+    cx.try_global::<RenderedElements>()
+        .and_then(|elements| elements.0.get(&window.window_handle().window_id()))
+        .map_or(&[], |elements| elements.as_slice())
+}
+
+/// A trait for the elements that can be described to the assistive technologies.
+///
+/// The role and states are detected from the element, use the `accessibility_*` methods
+/// to set the label, description or value that can't be detected.
+///
+/// The properties are collected when the element is rendered, see [`rendered_accessibility`].
+pub trait Accessible: Sized {
+    /// Returns a mutable reference to the accessibility properties set by the user.
+    fn accessibility_mut(&mut self) -> &mut AccessibilityProperties;
+
+    /// Returns the accessibility properties of the element.
+    fn accessibility(&self, cx: &App) -> AccessibilityProperties;
+
+    /// Set the role of the element, default is detected from the element.
+    fn accessibility_role(mut self, role: AccessibilityRole) -> Self {
+        self.accessibility_mut().role = Some(role);
+        self
+    }
+
+    /// Set the accessible name of the element, default is the label of the element.
+    ///
+    /// This is required for the elements without a text label, e.g.: Icon Button.
+    fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.accessibility_mut().label = Some(label.into());
+        self
+    }
+
+    /// Set the additional description of the element.
+    fn accessibility_description(mut self, description: impl Into<SharedString>) -> Self {
+        self.accessibility_mut().description = Some(description.into());
+        self
+    }
+
+    /// Set the human readable value of the element.
+    fn accessibility_value(mut self, value: impl Into<SharedString>) -> Self {
+        self.accessibility_mut().value = Some(value.into());
+        self
+    }
+}
+
+/// The priority of an [`Announcement`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Politeness {
+    /// Announce when the user is idle, e.g.: the results count of a search.
+    #[default]
+    Polite,
+    /// Announce immediately and interrupt the current one, e.g.: an error.
+    Assertive,
+}
+
+/// A message of the async update, see [`announce`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Announcement {
+    pub message: SharedString,
+    pub politeness: Politeness,
+}
+
+/// The live region of the application, all the announcements are sent through it.
+#[derive(Default)]
+struct Announcer {
+    last: Option<Announcement>,
+    listeners: Vec<Rc<dyn Fn(&Announcement, &mut App)>>,
+}
+
+impl Global for Announcer {}
+
+/// Announce a message of the async update, e.g.: a new notification, "5 results" of a search.
+///
+/// The message is sent to the listeners registered by [`on_announce`] (e.g.: a screen reader
+/// integration), and displayed by the [`LiveRegion`].
+pub fn announce(message: impl Into<SharedString>, politeness: Politeness, cx: &mut App) {
+    let announcement = Announcement {
+        message: message.into(),
+        politeness,
+    };
+    if announcement.message.is_empty() {
+        return;
+    }
+
+    let announcer = cx.default_global::<Announcer>();
+    announcer.last = Some(announcement.clone());
+    let listeners = announcer.listeners.clone();
+    for listener in listeners {
+        listener(&announcement, cx);
+    }
+    cx.refresh_windows();
+}
+
+/// Register a listener to receive all the announcements.
+pub fn on_announce(cx: &mut App, listener: impl Fn(&Announcement, &mut App) + 'static) {
+    cx.default_global::<Announcer>()
+        .listeners
+        .push(Rc::new(listener));
+}
+
+/// Returns the last announcement.
+pub fn last_announcement(cx: &App) -> Option<&Announcement> {
+    cx.try_global::<Announcer>()?.last.as_ref()
+}
+
+/// A visible status line to display the last announcement.
+#[derive(IntoElement)]
+pub struct LiveRegion {
+    style: StyleRefinement,
+}
+
+impl LiveRegion {
+    pub fn new() -> Self {
+        Self {
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for LiveRegion {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for LiveRegion {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let announcement = last_announcement(cx).cloned();
+
+        div()
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .whitespace_nowrap()
+            .truncate()
+            .refine_style(&self.style)
+            .when_some(announcement, |this, announcement| {
+                this.when(announcement.politeness == Politeness::Assertive, |this| {
+                    this.text_color(cx.theme().foreground)
+                })
+                .child(announcement.message)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccessibilityProperties, AccessibilityRole};
+
+    #[test]
+    fn test_merge_properties() {
+        let detected = AccessibilityProperties::new(AccessibilityRole::Checkbox)
+            .label(Some("Remember me"))
+            .checked(true);
+
+        let merged = detected.clone().merge(&AccessibilityProperties::default());
+        assert_eq!(merged, detected);
+
+        let user = AccessibilityProperties {
+            label: Some("Remember".into()),
+            description: Some("Keep me signed in".into()),
+            disabled: true,
+            ..Default::default()
+        };
+        let merged = detected.merge(&user);
+        assert_eq!(merged.role, Some(AccessibilityRole::Checkbox));
+        assert_eq!(merged.label, Some("Remember".into()));
+        assert_eq!(merged.description, Some("Keep me signed in".into()));
+        assert_eq!(merged.checked, Some(true));
+        assert!(merged.disabled);
+    }
+}
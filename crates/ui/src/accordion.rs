@@ -1,12 +1,15 @@
-use std::{cell::RefCell, collections::HashSet, rc::Rc, sync::Arc};
+use std::{cell::RefCell, collections::HashSet, rc::Rc, sync::Arc, time::Duration};
 
 use gpui::{
-    div, prelude::FluentBuilder as _, rems, AnyElement, App, ElementId, InteractiveElement as _,
-    IntoElement, ParentElement, RenderOnce, SharedString, StatefulInteractiveElement as _, Styled,
-    Window,
+    div, prelude::FluentBuilder as _, px, rems, AnyElement, App, ElementId,
+    InteractiveElement as _, IntoElement, ParentElement, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, Styled, Window,
 };
 
-use crate::{h_flex, v_flex, ActiveTheme as _, Icon, IconName, Sizable, Size};
+use crate::{
+    animation::{Transition, TransitionExt as _},
+    h_flex, v_flex, ActiveTheme as _, Icon, IconName, Sizable, Size,
+};
 
 /// An AccordionGroup is a container for multiple Accordion elements.
 #[derive(IntoElement)]
@@ -211,6 +214,7 @@ impl Sizable for AccordionItem {
 
 impl RenderOnce for AccordionItem {
     fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let reduce_motion = cx.theme().reduce_motion;
         let text_size = match self.size {
             Size::XSmall => rems(0.875),
             Size::Small => rems(0.875),
@@ -296,7 +300,14 @@ impl RenderOnce for AccordionItem {
                                 Size::Large => this.p_4(),
                                 _ => this.p_3(),
                             })
-                            .child(self.content),
+                            .child(self.content)
+                            .animate_in(
+                                ("accordion-content", self.index),
+                                Transition::new(Duration::from_millis(150))
+                                    .fade()
+                                    .slide_down(px(4.))
+                                    .reduce_motion(reduce_motion),
+                            ),
                     )
                 }),
         )
@@ -8,4 +8,15 @@ pub struct Confirm {
     pub secondary: bool,
 }
 
-actions!(list, [Cancel, SelectPrev, SelectNext]);
+actions!(
+    list,
+    [
+        Cancel,
+        SelectPrev,
+        SelectNext,
+        SelectFirst,
+        SelectLast,
+        SelectPageUp,
+        SelectPageDown
+    ]
+);
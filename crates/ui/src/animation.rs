@@ -1,3 +1,15 @@
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    px, AbsoluteLength, Animation, AnimationElement, AnimationExt as _, App, DefiniteLength,
+    ElementId, IntoElement, Length, Pixels, Point, Styled, Window,
+};
+
+use crate::ActiveTheme as _;
+
 /// A cubic bezier function like CSS `cubic-bezier`.
 ///
 /// Builder:
@@ -17,3 +29,419 @@ pub fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32) -> impl Fn(f32) -> f32 {
         y
     }
 }
+
+/// The enter and exit transition of an element, the effects are combined, e.g.: fade and slide.
+///
+/// The slide effects are added to the existing inset of the element, e.g.: the `top` of an
+/// absolute element, or the `bottom` if only the `bottom` is set.
+///
+/// ```ignore
+/// let transition = Transition::new(Duration::from_millis(200)).fade().slide_up(px(8.));
+///
+/// // Stagger the children by 30ms.
+/// v_flex().children(items.into_iter().enumerate().map(|(ix, item)| {
+///     item.animate_in(("item", ix), transition.clone().stagger(ix, Duration::from_millis(30)))
+/// }))
+/// ```
+#[derive(Clone)]
+pub struct Transition {
+    duration: Duration,
+    delay: Duration,
+    easing: Rc<dyn Fn(f32) -> f32>,
+    fade: bool,
+    offset: Point<Pixels>,
+}
+
+impl Transition {
+    /// Create a transition with the `duration`, default easing is `ease-out`.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            delay: Duration::ZERO,
+            easing: Rc::new(cubic_bezier(0., 0., 0.58, 1.)),
+            fade: false,
+            offset: Point::default(),
+        }
+    }
+
+    /// Fade the opacity of the element.
+    pub fn fade(mut self) -> Self {
+        self.fade = true;
+        self
+    }
+
+    /// Slide the element up from the `distance` below.
+    pub fn slide_up(mut self, distance: Pixels) -> Self {
+        self.offset.y = distance;
+        self
+    }
+
+    /// Slide the element down from the `distance` above.
+    pub fn slide_down(mut self, distance: Pixels) -> Self {
+        self.offset.y = -distance;
+        self
+    }
+
+    /// Slide the element left from the `distance` on the right.
+    pub fn slide_left(mut self, distance: Pixels) -> Self {
+        self.offset.x = distance;
+        self
+    }
+
+    /// Slide the element right from the `distance` on the left.
+    pub fn slide_right(mut self, distance: Pixels) -> Self {
+        self.offset.x = -distance;
+        self
+    }
+
+    /// Set the easing function, e.g.: [`cubic_bezier`].
+    pub fn easing(mut self, easing: impl Fn(f32) -> f32 + 'static) -> Self {
+        self.easing = Rc::new(easing);
+        self
+    }
+
+    /// Set the delay before the transition starts.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Delay the transition of the `ix` child by `ix * step`, to stagger the children.
+    pub fn stagger(self, ix: usize, step: Duration) -> Self {
+        self.delay(step * ix as u32)
+    }
+
+    /// Keep the fade effect only if `reduce_motion` is true, e.g.: `cx.theme().reduce_motion`.
+    pub fn reduce_motion(mut self, reduce_motion: bool) -> Self {
+        if reduce_motion {
+            self.offset = Point::default();
+        }
+        self
+    }
+
+    fn animation(&self) -> Animation {
+        let easing = self.easing.clone();
+        let total = (self.delay + self.duration).as_secs_f32();
+        let delay = if total > 0. {
+            self.delay.as_secs_f32() / total
+        } else {
+            0.
+        };
+
+        Animation::new(self.delay + self.duration)
+            .with_easing(move |t| easing(delayed_progress(t, delay)))
+    }
+
+    /// Apply the effects to the element by the `progress`, `0.` is hidden and `1.` is shown.
+    fn apply<E: Styled>(&self, mut el: E, progress: f32) -> E {
+        if self.fade {
+            el = el.opacity(progress);
+        }
+        if self.offset.y != px(0.) {
+            let inset = &mut el.style().inset;
+            (inset.top, inset.bottom) =
+                offset_inset(inset.top, inset.bottom, self.offset.y * (1. - progress));
+        }
+        if self.offset.x != px(0.) {
+            let inset = &mut el.style().inset;
+            (inset.left, inset.right) =
+                offset_inset(inset.left, inset.right, self.offset.x * (1. - progress));
+        }
+        el
+    }
+}
+
+/// Offset the inset of an axis by `delta`, the `start` (top or left) is increased if it is in
+/// pixels, otherwise the `end` (bottom or right) is decreased if it is in pixels.
+///
+/// The inset in the other units is kept, it can't be offset without the layout.
+fn offset_inset(
+    start: Option<Length>,
+    end: Option<Length>,
+    delta: Pixels,
+) -> (Option<Length>, Option<Length>) {
+    fn pixels(length: Option<Length>) -> Option<Pixels> {
+        match length {
+            Some(Length::Definite(DefiniteLength::Absolute(AbsoluteLength::Pixels(pixels)))) => {
+                Some(pixels)
+            }
+            _ => None,
+        }
+    }
+    let is_auto = |length: Option<Length>| matches!(length, None | Some(Length::Auto));
+
+    if let Some(pixels) = pixels(start) {
+        (Some((pixels + delta).into()), end)
+    } else if let Some(pixels) = pixels(end).filter(|_| is_auto(start)) {
+        (start, Some((pixels - delta).into()))
+    } else if is_auto(start) && is_auto(end) {
+        (Some(delta.into()), end)
+    } else {
+        (start, end)
+    }
+}
+
+/// Returns the progress of the transition after the delay, the `delay` is the ratio of the
+/// total duration.
+fn delayed_progress(t: f32, delay: f32) -> f32 {
+    if delay >= 1. {
+        return if t >= 1. { 1. } else { 0. };
+    }
+
+    ((t - delay) / (1. - delay)).clamp(0., 1.)
+}
+
+/// Extension trait to animate the element by the [`Transition`].
+pub trait TransitionExt: Styled + IntoElement + Sized + 'static {
+    /// Animate the element to show, e.g.: when it is inserted.
+    fn animate_in(
+        self,
+        id: impl Into<ElementId>,
+        transition: Transition,
+    ) -> AnimationElement<Self> {
+        let animation = transition.animation();
+        self.with_animation(id, animation, move |this, delta| {
+            transition.apply(this, delta)
+        })
+    }
+
+    /// Animate the element to hide, the element should be kept rendering until the
+    /// transition is finished, e.g.: remove it after the duration of the transition.
+    fn animate_out(
+        self,
+        id: impl Into<ElementId>,
+        transition: Transition,
+    ) -> AnimationElement<Self> {
+        let animation = transition.animation();
+        self.with_animation(id, animation, move |this, delta| {
+            transition.apply(this, 1. - delta)
+        })
+    }
+}
+
+impl<E: Styled + IntoElement + 'static> TransitionExt for E {}
+
+/// The spring is settled if the distance to the target and the velocity are less than it.
+const SPRING_REST_THRESHOLD: f32 = 0.001;
+/// The max time step of the spring simulation, the step is split into the sub-steps.
+const SPRING_SUB_STEP: f32 = 1. / 240.;
+/// The max elapsed time of a frame, to avoid the jump after the app is stalled.
+const SPRING_MAX_FRAME: f32 = 1. / 15.;
+
+/// A spring-physics animated value, with the stiffness and damping of the spring.
+///
+/// The target can be changed at anytime, the velocity is kept, so the animation is
+/// interruptible without a jump.
+///
+/// ```ignore
+/// let mut spring = Spring::new(0.).stiffness(300.).damping(30.);
+/// spring.set_target(1.);
+/// spring.step(Duration::from_millis(16));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    value: f32,
+    target: f32,
+    velocity: f32,
+    stiffness: f32,
+    damping: f32,
+}
+
+impl Spring {
+    /// Create a spring settled at the `value`, default stiffness is 170 and damping is 26.
+    pub fn new(value: f32) -> Self {
+        Self {
+            value,
+            target: value,
+            velocity: 0.,
+            stiffness: 170.,
+            damping: 26.,
+        }
+    }
+
+    /// Set the stiffness of the spring, the larger the faster.
+    pub fn stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness;
+        self
+    }
+
+    /// Set the damping of the spring, the smaller the bouncier.
+    pub fn damping(mut self, damping: f32) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    pub fn velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    /// Set the target to animate to, the current velocity is kept.
+    pub fn set_target(&mut self, target: f32) {
+        self.target = target;
+    }
+
+    /// Set the velocity, e.g.: the velocity of the drag when it is released.
+    pub fn set_velocity(&mut self, velocity: f32) {
+        self.velocity = velocity;
+    }
+
+    /// Jump to the `value` and settle without the animation.
+    pub fn jump_to(&mut self, value: f32) {
+        self.value = value;
+        self.target = value;
+        self.velocity = 0.;
+    }
+
+    /// Returns true if the spring is at rest at the target.
+    pub fn is_settled(&self) -> bool {
+        (self.value - self.target).abs() < SPRING_REST_THRESHOLD
+            && self.velocity.abs() < SPRING_REST_THRESHOLD
+    }
+
+    /// Advance the spring by the elapsed time, returns the new value.
+    pub fn step(&mut self, elapsed: Duration) -> f32 {
+        let mut remaining = elapsed.as_secs_f32();
+        while remaining > 0. && !self.is_settled() {
+            let dt = remaining.min(SPRING_SUB_STEP);
+            let force = -self.stiffness * (self.value - self.target) - self.damping * self.velocity;
+            self.velocity += force * dt;
+            self.value += self.velocity * dt;
+            remaining -= dt;
+        }
+
+        if self.is_settled() {
+            self.jump_to(self.target);
+        }
+        self.value
+    }
+}
+
+/// A [`Spring`] driven by the frames of the window, to keep in the state of a view.
+///
+/// ```ignore
+/// // In the render of the view.
+/// let x = self.indicator_x.value(window, cx);
+/// div().absolute().left(px(x))
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpringValue {
+    spring: Spring,
+    last_frame: Option<Instant>,
+}
+
+impl SpringValue {
+    pub fn new(spring: Spring) -> Self {
+        Self {
+            spring,
+            last_frame: None,
+        }
+    }
+
+    /// Returns the spring to set the target or velocity.
+    pub fn spring_mut(&mut self) -> &mut Spring {
+        &mut self.spring
+    }
+
+    /// Set the target to animate to, see [`Spring::set_target`].
+    pub fn set_target(&mut self, target: f32) {
+        self.spring.set_target(target);
+    }
+
+    /// Advance the spring to the current frame and returns the value, the next frame is
+    /// requested until the spring is settled.
+    ///
+    /// The spring jumps to the target if the `reduce_motion` of the theme is true.
+    pub fn value(&mut self, window: &mut Window, cx: &App) -> f32 {
+        if cx.theme().reduce_motion {
+            self.spring.jump_to(self.spring.target());
+        }
+        if self.spring.is_settled() {
+            self.last_frame = None;
+            return self.spring.value();
+        }
+
+        let now = Instant::now();
+        if let Some(last_frame) = self.last_frame {
+            let elapsed = now.duration_since(last_frame);
+            self.spring
+                .step(elapsed.min(Duration::from_secs_f32(SPRING_MAX_FRAME)));
+        }
+        self.last_frame = Some(now);
+        window.request_animation_frame();
+        self.spring.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gpui::{px, relative, Length};
+
+    use super::{delayed_progress, offset_inset, Spring};
+
+    #[test]
+    fn test_delayed_progress() {
+        assert_eq!(delayed_progress(0., 0.), 0.);
+        assert_eq!(delayed_progress(0.5, 0.), 0.5);
+        assert_eq!(delayed_progress(0.25, 0.5), 0.);
+        assert_eq!(delayed_progress(0.75, 0.5), 0.5);
+        assert_eq!(delayed_progress(1., 0.5), 1.);
+        assert_eq!(delayed_progress(0.5, 1.), 0.);
+        assert_eq!(delayed_progress(1., 1.), 1.);
+    }
+
+    #[test]
+    fn test_offset_inset() {
+        let px_len = |value: f32| Some(Length::from(px(value)));
+
+        assert_eq!(offset_inset(None, None, px(8.)), (px_len(8.), None));
+        assert_eq!(offset_inset(px_len(10.), None, px(8.)), (px_len(18.), None));
+        assert_eq!(offset_inset(None, px_len(10.), px(8.)), (None, px_len(2.)));
+        assert_eq!(
+            offset_inset(Some(Length::Auto), px_len(0.), px(-4.)),
+            (Some(Length::Auto), px_len(4.))
+        );
+        let half = Some(Length::from(relative(0.5)));
+        assert_eq!(offset_inset(half, None, px(8.)), (half, None));
+    }
+
+    #[test]
+    fn test_spring() {
+        let mut spring = Spring::new(0.);
+        assert!(spring.is_settled());
+        spring.set_target(1.);
+        assert!(!spring.is_settled());
+
+        let value = spring.step(Duration::from_millis(16));
+        assert!(value > 0. && value < 1.);
+        assert!(spring.velocity() > 0.);
+
+        // Retarget keeps the velocity.
+        let velocity = spring.velocity();
+        spring.set_target(-1.);
+        assert_eq!(spring.velocity(), velocity);
+
+        spring.step(Duration::from_secs(5));
+        assert!(spring.is_settled());
+        assert_eq!(spring.value(), -1.);
+        assert_eq!(spring.velocity(), 0.);
+
+        // An underdamped spring overshoots the target.
+        let mut spring = Spring::new(0.).stiffness(300.).damping(5.);
+        spring.set_target(1.);
+        let mut max = 0f32;
+        for _ in 0..60 {
+            max = max.max(spring.step(Duration::from_millis(16)));
+        }
+        assert!(max > 1.);
+    }
+}
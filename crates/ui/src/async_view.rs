@@ -0,0 +1,208 @@
+//! A view to render the async loaded content, with the loading, error and empty states.
+//!
+//! ```ignore
+//! let users = cx.new(|cx| {
+//!     AsyncView::new(
+//!         |_, cx| cx.background_spawn(async move { fetch_users().await }),
+//!         |users: &Vec<User>, _, _| v_flex().children(users.iter().map(render_user)),
+//!         window,
+//!         cx,
+//!     )
+//!     .is_empty(|users| users.is_empty())
+//! });
+//! ```
+use std::rc::Rc;
+
+use anyhow::Result;
+use gpui::{
+    div, prelude::FluentBuilder as _, px, AnyElement, App, Context, IntoElement,
+    ParentElement as _, Render, SharedString, Styled as _, Task, Window,
+};
+
+use crate::{
+    alert::Alert,
+    button::{Button, ButtonVariants as _},
+    i18n::t,
+    skeleton::Skeleton,
+    v_flex, ActiveTheme as _, Icon, IconName, Sizable as _,
+};
+
+type Loader<T> = Rc<dyn Fn(&mut Window, &mut App) -> Task<Result<T>>>;
+type Builder<T> = Rc<dyn Fn(&T, &mut Window, &mut App) -> AnyElement>;
+type StateBuilder = Rc<dyn Fn(&mut Window, &mut App) -> AnyElement>;
+
+/// The state of the [`AsyncView`].
+pub enum AsyncState<T> {
+    Loading,
+    Error(SharedString),
+    Loaded(T),
+}
+
+/// A view to load the data by the task, and render it by the builder when it is loaded.
+pub struct AsyncView<T: 'static> {
+    state: AsyncState<T>,
+    loader: Loader<T>,
+    builder: Builder<T>,
+    loading: Option<StateBuilder>,
+    empty: Option<StateBuilder>,
+    error: Option<Rc<dyn Fn(&SharedString, &mut Window, &mut App) -> AnyElement>>,
+    is_empty: Option<Rc<dyn Fn(&T) -> bool>>,
+    _task: Option<Task<()>>,
+}
+
+impl<T: 'static> AsyncView<T> {
+    /// Create a view and start to load the data by the `loader`.
+    pub fn new<E: IntoElement>(
+        loader: impl Fn(&mut Window, &mut App) -> Task<Result<T>> + 'static,
+        builder: impl Fn(&T, &mut Window, &mut App) -> E + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            state: AsyncState::Loading,
+            loader: Rc::new(loader),
+            builder: Rc::new(move |data, window, cx| builder(data, window, cx).into_any_element()),
+            loading: None,
+            empty: None,
+            error: None,
+            is_empty: None,
+            _task: None,
+        };
+        this.reload(window, cx);
+        this
+    }
+
+    /// Set the element of the loading state, default is the skeleton lines.
+    pub fn loading<E: IntoElement>(
+        mut self,
+        loading: impl Fn(&mut Window, &mut App) -> E + 'static,
+    ) -> Self {
+        self.loading = Some(Rc::new(move |window, cx| {
+            loading(window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the element of the error state, default is an error alert with the retry button.
+    pub fn error<E: IntoElement>(
+        mut self,
+        error: impl Fn(&SharedString, &mut Window, &mut App) -> E + 'static,
+    ) -> Self {
+        self.error = Some(Rc::new(move |message, window, cx| {
+            error(message, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the element of the empty state, the data is empty if [`Self::is_empty`] returns true.
+    pub fn empty<E: IntoElement>(
+        mut self,
+        empty: impl Fn(&mut Window, &mut App) -> E + 'static,
+    ) -> Self {
+        self.empty = Some(Rc::new(move |window, cx| {
+            empty(window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the function to check if the loaded data is empty.
+    pub fn is_empty(mut self, is_empty: impl Fn(&T) -> bool + 'static) -> Self {
+        self.is_empty = Some(Rc::new(is_empty));
+        self
+    }
+
+    /// Returns the state of the view.
+    pub fn state(&self) -> &AsyncState<T> {
+        &self.state
+    }
+
+    /// Load the data again, the loading task in progress is canceled.
+    pub fn reload(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.state = AsyncState::Loading;
+        let task = (self.loader)(window, cx);
+        self._task = Some(cx.spawn(async move |this, cx| {
+            let state = match task.await {
+                Ok(data) => AsyncState::Loaded(data),
+                Err(err) => AsyncState::Error(err.to_string().into()),
+            };
+            _ = this.update(cx, |this, cx| {
+                this.state = state;
+                cx.notify();
+            });
+        }));
+        cx.notify();
+    }
+
+    fn render_loading(&self, window: &mut Window, cx: &mut App) -> AnyElement {
+        if let Some(loading) = &self.loading {
+            return loading(window, cx);
+        }
+
+        v_flex()
+            .gap_2()
+            .child(Skeleton::new().h_4().w_full())
+            .child(Skeleton::new().h_4().w_full())
+            .child(Skeleton::new().h_4().w_3_4())
+            .into_any_element()
+    }
+
+    fn render_error(
+        &self,
+        message: &SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        if let Some(error) = &self.error {
+            return error(message, window, cx);
+        }
+
+        v_flex()
+            .gap_2()
+            .items_start()
+            .child(Alert::error("async-view-error", message.clone()))
+            .child(
+                Button::new("async-view-retry")
+                    .label(t!("AsyncView.retry"))
+                    .outline()
+                    .small()
+                    .on_click(cx.listener(|this, _, window, cx| this.reload(window, cx))),
+            )
+            .into_any_element()
+    }
+
+    fn render_empty(&self, window: &mut Window, cx: &mut App) -> AnyElement {
+        if let Some(empty) = &self.empty {
+            return empty(window, cx);
+        }
+
+        v_flex()
+            .py_6()
+            .gap_2()
+            .items_center()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(Icon::new(IconName::Inbox).size(px(28.)))
+            .child(t!("AsyncView.empty"))
+            .into_any_element()
+    }
+}
+
+impl<T: 'static> Render for AsyncView<T> {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div().size_full().map(|this| match &self.state {
+            AsyncState::Loading => this.child(self.render_loading(window, cx)),
+            AsyncState::Error(message) => {
+                let message = message.clone();
+                this.child(self.render_error(&message, window, cx))
+            }
+            AsyncState::Loaded(data) => {
+                let is_empty = self.is_empty.as_ref().map_or(false, |f| f(data));
+                if is_empty {
+                    this.child(self.render_empty(window, cx))
+                } else {
+                    this.child((self.builder)(data, window, cx))
+                }
+            }
+        })
+    }
+}
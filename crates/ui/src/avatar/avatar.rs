@@ -5,8 +5,10 @@ use gpui::{
 };
 
 use crate::{
+    auto_foreground,
     avatar::{avatar_size, AvatarSized as _},
-    ActiveTheme, Colorize, Icon, IconName, Sizable, Size, StyledExt,
+    image_cache::cached_image,
+    readable_foreground, ActiveTheme, Colorize, Icon, IconName, Sizable, Size, StyledExt,
 };
 
 /// User avatar element.
@@ -19,6 +21,7 @@ pub struct Avatar {
     src: Option<ImageSource>,
     name: Option<SharedString>,
     short_name: SharedString,
+    color: Option<Hsla>,
     placeholder: Icon,
     size: Size,
 }
@@ -31,6 +34,7 @@ impl Avatar {
             src: None,
             name: None,
             short_name: SharedString::default(),
+            color: None,
             placeholder: Icon::new(IconName::User),
             size: Size::Medium,
         }
@@ -52,6 +56,14 @@ impl Avatar {
         self
     }
 
+    /// Set the background color of the name initials, default is generated by the name.
+    ///
+    /// The text color will be black or white to keep readable on the color.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
     /// Set placeholder icon, default: [`IconName::User`]
     pub fn placeholder(mut self, icon: impl Into<Icon>) -> Self {
         self.placeholder = icon.into();
@@ -108,18 +120,34 @@ impl RenderOnce for Avatar {
             })
             .map(|this| match self.src {
                 None => this.when(self.name.is_some(), |this| {
-                    let color_ix = gpui::hash(&self.short_name) % COLOR_COUNT;
-                    let color = default_color(color_ix, cx);
-
-                    this.bg(color.opacity(BG_OPACITY))
-                        .text_color(color)
+                    let (bg, fg) = match self.color {
+                        Some(color) => (color, auto_foreground(color)),
+                        None => {
+                            let color_ix = gpui::hash(&self.short_name) % COLOR_COUNT;
+                            let color = default_color(color_ix, cx);
+                            let bg = color.opacity(BG_OPACITY);
+                            (
+                                bg,
+                                readable_foreground(color, cx.theme().background.blend(bg)),
+                            )
+                        }
+                    };
+
+                    this.bg(bg)
+                        .text_color(fg)
                         .child(div().avatar_text_size(self.size).child(self.short_name))
                 }),
                 Some(src) => this.child(
-                    img(src)
-                        .avatar_size(self.size)
-                        .rounded_full()
-                        .refine_style(&inner_style),
+                    img(match src {
+                        ImageSource::Resource(resource) => {
+                            let size = avatar_size(self.size);
+                            cached_image(resource, Some(size), Some(size))
+                        }
+                        src => src,
+                    })
+                    .avatar_size(self.size)
+                    .rounded_full()
+                    .refine_style(&inner_style),
                 ),
             })
             .refine_style(&self.style)
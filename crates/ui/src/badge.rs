@@ -3,7 +3,7 @@ use gpui::{
     RenderOnce, StyleRefinement, Styled, Window,
 };
 
-use crate::{h_flex, white, ActiveTheme, Icon, Sizable, Size, StyledExt};
+use crate::{auto_foreground, h_flex, white, ActiveTheme, Icon, Sizable, Size, StyledExt};
 
 #[derive(Default, Clone)]
 enum BadgeVariant {
@@ -79,6 +79,8 @@ impl Badge {
     }
 
     /// Set the color (background) of the badge.
+    ///
+    /// The text color will be black or white to keep readable on the color.
     pub fn color(mut self, color: impl Into<Hsla>) -> Self {
         self.color = Some(color.into());
         self
@@ -123,7 +125,7 @@ impl RenderOnce for Badge {
                         .items_center()
                         .rounded_full()
                         .bg(self.color.unwrap_or(cx.theme().red))
-                        .text_color(white())
+                        .text_color(self.color.map(auto_foreground).unwrap_or(white()))
                         .text_size(text_size)
                         .map(|this| match self.variant {
                             BadgeVariant::Dot => this.top_0().right_0().size(px(6.)),
@@ -6,12 +6,13 @@ use gpui::{
     StyleRefinement, Styled, Window,
 };
 
-use crate::{h_flex, ActiveTheme, Icon, IconName, StyledExt};
+use crate::{h_flex, ActiveTheme, Icon, IconName, LayoutDirection, StyledExt};
 
 #[derive(IntoElement)]
 pub struct Breadcrumb {
     style: StyleRefinement,
     items: Vec<BreadcrumbItem>,
+    direction: Option<LayoutDirection>,
 }
 
 #[derive(IntoElement)]
@@ -88,9 +89,16 @@ impl Breadcrumb {
         Self {
             items: Vec::new(),
             style: StyleRefinement::default(),
+            direction: None,
         }
     }
 
+    /// Set the layout direction, default is the direction of the window.
+    pub fn direction(mut self, direction: LayoutDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
     /// Add an item to the breadcrumb.
     pub fn item(mut self, item: BreadcrumbItem) -> Self {
         self.items.push(item);
@@ -99,10 +107,16 @@ impl Breadcrumb {
 }
 
 #[derive(IntoElement)]
-struct BreadcrumbSeparator;
+struct BreadcrumbSeparator(LayoutDirection);
 impl RenderOnce for BreadcrumbSeparator {
     fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
-        Icon::new(IconName::ChevronRight)
+        let icon = if self.0.is_rtl() {
+            IconName::ChevronLeft
+        } else {
+            IconName::ChevronRight
+        };
+
+        Icon::new(icon)
             .text_color(cx.theme().muted_foreground)
             .size_3p5()
             .into_any_element()
@@ -116,8 +130,11 @@ impl Styled for Breadcrumb {
 }
 
 impl RenderOnce for Breadcrumb {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let items_count = self.items.len();
+        let direction = self
+            .direction
+            .unwrap_or_else(|| LayoutDirection::of(window, cx));
 
         let mut children = vec![];
         for (ix, item) in self.items.into_iter().enumerate() {
@@ -125,11 +142,12 @@ impl RenderOnce for Breadcrumb {
 
             children.push(item.is_last(is_last).into_any_element());
             if !is_last {
-                children.push(BreadcrumbSeparator.into_any_element());
+                children.push(BreadcrumbSeparator(direction).into_any_element());
             }
         }
 
         h_flex()
+            .flex_row_with(direction)
             .gap_1p5()
             .text_sm()
             .text_color(cx.theme().muted_foreground)
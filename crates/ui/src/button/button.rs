@@ -1,8 +1,13 @@
 use std::rc::Rc;
 
 use crate::{
-    h_flex, indicator::Indicator, tooltip::Tooltip, ActiveTheme, Colorize as _, Disableable, Icon,
-    Selectable, Sizable, Size, StyleSized, StyledExt,
+    accessibility::{self, AccessibilityProperties, AccessibilityRole, Accessible},
+    h_flex,
+    indicator::Indicator,
+    telemetry::{self, Interaction, InteractionKind},
+    tooltip::Tooltip,
+    ActiveTheme, Colorize as _, Disableable, Icon, LayoutDirection, Selectable, Sizable, Size,
+    StyleSized, StyledExt,
 };
 use gpui::{
     div, prelude::FluentBuilder as _, relative, Action, AnyElement, App, ClickEvent, Corners, Div,
@@ -203,6 +208,8 @@ pub struct Button {
     pub(crate) stop_propagation: bool,
     loading: bool,
     loading_icon: Option<Icon>,
+    accessibility: AccessibilityProperties,
+    direction: Option<LayoutDirection>,
 }
 
 impl From<Button> for AnyElement {
@@ -233,6 +240,8 @@ impl Button {
             outline: false,
             children: Vec::new(),
             loading_icon: None,
+            accessibility: AccessibilityProperties::default(),
+            direction: None,
         }
     }
 
@@ -310,7 +319,11 @@ impl Button {
         mut self,
         handler: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
     ) -> Self {
-        self.on_click = Some(Box::new(handler));
+        let id = self.base.interactivity().element_id.clone();
+        self.on_click = Some(Box::new(move |event, window, cx| {
+            telemetry::record(cx, || Interaction::new(InteractionKind::Click, id.clone()));
+            handler(event, window, cx);
+        }));
         self
     }
 
@@ -323,6 +336,31 @@ impl Button {
         self.loading_icon = Some(icon.into());
         self
     }
+
+    /// Set the layout direction, default is the direction of the window.
+    pub fn direction(mut self, direction: LayoutDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+}
+
+impl Accessible for Button {
+    fn accessibility_mut(&mut self) -> &mut AccessibilityProperties {
+        &mut self.accessibility
+    }
+
+    fn accessibility(&self, _: &App) -> AccessibilityProperties {
+        let label = self
+            .label
+            .clone()
+            .or_else(|| self.tooltip.as_ref().map(|(tooltip, _)| tooltip.clone()));
+
+        AccessibilityProperties::new(AccessibilityRole::Button)
+            .label(label)
+            .selected(self.selected)
+            .disabled(self.disabled || self.loading)
+            .merge(&self.accessibility)
+    }
 }
 
 impl Disableable for Button {
@@ -376,8 +414,13 @@ impl InteractiveElement for Button {
 }
 
 impl RenderOnce for Button {
-    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        accessibility::record_rendered(self.accessibility(cx), window, cx);
+
         let style: ButtonVariant = self.variant;
+        let direction = self
+            .direction
+            .unwrap_or_else(|| LayoutDirection::of(window, cx));
         let normal_style = style.normal(self.outline, cx);
         let icon_size = match self.size {
             Size::Size(v) => Size::Size(v * 0.75),
@@ -488,6 +531,7 @@ impl RenderOnce for Button {
             .child({
                 h_flex()
                     .id("label")
+                    .flex_row_with(direction)
                     .items_center()
                     .justify_center()
                     .button_text_size(self.size)
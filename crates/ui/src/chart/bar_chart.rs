@@ -11,7 +11,7 @@ use crate::{
         shape::Bar,
         Axis, AxisText, Grid, Plot, AXIS_GAP,
     },
-    ActiveTheme,
+    readable_foreground, ActiveTheme,
 };
 
 #[derive(IntoPlot)]
@@ -143,19 +143,26 @@ where
         let y_fn = y_fn.clone();
         let default_fill = cx.theme().chart_2;
         let fill = self.fill.clone();
-        let label_color = cx.theme().foreground;
+        let foreground = cx.theme().foreground;
         let mut bar = Bar::new()
             .data(&self.data)
             .band_width(band_width)
             .x(move |d| x.tick(&x_fn(d)))
             .y0(height)
             .y1(move |d| y.tick(&y_fn(d)))
-            .fill(move |d| fill.as_ref().map(|f| f(d)).unwrap_or(default_fill));
+            .fill({
+                let fill = fill.clone();
+                move |d| fill.as_ref().map(|f| f(d)).unwrap_or(default_fill)
+            });
 
         if let Some(label) = self.label.as_ref() {
             let label = label.clone();
-            bar =
-                bar.label(move |d, p| Text::new(label(d), p, label_color).align(TextAlign::Center));
+            bar = bar.label(move |d, p| {
+                // Keep the label readable on the bar color.
+                let background = fill.as_ref().map(|f| f(d)).unwrap_or(default_fill);
+                let color = readable_foreground(foreground, background);
+                Text::new(label(d), p, color).align(TextAlign::Center)
+            });
         }
 
         bar.paint(&bounds, window, cx);
@@ -1,7 +1,9 @@
 use std::time::Duration;
 
 use crate::{
-    text::Text, v_flex, ActiveTheme, Disableable, IconName, Selectable, Sizable, Size,
+    accessibility::{self, text_label, AccessibilityProperties, AccessibilityRole, Accessible},
+    text::Text,
+    v_flex, ActiveTheme, Disableable, IconName, ReadOnly, Selectable, Sizable, Size,
     StyledExt as _,
 };
 use gpui::{
@@ -20,8 +22,10 @@ pub struct Checkbox {
     children: Vec<AnyElement>,
     checked: bool,
     disabled: bool,
+    read_only: bool,
     size: Size,
     on_click: Option<Box<dyn Fn(&bool, &mut Window, &mut App) + 'static>>,
+    accessibility: AccessibilityProperties,
 }
 
 impl Checkbox {
@@ -34,8 +38,10 @@ impl Checkbox {
             children: Vec::new(),
             checked: false,
             disabled: false,
+            read_only: false,
             size: Size::default(),
             on_click: None,
+            accessibility: AccessibilityProperties::default(),
         }
     }
 
@@ -68,6 +74,21 @@ impl Styled for Checkbox {
     }
 }
 
+impl Accessible for Checkbox {
+    fn accessibility_mut(&mut self) -> &mut AccessibilityProperties {
+        &mut self.accessibility
+    }
+
+    fn accessibility(&self, _: &App) -> AccessibilityProperties {
+        AccessibilityProperties::new(AccessibilityRole::Checkbox)
+            .label(text_label(self.label.as_ref()))
+            .checked(self.checked)
+            .disabled(self.disabled)
+            .read_only(self.read_only)
+            .merge(&self.accessibility)
+    }
+}
+
 impl Disableable for Checkbox {
     fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
@@ -75,6 +96,14 @@ impl Disableable for Checkbox {
     }
 }
 
+impl ReadOnly for Checkbox {
+    /// Set the read-only state of the checkbox, the checked state can't be changed by the user.
+    fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+}
+
 impl Selectable for Checkbox {
     fn selected(self, selected: bool) -> Self {
         self.checked(selected)
@@ -157,6 +186,8 @@ pub(crate) fn checkbox_check_icon(
 
 impl RenderOnce for Checkbox {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        accessibility::record_rendered(self.accessibility(cx), window, cx);
+
         let checked = self.checked;
         let border_color = if checked {
             cx.theme().primary
@@ -243,7 +274,7 @@ impl RenderOnce for Checkbox {
                     )
                 })
                 .when_some(
-                    self.on_click.filter(|_| !self.disabled),
+                    self.on_click.filter(|_| !self.disabled && !self.read_only),
                     |this, on_click| {
                         this.on_click(move |_, window, cx| {
                             cx.stop_propagation();
@@ -0,0 +1,272 @@
+//! An opt-in and in-memory history of the clipboard, the copies and the pastes of the inputs
+//! are recorded when it is enabled.
+//!
+//! ```ignore
+//! clipboard_history::enable(20, cx);
+//!
+//! h_flex()
+//!     .child(TextInput::new(&self.input))
+//!     .child(ClipboardHistoryPopover::for_input("history", &self.input))
+//! ```
+use std::{collections::VecDeque, rc::Rc, sync::Arc};
+
+use gpui::{
+    div, img, prelude::FluentBuilder as _, px, App, AppContext as _, ClipboardEntry, ClipboardItem,
+    Corner, ElementId, Entity, Global, Image, InteractiveElement as _, IntoElement,
+    ParentElement as _, RenderOnce, SharedString, StatefulInteractiveElement as _, Styled as _,
+    Window,
+};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    i18n::t,
+    input::InputState,
+    popover::{Popover, PopoverContent},
+    v_flex, ActiveTheme as _, IconName, Sizable as _,
+};
+
+/// An entry of the clipboard history.
+#[derive(Debug, Clone)]
+pub enum ClipboardHistoryEntry {
+    Text(SharedString),
+    Image(Arc<Image>),
+}
+
+impl ClipboardHistoryEntry {
+    /// Returns the entry of the clipboard item, `None` if it is empty.
+    pub fn from_item(item: &ClipboardItem) -> Option<Self> {
+        match item.entries().first()? {
+            ClipboardEntry::String(string) if !string.text().is_empty() => {
+                Some(Self::Text(string.text().clone().into()))
+            }
+            ClipboardEntry::Image(image) => Some(Self::Image(Arc::new(image.clone()))),
+            _ => None,
+        }
+    }
+
+    /// Returns the clipboard item of the entry.
+    pub fn to_item(&self) -> ClipboardItem {
+        match self {
+            Self::Text(text) => ClipboardItem::new_string(text.to_string()),
+            Self::Image(image) => ClipboardItem::new_image(image),
+        }
+    }
+
+    fn is_same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Text(a), Self::Text(b)) => a == b,
+            (Self::Image(a), Self::Image(b)) => a.id == b.id,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ClipboardHistory {
+    capacity: usize,
+    /// The entries from the newest to the oldest.
+    entries: VecDeque<ClipboardHistoryEntry>,
+}
+
+impl Global for ClipboardHistory {}
+
+impl ClipboardHistory {
+    /// Push the entry to the front, the same entry is moved to the front.
+    fn push(&mut self, entry: ClipboardHistoryEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.entries.retain(|e| !e.is_same(&entry));
+        self.entries.push_front(entry);
+        self.entries.truncate(self.capacity);
+    }
+}
+
+/// Enable the history with the max number of the entries.
+pub fn enable(capacity: usize, cx: &mut App) {
+    let history = cx.default_global::<ClipboardHistory>();
+    history.capacity = capacity;
+    history.entries.truncate(capacity);
+}
+
+/// Disable the history and clear the entries.
+pub fn disable(cx: &mut App) {
+    if cx.has_global::<ClipboardHistory>() {
+        cx.set_global(ClipboardHistory::default());
+    }
+}
+
+/// Returns true if the history is enabled.
+pub fn is_enabled(cx: &App) -> bool {
+    cx.try_global::<ClipboardHistory>()
+        .map_or(false, |history| history.capacity > 0)
+}
+
+/// Returns the entries, from the newest to the oldest.
+pub fn entries(cx: &App) -> Vec<ClipboardHistoryEntry> {
+    cx.try_global::<ClipboardHistory>()
+        .map(|history| history.entries.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Clear the entries.
+pub fn clear(cx: &mut App) {
+    if cx.has_global::<ClipboardHistory>() {
+        cx.global_mut::<ClipboardHistory>().entries.clear();
+    }
+}
+
+/// Record the clipboard item if the history is enabled.
+pub fn record(item: &ClipboardItem, cx: &mut App) {
+    if !is_enabled(cx) {
+        return;
+    }
+
+    if let Some(entry) = ClipboardHistoryEntry::from_item(item) {
+        cx.global_mut::<ClipboardHistory>().push(entry);
+    }
+}
+
+/// Write the item to the clipboard and record it.
+pub fn write(item: ClipboardItem, cx: &mut App) {
+    record(&item, cx);
+    cx.write_to_clipboard(item);
+}
+
+/// A button to open the popover of the clipboard history, click an entry to paste it.
+#[derive(IntoElement)]
+pub struct ClipboardHistoryPopover {
+    id: ElementId,
+    on_paste: Rc<dyn Fn(&ClipboardHistoryEntry, &mut Window, &mut App)>,
+}
+
+impl ClipboardHistoryPopover {
+    /// Create a popover to write the clicked entry to the clipboard.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            on_paste: Rc::new(|entry, _, cx| write(entry.to_item(), cx)),
+        }
+    }
+
+    /// Create a popover to insert the clicked text entry into the input.
+    pub fn for_input(id: impl Into<ElementId>, input: &Entity<InputState>) -> Self {
+        let input = input.clone();
+        Self::new(id).on_paste(move |entry, window, cx| {
+            let ClipboardHistoryEntry::Text(text) = entry else {
+                return;
+            };
+            input.update(cx, |input, cx| {
+                input.insert(text.to_string(), window, cx);
+                input.focus(window, cx);
+            });
+        })
+    }
+
+    /// Set the handler of the clicked entry.
+    pub fn on_paste(
+        mut self,
+        on_paste: impl Fn(&ClipboardHistoryEntry, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_paste = Rc::new(on_paste);
+        self
+    }
+}
+
+impl RenderOnce for ClipboardHistoryPopover {
+    fn render(self, _: &mut Window, _: &mut App) -> impl IntoElement {
+        let on_paste = self.on_paste.clone();
+
+        Popover::new(self.id)
+            .anchor(Corner::TopRight)
+            .trigger(
+                Button::new("clipboard-history")
+                    .ghost()
+                    .xsmall()
+                    .icon(IconName::Copy)
+                    .tooltip(t!("ClipboardHistory.title")),
+            )
+            .content(move |window, cx| {
+                let on_paste = on_paste.clone();
+                cx.new(|cx| {
+                    PopoverContent::new(window, cx, move |_, cx| {
+                        let entries = entries(cx);
+
+                        v_flex()
+                            .id("entries")
+                            .w(px(280.))
+                            .max_h(px(320.))
+                            .overflow_y_scroll()
+                            .gap_1()
+                            .when(entries.is_empty(), |this| {
+                                this.child(
+                                    div()
+                                        .p_2()
+                                        .text_sm()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(t!("ClipboardHistory.empty")),
+                                )
+                            })
+                            .children(entries.into_iter().enumerate().map(|(ix, entry)| {
+                                let on_paste = on_paste.clone();
+                                h_flex()
+                                    .id(ix)
+                                    .px_2()
+                                    .py_1()
+                                    .rounded(cx.theme().radius)
+                                    .text_sm()
+                                    .overflow_hidden()
+                                    .hover(|this| this.bg(cx.theme().accent))
+                                    .map(|this| match &entry {
+                                        ClipboardHistoryEntry::Text(text) => this
+                                            .child(div().truncate().child(text.replace('\n', " "))),
+                                        ClipboardHistoryEntry::Image(image) => {
+                                            this.child(img(image.clone()).max_h(px(64.)))
+                                        }
+                                    })
+                                    .on_click(cx.listener(move |_, _, window, cx| {
+                                        on_paste(&entry, window, cx);
+                                        cx.emit(gpui::DismissEvent);
+                                    }))
+                            }))
+                            .into_any_element()
+                    })
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClipboardHistory, ClipboardHistoryEntry};
+
+    fn texts(history: &ClipboardHistory) -> Vec<String> {
+        history
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                ClipboardHistoryEntry::Text(text) => text.to_string(),
+                ClipboardHistoryEntry::Image(_) => "image".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_clipboard_history() {
+        let mut history = ClipboardHistory::default();
+        history.push(ClipboardHistoryEntry::Text("a".into()));
+        assert!(history.entries.is_empty());
+
+        history.capacity = 3;
+        for text in ["a", "b", "c", "d"] {
+            history.push(ClipboardHistoryEntry::Text(text.into()));
+        }
+        assert_eq!(texts(&history), vec!["d", "c", "b"]);
+
+        // The same entry is moved to the front.
+        history.push(ClipboardHistoryEntry::Text("b".into()));
+        assert_eq!(texts(&history), vec!["b", "d", "c"]);
+    }
+}
@@ -12,6 +12,7 @@ use crate::{
     divider::Divider,
     h_flex,
     input::{InputEvent, InputState, TextInput},
+    overlay::OverlayLayer,
     tooltip::Tooltip,
     v_flex, ActiveTheme as _, Colorize as _, Icon, Selectable as _, Sizable, Size, StyleSized,
     StyledExt,
@@ -459,7 +460,7 @@ impl RenderOnce for ColorPicker {
                                     ),
                             ),
                     )
-                    .with_priority(1),
+                    .with_priority(OverlayLayer::Popover.priority()),
                 )
             })
     }
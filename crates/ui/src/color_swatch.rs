@@ -0,0 +1,271 @@
+//! The swatches to display the colors, e.g.: the palettes of the design tools.
+//!
+//! ```ignore
+//! ColorSwatch::new("primary", cx.theme().primary).label("Primary");
+//!
+//! PaletteRow::new("blue").colors(
+//!     [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950]
+//!         .map(|scale| (scale.to_string(), ColorName::Blue.scale(scale))),
+//! );
+//! ```
+use std::{rc::Rc, time::Duration};
+
+use gpui::{
+    canvas, div, fill, point, prelude::FluentBuilder as _, px, size, App, Bounds, ClickEvent,
+    ClipboardItem, ElementId, Hsla, InteractiveElement as _, IntoElement, ParentElement as _,
+    Pixels, RenderOnce, SharedString, StatefulInteractiveElement as _, StyleRefinement, Styled,
+    Window,
+};
+
+use crate::{
+    auto_foreground, h_flex, tooltip::Tooltip, white, ActiveTheme as _, Colorize as _, Icon,
+    IconName, Sizable, Size, StyleSized as _, StyledExt as _,
+};
+
+const CHECKER_SIZE: Pixels = px(4.);
+
+/// Returns the text color for the label on the `color`, the translucent color is blended on
+/// the `background` first.
+fn label_color(color: Hsla, background: Hsla) -> Hsla {
+    auto_foreground(background.blend(color))
+}
+
+/// The checkerboard behind the translucent colors.
+fn checkerboard() -> impl IntoElement {
+    canvas(
+        |_, _, _| {},
+        |bounds, _, window, _| {
+            let dark = white().darken(0.2);
+            let cols = (bounds.size.width / CHECKER_SIZE).ceil() as usize;
+            let rows = (bounds.size.height / CHECKER_SIZE).ceil() as usize;
+
+            window.paint_quad(fill(bounds, white()));
+            for row in 0..rows {
+                for col in (row % 2..cols).step_by(2) {
+                    let origin =
+                        bounds.origin + point(CHECKER_SIZE * col as f32, CHECKER_SIZE * row as f32);
+                    let cell =
+                        Bounds::new(origin, size(CHECKER_SIZE, CHECKER_SIZE)).intersect(&bounds);
+                    window.paint_quad(fill(cell, dark));
+                }
+            }
+        },
+    )
+    .absolute()
+    .top_0()
+    .left_0()
+    .size_full()
+}
+
+/// A swatch of a color, click to copy the hex of the color.
+#[derive(IntoElement)]
+pub struct ColorSwatch {
+    id: ElementId,
+    style: StyleRefinement,
+    color: Hsla,
+    label: Option<SharedString>,
+    show_hex: bool,
+    size: Size,
+    on_click: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
+}
+
+impl ColorSwatch {
+    pub fn new(id: impl Into<ElementId>, color: Hsla) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            color,
+            label: None,
+            show_hex: false,
+            size: Size::default(),
+            on_click: None,
+        }
+    }
+
+    /// Set the name of the color, it is shown in the tooltip.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set to show the hex on the swatch, default is false.
+    pub fn show_hex(mut self, show_hex: bool) -> Self {
+        self.show_hex = show_hex;
+        self
+    }
+
+    /// Set the click handler, instead of copying the hex.
+    pub fn on_click(
+        mut self,
+        handler: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl Sizable for ColorSwatch {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Styled for ColorSwatch {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for ColorSwatch {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let copied = window.use_keyed_state(self.id.clone(), cx, |_, _| false);
+        let is_copied = *copied.read(cx);
+        let hex: SharedString = self.color.to_hex().into();
+        let foreground = label_color(self.color, cx.theme().background);
+        let tooltip: SharedString = match &self.label {
+            Some(label) => format!("{} {}", label, hex).into(),
+            None => hex.clone(),
+        };
+
+        div()
+            .id(self.id)
+            .relative()
+            .flex_none()
+            .overflow_hidden()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius)
+            .size_with(self.size)
+            .refine_style(&self.style)
+            .cursor_pointer()
+            .when(self.color.a < 1., |this| this.child(checkerboard()))
+            .child(
+                h_flex()
+                    .absolute()
+                    .size_full()
+                    .justify_center()
+                    .bg(self.color)
+                    .text_color(foreground)
+                    .input_text_size(self.size)
+                    .when(self.show_hex && !is_copied, |this| this.child(hex.clone()))
+                    .when(is_copied, |this| {
+                        this.child(Icon::new(IconName::Check).xsmall())
+                    }),
+            )
+            .tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx))
+            .on_click(move |event, window, cx| {
+                if let Some(on_click) = &self.on_click {
+                    return on_click(event, window, cx);
+                }
+
+                cx.write_to_clipboard(ClipboardItem::new_string(hex.to_string()));
+                copied.update(cx, |copied, cx| {
+                    *copied = true;
+                    cx.notify();
+                });
+                let copied = copied.clone();
+                cx.spawn(async move |cx| {
+                    cx.background_executor().timer(Duration::from_secs(2)).await;
+                    _ = copied.update(cx, |copied, cx| {
+                        *copied = false;
+                        cx.notify();
+                    });
+                })
+                .detach();
+            })
+    }
+}
+
+/// A row of the colors, e.g.: the scales of a color, the swatches fill the width.
+#[derive(IntoElement)]
+pub struct PaletteRow {
+    id: ElementId,
+    style: StyleRefinement,
+    colors: Vec<(SharedString, Hsla)>,
+    size: Size,
+}
+
+impl PaletteRow {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            colors: vec![],
+            size: Size::default(),
+        }
+    }
+
+    /// Add a color with the label.
+    pub fn color(mut self, label: impl Into<SharedString>, color: Hsla) -> Self {
+        self.colors.push((label.into(), color));
+        self
+    }
+
+    /// Add the colors with the labels.
+    pub fn colors<L: Into<SharedString>>(
+        mut self,
+        colors: impl IntoIterator<Item = (L, Hsla)>,
+    ) -> Self {
+        self.colors.extend(
+            colors
+                .into_iter()
+                .map(|(label, color)| (label.into(), color)),
+        );
+        self
+    }
+}
+
+impl Sizable for PaletteRow {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Styled for PaletteRow {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for PaletteRow {
+    fn render(self, _: &mut Window, _: &mut App) -> impl IntoElement {
+        let size = self.size;
+
+        h_flex()
+            .id(self.id)
+            .w_full()
+            .gap_1()
+            .refine_style(&self.style)
+            .children(
+                self.colors
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(ix, (label, color))| {
+                        ColorSwatch::new(ix, color)
+                            .label(label)
+                            .show_hex(true)
+                            .with_size(size)
+                            .flex_1()
+                            .w_auto()
+                            .min_w_0()
+                    }),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{black, white, Colorize as _};
+
+    use super::label_color;
+
+    #[test]
+    fn test_label_color() {
+        assert_eq!(label_color(black(), white()), white());
+        assert_eq!(label_color(white(), black()), black());
+        // The translucent color is blended on the background.
+        assert_eq!(label_color(black().opacity(0.05), white()), black());
+    }
+}
@@ -0,0 +1,736 @@
+//! A chat input with the attachments, the slash commands, the mentions and the emojis.
+//!
+//! - `enter` to submit and `shift-enter` to insert a new line, or `secondary-enter` to submit
+//!   if [`ComposerState::submit_on_enter`] is false.
+//! - Type `/` at the start to complete the commands, and `@` to complete the mentions.
+//! - Paste the images or drop the files to attach them.
+//!
+//! ```ignore
+//! let state = cx.new(|cx| {
+//!     ComposerState::new(window, cx)
+//!         .commands(vec![SlashCommand::new("giphy", "Search a GIF")])
+//!         .mentions(|query, cx| find_users(query, cx))
+//! });
+//! cx.subscribe(&state, |this, _, event: &ComposerEvent, cx| match event {
+//!     ComposerEvent::Submit { text, attachments } => this.send(text, attachments, cx),
+//!     ComposerEvent::Command { name, args } => this.run(name, args, cx),
+//! });
+//!
+//! // In render
+//! Composer::new(&state)
+//! ```
+use std::{ops::Range, path::PathBuf, rc::Rc, sync::Arc};
+
+use gpui::{
+    actions, div, img, prelude::FluentBuilder as _, App, AppContext as _, ClipboardEntry, Context,
+    Corner, Entity, EntityInputHandler as _, EventEmitter, ExternalPaths, FocusHandle, Focusable,
+    Image, InteractiveElement as _, IntoElement, KeyBinding, ParentElement as _, Render,
+    RenderOnce, SharedString, StatefulInteractiveElement as _, StyleRefinement, Styled,
+    Subscription, Window,
+};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    emoji_picker::EmojiPicker,
+    file_dialog::FileDialog,
+    h_flex,
+    i18n::t,
+    input::{self, InputEvent, InputState, TextInput},
+    v_flex, ActiveTheme as _, Disableable as _, IconName, Sizable as _, StyledExt as _,
+};
+
+const CONTEXT: &str = "Composer";
+
+actions!(composer, [NewLine]);
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([KeyBinding::new("shift-enter", NewLine, Some(CONTEXT))]);
+}
+
+/// An attachment of the [`Composer`].
+#[derive(Debug, Clone)]
+pub enum Attachment {
+    File(PathBuf),
+    /// The pasted image.
+    Image(Arc<Image>),
+}
+
+impl Attachment {
+    /// Returns the name shown in the chip of the attachment.
+    pub fn name(&self) -> SharedString {
+        match self {
+            Self::File(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string_lossy().to_string())
+                .into(),
+            Self::Image(_) => t!("Composer.image").into(),
+        }
+    }
+}
+
+/// A slash command of the [`Composer`], e.g.: `/giphy`.
+#[derive(Debug, Clone)]
+pub struct SlashCommand {
+    /// The name without the slash.
+    pub name: SharedString,
+    pub description: SharedString,
+}
+
+impl SlashCommand {
+    pub fn new(name: impl Into<SharedString>, description: impl Into<SharedString>) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+        }
+    }
+}
+
+/// The events of the [`ComposerState`].
+#[derive(Debug, Clone)]
+pub enum ComposerEvent {
+    /// The message is submitted, the text is trimmed.
+    Submit {
+        text: SharedString,
+        attachments: Vec<Attachment>,
+    },
+    /// A slash command is submitted, e.g.: `/giphy cats` with the `cats` args.
+    Command {
+        name: SharedString,
+        args: SharedString,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompletionKind {
+    Command,
+    Mention,
+}
+
+impl CompletionKind {
+    fn trigger(&self) -> char {
+        match self {
+            Self::Command => '/',
+            Self::Mention => '@',
+        }
+    }
+}
+
+struct CompletionItem {
+    label: SharedString,
+    description: Option<SharedString>,
+}
+
+struct Completion {
+    kind: CompletionKind,
+    /// The byte range of the completed word in the text, with the trigger.
+    range: Range<usize>,
+    items: Vec<CompletionItem>,
+    selected_ix: usize,
+}
+
+/// Returns the completion kind and the range of the word before the `cursor`.
+///
+/// The commands are only completed at the start of the text.
+fn completion_at(text: &str, cursor: usize) -> Option<(CompletionKind, Range<usize>)> {
+    let before = text.get(..cursor)?;
+    let start = before
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map_or(0, |(ix, c)| ix + c.len_utf8());
+
+    match before[start..].chars().next()? {
+        '/' if before[..start].trim().is_empty() => Some((CompletionKind::Command, start..cursor)),
+        '@' => Some((CompletionKind::Mention, start..cursor)),
+        _ => None,
+    }
+}
+
+/// Returns the name and the args of the slash command of the `text`.
+fn parse_command(text: &str, commands: &[SlashCommand]) -> Option<(SharedString, SharedString)> {
+    let text = text.strip_prefix('/')?;
+    let (name, args) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+
+    let command = commands.iter().find(|command| command.name == name)?;
+    Some((command.name.clone(), args.trim().to_string().into()))
+}
+
+/// State of the [`Composer`].
+pub struct ComposerState {
+    input: Entity<InputState>,
+    attachments: Vec<Attachment>,
+    commands: Vec<SlashCommand>,
+    mentions: Option<Rc<dyn Fn(&str, &App) -> Vec<SharedString>>>,
+    submit_on_enter: bool,
+    completion: Option<Completion>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<ComposerEvent> for ComposerState {}
+
+impl ComposerState {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .auto_grow(1, 8)
+                .placeholder(t!("Composer.placeholder"))
+        });
+        let _subscriptions = vec![cx.subscribe(&input, |this, _, event: &InputEvent, cx| {
+            if let InputEvent::Change(_) = event {
+                this.update_completion(cx);
+            }
+        })];
+
+        Self {
+            input,
+            attachments: vec![],
+            commands: vec![],
+            mentions: None,
+            submit_on_enter: true,
+            completion: None,
+            _subscriptions,
+        }
+    }
+
+    /// Set the placeholder of the input.
+    pub fn set_placeholder(
+        &mut self,
+        placeholder: impl Into<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.input.update(cx, |input, cx| {
+            input.set_placeholder(placeholder, window, cx)
+        });
+    }
+
+    /// Set the slash commands, they are completed by typing `/` at the start.
+    pub fn commands(mut self, commands: Vec<SlashCommand>) -> Self {
+        self.commands = commands;
+        self
+    }
+
+    /// Set the provider of the mentions, it returns the names matching the query typed after `@`.
+    pub fn mentions(
+        mut self,
+        provider: impl Fn(&str, &App) -> Vec<SharedString> + 'static,
+    ) -> Self {
+        self.mentions = Some(Rc::new(provider));
+        self
+    }
+
+    /// Set to submit by `enter`, default is true.
+    ///
+    /// If false, `enter` inserts a new line and `secondary-enter` submits.
+    pub fn submit_on_enter(mut self, submit_on_enter: bool) -> Self {
+        self.submit_on_enter = submit_on_enter;
+        self
+    }
+
+    /// Returns the input of the composer.
+    pub fn input(&self) -> &Entity<InputState> {
+        &self.input
+    }
+
+    /// Returns the attachments to submit.
+    pub fn attachments(&self) -> &[Attachment] {
+        &self.attachments
+    }
+
+    /// Add an attachment to submit.
+    pub fn add_attachment(&mut self, attachment: Attachment, cx: &mut Context<Self>) {
+        self.attachments.push(attachment);
+        cx.notify();
+    }
+
+    /// Remove the attachment at the `ix`.
+    pub fn remove_attachment(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix < self.attachments.len() {
+            self.attachments.remove(ix);
+            cx.notify();
+        }
+    }
+
+    /// Clear the text and the attachments.
+    pub fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.input
+            .update(cx, |input, cx| input.set_value("", window, cx));
+        self.attachments.clear();
+        self.completion = None;
+        cx.notify();
+    }
+
+    /// Submit the message or the slash command, do nothing if it is empty.
+    pub fn submit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.input.read(cx).value().trim().to_string();
+        if text.is_empty() && self.attachments.is_empty() {
+            return;
+        }
+
+        let event = match parse_command(&text, &self.commands) {
+            // The attachments are kept for the next message.
+            Some((name, args)) => {
+                let attachments = std::mem::take(&mut self.attachments);
+                self.clear(window, cx);
+                self.attachments = attachments;
+                ComposerEvent::Command { name, args }
+            }
+            None => {
+                let attachments = std::mem::take(&mut self.attachments);
+                self.clear(window, cx);
+                ComposerEvent::Submit {
+                    text: text.into(),
+                    attachments,
+                }
+            }
+        };
+        cx.emit(event);
+    }
+
+    fn is_empty(&self, cx: &App) -> bool {
+        self.input.read(cx).value().trim().is_empty() && self.attachments.is_empty()
+    }
+
+    fn update_completion(&mut self, cx: &mut Context<Self>) {
+        let input = self.input.read(cx);
+        let text = input.value().clone();
+        let cursor = input.cursor().offset();
+
+        self.completion = completion_at(&text, cursor).and_then(|(kind, range)| {
+            let query = &text[range.start + 1..range.end];
+            let items = match kind {
+                CompletionKind::Command => {
+                    let query = query.to_lowercase();
+                    self.commands
+                        .iter()
+                        .filter(|command| command.name.to_lowercase().starts_with(&query))
+                        .map(|command| CompletionItem {
+                            label: command.name.clone(),
+                            description: Some(command.description.clone()),
+                        })
+                        .collect::<Vec<_>>()
+                }
+                CompletionKind::Mention => self
+                    .mentions
+                    .as_ref()
+                    .map(|provider| provider(query, cx))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|label| CompletionItem {
+                        label,
+                        description: None,
+                    })
+                    .collect(),
+            };
+
+            (!items.is_empty()).then_some(Completion {
+                kind,
+                range,
+                items,
+                selected_ix: 0,
+            })
+        });
+        cx.notify();
+    }
+
+    fn select_completion(&mut self, delta: isize, cx: &mut Context<Self>) {
+        if let Some(completion) = &mut self.completion {
+            let len = completion.items.len() as isize;
+            completion.selected_ix =
+                (completion.selected_ix as isize + delta).rem_euclid(len) as usize;
+            cx.notify();
+        }
+    }
+
+    fn confirm_completion(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(completion) = self.completion.take() else {
+            return;
+        };
+        let Some(item) = completion.items.get(ix) else {
+            return;
+        };
+
+        let text = self.input.read(cx).value().clone();
+        let new_text = format!("{}{} ", completion.kind.trigger(), item.label);
+        let start = text[..completion.range.start].encode_utf16().count();
+        let end = start + text[completion.range.clone()].encode_utf16().count();
+        self.input.update(cx, |input, cx| {
+            input.replace_text_in_range(Some(start..end), &new_text, window, cx);
+        });
+        cx.notify();
+    }
+
+    fn open_files(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        let task = FileDialog::new().multiple(true).open_file(cx);
+        cx.spawn(async move |this, cx| {
+            if let Some(paths) = task.await? {
+                this.update(cx, |this, cx| {
+                    this.attachments
+                        .extend(paths.into_iter().map(Attachment::File));
+                    cx.notify();
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach();
+    }
+
+    fn on_action_move_up(&mut self, _: &input::MoveUp, _: &mut Window, cx: &mut Context<Self>) {
+        if self.completion.is_some() {
+            self.select_completion(-1, cx);
+            cx.stop_propagation();
+        }
+    }
+
+    fn on_action_move_down(&mut self, _: &input::MoveDown, _: &mut Window, cx: &mut Context<Self>) {
+        if self.completion.is_some() {
+            self.select_completion(1, cx);
+            cx.stop_propagation();
+        }
+    }
+
+    fn on_action_enter(
+        &mut self,
+        action: &input::Enter,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(completion) = &self.completion {
+            let ix = completion.selected_ix;
+            self.confirm_completion(ix, window, cx);
+            cx.stop_propagation();
+            return;
+        }
+
+        if self.submit_on_enter != action.secondary {
+            self.submit(window, cx);
+            cx.stop_propagation();
+        }
+    }
+
+    fn on_action_tab(
+        &mut self,
+        _: &input::IndentInline,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(completion) = &self.completion {
+            let ix = completion.selected_ix;
+            self.confirm_completion(ix, window, cx);
+            cx.stop_propagation();
+        }
+    }
+
+    fn on_action_escape(&mut self, _: &input::Escape, _: &mut Window, cx: &mut Context<Self>) {
+        if self.completion.take().is_some() {
+            cx.stop_propagation();
+            cx.notify();
+        }
+    }
+
+    fn on_action_paste(&mut self, _: &input::Paste, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(clipboard) = cx.read_from_clipboard() else {
+            return;
+        };
+
+        let images = clipboard
+            .entries()
+            .iter()
+            .filter_map(|entry| match entry {
+                ClipboardEntry::Image(image) => Some(Attachment::Image(Arc::new(image.clone()))),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        // Paste the text by the input if there is no image.
+        if !images.is_empty() {
+            self.attachments.extend(images);
+            cx.stop_propagation();
+            cx.notify();
+        }
+    }
+
+    fn on_action_new_line(&mut self, _: &NewLine, window: &mut Window, cx: &mut Context<Self>) {
+        self.input
+            .update(cx, |input, cx| input.insert("\n", window, cx));
+    }
+
+    fn on_drop_paths(&mut self, paths: &ExternalPaths, _: &mut Window, cx: &mut Context<Self>) {
+        self.attachments
+            .extend(paths.paths().iter().cloned().map(Attachment::File));
+        cx.notify();
+    }
+}
+
+impl Focusable for ComposerState {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+impl Render for ComposerState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        Composer::new(&cx.entity())
+    }
+}
+
+/// A chat input of the [`ComposerState`].
+#[derive(IntoElement)]
+pub struct Composer {
+    state: Entity<ComposerState>,
+    style: StyleRefinement,
+    attachable: bool,
+    emoji: bool,
+}
+
+impl Composer {
+    pub fn new(state: &Entity<ComposerState>) -> Self {
+        Self {
+            state: state.clone(),
+            style: StyleRefinement::default(),
+            attachable: true,
+            emoji: true,
+        }
+    }
+
+    /// Set to show the button to attach the files, default is true.
+    pub fn attachable(mut self, attachable: bool) -> Self {
+        self.attachable = attachable;
+        self
+    }
+
+    /// Set to show the emoji picker, default is true.
+    pub fn emoji(mut self, emoji: bool) -> Self {
+        self.emoji = emoji;
+        self
+    }
+
+    fn render_completion(
+        &self,
+        completion: &Completion,
+        window: &Window,
+        cx: &App,
+    ) -> impl IntoElement {
+        let trigger = completion.kind.trigger();
+
+        v_flex()
+            .absolute()
+            .bottom_full()
+            .left_0()
+            .right_0()
+            .mb_1()
+            .p_1()
+            .max_h_64()
+            .overflow_hidden()
+            .bg(cx.theme().popover)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius)
+            .shadow_md()
+            .children(completion.items.iter().enumerate().map(|(ix, item)| {
+                h_flex()
+                    .id(("completion", ix))
+                    .gap_2()
+                    .px_2()
+                    .py_1()
+                    .rounded(cx.theme().radius)
+                    .text_sm()
+                    .cursor_pointer()
+                    .when(ix == completion.selected_ix, |this| {
+                        this.bg(cx.theme().accent)
+                    })
+                    .child(format!("{}{}", trigger, item.label))
+                    .when_some(item.description.clone(), |this, description| {
+                        this.child(
+                            div()
+                                .flex_1()
+                                .truncate()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(description),
+                        )
+                    })
+                    .on_click(
+                        window.listener_for(&self.state, move |state, _, window, cx| {
+                            state.confirm_completion(ix, window, cx)
+                        }),
+                    )
+            }))
+    }
+
+    fn render_attachments(
+        &self,
+        attachments: &[Attachment],
+        window: &Window,
+        cx: &App,
+    ) -> impl IntoElement {
+        h_flex()
+            .flex_wrap()
+            .gap_1()
+            .children(attachments.iter().enumerate().map(|(ix, attachment)| {
+                h_flex()
+                    .gap_1()
+                    .pl_1()
+                    .rounded(cx.theme().radius)
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .bg(cx.theme().muted)
+                    .text_xs()
+                    .when_some(
+                        match attachment {
+                            Attachment::Image(image) => Some(image.clone()),
+                            Attachment::File(_) => None,
+                        },
+                        |this, image| this.child(img(image).size_5().rounded_sm()),
+                    )
+                    .child(div().max_w_40().truncate().child(attachment.name()))
+                    .child(
+                        Button::new(("remove-attachment", ix))
+                            .ghost()
+                            .xsmall()
+                            .icon(IconName::Close)
+                            .tooltip(t!("Composer.remove_attachment"))
+                            .on_click(window.listener_for(&self.state, move |state, _, _, cx| {
+                                state.remove_attachment(ix, cx)
+                            })),
+                    )
+            }))
+    }
+}
+
+impl Styled for Composer {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl Focusable for Composer {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.state.read(cx).focus_handle(cx)
+    }
+}
+
+impl RenderOnce for Composer {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let input = state.input.clone();
+        let is_empty = state.is_empty(cx);
+        let completion = state.completion.as_ref().map(|completion| {
+            self.render_completion(completion, window, cx)
+                .into_any_element()
+        });
+        let attachments = (!state.attachments.is_empty()).then(|| {
+            self.render_attachments(&state.attachments, window, cx)
+                .into_any_element()
+        });
+
+        v_flex()
+            .id(("composer", self.state.entity_id()))
+            .key_context(CONTEXT)
+            .relative()
+            .capture_action(window.listener_for(&self.state, ComposerState::on_action_move_up))
+            .capture_action(window.listener_for(&self.state, ComposerState::on_action_move_down))
+            .capture_action(window.listener_for(&self.state, ComposerState::on_action_enter))
+            .capture_action(window.listener_for(&self.state, ComposerState::on_action_tab))
+            .capture_action(window.listener_for(&self.state, ComposerState::on_action_escape))
+            .capture_action(window.listener_for(&self.state, ComposerState::on_action_paste))
+            .on_action(window.listener_for(&self.state, ComposerState::on_action_new_line))
+            .when(self.attachable, |this| {
+                this.drag_over::<ExternalPaths>(|this, _, _, cx| {
+                    this.border_color(cx.theme().drag_border)
+                })
+                .on_drop(window.listener_for(&self.state, ComposerState::on_drop_paths))
+            })
+            .gap_2()
+            .p_2()
+            .border_1()
+            .border_color(cx.theme().input)
+            .rounded(cx.theme().radius)
+            .bg(cx.theme().background)
+            .refine_style(&self.style)
+            .children(completion)
+            .children(attachments)
+            .child(TextInput::new(&input).appearance(false))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .when(self.attachable, |this| {
+                        this.child(
+                            Button::new("attach")
+                                .ghost()
+                                .xsmall()
+                                .icon(IconName::Plus)
+                                .tooltip(t!("Composer.attach"))
+                                .on_click(
+                                    window.listener_for(&self.state, |state, _, window, cx| {
+                                        state.open_files(window, cx)
+                                    }),
+                                ),
+                        )
+                    })
+                    .when(self.emoji, |this| {
+                        this.child(
+                            EmojiPicker::for_input("emoji", &input).anchor(Corner::BottomLeft),
+                        )
+                    })
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("submit")
+                            .primary()
+                            .xsmall()
+                            .icon(IconName::ArrowUp)
+                            .tooltip(t!("Composer.submit"))
+                            .disabled(is_empty)
+                            .on_click(window.listener_for(&self.state, |state, _, window, cx| {
+                                state.submit(window, cx)
+                            })),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{completion_at, parse_command, CompletionKind, SlashCommand};
+
+    #[test]
+    fn test_completion_at() {
+        assert_eq!(
+            completion_at("/gi", 3),
+            Some((CompletionKind::Command, 0..3))
+        );
+        assert_eq!(
+            completion_at("  /gi", 5),
+            Some((CompletionKind::Command, 2..5))
+        );
+        // The commands are only completed at the start.
+        assert_eq!(completion_at("hi /gi", 6), None);
+        assert_eq!(
+            completion_at("hi @jo", 6),
+            Some((CompletionKind::Mention, 3..6))
+        );
+        assert_eq!(
+            completion_at("hi @jo there", 6),
+            Some((CompletionKind::Mention, 3..6))
+        );
+        assert_eq!(completion_at("hi @jo there", 12), None);
+        assert_eq!(completion_at("email@example", 13), None);
+        assert_eq!(completion_at("", 0), None);
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let commands = vec![SlashCommand::new("giphy", ""), SlashCommand::new("me", "")];
+
+        assert_eq!(
+            parse_command("/giphy cats  dogs ", &commands),
+            Some(("giphy".into(), "cats  dogs".into()))
+        );
+        assert_eq!(
+            parse_command("/me", &commands),
+            Some(("me".into(), "".into()))
+        );
+        assert_eq!(parse_command("/unknown", &commands), None);
+        assert_eq!(parse_command("giphy", &commands), None);
+    }
+}
@@ -0,0 +1,251 @@
+//! A debug overlay to diagnose the render performance, toggle it by `ctrl-alt-shift-d`
+//! (`cmd-alt-shift-d` on macOS) in the [`Root`](crate::Root).
+//!
+//! The overlay shows the FPS, the frame time percentiles, and the timings and the call counts
+//! in the last frame of the named regions measured by [`profile`].
+//!
+//! The number of the accessible elements in the window and those without a label are also
+//! shown, see [`rendered_accessibility`].
+//!
+//! ```ignore
+//! let list = gpui_component::debug_overlay::profile("file-list", cx, |cx| {
+//!     self.render_list(cx)
+//! });
+//! ```
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    actions, div, px, App, Global, IntoElement, KeyBinding, ParentElement as _, RenderOnce,
+    SharedString, Styled, Window,
+};
+
+use crate::{
+    accessibility::rendered_accessibility, h_flex, v_flex, ActiveTheme as _, Colorize as _,
+};
+
+actions!(debug_overlay, [ToggleDebugOverlay]);
+
+/// The number of the recent frames to calculate the stats.
+const MAX_SAMPLES: usize = 120;
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-alt-shift-d", ToggleDebugOverlay, Some("Root")),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-alt-shift-d", ToggleDebugOverlay, Some("Root")),
+    ]);
+}
+
+#[derive(Default)]
+struct RegionStats {
+    samples: VecDeque<Duration>,
+    /// The number of calls in the current frame.
+    calls: usize,
+    /// The number of calls in the last frame.
+    last_calls: usize,
+}
+
+#[derive(Default)]
+struct RenderStats {
+    visible: bool,
+    last_frame: Option<Instant>,
+    frames: VecDeque<Duration>,
+    regions: HashMap<SharedString, RegionStats>,
+}
+
+impl Global for RenderStats {}
+
+fn push_sample(samples: &mut VecDeque<Duration>, sample: Duration) {
+    if samples.len() >= MAX_SAMPLES {
+        samples.pop_front();
+    }
+    samples.push_back(sample);
+}
+
+/// Returns true if the debug overlay is visible.
+pub fn is_visible(cx: &App) -> bool {
+    cx.try_global::<RenderStats>()
+        .map_or(false, |stats| stats.visible)
+}
+
+/// Show or hide the debug overlay, the stats are reset when it is hidden.
+pub fn set_visible(visible: bool, cx: &mut App) {
+    let stats = cx.default_global::<RenderStats>();
+    if !visible {
+        *stats = RenderStats::default();
+    }
+    stats.visible = visible;
+    cx.refresh_windows();
+}
+
+/// Measure the time of the named region (e.g.: the render of a component), shown in the debug overlay.
+///
+/// The region is only measured while the overlay is visible.
+pub fn profile<R>(name: impl Into<SharedString>, cx: &mut App, f: impl FnOnce(&mut App) -> R) -> R {
+    if !is_visible(cx) {
+        return f(cx);
+    }
+
+    let start = Instant::now();
+    let result = f(cx);
+    record(name, start.elapsed(), cx);
+    result
+}
+
+/// Record the time of the named region, use this if the region can not be wrapped by [`profile`].
+pub fn record(name: impl Into<SharedString>, duration: Duration, cx: &mut App) {
+    if !is_visible(cx) {
+        return;
+    }
+
+    let region = cx
+        .global_mut::<RenderStats>()
+        .regions
+        .entry(name.into())
+        .or_default();
+    region.calls += 1;
+    push_sample(&mut region.samples, duration);
+}
+
+/// Returns the `p` (0.0 ~ 1.0) percentile of the samples by the nearest rank.
+fn percentile(samples: &[Duration], p: f64) -> Duration {
+    if samples.is_empty() {
+        return Duration::ZERO;
+    }
+
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    let rank = (p.clamp(0., 1.) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.)
+}
+
+/// The overlay to show the render stats.
+///
+/// It is rendered by the [`Root`](crate::Root) when visible, if the [`Root`](crate::Root)
+/// is not used, place it in the window manually when [`is_visible`] is true.
+#[derive(IntoElement)]
+pub struct DebugOverlay;
+
+impl DebugOverlay {
+    /// Record the current frame, the overlay is rendered in each frame while it is visible.
+    fn record_frame(cx: &mut App) {
+        let stats = cx.default_global::<RenderStats>();
+        let now = Instant::now();
+        if let Some(last_frame) = stats.last_frame.replace(now) {
+            push_sample(&mut stats.frames, now - last_frame);
+        }
+        for region in stats.regions.values_mut() {
+            region.last_calls = std::mem::take(&mut region.calls);
+        }
+    }
+}
+
+impl RenderOnce for DebugOverlay {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        Self::record_frame(cx);
+        // Keep rendering the frames to measure the frame time.
+        window.request_animation_frame();
+
+        let stats = cx.global::<RenderStats>();
+        let frames = stats.frames.iter().copied().collect::<Vec<_>>();
+        let avg = frames.iter().sum::<Duration>() / frames.len().max(1) as u32;
+        let fps = if avg.is_zero() {
+            0.
+        } else {
+            1. / avg.as_secs_f64()
+        };
+
+        let mut regions = stats
+            .regions
+            .iter()
+            .map(|(name, region)| {
+                let samples = region.samples.iter().copied().collect::<Vec<_>>();
+                (
+                    name.clone(),
+                    region.last_calls,
+                    percentile(&samples, 0.5),
+                    percentile(&samples, 0.95),
+                )
+            })
+            .collect::<Vec<_>>();
+        regions.sort_by(|a, b| b.3.cmp(&a.3).then_with(|| a.0.cmp(&b.0)));
+        let elements = regions.iter().map(|region| region.1).sum::<usize>();
+        let accessible = rendered_accessibility(window, cx);
+        let unlabeled = accessible
+            .iter()
+            .filter(|properties| properties.label.is_none())
+            .count();
+        let accessible = accessible.len();
+
+        let row = |label: SharedString, value: String| {
+            h_flex()
+                .gap_3()
+                .justify_between()
+                .child(label)
+                .child(div().font_semibold().child(value))
+        };
+
+        v_flex()
+            .absolute()
+            .bottom_2()
+            .left_2()
+            .min_w(px(180.))
+            .p_2()
+            .gap_0p5()
+            .rounded(cx.theme().radius)
+            .bg(cx.theme().popover.opacity(0.9))
+            .text_color(cx.theme().popover_foreground)
+            .border_1()
+            .border_color(cx.theme().border)
+            .font_family("Menlo, Monaco, Consolas, monospace")
+            .text_xs()
+            .child(row("FPS".into(), format!("{:.0}", fps)))
+            .child(row("Frame p50".into(), format_ms(percentile(&frames, 0.5))))
+            .child(row(
+                "Frame p95".into(),
+                format_ms(percentile(&frames, 0.95)),
+            ))
+            .child(row(
+                "Frame p99".into(),
+                format_ms(percentile(&frames, 0.99)),
+            ))
+            .child(row("Elements".into(), elements.to_string()))
+            .child(row("Accessible".into(), accessible.to_string()))
+            .child(row("Unlabeled".into(), unlabeled.to_string()))
+            .children(regions.into_iter().map(|(name, calls, p50, p95)| {
+                row(
+                    name,
+                    format!("{} × {} / {}", calls, format_ms(p50), format_ms(p95)),
+                )
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::percentile;
+
+    #[test]
+    fn test_percentile() {
+        let ms = Duration::from_millis;
+        assert_eq!(percentile(&[], 0.5), Duration::ZERO);
+        assert_eq!(percentile(&[ms(5)], 0.99), ms(5));
+
+        let samples = (1..=100).rev().map(ms).collect::<Vec<_>>();
+        assert_eq!(percentile(&samples, 0.5), ms(50));
+        assert_eq!(percentile(&samples, 0.95), ms(95));
+        assert_eq!(percentile(&samples, 0.99), ms(99));
+        assert_eq!(percentile(&samples, 1.), ms(100));
+        assert_eq!(percentile(&samples, 0.), ms(1));
+    }
+}
@@ -0,0 +1,262 @@
+//! The awaitable dialogs based on the [`Modal`], the result is the button clicked by the user.
+//!
+//! The dialogs are stacked by the modal layer of the [`Root`](crate::Root), so a dialog can be
+//! opened from another dialog.
+//!
+//! ```ignore
+//! cx.spawn_in(window, async move |this, window| {
+//!     let confirmed = window.update(|window, cx| {
+//!         Dialog::confirm("Delete file?", "This action cannot be undone.")
+//!             .ok_button(DialogButton::new("Delete").role(DialogButtonRole::Destructive))
+//!             .confirmed(window, cx)
+//!     })?;
+//!     if confirmed.await {
+//!         // ...
+//!     }
+//!     anyhow::Ok(())
+//! })
+//! .detach();
+//! ```
+use std::future::Future;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, App, ClickEvent, ParentElement as _, Pixels, SharedString,
+    Window,
+};
+use smol::channel;
+
+use crate::{
+    button::{Button, ButtonVariant, ButtonVariants as _},
+    i18n::t,
+    modal::Modal,
+    ContextModal as _,
+};
+
+/// The role of the [`DialogButton`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DialogButtonRole {
+    #[default]
+    Normal,
+    /// The primary action, it is the default button for `enter` if no button is set by
+    /// [`DialogButton::default`].
+    Primary,
+    /// The destructive action, it is never the default button unless set explicitly.
+    Destructive,
+    /// The cancel action, it is the result of `escape`.
+    Cancel,
+}
+
+impl DialogButtonRole {
+    fn variant(&self) -> ButtonVariant {
+        match self {
+            Self::Primary => ButtonVariant::Primary,
+            Self::Destructive => ButtonVariant::Danger,
+            Self::Normal | Self::Cancel => ButtonVariant::default(),
+        }
+    }
+}
+
+/// A button of the [`Dialog`].
+#[derive(Debug, Clone)]
+pub struct DialogButton {
+    label: SharedString,
+    role: DialogButtonRole,
+    is_default: bool,
+}
+
+impl DialogButton {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            role: DialogButtonRole::Normal,
+            is_default: false,
+        }
+    }
+
+    /// Set the role of the button, default is [`DialogButtonRole::Normal`].
+    pub fn role(mut self, role: DialogButtonRole) -> Self {
+        self.role = role;
+        self
+    }
+
+    /// Set the button to be clicked by `enter`.
+    pub fn default(mut self, is_default: bool) -> Self {
+        self.is_default = is_default;
+        self
+    }
+}
+
+/// Returns the index of the button for `enter`, the explicit default button first, then the
+/// first primary button.
+fn default_button(buttons: &[DialogButton]) -> Option<usize> {
+    buttons
+        .iter()
+        .position(|button| button.is_default)
+        .or_else(|| {
+            buttons
+                .iter()
+                .position(|button| button.role == DialogButtonRole::Primary)
+        })
+}
+
+/// Returns the index of the button for `escape`.
+fn cancel_button(buttons: &[DialogButton]) -> Option<usize> {
+    buttons
+        .iter()
+        .position(|button| button.role == DialogButtonRole::Cancel)
+}
+
+/// A modal dialog with a message and the buttons, opened by [`Dialog::prompt`].
+#[derive(Debug, Clone)]
+pub struct Dialog {
+    title: SharedString,
+    message: Option<SharedString>,
+    buttons: Vec<DialogButton>,
+    width: Option<Pixels>,
+}
+
+impl Dialog {
+    pub fn new(title: impl Into<SharedString>) -> Self {
+        Self {
+            title: title.into(),
+            message: None,
+            buttons: vec![],
+            width: None,
+        }
+    }
+
+    /// Create a dialog with the Cancel and OK buttons.
+    pub fn confirm(title: impl Into<SharedString>, message: impl Into<SharedString>) -> Self {
+        Self::new(title)
+            .message(message)
+            .button(DialogButton::new(t!("Modal.cancel")).role(DialogButtonRole::Cancel))
+            .button(DialogButton::new(t!("Modal.ok")).role(DialogButtonRole::Primary))
+    }
+
+    /// Create a dialog with the OK button.
+    pub fn alert(title: impl Into<SharedString>, message: impl Into<SharedString>) -> Self {
+        Self::new(title)
+            .message(message)
+            .button(DialogButton::new(t!("Modal.ok")).role(DialogButtonRole::Primary))
+    }
+
+    pub fn message(mut self, message: impl Into<SharedString>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Add a button, the buttons are laid out from left to right in the footer.
+    pub fn button(mut self, button: DialogButton) -> Self {
+        self.buttons.push(button);
+        self
+    }
+
+    /// Replace the last button, e.g.: the OK button of [`Self::confirm`].
+    pub fn ok_button(mut self, button: DialogButton) -> Self {
+        self.buttons.pop();
+        self.buttons.push(button);
+        self
+    }
+
+    /// Set the width of the dialog, default is the width of the [`Modal`].
+    pub fn width(mut self, width: Pixels) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Open the dialog, returns the index of the clicked button.
+    ///
+    /// The `escape` results the cancel button, or `None` if there is no cancel button, also
+    /// `None` if the dialog is closed by [`ContextModal::close_modal`].
+    pub fn prompt(self, window: &mut Window, cx: &mut App) -> impl Future<Output = Option<usize>> {
+        let (tx, rx) = channel::bounded::<Option<usize>>(1);
+        let default_ix = default_button(&self.buttons);
+        let cancel_ix = cancel_button(&self.buttons);
+
+        window.open_modal(cx, move |modal: Modal, _, _| {
+            let buttons = self.buttons.clone();
+            let mut modal = modal
+                .title(self.title.clone())
+                .show_close(false)
+                .overlay_closable(false)
+                .when_some(self.width, |this, width| this.width(width))
+                .on_ok({
+                    let tx = tx.clone();
+                    move |_, _, _| {
+                        let Some(ix) = default_ix else {
+                            return false;
+                        };
+                        _ = tx.try_send(Some(ix));
+                        true
+                    }
+                })
+                .on_cancel({
+                    let tx = tx.clone();
+                    move |_, _, _| {
+                        _ = tx.try_send(cancel_ix);
+                        true
+                    }
+                })
+                .footer({
+                    let tx = tx.clone();
+                    move |_, _, _, _| {
+                        buttons
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, button)| {
+                                let tx = tx.clone();
+                                Button::new(ix)
+                                    .label(button.label.clone())
+                                    .with_variant(button.role.variant())
+                                    .on_click(move |_: &ClickEvent, window, cx| {
+                                        _ = tx.try_send(Some(ix));
+                                        window.close_modal(cx);
+                                    })
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                });
+            if let Some(message) = self.message.clone() {
+                modal = modal.child(div().child(message));
+            }
+            modal
+        });
+
+        async move { rx.recv().await.ok().flatten() }
+    }
+
+    /// Open the dialog, returns true if a button without the cancel role is clicked.
+    pub fn confirmed(self, window: &mut Window, cx: &mut App) -> impl Future<Output = bool> {
+        let cancel_ix = cancel_button(&self.buttons);
+        let result = self.prompt(window, cx);
+        async move {
+            match result.await {
+                Some(ix) => Some(ix) != cancel_ix,
+                None => false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cancel_button, default_button, DialogButton, DialogButtonRole};
+
+    #[test]
+    fn test_dialog_buttons() {
+        let cancel = DialogButton::new("Cancel").role(DialogButtonRole::Cancel);
+        let delete = DialogButton::new("Delete").role(DialogButtonRole::Destructive);
+        let save = DialogButton::new("Save").role(DialogButtonRole::Primary);
+
+        let buttons = vec![cancel.clone(), delete.clone(), save.clone()];
+        assert_eq!(default_button(&buttons), Some(2));
+        assert_eq!(cancel_button(&buttons), Some(0));
+
+        // The destructive button is not the default unless set explicitly.
+        let buttons = vec![cancel.clone(), delete.clone()];
+        assert_eq!(default_button(&buttons), None);
+        let buttons = vec![cancel, delete.default(true), save];
+        assert_eq!(default_button(&buttons), Some(1));
+        assert_eq!(cancel_button(&buttons[1..]), None);
+    }
+}
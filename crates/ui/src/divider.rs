@@ -1,4 +1,4 @@
-use crate::{ActiveTheme, StyledExt};
+use crate::{snap_stroke, ActiveTheme, StyledExt};
 use gpui::{
     div, prelude::FluentBuilder as _, px, App, Axis, Div, Hsla, IntoElement, ParentElement,
     RenderOnce, SharedString, StyleRefinement, Styled, Window,
@@ -57,7 +57,9 @@ impl Styled for Divider {
 }
 
 impl RenderOnce for Divider {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let thickness = snap_stroke(px(1.), window.scale_factor());
+
         self.base
             .flex()
             .flex_shrink_0()
@@ -68,8 +70,8 @@ impl RenderOnce for Divider {
                 div()
                     .absolute()
                     .map(|this| match self.axis {
-                        Axis::Vertical => this.w(px(1.)).h_full(),
-                        Axis::Horizontal => this.h(px(1.)).w_full(),
+                        Axis::Vertical => this.w(thickness).h_full(),
+                        Axis::Horizontal => this.h(thickness).w_full(),
                     })
                     .bg(self.color.unwrap_or(cx.theme().border)),
             )
@@ -68,6 +68,9 @@ pub struct DockArea {
     /// The panel style, default is [`PanelStyle::Default`](PanelStyle::Default).
     pub(crate) panel_style: PanelStyle,
 
+    /// The key to persist the layout, see [`DockArea::persist_layout`].
+    layout_key: Option<SharedString>,
+
     _subscriptions: Vec<Subscription>,
 }
 
@@ -464,6 +467,7 @@ impl DockArea {
             bottom_dock: None,
             locked: false,
             panel_style: PanelStyle::Default,
+            layout_key: None,
             _subscriptions: vec![],
         };
 
@@ -845,6 +849,35 @@ impl DockArea {
         Ok(())
     }
 
+    /// Persist the layout to the [`state`](crate::state) storage with the `key`.
+    ///
+    /// The persisted layout is restored now if its version is the same as the dock area,
+    /// and the layout is saved when it is changed. The panels must be registered by
+    /// [`register_panel`] to be restored.
+    pub fn persist_layout(
+        &mut self,
+        key: impl Into<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let key = key.into();
+        if let Some(state) = crate::state::get::<DockAreaState>(&key, cx) {
+            if state.version == self.version {
+                if let Err(err) = self.load(state, window, cx) {
+                    tracing::error!("failed to restore the dock layout: {:?}", err);
+                }
+            }
+        }
+        self.layout_key = Some(key);
+    }
+
+    fn save_layout(&self, cx: &mut App) {
+        if let Some(key) = &self.layout_key {
+            let state = self.dump(cx);
+            crate::state::set(key.to_string(), &state, cx);
+        }
+    }
+
     /// Dump the dock panels layout to PanelState.
     ///
     /// See also [DockArea::load].
@@ -886,8 +919,9 @@ impl DockArea {
                 self._subscriptions.push(cx.subscribe_in(
                     view,
                     window,
-                    move |_, _, event, window, cx| match event {
+                    move |this, _, event, window, cx| match event {
                         PanelEvent::LayoutChanged => {
+                            this.save_layout(cx);
                             cx.spawn_in(window, async move |view, window| {
                                 _ = view.update_in(window, |view, window, cx| {
                                     view.update_toggle_button_tab_panels(window, cx)
@@ -923,7 +957,7 @@ impl DockArea {
             cx.subscribe_in(
                 view,
                 window,
-                move |_, panel, event, window, cx| match event {
+                move |this, panel, event, window, cx| match event {
                     PanelEvent::ZoomIn => {
                         let panel = panel.clone();
                         cx.spawn_in(window, async move |view, window| {
@@ -942,6 +976,7 @@ impl DockArea {
                         })
                         .detach(),
                     PanelEvent::LayoutChanged => {
+                        this.save_layout(cx);
                         cx.spawn_in(window, async move |view, window| {
                             _ = view.update_in(window, |view, window, cx| {
                                 view.update_toggle_button_tab_panels(window, cx)
@@ -6,7 +6,7 @@ use gpui::{
     Focusable, Global, Hsla, IntoElement, Render, SharedString, WeakEntity, Window,
 };
 
-use rust_i18n::t;
+use crate::i18n::t;
 
 use super::{invalid_panel::InvalidPanel, DockArea, PanelInfo, PanelState};
 
@@ -1,12 +1,12 @@
 use std::sync::Arc;
 
+use crate::i18n::t;
 use gpui::{
-    div, prelude::FluentBuilder, px, relative, rems, App, AppContext, Context, Corner,
-    DismissEvent, Div, DragMoveEvent, Empty, Entity, EventEmitter, FocusHandle, Focusable,
+    div, prelude::FluentBuilder, px, relative, rems, AnyWindowHandle, App, AppContext, Context,
+    Corner, DismissEvent, Div, DragMoveEvent, Empty, Entity, EventEmitter, FocusHandle, Focusable,
     InteractiveElement as _, IntoElement, ParentElement, Pixels, Render, ScrollHandle,
     SharedString, StatefulInteractiveElement, StyleRefinement, Styled, WeakEntity, Window,
 };
-use rust_i18n::t;
 
 use crate::{
     button::{Button, ButtonVariants as _},
@@ -35,11 +35,21 @@ struct TabState {
 pub(crate) struct DragPanel {
     pub(crate) panel: Arc<dyn PanelView>,
     pub(crate) tab_panel: Entity<TabPanel>,
+    /// The window of the `tab_panel`, the panel can be dropped to the other window.
+    pub(crate) window: AnyWindowHandle,
 }
 
 impl DragPanel {
-    pub(crate) fn new(panel: Arc<dyn PanelView>, tab_panel: Entity<TabPanel>) -> Self {
-        Self { panel, tab_panel }
+    pub(crate) fn new(
+        panel: Arc<dyn PanelView>,
+        tab_panel: Entity<TabPanel>,
+        window: &Window,
+    ) -> Self {
+        Self {
+            panel,
+            tab_panel,
+            window: window.window_handle(),
+        }
     }
 }
 
@@ -93,7 +103,7 @@ impl Panel for TabPanel {
     fn title(&self, window: &Window, cx: &App) -> gpui::AnyElement {
         self.active_panel(cx)
             .map(|panel| panel.title(window, cx))
-            .unwrap_or("Empty Tab".into_any_element())
+            .unwrap_or(t!("Dock.Empty Tab").into_any_element())
     }
 
     fn closable(&self, cx: &App) -> bool {
@@ -637,10 +647,7 @@ impl TabPanel {
                         .child(panel.title(window, cx))
                         .when(state.draggable, |this| {
                             this.on_drag(
-                                DragPanel {
-                                    panel: panel.clone(),
-                                    tab_panel: view,
-                                },
+                                DragPanel::new(panel.clone(), view, window),
                                 |drag, _, _, cx| {
                                     cx.stop_propagation();
                                     cx.new(|_| drag.clone())
@@ -725,7 +732,7 @@ impl TabPanel {
                         .when(!droppable, |this| {
                             this.when(state.draggable, |this| {
                                 this.on_drag(
-                                    DragPanel::new(panel.clone(), view.clone()),
+                                    DragPanel::new(panel.clone(), view.clone(), window),
                                     |drag, _, _, cx| {
                                         cx.stop_propagation();
                                         cx.new(|_| drag.clone())
@@ -921,6 +928,16 @@ impl TabPanel {
         // Cannot update ui::dock::tab_panel::TabPanel while it is already being updated
         if is_same_tab {
             self.detach_panel(panel.clone(), window, cx);
+        } else if drag.window != window.window_handle() {
+            // Dragged from the other window, detach it in the window of the source tab panel.
+            let tab_panel = drag.tab_panel.clone();
+            let panel = panel.clone();
+            _ = drag.window.update(cx, |_, window, cx| {
+                tab_panel.update(cx, |view, cx| {
+                    view.detach_panel(panel, window, cx);
+                    view.remove_self_if_empty(window, cx);
+                });
+            });
         } else {
             let _ = drag.tab_panel.update(cx, |view, cx| {
                 view.detach_panel(panel.clone(), window, cx);
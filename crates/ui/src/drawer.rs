@@ -14,12 +14,17 @@ use crate::{
     modal::overlay_color,
     root::ContextModal as _,
     title_bar::TITLE_BAR_HEIGHT,
-    v_flex, ActiveTheme, IconName, Placement, Sizable, StyledExt as _,
+    v_flex, ActiveTheme, FocusNext, FocusPrev, FocusTrap, FocusTrapExt as _, IconName, Placement,
+    Sizable, StyledExt as _,
 };
 
 const CONTEXT: &str = "Drawer";
 pub fn init(cx: &mut App) {
-    cx.bind_keys([KeyBinding::new("escape", Cancel, Some(CONTEXT))])
+    cx.bind_keys([
+        KeyBinding::new("escape", Cancel, Some(CONTEXT)),
+        KeyBinding::new("tab", FocusNext, Some(CONTEXT)),
+        KeyBinding::new("shift-tab", FocusPrev, Some(CONTEXT)),
+    ])
 }
 
 #[derive(IntoElement)]
@@ -35,6 +40,7 @@ pub struct Drawer {
     margin_top: Pixels,
     overlay: bool,
     overlay_closable: bool,
+    focus_handles: Vec<FocusHandle>,
 }
 
 impl Drawer {
@@ -50,6 +56,7 @@ impl Drawer {
             margin_top: TITLE_BAR_HEIGHT,
             overlay: true,
             overlay_closable: true,
+            focus_handles: vec![],
             on_close: Rc::new(|_, _, _| {}),
         }
     }
@@ -98,6 +105,15 @@ impl Drawer {
         self
     }
 
+    /// Set the focus handles to cycle by Tab / Shift-Tab, default is empty.
+    ///
+    /// The focus is always trapped inside the drawer, even without focus handles, it will never
+    /// escape to the views behind.
+    pub fn focus_handles(mut self, handles: impl IntoIterator<Item = FocusHandle>) -> Self {
+        self.focus_handles = handles.into_iter().collect();
+        self
+    }
+
     /// Listen to the close event of the drawer.
     pub fn on_close(
         mut self,
@@ -123,6 +139,7 @@ impl Styled for Drawer {
 impl RenderOnce for Drawer {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let placement = self.placement;
+        let reduce_motion = cx.theme().reduce_motion;
         let titlebar_height = self.margin_top;
         let window_paddings = crate::window_border::window_paddings(window);
         let size = window.viewport_size()
@@ -131,6 +148,9 @@ impl RenderOnce for Drawer {
                 window_paddings.top + window_paddings.bottom,
             );
         let on_close = self.on_close.clone();
+        let focus_trap = FocusTrap::new()
+            .container(&self.focus_handle)
+            .handles(self.focus_handles);
 
         anchored()
             .position(point(
@@ -158,6 +178,7 @@ impl RenderOnce for Drawer {
                             .id("drawer")
                             .key_context(CONTEXT)
                             .track_focus(&self.focus_handle)
+                            .focus_trap(&focus_trap)
                             .on_action({
                                 let on_close = self.on_close.clone();
                                 move |_: &Cancel, window, cx| {
@@ -231,6 +252,10 @@ impl RenderOnce for Drawer {
                                 "slide",
                                 Animation::new(Duration::from_secs_f64(0.15)),
                                 move |this, delta| {
+                                    if reduce_motion {
+                                        return this.opacity(delta);
+                                    }
+
                                     let y = px(-100.) + delta * px(100.);
                                     this.map(|this| match placement {
                                         Placement::Top => this.top(y),
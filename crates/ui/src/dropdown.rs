@@ -1,19 +1,20 @@
+use crate::i18n::t;
 use gpui::{
     anchored, canvas, deferred, div, prelude::FluentBuilder, px, rems, AnyElement, App, AppContext,
     Bounds, ClickEvent, Context, DismissEvent, Edges, ElementId, Empty, Entity, EventEmitter,
-    FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding, Length, ParentElement,
-    Pixels, Render, RenderOnce, SharedString, StatefulInteractiveElement, StyleRefinement, Styled,
-    Subscription, Task, WeakEntity, Window,
+    FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding, Length, MouseDownEvent,
+    ParentElement, Pixels, Render, RenderOnce, SharedString, StatefulInteractiveElement,
+    StyleRefinement, Styled, Subscription, Task, WeakEntity, Window,
 };
-use rust_i18n::t;
 
 use crate::{
     actions::{Cancel, Confirm, SelectNext, SelectPrev},
     h_flex,
     input::clear_button,
     list::{List, ListDelegate},
-    v_flex, ActiveTheme, Disableable, Icon, IconName, IndexPath, Selectable, Sizable, Size,
-    StyleSized, StyledExt,
+    overlay::{self, OverlayId, OverlayLayer},
+    v_flex, ActiveTheme, Disableable, Icon, IconName, IndexPath, ReadOnly, Selectable, Sizable,
+    Size, StyleSized, StyledExt,
 };
 
 #[derive(Clone)]
@@ -158,6 +159,10 @@ where
         self.delegate.items_count(section)
     }
 
+    fn item_text(&self, ix: IndexPath, _: &App) -> Option<SharedString> {
+        self.delegate.item(ix).map(|item| item.title())
+    }
+
     fn render_section_header(
         &self,
         section: usize,
@@ -283,6 +288,8 @@ pub struct DropdownState<D: DropdownDelegate + 'static> {
     /// Store the bounds of the input
     bounds: Bounds<Pixels>,
     open: bool,
+    /// The overlay of the opened list, see [`overlay::sync`].
+    overlay_id: Option<OverlayId>,
     selected_value: Option<<D::Item as DropdownItem>::Value>,
     _subscriptions: Vec<Subscription>,
 }
@@ -301,6 +308,7 @@ pub struct Dropdown<D: DropdownDelegate + 'static> {
     empty: Option<AnyElement>,
     menu_width: Length,
     disabled: bool,
+    read_only: bool,
     appearance: bool,
 }
 
@@ -532,6 +540,7 @@ where
             size: Size::Medium,
             selected_value: None,
             open: false,
+            overlay_id: None,
             bounds: Bounds::default(),
             empty: None,
             _subscriptions,
@@ -694,6 +703,7 @@ where
             empty: None,
             menu_width: Length::Auto,
             disabled: false,
+            read_only: false,
             appearance: true,
         }
     }
@@ -733,6 +743,8 @@ where
     }
 
     /// Set the disable state for the dropdown.
+    ///
+    /// The disabled dropdown is not focusable.
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
@@ -797,6 +809,19 @@ where
     }
 }
 
+impl<D> ReadOnly for Dropdown<D>
+where
+    D: DropdownDelegate + 'static,
+{
+    /// Set the read-only state for the dropdown.
+    ///
+    /// The read-only dropdown is focusable, but the menu can't be opened to change the value.
+    fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+}
+
 impl<D> Sizable for Dropdown<D>
 where
     D: DropdownDelegate + 'static,
@@ -858,20 +883,42 @@ where
             });
         }
 
+        let overlay_id = self.state.update(cx, |state, cx| {
+            let weak_state = cx.entity().downgrade();
+            overlay::sync(
+                &mut state.overlay_id,
+                state.open,
+                OverlayLayer::Popover,
+                window,
+                cx,
+                move |_, cx| {
+                    _ = weak_state.update(cx, |state, cx| {
+                        state.open = false;
+                        cx.notify();
+                    });
+                },
+            )
+        });
+
         let state = self.state.read(cx);
         let show_clean = self.cleanable && state.selected_index(cx).is_some();
         let bounds = state.bounds;
-        let allow_open = !(state.open || self.disabled);
+        let interactive = !(self.disabled || self.read_only);
+        let allow_open = !state.open && interactive;
         let outline_visible = state.open || is_focused && !self.disabled;
         let popup_radius = cx.theme().radius.min(px(8.));
 
         div()
             .id(self.id.clone())
             .key_context(CONTEXT)
-            .track_focus(&self.focus_handle(cx))
-            .on_action(window.listener_for(&self.state, DropdownState::up))
-            .on_action(window.listener_for(&self.state, DropdownState::down))
-            .on_action(window.listener_for(&self.state, DropdownState::enter))
+            .when(!self.disabled, |this| {
+                this.track_focus(&self.focus_handle(cx))
+            })
+            .when(interactive, |this| {
+                this.on_action(window.listener_for(&self.state, DropdownState::up))
+                    .on_action(window.listener_for(&self.state, DropdownState::down))
+                    .on_action(window.listener_for(&self.state, DropdownState::enter))
+            })
             .on_action(window.listener_for(&self.state, DropdownState::escape))
             .size_full()
             .relative()
@@ -884,10 +931,13 @@ where
                     .justify_between()
                     .when(self.appearance, |this| {
                         this.bg(cx.theme().background)
-                            .border_1()
+                            .border_snapped(px(1.), window)
                             .border_color(cx.theme().input)
                             .rounded(cx.theme().radius)
                             .when(cx.theme().shadow, |this| this.shadow_xs())
+                            .when(self.read_only, |this| {
+                                this.bg(cx.theme().muted.opacity(0.5))
+                            })
                     })
                     .map(|this| {
                         if self.disabled {
@@ -922,7 +972,7 @@ where
                             )
                             .when(show_clean, |this| {
                                 this.child(clear_button(cx).map(|this| {
-                                    if self.disabled {
+                                    if !interactive {
                                         this.disabled(true)
                                     } else {
                                         this.on_click(
@@ -943,7 +993,7 @@ where
                                     }
                                 };
 
-                                this.child(icon.xsmall().text_color(match self.disabled {
+                                this.child(icon.xsmall().text_color(match !interactive {
                                     true => cx.theme().muted_foreground.opacity(0.5),
                                     false => cx.theme().muted_foreground,
                                 }))
@@ -982,15 +1032,24 @@ where
                                         .shadow_md()
                                         .child(state.list.clone()),
                                 )
+                                .when_some(overlay_id, |this, id| {
+                                    this.child(overlay::bounds_tracker(id))
+                                })
                                 .on_mouse_down_out(window.listener_for(
                                     &self.state,
-                                    |this, _, window, cx| {
+                                    move |this, event: &MouseDownEvent, window, cx| {
+                                        // Keep open if click in the overlays above.
+                                        if overlay_id.map_or(false, |id| {
+                                            overlay::contains_above(id, event.position, window, cx)
+                                        }) {
+                                            return;
+                                        }
                                         this.escape(&Cancel, window, cx);
                                     },
                                 )),
                         ),
                     )
-                    .with_priority(1),
+                    .with_priority(OverlayLayer::Popover.priority()),
                 )
             })
     }
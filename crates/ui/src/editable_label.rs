@@ -0,0 +1,360 @@
+//! A label that turns into an input to edit the text in place, e.g.: to rename the items of
+//! the trees, the tabs and the table cells.
+//!
+//! The editing is started by the click or `F2`, committed by `enter` or the blur, and
+//! cancelled by `escape`.
+//!
+//! ```ignore
+//! let state = cx.new(|cx| {
+//!     EditableLabelState::new(window, cx)
+//!         .default_value("main.rs")
+//!         .validate(|value| match value.is_empty() {
+//!             true => Err("The name is required.".into()),
+//!             false => Ok(()),
+//!         })
+//! });
+//! cx.subscribe(&state, |this, _, event: &EditableLabelEvent, cx| {
+//!     if let EditableLabelEvent::Change(name) = event {
+//!         this.rename(name, cx);
+//!     }
+//! });
+//!
+//! // In render
+//! EditableLabel::new(&state).edit_on_double_click(true)
+//! ```
+use std::rc::Rc;
+
+use gpui::{
+    actions, div, prelude::FluentBuilder as _, relative, App, AppContext as _, Context, Entity,
+    EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement, KeyBinding,
+    ParentElement as _, Render, RenderOnce, SharedString, StatefulInteractiveElement as _,
+    StyleRefinement, Styled, Subscription, Window,
+};
+
+use crate::{
+    input::{self, InputEvent, InputState, SelectAll, TextInput},
+    ActiveTheme as _, Sizable, Size, StyledExt as _,
+};
+
+const CONTEXT: &str = "EditableLabel";
+
+actions!(editable_label, [Edit]);
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([KeyBinding::new("f2", Edit, Some(CONTEXT))])
+}
+
+type Validate = Rc<dyn Fn(&str) -> Result<(), SharedString>>;
+
+/// The events of the [`EditableLabelState`].
+#[derive(Debug, Clone)]
+pub enum EditableLabelEvent {
+    /// The value is changed by the user.
+    Change(SharedString),
+    /// The editing is cancelled by `escape`, or the invalid value is discarded by the blur.
+    Cancel,
+}
+
+/// Returns the trimmed value to commit, `None` if it is unchanged.
+fn commit_value(
+    old: &str,
+    new: &str,
+    validate: Option<&Validate>,
+) -> Result<Option<SharedString>, SharedString> {
+    let new = new.trim();
+    if new == old {
+        return Ok(None);
+    }
+    if let Some(validate) = validate {
+        validate(new)?;
+    }
+    Ok(Some(SharedString::from(new.to_string())))
+}
+
+/// State of the [`EditableLabel`].
+pub struct EditableLabelState {
+    focus_handle: FocusHandle,
+    value: SharedString,
+    editing: bool,
+    error: Option<SharedString>,
+    input: Entity<InputState>,
+    validate: Option<Validate>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<EditableLabelEvent> for EditableLabelState {}
+
+impl EditableLabelState {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| InputState::new(window, cx));
+
+        let _subscriptions =
+            vec![
+                cx.subscribe_in(&input, window, |this, _, event: &InputEvent, window, cx| {
+                    match event {
+                        InputEvent::PressEnter { .. } => this.commit(false, window, cx),
+                        InputEvent::Blur => this.commit(true, window, cx),
+                        InputEvent::Change(_) => {
+                            if this.error.take().is_some() {
+                                cx.notify();
+                            }
+                        }
+                        _ => {}
+                    }
+                }),
+            ];
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            value: SharedString::default(),
+            editing: false,
+            error: None,
+            input,
+            validate: None,
+            _subscriptions,
+        }
+    }
+
+    /// Set the default value.
+    pub fn default_value(mut self, value: impl Into<SharedString>) -> Self {
+        self.value = value.into();
+        self
+    }
+
+    /// Set the validation of the edited value, the error is shown under the input and the
+    /// value is not committed.
+    pub fn validate(
+        mut self,
+        validate: impl Fn(&str) -> Result<(), SharedString> + 'static,
+    ) -> Self {
+        self.validate = Some(Rc::new(validate));
+        self
+    }
+
+    /// Returns the current value.
+    pub fn value(&self) -> &SharedString {
+        &self.value
+    }
+
+    /// Set the value, the editing is not changed.
+    pub fn set_value(&mut self, value: impl Into<SharedString>, cx: &mut Context<Self>) {
+        self.value = value.into();
+        cx.notify();
+    }
+
+    /// Returns true if the label is being edited.
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    /// Returns the validation error of the edited value.
+    pub fn error(&self) -> Option<&SharedString> {
+        self.error.as_ref()
+    }
+
+    /// Start editing, the input is focused with the text selected.
+    pub fn start_editing(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing {
+            return;
+        }
+
+        self.editing = true;
+        self.error = None;
+        let value = self.value.clone();
+        self.input.update(cx, |input, cx| {
+            input.set_value(value, window, cx);
+            input.select_all(&SelectAll, window, cx);
+            input.focus(window, cx);
+        });
+        cx.notify();
+    }
+
+    /// Cancel editing, the value is unchanged.
+    pub fn cancel_editing(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.editing {
+            return;
+        }
+
+        self.stop_editing(window, cx);
+        cx.emit(EditableLabelEvent::Cancel);
+    }
+
+    fn stop_editing(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editing = false;
+        self.error = None;
+        // Keep the focus on the label for `F2`, unless the focus has been moved by the blur.
+        if self.input.read(cx).focus_handle(cx).is_focused(window) {
+            self.focus_handle.focus(window);
+        }
+        cx.notify();
+    }
+
+    /// Commit the edited value, the invalid value is kept to edit, or discarded if `blur`.
+    fn commit(&mut self, blur: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.editing {
+            return;
+        }
+
+        let new = self.input.read(cx).value().clone();
+        match commit_value(&self.value, &new, self.validate.as_ref()) {
+            Ok(value) => {
+                self.stop_editing(window, cx);
+                if let Some(value) = value {
+                    self.value = value.clone();
+                    cx.emit(EditableLabelEvent::Change(value));
+                }
+            }
+            Err(_) if blur => self.cancel_editing(window, cx),
+            Err(error) => {
+                self.error = Some(error);
+                cx.notify();
+            }
+        }
+    }
+
+    fn on_edit(&mut self, _: &Edit, window: &mut Window, cx: &mut Context<Self>) {
+        self.start_editing(window, cx);
+    }
+
+    fn on_escape(&mut self, _: &input::Escape, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.editing {
+            cx.propagate();
+            return;
+        }
+
+        self.cancel_editing(window, cx);
+    }
+}
+
+impl Focusable for EditableLabelState {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for EditableLabelState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        EditableLabel::new(&cx.entity())
+    }
+}
+
+/// A label to edit the text of the [`EditableLabelState`] in place.
+#[derive(IntoElement)]
+pub struct EditableLabel {
+    state: Entity<EditableLabelState>,
+    style: StyleRefinement,
+    size: Size,
+    double_click: bool,
+}
+
+impl EditableLabel {
+    pub fn new(state: &Entity<EditableLabelState>) -> Self {
+        Self {
+            state: state.clone(),
+            style: StyleRefinement::default(),
+            size: Size::default(),
+            double_click: false,
+        }
+    }
+
+    /// Set to start editing by the double click instead of the click, e.g.: the click selects
+    /// the row of a tree. Default is false.
+    pub fn edit_on_double_click(mut self, double_click: bool) -> Self {
+        self.double_click = double_click;
+        self
+    }
+}
+
+impl Sizable for EditableLabel {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Styled for EditableLabel {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl Focusable for EditableLabel {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.state.read(cx).focus_handle.clone()
+    }
+}
+
+impl RenderOnce for EditableLabel {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let click_count = if self.double_click { 2 } else { 1 };
+
+        div()
+            .id(("editable-label", self.state.entity_id()))
+            .key_context(CONTEXT)
+            .track_focus(&state.focus_handle)
+            .relative()
+            .min_w_0()
+            .on_action(window.listener_for(&self.state, EditableLabelState::on_edit))
+            .on_action(window.listener_for(&self.state, EditableLabelState::on_escape))
+            .refine_style(&self.style)
+            .map(|this| {
+                if state.editing {
+                    this.child(
+                        TextInput::new(&state.input)
+                            .with_size(self.size)
+                            .when(state.error.is_some(), |this| {
+                                this.border_color(cx.theme().danger)
+                            }),
+                    )
+                    .when_some(state.error.clone(), |this, error| {
+                        this.child(
+                            div()
+                                .absolute()
+                                .top(relative(1.))
+                                .left_0()
+                                .mt_1()
+                                .text_xs()
+                                .text_color(cx.theme().danger)
+                                .child(error),
+                        )
+                    })
+                } else {
+                    let state = self.state.clone();
+                    this.truncate()
+                        .child(self.state.read(cx).value.clone())
+                        .on_click(move |event, window, cx| {
+                            if event.up.click_count == click_count {
+                                state.update(cx, |state, cx| state.start_editing(window, cx));
+                            }
+                        })
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::{commit_value, Validate};
+
+    #[test]
+    fn test_commit_value() {
+        assert_eq!(commit_value("a", " b ", None), Ok(Some("b".into())));
+        assert_eq!(commit_value("a", "a ", None), Ok(None));
+
+        let validate: Validate = Rc::new(|value| match value.is_empty() {
+            true => Err("required".into()),
+            false => Ok(()),
+        });
+        assert_eq!(
+            commit_value("a", "  ", Some(&validate)),
+            Err("required".into())
+        );
+        assert_eq!(
+            commit_value("a", "b", Some(&validate)),
+            Ok(Some("b".into()))
+        );
+    }
+}
@@ -0,0 +1,429 @@
+//! The emoji registry shared by the [`EmojiPicker`](crate::emoji_picker::EmojiPicker) and
+//! the shortcodes of the [`TextView`](crate::text::TextView), e.g.: `:rocket:`.
+//!
+//! The custom emojis are registered once by [`register_custom_emojis`], they are shown in all
+//! the emoji pickers. The custom emojis are images, so their shortcodes are kept as is in the
+//! text of the TextView.
+use gpui::{App, Global, ImageSource, SharedString};
+
+/// The emoji of the GitHub shortcodes, sorted by the name to binary search.
+const SHORTCODES: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("100", "💯"),
+    ("1st_place_medal", "🥇"),
+    ("airplane", "✈️"),
+    ("alarm_clock", "⏰"),
+    ("alien", "👽"),
+    ("angry", "😠"),
+    ("apple", "🍎"),
+    ("arrow_down", "⬇️"),
+    ("arrow_left", "⬅️"),
+    ("arrow_right", "➡️"),
+    ("arrow_up", "⬆️"),
+    ("arrows_counterclockwise", "🔄"),
+    ("art", "🎨"),
+    ("astonished", "😲"),
+    ("avocado", "🥑"),
+    ("balloon", "🎈"),
+    ("ballot_box_with_check", "☑️"),
+    ("banana", "🍌"),
+    ("bangbang", "‼️"),
+    ("bar_chart", "📊"),
+    ("basketball", "🏀"),
+    ("bear", "🐻"),
+    ("bee", "🐝"),
+    ("beer", "🍺"),
+    ("beers", "🍻"),
+    ("bell", "🔔"),
+    ("bike", "🚲"),
+    ("bird", "🐦"),
+    ("birthday", "🎂"),
+    ("black_circle", "⚫"),
+    ("black_heart", "🖤"),
+    ("blue_heart", "💙"),
+    ("blush", "😊"),
+    ("book", "📖"),
+    ("bookmark", "🔖"),
+    ("books", "📚"),
+    ("boom", "💥"),
+    ("brain", "🧠"),
+    ("broken_heart", "💔"),
+    ("bug", "🐛"),
+    ("bulb", "💡"),
+    ("burrito", "🌯"),
+    ("bus", "🚌"),
+    ("butterfly", "🦋"),
+    ("cactus", "🌵"),
+    ("cake", "🍰"),
+    ("calendar", "📆"),
+    ("call_me_hand", "🤙"),
+    ("camera", "📷"),
+    ("car", "🚗"),
+    ("cat", "🐱"),
+    ("champagne", "🍾"),
+    ("chart_with_downwards_trend", "📉"),
+    ("chart_with_upwards_trend", "📈"),
+    ("checkered_flag", "🏁"),
+    ("cherries", "🍒"),
+    ("cherry_blossom", "🌸"),
+    ("chicken", "🐔"),
+    ("clap", "👏"),
+    ("clipboard", "📋"),
+    ("cloud", "☁️"),
+    ("clown_face", "🤡"),
+    ("cocktail", "🍸"),
+    ("coffee", "☕"),
+    ("collision", "💥"),
+    ("computer", "💻"),
+    ("confetti_ball", "🎊"),
+    ("confounded", "😖"),
+    ("confused", "😕"),
+    ("construction", "🚧"),
+    ("cookie", "🍪"),
+    ("cool", "🆒"),
+    ("cow", "🐮"),
+    ("crab", "🦀"),
+    ("credit_card", "💳"),
+    ("crescent_moon", "🌙"),
+    ("crossed_fingers", "🤞"),
+    ("cry", "😢"),
+    ("crystal_ball", "🔮"),
+    ("dart", "🎯"),
+    ("date", "📅"),
+    ("deciduous_tree", "🌳"),
+    ("desktop_computer", "🖥️"),
+    ("disappointed", "😞"),
+    ("dizzy", "💫"),
+    ("dog", "🐶"),
+    ("dollar", "💵"),
+    ("dolphin", "🐬"),
+    ("doughnut", "🍩"),
+    ("droplet", "💧"),
+    ("earth_americas", "🌎"),
+    ("email", "📧"),
+    ("envelope", "✉️"),
+    ("evergreen_tree", "🌲"),
+    ("exclamation", "❗"),
+    ("exploding_head", "🤯"),
+    ("expressionless", "😑"),
+    ("eyes", "👀"),
+    ("facepalm", "🤦"),
+    ("facepunch", "👊"),
+    ("fallen_leaf", "🍂"),
+    ("file_folder", "📁"),
+    ("fire", "🔥"),
+    ("fish", "🐟"),
+    ("fist_oncoming", "👊"),
+    ("flashlight", "🔦"),
+    ("flushed", "😳"),
+    ("football", "🏈"),
+    ("four_leaf_clover", "🍀"),
+    ("fox_face", "🦊"),
+    ("free", "🆓"),
+    ("fries", "🍟"),
+    ("frog", "🐸"),
+    ("game_die", "🎲"),
+    ("gear", "⚙️"),
+    ("gem", "💎"),
+    ("ghost", "👻"),
+    ("gift", "🎁"),
+    ("globe_with_meridians", "🌐"),
+    ("grapes", "🍇"),
+    ("green_apple", "🍏"),
+    ("green_circle", "🟢"),
+    ("green_heart", "💚"),
+    ("grey_question", "❔"),
+    ("grimacing", "😬"),
+    ("grin", "😁"),
+    ("grinning", "😀"),
+    ("guitar", "🎸"),
+    ("hamburger", "🍔"),
+    ("hammer", "🔨"),
+    ("hammer_and_wrench", "🛠️"),
+    ("hand", "✋"),
+    ("handshake", "🤝"),
+    ("hankey", "💩"),
+    ("headphones", "🎧"),
+    ("hear_no_evil", "🙉"),
+    ("heart", "❤️"),
+    ("heart_eyes", "😍"),
+    ("heart_eyes_cat", "😻"),
+    ("heavy_check_mark", "✔️"),
+    ("heavy_exclamation_mark", "❗"),
+    ("heavy_minus_sign", "➖"),
+    ("heavy_plus_sign", "➕"),
+    ("honeybee", "🐝"),
+    ("hotdog", "🌭"),
+    ("hourglass", "⌛"),
+    ("hourglass_flowing_sand", "⏳"),
+    ("house", "🏠"),
+    ("hugs", "🤗"),
+    ("inbox_tray", "📥"),
+    ("innocent", "😇"),
+    ("interrobang", "⁉️"),
+    ("iphone", "📱"),
+    ("joy", "😂"),
+    ("key", "🔑"),
+    ("keyboard", "⌨️"),
+    ("kissing_heart", "😘"),
+    ("label", "🏷️"),
+    ("large_blue_circle", "🔵"),
+    ("laughing", "😆"),
+    ("link", "🔗"),
+    ("lion", "🦁"),
+    ("lock", "🔒"),
+    ("loudspeaker", "📢"),
+    ("mag", "🔍"),
+    ("man_shrugging", "🤷‍♂️"),
+    ("maple_leaf", "🍁"),
+    ("mask", "😷"),
+    ("medal_sports", "🏅"),
+    ("mega", "📣"),
+    ("memo", "📝"),
+    ("metal", "🤘"),
+    ("microphone", "🎤"),
+    ("microscope", "🔬"),
+    ("moneybag", "💰"),
+    ("monkey_face", "🐵"),
+    ("mountain", "⛰️"),
+    ("mouse", "🐭"),
+    ("muscle", "💪"),
+    ("musical_note", "🎵"),
+    ("negative_squared_cross_mark", "❎"),
+    ("nerd_face", "🤓"),
+    ("neutral_face", "😐"),
+    ("new", "🆕"),
+    ("no_bell", "🔕"),
+    ("no_entry", "⛔"),
+    ("no_entry_sign", "🚫"),
+    ("no_mouth", "😶"),
+    ("notes", "🎶"),
+    ("ocean", "🌊"),
+    ("octopus", "🐙"),
+    ("office", "🏢"),
+    ("ok", "🆗"),
+    ("ok_hand", "👌"),
+    ("open_book", "📖"),
+    ("open_file_folder", "📂"),
+    ("open_hands", "👐"),
+    ("open_mouth", "😮"),
+    ("orange_heart", "🧡"),
+    ("outbox_tray", "📤"),
+    ("package", "📦"),
+    ("page_facing_up", "📄"),
+    ("panda_face", "🐼"),
+    ("paperclip", "📎"),
+    ("partying_face", "🥳"),
+    ("peach", "🍑"),
+    ("pencil", "📝"),
+    ("pencil2", "✏️"),
+    ("penguin", "🐧"),
+    ("pensive", "😔"),
+    ("pig", "🐷"),
+    ("pill", "💊"),
+    ("pizza", "🍕"),
+    ("pleading_face", "🥺"),
+    ("point_down", "👇"),
+    ("point_left", "👈"),
+    ("point_right", "👉"),
+    ("point_up_2", "👆"),
+    ("poop", "💩"),
+    ("popcorn", "🍿"),
+    ("pray", "🙏"),
+    ("punch", "👊"),
+    ("purple_heart", "💜"),
+    ("pushpin", "📌"),
+    ("question", "❓"),
+    ("rage", "😡"),
+    ("rainbow", "🌈"),
+    ("raised_hand", "✋"),
+    ("raised_hands", "🙌"),
+    ("ramen", "🍜"),
+    ("recycle", "♻️"),
+    ("red_car", "🚗"),
+    ("red_circle", "🔴"),
+    ("relieved", "😌"),
+    ("robot", "🤖"),
+    ("rocket", "🚀"),
+    ("rofl", "🤣"),
+    ("roll_eyes", "🙄"),
+    ("rose", "🌹"),
+    ("rotating_light", "🚨"),
+    ("satisfied", "😆"),
+    ("scissors", "✂️"),
+    ("scream", "😱"),
+    ("see_no_evil", "🙈"),
+    ("seedling", "🌱"),
+    ("shield", "🛡️"),
+    ("ship", "🚢"),
+    ("shrug", "🤷"),
+    ("shushing_face", "🤫"),
+    ("skull", "💀"),
+    ("sleeping", "😴"),
+    ("sleepy", "😪"),
+    ("slightly_frowning_face", "🙁"),
+    ("slightly_smiling_face", "🙂"),
+    ("smile", "😄"),
+    ("smiley", "😃"),
+    ("smiley_cat", "😺"),
+    ("smiling_face_with_three_hearts", "🥰"),
+    ("smirk", "😏"),
+    ("snail", "🐌"),
+    ("snake", "🐍"),
+    ("snowflake", "❄️"),
+    ("sob", "😭"),
+    ("soccer", "⚽"),
+    ("sos", "🆘"),
+    ("sparkles", "✨"),
+    ("sparkling_heart", "💖"),
+    ("speak_no_evil", "🙊"),
+    ("speech_balloon", "💬"),
+    ("star", "⭐"),
+    ("star2", "🌟"),
+    ("star_struck", "🤩"),
+    ("stopwatch", "⏱️"),
+    ("strawberry", "🍓"),
+    ("stuck_out_tongue", "😛"),
+    ("stuck_out_tongue_winking_eye", "😜"),
+    ("sunflower", "🌻"),
+    ("sunglasses", "😎"),
+    ("sunny", "☀️"),
+    ("sushi", "🍣"),
+    ("sweat", "😓"),
+    ("sweat_smile", "😅"),
+    ("taco", "🌮"),
+    ("tada", "🎉"),
+    ("taxi", "🚕"),
+    ("tea", "🍵"),
+    ("telescope", "🔭"),
+    ("tennis", "🎾"),
+    ("test_tube", "🧪"),
+    ("thinking", "🤔"),
+    ("thought_balloon", "💭"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("tiger", "🐯"),
+    ("tired_face", "😫"),
+    ("triangular_flag_on_post", "🚩"),
+    ("triumph", "😤"),
+    ("trophy", "🏆"),
+    ("tulip", "🌷"),
+    ("turtle", "🐢"),
+    ("tv", "📺"),
+    ("two_hearts", "💕"),
+    ("umbrella", "☔"),
+    ("unicorn", "🦄"),
+    ("unlock", "🔓"),
+    ("up", "🆙"),
+    ("upside_down_face", "🙃"),
+    ("v", "✌️"),
+    ("video_game", "🎮"),
+    ("warning", "⚠️"),
+    ("wastebasket", "🗑️"),
+    ("watch", "⌚"),
+    ("watermelon", "🍉"),
+    ("wave", "👋"),
+    ("weary", "😩"),
+    ("whale", "🐳"),
+    ("white_check_mark", "✅"),
+    ("white_circle", "⚪"),
+    ("wine_glass", "🍷"),
+    ("wink", "😉"),
+    ("woman_shrugging", "🤷‍♀️"),
+    ("worried", "😟"),
+    ("wrench", "🔧"),
+    ("x", "❌"),
+    ("yawning_face", "🥱"),
+    ("yellow_circle", "🟡"),
+    ("yellow_heart", "💛"),
+    ("yum", "😋"),
+    ("zany_face", "🤪"),
+    ("zap", "⚡"),
+    ("zzz", "💤"),
+];
+
+/// Returns the emoji of the shortcode `name` without the colons, e.g.: `rocket`.
+pub fn shortcode_emoji(name: &str) -> Option<&'static str> {
+    SHORTCODES
+        .binary_search_by_key(&name, |(code, _)| *code)
+        .ok()
+        .map(|ix| SHORTCODES[ix].1)
+}
+
+/// Returns the shortcodes of the `emoji`, e.g.: `rocket` for `🚀`.
+pub(crate) fn emoji_shortcodes(emoji: &str) -> impl Iterator<Item = &'static str> + '_ {
+    SHORTCODES
+        .iter()
+        .filter(move |(_, e)| *e == emoji)
+        .map(|(code, _)| *code)
+}
+
+/// A custom emoji of the app, e.g.: the emojis of the workspace in a chat app.
+#[derive(Clone)]
+pub struct CustomEmoji {
+    /// The name without the colons, the emoji is inserted as `:shortcode:`.
+    pub shortcode: SharedString,
+    pub image: ImageSource,
+}
+
+impl CustomEmoji {
+    pub fn new(shortcode: impl Into<SharedString>, image: impl Into<ImageSource>) -> Self {
+        Self {
+            shortcode: shortcode.into(),
+            image: image.into(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct EmojiRegistry {
+    custom: Vec<CustomEmoji>,
+}
+
+impl Global for EmojiRegistry {}
+
+/// Register the custom emojis, the emoji with the same shortcode is replaced.
+pub fn register_custom_emojis(emojis: impl IntoIterator<Item = CustomEmoji>, cx: &mut App) {
+    let registry = cx.default_global::<EmojiRegistry>();
+    for emoji in emojis {
+        registry.custom.retain(|e| e.shortcode != emoji.shortcode);
+        registry.custom.push(emoji);
+    }
+}
+
+/// Returns the registered custom emojis.
+pub fn custom_emojis(cx: &App) -> Vec<CustomEmoji> {
+    cx.try_global::<EmojiRegistry>()
+        .map(|registry| registry.custom.clone())
+        .unwrap_or_default()
+}
+
+/// Returns the registered custom emoji of the `shortcode` without the colons.
+pub fn custom_emoji(shortcode: &str, cx: &App) -> Option<CustomEmoji> {
+    cx.try_global::<EmojiRegistry>()?
+        .custom
+        .iter()
+        .find(|emoji| emoji.shortcode == shortcode)
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{emoji_shortcodes, shortcode_emoji, SHORTCODES};
+
+    #[test]
+    fn test_shortcodes_sorted() {
+        assert!(SHORTCODES.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_shortcode_emoji() {
+        assert_eq!(shortcode_emoji("rocket"), Some("🚀"));
+        assert_eq!(shortcode_emoji("+1"), Some("👍"));
+        assert_eq!(shortcode_emoji("not_an_emoji"), None);
+
+        let codes = emoji_shortcodes("💥").collect::<Vec<_>>();
+        assert_eq!(codes, vec!["boom", "collision"]);
+    }
+}
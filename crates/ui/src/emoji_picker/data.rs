@@ -0,0 +1,402 @@
+/// A built-in emoji with the keywords to search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct EmojiData {
+    pub(super) emoji: &'static str,
+    pub(super) keywords: &'static str,
+    /// The emoji supports the skin tone modifiers.
+    pub(super) skin_tone: bool,
+}
+
+const fn e(emoji: &'static str, keywords: &'static str) -> EmojiData {
+    EmojiData {
+        emoji,
+        keywords,
+        skin_tone: false,
+    }
+}
+
+/// The emoji supports the skin tones.
+const fn t(emoji: &'static str, keywords: &'static str) -> EmojiData {
+    EmojiData {
+        emoji,
+        keywords,
+        skin_tone: true,
+    }
+}
+
+pub(super) const SMILEYS: &[EmojiData] = &[
+    e("😀", "grinning smile happy"),
+    e("😃", "smiley happy joy"),
+    e("😄", "smile happy laugh"),
+    e("😁", "grin beaming"),
+    e("😆", "laughing satisfied"),
+    e("😅", "sweat smile relief"),
+    e("🤣", "rofl rolling laughing"),
+    e("😂", "joy tears laughing"),
+    e("🙂", "slightly smiling"),
+    e("🙃", "upside down silly"),
+    e("😉", "wink"),
+    e("😊", "blush smiling"),
+    e("😇", "innocent halo angel"),
+    e("🥰", "smiling hearts love"),
+    e("😍", "heart eyes love"),
+    e("🤩", "star struck excited"),
+    e("😘", "kiss blowing"),
+    e("😋", "yum delicious"),
+    e("😛", "tongue"),
+    e("😜", "wink tongue"),
+    e("🤪", "zany crazy"),
+    e("🤔", "thinking hmm"),
+    e("🤗", "hug hugging"),
+    e("🤫", "shush quiet secret"),
+    e("😐", "neutral meh"),
+    e("😑", "expressionless"),
+    e("😶", "no mouth silent"),
+    e("🙄", "eye roll"),
+    e("😏", "smirk"),
+    e("😬", "grimace awkward"),
+    e("😌", "relieved"),
+    e("😔", "pensive"),
+    e("😴", "sleeping tired"),
+    e("😷", "mask sick"),
+    e("🤒", "thermometer sick ill"),
+    e("🤯", "exploding head mind blown"),
+    e("🥳", "party celebrate"),
+    e("😎", "sunglasses cool"),
+    e("🤓", "nerd geek"),
+    e("😕", "confused"),
+    e("😟", "worried"),
+    e("😮", "open mouth surprised wow"),
+    e("😳", "flushed embarrassed"),
+    e("🥺", "pleading puppy eyes"),
+    e("😢", "cry sad tear"),
+    e("😭", "sob crying"),
+    e("😱", "scream fear"),
+    e("😡", "angry rage pout"),
+    e("🤬", "cursing swearing"),
+    e("💩", "poop"),
+    e("👻", "ghost"),
+    e("🤖", "robot bot"),
+];
+
+pub(super) const PEOPLE: &[EmojiData] = &[
+    t("👋", "wave hello hi bye hand"),
+    t("🤚", "raised back hand"),
+    t("✋", "raised hand high five stop"),
+    t("👌", "ok perfect hand"),
+    t("✌️", "victory peace"),
+    t("🤞", "crossed fingers luck"),
+    t("🤘", "rock horns metal"),
+    t("👍", "thumbs up like yes +1"),
+    t("👎", "thumbs down dislike no -1"),
+    t("👏", "clap applause"),
+    t("🙌", "raising hands hooray"),
+    t("🙏", "pray please thanks"),
+    t("💪", "muscle strong flex"),
+    t("✍️", "writing hand"),
+    t("👉", "point right"),
+    t("👈", "point left"),
+    t("👆", "point up"),
+    t("👇", "point down"),
+    e("👀", "eyes look"),
+    e("🧠", "brain smart"),
+    t("👶", "baby child"),
+    t("👦", "boy child"),
+    t("👧", "girl child"),
+    t("🧑", "person adult"),
+    t("👨", "man"),
+    t("👩", "woman"),
+    t("🧓", "older person"),
+    t("👮", "police officer cop"),
+    t("🧑‍💻", "technologist developer coder programmer"),
+    t("🧑‍🎨", "artist painter"),
+    t("🧑‍🚀", "astronaut"),
+    t("🤷", "shrug whatever"),
+    t("🤦", "facepalm"),
+    t("🙋", "raising hand question"),
+    t("🙇", "bow sorry"),
+    t("🏃", "running run"),
+    t("💃", "dancing dancer"),
+    e("👪", "family"),
+];
+
+pub(super) const ANIMALS: &[EmojiData] = &[
+    e("🐶", "dog puppy"),
+    e("🐱", "cat kitten"),
+    e("🐭", "mouse"),
+    e("🐹", "hamster"),
+    e("🐰", "rabbit bunny"),
+    e("🦊", "fox"),
+    e("🐻", "bear"),
+    e("🐼", "panda"),
+    e("🐨", "koala"),
+    e("🐯", "tiger"),
+    e("🦁", "lion"),
+    e("🐮", "cow"),
+    e("🐷", "pig"),
+    e("🐸", "frog"),
+    e("🐵", "monkey"),
+    e("🐔", "chicken"),
+    e("🐧", "penguin"),
+    e("🐦", "bird"),
+    e("🦆", "duck"),
+    e("🦉", "owl"),
+    e("🐺", "wolf"),
+    e("🐴", "horse"),
+    e("🦄", "unicorn"),
+    e("🐝", "bee honeybee"),
+    e("🦋", "butterfly"),
+    e("🐢", "turtle slow"),
+    e("🐍", "snake"),
+    e("🐙", "octopus"),
+    e("🐬", "dolphin"),
+    e("🐳", "whale"),
+    e("🦀", "crab rust"),
+    e("🌸", "cherry blossom flower"),
+    e("🌹", "rose flower"),
+    e("🌻", "sunflower"),
+    e("🌲", "evergreen tree"),
+    e("🌵", "cactus"),
+    e("🍀", "clover luck"),
+    e("🍁", "maple leaf autumn"),
+    e("🌞", "sun"),
+    e("🌙", "moon night"),
+    e("⭐", "star"),
+    e("🔥", "fire hot lit"),
+    e("🌈", "rainbow"),
+    e("❄️", "snowflake cold winter"),
+    e("💧", "droplet water"),
+];
+
+pub(super) const FOOD: &[EmojiData] = &[
+    e("🍏", "green apple"),
+    e("🍎", "red apple"),
+    e("🍐", "pear"),
+    e("🍊", "orange tangerine"),
+    e("🍋", "lemon"),
+    e("🍌", "banana"),
+    e("🍉", "watermelon"),
+    e("🍇", "grapes"),
+    e("🍓", "strawberry"),
+    e("🍒", "cherries"),
+    e("🍑", "peach"),
+    e("🥭", "mango"),
+    e("🍍", "pineapple"),
+    e("🥥", "coconut"),
+    e("🥑", "avocado"),
+    e("🍅", "tomato"),
+    e("🥕", "carrot"),
+    e("🌽", "corn"),
+    e("🌶️", "hot pepper chili"),
+    e("🥦", "broccoli"),
+    e("🍞", "bread"),
+    e("🧀", "cheese"),
+    e("🍳", "egg cooking"),
+    e("🥓", "bacon"),
+    e("🍔", "hamburger burger"),
+    e("🍟", "fries"),
+    e("🍕", "pizza"),
+    e("🌭", "hot dog"),
+    e("🌮", "taco"),
+    e("🍣", "sushi"),
+    e("🍜", "ramen noodles"),
+    e("🍚", "rice"),
+    e("🍦", "ice cream"),
+    e("🍩", "doughnut donut"),
+    e("🍪", "cookie"),
+    e("🎂", "birthday cake"),
+    e("🍫", "chocolate"),
+    e("🍿", "popcorn"),
+    e("☕", "coffee hot"),
+    e("🍵", "tea"),
+    e("🍺", "beer"),
+    e("🍷", "wine"),
+    e("🥤", "cup drink soda"),
+];
+
+pub(super) const ACTIVITIES: &[EmojiData] = &[
+    e("⚽", "soccer football"),
+    e("🏀", "basketball"),
+    e("🏈", "american football"),
+    e("⚾", "baseball"),
+    e("🎾", "tennis"),
+    e("🏐", "volleyball"),
+    e("🏓", "ping pong table tennis"),
+    e("🏸", "badminton"),
+    e("🥊", "boxing glove"),
+    e("⛳", "golf"),
+    e("🎣", "fishing"),
+    e("🎿", "ski"),
+    e("🏆", "trophy win"),
+    e("🥇", "gold medal first"),
+    e("🎮", "video game controller"),
+    e("🎲", "dice game"),
+    e("🧩", "puzzle piece"),
+    e("♟️", "chess pawn"),
+    e("🎯", "target dart bullseye"),
+    e("🎨", "art palette paint"),
+    e("🎬", "clapper movie film"),
+    e("🎤", "microphone sing karaoke"),
+    e("🎧", "headphones music"),
+    e("🎸", "guitar"),
+    e("🎹", "piano keyboard"),
+    e("🥁", "drum"),
+    e("🎉", "party popper tada celebrate"),
+    e("🎁", "gift present"),
+    e("🎈", "balloon"),
+    e("🎃", "pumpkin halloween"),
+    e("🎄", "christmas tree"),
+];
+
+pub(super) const TRAVEL: &[EmojiData] = &[
+    e("🚗", "car automobile"),
+    e("🚕", "taxi"),
+    e("🚌", "bus"),
+    e("🚑", "ambulance"),
+    e("🚒", "fire engine"),
+    e("🚓", "police car"),
+    e("🚲", "bicycle bike"),
+    e("🛵", "scooter"),
+    e("🏍️", "motorcycle"),
+    e("🚂", "locomotive train"),
+    e("🚄", "high speed train"),
+    e("✈️", "airplane plane flight"),
+    e("🚀", "rocket launch ship"),
+    e("🛸", "ufo flying saucer"),
+    e("🚁", "helicopter"),
+    e("⛵", "sailboat"),
+    e("🚢", "ship"),
+    e("⚓", "anchor"),
+    e("⛽", "fuel gas station"),
+    e("🚦", "traffic light"),
+    e("🗺️", "world map"),
+    e("🗽", "statue of liberty"),
+    e("🗼", "tokyo tower"),
+    e("🏰", "castle"),
+    e("🏠", "house home"),
+    e("🏢", "office building"),
+    e("🏥", "hospital"),
+    e("🏫", "school"),
+    e("⛺", "tent camping"),
+    e("🌋", "volcano"),
+    e("🏖️", "beach umbrella"),
+    e("🌃", "night city"),
+    e("🌍", "earth globe world"),
+];
+
+pub(super) const OBJECTS: &[EmojiData] = &[
+    e("⌚", "watch time"),
+    e("📱", "mobile phone"),
+    e("💻", "laptop computer"),
+    e("⌨️", "keyboard"),
+    e("🖥️", "desktop computer"),
+    e("🖨️", "printer"),
+    e("🖱️", "computer mouse"),
+    e("💾", "floppy disk save"),
+    e("📷", "camera photo"),
+    e("🎥", "movie camera video"),
+    e("📺", "television tv"),
+    e("🔋", "battery"),
+    e("🔌", "electric plug"),
+    e("💡", "light bulb idea"),
+    e("🔦", "flashlight"),
+    e("📚", "books library"),
+    e("📖", "open book read"),
+    e("📝", "memo note write"),
+    e("✏️", "pencil"),
+    e("🖊️", "pen"),
+    e("📎", "paperclip attachment"),
+    e("📌", "pushpin pin"),
+    e("📁", "folder directory"),
+    e("📅", "calendar date"),
+    e("📊", "bar chart"),
+    e("📈", "chart increasing up"),
+    e("🔒", "locked lock"),
+    e("🔑", "key"),
+    e("🔨", "hammer"),
+    e("🔧", "wrench tool"),
+    e("⚙️", "gear settings"),
+    e("🧪", "test tube"),
+    e("🔍", "magnifying glass search"),
+    e("💰", "money bag"),
+    e("💳", "credit card"),
+    e("✉️", "envelope email mail"),
+    e("📦", "package box"),
+    e("🗑️", "wastebasket trash delete"),
+    e("⏰", "alarm clock"),
+    e("⏳", "hourglass"),
+];
+
+pub(super) const SYMBOLS: &[EmojiData] = &[
+    e("❤️", "red heart love"),
+    e("🧡", "orange heart"),
+    e("💛", "yellow heart"),
+    e("💚", "green heart"),
+    e("💙", "blue heart"),
+    e("💜", "purple heart"),
+    e("🖤", "black heart"),
+    e("🤍", "white heart"),
+    e("💔", "broken heart"),
+    e("💯", "hundred points perfect"),
+    e("✅", "check mark done yes"),
+    e("✔️", "check mark"),
+    e("❌", "cross mark no wrong"),
+    e("➕", "plus add"),
+    e("➖", "minus"),
+    e("❓", "question mark"),
+    e("❗", "exclamation mark"),
+    e("⚠️", "warning"),
+    e("🚫", "prohibited forbidden"),
+    e("⛔", "no entry"),
+    e("♻️", "recycle"),
+    e("⭕", "circle"),
+    e("🔴", "red circle"),
+    e("🟢", "green circle"),
+    e("🔵", "blue circle"),
+    e("⬆️", "up arrow"),
+    e("⬇️", "down arrow"),
+    e("➡️", "right arrow"),
+    e("⬅️", "left arrow"),
+    e("🔄", "counterclockwise arrows refresh repeat"),
+    e("▶️", "play button"),
+    e("⏸️", "pause button"),
+    e("🔔", "bell notification"),
+    e("🔕", "bell mute"),
+    e("💤", "zzz sleep"),
+    e("💬", "speech bubble comment"),
+    e("💭", "thought bubble"),
+    e("✨", "sparkles"),
+    e("⚡", "high voltage lightning zap"),
+    e("🆕", "new button"),
+    e("🆗", "ok button"),
+    e("©️", "copyright"),
+    e("™️", "trade mark"),
+];
+
+pub(super) const FLAGS: &[EmojiData] = &[
+    e("🏁", "checkered flag finish"),
+    e("🚩", "red flag"),
+    e("🏳️", "white flag"),
+    e("🏴", "black flag"),
+    e("🏳️‍🌈", "rainbow flag pride"),
+    e("🇺🇸", "united states america us"),
+    e("🇬🇧", "united kingdom britain uk gb"),
+    e("🇨🇳", "china cn"),
+    e("🇭🇰", "hong kong hk"),
+    e("🇹🇼", "taiwan tw"),
+    e("🇯🇵", "japan jp"),
+    e("🇰🇷", "south korea kr"),
+    e("🇸🇬", "singapore sg"),
+    e("🇮🇳", "india in"),
+    e("🇩🇪", "germany de"),
+    e("🇫🇷", "france fr"),
+    e("🇮🇹", "italy it"),
+    e("🇪🇸", "spain es"),
+    e("🇷🇺", "russia ru"),
+    e("🇨🇦", "canada ca"),
+    e("🇲🇽", "mexico mx"),
+    e("🇧🇷", "brazil br"),
+    e("🇦🇺", "australia au"),
+    e("🇪🇺", "european union eu"),
+    e("🇺🇳", "united nations un"),
+];
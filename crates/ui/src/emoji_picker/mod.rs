@@ -0,0 +1,640 @@
+//! A popover to pick an emoji, with the category tabs, the keyword search, the skin tones,
+//! the recently used emojis and the custom emojis.
+//!
+//! ```ignore
+//! EmojiPicker::for_input("emoji", &self.input).custom_emojis(|_| {
+//!     vec![CustomEmoji::new("party-parrot", "https://example.com/parrot.gif")]
+//! })
+//! ```
+//!
+//! The built-in emojis are searched by the shortcodes of the [`crate::emoji`] registry too,
+//! and the custom emojis registered by [`crate::emoji::register_custom_emojis`] are shown
+//! in all the pickers.
+mod data;
+
+use std::{collections::VecDeque, rc::Rc};
+
+use gpui::{
+    div, img, prelude::FluentBuilder as _, px, App, AppContext as _, Context, Corner, DismissEvent,
+    ElementId, Entity, EventEmitter, FocusHandle, Focusable, Global, InteractiveElement as _,
+    IntoElement, ParentElement as _, Render, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, Styled as _, Subscription, Window,
+};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    emoji::{self, emoji_shortcodes},
+    h_flex,
+    i18n::t,
+    input::{InputEvent, InputState, TextInput},
+    popover::Popover,
+    v_flex, ActiveTheme as _, Icon, IconName, Selectable as _, Sizable, Size,
+};
+
+use self::data::EmojiData;
+pub use crate::emoji::CustomEmoji;
+
+/// The max number of the recently used emojis.
+const MAX_RECENT: usize = 32;
+
+/// The categories of the emojis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmojiCategory {
+    Recent,
+    Smileys,
+    People,
+    Animals,
+    Food,
+    Activities,
+    Travel,
+    Objects,
+    Symbols,
+    Flags,
+    Custom,
+}
+
+impl EmojiCategory {
+    const BUILTIN: [Self; 9] = [
+        Self::Smileys,
+        Self::People,
+        Self::Animals,
+        Self::Food,
+        Self::Activities,
+        Self::Travel,
+        Self::Objects,
+        Self::Symbols,
+        Self::Flags,
+    ];
+
+    /// Returns the name of the category.
+    pub fn label(&self) -> SharedString {
+        match self {
+            Self::Recent => t!("EmojiPicker.recent"),
+            Self::Smileys => t!("EmojiPicker.smileys"),
+            Self::People => t!("EmojiPicker.people"),
+            Self::Animals => t!("EmojiPicker.animals"),
+            Self::Food => t!("EmojiPicker.food"),
+            Self::Activities => t!("EmojiPicker.activities"),
+            Self::Travel => t!("EmojiPicker.travel"),
+            Self::Objects => t!("EmojiPicker.objects"),
+            Self::Symbols => t!("EmojiPicker.symbols"),
+            Self::Flags => t!("EmojiPicker.flags"),
+            Self::Custom => t!("EmojiPicker.custom"),
+        }
+        .into()
+    }
+
+    /// The emoji shown in the tab of the category.
+    fn tab(&self) -> &'static str {
+        match self {
+            Self::Recent => "🕘",
+            Self::Smileys => "😀",
+            Self::People => "👋",
+            Self::Animals => "🐶",
+            Self::Food => "🍔",
+            Self::Activities => "⚽",
+            Self::Travel => "🚗",
+            Self::Objects => "💡",
+            Self::Symbols => "❤️",
+            Self::Flags => "🏁",
+            Self::Custom => "⭐",
+        }
+    }
+
+    fn emojis(&self) -> &'static [EmojiData] {
+        match self {
+            Self::Smileys => data::SMILEYS,
+            Self::People => data::PEOPLE,
+            Self::Animals => data::ANIMALS,
+            Self::Food => data::FOOD,
+            Self::Activities => data::ACTIVITIES,
+            Self::Travel => data::TRAVEL,
+            Self::Objects => data::OBJECTS,
+            Self::Symbols => data::SYMBOLS,
+            Self::Flags => data::FLAGS,
+            Self::Recent | Self::Custom => &[],
+        }
+    }
+}
+
+/// The skin tones of the people and hand emojis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SkinTone {
+    #[default]
+    Default,
+    Light,
+    MediumLight,
+    Medium,
+    MediumDark,
+    Dark,
+}
+
+impl SkinTone {
+    pub const ALL: [Self; 6] = [
+        Self::Default,
+        Self::Light,
+        Self::MediumLight,
+        Self::Medium,
+        Self::MediumDark,
+        Self::Dark,
+    ];
+
+    fn modifier(&self) -> Option<char> {
+        match self {
+            Self::Default => None,
+            Self::Light => Some('\u{1F3FB}'),
+            Self::MediumLight => Some('\u{1F3FC}'),
+            Self::Medium => Some('\u{1F3FD}'),
+            Self::MediumDark => Some('\u{1F3FE}'),
+            Self::Dark => Some('\u{1F3FF}'),
+        }
+    }
+
+    /// Apply the tone to the emoji, the modifier is inserted after the base and replaces the
+    /// variation selector, e.g.: `✌️` to `✌🏽`.
+    pub fn apply(&self, emoji: &str) -> String {
+        let Some(modifier) = self.modifier() else {
+            return emoji.to_string();
+        };
+        let mut chars = emoji.chars();
+        let Some(base) = chars.next() else {
+            return emoji.to_string();
+        };
+
+        let rest = chars.as_str().trim_start_matches('\u{FE0F}');
+        format!("{}{}{}", base, modifier, rest)
+    }
+
+    fn next(&self) -> Self {
+        let ix = Self::ALL.iter().position(|tone| tone == self).unwrap_or(0);
+        Self::ALL[(ix + 1) % Self::ALL.len()]
+    }
+}
+
+/// The picked emoji.
+#[derive(Clone)]
+pub enum Emoji {
+    /// The emoji with the skin tone applied.
+    Unicode(SharedString),
+    Custom(CustomEmoji),
+}
+
+impl Emoji {
+    /// Returns the text to insert, the custom emoji is `:shortcode:`.
+    pub fn text(&self) -> SharedString {
+        match self {
+            Self::Unicode(emoji) => emoji.clone(),
+            Self::Custom(emoji) => format!(":{}:", emoji.shortcode).into(),
+        }
+    }
+}
+
+impl PartialEq for Emoji {
+    fn eq(&self, other: &Self) -> bool {
+        self.text() == other.text()
+    }
+}
+
+type CustomEmojis = Rc<dyn Fn(&App) -> Vec<CustomEmoji>>;
+
+/// The recently used emojis and the skin tone, shared by the pickers.
+#[derive(Default)]
+struct EmojiHistory {
+    /// From the newest to the oldest.
+    recent: VecDeque<Emoji>,
+    skin_tone: SkinTone,
+}
+
+impl Global for EmojiHistory {}
+
+impl EmojiHistory {
+    /// Push the emoji to the front, the same emoji is moved to the front.
+    fn push(&mut self, emoji: Emoji) {
+        self.recent.retain(|e| *e != emoji);
+        self.recent.push_front(emoji);
+        self.recent.truncate(MAX_RECENT);
+    }
+}
+
+/// Returns the recently used emojis, from the newest to the oldest.
+pub fn recent_emojis(cx: &App) -> Vec<Emoji> {
+    cx.try_global::<EmojiHistory>()
+        .map(|history| history.recent.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Returns the built-in emojis matching all the words of the `query` by the prefixes of
+/// the keywords or the shortcodes.
+fn search(query: &str) -> Vec<&'static EmojiData> {
+    let query = query.to_lowercase();
+    let words = query.split_whitespace().collect::<Vec<_>>();
+    if words.is_empty() {
+        return vec![];
+    }
+
+    EmojiCategory::BUILTIN
+        .iter()
+        .flat_map(|category| category.emojis())
+        .filter(|data| {
+            words.iter().all(|word| {
+                data.keywords
+                    .split_whitespace()
+                    .chain(emoji_shortcodes(data.emoji))
+                    .any(|keyword| keyword.starts_with(word))
+            })
+        })
+        .collect()
+}
+
+/// The events of the [`EmojiPickerState`].
+#[derive(Clone)]
+pub enum EmojiPickerEvent {
+    Select(Emoji),
+}
+
+/// The panel of the [`EmojiPicker`], it can be embedded in the views, e.g.: the chat composer.
+pub struct EmojiPickerState {
+    search: Entity<InputState>,
+    query: SharedString,
+    category: EmojiCategory,
+    custom_emojis: Option<CustomEmojis>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<EmojiPickerEvent> for EmojiPickerState {}
+impl EventEmitter<DismissEvent> for EmojiPickerState {}
+
+impl EmojiPickerState {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let search = cx.new(|cx| {
+            InputState::new(window, cx).placeholder(t!("EmojiPicker.search_placeholder"))
+        });
+        let _subscriptions = vec![cx.subscribe(&search, |this, _, event: &InputEvent, cx| {
+            if let InputEvent::Change(query) = event {
+                this.query = query.trim().to_string().into();
+                cx.notify();
+            }
+        })];
+        let category = if recent_emojis(cx).is_empty() {
+            EmojiCategory::Smileys
+        } else {
+            EmojiCategory::Recent
+        };
+
+        Self {
+            search,
+            query: SharedString::default(),
+            category,
+            custom_emojis: None,
+            _subscriptions,
+        }
+    }
+
+    /// Set the provider of the custom emojis, they are shown in the custom category after the
+    /// registered custom emojis (see [`crate::emoji::register_custom_emojis`]).
+    pub fn custom_emojis(mut self, provider: impl Fn(&App) -> Vec<CustomEmoji> + 'static) -> Self {
+        self.custom_emojis = Some(Rc::new(provider));
+        self
+    }
+
+    /// Returns the skin tone of the emojis, it is shared by the pickers.
+    pub fn skin_tone(cx: &App) -> SkinTone {
+        cx.try_global::<EmojiHistory>()
+            .map(|history| history.skin_tone)
+            .unwrap_or_default()
+    }
+
+    /// Set the skin tone of the emojis.
+    pub fn set_skin_tone(skin_tone: SkinTone, cx: &mut App) {
+        cx.default_global::<EmojiHistory>().skin_tone = skin_tone;
+    }
+
+    fn custom_emojis_list(&self, cx: &App) -> Vec<CustomEmoji> {
+        let mut emojis = emoji::custom_emojis(cx);
+        if let Some(provider) = self.custom_emojis.as_ref() {
+            for emoji in provider(cx) {
+                if !emojis.iter().any(|e| e.shortcode == emoji.shortcode) {
+                    emojis.push(emoji);
+                }
+            }
+        }
+        emojis
+    }
+
+    fn select(&mut self, emoji: Emoji, cx: &mut Context<Self>) {
+        cx.default_global::<EmojiHistory>().push(emoji.clone());
+        cx.emit(EmojiPickerEvent::Select(emoji));
+        cx.emit(DismissEvent);
+    }
+
+    fn set_category(
+        &mut self,
+        category: EmojiCategory,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.category = category;
+        if !self.query.is_empty() {
+            self.search
+                .update(cx, |search, cx| search.set_value("", window, cx));
+            self.query = SharedString::default();
+        }
+        cx.notify();
+    }
+
+    /// Returns the emojis of the search or the current category.
+    fn emojis(&self, cx: &App) -> Vec<Emoji> {
+        let skin_tone = Self::skin_tone(cx);
+        let unicode = |data: &EmojiData| {
+            let emoji = match data.skin_tone {
+                true => skin_tone.apply(data.emoji),
+                false => data.emoji.to_string(),
+            };
+            Emoji::Unicode(emoji.into())
+        };
+
+        if !self.query.is_empty() {
+            let query = self.query.to_lowercase();
+            let custom = self
+                .custom_emojis_list(cx)
+                .into_iter()
+                .filter(|emoji| emoji.shortcode.to_lowercase().contains(&query))
+                .map(Emoji::Custom);
+            return search(&query)
+                .into_iter()
+                .map(unicode)
+                .chain(custom)
+                .collect();
+        }
+
+        match self.category {
+            EmojiCategory::Recent => recent_emojis(cx),
+            EmojiCategory::Custom => self
+                .custom_emojis_list(cx)
+                .into_iter()
+                .map(Emoji::Custom)
+                .collect(),
+            category => category.emojis().iter().map(unicode).collect(),
+        }
+    }
+
+    fn render_tabs(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut categories = vec![];
+        if !recent_emojis(cx).is_empty() {
+            categories.push(EmojiCategory::Recent);
+        }
+        categories.extend(EmojiCategory::BUILTIN);
+        if !self.custom_emojis_list(cx).is_empty() {
+            categories.push(EmojiCategory::Custom);
+        }
+
+        h_flex()
+            .gap_0p5()
+            .children(categories.into_iter().map(|category| {
+                Button::new(SharedString::from(format!("emoji-tab-{:?}", category)))
+                    .ghost()
+                    .xsmall()
+                    .label(category.tab())
+                    .selected(self.query.is_empty() && self.category == category)
+                    .tooltip(category.label())
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.set_category(category, window, cx)
+                    }))
+            }))
+    }
+}
+
+impl Focusable for EmojiPickerState {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.search.focus_handle(cx)
+    }
+}
+
+impl Render for EmojiPickerState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let skin_tone = Self::skin_tone(cx);
+        let emojis = self.emojis(cx);
+        let title = if self.query.is_empty() {
+            self.category.label()
+        } else {
+            t!("EmojiPicker.results").into()
+        };
+
+        v_flex()
+            .w(px(320.))
+            .h(px(360.))
+            .gap_2()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        TextInput::new(&self.search)
+                            .small()
+                            .prefix(
+                                Icon::new(IconName::Search).text_color(cx.theme().muted_foreground),
+                            )
+                            .cleanable(),
+                    )
+                    .child(
+                        Button::new("skin-tone")
+                            .ghost()
+                            .small()
+                            .label(skin_tone.apply("✋"))
+                            .tooltip(t!("EmojiPicker.skin_tone"))
+                            .on_click(cx.listener(move |_, _, _, cx| {
+                                Self::set_skin_tone(skin_tone.next(), cx);
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(self.render_tabs(cx))
+            .child(
+                v_flex()
+                    .id("emojis")
+                    .flex_1()
+                    .min_h_0()
+                    .overflow_y_scroll()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(title),
+                    )
+                    .when(emojis.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .py_4()
+                                .text_sm()
+                                .text_center()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(t!("EmojiPicker.empty")),
+                        )
+                    })
+                    .child(
+                        h_flex()
+                            .flex_wrap()
+                            .children(emojis.into_iter().enumerate().map(|(ix, emoji)| {
+                                h_flex()
+                                    .id(ix)
+                                    .size_8()
+                                    .justify_center()
+                                    .rounded(cx.theme().radius)
+                                    .text_xl()
+                                    .cursor_pointer()
+                                    .hover(|this| this.bg(cx.theme().accent))
+                                    .map(|this| match &emoji {
+                                        Emoji::Unicode(text) => this.child(text.clone()),
+                                        Emoji::Custom(custom) => {
+                                            this.child(img(custom.image.clone()).size_6())
+                                        }
+                                    })
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.select(emoji.clone(), cx)
+                                    }))
+                            })),
+                    ),
+            )
+    }
+}
+
+/// A button to open the popover of the [`EmojiPickerState`].
+#[derive(IntoElement)]
+pub struct EmojiPicker {
+    id: ElementId,
+    anchor: Corner,
+    size: Size,
+    custom_emojis: Option<CustomEmojis>,
+    on_select: Option<Rc<dyn Fn(&Emoji, &mut Window, &mut App)>>,
+}
+
+impl EmojiPicker {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            anchor: Corner::TopLeft,
+            size: Size::XSmall,
+            custom_emojis: None,
+            on_select: None,
+        }
+    }
+
+    /// Create a picker to insert the picked emoji into the input.
+    pub fn for_input(id: impl Into<ElementId>, input: &Entity<InputState>) -> Self {
+        let input = input.clone();
+        Self::new(id).on_select(move |emoji, window, cx| {
+            input.update(cx, |input, cx| {
+                input.insert(emoji.text().to_string(), window, cx);
+                input.focus(window, cx);
+            });
+        })
+    }
+
+    /// Set the anchor corner of the popover, default is top left.
+    pub fn anchor(mut self, anchor: Corner) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Set the provider of the custom emojis, see [`EmojiPickerState::custom_emojis`].
+    pub fn custom_emojis(mut self, provider: impl Fn(&App) -> Vec<CustomEmoji> + 'static) -> Self {
+        self.custom_emojis = Some(Rc::new(provider));
+        self
+    }
+
+    /// Set the handler of the picked emoji.
+    pub fn on_select(
+        mut self,
+        on_select: impl Fn(&Emoji, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_select = Some(Rc::new(on_select));
+        self
+    }
+}
+
+impl Sizable for EmojiPicker {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl RenderOnce for EmojiPicker {
+    fn render(self, _: &mut Window, _: &mut App) -> impl IntoElement {
+        let custom_emojis = self.custom_emojis.clone();
+        let on_select = self.on_select.clone();
+
+        Popover::new(self.id)
+            .anchor(self.anchor)
+            .trigger(
+                Button::new("emoji-picker")
+                    .ghost()
+                    .with_size(self.size)
+                    .label("🙂"),
+            )
+            .content(move |window, cx| {
+                let state = cx.new(|cx| {
+                    let mut state = EmojiPickerState::new(window, cx);
+                    state.custom_emojis = custom_emojis.clone();
+                    state
+                });
+                if let Some(on_select) = on_select.clone() {
+                    window
+                        .subscribe(&state, cx, move |_, event, window, cx| {
+                            let EmojiPickerEvent::Select(emoji) = event;
+                            on_select(emoji, window, cx);
+                        })
+                        .detach();
+                }
+                state
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{search, Emoji, EmojiHistory, SkinTone, MAX_RECENT};
+
+    #[test]
+    fn test_skin_tone() {
+        assert_eq!(SkinTone::Default.apply("👍"), "👍");
+        assert_eq!(SkinTone::Medium.apply("👍"), "👍🏽");
+        // The variation selector is replaced.
+        assert_eq!(SkinTone::Dark.apply("✌️"), "✌🏿");
+        // The modifier is applied to the base of the ZWJ sequence.
+        assert_eq!(SkinTone::Light.apply("🧑‍💻"), "🧑🏻‍💻");
+        assert_eq!(SkinTone::Dark.next(), SkinTone::Default);
+    }
+
+    #[test]
+    fn test_search() {
+        let emojis = |query| {
+            search(query)
+                .into_iter()
+                .map(|data| data.emoji)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(emojis("thumbs up"), vec!["👍"]);
+        assert_eq!(emojis("PIZ"), vec!["🍕"]);
+        // Search by the shortcode.
+        assert_eq!(emojis("alarm_clock"), vec!["⏰"]);
+        assert!(emojis("heart").contains(&"❤️"));
+        assert!(emojis(" ").is_empty());
+        assert!(emojis("nothing-matches").is_empty());
+    }
+
+    #[test]
+    fn test_history() {
+        let mut history = EmojiHistory::default();
+        for ix in 0..MAX_RECENT + 1 {
+            history.push(Emoji::Unicode(ix.to_string().into()));
+        }
+        assert_eq!(history.recent.len(), MAX_RECENT);
+
+        // The same emoji is moved to the front.
+        history.push(Emoji::Unicode("5".into()));
+        assert_eq!(history.recent.len(), MAX_RECENT);
+        assert_eq!(history.recent[0].text(), "5");
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+};
+
+use anyhow::Result;
+use gpui::{
+    div, AnyElement, App, ElementId, IntoElement, ParentElement as _, RenderOnce, SharedString,
+    Styled as _, Window,
+};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    i18n::t,
+    v_flex, ActiveTheme as _, Icon, IconName, Sizable as _, StyledExt as _,
+};
+
+type ChildBuilder = Box<dyn Fn(&mut Window, &mut App) -> Result<AnyElement>>;
+
+/// A wrapper to catch the errors and the panics of building the child, and render a
+/// fallback card with the error message and a reload button instead.
+///
+/// Only the panics in the child builder are caught, the panics in the layout or paint
+/// of the child elements are not.
+///
+/// ```ignore
+/// ErrorBoundary::new("chart", |window, cx| Ok(render_chart(&data, window, cx)))
+///     .on_error(|error, _, _| tracing::error!("failed to render the chart: {}", error))
+/// ```
+#[derive(IntoElement)]
+pub struct ErrorBoundary {
+    id: ElementId,
+    builder: ChildBuilder,
+    on_error: Option<Rc<dyn Fn(&SharedString, &mut Window, &mut App)>>,
+}
+
+impl ErrorBoundary {
+    pub fn new<E: IntoElement>(
+        id: impl Into<ElementId>,
+        builder: impl Fn(&mut Window, &mut App) -> Result<E> + 'static,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            builder: Box::new(move |window, cx| {
+                builder(window, cx).map(|child| child.into_any_element())
+            }),
+            on_error: None,
+        }
+    }
+
+    /// Set the callback to report the error, it is called once when the error is caught.
+    pub fn on_error(
+        mut self,
+        on_error: impl Fn(&SharedString, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_error = Some(Rc::new(on_error));
+        self
+    }
+}
+
+/// Returns the message of the panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> SharedString {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        SharedString::from(message.to_string())
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        SharedString::from(message.clone())
+    } else {
+        "panic".into()
+    }
+}
+
+impl RenderOnce for ErrorBoundary {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let error_state = window.use_keyed_state(self.id.clone(), cx, |_, _| None::<SharedString>);
+
+        if error_state.read(cx).is_none() {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| (self.builder)(window, cx)));
+            let error = match result {
+                Ok(Ok(child)) => return child,
+                Ok(Err(err)) => SharedString::from(err.to_string()),
+                Err(payload) => panic_message(payload.as_ref()),
+            };
+
+            if let Some(on_error) = &self.on_error {
+                on_error(&error, window, cx);
+            }
+            error_state.update(cx, |state, _| *state = Some(error));
+        }
+
+        let error = error_state.read(cx).clone().unwrap_or_default();
+        v_flex()
+            .p_4()
+            .gap_3()
+            .items_start()
+            .rounded(cx.theme().radius_lg)
+            .border_1()
+            .border_color(cx.theme().danger.opacity(0.5))
+            .bg(cx.theme().danger.opacity(0.05))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .font_semibold()
+                    .text_color(cx.theme().danger)
+                    .child(Icon::new(IconName::TriangleAlert))
+                    .child(t!("ErrorBoundary.title")),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(error),
+            )
+            .child(
+                Button::new("reload")
+                    .label(t!("ErrorBoundary.reload"))
+                    .outline()
+                    .small()
+                    .on_click(move |_, window, cx| {
+                        error_state.update(cx, |state, _| *state = None);
+                        window.refresh();
+                    }),
+            )
+            .into_any_element()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::panic_message;
+
+    #[test]
+    fn test_panic_message() {
+        let payload = std::panic::catch_unwind(|| panic!("boom")).unwrap_err();
+        assert_eq!(&*panic_message(payload.as_ref()), "boom");
+
+        let payload = std::panic::catch_unwind(|| panic!("code: {}", 42)).unwrap_err();
+        assert_eq!(&*panic_message(payload.as_ref()), "code: 42");
+    }
+}
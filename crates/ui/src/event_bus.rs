@@ -0,0 +1,88 @@
+//! A typed global event bus, to communicate between the loosely coupled components
+//! (e.g.: a toolbar button and a dock panel) without passing the callbacks through the parents.
+//!
+//! The topic is the type of the event, the subscriptions are tied to the lifetime of the
+//! subscribed entity, they are removed after the entity is released.
+//!
+//! ```ignore
+//! struct OpenFile(PathBuf);
+//!
+//! event_bus::subscribe(&editor, cx, |editor, event: &OpenFile, window, cx| {
+//!     editor.open(&event.0, window, cx);
+//! });
+//!
+//! event_bus::publish(OpenFile(path), window, cx);
+//! ```
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use gpui::{App, Context, Entity, Global, Window};
+
+/// The handler of a subscriber, returns false if the subscribed entity is released.
+type Handler = Rc<dyn Fn(&dyn Any, &mut Window, &mut App) -> bool>;
+
+#[derive(Default)]
+struct EventBus {
+    next_id: usize,
+    subscribers: HashMap<TypeId, Vec<(usize, Handler)>>,
+}
+
+impl Global for EventBus {}
+
+/// Subscribe the `entity` to the events of type `E`.
+///
+/// The subscription holds a weak reference of the entity, it is removed after the entity is released.
+pub fn subscribe<T: 'static, E: 'static>(
+    entity: &Entity<T>,
+    cx: &mut App,
+    handler: impl Fn(&mut T, &E, &mut Window, &mut Context<T>) + 'static,
+) {
+    let entity = entity.downgrade();
+    let handler: Handler = Rc::new(move |event, window, cx| {
+        let Some(event) = event.downcast_ref::<E>() else {
+            return true;
+        };
+        entity
+            .update(cx, |this, cx| handler(this, event, window, cx))
+            .is_ok()
+    });
+
+    let bus = cx.default_global::<EventBus>();
+    let id = bus.next_id;
+    bus.next_id += 1;
+    bus.subscribers
+        .entry(TypeId::of::<E>())
+        .or_default()
+        .push((id, handler));
+}
+
+/// Publish the event to the subscribers of the event type `E`, in the subscribed order.
+pub fn publish<E: 'static>(event: E, window: &mut Window, cx: &mut App) {
+    let Some(subscribers) = cx
+        .try_global::<EventBus>()
+        .and_then(|bus| bus.subscribers.get(&TypeId::of::<E>()))
+        .cloned()
+    else {
+        return;
+    };
+
+    // The handlers may publish or subscribe the events, so call them without borrowing the bus.
+    let released = subscribers
+        .into_iter()
+        .filter(|(_, handler)| !handler(&event, window, cx))
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+
+    if !released.is_empty() {
+        if let Some(subscribers) = cx
+            .global_mut::<EventBus>()
+            .subscribers
+            .get_mut(&TypeId::of::<E>())
+        {
+            subscribers.retain(|(id, _)| !released.contains(id));
+        }
+    }
+}
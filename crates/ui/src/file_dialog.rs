@@ -0,0 +1,204 @@
+//! The async wrappers of the native file dialogs.
+//!
+//! ```ignore
+//! let task = FileDialog::new()
+//!     .filter("Images", ["png", "jpg"])
+//!     .multiple(true)
+//!     .open_file(cx);
+//!
+//! cx.spawn(async move |cx| {
+//!     if let Some(paths) = task.await? {
+//!         // ...
+//!     }
+//!     anyhow::Ok(())
+//! })
+//! .detach();
+//! ```
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use gpui::{App, PathPromptOptions, SharedString, Task};
+
+/// The file filter of the [`FileDialog`], matches the files by the extensions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileFilter {
+    pub name: SharedString,
+    /// The extensions without the leading dot, e.g.: `png`.
+    pub extensions: Vec<SharedString>,
+}
+
+impl FileFilter {
+    /// Returns true if the extension of the `path` matches the filter (case-insensitive).
+    pub fn matches(&self, path: &Path) -> bool {
+        let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+            return false;
+        };
+
+        self.extensions
+            .iter()
+            .any(|ext| ext.eq_ignore_ascii_case(extension))
+    }
+}
+
+/// The builder of the native file dialogs.
+///
+/// The native dialogs of GPUI do not support the filters, so the selected files are
+/// filtered after the dialog is closed.
+#[derive(Debug, Clone, Default)]
+pub struct FileDialog {
+    filters: Vec<FileFilter>,
+    directory: Option<PathBuf>,
+    multiple: bool,
+    prompt: Option<SharedString>,
+}
+
+impl FileDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file filter, the files match any of the filters are accepted.
+    pub fn filter(
+        mut self,
+        name: impl Into<SharedString>,
+        extensions: impl IntoIterator<Item = impl Into<SharedString>>,
+    ) -> Self {
+        self.filters.push(FileFilter {
+            name: name.into(),
+            extensions: extensions.into_iter().map(Into::into).collect(),
+        });
+        self
+    }
+
+    /// Set the default directory of the save dialog, default is the current directory.
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Set to allow selecting multiple files or folders, default is false.
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Set the label of the confirm button of the open dialog.
+    pub fn prompt(mut self, prompt: impl Into<SharedString>) -> Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// Returns true if the `path` is accepted by the filters.
+    fn accepts(&self, path: &Path) -> bool {
+        self.filters.is_empty() || self.filters.iter().any(|filter| filter.matches(path))
+    }
+
+    /// Open the dialog to select the files, `None` if the dialog is canceled.
+    ///
+    /// The files not matching the filters are removed from the result.
+    pub fn open_file(self, cx: &mut App) -> Task<Result<Option<Vec<PathBuf>>>> {
+        self.open(true, cx)
+    }
+
+    /// Open the dialog to select the folders, `None` if the dialog is canceled.
+    pub fn open_folder(self, cx: &mut App) -> Task<Result<Option<Vec<PathBuf>>>> {
+        self.open(false, cx)
+    }
+
+    fn open(self, files: bool, cx: &mut App) -> Task<Result<Option<Vec<PathBuf>>>> {
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files,
+            directories: !files,
+            multiple: self.multiple,
+            prompt: self.prompt.clone(),
+        });
+
+        cx.spawn(async move |_| {
+            let Some(paths) = receiver.await?? else {
+                return Ok(None);
+            };
+
+            if !files {
+                return Ok(Some(paths));
+            }
+            let paths = paths
+                .into_iter()
+                .filter(|path| self.accepts(path))
+                .collect::<Vec<_>>();
+            Ok((!paths.is_empty()).then_some(paths))
+        })
+    }
+
+    /// Open the dialog to select the path to save, `None` if the dialog is canceled.
+    ///
+    /// The extension of the first filter is appended if the path has no extension.
+    pub fn save_file(self, cx: &mut App) -> Task<Result<Option<PathBuf>>> {
+        let directory = self
+            .directory
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default();
+        let receiver = cx.prompt_for_new_path(&directory);
+
+        cx.spawn(async move |_| {
+            let Some(path) = receiver.await?? else {
+                return Ok(None);
+            };
+
+            Ok(Some(self.with_default_extension(path)))
+        })
+    }
+
+    fn with_default_extension(&self, path: PathBuf) -> PathBuf {
+        if path.extension().is_some() {
+            return path;
+        }
+
+        match self
+            .filters
+            .first()
+            .and_then(|filter| filter.extensions.first())
+        {
+            Some(extension) => path.with_extension(&extension[..]),
+            None => path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::FileDialog;
+
+    #[test]
+    fn test_filters() {
+        let dialog = FileDialog::new()
+            .filter("Images", ["png", "jpg"])
+            .filter("Text", ["txt"]);
+        assert!(dialog.accepts(Path::new("a.png")));
+        assert!(dialog.accepts(Path::new("dir/a.JPG")));
+        assert!(dialog.accepts(Path::new("a.txt")));
+        assert!(!dialog.accepts(Path::new("a.rs")));
+        assert!(!dialog.accepts(Path::new("png")));
+
+        assert!(FileDialog::new().accepts(Path::new("a.rs")));
+    }
+
+    #[test]
+    fn test_default_extension() {
+        let dialog = FileDialog::new().filter("Images", ["png", "jpg"]);
+        assert_eq!(
+            dialog.with_default_extension(PathBuf::from("a")),
+            PathBuf::from("a.png")
+        );
+        assert_eq!(
+            dialog.with_default_extension(PathBuf::from("a.jpg")),
+            PathBuf::from("a.jpg")
+        );
+        assert_eq!(
+            FileDialog::new().with_default_extension(PathBuf::from("a")),
+            PathBuf::from("a")
+        );
+    }
+}
@@ -1,4 +1,34 @@
-use gpui::{App, FocusHandle, Window};
+use std::rc::Rc;
+
+use gpui::{actions, App, FocusHandle, InteractiveElement, KeyBinding, Window};
+
+use crate::LayoutDirection;
+
+actions!(focus, [FocusNext, FocusPrev]);
+actions!(
+    roving_focus,
+    [
+        RovingPrev,
+        RovingNext,
+        RovingLeft,
+        RovingRight,
+        RovingFirst,
+        RovingLast
+    ]
+);
+
+const ROVING_CONTEXT: &str = "RovingFocus";
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("left", RovingLeft, Some(ROVING_CONTEXT)),
+        KeyBinding::new("up", RovingPrev, Some(ROVING_CONTEXT)),
+        KeyBinding::new("right", RovingRight, Some(ROVING_CONTEXT)),
+        KeyBinding::new("down", RovingNext, Some(ROVING_CONTEXT)),
+        KeyBinding::new("home", RovingFirst, Some(ROVING_CONTEXT)),
+        KeyBinding::new("end", RovingLast, Some(ROVING_CONTEXT)),
+    ]);
+}
 
 /// A trait for views that can cycle focus between its children.
 ///
@@ -38,3 +68,438 @@ pub trait FocusableCycle {
         cx.stop_propagation();
     }
 }
+
+/// Returns the index to focus next in a cycle of `len` items.
+///
+/// If `current` is `None` (focus is not in the cycle), the first item is used for next,
+/// and the last item for prev.
+pub(crate) fn cycle_index(current: Option<usize>, len: usize, is_next: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+
+    Some(match (current, is_next) {
+        (Some(ix), true) => (ix + 1) % len,
+        (Some(ix), false) => (ix + len - 1) % len,
+        (None, true) => 0,
+        (None, false) => len - 1,
+    })
+}
+
+/// A focus trap to keep the Tab / Shift-Tab focus navigation inside of an overlay.
+///
+/// The focus will cycle between the `handles` in order, and never escape the trap.
+/// When the focus is not in the `handles`, the Tab will focus the first (or the last) handle.
+///
+/// When there is no handles, the focus is kept in the [`FocusTrap::container`], or the trap
+/// does nothing if there is no container, the [`FocusNext`] and [`FocusPrev`] actions are
+/// propagated, e.g.: to the focused input.
+///
+/// Used by [`crate::modal::Modal`], [`crate::drawer::Drawer`] and [`crate::popover::PopoverContent`],
+/// the modals and drawers are always trapped by their container.
+#[derive(Clone, Default)]
+pub struct FocusTrap {
+    handles: Rc<Vec<FocusHandle>>,
+    container: Option<FocusHandle>,
+}
+
+impl FocusTrap {
+    /// Create a new focus trap, use [`FocusTrapExt::focus_trap`] to apply it to the container.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the focus handles to cycle, in the Tab order.
+    pub fn handles(mut self, handles: impl IntoIterator<Item = FocusHandle>) -> Self {
+        self.handles = Rc::new(handles.into_iter().collect());
+        self
+    }
+
+    /// Set the focus handle of the container, the focus is moved back to it if the focus has
+    /// escaped and there is no handles to cycle.
+    pub fn container(mut self, container: &FocusHandle) -> Self {
+        self.container = Some(container.clone());
+        self
+    }
+
+    /// Move focus to the next focus handle in the trap.
+    pub fn focus_next(&self, window: &mut Window, cx: &mut App) {
+        self.cycle(true, window, cx);
+    }
+
+    /// Move focus to the previous focus handle in the trap.
+    pub fn focus_prev(&self, window: &mut Window, cx: &mut App) {
+        self.cycle(false, window, cx);
+    }
+
+    /// Returns true if there is no focus handles to cycle and no container to keep the focus.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty() && self.container.is_none()
+    }
+
+    fn cycle(&self, is_next: bool, window: &mut Window, cx: &mut App) {
+        if self.handles.is_empty() {
+            if let Some(container) = &self.container {
+                if !container.contains_focused(window, cx) {
+                    container.focus(window);
+                }
+            }
+            return;
+        }
+
+        let current = self
+            .handles
+            .iter()
+            .position(|handle| handle.contains_focused(window, cx));
+
+        if let Some(ix) = cycle_index(current, self.handles.len(), is_next) {
+            self.handles[ix].focus(window);
+        }
+    }
+}
+
+/// Extension trait to apply a [`FocusTrap`] to an element.
+///
+/// The element must have a key context that binds `tab` to [`FocusNext`]
+/// and `shift-tab` to [`FocusPrev`].
+pub trait FocusTrapExt: InteractiveElement + Sized {
+    /// Handle the [`FocusNext`] and [`FocusPrev`] actions with the `trap`,
+    /// the actions will not propagate to the parent elements unless the trap is empty.
+    fn focus_trap(self, trap: &FocusTrap) -> Self {
+        self.on_action({
+            let trap = trap.clone();
+            move |_: &FocusNext, window, cx| {
+                if trap.is_empty() {
+                    cx.propagate();
+                    return;
+                }
+                trap.focus_next(window, cx)
+            }
+        })
+        .on_action({
+            let trap = trap.clone();
+            move |_: &FocusPrev, window, cx| {
+                if trap.is_empty() {
+                    cx.propagate();
+                    return;
+                }
+                trap.focus_prev(window, cx)
+            }
+        })
+    }
+}
+
+impl<E: InteractiveElement> FocusTrapExt for E {}
+
+/// Saves the focused element, for restore the focus later.
+///
+/// For example, save the focus of the trigger element when open an overlay,
+/// and restore it when the overlay is closed.
+#[derive(Clone, Default)]
+pub struct FocusRestore {
+    handle: Option<FocusHandle>,
+}
+
+impl FocusRestore {
+    /// Save the current focused handle of the window.
+    pub fn save(window: &Window, cx: &App) -> Self {
+        Self {
+            handle: window.focused(cx),
+        }
+    }
+
+    /// Restore the focus to the saved focus handle.
+    pub fn restore(&self, window: &mut Window) {
+        if let Some(handle) = &self.handle {
+            window.focus(handle);
+        }
+    }
+}
+
+/// The movement of the [`RovingFocus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RovingMove {
+    Prev,
+    Next,
+    First,
+    Last,
+}
+
+impl RovingMove {
+    /// Returns the movement of the `left` key in the `direction`.
+    fn left(direction: LayoutDirection) -> Self {
+        if direction.is_rtl() {
+            Self::Next
+        } else {
+            Self::Prev
+        }
+    }
+
+    /// Returns the movement of the `right` key in the `direction`.
+    fn right(direction: LayoutDirection) -> Self {
+        if direction.is_rtl() {
+            Self::Prev
+        } else {
+            Self::Next
+        }
+    }
+}
+
+/// Returns the next active index of the roving focus, skip the disabled items.
+///
+/// Returns `None` if there is no enabled item to move to.
+pub(crate) fn roving_index(
+    current: Option<usize>,
+    len: usize,
+    movement: RovingMove,
+    wrap: bool,
+    is_disabled: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+
+    match movement {
+        RovingMove::First => (0..len).find(|ix| !is_disabled(*ix)),
+        RovingMove::Last => (0..len).rev().find(|ix| !is_disabled(*ix)),
+        RovingMove::Next | RovingMove::Prev => {
+            let is_next = movement == RovingMove::Next;
+            let mut ix = current;
+            for _ in 0..len {
+                let next_ix = match (ix, is_next) {
+                    (None, true) => 0,
+                    (None, false) => len - 1,
+                    (Some(ix), true) if ix + 1 >= len => {
+                        if !wrap {
+                            return current;
+                        }
+                        0
+                    }
+                    (Some(ix), true) => ix + 1,
+                    (Some(0), false) => {
+                        if !wrap {
+                            return current;
+                        }
+                        len - 1
+                    }
+                    (Some(ix), false) => ix - 1,
+                };
+
+                if !is_disabled(next_ix) {
+                    return Some(next_ix);
+                }
+                ix = Some(next_ix);
+            }
+
+            current
+        }
+    }
+}
+
+/// A roving focus for composite widgets (e.g.: RadioGroup, TabBar, Toolbar).
+///
+/// The composite widget is a single Tab stop, and the active item is moved by arrow keys:
+///
+/// - `left`, `up` to move to previous item.
+/// - `right`, `down` to move to next item.
+/// - `home`, `end` to move to first or last item.
+///
+/// The `left` and `right` are mirrored if the items are laid out in
+/// [`LayoutDirection::RightToLeft`], see [`RovingFocus::direction`].
+#[derive(Clone)]
+pub struct RovingFocus {
+    focus_handle: FocusHandle,
+    len: usize,
+    active_ix: Option<usize>,
+    wrap: bool,
+    direction: LayoutDirection,
+    disabled: Rc<Vec<usize>>,
+    on_change: Rc<dyn Fn(&usize, &mut Window, &mut App)>,
+}
+
+impl RovingFocus {
+    /// Create a new roving focus for `len` items.
+    ///
+    /// The `on_change` will be called with the new active index when moved by the keyboard.
+    pub fn new(
+        focus_handle: &FocusHandle,
+        len: usize,
+        on_change: impl Fn(&usize, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        Self {
+            focus_handle: focus_handle.clone(),
+            len,
+            active_ix: None,
+            wrap: true,
+            direction: LayoutDirection::LeftToRight,
+            disabled: Rc::new(vec![]),
+            on_change: Rc::new(on_change),
+        }
+    }
+
+    /// Set the current active index, default is `None`.
+    pub fn active_ix(mut self, ix: Option<usize>) -> Self {
+        self.active_ix = ix;
+        self
+    }
+
+    /// Set whether to wrap around at the ends, default is `true`.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Set the layout direction of the items, the `left` and `right` keys are mirrored for
+    /// [`LayoutDirection::RightToLeft`], default is [`LayoutDirection::LeftToRight`].
+    pub fn direction(mut self, direction: LayoutDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set the disabled item indexes, they will be skipped.
+    pub fn disabled(mut self, disabled: impl IntoIterator<Item = usize>) -> Self {
+        self.disabled = Rc::new(disabled.into_iter().collect());
+        self
+    }
+
+    fn move_to(&self, movement: RovingMove, window: &mut Window, cx: &mut App) {
+        let disabled = self.disabled.clone();
+        let Some(ix) = roving_index(self.active_ix, self.len, movement, self.wrap, |ix| {
+            disabled.contains(&ix)
+        }) else {
+            return;
+        };
+
+        if Some(ix) != self.active_ix {
+            (self.on_change)(&ix, window, cx);
+        }
+    }
+}
+
+/// Extension trait to apply a [`RovingFocus`] to an element.
+pub trait RovingFocusExt: InteractiveElement + Sized {
+    /// Make the element as a single Tab stop, and move the active item by arrow keys.
+    ///
+    /// This will set the key context and track the focus handle of the element.
+    fn roving_focus(self, roving: RovingFocus) -> Self {
+        self.key_context(ROVING_CONTEXT)
+            .track_focus(&roving.focus_handle)
+            .on_action({
+                let roving = roving.clone();
+                move |_: &RovingPrev, window, cx| roving.move_to(RovingMove::Prev, window, cx)
+            })
+            .on_action({
+                let roving = roving.clone();
+                move |_: &RovingNext, window, cx| roving.move_to(RovingMove::Next, window, cx)
+            })
+            .on_action({
+                let roving = roving.clone();
+                move |_: &RovingLeft, window, cx| {
+                    roving.move_to(RovingMove::left(roving.direction), window, cx)
+                }
+            })
+            .on_action({
+                let roving = roving.clone();
+                move |_: &RovingRight, window, cx| {
+                    roving.move_to(RovingMove::right(roving.direction), window, cx)
+                }
+            })
+            .on_action({
+                let roving = roving.clone();
+                move |_: &RovingFirst, window, cx| roving.move_to(RovingMove::First, window, cx)
+            })
+            .on_action(move |_: &RovingLast, window, cx| {
+                roving.move_to(RovingMove::Last, window, cx)
+            })
+    }
+}
+
+impl<E: InteractiveElement> RovingFocusExt for E {}
+
+#[cfg(test)]
+mod tests {
+    use crate::LayoutDirection;
+
+    use super::{cycle_index, roving_index, RovingMove};
+
+    #[test]
+    fn test_cycle_index() {
+        assert_eq!(cycle_index(None, 0, true), None);
+        assert_eq!(cycle_index(Some(0), 0, false), None);
+        assert_eq!(cycle_index(None, 3, true), Some(0));
+        assert_eq!(cycle_index(None, 3, false), Some(2));
+        assert_eq!(cycle_index(Some(0), 3, true), Some(1));
+        assert_eq!(cycle_index(Some(2), 3, true), Some(0));
+        assert_eq!(cycle_index(Some(0), 3, false), Some(2));
+        assert_eq!(cycle_index(Some(1), 3, false), Some(0));
+        assert_eq!(cycle_index(Some(0), 1, true), Some(0));
+    }
+
+    #[test]
+    fn test_roving_index() {
+        let none = |_: usize| false;
+        assert_eq!(roving_index(None, 0, RovingMove::Next, true, none), None);
+        assert_eq!(roving_index(None, 3, RovingMove::Next, true, none), Some(0));
+        assert_eq!(roving_index(None, 3, RovingMove::Prev, true, none), Some(2));
+        assert_eq!(
+            roving_index(Some(0), 3, RovingMove::Next, true, none),
+            Some(1)
+        );
+        assert_eq!(
+            roving_index(Some(2), 3, RovingMove::Next, true, none),
+            Some(0)
+        );
+        assert_eq!(
+            roving_index(Some(2), 3, RovingMove::Next, false, none),
+            Some(2)
+        );
+        assert_eq!(
+            roving_index(Some(0), 3, RovingMove::Prev, true, none),
+            Some(2)
+        );
+        assert_eq!(
+            roving_index(Some(0), 3, RovingMove::Prev, false, none),
+            Some(0)
+        );
+        assert_eq!(
+            roving_index(Some(1), 3, RovingMove::First, true, none),
+            Some(0)
+        );
+        assert_eq!(
+            roving_index(Some(1), 3, RovingMove::Last, true, none),
+            Some(2)
+        );
+
+        let disabled = |ix: usize| ix == 1 || ix == 3;
+        assert_eq!(
+            roving_index(Some(0), 4, RovingMove::Next, true, disabled),
+            Some(2)
+        );
+        assert_eq!(
+            roving_index(Some(2), 4, RovingMove::Next, true, disabled),
+            Some(0)
+        );
+        assert_eq!(
+            roving_index(Some(2), 4, RovingMove::Next, false, disabled),
+            Some(2)
+        );
+        assert_eq!(
+            roving_index(Some(1), 4, RovingMove::Last, true, disabled),
+            Some(2)
+        );
+        assert_eq!(
+            roving_index(None, 2, RovingMove::First, true, |_| true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_roving_move_direction() {
+        let ltr = LayoutDirection::LeftToRight;
+        let rtl = LayoutDirection::RightToLeft;
+        assert_eq!(RovingMove::left(ltr), RovingMove::Prev);
+        assert_eq!(RovingMove::right(ltr), RovingMove::Next);
+        assert_eq!(RovingMove::left(rtl), RovingMove::Next);
+        assert_eq!(RovingMove::right(rtl), RovingMove::Prev);
+    }
+}
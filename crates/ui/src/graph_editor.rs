@@ -0,0 +1,821 @@
+//! A node graph editor for the workflow and shader-style tools, based on the [`Viewport`].
+//!
+//! - Drag the node header to move the selected nodes, shift-click to toggle the selection.
+//! - Drag from an output port to an input port to connect them, the port kinds must match.
+//! - Alt-click an input port to disconnect it.
+//! - Shift-drag on the background to select the nodes by the marquee.
+//!
+//! ```ignore
+//! let editor = cx.new(|cx| {
+//!     let mut editor = GraphEditor::new(window, cx);
+//!     editor.add_node(
+//!         GraphNode::new("texture", "Texture")
+//!             .position(point(px(0.), px(0.)))
+//!             .output(GraphPort::new("color", "Color").kind("vec4")),
+//!         cx,
+//!     );
+//!     editor.add_node(
+//!         GraphNode::new("output", "Output")
+//!             .position(point(px(260.), px(0.)))
+//!             .input(GraphPort::new("color", "Color").kind("vec4")),
+//!         cx,
+//!     );
+//!     editor
+//! });
+//!
+//! let layout = serde_json::to_string(&editor.read(cx).layout())?;
+//! ```
+use std::collections::HashSet;
+
+use gpui::{
+    canvas, div, point, prelude::FluentBuilder as _, px, AppContext as _, Bounds, Context, Entity,
+    EventEmitter, Hsla, InteractiveElement as _, IntoElement, MouseButton, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, ParentElement as _, PathBuilder, Pixels, Point, Render,
+    SharedString, Styled as _, Window,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    h_flex,
+    styled::rect_from_points,
+    viewport::{Viewport, ViewportState, ViewportTransform},
+    ActiveTheme as _, StyledExt as _,
+};
+
+const NODE_WIDTH: Pixels = px(180.);
+const HEADER_HEIGHT: Pixels = px(28.);
+const ROW_HEIGHT: Pixels = px(24.);
+const PORT_SIZE: Pixels = px(10.);
+
+/// A port of the [`GraphNode`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphPort {
+    pub id: SharedString,
+    pub label: SharedString,
+    /// The kind of the value, the ports can be connected only if the kinds are the same,
+    /// `None` to accept any kind.
+    pub kind: Option<SharedString>,
+}
+
+impl GraphPort {
+    pub fn new(id: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            kind: None,
+        }
+    }
+
+    /// Set the kind of the value, e.g.: `float`, `vec4`.
+    pub fn kind(mut self, kind: impl Into<SharedString>) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
+
+    /// Returns true if the port can be connected to the `other` port by the kinds.
+    pub fn is_compatible(&self, other: &GraphPort) -> bool {
+        match (&self.kind, &other.kind) {
+            (Some(kind), Some(other_kind)) => kind == other_kind,
+            _ => true,
+        }
+    }
+}
+
+/// A node of the [`GraphEditor`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub id: SharedString,
+    pub title: SharedString,
+    /// The position in the canvas coordinates.
+    pub position: Point<Pixels>,
+    pub inputs: Vec<GraphPort>,
+    pub outputs: Vec<GraphPort>,
+}
+
+impl GraphNode {
+    pub fn new(id: impl Into<SharedString>, title: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            position: Point::default(),
+            inputs: vec![],
+            outputs: vec![],
+        }
+    }
+
+    pub fn position(mut self, position: Point<Pixels>) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn input(mut self, port: GraphPort) -> Self {
+        self.inputs.push(port);
+        self
+    }
+
+    pub fn output(mut self, port: GraphPort) -> Self {
+        self.outputs.push(port);
+        self
+    }
+
+    /// Returns the bounds of the node in the canvas coordinates.
+    pub fn bounds(&self) -> Bounds<Pixels> {
+        let rows = self.inputs.len().max(self.outputs.len()) as f32;
+        Bounds::new(
+            self.position,
+            gpui::size(NODE_WIDTH, HEADER_HEIGHT + ROW_HEIGHT * rows + px(8.)),
+        )
+    }
+
+    /// Returns the position of the port in the canvas coordinates.
+    fn port_position(&self, port_id: &str, input: bool) -> Option<Point<Pixels>> {
+        let ports = if input { &self.inputs } else { &self.outputs };
+        let ix = ports.iter().position(|port| port.id.as_ref() == port_id)?;
+        let x = if input { px(0.) } else { NODE_WIDTH };
+        Some(self.position + point(x, HEADER_HEIGHT + ROW_HEIGHT * ix as f32 + ROW_HEIGHT / 2.))
+    }
+}
+
+/// An edge from an output port to an input port.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub from_node: SharedString,
+    pub from_port: SharedString,
+    pub to_node: SharedString,
+    pub to_port: SharedString,
+}
+
+impl GraphEdge {
+    pub fn new(
+        from: (impl Into<SharedString>, impl Into<SharedString>),
+        to: (impl Into<SharedString>, impl Into<SharedString>),
+    ) -> Self {
+        Self {
+            from_node: from.0.into(),
+            from_port: from.1.into(),
+            to_node: to.0.into(),
+            to_port: to.1.into(),
+        }
+    }
+}
+
+/// The position of a node in the [`GraphLayout`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeLayout {
+    pub id: SharedString,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// The serializable layout of the graph, the positions of the nodes and the edges.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphLayout {
+    pub nodes: Vec<NodeLayout>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// The events of the [`GraphEditor`].
+#[derive(Debug, Clone)]
+pub enum GraphEditorEvent {
+    Connected(GraphEdge),
+    Disconnected(GraphEdge),
+    /// The nodes are moved by the drag.
+    Moved(Vec<SharedString>),
+    SelectionChanged,
+}
+
+enum GraphDrag {
+    /// Move the selected nodes, with the last mouse position in the canvas.
+    Move { last: Point<Pixels>, moved: bool },
+    /// Connect from the output port to the mouse position in the canvas.
+    Connect {
+        node: SharedString,
+        port: SharedString,
+        position: Point<Pixels>,
+    },
+    /// Select the nodes in the marquee, the positions are in the canvas.
+    Marquee {
+        start: Point<Pixels>,
+        current: Point<Pixels>,
+    },
+}
+
+/// A node graph editor with the draggable nodes, the typed ports and the bezier edges.
+pub struct GraphEditor {
+    viewport: Entity<ViewportState>,
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+    selection: HashSet<SharedString>,
+    drag: Option<GraphDrag>,
+    minimap: bool,
+}
+
+impl EventEmitter<GraphEditorEvent> for GraphEditor {}
+
+impl GraphEditor {
+    pub fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            viewport: cx.new(|_| ViewportState::new()),
+            nodes: vec![],
+            edges: vec![],
+            selection: HashSet::new(),
+            drag: None,
+            minimap: false,
+        }
+    }
+
+    /// Set to show the minimap of the viewport, default is false.
+    pub fn minimap(mut self, minimap: bool) -> Self {
+        self.minimap = minimap;
+        self
+    }
+
+    /// Returns the state of the viewport, to zoom or pan the graph.
+    pub fn viewport(&self) -> &Entity<ViewportState> {
+        &self.viewport
+    }
+
+    pub fn nodes(&self) -> &[GraphNode] {
+        &self.nodes
+    }
+
+    pub fn edges(&self) -> &[GraphEdge] {
+        &self.edges
+    }
+
+    /// Returns the ids of the selected nodes.
+    pub fn selection(&self) -> &HashSet<SharedString> {
+        &self.selection
+    }
+
+    fn node(&self, id: &str) -> Option<&GraphNode> {
+        self.nodes.iter().find(|node| node.id.as_ref() == id)
+    }
+
+    fn sync_content_bounds(&self, cx: &mut Context<Self>) {
+        let Some(bounds) = self
+            .nodes
+            .iter()
+            .map(|node| node.bounds())
+            .reduce(|a, b| a.union(&b))
+        else {
+            return;
+        };
+
+        self.viewport
+            .update(cx, |viewport, cx| viewport.set_content_bounds(bounds, cx));
+    }
+
+    /// Add a node, the node with the same id is replaced.
+    pub fn add_node(&mut self, node: GraphNode, cx: &mut Context<Self>) {
+        match self.nodes.iter_mut().find(|n| n.id == node.id) {
+            Some(old) => *old = node,
+            None => self.nodes.push(node),
+        }
+        self.sync_content_bounds(cx);
+        cx.notify();
+    }
+
+    /// Remove the node and the edges of it.
+    pub fn remove_node(&mut self, id: &str, cx: &mut Context<Self>) {
+        self.nodes.retain(|node| node.id.as_ref() != id);
+        self.edges
+            .retain(|edge| edge.from_node.as_ref() != id && edge.to_node.as_ref() != id);
+        self.selection.remove(&SharedString::from(id.to_string()));
+        self.sync_content_bounds(cx);
+        cx.notify();
+    }
+
+    /// Remove the selected nodes.
+    pub fn remove_selected(&mut self, cx: &mut Context<Self>) {
+        for id in self.selection.clone() {
+            self.remove_node(&id, cx);
+        }
+        cx.emit(GraphEditorEvent::SelectionChanged);
+    }
+
+    /// Returns true if the edge can be connected, the ports must exist and be compatible.
+    pub fn can_connect(&self, edge: &GraphEdge) -> bool {
+        if edge.from_node == edge.to_node || self.edges.contains(edge) {
+            return false;
+        }
+
+        let (Some(from), Some(to)) = (self.node(&edge.from_node), self.node(&edge.to_node)) else {
+            return false;
+        };
+        let from_port = from.outputs.iter().find(|port| port.id == edge.from_port);
+        let to_port = to.inputs.iter().find(|port| port.id == edge.to_port);
+        match (from_port, to_port) {
+            (Some(from_port), Some(to_port)) => from_port.is_compatible(to_port),
+            _ => false,
+        }
+    }
+
+    /// Connect the edge, the existing edge to the input port is replaced.
+    ///
+    /// Returns false if the edge can not be connected.
+    pub fn connect(&mut self, edge: GraphEdge, cx: &mut Context<Self>) -> bool {
+        if !self.can_connect(&edge) {
+            return false;
+        }
+
+        self.disconnect_input(&edge.to_node.clone(), &edge.to_port.clone(), cx);
+        self.edges.push(edge.clone());
+        cx.emit(GraphEditorEvent::Connected(edge));
+        cx.notify();
+        true
+    }
+
+    /// Remove the edge.
+    pub fn disconnect(&mut self, edge: &GraphEdge, cx: &mut Context<Self>) {
+        let len = self.edges.len();
+        self.edges.retain(|e| e != edge);
+        if self.edges.len() != len {
+            cx.emit(GraphEditorEvent::Disconnected(edge.clone()));
+            cx.notify();
+        }
+    }
+
+    fn disconnect_input(&mut self, node: &str, port: &str, cx: &mut Context<Self>) {
+        let edges = self
+            .edges
+            .iter()
+            .filter(|edge| edge.to_node.as_ref() == node && edge.to_port.as_ref() == port)
+            .cloned()
+            .collect::<Vec<_>>();
+        for edge in edges {
+            self.disconnect(&edge, cx);
+        }
+    }
+
+    /// Returns the layout of the graph to serialize.
+    pub fn layout(&self) -> GraphLayout {
+        GraphLayout {
+            nodes: self
+                .nodes
+                .iter()
+                .map(|node| NodeLayout {
+                    id: node.id.clone(),
+                    x: node.position.x.into(),
+                    y: node.position.y.into(),
+                })
+                .collect(),
+            edges: self.edges.clone(),
+        }
+    }
+
+    /// Apply the layout, the positions of the nodes are restored and the edges are replaced.
+    ///
+    /// The nodes and the edges not exist in the graph are ignored.
+    pub fn apply_layout(&mut self, layout: &GraphLayout, cx: &mut Context<Self>) {
+        for node_layout in &layout.nodes {
+            if let Some(node) = self.nodes.iter_mut().find(|n| n.id == node_layout.id) {
+                node.position = point(px(node_layout.x), px(node_layout.y));
+            }
+        }
+
+        self.edges.clear();
+        for edge in &layout.edges {
+            if self.can_connect(edge) {
+                self.edges.push(edge.clone());
+            }
+        }
+        self.sync_content_bounds(cx);
+        cx.notify();
+    }
+
+    /// Zoom and pan the viewport to fit all nodes.
+    pub fn fit(&mut self, cx: &mut Context<Self>) {
+        self.viewport
+            .update(cx, |viewport, cx| viewport.fit_to_content(px(32.), cx));
+    }
+
+    fn set_selection(&mut self, selection: HashSet<SharedString>, cx: &mut Context<Self>) {
+        if self.selection != selection {
+            self.selection = selection;
+            cx.emit(GraphEditorEvent::SelectionChanged);
+        }
+    }
+
+    fn canvas_position(&self, position: Point<Pixels>, cx: &Context<Self>) -> Point<Pixels> {
+        self.viewport.read(cx).window_to_canvas(position)
+    }
+
+    fn on_node_mouse_down(
+        &mut self,
+        id: SharedString,
+        event: &MouseDownEvent,
+        cx: &mut Context<Self>,
+    ) {
+        cx.stop_propagation();
+
+        let mut selection = self.selection.clone();
+        if event.modifiers.shift {
+            if !selection.remove(&id) {
+                selection.insert(id);
+            }
+        } else if !selection.contains(&id) {
+            selection = HashSet::from_iter([id]);
+        }
+        self.set_selection(selection, cx);
+
+        self.drag = Some(GraphDrag::Move {
+            last: self.canvas_position(event.position, cx),
+            moved: false,
+        });
+        cx.notify();
+    }
+
+    fn on_output_mouse_down(
+        &mut self,
+        node: SharedString,
+        port: SharedString,
+        event: &MouseDownEvent,
+        cx: &mut Context<Self>,
+    ) {
+        cx.stop_propagation();
+        self.drag = Some(GraphDrag::Connect {
+            node,
+            port,
+            position: self.canvas_position(event.position, cx),
+        });
+        cx.notify();
+    }
+
+    fn on_input_mouse_down(
+        &mut self,
+        node: SharedString,
+        port: SharedString,
+        event: &MouseDownEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if event.modifiers.alt {
+            cx.stop_propagation();
+            self.disconnect_input(&node, &port, cx);
+        }
+    }
+
+    fn on_input_mouse_up(
+        &mut self,
+        node: SharedString,
+        port: SharedString,
+        _: &MouseUpEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(GraphDrag::Connect {
+            node: from_node,
+            port: from_port,
+            ..
+        }) = self.drag.take()
+        {
+            self.connect(GraphEdge::new((from_node, from_port), (node, port)), cx);
+            cx.notify();
+        }
+    }
+
+    fn on_background_mouse_down(&mut self, event: &MouseDownEvent, cx: &mut Context<Self>) {
+        if event.modifiers.shift {
+            // Select by the marquee instead of panning the viewport.
+            cx.stop_propagation();
+            let position = self.canvas_position(event.position, cx);
+            self.drag = Some(GraphDrag::Marquee {
+                start: position,
+                current: position,
+            });
+        } else {
+            self.set_selection(HashSet::new(), cx);
+        }
+        cx.notify();
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if self.drag.is_none() {
+            return;
+        }
+        if event.pressed_button != Some(MouseButton::Left) {
+            self.drag = None;
+            cx.notify();
+            return;
+        }
+
+        let position = self.canvas_position(event.position, cx);
+        match self.drag.as_mut() {
+            Some(GraphDrag::Move { last, moved }) => {
+                let delta = position - *last;
+                *last = position;
+                *moved = true;
+                for node in self.nodes.iter_mut() {
+                    if self.selection.contains(&node.id) {
+                        node.position = node.position + delta;
+                    }
+                }
+            }
+            Some(GraphDrag::Connect { position: p, .. }) => *p = position,
+            Some(GraphDrag::Marquee { start, current }) => {
+                *current = position;
+                let rect = rect_from_points(*start, *current);
+                let selection = self
+                    .nodes
+                    .iter()
+                    .filter(|node| node.bounds().intersects(&rect))
+                    .map(|node| node.id.clone())
+                    .collect();
+                self.set_selection(selection, cx);
+            }
+            None => {}
+        }
+        cx.notify();
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(GraphDrag::Move { moved: true, .. }) = self.drag {
+            self.sync_content_bounds(cx);
+            let mut ids = self.selection.iter().cloned().collect::<Vec<_>>();
+            ids.sort();
+            cx.emit(GraphEditorEvent::Moved(ids));
+        }
+        if self.drag.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Returns the curves of the edges and the dragging edge in the canvas coordinates.
+    fn edge_curves(&self, cx: &Context<Self>) -> Vec<(Point<Pixels>, Point<Pixels>, Hsla)> {
+        let mut curves = self
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                let from = self
+                    .node(&edge.from_node)?
+                    .port_position(&edge.from_port, false)?;
+                let to = self
+                    .node(&edge.to_node)?
+                    .port_position(&edge.to_port, true)?;
+                Some((from, to, cx.theme().muted_foreground))
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(GraphDrag::Connect {
+            node,
+            port,
+            position,
+        }) = &self.drag
+        {
+            if let Some(from) = self
+                .node(node)
+                .and_then(|node| node.port_position(port, false))
+            {
+                curves.push((from, *position, cx.theme().primary));
+            }
+        }
+
+        curves
+    }
+
+    /// Returns the output port of the dragging edge.
+    fn connecting_port(&self) -> Option<&GraphPort> {
+        let Some(GraphDrag::Connect { node, port, .. }) = &self.drag else {
+            return None;
+        };
+        self.node(node)?
+            .outputs
+            .iter()
+            .find(|output| output.id == *port)
+    }
+
+    fn render_node(
+        &self,
+        node: &GraphNode,
+        transform: ViewportTransform,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let origin = transform.to_screen(node.position);
+        let bounds = node.bounds();
+        let selected = self.selection.contains(&node.id);
+        let connecting_port = self.connecting_port();
+        let scale = |length: Pixels| transform.scale(length);
+
+        let port_dot = |highlighted: bool| {
+            div()
+                .absolute()
+                .top(scale((ROW_HEIGHT - PORT_SIZE) / 2.))
+                .size(scale(PORT_SIZE))
+                .rounded_full()
+                .border_1()
+                .border_color(cx.theme().background)
+                .bg(if highlighted {
+                    cx.theme().primary
+                } else {
+                    cx.theme().muted_foreground
+                })
+        };
+
+        div()
+            .absolute()
+            .left(origin.x)
+            .top(origin.y)
+            .w(scale(bounds.size.width))
+            .h(scale(bounds.size.height))
+            .text_size(scale(px(12.)))
+            .rounded(scale(cx.theme().radius))
+            .border_1()
+            .border_color(if selected {
+                cx.theme().primary
+            } else {
+                cx.theme().border
+            })
+            .bg(cx.theme().popover)
+            .text_color(cx.theme().popover_foreground)
+            .shadow_sm()
+            .child(
+                h_flex()
+                    .h(scale(HEADER_HEIGHT))
+                    .px(scale(px(8.)))
+                    .rounded_t(scale(cx.theme().radius))
+                    .bg(cx.theme().muted)
+                    .font_semibold()
+                    .overflow_hidden()
+                    .child(node.title.clone())
+                    .on_mouse_down(MouseButton::Left, {
+                        let id = node.id.clone();
+                        cx.listener(move |this, event, _, cx| {
+                            this.on_node_mouse_down(id.clone(), event, cx)
+                        })
+                    }),
+            )
+            .children(node.inputs.iter().enumerate().map(|(ix, port)| {
+                let highlighted =
+                    connecting_port.map_or(false, |output| output.is_compatible(port));
+                div()
+                    .absolute()
+                    .left_0()
+                    .top(scale(HEADER_HEIGHT + ROW_HEIGHT * ix as f32))
+                    .h(scale(ROW_HEIGHT))
+                    .w(scale(NODE_WIDTH / 2.))
+                    .pl(scale(px(10.)))
+                    .flex()
+                    .items_center()
+                    .child(port_dot(highlighted).left(-scale(PORT_SIZE / 2.)))
+                    .child(port.label.clone())
+                    .on_mouse_down(MouseButton::Left, {
+                        let (node, port) = (node.id.clone(), port.id.clone());
+                        cx.listener(move |this, event, _, cx| {
+                            this.on_input_mouse_down(node.clone(), port.clone(), event, cx)
+                        })
+                    })
+                    .on_mouse_up(MouseButton::Left, {
+                        let (node, port) = (node.id.clone(), port.id.clone());
+                        cx.listener(move |this, event, _, cx| {
+                            this.on_input_mouse_up(node.clone(), port.clone(), event, cx)
+                        })
+                    })
+            }))
+            .children(node.outputs.iter().enumerate().map(|(ix, port)| {
+                div()
+                    .absolute()
+                    .right_0()
+                    .top(scale(HEADER_HEIGHT + ROW_HEIGHT * ix as f32))
+                    .h(scale(ROW_HEIGHT))
+                    .w(scale(NODE_WIDTH / 2.))
+                    .pr(scale(px(10.)))
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .child(port.label.clone())
+                    .child(port_dot(false).right(-scale(PORT_SIZE / 2.)))
+                    .on_mouse_down(MouseButton::Left, {
+                        let (node, port) = (node.id.clone(), port.id.clone());
+                        cx.listener(move |this, event, _, cx| {
+                            this.on_output_mouse_down(node.clone(), port.clone(), event, cx)
+                        })
+                    })
+            }))
+    }
+}
+
+impl Render for GraphEditor {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let transform = self.viewport.read(cx).transform();
+        let curves = self.edge_curves(cx);
+        let marquee = match &self.drag {
+            Some(GraphDrag::Marquee { start, current }) => Some(rect_from_points(
+                transform.to_screen(*start),
+                transform.to_screen(*current),
+            )),
+            _ => None,
+        };
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| self.render_node(node, transform, cx).into_any_element())
+            .collect::<Vec<_>>();
+
+        div()
+            .size_full()
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .child(
+                Viewport::new("graph-editor", &self.viewport)
+                    .minimap(self.minimap)
+                    .child(div().absolute().size_full().on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, event, _, cx| this.on_background_mouse_down(event, cx)),
+                    ))
+                    .child(
+                        canvas(
+                            |_, _, _| {},
+                            move |bounds, _, window, _| {
+                                let width = transform.scale(px(2.)).max(px(1.));
+                                for (from, to, color) in curves {
+                                    let from = transform.to_screen(from) + bounds.origin;
+                                    let to = transform.to_screen(to) + bounds.origin;
+                                    let dx =
+                                        ((to.x - from.x).abs() / 2.).max(transform.scale(px(40.)));
+
+                                    let mut builder = PathBuilder::stroke(width);
+                                    builder.move_to(from);
+                                    builder.cubic_bezier_to(
+                                        to,
+                                        from + point(dx, px(0.)),
+                                        to - point(dx, px(0.)),
+                                    );
+                                    if let Ok(path) = builder.build() {
+                                        window.paint_path(path, color);
+                                    }
+                                }
+                            },
+                        )
+                        .absolute()
+                        .size_full(),
+                    )
+                    .children(nodes)
+                    .when_some(marquee, |this, marquee| {
+                        this.child(
+                            div()
+                                .absolute()
+                                .left(marquee.origin.x)
+                                .top(marquee.origin.y)
+                                .w(marquee.size.width)
+                                .h(marquee.size.height)
+                                .border_1()
+                                .border_color(cx.theme().primary)
+                                .bg(cx.theme().primary.opacity(0.1)),
+                        )
+                    }),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px};
+
+    use super::{GraphEdge, GraphLayout, GraphNode, GraphPort, NodeLayout};
+
+    #[test]
+    fn test_port_compatible() {
+        let float = GraphPort::new("a", "A").kind("float");
+        let vec4 = GraphPort::new("b", "B").kind("vec4");
+        let any = GraphPort::new("c", "C");
+        assert!(float.is_compatible(&float.clone()));
+        assert!(!float.is_compatible(&vec4));
+        assert!(any.is_compatible(&vec4));
+        assert!(vec4.is_compatible(&any));
+    }
+
+    #[test]
+    fn test_node_layout() {
+        let node = GraphNode::new("n", "Node")
+            .position(point(px(100.), px(50.)))
+            .input(GraphPort::new("a", "A"))
+            .input(GraphPort::new("b", "B"))
+            .output(GraphPort::new("out", "Out"));
+
+        assert_eq!(node.bounds().size.height, px(28. + 24. * 2. + 8.));
+        assert_eq!(
+            node.port_position("b", true),
+            Some(point(px(100.), px(50. + 28. + 24. + 12.)))
+        );
+        assert_eq!(
+            node.port_position("out", false),
+            Some(point(px(280.), px(50. + 28. + 12.)))
+        );
+        assert_eq!(node.port_position("out", true), None);
+    }
+
+    #[test]
+    fn test_layout_serialize() {
+        let layout = GraphLayout {
+            nodes: vec![NodeLayout {
+                id: "n".into(),
+                x: 10.,
+                y: 20.,
+            }],
+            edges: vec![GraphEdge::new(("a", "out"), ("b", "in"))],
+        };
+
+        let json = serde_json::to_string(&layout).unwrap();
+        assert_eq!(serde_json::from_str::<GraphLayout>(&json).unwrap(), layout);
+    }
+}
@@ -0,0 +1,770 @@
+//! A virtualized grid of the items, e.g.: the file thumbnails, the emoji picker and the asset
+//! browsers, only the visible rows are rendered.
+//!
+//! The items are selected by the click, `shift` to select the range, `secondary` to toggle,
+//! or dragging a marquee on the empty space. The arrow keys move the selection in 2D, the cells
+//! are laid out from the right for the RTL layout direction.
+//!
+//! ```ignore
+//! struct Thumbnails { files: Vec<PathBuf> }
+//!
+//! impl GridDelegate for Thumbnails {
+//!     fn items_count(&self, _: &App) -> usize {
+//!         self.files.len()
+//!     }
+//!
+//!     fn render_item(&self, ix: usize, _: &mut Window, _: &mut Context<Grid<Self>>) -> impl IntoElement {
+//!         img(self.files[ix].clone()).size_full()
+//!     }
+//! }
+//!
+//! let grid = cx.new(|cx| {
+//!     Grid::new(Thumbnails { files }, window, cx)
+//!         .cell_size(size(px(120.), px(90.)))
+//!         .responsive(true)
+//! });
+//! ```
+use std::{collections::BTreeSet, ops::Range};
+
+use gpui::{
+    actions, canvas, div, point, prelude::FluentBuilder as _, px, size, uniform_list, Action,
+    AnyElement, App, Bounds, Context, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement as _, IntoElement, KeyBinding, MouseButton, MouseDownEvent, MouseMoveEvent,
+    MouseUpEvent, ParentElement as _, Pixels, Point, Render, ScrollStrategy, Size, Styled as _,
+    UniformListScrollHandle, Window,
+};
+use serde::Deserialize;
+
+use crate::{
+    actions::{Cancel, Confirm, SelectFirst, SelectLast},
+    h_flex,
+    scroll::{Scrollbar, ScrollbarState},
+    styled::rect_from_points,
+    ActiveTheme as _, Icon, IconName, LayoutDirection, StyledExt as _,
+};
+
+const CONTEXT: &str = "Grid";
+
+/// The direction to move the selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Returns the direction in the layout, the `Left` and `Right` are mirrored for
+    /// [`LayoutDirection::RightToLeft`].
+    fn mirrored(self, direction: LayoutDirection) -> Self {
+        match self {
+            Self::Left if direction.is_rtl() => Self::Right,
+            Self::Right if direction.is_rtl() => Self::Left,
+            _ => self,
+        }
+    }
+}
+
+/// Move the selection to the adjacent item.
+#[derive(Clone, Action, PartialEq, Eq, Deserialize)]
+#[action(namespace = grid, no_json)]
+pub struct MoveSelection {
+    pub direction: Direction,
+    /// Extend the selection from the anchor, e.g.: with `shift`.
+    pub extend: bool,
+}
+
+actions!(grid, [SelectAll]);
+
+pub fn init(cx: &mut App) {
+    let context = Some(CONTEXT);
+    let bindings = [
+        ("up", Direction::Up),
+        ("down", Direction::Down),
+        ("left", Direction::Left),
+        ("right", Direction::Right),
+    ]
+    .into_iter()
+    .flat_map(|(key, direction)| {
+        [false, true].map(|extend| {
+            let keystroke = if extend {
+                format!("shift-{}", key)
+            } else {
+                key.to_string()
+            };
+            KeyBinding::new(&keystroke, MoveSelection { direction, extend }, context)
+        })
+    });
+
+    cx.bind_keys(bindings);
+    cx.bind_keys([
+        KeyBinding::new("escape", Cancel, context),
+        KeyBinding::new("enter", Confirm { secondary: false }, context),
+        KeyBinding::new("secondary-enter", Confirm { secondary: true }, context),
+        KeyBinding::new("home", SelectFirst, context),
+        KeyBinding::new("end", SelectLast, context),
+        KeyBinding::new("secondary-a", SelectAll, context),
+    ]);
+}
+
+/// A delegate for the [`Grid`].
+#[allow(unused)]
+pub trait GridDelegate: Sized + 'static {
+    /// Return the number of the items.
+    fn items_count(&self, cx: &App) -> usize;
+
+    /// Render the item at the given index, it fills the cell.
+    fn render_item(
+        &self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Grid<Self>>,
+    ) -> impl IntoElement;
+
+    /// Return a Element to show when the grid is empty.
+    fn render_empty(&self, window: &mut Window, cx: &mut Context<Grid<Self>>) -> impl IntoElement {
+        h_flex()
+            .size_full()
+            .justify_center()
+            .text_color(cx.theme().muted_foreground.opacity(0.6))
+            .child(Icon::new(IconName::Inbox).size_12())
+            .into_any_element()
+    }
+
+    /// Called when the visible range of the items changed, e.g.: to load the thumbnails of the
+    /// visible items only.
+    ///
+    /// NOTE: Make sure this method is fast, and load the data in the background task.
+    fn visible_items_changed(
+        &mut self,
+        visible_range: Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<Grid<Self>>,
+    ) {
+    }
+
+    /// Confirm the item, e.g.: double clicked or pressed Enter.
+    fn confirm(
+        &mut self,
+        ix: usize,
+        secondary: bool,
+        window: &mut Window,
+        cx: &mut Context<Grid<Self>>,
+    ) {
+    }
+}
+
+/// The events of the [`Grid`].
+#[derive(Debug, Clone)]
+pub enum GridEvent {
+    /// The selected indices are changed.
+    Select(Vec<usize>),
+    /// Double clicked or pressed Enter on the item.
+    Confirm(usize),
+    /// Pressed ESC to deselect the items.
+    Cancel,
+}
+
+/// The layout of the cells, in the content coordinates of the grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GridLayout {
+    columns: usize,
+    count: usize,
+    cell_size: Size<Pixels>,
+    gap: Pixels,
+}
+
+impl GridLayout {
+    /// Fill the `width` by the columns of the `cell_size`, the responsive cells are stretched
+    /// to fill the width by the aspect ratio.
+    fn new(
+        width: Pixels,
+        cell_size: Size<Pixels>,
+        gap: Pixels,
+        responsive: bool,
+        count: usize,
+    ) -> Self {
+        let columns = (((width + gap) / (cell_size.width + gap)).floor() as usize).max(1);
+        let cell_size = if responsive && width > px(0.) {
+            let width = ((width - gap * (columns - 1) as f32) / columns as f32).max(px(1.));
+            size(width, cell_size.height * (width / cell_size.width))
+        } else {
+            cell_size
+        };
+
+        Self {
+            columns,
+            count,
+            cell_size,
+            gap,
+        }
+    }
+
+    fn rows(&self) -> usize {
+        self.count.div_ceil(self.columns)
+    }
+
+    fn stride(&self) -> Size<Pixels> {
+        size(
+            self.cell_size.width + self.gap,
+            self.cell_size.height + self.gap,
+        )
+    }
+
+    fn cell_bounds(&self, ix: usize) -> Bounds<Pixels> {
+        let stride = self.stride();
+        let (row, col) = (ix / self.columns, ix % self.columns);
+        Bounds::new(
+            point(stride.width * col as f32, stride.height * row as f32),
+            self.cell_size,
+        )
+    }
+
+    /// Returns the item at the `position`, `None` for the gaps and the empty space.
+    fn index_at(&self, position: Point<Pixels>) -> Option<usize> {
+        if position.x < px(0.) || position.y < px(0.) {
+            return None;
+        }
+
+        let stride = self.stride();
+        let col = (position.x / stride.width).floor() as usize;
+        let row = (position.y / stride.height).floor() as usize;
+        let ix = row * self.columns + col;
+        if col >= self.columns || ix >= self.count {
+            return None;
+        }
+
+        self.cell_bounds(ix).contains(&position).then_some(ix)
+    }
+
+    /// Returns the items intersecting the `bounds`.
+    fn indices_in(&self, bounds: Bounds<Pixels>) -> Vec<usize> {
+        let stride = self.stride();
+        let rows = (bounds.top() / stride.height).floor().max(0.) as usize
+            ..(bounds.bottom() / stride.height).ceil().max(0.) as usize;
+        let cols = (bounds.left() / stride.width).floor().max(0.) as usize
+            ..((bounds.right() / stride.width).ceil().max(0.) as usize).min(self.columns);
+
+        rows.flat_map(|row| cols.clone().map(move |col| row * self.columns + col))
+            .take_while(|ix| *ix < self.count)
+            .filter(|ix| self.cell_bounds(*ix).intersects(&bounds))
+            .collect()
+    }
+
+    /// Returns the adjacent item of the `ix` in the `direction`.
+    fn move_index(&self, ix: usize, direction: Direction) -> usize {
+        let last = self.count.saturating_sub(1);
+        match direction {
+            Direction::Left => ix.saturating_sub(1),
+            Direction::Right => (ix + 1).min(last),
+            Direction::Up => ix.checked_sub(self.columns).unwrap_or(ix),
+            // Move to the last item if the next row is shorter.
+            Direction::Down if ix / self.columns + 1 < self.rows() => (ix + self.columns).min(last),
+            Direction::Down => ix,
+        }
+    }
+}
+
+/// Select the items by dragging a marquee, the positions are in the content coordinates.
+struct Marquee {
+    start: Point<Pixels>,
+    current: Point<Pixels>,
+    /// The selection before dragging, kept with `shift` or `secondary`.
+    base: BTreeSet<usize>,
+}
+
+/// A virtualized grid of the items with the fixed or responsive cell size.
+pub struct Grid<D: GridDelegate> {
+    focus_handle: FocusHandle,
+    delegate: D,
+    cell_size: Size<Pixels>,
+    gap: Pixels,
+    responsive: bool,
+    multiple: bool,
+    scroll_handle: UniformListScrollHandle,
+    scroll_state: ScrollbarState,
+    bounds: Bounds<Pixels>,
+    /// The cells are laid out from the right for [`LayoutDirection::RightToLeft`].
+    direction: LayoutDirection,
+    layout: GridLayout,
+    visible_range: Range<usize>,
+    selection: BTreeSet<usize>,
+    /// The item moved by the keyboard.
+    cursor: Option<usize>,
+    /// The start of the range selection.
+    anchor: Option<usize>,
+    marquee: Option<Marquee>,
+}
+
+impl<D: GridDelegate> EventEmitter<GridEvent> for Grid<D> {}
+
+impl<D: GridDelegate> Grid<D> {
+    pub fn new(delegate: D, _: &mut Window, cx: &mut Context<Self>) -> Self {
+        let cell_size = size(px(96.), px(96.));
+        let gap = px(8.);
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            delegate,
+            cell_size,
+            gap,
+            responsive: false,
+            multiple: true,
+            scroll_handle: UniformListScrollHandle::new(),
+            scroll_state: ScrollbarState::default(),
+            bounds: Bounds::default(),
+            direction: LayoutDirection::default(),
+            layout: GridLayout::new(px(0.), cell_size, gap, false, 0),
+            visible_range: 0..0,
+            selection: BTreeSet::new(),
+            cursor: None,
+            anchor: None,
+            marquee: None,
+        }
+    }
+
+    /// Set the size of the cells, default is 96x96.
+    ///
+    /// The responsive cells use it as the minimum size and the aspect ratio.
+    pub fn cell_size(mut self, size: Size<Pixels>) -> Self {
+        self.cell_size = size;
+        self
+    }
+
+    /// Set the gap between the cells, default is 8px.
+    pub fn gap(mut self, gap: Pixels) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Set to stretch the cells to fill the width, default is false.
+    pub fn responsive(mut self, responsive: bool) -> Self {
+        self.responsive = responsive;
+        self
+    }
+
+    /// Set to select the multiple items, default is true.
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    pub fn delegate(&self) -> &D {
+        &self.delegate
+    }
+
+    pub fn delegate_mut(&mut self) -> &mut D {
+        &mut self.delegate
+    }
+
+    /// Returns the number of the columns in the last layout.
+    pub fn columns(&self) -> usize {
+        self.layout.columns
+    }
+
+    /// Returns the selected indices in ascending order.
+    pub fn selected_indices(&self) -> Vec<usize> {
+        self.selection.iter().copied().collect()
+    }
+
+    /// Set the selected indices, this method will not scroll to the items.
+    pub fn set_selected_indices(
+        &mut self,
+        indices: impl IntoIterator<Item = usize>,
+        cx: &mut Context<Self>,
+    ) {
+        self.selection = indices.into_iter().collect();
+        self.cursor = self.selection.last().copied();
+        self.anchor = self.cursor;
+        cx.notify();
+    }
+
+    /// Scroll to the row of the item.
+    pub fn scroll_to_item(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.scroll_handle
+            .scroll_to_item(ix / self.layout.columns, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn set_selection(&mut self, selection: BTreeSet<usize>, cx: &mut Context<Self>) {
+        if self.selection == selection {
+            return;
+        }
+
+        self.selection = selection;
+        cx.emit(GridEvent::Select(self.selected_indices()));
+        cx.notify();
+    }
+
+    /// Select the item, `extend` to select the range from the anchor, `toggle` to add or
+    /// remove the item.
+    fn select_item(&mut self, ix: usize, extend: bool, toggle: bool, cx: &mut Context<Self>) {
+        let mut selection = if self.multiple && toggle {
+            self.selection.clone()
+        } else {
+            BTreeSet::new()
+        };
+
+        match self.anchor {
+            Some(anchor) if self.multiple && extend => {
+                selection.extend(anchor.min(ix)..=anchor.max(ix));
+            }
+            _ if self.multiple && toggle => {
+                if !selection.remove(&ix) {
+                    selection.insert(ix);
+                }
+                self.anchor = Some(ix);
+            }
+            _ => {
+                selection.insert(ix);
+                self.anchor = Some(ix);
+            }
+        }
+
+        self.cursor = Some(ix);
+        self.set_selection(selection, cx);
+        self.scroll_to_item(ix, cx);
+    }
+
+    fn confirm(&mut self, ix: usize, secondary: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.delegate.confirm(ix, secondary, window, cx);
+        cx.emit(GridEvent::Confirm(ix));
+        cx.notify();
+    }
+
+    fn scroll_offset(&self) -> Point<Pixels> {
+        self.scroll_handle.0.borrow().base_handle.offset()
+    }
+
+    /// Returns the position in the content coordinates by the window position, the x is
+    /// mirrored for [`LayoutDirection::RightToLeft`].
+    fn content_position(&self, position: Point<Pixels>) -> Point<Pixels> {
+        let mut position = position - self.bounds.origin - self.scroll_offset();
+        if self.direction.is_rtl() {
+            position.x = self.bounds.size.width - position.x;
+        }
+        position
+    }
+
+    fn on_action_move(&mut self, action: &MoveSelection, _: &mut Window, cx: &mut Context<Self>) {
+        if self.layout.count == 0 {
+            return;
+        }
+
+        let ix = match self.cursor {
+            Some(ix) => self
+                .layout
+                .move_index(ix, action.direction.mirrored(self.direction)),
+            None => 0,
+        };
+        self.select_item(ix, action.extend, false, cx);
+    }
+
+    fn on_action_select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if self.layout.count > 0 {
+            self.select_item(0, false, false, cx);
+        }
+    }
+
+    fn on_action_select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if self.layout.count > 0 {
+            self.select_item(self.layout.count - 1, false, false, cx);
+        }
+    }
+
+    fn on_action_select_all(&mut self, _: &SelectAll, _: &mut Window, cx: &mut Context<Self>) {
+        if self.multiple {
+            self.set_selection((0..self.layout.count).collect(), cx);
+        }
+    }
+
+    fn on_action_confirm(&mut self, action: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.cursor.filter(|ix| self.selection.contains(ix)) {
+            self.confirm(ix, action.secondary, window, cx);
+        }
+    }
+
+    fn on_action_cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selection.is_empty() {
+            cx.propagate();
+            return;
+        }
+
+        self.set_selection(BTreeSet::new(), cx);
+        cx.emit(GridEvent::Cancel);
+    }
+
+    fn on_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.focus_handle.focus(window);
+        let position = self.content_position(event.position);
+        let extend = event.modifiers.shift;
+        let toggle = event.modifiers.secondary();
+
+        match self.layout.index_at(position) {
+            Some(ix) if event.click_count == 2 => {
+                self.select_item(ix, false, false, cx);
+                self.confirm(ix, toggle, window, cx);
+            }
+            Some(ix) => self.select_item(ix, extend, toggle, cx),
+            None => {
+                let base = if self.multiple && (extend || toggle) {
+                    self.selection.clone()
+                } else {
+                    BTreeSet::new()
+                };
+                self.set_selection(base.clone(), cx);
+                if self.multiple {
+                    self.marquee = Some(Marquee {
+                        start: position,
+                        current: position,
+                        base,
+                    });
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if self.marquee.is_none() {
+            return;
+        }
+        if event.pressed_button != Some(MouseButton::Left) {
+            self.marquee = None;
+            cx.notify();
+            return;
+        }
+
+        let position = self.content_position(event.position);
+        let Some(marquee) = self.marquee.as_mut() else {
+            return;
+        };
+        marquee.current = position;
+        let mut selection = marquee.base.clone();
+        selection.extend(
+            self.layout
+                .indices_in(rect_from_points(marquee.start, marquee.current)),
+        );
+        self.set_selection(selection, cx);
+        cx.notify();
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if self.marquee.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    fn update_visible_range_if_need(
+        &mut self,
+        rows: Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let columns = self.layout.columns;
+        let visible_range = rows.start * columns..(rows.end * columns).min(self.layout.count);
+        if self.visible_range == visible_range {
+            return;
+        }
+
+        self.delegate
+            .visible_items_changed(visible_range.clone(), window, cx);
+        self.visible_range = visible_range;
+    }
+
+    fn render_row(&self, row: usize, window: &mut Window, cx: &mut Context<Self>) -> AnyElement {
+        let layout = self.layout;
+        let is_focused = self.focus_handle.is_focused(window);
+        let range = row * layout.columns..((row + 1) * layout.columns).min(layout.count);
+
+        let mut cells = Vec::with_capacity(range.len());
+        for ix in range {
+            let selected = self.selection.contains(&ix);
+            cells.push(
+                div()
+                    .flex_none()
+                    .w(layout.cell_size.width)
+                    .h(layout.cell_size.height)
+                    .overflow_hidden()
+                    .rounded(cx.theme().radius)
+                    .border_1()
+                    .border_color(gpui::transparent_black())
+                    .when(selected, |this| {
+                        this.bg(cx.theme().accent).border_color(cx.theme().primary)
+                    })
+                    .when(is_focused && self.cursor == Some(ix), |this| {
+                        this.border_color(cx.theme().ring)
+                    })
+                    .child(self.delegate.render_item(ix, window, cx)),
+            );
+        }
+
+        h_flex()
+            .flex_row_with(self.direction)
+            .w_full()
+            .gap(layout.gap)
+            .pb(layout.gap)
+            .children(cells)
+            .into_any_element()
+    }
+}
+
+impl<D: GridDelegate> Focusable for Grid<D> {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl<D: GridDelegate> Render for Grid<D> {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let count = self.delegate.items_count(cx);
+        self.layout = GridLayout::new(
+            self.bounds.size.width,
+            self.cell_size,
+            self.gap,
+            self.responsive,
+            count,
+        );
+        self.selection.retain(|ix| *ix < count);
+        self.direction = LayoutDirection::of(window, cx);
+        let marquee = self.marquee.as_ref().map(|marquee| {
+            let offset = self.scroll_offset();
+            let mut marquee = rect_from_points(marquee.start + offset, marquee.current + offset);
+            if self.direction.is_rtl() {
+                marquee.origin.x = self.bounds.size.width - marquee.right();
+            }
+            marquee
+        });
+        let view = cx.entity().clone();
+
+        div()
+            .key_context(CONTEXT)
+            .id("grid")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .relative()
+            .overflow_hidden()
+            .on_action(cx.listener(Self::on_action_move))
+            .on_action(cx.listener(Self::on_action_select_first))
+            .on_action(cx.listener(Self::on_action_select_last))
+            .on_action(cx.listener(Self::on_action_select_all))
+            .on_action(cx.listener(Self::on_action_confirm))
+            .on_action(cx.listener(Self::on_action_cancel))
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .child(
+                canvas(
+                    move |bounds, _, cx| {
+                        view.update(cx, |r, cx| {
+                            if r.bounds != bounds {
+                                r.bounds = bounds;
+                                cx.notify();
+                            }
+                        })
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .map(|this| {
+                if count == 0 {
+                    return this.child(self.delegate.render_empty(window, cx));
+                }
+
+                this.child(
+                    uniform_list(
+                        "grid-rows",
+                        self.layout.rows(),
+                        cx.processor(move |grid, visible_range: Range<usize>, window, cx| {
+                            grid.update_visible_range_if_need(visible_range.clone(), window, cx);
+                            visible_range
+                                .map(|row| grid.render_row(row, window, cx))
+                                .collect()
+                        }),
+                    )
+                    .size_full()
+                    .track_scroll(self.scroll_handle.clone()),
+                )
+                .child(Scrollbar::uniform_scroll(
+                    &self.scroll_state,
+                    &self.scroll_handle,
+                ))
+            })
+            .when_some(marquee, |this, marquee| {
+                this.child(
+                    div()
+                        .absolute()
+                        .left(marquee.origin.x)
+                        .top(marquee.origin.y)
+                        .w(marquee.size.width)
+                        .h(marquee.size.height)
+                        .border_1()
+                        .border_color(cx.theme().primary)
+                        .bg(cx.theme().primary.opacity(0.1)),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, Bounds};
+
+    use crate::LayoutDirection;
+
+    use super::{Direction, GridLayout};
+
+    #[test]
+    fn test_layout() {
+        let layout = GridLayout::new(px(330.), size(px(100.), px(50.)), px(10.), false, 10);
+        assert_eq!(layout.columns, 3);
+        assert_eq!(layout.rows(), 4);
+        assert_eq!(layout.index_at(point(px(5.), px(5.))), Some(0));
+        assert_eq!(layout.index_at(point(px(115.), px(65.))), Some(4));
+        // The gap and the empty space.
+        assert_eq!(layout.index_at(point(px(105.), px(5.))), None);
+        assert_eq!(layout.index_at(point(px(115.), px(185.))), None);
+
+        let layout = GridLayout::new(px(350.), size(px(100.), px(100.)), px(10.), true, 10);
+        assert_eq!(layout.columns, 3);
+        assert_eq!(layout.cell_size, size(px(110.), px(110.)));
+    }
+
+    #[test]
+    fn test_indices_in() {
+        let layout = GridLayout::new(px(330.), size(px(100.), px(50.)), px(10.), false, 10);
+        let bounds = Bounds::from_corners(point(px(50.), px(20.)), point(px(150.), px(70.)));
+        assert_eq!(layout.indices_in(bounds), vec![0, 1, 3, 4]);
+        let bounds = Bounds::from_corners(point(px(0.), px(170.)), point(px(330.), px(400.)));
+        assert_eq!(layout.indices_in(bounds), vec![9]);
+    }
+
+    #[test]
+    fn test_move_index() {
+        let layout = GridLayout::new(px(330.), size(px(100.), px(50.)), px(10.), false, 8);
+        assert_eq!(layout.move_index(0, Direction::Left), 0);
+        assert_eq!(layout.move_index(2, Direction::Right), 3);
+        assert_eq!(layout.move_index(1, Direction::Up), 1);
+        assert_eq!(layout.move_index(4, Direction::Down), 7);
+        assert_eq!(layout.move_index(5, Direction::Down), 7);
+        assert_eq!(layout.move_index(7, Direction::Down), 7);
+
+        let rtl = LayoutDirection::RightToLeft;
+        assert_eq!(Direction::Left.mirrored(rtl), Direction::Right);
+        assert_eq!(Direction::Right.mirrored(rtl), Direction::Left);
+        assert_eq!(Direction::Up.mirrored(rtl), Direction::Up);
+        assert_eq!(
+            Direction::Left.mirrored(LayoutDirection::LeftToRight),
+            Direction::Left
+        );
+    }
+}
@@ -18,6 +18,8 @@ pub struct SyntaxHighlighter {
     language: SharedString,
     query: Option<Query>,
     injection_queries: HashMap<SharedString, Query>,
+    /// The languages of the injections, to highlight without the [`LanguageRegistry`].
+    injection_languages: HashMap<SharedString, tree_sitter::Language>,
     parser: Parser,
     old_tree: Option<Tree>,
     text: SharedString,
@@ -154,11 +156,14 @@ impl SyntaxHighlighter {
         }
 
         let mut injection_queries = HashMap::new();
+        let mut injection_languages = HashMap::new();
         for inj_language in config.injection_languages.iter() {
             if let Some(inj_config) = registry.language(&inj_language) {
                 match Query::new(&inj_config.language, &inj_config.highlights) {
                     Ok(q) => {
                         injection_queries.insert(inj_config.name.clone(), q);
+                        injection_languages
+                            .insert(inj_config.name.clone(), inj_config.language.clone());
                     }
                     Err(e) => {
                         tracing::error!(
@@ -177,6 +182,7 @@ impl SyntaxHighlighter {
             language: config.name.clone(),
             query: Some(query),
             injection_queries,
+            injection_languages,
             parser,
             old_tree: None,
             text: SharedString::new(""),
@@ -204,7 +210,18 @@ impl SyntaxHighlighter {
         selected_range: &Range<usize>,
         full_text: &SharedString,
         new_text: &str,
-        cx: &mut App,
+        _: &mut App,
+    ) {
+        self.update_text(selected_range, full_text, new_text);
+    }
+
+    /// Same as [`Self::update`], but it does not need the [`App`], so the large text
+    /// can be highlighted in the background thread.
+    pub(crate) fn update_text(
+        &mut self,
+        selected_range: &Range<usize>,
+        full_text: &SharedString,
+        new_text: &str,
     ) {
         if &self.text == full_text {
             return;
@@ -247,7 +264,7 @@ impl SyntaxHighlighter {
         self.text = full_text.clone();
 
         // let measure = Measure::new("build_styles");
-        self.build_styles(changed_ranges, changed_len, cx);
+        self.build_styles(changed_ranges, changed_len);
         // measure.end();
     }
 
@@ -256,7 +273,6 @@ impl SyntaxHighlighter {
         &mut self,
         changed_ranges: Option<impl ExactSizeIterator<Item = tree_sitter::Range>>,
         changed_len: isize,
-        cx: &mut App,
     ) {
         let Some(tree) = &self.old_tree else {
             return;
@@ -346,7 +362,7 @@ impl SyntaxHighlighter {
             let (language_name, content_node, _) = self.injection_for_match(None, query, m, source);
             if let Some(language_name) = language_name {
                 if let Some(content_node) = content_node {
-                    let styles = self.handle_injection(&language_name, content_node, source, cx);
+                    let styles = self.handle_injection(&language_name, content_node, source);
                     for (node_range, highlight_name) in styles {
                         self.cache.insert(
                             node_range.start,
@@ -407,7 +423,6 @@ impl SyntaxHighlighter {
         injection_language: &str,
         node: Node,
         source: &[u8],
-        cx: &App,
     ) -> Vec<(Range<usize>, String)> {
         let start_offset = node.start_byte();
         let end_offset = node.end_byte();
@@ -421,11 +436,11 @@ impl SyntaxHighlighter {
         if content.is_empty() {
             return cache;
         };
-        let Some(config) = LanguageRegistry::global(cx).language(injection_language) else {
+        let Some(language) = self.injection_languages.get(injection_language) else {
             return cache;
         };
         let mut parser = Parser::new();
-        if parser.set_language(&config.language).is_err() {
+        if parser.set_language(language).is_err() {
             return cache;
         }
         let Some(tree) = parser.parse(content, None) else {
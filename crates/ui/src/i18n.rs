@@ -0,0 +1,388 @@
+//! The translation layer of the built-in component strings.
+//!
+//! The built-in translations are in `locales/ui.yml`, use [`set_translator`] to
+//! provide the translations for other languages or to override the built-in ones.
+//!
+//! The translations support the `%{name}` placeholders and a subset of the ICU MessageFormat,
+//! see [`format_message`].
+use std::sync::{Arc, RwLock};
+
+use gpui::SharedString;
+
+type Translator = dyn Fn(&str, &str) -> Option<SharedString> + Send + Sync + 'static;
+
+static TRANSLATOR: RwLock<Option<Arc<Translator>>> = RwLock::new(None);
+
+/// Set a custom translator for the built-in component strings.
+///
+/// The translator receives the current locale and the key (e.g.: `Modal.ok`),
+/// return `None` to fallback to the built-in translations.
+///
+/// The text may contain the `%{name}` placeholders, e.g.: `%{count} minutes`.
+///
+/// ```ignore
+/// gpui_component::set_translator(|locale, key| match (locale, key) {
+///     ("fr", "Modal.ok") => Some("D'accord".into()),
+///     ("fr", "Modal.cancel") => Some("Annuler".into()),
+///     _ => None,
+/// });
+/// ```
+pub fn set_translator(
+    translator: impl Fn(&str, &str) -> Option<SharedString> + Send + Sync + 'static,
+) {
+    if let Ok(mut current) = TRANSLATOR.write() {
+        *current = Some(Arc::new(translator));
+    }
+}
+
+/// Returns the translation of the key for the current locale.
+pub(crate) fn translate(key: &str) -> SharedString {
+    let translator = TRANSLATOR.read().ok().and_then(|t| t.clone());
+    if let Some(translator) = translator {
+        if let Some(text) = translator(&crate::locale(), key) {
+            return text;
+        }
+    }
+
+    rust_i18n::t!(key).into()
+}
+
+/// Format the message of the text, then replace the `%{name}` placeholders with the values.
+///
+/// The message is formatted first, so the braces in the values are kept as is.
+pub(crate) fn interpolate(text: &str, args: &[(&str, String)]) -> SharedString {
+    let text = if text.contains('{') {
+        format_message_with_locale(&crate::locale(), text, args)
+    } else {
+        text.to_string()
+    };
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text.as_str();
+    while let Some(start) = rest.find("%{") {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let value = rest[2..].find('}').and_then(|end| {
+            let name = &rest[2..2 + end];
+            let value = args.iter().find(|(arg, _)| *arg == name)?;
+            Some((value, end + 3))
+        });
+        match value {
+            Some(((_, value), len)) => {
+                out.push_str(value);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push_str("%{");
+                rest = &rest[2..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out.into()
+}
+
+/// The plural category of the number, see the [CLDR plural rules].
+///
+/// [CLDR plural rules]: https://cldr.unicode.org/index/cldr-spec/plural-rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// Returns the plural category of the number for the locale, e.g.: `en`, `zh-CN`.
+    pub fn of(locale: &str, n: f64) -> Self {
+        let language = locale.split(['-', '_']).next().unwrap_or_default();
+        let is_int = n.fract() == 0.;
+        let i = n.abs().trunc() as u64;
+
+        match language {
+            "zh" | "ja" | "ko" | "th" | "vi" | "id" | "ms" => Self::Other,
+            "fr" | "pt" if n.abs() < 2. => Self::One,
+            "ru" | "uk" | "be" if is_int => match (i % 10, i % 100) {
+                (1, r) if r != 11 => Self::One,
+                (2..=4, r) if !(12..=14).contains(&r) => Self::Few,
+                _ => Self::Many,
+            },
+            "pl" if is_int => match (i, i % 10, i % 100) {
+                (1, _, _) => Self::One,
+                (_, 2..=4, r) if !(12..=14).contains(&r) => Self::Few,
+                _ => Self::Many,
+            },
+            "ar" if is_int => match (i, i % 100) {
+                (0, _) => Self::Zero,
+                (1, _) => Self::One,
+                (2, _) => Self::Two,
+                (_, 3..=10) => Self::Few,
+                (_, 11..=99) => Self::Many,
+                _ => Self::Other,
+            },
+            _ if is_int && i == 1 => Self::One,
+            _ => Self::Other,
+        }
+    }
+
+    fn keyword(&self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::One => "one",
+            Self::Two => "two",
+            Self::Few => "few",
+            Self::Many => "many",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Format the message with the current locale, the message is a subset of the ICU MessageFormat:
+///
+/// - `{name}`: the argument value.
+/// - `{name, plural, =0 {...} one {...} other {...}}`: choose by the exact value or the plural category,
+///   the `#` in the chosen message is replaced by the value.
+/// - `{name, select, male {...} other {...}}`: choose by the value, fallback to `other`.
+///
+/// ```ignore
+/// let text = gpui_component::format_message(
+///     "{count, plural, =0 {No items} one {# item} other {# items}}",
+///     &[("count", 3.to_string())],
+/// );
+/// assert_eq!(text, "3 items");
+/// ```
+pub fn format_message(message: &str, args: &[(&str, String)]) -> SharedString {
+    format_message_with_locale(&crate::locale(), message, args).into()
+}
+
+pub(crate) fn format_message_with_locale(
+    locale: &str,
+    message: &str,
+    args: &[(&str, String)],
+) -> String {
+    format_part(locale, message, args, None)
+}
+
+fn format_part(locale: &str, message: &str, args: &[(&str, String)], hash: Option<&str>) -> String {
+    let mut out = String::with_capacity(message.len());
+    let mut rest = message;
+
+    while let Some(c) = rest.chars().next() {
+        // Keep the `%{name}` placeholders, they are replaced by `interpolate`.
+        if c == '%' && rest[1..].starts_with('{') {
+            if let Some(end) = matching_brace(&rest[1..]) {
+                out.push_str(&rest[..end + 2]);
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+
+        if c == '#' {
+            if let Some(hash) = hash {
+                out.push_str(hash);
+                rest = &rest[1..];
+                continue;
+            }
+        }
+
+        if c != '{' {
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let Some(end) = matching_brace(rest) else {
+            // Unbalanced braces, keep the rest as is.
+            out.push_str(rest);
+            break;
+        };
+
+        out.push_str(&format_argument(locale, &rest[1..end], args));
+        rest = &rest[end + 1..];
+    }
+
+    out
+}
+
+/// Format the argument body (without the outer braces), e.g.: `count, plural, one {# item} other {# items}`.
+fn format_argument(locale: &str, body: &str, args: &[(&str, String)]) -> String {
+    let mut parts = body.splitn(3, ',');
+    let name = parts.next().unwrap_or_default().trim();
+    let kind = parts.next().map(|kind| kind.trim());
+    let options = parts.next().unwrap_or_default();
+
+    let Some(value) = args
+        .iter()
+        .find(|(arg, _)| *arg == name)
+        .map(|(_, value)| value.as_str())
+    else {
+        // Keep the unknown argument as is.
+        return format!("{{{}}}", body);
+    };
+
+    let options = parse_options(options);
+    let chosen = match kind {
+        Some("plural") => {
+            let n = value.trim().parse::<f64>().unwrap_or_default();
+            let exact = format!("={}", value.trim());
+            let category = PluralCategory::of(locale, n).keyword();
+            options
+                .iter()
+                .find(|(key, _)| *key == exact)
+                .or_else(|| options.iter().find(|(key, _)| *key == category))
+                .or_else(|| options.iter().find(|(key, _)| *key == "other"))
+                .map(|(_, message)| format_part(locale, message, args, Some(value)))
+        }
+        Some("select") => options
+            .iter()
+            .find(|(key, _)| *key == value)
+            .or_else(|| options.iter().find(|(key, _)| *key == "other"))
+            .map(|(_, message)| format_part(locale, message, args, None)),
+        _ => None,
+    };
+
+    chosen.unwrap_or_else(|| value.to_string())
+}
+
+/// Parse the options like `one {# item} other {# items}` to `[("one", "# item"), ("other", "# items")]`.
+fn parse_options(source: &str) -> Vec<(&str, &str)> {
+    let mut options = vec![];
+    let mut rest = source;
+
+    while let Some(start) = rest.find('{') {
+        let key = rest[..start].trim();
+        let Some(end) = matching_brace(&rest[start..]) else {
+            break;
+        };
+
+        options.push((key, &rest[start + 1..start + end]));
+        rest = &rest[start + end + 1..];
+    }
+
+    options
+}
+
+/// Returns the byte index of the brace that closes the `{` at the start of the text.
+fn matching_brace(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (ix, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(ix);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Translate the key with the current locale, like `rust_i18n::t!`.
+///
+/// ```ignore
+/// t!("Modal.ok");
+/// t!("Locale.minutes", count = 3);
+/// ```
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::translate($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::interpolate(
+            &$crate::i18n::translate($key),
+            &[$((stringify!($name), $value.to_string())),+],
+        )
+    };
+}
+
+pub(crate) use t;
+
+#[cfg(test)]
+mod tests {
+    use super::{format_message_with_locale, interpolate, PluralCategory};
+
+    #[test]
+    fn test_interpolate() {
+        assert_eq!(interpolate("Hello", &[]).to_string(), "Hello");
+        assert_eq!(
+            interpolate("%{count} minutes", &[("count", "3".to_string())]).to_string(),
+            "3 minutes"
+        );
+        assert_eq!(
+            interpolate(
+                "%{a} and %{b}, %{a}",
+                &[("a", "foo".to_string()), ("b", "bar".to_string())]
+            )
+            .to_string(),
+            "foo and bar, foo"
+        );
+        // The braces in the values are not formatted.
+        assert_eq!(
+            interpolate("%{name}: %{other}", &[("name", "{count}".to_string())]).to_string(),
+            "{count}: %{other}"
+        );
+    }
+
+    #[test]
+    fn test_plural_category() {
+        assert_eq!(PluralCategory::of("en", 1.), PluralCategory::One);
+        assert_eq!(PluralCategory::of("en", 0.), PluralCategory::Other);
+        assert_eq!(PluralCategory::of("en", 1.5), PluralCategory::Other);
+        assert_eq!(PluralCategory::of("zh-CN", 1.), PluralCategory::Other);
+        assert_eq!(PluralCategory::of("fr", 0.), PluralCategory::One);
+        assert_eq!(PluralCategory::of("ru", 21.), PluralCategory::One);
+        assert_eq!(PluralCategory::of("ru", 3.), PluralCategory::Few);
+        assert_eq!(PluralCategory::of("ru", 11.), PluralCategory::Many);
+        assert_eq!(PluralCategory::of("pl", 22.), PluralCategory::Few);
+        assert_eq!(PluralCategory::of("pl", 25.), PluralCategory::Many);
+    }
+
+    #[test]
+    fn test_format_message() {
+        let message = "{count, plural, =0 {No items} one {# item} other {# items}}";
+        let format = |locale: &str, count: usize| {
+            format_message_with_locale(locale, message, &[("count", count.to_string())])
+        };
+        assert_eq!(format("en", 0), "No items");
+        assert_eq!(format("en", 1), "1 item");
+        assert_eq!(format("en", 5), "5 items");
+        assert_eq!(format("ja", 1), "1 items");
+
+        assert_eq!(
+            format_message_with_locale(
+                "en",
+                "{name} selected {count, plural, one {# file} other {# files}}.",
+                &[("name", "Jason".to_string()), ("count", "2".to_string())]
+            ),
+            "Jason selected 2 files."
+        );
+        assert_eq!(
+            format_message_with_locale(
+                "en",
+                "{gender, select, female {She} male {He} other {They}} liked it",
+                &[("gender", "unknown".to_string())]
+            ),
+            "They liked it"
+        );
+        assert_eq!(
+            format_message_with_locale("en", "Hello {name}", &[]),
+            "Hello {name}"
+        );
+        assert_eq!(
+            interpolate(
+                "%{name}: {count, plural, one {# item} other {# items}}",
+                &[("name", "Cart".to_string()), ("count", "1".to_string())]
+            )
+            .to_string(),
+            "Cart: 1 item"
+        );
+    }
+}
@@ -1,9 +1,13 @@
-use crate::{ActiveTheme, Sizable, Size};
+use std::collections::HashMap;
+
+use crate::{snap_to_pixels, ActiveTheme, Sizable, Size};
 use gpui::{
-    prelude::FluentBuilder as _, svg, AnyElement, App, AppContext, Context, Entity, Hsla,
-    IntoElement, Radians, Render, RenderOnce, SharedString, StyleRefinement, Styled, Svg,
-    Transformation, Window,
+    prelude::FluentBuilder as _, rems, svg, AnyElement, App, AppContext, Context, Entity, Global,
+    Hsla, IntoElement, Pixels, Radians, Render, RenderOnce, SharedString, StyleRefinement, Styled,
+    Svg, Transformation, Window,
 };
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 #[derive(IntoElement, Clone)]
 pub enum IconName {
@@ -46,6 +50,7 @@ pub enum IconName {
     Globe,
     Heart,
     HeartOff,
+    ImageOff,
     Inbox,
     Info,
     Inspector,
@@ -130,6 +135,7 @@ impl IconName {
             Self::Globe => "icons/globe.svg",
             Self::Heart => "icons/heart.svg",
             Self::HeartOff => "icons/heart-off.svg",
+            Self::ImageOff => "icons/image-off.svg",
             Self::Inbox => "icons/inbox.svg",
             Self::Info => "icons/info.svg",
             Self::Inspector => "icons/inspector.svg",
@@ -174,14 +180,35 @@ impl IconName {
         .into()
     }
 
+    /// Return the name of the icon, e.g.: `arrow-down`.
+    ///
+    /// Register an icon with the same name to override the built-in icon.
+    pub fn name(self) -> SharedString {
+        self.path()
+            .trim_start_matches("icons/")
+            .trim_end_matches(".svg")
+            .to_string()
+            .into()
+    }
+
     /// Return the icon as a Entity<Icon>
     pub fn view(self, cx: &mut App) -> Entity<Icon> {
         Icon::build(self).view(cx)
     }
 }
 
-impl From<IconName> for Icon {
-    fn from(val: IconName) -> Self {
+impl IconNamed for IconName {
+    fn path(&self) -> SharedString {
+        self.clone().path()
+    }
+
+    fn name(&self) -> Option<SharedString> {
+        Some(self.clone().name())
+    }
+}
+
+impl<T: IconNamed> From<T> for Icon {
+    fn from(val: T) -> Self {
         Icon::build(val)
     }
 }
@@ -198,11 +225,99 @@ impl RenderOnce for IconName {
     }
 }
 
+/// The stroke weight of the icons.
+///
+/// The variants are registered by [`register_icon_variants`], fallback to the `Regular` icon.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum IconWeight {
+    Light,
+    #[default]
+    Regular,
+    Bold,
+}
+
+/// A registry of the custom icons, the icons are resolved by name when rendering.
+#[derive(Default)]
+pub struct IconRegistry {
+    icons: HashMap<(SharedString, IconWeight), SharedString>,
+}
+
+impl Global for IconRegistry {}
+
+impl IconRegistry {
+    pub fn global(cx: &App) -> Option<&Self> {
+        cx.try_global::<IconRegistry>()
+    }
+
+    pub fn global_mut(cx: &mut App) -> &mut Self {
+        cx.default_global::<IconRegistry>()
+    }
+
+    /// Register the icon path of the Assets bundle by name.
+    pub fn register(&mut self, name: impl Into<SharedString>, path: impl Into<SharedString>) {
+        self.register_variant(name, IconWeight::Regular, path);
+    }
+
+    /// Register the icon path of the Assets bundle by name for the weight.
+    pub fn register_variant(
+        &mut self,
+        name: impl Into<SharedString>,
+        weight: IconWeight,
+        path: impl Into<SharedString>,
+    ) {
+        self.icons.insert((name.into(), weight), path.into());
+    }
+
+    /// Returns the icon path of the name and weight, fallback to the `Regular` weight.
+    pub fn resolve(&self, name: &SharedString, weight: IconWeight) -> Option<SharedString> {
+        self.icons
+            .get(&(name.clone(), weight))
+            .or_else(|| self.icons.get(&(name.clone(), IconWeight::Regular)))
+            .cloned()
+    }
+}
+
+/// Register a set of icons by name, the name can be used by [`Icon::named`].
+///
+/// The built-in icons can be overridden by registering the same name, e.g.: `arrow-down`.
+///
+/// ```ignore
+/// gpui_component::register_icons(cx, [
+///     ("brand-github", "icons/brand/github.svg"),
+///     ("brand-gitlab", "icons/brand/gitlab.svg"),
+/// ]);
+/// ```
+pub fn register_icons<N, P>(cx: &mut App, icons: impl IntoIterator<Item = (N, P)>)
+where
+    N: Into<SharedString>,
+    P: Into<SharedString>,
+{
+    register_icon_variants(cx, IconWeight::Regular, icons);
+}
+
+/// Register a set of icons by name for the weight, see also [`register_icons`].
+pub fn register_icon_variants<N, P>(
+    cx: &mut App,
+    weight: IconWeight,
+    icons: impl IntoIterator<Item = (N, P)>,
+) where
+    N: Into<SharedString>,
+    P: Into<SharedString>,
+{
+    let registry = IconRegistry::global_mut(cx);
+    for (name, path) in icons {
+        registry.register_variant(name, weight, path);
+    }
+}
+
 #[derive(IntoElement)]
 pub struct Icon {
     base: Svg,
     style: StyleRefinement,
     path: SharedString,
+    /// The name to resolve the path from the [`IconRegistry`].
+    name: Option<SharedString>,
+    weight: Option<IconWeight>,
     text_color: Option<Hsla>,
     size: Option<Size>,
     rotation: Option<Radians>,
@@ -214,6 +329,8 @@ impl Default for Icon {
             base: svg().flex_none().size_4(),
             style: StyleRefinement::default(),
             path: "".into(),
+            name: None,
+            weight: None,
             text_color: None,
             size: None,
             rotation: None,
@@ -225,6 +342,8 @@ impl Clone for Icon {
     fn clone(&self) -> Self {
         let mut this = Self::default().path(self.path.clone());
         this.style = self.style.clone();
+        this.name = self.name.clone();
+        this.weight = self.weight;
         this.rotation = self.rotation;
         this.size = self.size;
         this.text_color = self.text_color;
@@ -232,8 +351,15 @@ impl Clone for Icon {
     }
 }
 
+/// A trait for the icon sets, implement it for your enum to use it as an [`Icon`].
 pub trait IconNamed {
+    /// The icon path of the Assets bundle.
     fn path(&self) -> SharedString;
+
+    /// The name to lookup the [`IconRegistry`] before using the path, default is None.
+    fn name(&self) -> Option<SharedString> {
+        None
+    }
 }
 
 impl Icon {
@@ -241,8 +367,17 @@ impl Icon {
         icon.into()
     }
 
-    fn build(name: IconName) -> Self {
-        Self::default().path(name.path())
+    fn build(icon: impl IconNamed) -> Self {
+        let mut this = Self::default().path(icon.path());
+        this.name = icon.name();
+        this
+    }
+
+    /// Create an icon by the name registered in the [`IconRegistry`].
+    pub fn named(name: impl Into<SharedString>) -> Self {
+        let mut this = Self::default();
+        this.name = Some(name.into());
+        this
     }
 
     /// Set the icon path of the Assets bundle
@@ -250,9 +385,42 @@ impl Icon {
     /// For example: `icons/foo.svg`
     pub fn path(mut self, path: impl Into<SharedString>) -> Self {
         self.path = path.into();
+        self.name = None;
+        self
+    }
+
+    /// Set the stroke weight of the icon, default is the theme `icon_weight`.
+    pub fn weight(mut self, weight: IconWeight) -> Self {
+        self.weight = Some(weight);
         self
     }
 
+    /// Returns the size snapped to the physical pixels, to keep the icon crisp at the fractional scale factors.
+    fn pixel_size(&self, window: &Window) -> Option<Pixels> {
+        let rem_size = window.rem_size();
+        let size = match self.size? {
+            Size::Size(px) => px,
+            Size::XSmall => rems(0.75).to_pixels(rem_size),
+            Size::Small => rems(0.875).to_pixels(rem_size),
+            Size::Medium => rems(1.).to_pixels(rem_size),
+            Size::Large => rems(1.5).to_pixels(rem_size),
+        };
+
+        Some(snap_to_pixels(size, window.scale_factor()))
+    }
+
+    /// Returns the path to render, resolved from the [`IconRegistry`] first.
+    fn resolved_path(&self, cx: &App) -> SharedString {
+        let Some(name) = self.name.as_ref() else {
+            return self.path.clone();
+        };
+        let weight = self.weight.unwrap_or(cx.theme().icon_weight);
+
+        IconRegistry::global(cx)
+            .and_then(|registry| registry.resolve(name, weight))
+            .unwrap_or_else(|| self.path.clone())
+    }
+
     /// Create a new view for the icon
     pub fn view(self, cx: &mut App) -> Entity<Icon> {
         cx.new(|_| self)
@@ -295,7 +463,9 @@ impl Sizable for Icon {
 }
 
 impl RenderOnce for Icon {
-    fn render(self, window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let path = self.resolved_path(cx);
+        let size = self.pixel_size(window);
         let text_color = self.text_color.unwrap_or_else(|| window.text_style().color);
         let text_size = window.text_style().font_size.to_pixels(window.rem_size());
         let has_base_size = self.style.size.width.is_some() || self.style.size.height.is_some();
@@ -305,15 +475,11 @@ impl RenderOnce for Icon {
 
         base.flex_shrink_0()
             .text_color(text_color)
-            .when(!has_base_size, |this| this.size(text_size))
-            .when_some(self.size, |this, size| match size {
-                Size::Size(px) => this.size(px),
-                Size::XSmall => this.size_3(),
-                Size::Small => this.size_3p5(),
-                Size::Medium => this.size_4(),
-                Size::Large => this.size_6(),
+            .when(!has_base_size, |this| {
+                this.size(snap_to_pixels(text_size, window.scale_factor()))
             })
-            .path(self.path)
+            .when_some(size, |this, size| this.size(size))
+            .path(path)
     }
 }
 
@@ -325,6 +491,7 @@ impl From<Icon> for AnyElement {
 
 impl Render for Icon {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let size = self.pixel_size(window);
         let text_color = self.text_color.unwrap_or_else(|| cx.theme().foreground);
         let text_size = window.text_style().font_size.to_pixels(window.rem_size());
         let has_base_size = self.style.size.width.is_some() || self.style.size.height.is_some();
@@ -334,17 +501,49 @@ impl Render for Icon {
 
         base.flex_shrink_0()
             .text_color(text_color)
-            .when(!has_base_size, |this| this.size(text_size))
-            .when_some(self.size, |this, size| match size {
-                Size::Size(px) => this.size(px),
-                Size::XSmall => this.size_3(),
-                Size::Small => this.size_3p5(),
-                Size::Medium => this.size_4(),
-                Size::Large => this.size_6(),
+            .when(!has_base_size, |this| {
+                this.size(snap_to_pixels(text_size, window.scale_factor()))
             })
-            .path(self.path.clone())
+            .when_some(size, |this, size| this.size(size))
+            .path(self.resolved_path(cx))
             .when_some(self.rotation, |this, rotation| {
                 this.with_transformation(Transformation::rotate(rotation))
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{IconName, IconRegistry, IconWeight};
+
+    #[test]
+    fn test_icon_name() {
+        assert_eq!(IconName::ArrowDown.name().to_string(), "arrow-down");
+        assert_eq!(IconName::ALargeSmall.name().to_string(), "a-large-small");
+    }
+
+    #[test]
+    fn test_registry_resolve() {
+        let mut registry = IconRegistry::default();
+        registry.register("github", "icons/github.svg");
+        registry.register_variant("github", IconWeight::Bold, "icons/github-bold.svg");
+
+        let name = "github".into();
+        assert_eq!(
+            registry.resolve(&name, IconWeight::Regular),
+            Some("icons/github.svg".into())
+        );
+        assert_eq!(
+            registry.resolve(&name, IconWeight::Bold),
+            Some("icons/github-bold.svg".into())
+        );
+        assert_eq!(
+            registry.resolve(&name, IconWeight::Light),
+            Some("icons/github.svg".into())
+        );
+        assert_eq!(
+            registry.resolve(&"gitlab".into(), IconWeight::Regular),
+            None
+        );
+    }
+}
@@ -0,0 +1,403 @@
+//! The shared image cache of the [`Avatar`](crate::avatar::Avatar) and the images in the
+//! [`TextView`](crate::text::TextView).
+//!
+//! The images are fetched and decoded in the background, downscaled to the display size,
+//! and kept in memory until the memory budget is reached, then the least recently used
+//! images are evicted first.
+//!
+//! The image decoded in a larger size is reused for the smaller display sizes, so the image
+//! is only decoded again when the display size grows (e.g.: zoomed in).
+//!
+//! ```ignore
+//! img(cached_image(url, Some(px(48.)), Some(px(48.))))
+//! ```
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use gpui::{
+    App, AssetLogger, EntityId, Global, ImageAssetLoader, ImageCacheError, ImageSource, Pixels,
+    RenderImage, Resource, Task, Window,
+};
+use image::{imageops::FilterType, Frame, RgbaImage};
+use smallvec::SmallVec;
+
+/// The default memory budget (in bytes) of the decoded images.
+const DEFAULT_BUDGET: usize = 128 * 1024 * 1024;
+/// The display sizes are rounded up to this step (in device pixels), to avoid decoding
+/// the image again on each resize.
+const SIZE_STEP: u32 = 128;
+
+type ImageResult = Result<Arc<RenderImage>, ImageCacheError>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ImageKey {
+    resource: Resource,
+    /// The max display size in device pixels, the image is decoded to fit this size.
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+}
+
+impl ImageKey {
+    /// Returns true if the image of this key can be displayed in the size of `other`
+    /// without upscaling.
+    fn covers(&self, other: &ImageKey) -> bool {
+        fn covers_size(size: Option<u32>, other: Option<u32>) -> bool {
+            match (size, other) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(size), Some(other)) => size >= other,
+            }
+        }
+
+        self.resource == other.resource
+            && covers_size(self.max_width, other.max_width)
+            && covers_size(self.max_height, other.max_height)
+    }
+}
+
+enum ImageEntry {
+    Loading {
+        /// The views to notify when the image is loaded.
+        waiters: Vec<EntityId>,
+        _task: Task<()>,
+    },
+    Loaded(ImageResult),
+}
+
+/// The global LRU cache of the decoded images.
+pub struct ImageCache {
+    entries: HashMap<ImageKey, ImageEntry>,
+    /// The keys of the loaded images, the least recently used first.
+    usages: VecDeque<ImageKey>,
+    /// The keys displayed by the image of the larger key.
+    aliases: HashMap<ImageKey, ImageKey>,
+    used_bytes: usize,
+    budget: usize,
+}
+
+impl Global for ImageCache {}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            usages: VecDeque::new(),
+            aliases: HashMap::new(),
+            used_bytes: 0,
+            budget: DEFAULT_BUDGET,
+        }
+    }
+}
+
+impl ImageCache {
+    /// Set the memory budget (in bytes) of the decoded images, default is 128 MB.
+    pub fn set_budget(budget: usize, cx: &mut App) {
+        let this = cx.default_global::<Self>();
+        this.budget = budget;
+        let evicted = this.evict();
+        for image in evicted {
+            cx.drop_image(image, None);
+        }
+    }
+
+    /// Returns the memory (in bytes) used by the decoded images.
+    pub fn used_bytes(cx: &App) -> usize {
+        cx.try_global::<Self>().map_or(0, |this| this.used_bytes)
+    }
+
+    /// Remove the images of the `resource` (in all sizes), they will be fetched again on the next render.
+    pub fn invalidate(resource: impl Into<Resource>, cx: &mut App) {
+        let resource = resource.into();
+        Self::remove_where(|key| key.resource == resource, cx);
+    }
+
+    /// Remove all the images from the cache.
+    pub fn clear(cx: &mut App) {
+        Self::remove_where(|_| true, cx);
+    }
+
+    fn remove_where(predicate: impl Fn(&ImageKey) -> bool, cx: &mut App) {
+        let this = cx.default_global::<Self>();
+        let keys = this
+            .entries
+            .keys()
+            .filter(|key| predicate(key))
+            .cloned()
+            .collect::<Vec<_>>();
+        if keys.is_empty() {
+            return;
+        }
+
+        let mut removed = vec![];
+        for key in keys {
+            if let Some(ImageEntry::Loaded(Ok(image))) = this.entries.remove(&key) {
+                this.used_bytes = this.used_bytes.saturating_sub(image_bytes(&image));
+                removed.push(image);
+            }
+        }
+        this.usages.retain(|key| !predicate(key));
+        this.aliases
+            .retain(|key, target| !predicate(key) && !predicate(target));
+
+        for image in removed {
+            cx.drop_image(image, None);
+        }
+        cx.refresh_windows();
+    }
+
+    /// Mark the `key` as the most recently used.
+    fn touch(&mut self, key: &ImageKey) {
+        if let Some(ix) = self.usages.iter().position(|k| k == key) {
+            if let Some(key) = self.usages.remove(ix) {
+                self.usages.push_back(key);
+            }
+        }
+    }
+
+    /// Evict the least recently used images until the budget is reached,
+    /// the most recently used image is always kept.
+    fn evict(&mut self) -> Vec<Arc<RenderImage>> {
+        let mut evicted = vec![];
+        while self.used_bytes > self.budget && self.usages.len() > 1 {
+            let Some(key) = self.usages.pop_front() else {
+                break;
+            };
+            if let Some(ImageEntry::Loaded(Ok(image))) = self.entries.remove(&key) {
+                self.used_bytes = self.used_bytes.saturating_sub(image_bytes(&image));
+                evicted.push(image);
+            }
+            self.aliases.retain(|_, target| *target != key);
+        }
+        evicted
+    }
+
+    fn load(key: ImageKey, window: &mut Window, cx: &mut App) -> Option<ImageResult> {
+        let this = cx.default_global::<Self>();
+        if let Some(target) = this.aliases.get(&key) {
+            if let Some(ImageEntry::Loaded(result)) = this.entries.get(target) {
+                let (target, result) = (target.clone(), result.clone());
+                this.touch(&target);
+                return Some(result);
+            }
+        }
+
+        match this.entries.get_mut(&key) {
+            Some(ImageEntry::Loaded(result)) => {
+                let result = result.clone();
+                this.touch(&key);
+                return Some(result);
+            }
+            Some(ImageEntry::Loading { waiters, .. }) => {
+                let entity = window.current_view();
+                if !waiters.contains(&entity) {
+                    waiters.push(entity);
+                }
+                return None;
+            }
+            None => {}
+        }
+
+        // Reuse the image decoded in a larger size.
+        if let Some(target) = this.usages.iter().rev().find(|k| k.covers(&key)).cloned() {
+            if let Some(ImageEntry::Loaded(result)) = this.entries.get(&target) {
+                let result = result.clone();
+                this.touch(&target);
+                this.aliases.insert(key, target);
+                return Some(result);
+            }
+        }
+
+        let fetch = AssetLogger::<ImageAssetLoader>::load(key.resource.clone(), cx);
+        let (max_width, max_height) = (key.max_width, key.max_height);
+        let decode = cx.background_executor().spawn(async move {
+            let image = fetch.await?;
+            Ok(resize_to_fit(image, max_width, max_height))
+        });
+
+        // Spawn on the app instead of the window, the image is shared by all windows and the
+        // entry must not be left loading if the window is closed.
+        let entity = window.current_view();
+        let task = cx.spawn({
+            let key = key.clone();
+            async move |cx| {
+                let result = decode.await;
+                _ = cx.update(|cx| Self::finish_load(key, result, cx));
+            }
+        });
+
+        cx.global_mut::<Self>().entries.insert(
+            key,
+            ImageEntry::Loading {
+                waiters: vec![entity],
+                _task: task,
+            },
+        );
+        None
+    }
+
+    fn finish_load(key: ImageKey, result: ImageResult, cx: &mut App) {
+        let this = cx.global_mut::<Self>();
+        // The entry is removed if it was invalidated while loading.
+        let Some(ImageEntry::Loading { waiters, .. }) = this.entries.remove(&key) else {
+            return;
+        };
+
+        if let Ok(image) = &result {
+            this.used_bytes += image_bytes(image);
+            this.usages.push_back(key.clone());
+        }
+        this.entries.insert(key, ImageEntry::Loaded(result));
+
+        let evicted = this.evict();
+        for image in evicted {
+            cx.drop_image(image, None);
+        }
+        for entity in waiters {
+            cx.notify(entity);
+        }
+    }
+}
+
+/// Returns the [`ImageSource`] to load the `resource` by the shared [`ImageCache`].
+///
+/// The image is decoded to fit the `max_width` and `max_height` (if given), use the display
+/// size of the image to avoid keeping the full size image in memory.
+pub fn cached_image(
+    resource: impl Into<Resource>,
+    max_width: Option<Pixels>,
+    max_height: Option<Pixels>,
+) -> ImageSource {
+    let resource = resource.into();
+    ImageSource::Custom(Arc::new(move |window, cx| {
+        let scale_factor = window.scale_factor();
+        let to_device = |size: Pixels| round_up_size((size.0 * scale_factor).ceil() as u32);
+        let key = ImageKey {
+            resource: resource.clone(),
+            max_width: max_width.map(to_device),
+            max_height: max_height.map(to_device),
+        };
+
+        ImageCache::load(key, window, cx)
+    }))
+}
+
+/// Round up the size to the [`SIZE_STEP`].
+fn round_up_size(size: u32) -> u32 {
+    size.max(1).div_ceil(SIZE_STEP) * SIZE_STEP
+}
+
+fn image_bytes(image: &RenderImage) -> usize {
+    (0..image.frame_count())
+        .map(|ix| {
+            let size = image.size(ix);
+            size.width.0.max(0) as usize * size.height.0.max(0) as usize * 4
+        })
+        .sum()
+}
+
+/// Downscale the image to fit the max size, the animated images are kept as is.
+fn resize_to_fit(
+    image: Arc<RenderImage>,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+) -> Arc<RenderImage> {
+    if image.frame_count() != 1 {
+        return image;
+    }
+
+    let size = image.size(0);
+    let (width, height) = (size.width.0.max(0) as u32, size.height.0.max(0) as u32);
+    let Some((new_width, new_height)) = fit_size(width, height, max_width, max_height) else {
+        return image;
+    };
+    let Some(buffer) = image
+        .as_bytes(0)
+        .and_then(|bytes| RgbaImage::from_raw(width, height, bytes.to_vec()))
+    else {
+        return image;
+    };
+
+    // The channel order (BGRA) is not changed by the resize.
+    let resized = image::imageops::resize(&buffer, new_width, new_height, FilterType::Triangle);
+    Arc::new(RenderImage::new(SmallVec::from_elem(
+        Frame::new(resized),
+        1,
+    )))
+}
+
+/// Returns the size to downscale the image to fit the max size (keep the aspect ratio),
+/// None if the image is already fit.
+fn fit_size(
+    width: u32,
+    height: u32,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+) -> Option<(u32, u32)> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let mut scale = 1.0_f64;
+    if let Some(max_width) = max_width {
+        scale = scale.min(max_width as f64 / width as f64);
+    }
+    if let Some(max_height) = max_height {
+        scale = scale.min(max_height as f64 / height as f64);
+    }
+    if scale >= 1. {
+        return None;
+    }
+
+    Some((
+        ((width as f64 * scale).round() as u32).max(1),
+        ((height as f64 * scale).round() as u32).max(1),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::Resource;
+
+    use super::{fit_size, round_up_size, ImageKey};
+
+    #[test]
+    fn test_fit_size() {
+        assert_eq!(fit_size(100, 50, None, None), None);
+        assert_eq!(fit_size(100, 50, Some(200), Some(200)), None);
+        assert_eq!(fit_size(100, 50, Some(50), None), Some((50, 25)));
+        assert_eq!(fit_size(100, 50, None, Some(10)), Some((20, 10)));
+        assert_eq!(fit_size(400, 400, Some(96), Some(96)), Some((96, 96)));
+        assert_eq!(fit_size(1000, 1, Some(10), None), Some((10, 1)));
+        assert_eq!(fit_size(0, 0, Some(10), Some(10)), None);
+    }
+
+    #[test]
+    fn test_round_up_size() {
+        assert_eq!(round_up_size(0), 128);
+        assert_eq!(round_up_size(1), 128);
+        assert_eq!(round_up_size(128), 128);
+        assert_eq!(round_up_size(129), 256);
+    }
+
+    #[test]
+    fn test_key_covers() {
+        let key = |uri: &'static str, max_width: Option<u32>, max_height: Option<u32>| ImageKey {
+            resource: Resource::Uri(uri.into()),
+            max_width,
+            max_height,
+        };
+
+        let full = key("a.png", None, None);
+        let large = key("a.png", Some(512), None);
+        let small = key("a.png", Some(256), Some(128));
+        assert!(full.covers(&large));
+        assert!(full.covers(&small));
+        assert!(large.covers(&small));
+        assert!(large.covers(&large));
+        assert!(!small.covers(&large));
+        assert!(!large.covers(&full));
+        assert!(!key("b.png", None, None).covers(&small));
+    }
+}
@@ -547,15 +547,17 @@ impl Element for TextElement {
         let font_size = style.font_size.to_pixels(window.rem_size());
         let mut bounds = bounds;
 
+        let foreground = if state.disabled {
+            cx.theme().muted_foreground
+        } else {
+            cx.theme().foreground
+        };
         let (display_text, text_color) = if is_empty {
             (placeholder, cx.theme().muted_foreground)
         } else if state.masked {
-            (
-                "*".repeat(text.chars().count()).into(),
-                cx.theme().foreground,
-            )
+            ("*".repeat(text.chars().count()).into(), foreground)
         } else {
-            (text.clone(), cx.theme().foreground)
+            (text.clone(), foreground)
         };
 
         let text_style = window.text_style();
@@ -6,7 +6,8 @@ use gpui::{
 
 use crate::{
     button::{Button, ButtonVariants as _},
-    h_flex, ActiveTheme, Disableable, IconName, Sizable, Size, StyleSized, StyledExt as _,
+    h_flex, ActiveTheme, Disableable, IconName, ReadOnly, Sizable, Size, StyleSized,
+    StyledExt as _,
 };
 
 use super::{InputState, TextInput};
@@ -31,6 +32,7 @@ pub struct NumberInput {
     suffix: Option<AnyElement>,
     appearance: bool,
     disabled: bool,
+    read_only: bool,
 }
 
 impl NumberInput {
@@ -44,6 +46,7 @@ impl NumberInput {
             suffix: None,
             appearance: true,
             disabled: false,
+            read_only: false,
         }
     }
 
@@ -93,6 +96,13 @@ impl Disableable for NumberInput {
     }
 }
 
+impl ReadOnly for NumberInput {
+    fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+}
+
 impl InputState {
     fn on_action_increment(&mut self, _: &Increment, window: &mut Window, cx: &mut Context<Self>) {
         self.on_number_input_step(StepAction::Increment, window, cx);
@@ -103,7 +113,7 @@ impl InputState {
     }
 
     fn on_number_input_step(&mut self, action: StepAction, _: &mut Window, cx: &mut Context<Self>) {
-        if self.disabled {
+        if !self.is_editable() {
             return;
         }
 
@@ -148,9 +158,12 @@ impl RenderOnce for NumberInput {
             .when(self.appearance, |this| {
                 this.bg(cx.theme().background)
                     .border_color(cx.theme().input)
-                    .border_1()
+                    .border_snapped(px(1.), window)
                     .rounded(cx.theme().radius)
             })
+            .when(self.read_only, |this| {
+                this.bg(cx.theme().muted.opacity(0.5))
+            })
             .when(self.disabled, |this| this.bg(cx.theme().muted))
             .when(focused, |this| this.focused_border(cx))
             .child(
@@ -159,7 +172,7 @@ impl RenderOnce for NumberInput {
                     .with_size(self.size.smaller())
                     .icon(IconName::Minus)
                     .compact()
-                    .disabled(self.disabled)
+                    .disabled(self.disabled || self.read_only)
                     .on_click({
                         let state = self.state.clone();
                         move |_, window, cx| {
@@ -171,6 +184,7 @@ impl RenderOnce for NumberInput {
                 TextInput::new(&self.state)
                     .appearance(false)
                     .disabled(self.disabled)
+                    .read_only(self.read_only)
                     .px(px(2.))
                     .gap_0()
                     .when_some(self.prefix, |this, prefix| this.prefix(prefix))
@@ -182,7 +196,7 @@ impl RenderOnce for NumberInput {
                     .with_size(self.size.smaller())
                     .icon(IconName::Plus)
                     .compact()
-                    .disabled(self.disabled)
+                    .disabled(self.disabled || self.read_only)
                     .on_click({
                         let state = self.state.clone();
                         move |_, window, cx| {
@@ -307,9 +307,9 @@ impl RenderOnce for OtpInput {
 
         v_flex()
             .id(("otp-input", self.state.entity_id()))
-            .track_focus(&self.state.read(cx).focus_handle)
             .when(!self.disabled, |this| {
-                this.on_key_down(window.listener_for(&self.state, OtpState::on_key_down))
+                this.track_focus(&self.state.read(cx).focus_handle)
+                    .on_key_down(window.listener_for(&self.state, OtpState::on_key_down))
             })
             .items_center()
             .child(
@@ -33,7 +33,9 @@ use super::{
 use crate::input::hover_popover::DiagnosticPopover;
 use crate::input::marker::Marker;
 use crate::input::{Cursor, LineColumn, Selection};
-use crate::{history::History, scroll::ScrollbarState, Root};
+use crate::{
+    clipboard_history, highlighter::Language, history::History, scroll::ScrollbarState, Root,
+};
 
 #[derive(Action, Clone, PartialEq, Eq, Deserialize)]
 #[action(namespace = input, no_json)]
@@ -260,6 +262,7 @@ pub struct InputState {
     pub(super) last_selected_range: Option<Selection>,
     pub(super) selecting: bool,
     pub(super) disabled: bool,
+    pub(super) read_only: bool,
     pub(super) masked: bool,
     pub(super) clean_on_escape: bool,
     pub(super) pattern: Option<regex::Regex>,
@@ -330,6 +333,7 @@ impl InputState {
             input_bounds: Bounds::default(),
             selecting: false,
             disabled: false,
+            read_only: false,
             masked: false,
             clean_on_escape: false,
             loading: false,
@@ -664,9 +668,11 @@ impl InputState {
         cx: &mut Context<Self>,
     ) {
         self.history.ignore = true;
-        let was_disabled = self.disabled;
+        // The value can be changed by the code, even if the input is not editable by the user.
+        let (was_disabled, was_read_only) = (self.disabled, self.read_only);
+        (self.disabled, self.read_only) = (false, false);
         self.replace_text(value, window, cx);
-        self.disabled = was_disabled;
+        (self.disabled, self.read_only) = (was_disabled, was_read_only);
         self.history.ignore = false;
         // Ensure cursor to start when set text
         if self.mode.is_single_line() {
@@ -724,13 +730,28 @@ impl InputState {
 
     /// Set with disabled mode.
     ///
-    /// See also: [`Self::set_disabled`], [`Self::is_disabled`].
+    /// See also: [`Self::is_disabled`].
     #[allow(unused)]
     pub(crate) fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
     }
 
+    /// Returns true if the input is disabled, it is not focusable and the text can't be selected.
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Returns true if the input is read-only, the text can be selected and copied but not changed.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns true if the text can be changed by the user.
+    pub(crate) fn is_editable(&self) -> bool {
+        !self.disabled && !self.read_only
+    }
+
     /// Set with password masked state.
     pub fn masked(mut self, masked: bool) -> Self {
         self.masked = masked;
@@ -966,7 +987,7 @@ impl InputState {
         self.select_to(Cursor::new(self.next_boundary(offset)), window, cx);
     }
 
-    pub(super) fn select_all(
+    pub(crate) fn select_all(
         &mut self,
         _: &SelectAll,
         window: &mut Window,
@@ -1636,7 +1657,20 @@ impl InputState {
         }
 
         let selected_text = self.text_for_range_utf8(self.selected_range).to_string();
-        cx.write_to_clipboard(ClipboardItem::new_string(selected_text));
+        let item = self.clipboard_item(selected_text, cx);
+        clipboard_history::write(item, cx);
+    }
+
+    /// Returns the clipboard item of the copied `text`, the Markdown editor copies the rich text.
+    fn clipboard_item(&self, text: String, cx: &mut App) -> ClipboardItem {
+        match &self.mode {
+            InputMode::CodeEditor { language, .. }
+                if Language::from_str(language) == Language::Markdown =>
+            {
+                crate::text::markdown_clipboard_item(&text, cx)
+            }
+            _ => ClipboardItem::new_string(text),
+        }
     }
 
     pub(super) fn cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
@@ -1645,12 +1679,15 @@ impl InputState {
         }
 
         let selected_text = self.text_for_range_utf8(self.selected_range).to_string();
-        cx.write_to_clipboard(ClipboardItem::new_string(selected_text));
+        let item = self.clipboard_item(selected_text, cx);
+        clipboard_history::write(item, cx);
         self.replace_text_in_range(None, "", window, cx);
     }
 
     pub(super) fn paste(&mut self, _: &Paste, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(clipboard) = cx.read_from_clipboard() {
+            // Record the copies of the other apps.
+            clipboard_history::record(&clipboard, cx);
             let mut new_text = clipboard.text().unwrap_or_default();
             if !self.mode.is_multi_line() {
                 new_text = new_text.replace('\n', "");
@@ -1981,7 +2018,8 @@ impl InputState {
 
     /// Returns the true to let InputElement to render cursor, when Input is focused and current BlinkCursor is visible.
     pub(crate) fn show_cursor(&self, window: &Window, cx: &App) -> bool {
-        self.focus_handle.is_focused(window)
+        self.is_editable()
+            && self.focus_handle.is_focused(window)
             && self.blink_cursor.read(cx).visible()
             && window.is_window_active()
     }
@@ -2157,7 +2195,7 @@ impl EntityInputHandler for InputState {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if self.disabled {
+        if !self.is_editable() {
             return;
         }
 
@@ -2204,7 +2242,7 @@ impl EntityInputHandler for InputState {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if self.disabled {
+        if !self.is_editable() {
             return;
         }
 
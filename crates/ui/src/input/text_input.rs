@@ -9,8 +9,8 @@ use crate::button::{Button, ButtonVariants as _};
 use crate::indicator::Indicator;
 use crate::input::clear_button;
 use crate::scroll::Scrollbar;
-use crate::ActiveTheme;
 use crate::{h_flex, StyledExt};
+use crate::{ActiveTheme, ReadOnly};
 use crate::{IconName, Size};
 use crate::{Sizable, StyleSized};
 
@@ -28,10 +28,19 @@ pub struct TextInput {
     cleanable: bool,
     mask_toggle: bool,
     disabled: bool,
+    read_only: bool,
     bordered: bool,
     focus_bordered: bool,
 }
 
+impl ReadOnly for TextInput {
+    /// Set to read-only, the text can be selected and copied, but can't be changed.
+    fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+}
+
 impl Sizable for TextInput {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
         self.size = size.into();
@@ -53,6 +62,7 @@ impl TextInput {
             cleanable: false,
             mask_toggle: false,
             disabled: false,
+            read_only: false,
             bordered: true,
             focus_bordered: true,
         }
@@ -111,6 +121,8 @@ impl TextInput {
     }
 
     /// Set to disable the input field.
+    ///
+    /// The disabled input is not focusable, and the text can't be selected.
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
@@ -156,6 +168,7 @@ impl RenderOnce for TextInput {
             state.mode.set_height(self.height);
             state.text_wrapper.set_font(font, font_size, cx);
             state.disabled = self.disabled;
+            state.read_only = self.read_only;
         });
 
         let state = self.state.read(cx);
@@ -168,6 +181,8 @@ impl RenderOnce for TextInput {
 
         let bg = if state.disabled {
             cx.theme().muted
+        } else if state.read_only {
+            cx.theme().muted.opacity(0.5)
         } else {
             cx.theme().background
         };
@@ -175,6 +190,7 @@ impl RenderOnce for TextInput {
         let prefix = self.prefix;
         let suffix = self.suffix;
         let show_clear_button = self.cleanable
+            && state.is_editable()
             && !state.loading
             && !state.text.is_empty()
             && state.mode.is_single_line();
@@ -184,8 +200,10 @@ impl RenderOnce for TextInput {
             .id(("input", self.state.entity_id()))
             .flex()
             .key_context(crate::input::CONTEXT)
-            .track_focus(&state.focus_handle)
             .when(!state.disabled, |this| {
+                this.track_focus(&state.focus_handle)
+            })
+            .when(state.is_editable(), |this| {
                 this.on_action(window.listener_for(&self.state, InputState::backspace))
                     .on_action(window.listener_for(&self.state, InputState::delete))
                     .on_action(
@@ -235,21 +253,30 @@ impl RenderOnce for TextInput {
             .on_action(window.listener_for(&self.state, InputState::show_character_palette))
             .on_action(window.listener_for(&self.state, InputState::copy))
             .on_key_down(window.listener_for(&self.state, InputState::on_key_down))
-            .on_mouse_down(
-                MouseButton::Left,
-                window.listener_for(&self.state, InputState::on_mouse_down),
-            )
-            .on_mouse_up(
-                MouseButton::Left,
-                window.listener_for(&self.state, InputState::on_mouse_up),
-            )
-            .on_mouse_move(window.listener_for(&self.state, InputState::on_mouse_move))
+            .when(!state.disabled, |this| {
+                this.on_mouse_down(
+                    MouseButton::Left,
+                    window.listener_for(&self.state, InputState::on_mouse_down),
+                )
+                .on_mouse_up(
+                    MouseButton::Left,
+                    window.listener_for(&self.state, InputState::on_mouse_up),
+                )
+                .on_mouse_move(window.listener_for(&self.state, InputState::on_mouse_move))
+            })
             .on_scroll_wheel(window.listener_for(&self.state, InputState::on_scroll_wheel))
             .size_full()
             .line_height(LINE_HEIGHT)
             .input_py(self.size)
             .input_h(self.size)
-            .cursor_text()
+            .map(|this| {
+                if state.disabled {
+                    this.cursor_default()
+                        .text_color(cx.theme().muted_foreground)
+                } else {
+                    this.cursor_text()
+                }
+            })
             .text_size(font_size)
             .when(state.mode.is_multi_line(), |this| {
                 this.h_auto()
@@ -260,7 +287,7 @@ impl RenderOnce for TextInput {
                     .rounded(cx.theme().radius)
                     .when(self.bordered, |this| {
                         this.border_color(cx.theme().input)
-                            .border_1()
+                            .border_snapped(px(1.), window)
                             .when(cx.theme().shadow, |this| this.shadow_xs())
                             .when(focused && self.focus_bordered, |this| {
                                 this.focused_border(cx)
@@ -1,6 +1,6 @@
 use std::ops::Range;
 
-use crate::input::LineColumn;
+use crate::{input::LineColumn, text::cjk};
 use gpui::{App, Font, LineFragment, Pixels, SharedString};
 
 #[allow(unused)]
@@ -77,9 +77,21 @@ impl TextWrapper {
             let mut prev_boundary_ix = 0;
 
             // Here only have wrapped line, if there is no wrap meet, the `line_wraps` result will empty.
-            for boundary in line_wrapper.wrap_line(&[LineFragment::text(line)], wrap_width) {
-                line_wraps.push(prev_boundary_ix..boundary.ix);
-                prev_boundary_ix = boundary.ix;
+            //
+            // Wrap the rest of the line again after each boundary, because the boundary may be
+            // adjusted to avoid starting a line with the CJK closing punctuation.
+            while let Some(boundary) = line_wrapper
+                .wrap_line(&[LineFragment::text(&line[prev_boundary_ix..])], wrap_width)
+                .next()
+            {
+                let rest = &line[prev_boundary_ix..];
+                let boundary_ix = prev_boundary_ix + cjk::adjust_line_break(rest, boundary.ix);
+                if boundary_ix <= prev_boundary_ix {
+                    break;
+                }
+
+                line_wraps.push(prev_boundary_ix..boundary_ix);
+                prev_boundary_ix = boundary_ix;
             }
 
             lines.push(LineWrap {
@@ -5,7 +5,10 @@ use gpui::{
     SharedString, StyleRefinement, Styled, StyledText, Window,
 };
 
-use crate::{ActiveTheme, StyledExt};
+use crate::{
+    text::{cjk, hyphenation, utils},
+    ActiveTheme, StyledExt,
+};
 
 const MASKED: &'static str = "•";
 
@@ -16,6 +19,8 @@ pub struct Label {
     secondary: Option<SharedString>,
     masked: bool,
     highlights_text: Option<SharedString>,
+    hyphens: bool,
+    cjk_spacing: bool,
 }
 
 impl Label {
@@ -27,6 +32,8 @@ impl Label {
             secondary: None,
             masked: false,
             highlights_text: None,
+            hyphens: false,
+            cjk_spacing: false,
         }
     }
 
@@ -47,6 +54,24 @@ impl Label {
         self
     }
 
+    /// Set to hyphenate the long words when the label is narrow, default is false.
+    ///
+    /// A hyphen is drawn at the end of the line if the line is broken at a soft hyphen.
+    ///
+    /// See also [`register_hyphenation_patterns`](crate::text::register_hyphenation_patterns).
+    pub fn hyphens(mut self, hyphens: bool) -> Self {
+        self.hyphens = hyphens;
+        self
+    }
+
+    /// Set to add the spacing between the CJK and Latin characters, default is false.
+    ///
+    /// The label is wrapped by the GPUI line wrapper, the closing punctuation may start a line.
+    pub fn cjk_spacing(mut self, cjk_spacing: bool) -> Self {
+        self.cjk_spacing = cjk_spacing;
+        self
+    }
+
     fn full_text(&self) -> SharedString {
         match &self.secondary {
             Some(secondary) => format!("{} {}", self.label, secondary).into(),
@@ -140,7 +165,7 @@ impl Styled for Label {
 }
 
 impl RenderOnce for Label {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
         let mut text = self.full_text();
         let chars_count = text.chars().count();
 
@@ -148,15 +173,47 @@ impl RenderOnce for Label {
             text = SharedString::from(MASKED.repeat(chars_count))
         };
 
-        let highlights = self.measure_highlights(text.len(), cx);
+        #[cfg(any(test, feature = "test-support"))]
+        crate::test_support::record_text(text.clone(), _window, cx);
+
+        let mut highlights = self.measure_highlights(text.len(), cx);
+
+        if self.hyphens && !self.masked {
+            if let Some((hyphenated, inserted)) = hyphenation::current_patterns()
+                .and_then(|patterns| hyphenation::hyphenate_text(&text, &patterns))
+            {
+                text = hyphenated.into();
+                for (range, _) in highlights.iter_mut().flatten() {
+                    *range = utils::remap_range(range, &inserted);
+                }
+            }
+        }
+
+        // Add the spacing between the CJK and Latin characters.
+        if self.cjk_spacing && !self.masked {
+            if let Some((prepared, inserted)) = cjk::prepare_text(&text) {
+                text = prepared.into();
+                for (range, _) in highlights.iter_mut().flatten() {
+                    *range = utils::remap_range(range, &inserted);
+                }
+            }
+        }
+
+        let styled_text =
+            StyledText::new(&text).when_some(highlights, |this, hl| this.with_highlights(hl));
+        let text_layout = styled_text.layout().clone();
+        let has_soft_hyphens = text.contains(hyphenation::SOFT_HYPHEN);
 
         div()
+            .when(has_soft_hyphens, |this| this.relative())
             .line_height(rems(1.25))
             .text_color(cx.theme().foreground)
             .refine_style(&self.style)
-            .child(
-                StyledText::new(&text).when_some(highlights, |this, hl| this.with_highlights(hl)),
-            )
+            .child(styled_text)
+            .when(has_soft_hyphens, |this| {
+                // Draw the hyphens at the line breaks of the soft hyphens.
+                this.child(hyphenation::hyphen_overlay(text.clone(), text_layout))
+            })
     }
 }
 
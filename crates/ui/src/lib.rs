@@ -1,5 +1,6 @@
 mod event;
 mod focusable;
+mod i18n;
 mod icon;
 mod index_path;
 #[cfg(any(feature = "inspector", debug_assertions))]
@@ -11,14 +12,17 @@ mod styled;
 mod svg_img;
 mod time;
 mod title_bar;
+mod type_ahead;
 mod virtual_list;
 mod window_border;
 
 pub(crate) mod actions;
 
+pub mod accessibility;
 pub mod accordion;
 pub mod alert;
 pub mod animation;
+pub mod async_view;
 pub mod avatar;
 pub mod badge;
 pub mod breadcrumb;
@@ -26,39 +30,71 @@ pub mod button;
 pub mod chart;
 pub mod checkbox;
 pub mod clipboard;
+pub mod clipboard_history;
 pub mod color_picker;
+pub mod color_swatch;
+pub mod composer;
+pub mod debug_overlay;
 pub mod description_list;
+pub mod dialog;
 pub mod divider;
 pub mod dock;
 pub mod drawer;
 pub mod dropdown;
+pub mod editable_label;
+pub mod emoji;
+pub mod emoji_picker;
+pub mod error_boundary;
+pub mod event_bus;
+pub mod file_dialog;
 pub mod form;
+pub mod graph_editor;
+pub mod grid;
 pub mod highlighter;
 pub mod history;
+pub mod image_cache;
 pub mod indicator;
 pub mod input;
 pub mod label;
 pub mod link;
 pub mod list;
+pub mod locale;
 pub mod modal;
 pub mod notification;
+pub mod notification_center;
+pub mod overlay;
 pub mod plot;
+pub mod plugin;
 pub mod popover;
+pub mod presence;
 pub mod progress;
 pub mod radio;
 pub mod resizable;
+pub mod responsive;
+pub mod router;
 pub mod scroll;
+pub mod settings;
+pub mod shortcut_input;
 pub mod sidebar;
 pub mod skeleton;
 pub mod slider;
+pub mod state;
+pub mod status_bar;
 pub mod switch;
 pub mod tab;
 pub mod table;
 pub mod tag;
+pub mod task_progress;
+pub mod telemetry;
 pub mod text;
 pub mod theme;
+pub mod toolbar;
 pub mod tooltip;
+pub mod viewport;
+pub mod windows;
 
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 #[cfg(feature = "webview")]
 pub mod webview;
 
@@ -69,12 +105,16 @@ pub use wry;
 
 pub use crate::Disableable;
 pub use event::InteractiveElementExt;
-pub use focusable::FocusableCycle;
+pub use focusable::{
+    FocusNext, FocusPrev, FocusRestore, FocusTrap, FocusTrapExt, FocusableCycle, RovingFirst,
+    RovingFocus, RovingFocusExt, RovingLast, RovingLeft, RovingNext, RovingPrev, RovingRight,
+};
+pub use i18n::{format_message, set_translator, PluralCategory};
 pub use index_path::IndexPath;
 #[cfg(any(feature = "inspector", debug_assertions))]
 pub use inspector::*;
 pub use menu::{context_menu, popup_menu};
-pub use root::{ContextModal, Root};
+pub use root::{ContextModal, ResetZoom, Root, ZoomIn, ZoomOut};
 pub use styled::*;
 pub use time::*;
 pub use title_bar::*;
@@ -96,6 +136,9 @@ rust_i18n::i18n!("locales", fallback = "en");
 /// You can initialize the UI module at your application's entry point.
 pub fn init(cx: &mut App) {
     theme::init(cx);
+    focusable::init(cx);
+    root::init(cx);
+    debug_overlay::init(cx);
     #[cfg(any(feature = "inspector", debug_assertions))]
     inspector::init(cx);
     highlighter::init(cx);
@@ -103,12 +146,17 @@ pub fn init(cx: &mut App) {
     dock::init(cx);
     drawer::init(cx);
     dropdown::init(cx);
+    composer::init(cx);
+    editable_label::init(cx);
+    grid::init(cx);
+    router::init(cx);
     input::init(cx);
     list::init(cx);
     modal::init(cx);
     popover::init(cx);
     menu::init(cx);
     table::init(cx);
+    text::init(cx);
 }
 
 #[inline]
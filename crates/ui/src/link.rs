@@ -3,7 +3,10 @@ use gpui::{
     ParentElement, RenderOnce, SharedString, StatefulInteractiveElement, StyleRefinement, Styled,
 };
 
-use crate::{ActiveTheme as _, StyledExt};
+use crate::{
+    accessibility::{self, AccessibilityProperties, AccessibilityRole, Accessible},
+    router, ActiveTheme as _, StyledExt,
+};
 
 /// A Link element like a `<a>` tag in HTML.
 #[derive(IntoElement)]
@@ -11,9 +14,11 @@ pub struct Link {
     id: ElementId,
     style: StyleRefinement,
     href: Option<SharedString>,
+    label: Option<SharedString>,
     disabled: bool,
     on_click: Option<Box<dyn Fn(&ClickEvent, &mut gpui::Window, &mut gpui::App) + 'static>>,
     children: Vec<AnyElement>,
+    accessibility: AccessibilityProperties,
 }
 
 impl Link {
@@ -22,9 +27,11 @@ impl Link {
             id: id.into(),
             style: StyleRefinement::default(),
             href: None,
+            label: None,
             on_click: None,
             disabled: false,
             children: Vec::new(),
+            accessibility: AccessibilityProperties::default(),
         }
     }
 
@@ -33,6 +40,12 @@ impl Link {
         self
     }
 
+    /// Set the text of the link, it is also the accessible name of the link.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
     pub fn on_click(
         mut self,
         handler: impl Fn(&ClickEvent, &mut gpui::Window, &mut gpui::App) + 'static,
@@ -47,6 +60,21 @@ impl Link {
     }
 }
 
+impl Accessible for Link {
+    fn accessibility_mut(&mut self) -> &mut AccessibilityProperties {
+        &mut self.accessibility
+    }
+
+    fn accessibility(&self, _: &gpui::App) -> AccessibilityProperties {
+        let mut properties = AccessibilityProperties::new(AccessibilityRole::Link)
+            .label(self.label.clone())
+            .disabled(self.disabled);
+        properties.value = self.href.clone();
+
+        properties.merge(&self.accessibility)
+    }
+}
+
 impl Styled for Link {
     fn style(&mut self) -> &mut gpui::StyleRefinement {
         &mut self.style
@@ -60,7 +88,9 @@ impl ParentElement for Link {
 }
 
 impl RenderOnce for Link {
-    fn render(self, _: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
+    fn render(self, window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
+        accessibility::record_rendered(self.accessibility(cx), window, cx);
+
         let href = self.href.clone();
         let on_click = self.on_click;
 
@@ -85,13 +115,14 @@ impl RenderOnce for Link {
             .on_click({
                 move |e, window, cx| {
                     if let Some(href) = &href {
-                        cx.open_url(&href.clone());
+                        router::open_url(href, window, cx);
                     }
                     if let Some(on_click) = &on_click {
                         on_click(e, window, cx);
                     }
                 }
             })
+            .children(self.label)
             .children(self.children)
     }
 }
@@ -94,6 +94,15 @@ impl RowsCache {
             .position(|p| p.is_entry() && p.eq_index_path(path))
     }
 
+    /// Returns the index paths of all the items in the flattened order.
+    pub(crate) fn entries(&self) -> Vec<IndexPath> {
+        self.entities
+            .iter()
+            .filter(|entry| entry.is_entry())
+            .map(|entry| entry.index())
+            .collect()
+    }
+
     /// Returns the sections count in the cache.
     pub(crate) fn sections_count(&self) -> usize {
         self.sections.len()
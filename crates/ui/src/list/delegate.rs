@@ -1,4 +1,7 @@
-use gpui::{AnyElement, App, Context, IntoElement, ParentElement as _, Styled as _, Task, Window};
+use gpui::{
+    AnyElement, App, Context, IntoElement, ParentElement as _, SharedString, Styled as _, Task,
+    Window,
+};
 
 use crate::{
     h_flex,
@@ -42,6 +45,13 @@ pub trait ListDelegate: Sized + 'static {
         cx: &mut Context<List<Self>>,
     ) -> Option<Self::Item>;
 
+    /// Return the text of the item at the given index, used to jump to the item by typing.
+    ///
+    /// Default is None, the type-ahead is disabled.
+    fn item_text(&self, ix: IndexPath, cx: &App) -> Option<SharedString> {
+        None
+    }
+
     /// Render the section header at the given index, default is None.
     ///
     /// NOTE: Every header should have same height.
@@ -1,10 +1,15 @@
 use std::ops::Range;
 use std::time::Duration;
 
-use crate::actions::{Cancel, Confirm, SelectNext, SelectPrev};
+use crate::accessibility::{announce, Politeness};
+use crate::actions::{
+    Cancel, Confirm, SelectFirst, SelectLast, SelectNext, SelectPageDown, SelectPageUp, SelectPrev,
+};
+use crate::i18n::t;
 use crate::input::InputState;
 use crate::list::cache::{MeasuredEntrySize, RowEntry, RowsCache};
 use crate::list::ListDelegate;
+use crate::type_ahead::{self, TypeAhead};
 use crate::{
     input::{InputEvent, TextInput},
     scroll::{Scrollbar, ScrollbarState},
@@ -15,13 +20,13 @@ use crate::{
 };
 use gpui::{
     div, prelude::FluentBuilder, AppContext, Entity, FocusHandle, Focusable, InteractiveElement,
-    IntoElement, KeyBinding, Length, MouseButton, ParentElement, Render, Styled, Task, Window,
+    IntoElement, KeyBinding, KeyDownEvent, Length, MouseButton, ParentElement, Render, Styled,
+    Task, Window,
 };
 use gpui::{
     px, size, App, AvailableSpace, Context, Edges, EventEmitter, ListSizingBehavior,
     MouseDownEvent, Pixels, ScrollStrategy, Subscription,
 };
-use rust_i18n::t;
 use smol::Timer;
 
 pub fn init(cx: &mut App) {
@@ -32,6 +37,10 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("secondary-enter", Confirm { secondary: true }, context),
         KeyBinding::new("up", SelectPrev, context),
         KeyBinding::new("down", SelectNext, context),
+        KeyBinding::new("home", SelectFirst, context),
+        KeyBinding::new("end", SelectLast, context),
+        KeyBinding::new("pageup", SelectPageUp, context),
+        KeyBinding::new("pagedown", SelectPageDown, context),
     ]);
 }
 
@@ -62,6 +71,7 @@ pub struct List<D: ListDelegate> {
     selected_index: Option<IndexPath>,
     mouse_right_clicked_index: Option<IndexPath>,
     reset_on_cancel: bool,
+    type_ahead: TypeAhead,
     _search_task: Task<()>,
     _load_more_task: Task<()>,
     _query_input_subscription: Subscription,
@@ -94,6 +104,7 @@ where
             querying: false,
             size: Size::default(),
             reset_on_cancel: true,
+            type_ahead: TypeAhead::default(),
             paddings: Edges::default(),
             _search_task: Task::ready(()),
             _load_more_task: Task::ready(()),
@@ -275,6 +286,12 @@ where
                     Timer::after(Duration::from_millis(100)).await;
                     _ = this.update_in(window, |this, window, cx| {
                         this.set_querying(false, window, cx);
+
+                        let delegate = this.delegate();
+                        let count = (0..delegate.sections_count(cx))
+                            .map(|section| delegate.items_count(section, cx))
+                            .sum::<usize>();
+                        announce(t!("List.results", count = count), Politeness::Polite, cx);
                     });
                 });
             }
@@ -404,6 +421,95 @@ where
         self.select_item(next_ix, window, cx);
     }
 
+    fn on_action_select_first(
+        &mut self,
+        _: &SelectFirst,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(first_ix) = self.rows_cache.entries().first() {
+            self.select_item(*first_ix, window, cx);
+        }
+    }
+
+    fn on_action_select_last(
+        &mut self,
+        _: &SelectLast,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(last_ix) = self.rows_cache.entries().last() {
+            self.select_item(*last_ix, window, cx);
+        }
+    }
+
+    fn select_page(&mut self, forward: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let entries = self.rows_cache.entries();
+        if entries.is_empty() {
+            return;
+        }
+
+        let current = self
+            .selected_index
+            .and_then(|selected| entries.iter().position(|ix| *ix == selected));
+        let ix = entries[type_ahead::page_index(current, entries.len(), forward)];
+        self.select_item(ix, window, cx);
+    }
+
+    fn on_action_select_page_up(
+        &mut self,
+        _: &SelectPageUp,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_page(false, window, cx);
+    }
+
+    fn on_action_select_page_down(
+        &mut self,
+        _: &SelectPageDown,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_page(true, window, cx);
+    }
+
+    /// Handle the Space to confirm and the type-ahead, only when the list has no query input.
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if self.query_input.is_some() || !self.selectable {
+            return;
+        }
+        let Some(c) = type_ahead::typed_char(event) else {
+            return;
+        };
+
+        if c == ' ' && !self.type_ahead.is_active() {
+            if self.selected_index.is_some() {
+                cx.stop_propagation();
+                self.on_action_confirm(&Confirm { secondary: false }, window, cx);
+            }
+            return;
+        }
+
+        let entries = self.rows_cache.entries();
+        let labels = entries
+            .iter()
+            .map(|ix| self.delegate.item_text(*ix, cx))
+            .collect::<Vec<_>>();
+        if labels.iter().all(|label| label.is_none()) {
+            return;
+        }
+
+        cx.stop_propagation();
+        let current = self
+            .selected_index
+            .and_then(|selected| entries.iter().position(|ix| *ix == selected));
+        let query = self.type_ahead.push(c).to_string();
+        if let Some(found) = type_ahead::find_match(&labels, current, &query) {
+            self.select_item(entries[found], window, cx);
+        }
+    }
+
     fn render_list_item(
         &self,
         ix: IndexPath,
@@ -626,6 +732,11 @@ where
                     .on_action(cx.listener(Self::on_action_confirm))
                     .on_action(cx.listener(Self::on_action_select_next))
                     .on_action(cx.listener(Self::on_action_select_prev))
+                    .on_action(cx.listener(Self::on_action_select_first))
+                    .on_action(cx.listener(Self::on_action_select_last))
+                    .on_action(cx.listener(Self::on_action_select_page_up))
+                    .on_action(cx.listener(Self::on_action_select_page_down))
+                    .on_key_down(cx.listener(Self::on_key_down))
                     .map(|this| {
                         if let Some(view) = initial_view {
                             this.child(view)
@@ -1,4 +1,7 @@
-use crate::{h_flex, ActiveTheme, Disableable, Icon, Selectable, Sizable as _, StyledExt};
+use crate::{
+    accessibility::{self, AccessibilityProperties, AccessibilityRole, Accessible},
+    h_flex, ActiveTheme, Disableable, Icon, LayoutDirection, Selectable, Sizable as _, StyledExt,
+};
 use gpui::{
     div, prelude::FluentBuilder as _, AnyElement, App, ClickEvent, Div, ElementId,
     InteractiveElement, IntoElement, MouseButton, MouseMoveEvent, ParentElement, RenderOnce,
@@ -34,6 +37,8 @@ pub struct ListItem {
     on_mouse_enter: Option<Box<dyn Fn(&MouseMoveEvent, &mut Window, &mut App) + 'static>>,
     suffix: Option<Box<dyn Fn(&mut Window, &mut App) -> AnyElement + 'static>>,
     children: SmallVec<[AnyElement; 2]>,
+    accessibility: AccessibilityProperties,
+    direction: Option<LayoutDirection>,
 }
 
 impl ListItem {
@@ -52,9 +57,17 @@ impl ListItem {
             check_icon: None,
             suffix: None,
             children: SmallVec::new(),
+            accessibility: AccessibilityProperties::default(),
+            direction: None,
         }
     }
 
+    /// Set the layout direction, default is the direction of the window.
+    pub fn direction(mut self, direction: LayoutDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
     /// Set this list item to as a separator, it not able to be selected.
     pub fn separator(mut self) -> Self {
         self.mode = ListItemMode::Separator;
@@ -113,6 +126,23 @@ impl ListItem {
     }
 }
 
+impl Accessible for ListItem {
+    fn accessibility_mut(&mut self) -> &mut AccessibilityProperties {
+        &mut self.accessibility
+    }
+
+    fn accessibility(&self, _: &App) -> AccessibilityProperties {
+        let mut properties = AccessibilityProperties::new(AccessibilityRole::ListItem)
+            .selected(self.selected)
+            .disabled(self.disabled || self.mode == ListItemMode::Separator);
+        if self.check_icon.is_some() {
+            properties.checked = Some(self.confirmed);
+        }
+
+        properties.merge(&self.accessibility)
+    }
+}
+
 impl Disableable for ListItem {
     fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
@@ -150,6 +180,8 @@ impl ParentElement for ListItem {
 
 impl RenderOnce for ListItem {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        accessibility::record_rendered(self.accessibility(cx), window, cx);
+
         let is_active = self.confirmed || self.selected;
 
         let corner_radii = self.style.corner_radii.clone();
@@ -158,8 +190,12 @@ impl RenderOnce for ListItem {
         selected_style.corner_radii = corner_radii;
 
         let is_selectable = !(self.disabled || self.mode.is_separator());
+        let direction = self
+            .direction
+            .unwrap_or_else(|| LayoutDirection::of(window, cx));
 
         self.base
+            .flex_row_with(direction)
             .relative()
             .gap_x_1()
             .py_1()
@@ -189,6 +225,7 @@ impl RenderOnce for ListItem {
             })
             .child(
                 h_flex()
+                    .flex_row_with(direction)
                     .w_full()
                     .items_center()
                     .justify_between()
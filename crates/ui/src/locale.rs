@@ -0,0 +1,194 @@
+//! Locale aware formatting for the dates, times, numbers and relative times.
+//!
+//! The formats follow the current locale, use [`crate::set_locale`] to switch it at runtime.
+use crate::i18n::t;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use gpui::SharedString;
+
+/// Returns the date format of the current locale, e.g.: `%m/%d/%Y` for `en`.
+pub fn date_format() -> SharedString {
+    t!("Locale.date_format").into()
+}
+
+/// Returns the time format of the current locale, e.g.: `%I:%M %p` for `en`.
+pub fn time_format() -> SharedString {
+    t!("Locale.time_format").into()
+}
+
+/// Format the date with the current locale.
+pub fn format_date(date: &NaiveDate) -> SharedString {
+    date.format(&date_format()).to_string().into()
+}
+
+/// Format the time with the current locale.
+pub fn format_time(time: &NaiveTime) -> SharedString {
+    time.format(&time_format()).to_string().into()
+}
+
+/// Format the date and time with the current locale.
+pub fn format_datetime(datetime: &NaiveDateTime) -> SharedString {
+    let format = format!("{} {}", date_format(), time_format());
+    datetime.format(&format).to_string().into()
+}
+
+/// Format the number with the group and decimal separators of the current locale.
+///
+/// ```ignore
+/// assert_eq!(format_number(1234567.891, 2), "1,234,567.89");
+/// ```
+pub fn format_number(value: f64, precision: usize) -> SharedString {
+    format_number_with(
+        value,
+        precision,
+        &t!("Locale.group_separator"),
+        &t!("Locale.decimal_separator"),
+    )
+    .into()
+}
+
+/// Format the `time` relative to the `now` with the current locale, e.g.: "3 minutes ago", "in 2 days".
+pub fn format_relative_time<Tz: TimeZone>(time: &DateTime<Tz>, now: &DateTime<Tz>) -> SharedString {
+    let seconds = time
+        .clone()
+        .signed_duration_since(now.clone())
+        .num_seconds();
+    let (unit, count) = relative_time_unit(seconds);
+
+    let key = match unit {
+        RelativeTimeUnit::Now => return t!("Locale.just_now").into(),
+        RelativeTimeUnit::Minute => "Locale.minutes",
+        RelativeTimeUnit::Hour => "Locale.hours",
+        RelativeTimeUnit::Day => "Locale.days",
+        RelativeTimeUnit::Month => "Locale.months",
+        RelativeTimeUnit::Year => "Locale.years",
+    };
+
+    let time = t!(key, count = count.abs());
+    if count < 0 {
+        t!("Locale.past", time = time).into()
+    } else {
+        t!("Locale.future", time = time).into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RelativeTimeUnit {
+    Now,
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+/// Returns the unit and the count (negative for the past) to display the relative time.
+pub(crate) fn relative_time_unit(seconds: i64) -> (RelativeTimeUnit, i64) {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    const MONTH: i64 = 30 * DAY;
+    const YEAR: i64 = 365 * DAY;
+
+    const UNITS: [(RelativeTimeUnit, i64); 5] = [
+        (RelativeTimeUnit::Minute, MINUTE),
+        (RelativeTimeUnit::Hour, HOUR),
+        (RelativeTimeUnit::Day, DAY),
+        (RelativeTimeUnit::Month, MONTH),
+        (RelativeTimeUnit::Year, YEAR),
+    ];
+
+    let abs = seconds.abs();
+    let sign = seconds.signum();
+    if abs < 45 {
+        return (RelativeTimeUnit::Now, 0);
+    }
+
+    let mut ix = UNITS
+        .iter()
+        .rposition(|(_, size)| abs >= *size)
+        .unwrap_or(0);
+    loop {
+        let (unit, size) = UNITS[ix];
+        // Round to the nearest, but at least 1.
+        let count = ((abs + size / 2) / size).max(1);
+
+        // Promote to the next unit if the rounded count reaches it, e.g.: 59.5 minutes is 1 hour.
+        match UNITS.get(ix + 1) {
+            Some((_, next_size)) if count >= next_size / size => ix += 1,
+            _ => return (unit, sign * count),
+        }
+    }
+}
+
+pub(crate) fn format_number_with(
+    value: f64,
+    precision: usize,
+    group_separator: &str,
+    decimal_separator: &str,
+) -> String {
+    let formatted = format!("{:.*}", precision, value.abs());
+    let (int_part, frac_part) = match formatted.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (formatted.as_str(), None),
+    };
+
+    let mut out = String::new();
+    if value.is_sign_negative() && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        out.push('-');
+    }
+    for (ix, c) in int_part.chars().enumerate() {
+        if ix > 0 && (int_part.len() - ix) % 3 == 0 {
+            out.push_str(group_separator);
+        }
+        out.push(c);
+    }
+    if let Some(frac_part) = frac_part {
+        out.push_str(decimal_separator);
+        out.push_str(frac_part);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_number_with, relative_time_unit, RelativeTimeUnit};
+
+    #[test]
+    fn test_format_number_with() {
+        assert_eq!(format_number_with(0., 0, ",", "."), "0");
+        assert_eq!(format_number_with(123., 0, ",", "."), "123");
+        assert_eq!(format_number_with(1234., 0, ",", "."), "1,234");
+        assert_eq!(format_number_with(1234567.891, 2, ",", "."), "1,234,567.89");
+        assert_eq!(
+            format_number_with(-1234567.891, 1, ".", ","),
+            "-1.234.567,9"
+        );
+        assert_eq!(format_number_with(-0.001, 2, ",", "."), "0.00");
+    }
+
+    #[test]
+    fn test_relative_time_unit() {
+        assert_eq!(relative_time_unit(0), (RelativeTimeUnit::Now, 0));
+        assert_eq!(relative_time_unit(-30), (RelativeTimeUnit::Now, 0));
+        assert_eq!(relative_time_unit(-45), (RelativeTimeUnit::Minute, -1));
+        assert_eq!(relative_time_unit(-180), (RelativeTimeUnit::Minute, -3));
+        assert_eq!(relative_time_unit(3600 * 2), (RelativeTimeUnit::Hour, 2));
+        assert_eq!(relative_time_unit(-86400 * 3), (RelativeTimeUnit::Day, -3));
+        assert_eq!(relative_time_unit(86400 * 65), (RelativeTimeUnit::Month, 2));
+        assert_eq!(
+            relative_time_unit(-86400 * 365 * 2),
+            (RelativeTimeUnit::Year, -2)
+        );
+
+        // The rounded count is promoted to the next unit.
+        assert_eq!(relative_time_unit(3569), (RelativeTimeUnit::Minute, 59));
+        assert_eq!(relative_time_unit(3599), (RelativeTimeUnit::Hour, 1));
+        assert_eq!(relative_time_unit(-84960), (RelativeTimeUnit::Day, -1));
+        assert_eq!(
+            relative_time_unit(86400 * 29 + 50000),
+            (RelativeTimeUnit::Month, 1)
+        );
+        assert_eq!(relative_time_unit(86400 * 364), (RelativeTimeUnit::Year, 1));
+    }
+}
@@ -1,4 +1,7 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 use gpui::{
     anchored, deferred, div, prelude::FluentBuilder, px, relative, AnyElement, App, Context,
@@ -8,6 +11,8 @@ use gpui::{
 };
 
 use crate::menu::popup_menu::PopupMenu;
+use crate::overlay::{self, OverlayId, OverlayLayer};
+use crate::telemetry::{self, Interaction, InteractionKind};
 
 pub trait ContextMenuExt: ParentElement + Sized {
     fn context_menu(
@@ -76,6 +81,8 @@ pub struct ContextMenuState {
     menu_view: Rc<RefCell<Option<Entity<PopupMenu>>>>,
     menu_element: Option<AnyElement>,
     open: Rc<RefCell<bool>>,
+    /// The overlay of the opened menu.
+    overlay_id: Rc<Cell<Option<OverlayId>>>,
     position: Rc<RefCell<Point<Pixels>>>,
 }
 
@@ -85,6 +92,7 @@ impl Default for ContextMenuState {
             menu_view: Rc::new(RefCell::new(None)),
             menu_element: None,
             open: Rc::new(RefCell::new(false)),
+            overlay_id: Rc::new(Cell::new(None)),
             position: Default::default(),
         }
     }
@@ -127,6 +135,7 @@ impl Element for ContextMenu {
                 let position = state.position.clone();
                 let position = position.borrow();
                 let open = state.open.clone();
+                let overlay_id = state.overlay_id.get();
                 let menu_view = state.menu_view.borrow().clone();
 
                 let (menu_element, menu_layout_id) = if *open.borrow() {
@@ -145,10 +154,17 @@ impl Element for ContextMenu {
                                     // Focus the menu, so that can be handle the action.
                                     menu.focus_handle(cx).focus(window);
 
-                                    this.child(div().occlude().child(menu.clone()))
+                                    this.child(
+                                        div()
+                                            .occlude()
+                                            .child(menu.clone())
+                                            .when_some(overlay_id, |this, id| {
+                                                this.child(overlay::bounds_tracker(id))
+                                            }),
+                                    )
                                 }),
                         )
-                        .with_priority(1)
+                        .with_priority(OverlayLayer::Menu.priority())
                         .into_any();
 
                         let menu_layout_id = menu_element.request_layout(window, cx);
@@ -211,6 +227,7 @@ impl Element for ContextMenu {
             return;
         };
 
+        let menu_id = self.id.clone();
         self.with_element_state(
             id.unwrap(),
             window,
@@ -218,6 +235,7 @@ impl Element for ContextMenu {
             |_view, state: &mut ContextMenuState, window, _| {
                 let position = state.position.clone();
                 let open = state.open.clone();
+                let overlay_id = state.overlay_id.clone();
                 let menu_view = state.menu_view.clone();
 
                 // When right mouse click, to build content menu, and show it at the mouse position.
@@ -229,15 +247,33 @@ impl Element for ContextMenu {
                         *position.borrow_mut() = event.position;
                         *open.borrow_mut() = true;
 
+                        telemetry::record(cx, || {
+                            Interaction::new(InteractionKind::MenuOpen, Some(menu_id.clone()))
+                        });
                         let menu = PopupMenu::build(window, cx, |menu, window, cx| {
                             (builder)(menu, window, cx)
                         })
                         .into_element();
 
+                        if let Some(id) = overlay_id.take() {
+                            overlay::close(id, cx);
+                        }
+                        overlay_id.set(Some(overlay::open(OverlayLayer::Menu, window, cx, {
+                            let open = open.clone();
+                            move |window, _| {
+                                *open.borrow_mut() = false;
+                                window.refresh();
+                            }
+                        })));
+
                         let open = open.clone();
+                        let overlay_id = overlay_id.clone();
                         window
-                            .subscribe(&menu, cx, move |_, _: &DismissEvent, window, _| {
+                            .subscribe(&menu, cx, move |_, _: &DismissEvent, window, cx| {
                                 *open.borrow_mut() = false;
+                                if let Some(id) = overlay_id.take() {
+                                    overlay::close(id, cx);
+                                }
                                 window.refresh();
                             })
                             .detach();
@@ -1,6 +1,12 @@
-use crate::actions::{Cancel, Confirm, SelectNext, SelectPrev};
+use crate::actions::{
+    Cancel, Confirm, SelectFirst, SelectLast, SelectNext, SelectPageDown, SelectPageUp, SelectPrev,
+};
+use crate::focusable::{roving_index, RovingMove};
 use crate::menu::menu_item::MenuItem;
+use crate::router;
 use crate::scroll::{Scrollbar, ScrollbarState};
+use crate::telemetry::{self, Interaction, InteractionKind};
+use crate::type_ahead::{self, TypeAhead};
 use crate::{
     button::Button, h_flex, popover::Popover, v_flex, ActiveTheme, Icon, IconName, Selectable,
     Sizable as _,
@@ -12,7 +18,7 @@ use gpui::{
     InteractiveElement, IntoElement, KeyBinding, ParentElement, Pixels, Render, ScrollHandle,
     SharedString, StatefulInteractiveElement, Styled, WeakEntity, Window,
 };
-use gpui::{MouseDownEvent, Subscription};
+use gpui::{KeyDownEvent, MouseDownEvent, Subscription};
 use std::ops::Deref;
 use std::rc::Rc;
 
@@ -25,6 +31,10 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("escape", Cancel, context),
         KeyBinding::new("up", SelectPrev, context),
         KeyBinding::new("down", SelectNext, context),
+        KeyBinding::new("home", SelectFirst, context),
+        KeyBinding::new("end", SelectLast, context),
+        KeyBinding::new("pageup", SelectPageUp, context),
+        KeyBinding::new("pagedown", SelectPageDown, context),
     ]);
 }
 
@@ -52,6 +62,9 @@ pub trait PopupMenuExt: Styled + Selectable + InteractiveElement + IntoElement +
             .trigger_style(style)
             .anchor(anchor.into())
             .content(move |window, cx| {
+                telemetry::record(cx, || {
+                    Interaction::new(InteractionKind::MenuOpen, id.clone())
+                });
                 PopupMenu::build(window, cx, |menu, window, cx| f(menu, window, cx))
             })
     }
@@ -84,13 +97,32 @@ enum PopupMenuItem {
 }
 
 impl PopupMenuItem {
-    fn is_clickable(&self) -> bool {
-        !matches!(self, PopupMenuItem::Separator)
-    }
-
     fn is_separator(&self) -> bool {
         matches!(self, PopupMenuItem::Separator)
     }
+
+    /// Returns true if the item can be selected by the keyboard.
+    fn is_selectable(&self) -> bool {
+        match self {
+            PopupMenuItem::Item { disabled, .. }
+            | PopupMenuItem::ElementItem { disabled, .. }
+            | PopupMenuItem::Submenu { disabled, .. } => !disabled,
+            _ => false,
+        }
+    }
+
+    /// Returns the label to match the type-ahead.
+    fn type_ahead_label(&self) -> Option<SharedString> {
+        match self {
+            PopupMenuItem::Item {
+                label, disabled, ..
+            }
+            | PopupMenuItem::Submenu {
+                label, disabled, ..
+            } if !disabled => Some(label.clone()),
+            _ => None,
+        }
+    }
 }
 
 pub struct PopupMenu {
@@ -110,6 +142,7 @@ pub struct PopupMenu {
     external_link_icon: bool,
     scroll_handle: ScrollHandle,
     scroll_state: ScrollbarState,
+    type_ahead: TypeAhead,
 
     previous_focus_handle: Option<FocusHandle>,
     _subscriptions: Vec<Subscription>,
@@ -145,6 +178,7 @@ impl PopupMenu {
                 scrollable: false,
                 scroll_handle: ScrollHandle::default(),
                 scroll_state: ScrollbarState::default(),
+                type_ahead: TypeAhead::default(),
                 external_link_icon: true,
                 _subscriptions,
             };
@@ -225,7 +259,7 @@ impl PopupMenu {
             disabled,
             action: None,
             is_link: true,
-            handler: Rc::new(move |_, cx| cx.open_url(&href)),
+            handler: Rc::new(move |window, cx| router::open_url(&href, window, cx)),
         });
         self
     }
@@ -255,7 +289,7 @@ impl PopupMenu {
             disabled,
             action: None,
             is_link: true,
-            handler: Rc::new(move |_, cx| cx.open_url(&href)),
+            handler: Rc::new(move |window, cx| router::open_url(&href, window, cx)),
         });
         self
     }
@@ -532,11 +566,14 @@ impl PopupMenu {
         self.menu_items.is_empty()
     }
 
-    fn clickable_menu_items(&self) -> impl Iterator<Item = (usize, &PopupMenuItem)> {
+    /// Returns the indices of the items that can be selected by the keyboard.
+    fn selectable_indices(&self) -> Vec<usize> {
         self.menu_items
             .iter()
             .enumerate()
-            .filter(|(_, item)| item.is_clickable())
+            .filter(|(_, item)| item.is_selectable())
+            .map(|(ix, _)| ix)
+            .collect()
     }
 
     fn on_click(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
@@ -547,57 +584,137 @@ impl PopupMenu {
     }
 
     fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
-        match self.selected_index {
-            Some(index) => {
-                let item = self.menu_items.get(index);
-                match item {
-                    Some(PopupMenuItem::Item { handler, .. }) => {
-                        handler(window, cx);
-                        self.dismiss(&Cancel, window, cx)
-                    }
-                    Some(PopupMenuItem::ElementItem { handler, .. }) => {
-                        handler(window, cx);
-                        self.dismiss(&Cancel, window, cx)
-                    }
-                    _ => {}
-                }
+        let Some(index) = self.selected_index else {
+            return;
+        };
+
+        match self.menu_items.get(index) {
+            Some(PopupMenuItem::Item { handler, label, .. }) => {
+                telemetry::record(cx, || {
+                    Interaction::new(InteractionKind::MenuSelect, None)
+                        .metadata("label", label.clone())
+                });
+                handler(window, cx);
+                self.dismiss(&Cancel, window, cx)
+            }
+            Some(PopupMenuItem::ElementItem { handler, .. }) => {
+                telemetry::record(cx, || Interaction::new(InteractionKind::MenuSelect, None));
+                handler(window, cx);
+                self.dismiss(&Cancel, window, cx)
+            }
+            Some(PopupMenuItem::Submenu { menu, .. }) => {
+                // Open the submenu and move the focus into it.
+                let menu = menu.clone();
+                self.hovered_menu_ix = Some(index);
+                menu.update(cx, |menu, cx| {
+                    menu.selected_index = menu.selectable_indices().first().copied();
+                    menu.focus_handle.focus(window);
+                    cx.notify();
+                });
+                cx.notify();
             }
             _ => {}
         }
     }
 
-    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
-        let count = self.clickable_menu_items().count();
-        if count > 0 {
-            let last_ix = count.saturating_sub(1);
-            let ix = self
-                .selected_index
-                .map(|index| if index == last_ix { 0 } else { index + 1 })
-                .unwrap_or(0);
-
-            self.selected_index = Some(ix);
-            cx.notify();
+    fn select_index(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(ix);
+        cx.notify();
+    }
+
+    /// Returns the position of the selected item in the selectable items.
+    fn selected_position(&self, indices: &[usize]) -> Option<usize> {
+        self.selected_index
+            .and_then(|selected| indices.iter().position(|ix| *ix == selected))
+    }
+
+    /// Move the selection by the `movement` of the [`RovingFocus`](crate::RovingFocus), the
+    /// separators and the disabled items are skipped.
+    fn move_selection(&mut self, movement: RovingMove, cx: &mut Context<Self>) {
+        let items = &self.menu_items;
+        if let Some(ix) = roving_index(self.selected_index, items.len(), movement, true, |ix| {
+            !items[ix].is_selectable()
+        }) {
+            self.select_index(ix, cx);
         }
     }
 
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_selection(RovingMove::Next, cx);
+    }
+
     fn select_prev(&mut self, _: &SelectPrev, _: &mut Window, cx: &mut Context<Self>) {
-        let count = self.clickable_menu_items().count();
-        if count > 0 {
-            let last_ix = count.saturating_sub(1);
-
-            let ix = self
-                .selected_index
-                .map(|index| {
-                    if index == last_ix {
-                        0
-                    } else {
-                        index.saturating_sub(1)
-                    }
-                })
-                .unwrap_or(last_ix);
-            self.selected_index = Some(ix);
+        self.move_selection(RovingMove::Prev, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_selection(RovingMove::First, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_selection(RovingMove::Last, cx);
+    }
+
+    fn select_page(&mut self, forward: bool, cx: &mut Context<Self>) {
+        let indices = self.selectable_indices();
+        if indices.is_empty() {
+            return;
+        }
+
+        let pos = type_ahead::page_index(self.selected_position(&indices), indices.len(), forward);
+        self.select_index(indices[pos], cx);
+    }
+
+    fn select_page_up(&mut self, _: &SelectPageUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_page(false, cx);
+    }
+
+    fn select_page_down(&mut self, _: &SelectPageDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_page(true, cx);
+    }
+
+    /// Handle the Space to confirm and the type-ahead to jump to the item by the label.
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(c) = type_ahead::typed_char(event) else {
+            return;
+        };
+        cx.stop_propagation();
+
+        if c == ' ' && !self.type_ahead.is_active() {
+            self.confirm(&Confirm { secondary: false }, window, cx);
+            return;
+        }
+
+        let labels = self
+            .menu_items
+            .iter()
+            .map(|item| item.type_ahead_label())
+            .collect::<Vec<_>>();
+        let query = self.type_ahead.push(c).to_string();
+        if let Some(ix) = type_ahead::find_match(&labels, self.selected_index, &query) {
+            self.select_index(ix, cx);
+        }
+    }
+
+    /// Close the innermost opened submenu first, or dismiss the menu if no submenu is opened.
+    fn cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.active_submenu().is_some() {
+            self.hovered_menu_ix = None;
             cx.notify();
+            return;
+        }
+
+        if let Some(parent_menu) = self.parent_menu.as_ref().and_then(|menu| menu.upgrade()) {
+            self.type_ahead.clear();
+            parent_menu.update(cx, |view, cx| {
+                view.hovered_menu_ix = None;
+                view.focus_handle.focus(window);
+                cx.notify();
+            });
+            return;
         }
+
+        self.dismiss(&Cancel, window, cx);
     }
 
     fn dismiss(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
@@ -605,6 +722,13 @@ impl PopupMenu {
             return;
         }
 
+        // This submenu has been closed by the parent menu, e.g.: pressed ESC.
+        if let Some(parent_menu) = self.parent_menu.as_ref().and_then(|menu| menu.upgrade()) {
+            if parent_menu.read(cx).active_submenu().as_ref() != Some(&cx.entity()) {
+                return;
+            }
+        }
+
         cx.emit(DismissEvent);
 
         // Focus back to the previous focused handle.
@@ -684,6 +808,8 @@ impl PopupMenu {
         let max_width = state.max_width;
         let has_icon = self.has_icon;
         let hovered = self.hovered_menu_ix == Some(ix);
+        let selected = self.selected_index == Some(ix);
+        let selectable = item.is_selectable();
         const EDGE_PADDING: Pixels = px(8.);
         const INNER_PADDING: Pixels = px(4.);
 
@@ -694,10 +820,26 @@ impl PopupMenu {
             .px(INNER_PADDING)
             .rounded(state.radius)
             .items_center()
-            .on_mouse_enter(cx.listener(move |this, _, _, cx| {
+            .on_mouse_enter(cx.listener(move |this, _, window, cx| {
                 this.hovered_menu_ix = Some(ix);
+                if selectable {
+                    this.selected_index = Some(ix);
+                }
+                // Take the focus back from the submenu that is opened by the keyboard.
+                if !this.focus_handle.is_focused(window)
+                    && this.focus_handle.contains_focused(window, cx)
+                {
+                    this.focus_handle.focus(window);
+                }
                 cx.notify();
-            }));
+            }))
+            .when(
+                selected && !matches!(item, PopupMenuItem::Submenu { .. }),
+                |this| {
+                    this.bg(cx.theme().accent)
+                        .text_color(cx.theme().accent_foreground)
+                },
+            );
 
         match item {
             PopupMenuItem::Separator => this.h_auto().p_0().disabled(true).child(
@@ -788,7 +930,7 @@ impl PopupMenu {
             } => this.selected(hovered).disabled(*disabled).child(
                 h_flex()
                     .items_start()
-                    .when(hovered, |this| {
+                    .when(hovered || selected, |this| {
                         this.rounded(cx.theme().radius)
                             .mx(-INNER_PADDING)
                             .px(INNER_PADDING)
@@ -881,8 +1023,13 @@ impl Render for PopupMenu {
             .track_focus(&self.focus_handle)
             .on_action(cx.listener(Self::select_next))
             .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::select_page_up))
+            .on_action(cx.listener(Self::select_page_down))
             .on_action(cx.listener(Self::confirm))
-            .on_action(cx.listener(Self::dismiss))
+            .on_action(cx.listener(Self::cancel))
+            .on_key_down(cx.listener(Self::on_key_down))
             .on_mouse_down_out(cx.listener(|this, ev: &MouseDownEvent, window, cx| {
                 // Do not dismiss, if click inside the parent menu
                 if let Some(parent) = this.parent_menu.as_ref() {
@@ -1,18 +1,19 @@
 use std::{rc::Rc, time::Duration};
 
+use crate::i18n::t;
 use gpui::{
     anchored, div, hsla, point, prelude::FluentBuilder, px, relative, Animation, AnimationExt as _,
     AnyElement, App, Axis, Bounds, BoxShadow, ClickEvent, Div, FocusHandle, Hsla,
     InteractiveElement, IntoElement, KeyBinding, MouseButton, ParentElement, Pixels, Point,
     RenderOnce, SharedString, StyleRefinement, Styled, Window,
 };
-use rust_i18n::t;
 
 use crate::{
     actions::{Cancel, Confirm},
     animation::cubic_bezier,
     button::{Button, ButtonVariant, ButtonVariants as _},
-    h_flex, v_flex, ActiveTheme as _, ContextModal, IconName, Root, Sizable as _, StyledExt,
+    h_flex, v_flex, ActiveTheme as _, ContextModal, FocusNext, FocusPrev, FocusTrap,
+    FocusTrapExt as _, IconName, Root, Sizable as _, StyledExt,
 };
 
 const CONTEXT: &str = "Modal";
@@ -20,6 +21,8 @@ pub fn init(cx: &mut App) {
     cx.bind_keys([
         KeyBinding::new("escape", Cancel, Some(CONTEXT)),
         KeyBinding::new("enter", Confirm { secondary: false }, Some(CONTEXT)),
+        KeyBinding::new("tab", FocusNext, Some(CONTEXT)),
+        KeyBinding::new("shift-tab", FocusPrev, Some(CONTEXT)),
     ]);
 }
 
@@ -90,6 +93,7 @@ pub struct Modal {
     overlay: bool,
     overlay_closable: bool,
     keyboard: bool,
+    focus_handles: Vec<FocusHandle>,
 
     /// This will be change when open the modal, the focus handle is create when open the modal.
     pub(crate) focus_handle: FocusHandle,
@@ -118,6 +122,7 @@ impl Modal {
             max_width: None,
             overlay: true,
             keyboard: true,
+            focus_handles: vec![],
             layer_ix: 0,
             overlay_visible: false,
             on_close: Rc::new(|_, _, _| {}),
@@ -258,6 +263,15 @@ impl Modal {
         self
     }
 
+    /// Set the focus handles to cycle by Tab / Shift-Tab, defaults to empty.
+    ///
+    /// The focus is always trapped inside the modal, even without focus handles, it will never
+    /// escape to the views behind.
+    pub fn focus_handles(mut self, handles: impl IntoIterator<Item = FocusHandle>) -> Self {
+        self.focus_handles = handles.into_iter().collect();
+        self
+    }
+
     pub(crate) fn has_overlay(&self) -> bool {
         self.overlay
     }
@@ -363,6 +377,11 @@ impl RenderOnce for Modal {
             padding_right = pr.to_pixels(self.width.into(), window.rem_size());
         }
 
+        let focus_trap = FocusTrap::new()
+            .container(&self.focus_handle)
+            .handles(self.focus_handles);
+
+        let reduce_motion = cx.theme().reduce_motion;
         let animation = Animation::new(Duration::from_secs_f64(0.25))
             .with_easing(cubic_bezier(0.32, 0.72, 0., 1.));
 
@@ -407,6 +426,7 @@ impl RenderOnce for Modal {
                             .px_0()
                             .key_context(CONTEXT)
                             .track_focus(&self.focus_handle)
+                            .focus_trap(&focus_trap)
                             .when(self.keyboard, |this| {
                                 this.on_action({
                                     let on_cancel = on_cancel.clone();
@@ -493,7 +513,11 @@ impl RenderOnce for Modal {
                                 )
                             })
                             .with_animation("slide-down", animation.clone(), move |this, delta| {
-                                let y_offset = px(0.) + delta * px(30.);
+                                let y_offset = if reduce_motion {
+                                    px(30.)
+                                } else {
+                                    px(0.) + delta * px(30.)
+                                };
                                 // This is equivalent to `shadow_xl` with an extra opacity.
                                 let shadow = vec![
                                     BoxShadow {
@@ -5,15 +5,17 @@ use std::{
     time::Duration,
 };
 
+use chrono::{DateTime, Local};
 use gpui::{
     div, prelude::FluentBuilder, px, Animation, AnimationExt, AnyElement, App, AppContext,
-    ClickEvent, Context, DismissEvent, ElementId, Entity, EventEmitter, InteractiveElement as _,
-    IntoElement, ParentElement as _, Render, SharedString, StatefulInteractiveElement,
-    StyleRefinement, Styled, Subscription, Window,
+    ClickEvent, Context, DismissEvent, ElementId, Entity, EventEmitter, Global,
+    InteractiveElement as _, IntoElement, ParentElement as _, Render, SharedString,
+    StatefulInteractiveElement, StyleRefinement, Styled, Subscription, Window,
 };
 use smol::Timer;
 
 use crate::{
+    accessibility::{announce, Politeness},
     animation::cubic_bezier,
     button::{Button, ButtonVariants as _},
     h_flex, v_flex, ActiveTheme as _, Icon, IconName, Sizable as _, StyledExt,
@@ -29,7 +31,7 @@ pub enum NotificationType {
 }
 
 impl NotificationType {
-    fn icon(&self, cx: &App) -> Icon {
+    pub(crate) fn icon(&self, cx: &App) -> Icon {
         match self {
             Self::Info => Icon::new(IconName::Info).text_color(cx.theme().info),
             Self::Success => Icon::new(IconName::CircleCheck).text_color(cx.theme().success),
@@ -73,6 +75,8 @@ pub struct Notification {
     action_builder: Option<Rc<dyn Fn(&mut Window, &mut Context<Self>) -> Button>>,
     content_builder: Option<Rc<dyn Fn(&mut Window, &mut Context<Self>) -> AnyElement>>,
     on_click: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
+    native: bool,
+    source: Option<SharedString>,
     closing: bool,
 }
 
@@ -127,6 +131,8 @@ impl Notification {
             action_builder: None,
             content_builder: None,
             on_click: None,
+            native: true,
+            source: None,
             closing: false,
         }
     }
@@ -205,6 +211,22 @@ impl Notification {
         self
     }
 
+    /// Set false to not mirror the notification to the native OS notification when
+    /// the window is not active, default is true.
+    ///
+    /// See also [`on_native_notification`].
+    pub fn native(mut self, native: bool) -> Self {
+        self.native = native;
+        self
+    }
+
+    /// Set the source of the notification, e.g.: the name of the module, the notifications are
+    /// grouped by the source in the [`NotificationCenter`](crate::notification_center::NotificationCenter).
+    pub fn source(mut self, source: impl Into<SharedString>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
     /// Set the click callback of the notification.
     pub fn on_click(
         mut self,
@@ -262,6 +284,7 @@ impl Styled for Notification {
 impl Render for Notification {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let closing = self.closing;
+        let reduce_motion = cx.theme().reduce_motion;
         let icon = match self.type_ {
             None => self.icon.clone(),
             Some(type_) => Some(type_.icon(cx)),
@@ -330,7 +353,10 @@ impl Render for Notification {
                 Animation::new(Duration::from_secs_f64(0.25))
                     .with_easing(cubic_bezier(0.4, 0., 0.2, 1.)),
                 move |this, delta| {
-                    if closing {
+                    if reduce_motion {
+                        let opacity = if closing { 1. - delta } else { delta };
+                        this.opacity(opacity)
+                    } else if closing {
                         let x_offset = px(0.) + delta * px(45.);
                         let opacity = 1. - delta;
                         this.left(px(0.) + x_offset)
@@ -349,10 +375,76 @@ impl Render for Notification {
     }
 }
 
+/// A native OS notification mirrored from a [`Notification`], see [`on_native_notification`].
+#[derive(Clone)]
+pub struct NativeNotification {
+    pub type_: NotificationType,
+    pub title: Option<SharedString>,
+    pub message: Option<SharedString>,
+    /// Activate the window of the notification, call it when the native notification is clicked.
+    pub focus_window: Rc<dyn Fn(&mut App)>,
+}
+
+#[derive(Default)]
+struct NativeNotifier {
+    handler: Option<Rc<dyn Fn(&NativeNotification, &mut App)>>,
+}
+
+impl Global for NativeNotifier {}
+
+/// Set the handler to show the native OS notifications, e.g.: by a platform notification crate.
+///
+/// The notifications pushed while the window is not active are sent to the handler,
+/// unless it is opted out by [`Notification::native`].
+pub fn on_native_notification(
+    cx: &mut App,
+    handler: impl Fn(&NativeNotification, &mut App) + 'static,
+) {
+    cx.default_global::<NativeNotifier>().handler = Some(Rc::new(handler));
+}
+
+fn send_native_notification(notification: &Notification, window: &Window, cx: &mut App) {
+    let Some(handler) = cx
+        .try_global::<NativeNotifier>()
+        .and_then(|notifier| notifier.handler.clone())
+    else {
+        return;
+    };
+
+    let window_handle = window.window_handle();
+    let native_notification = NativeNotification {
+        type_: notification.type_.unwrap_or_default(),
+        title: notification.title.clone(),
+        message: notification.message.clone(),
+        focus_window: Rc::new(move |cx| {
+            _ = window_handle.update(cx, |_, window, _| window.activate_window());
+        }),
+    };
+    handler(&native_notification, cx);
+}
+
+/// The max number of the records in the history of the [`NotificationList`].
+const MAX_HISTORY: usize = 100;
+
+/// A record of a pushed notification, kept in the history after it is dismissed.
+#[derive(Debug, Clone)]
+pub struct NotificationRecord {
+    pub id: usize,
+    pub type_: Option<NotificationType>,
+    pub title: Option<SharedString>,
+    pub message: Option<SharedString>,
+    pub source: Option<SharedString>,
+    pub created_at: DateTime<Local>,
+    pub read: bool,
+}
+
 /// A list of notifications.
 pub struct NotificationList {
     /// Notifications that will be auto hidden.
     pub(crate) notifications: VecDeque<Entity<Notification>>,
+    /// The records of the pushed notifications, from the oldest to the newest.
+    history: VecDeque<NotificationRecord>,
+    next_record_id: usize,
     expanded: bool,
     _subscriptions: HashMap<NotificationId, Subscription>,
 }
@@ -361,11 +453,61 @@ impl NotificationList {
     pub fn new(_window: &mut Window, _cx: &mut Context<Self>) -> Self {
         Self {
             notifications: VecDeque::new(),
+            history: VecDeque::new(),
+            next_record_id: 0,
             expanded: false,
             _subscriptions: HashMap::new(),
         }
     }
 
+    fn record(&mut self, notification: &Notification) {
+        self.history.push_back(NotificationRecord {
+            id: self.next_record_id,
+            type_: notification.type_,
+            title: notification.title.clone(),
+            message: notification.message.clone(),
+            source: notification.source.clone(),
+            created_at: Local::now(),
+            read: false,
+        });
+        self.next_record_id += 1;
+        if self.history.len() > MAX_HISTORY {
+            self.history.pop_front();
+        }
+    }
+
+    /// Returns the history of the notifications, from the newest to the oldest.
+    pub fn history(&self) -> Vec<NotificationRecord> {
+        self.history.iter().rev().cloned().collect()
+    }
+
+    /// Returns the number of the unread notifications in the history.
+    pub fn unread_count(&self) -> usize {
+        self.history.iter().filter(|record| !record.read).count()
+    }
+
+    /// Mark the notification of the history as read.
+    pub fn mark_read(&mut self, id: usize, cx: &mut Context<Self>) {
+        if let Some(record) = self.history.iter_mut().find(|record| record.id == id) {
+            record.read = true;
+            cx.notify();
+        }
+    }
+
+    /// Mark all the notifications of the history as read.
+    pub fn mark_all_read(&mut self, cx: &mut Context<Self>) {
+        self.history
+            .iter_mut()
+            .for_each(|record| record.read = true);
+        cx.notify();
+    }
+
+    /// Clear the history of the notifications.
+    pub fn clear_history(&mut self, cx: &mut Context<Self>) {
+        self.history.clear();
+        cx.notify();
+    }
+
     pub fn push(
         &mut self,
         notification: impl Into<Notification>,
@@ -376,6 +518,23 @@ impl NotificationList {
         let id = notification.id.clone();
         let autohide = notification.autohide;
 
+        let politeness = match notification.type_ {
+            Some(NotificationType::Warning | NotificationType::Error) => Politeness::Assertive,
+            _ => Politeness::Polite,
+        };
+        let message = notification
+            .title
+            .iter()
+            .chain(notification.message.iter())
+            .map(|s| s.as_ref())
+            .collect::<Vec<&str>>()
+            .join(". ");
+        announce(message, politeness, cx);
+        self.record(&notification);
+        if notification.native && !window.is_window_active() {
+            send_native_notification(&notification, window, cx);
+        }
+
         // Remove the notification by id, for keep unique.
         self.notifications.retain(|note| note.read(cx).id != id);
 
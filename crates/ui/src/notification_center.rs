@@ -0,0 +1,257 @@
+//! A panel of the past notifications, grouped by the source.
+//!
+//! ```ignore
+//! window.push_notification(Notification::info("Saved").source("Documents"), cx);
+//!
+//! // Open the notification center in a drawer
+//! window.open_notification_center(cx);
+//! ```
+use chrono::Local;
+use gpui::{
+    div, prelude::FluentBuilder as _, App, Entity, InteractiveElement as _, IntoElement,
+    ParentElement as _, RenderOnce, SharedString, StatefulInteractiveElement as _, StyleRefinement,
+    Styled, Window,
+};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    i18n::t,
+    locale::format_relative_time,
+    notification::{NotificationList, NotificationRecord},
+    v_flex, ActiveTheme as _, Disableable as _, Root, Sizable as _, StyledExt as _,
+};
+
+/// Group the records by the source, the groups are ordered by the newest record.
+///
+/// The `records` are from the newest to the oldest.
+fn group_by_source(
+    records: Vec<NotificationRecord>,
+) -> Vec<(Option<SharedString>, Vec<NotificationRecord>)> {
+    let mut groups: Vec<(Option<SharedString>, Vec<NotificationRecord>)> = vec![];
+    for record in records {
+        match groups
+            .iter_mut()
+            .find(|(source, _)| *source == record.source)
+        {
+            Some((_, records)) => records.push(record),
+            None => groups.push((record.source.clone(), vec![record])),
+        }
+    }
+    groups
+}
+
+/// A panel of the notification history of the window, with the buttons to mark all as read
+/// and to clear all.
+///
+/// See also [`ContextModal::open_notification_center`](crate::ContextModal::open_notification_center).
+#[derive(IntoElement)]
+pub struct NotificationCenter {
+    style: StyleRefinement,
+}
+
+impl NotificationCenter {
+    pub fn new() -> Self {
+        Self {
+            style: StyleRefinement::default(),
+        }
+    }
+
+    fn render_record(
+        list: &Entity<NotificationList>,
+        record: NotificationRecord,
+        cx: &App,
+    ) -> impl IntoElement {
+        let list = list.clone();
+        let id = record.id;
+        let time = format_relative_time(&record.created_at, &Local::now());
+
+        h_flex()
+            .id(("notification-record", id))
+            .items_start()
+            .gap_2()
+            .px_2()
+            .py_1p5()
+            .rounded(cx.theme().radius)
+            .hover(|this| this.bg(cx.theme().accent))
+            .when_some(record.type_, |this, type_| {
+                this.child(div().pt_0p5().child(type_.icon(cx)))
+            })
+            .child(
+                v_flex()
+                    .flex_1()
+                    .overflow_hidden()
+                    .text_sm()
+                    .when_some(record.title, |this, title| {
+                        this.child(div().font_semibold().child(title))
+                    })
+                    .when_some(record.message, |this, message| this.child(message))
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(time),
+                    ),
+            )
+            .when(!record.read, |this| {
+                this.child(
+                    div()
+                        .mt_1p5()
+                        .size_2()
+                        .flex_shrink_0()
+                        .rounded_full()
+                        .bg(cx.theme().primary),
+                )
+            })
+            .on_click(move |_, window, cx| {
+                list.update(cx, |list, cx| list.mark_read(id, cx));
+                window.refresh();
+            })
+    }
+}
+
+impl Default for NotificationCenter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Styled for NotificationCenter {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for NotificationCenter {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let list = Root::read(window, cx).notification.clone();
+        let history = list.read(cx).history();
+        let unread_count = list.read(cx).unread_count();
+        let is_empty = history.is_empty();
+
+        v_flex()
+            .gap_3()
+            .refine_style(&self.style)
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(t!("NotificationCenter.unread", count = unread_count)),
+                    )
+                    .child(
+                        Button::new("mark-all-read")
+                            .ghost()
+                            .xsmall()
+                            .label(t!("NotificationCenter.mark_all_read"))
+                            .disabled(unread_count == 0)
+                            .on_click({
+                                let list = list.clone();
+                                move |_, window, cx| {
+                                    list.update(cx, |list, cx| list.mark_all_read(cx));
+                                    window.refresh();
+                                }
+                            }),
+                    )
+                    .child(
+                        Button::new("clear-all")
+                            .ghost()
+                            .xsmall()
+                            .label(t!("NotificationCenter.clear_all"))
+                            .disabled(is_empty)
+                            .on_click({
+                                let list = list.clone();
+                                move |_, window, cx| {
+                                    list.update(cx, |list, cx| list.clear_history(cx));
+                                    window.refresh();
+                                }
+                            }),
+                    ),
+            )
+            .when(is_empty, |this| {
+                this.child(
+                    div()
+                        .py_6()
+                        .text_sm()
+                        .text_center()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(t!("NotificationCenter.empty")),
+                )
+            })
+            .children(
+                group_by_source(history)
+                    .into_iter()
+                    .map(|(source, records)| {
+                        v_flex()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .px_2()
+                                    .text_xs()
+                                    .font_semibold()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(source.unwrap_or_else(|| {
+                                        t!("NotificationCenter.general").into()
+                                    })),
+                            )
+                            .children(
+                                records
+                                    .into_iter()
+                                    .map(|record| Self::render_record(&list, record, cx)),
+                            )
+                    }),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+    use gpui::SharedString;
+
+    use super::group_by_source;
+    use crate::notification::NotificationRecord;
+
+    fn record(id: usize, source: Option<&'static str>) -> NotificationRecord {
+        NotificationRecord {
+            id,
+            type_: None,
+            title: None,
+            message: None,
+            source: source.map(SharedString::from),
+            created_at: Local::now(),
+            read: false,
+        }
+    }
+
+    #[test]
+    fn test_group_by_source() {
+        let groups = group_by_source(vec![
+            record(3, Some("Sync")),
+            record(2, None),
+            record(1, Some("Sync")),
+            record(0, Some("Build")),
+        ]);
+
+        let groups = groups
+            .iter()
+            .map(|(source, records)| {
+                (
+                    source.as_ref().map(|s| s.to_string()),
+                    records.iter().map(|r| r.id).collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![
+                (Some("Sync".to_string()), vec![3, 1]),
+                (None, vec![2]),
+                (Some("Build".to_string()), vec![0]),
+            ]
+        );
+    }
+}
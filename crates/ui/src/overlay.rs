@@ -0,0 +1,291 @@
+//! The window level overlay layers, to coordinate the stacking order, the outside click
+//! dismissal and the `escape` handling of the stacked overlays (e.g.: a menu in a popover).
+//!
+//! The overlay is registered by [`open`] when it is shown, and unregistered by [`close`]
+//! when it is dismissed. The `escape` is handled by the [`Root`](crate::Root) to dismiss the
+//! topmost overlay of the window if the focused element does not handle it.
+//!
+//! The [`Popover`](crate::popover::Popover), the dropdown list, the
+//! [`ContextMenu`](crate::menu::ContextMenu) and the date picker calendar are registered.
+//! The tooltips and the notifications are not dismissed by the outside click, so they only
+//! use the [`OverlayLayer::priority`] to paint.
+//!
+//! ```ignore
+//! let overlay_id = overlay::open(OverlayLayer::Popover, window, cx, move |window, cx| {
+//!     // Dismiss the popover.
+//! });
+//!
+//! div()
+//!     .on_mouse_down_out(move |event, window, cx| {
+//!         // Ignore the click in the overlays above, e.g.: a menu opened by the popover.
+//!         if overlay::contains_above(overlay_id, event.position, window, cx) {
+//!             return;
+//!         }
+//!         overlay::close(overlay_id, cx);
+//!     })
+//!     .child(overlay::bounds_tracker(overlay_id))
+//! ```
+use std::rc::Rc;
+
+use gpui::{canvas, App, Bounds, Global, IntoElement, Pixels, Point, Styled as _, Window};
+
+/// The layers of the overlays, the overlay in a higher layer is stacked above the lower ones,
+/// and the overlays in the same layer are stacked by the opened order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OverlayLayer {
+    /// The popovers, e.g.: [`Popover`](crate::popover::Popover), the dropdown list.
+    Popover,
+    /// The menus, e.g.: [`ContextMenu`](crate::menu::ContextMenu), the date picker calendar.
+    Menu,
+    /// The notifications (toasts).
+    Notification,
+    /// The tooltips.
+    Tooltip,
+}
+
+impl OverlayLayer {
+    /// Returns the priority of the `deferred` element to paint the overlay of the layer.
+    pub fn priority(&self) -> usize {
+        match self {
+            Self::Popover => 1,
+            Self::Menu => 2,
+            Self::Notification => 3,
+            Self::Tooltip => 4,
+        }
+    }
+}
+
+/// The id of an opened overlay, returned by [`open`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OverlayId(usize);
+
+struct OverlayEntry {
+    id: OverlayId,
+    window_id: u64,
+    layer: OverlayLayer,
+    bounds: Option<Bounds<Pixels>>,
+    on_dismiss: Rc<dyn Fn(&mut Window, &mut App)>,
+}
+
+#[derive(Default)]
+struct OverlayStack {
+    next_id: usize,
+    /// The opened overlays, in the opened order.
+    entries: Vec<OverlayEntry>,
+}
+
+impl Global for OverlayStack {}
+
+impl OverlayStack {
+    /// Returns the overlays of the window from the bottom to the top.
+    fn stacked(&self, window_id: u64) -> Vec<&OverlayEntry> {
+        let mut entries = self
+            .entries
+            .iter()
+            .filter(|entry| entry.window_id == window_id)
+            .collect::<Vec<_>>();
+        // The sort is stable, so the opened order is kept in the same layer.
+        entries.sort_by_key(|entry| entry.layer);
+        entries
+    }
+
+    fn contains_above(&self, id: OverlayId, position: Point<Pixels>) -> bool {
+        let Some(window_id) = self
+            .entries
+            .iter()
+            .find(|entry| entry.id == id)
+            .map(|entry| entry.window_id)
+        else {
+            return false;
+        };
+
+        self.stacked(window_id)
+            .into_iter()
+            .skip_while(|entry| entry.id != id)
+            .skip(1)
+            .any(|entry| {
+                entry
+                    .bounds
+                    .map_or(false, |bounds| bounds.contains(&position))
+            })
+    }
+}
+
+fn window_id(window: &Window) -> u64 {
+    window.window_handle().window_id().as_u64()
+}
+
+/// Register an overlay of the window, the `on_dismiss` is called to dismiss it by [`dismiss_topmost`].
+///
+/// The overlay must be unregistered by [`close`] after it is dismissed.
+pub fn open(
+    layer: OverlayLayer,
+    window: &Window,
+    cx: &mut App,
+    on_dismiss: impl Fn(&mut Window, &mut App) + 'static,
+) -> OverlayId {
+    let stack = cx.default_global::<OverlayStack>();
+    let id = OverlayId(stack.next_id);
+    stack.next_id += 1;
+    stack.entries.push(OverlayEntry {
+        id,
+        window_id: window_id(window),
+        layer,
+        bounds: None,
+        on_dismiss: Rc::new(on_dismiss),
+    });
+    id
+}
+
+/// Register the overlay while `is_open` and unregister it when closed, for the overlays shown
+/// by an open state (e.g.: the dropdown list), call this in the rendering with the `id` kept in
+/// the state.
+///
+/// Returns the id of the registered overlay if `is_open`.
+pub fn sync(
+    id: &mut Option<OverlayId>,
+    is_open: bool,
+    layer: OverlayLayer,
+    window: &Window,
+    cx: &mut App,
+    on_dismiss: impl Fn(&mut Window, &mut App) + 'static,
+) -> Option<OverlayId> {
+    match (is_open, *id) {
+        (true, None) => *id = Some(open(layer, window, cx, on_dismiss)),
+        (false, Some(prev)) => {
+            close(prev, cx);
+            *id = None;
+        }
+        _ => {}
+    }
+    *id
+}
+
+/// Unregister the overlay.
+pub fn close(id: OverlayId, cx: &mut App) {
+    if cx.has_global::<OverlayStack>() {
+        cx.global_mut::<OverlayStack>()
+            .entries
+            .retain(|entry| entry.id != id);
+    }
+}
+
+/// Update the bounds of the overlay, for the outside click check by [`contains_above`].
+pub fn set_bounds(id: OverlayId, bounds: Bounds<Pixels>, cx: &mut App) {
+    if !cx.has_global::<OverlayStack>() {
+        return;
+    }
+
+    if let Some(entry) = cx
+        .global_mut::<OverlayStack>()
+        .entries
+        .iter_mut()
+        .find(|entry| entry.id == id)
+    {
+        entry.bounds = Some(bounds);
+    }
+}
+
+/// An element to track the bounds of the overlay by [`set_bounds`], it should be the
+/// child of the overlay.
+pub fn bounds_tracker(id: OverlayId) -> impl IntoElement {
+    canvas(
+        move |bounds, _, cx| set_bounds(id, bounds, cx),
+        |_, _, _, _| {},
+    )
+    .absolute()
+    .size_full()
+}
+
+/// Returns true if the `position` is in an overlay stacked above the overlay `id`.
+///
+/// The outside click of an overlay should be ignored if this returns true.
+pub fn contains_above(id: OverlayId, position: Point<Pixels>, _: &Window, cx: &App) -> bool {
+    cx.try_global::<OverlayStack>()
+        .map_or(false, |stack| stack.contains_above(id, position))
+}
+
+/// Returns true if the overlay is the topmost overlay of the window.
+pub fn is_topmost(id: OverlayId, window: &Window, cx: &App) -> bool {
+    cx.try_global::<OverlayStack>().map_or(false, |stack| {
+        stack
+            .stacked(window_id(window))
+            .last()
+            .map_or(false, |entry| entry.id == id)
+    })
+}
+
+/// Returns true if there is any overlay opened in the window.
+pub fn has_overlay(window: &Window, cx: &App) -> bool {
+    cx.try_global::<OverlayStack>().map_or(false, |stack| {
+        let window_id = window_id(window);
+        stack
+            .entries
+            .iter()
+            .any(|entry| entry.window_id == window_id)
+    })
+}
+
+/// Dismiss the topmost overlay of the window, returns false if there is no overlay.
+pub fn dismiss_topmost(window: &mut Window, cx: &mut App) -> bool {
+    let Some((id, on_dismiss)) = cx.try_global::<OverlayStack>().and_then(|stack| {
+        stack
+            .stacked(window_id(window))
+            .last()
+            .map(|entry| (entry.id, entry.on_dismiss.clone()))
+    }) else {
+        return false;
+    };
+
+    close(id, cx);
+    on_dismiss(window, cx);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use gpui::{point, px, size, Bounds};
+
+    use super::{OverlayEntry, OverlayId, OverlayLayer, OverlayStack};
+
+    fn entry(id: usize, window_id: u64, layer: OverlayLayer, x: f32) -> OverlayEntry {
+        OverlayEntry {
+            id: OverlayId(id),
+            window_id,
+            layer,
+            bounds: Some(Bounds::new(point(px(x), px(0.)), size(px(100.), px(100.)))),
+            on_dismiss: Rc::new(|_, _| {}),
+        }
+    }
+
+    #[test]
+    fn test_overlay_stack() {
+        let stack = OverlayStack {
+            next_id: 4,
+            entries: vec![
+                entry(0, 1, OverlayLayer::Menu, 0.),
+                entry(1, 1, OverlayLayer::Popover, 100.),
+                entry(2, 2, OverlayLayer::Tooltip, 200.),
+                entry(3, 1, OverlayLayer::Popover, 200.),
+            ],
+        };
+
+        let ids = stack
+            .stacked(1)
+            .into_iter()
+            .map(|entry| entry.id.0)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 3, 0]);
+
+        // The menu is above the popovers.
+        assert!(stack.contains_above(OverlayId(1), point(px(10.), px(10.))));
+        assert!(!stack.contains_above(OverlayId(0), point(px(150.), px(10.))));
+        // The popover opened later is above.
+        assert!(stack.contains_above(OverlayId(1), point(px(250.), px(10.))));
+        assert!(!stack.contains_above(OverlayId(3), point(px(150.), px(10.))));
+        // The overlays of the other windows are ignored.
+        assert!(!stack.contains_above(OverlayId(3), point(px(250.), px(10.))));
+    }
+}
@@ -0,0 +1,226 @@
+//! The registry of the third-party plugins, to register the components, icons, theme colors
+//! and commands by the external crates under the namespaced ids, e.g.: `acme:chart`.
+//!
+//! ```ignore
+//! plugin::register(
+//!     Plugin::new("acme")
+//!         .component("chart", |window, cx| AcmeChart::new(window, cx).into_any_element())
+//!         .icon("logo", "icons/acme/logo.svg")
+//!         .theme_color("brand", hsla(0.6, 0.8, 0.5, 1.), hsla(0.6, 0.8, 0.6, 1.))
+//!         .command("open-dashboard", "Acme: Open Dashboard", OpenDashboard),
+//!     cx,
+//! )?;
+//!
+//! Icon::named("acme:logo");
+//! plugin::render_component("acme:chart", window, cx);
+//! plugin::theme_color("acme:brand", cx);
+//! ```
+use std::{collections::HashMap, rc::Rc};
+
+use anyhow::{anyhow, Result};
+use gpui::{Action, AnyElement, App, Global, Hsla, SharedString, Window};
+
+use crate::{icon::register_icons, ActiveTheme as _};
+
+type ComponentBuilder = Rc<dyn Fn(&mut Window, &mut App) -> AnyElement>;
+
+/// A command of the plugin, to list in the command palette.
+pub struct PluginCommand {
+    /// The namespaced id of the command.
+    pub id: SharedString,
+    pub title: SharedString,
+    pub action: Box<dyn Action>,
+}
+
+impl Clone for PluginCommand {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            action: self.action.boxed_clone(),
+        }
+    }
+}
+
+impl PluginCommand {
+    /// Dispatch the action of the command to the focused element of the window.
+    pub fn dispatch(&self, window: &mut Window, cx: &mut App) {
+        window.dispatch_action(self.action.boxed_clone(), cx);
+    }
+}
+
+/// A plugin with the contributions under the namespace, registered by [`register`].
+///
+/// The names of the contributions are prefixed by the namespace, e.g.: the icon `logo` of
+/// the `acme` plugin is `acme:logo`.
+pub struct Plugin {
+    namespace: SharedString,
+    components: Vec<(SharedString, ComponentBuilder)>,
+    icons: Vec<(SharedString, SharedString)>,
+    colors: Vec<(SharedString, Hsla, Hsla)>,
+    commands: Vec<PluginCommand>,
+}
+
+impl Plugin {
+    pub fn new(namespace: impl Into<SharedString>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            components: vec![],
+            icons: vec![],
+            colors: vec![],
+            commands: vec![],
+        }
+    }
+
+    fn qualified(&self, name: impl Into<SharedString>) -> SharedString {
+        qualified_id(&self.namespace, &name.into())
+    }
+
+    /// Add a component, rendered by [`render_component`].
+    pub fn component(
+        mut self,
+        name: impl Into<SharedString>,
+        build: impl Fn(&mut Window, &mut App) -> AnyElement + 'static,
+    ) -> Self {
+        self.components.push((self.qualified(name), Rc::new(build)));
+        self
+    }
+
+    /// Add an icon with the path of the Assets bundle, used by [`Icon::named`](crate::Icon::named).
+    pub fn icon(mut self, name: impl Into<SharedString>, path: impl Into<SharedString>) -> Self {
+        self.icons.push((self.qualified(name), path.into()));
+        self
+    }
+
+    /// Add a theme color of the light and dark mode, resolved by [`theme_color`].
+    pub fn theme_color(mut self, name: impl Into<SharedString>, light: Hsla, dark: Hsla) -> Self {
+        self.colors.push((self.qualified(name), light, dark));
+        self
+    }
+
+    /// Add a command to dispatch the `action`, listed by [`commands`].
+    pub fn command(
+        mut self,
+        name: impl Into<SharedString>,
+        title: impl Into<SharedString>,
+        action: impl Action,
+    ) -> Self {
+        self.commands.push(PluginCommand {
+            id: self.qualified(name),
+            title: title.into(),
+            action: Box::new(action),
+        });
+        self
+    }
+}
+
+#[derive(Default)]
+struct PluginRegistry {
+    namespaces: Vec<SharedString>,
+    components: HashMap<SharedString, ComponentBuilder>,
+    colors: HashMap<SharedString, (Hsla, Hsla)>,
+    commands: Vec<PluginCommand>,
+}
+
+impl Global for PluginRegistry {}
+
+/// Returns the namespaced id of the `name`.
+fn qualified_id(namespace: &str, name: &str) -> SharedString {
+    format!("{}:{}", namespace, name).into()
+}
+
+/// Returns an error if the namespace is not valid, it must be non-empty without `:`.
+fn validate_namespace(namespace: &str) -> Result<()> {
+    if namespace.is_empty() || namespace.contains(':') || namespace.contains(char::is_whitespace) {
+        return Err(anyhow!("invalid plugin namespace: {:?}", namespace));
+    }
+    Ok(())
+}
+
+/// Register the plugin, returns an error if the namespace is invalid or already registered.
+pub fn register(plugin: Plugin, cx: &mut App) -> Result<()> {
+    validate_namespace(&plugin.namespace)?;
+    if cx
+        .default_global::<PluginRegistry>()
+        .namespaces
+        .contains(&plugin.namespace)
+    {
+        return Err(anyhow!(
+            "plugin namespace {:?} is already registered",
+            plugin.namespace
+        ));
+    }
+
+    register_icons(cx, plugin.icons);
+
+    let registry = cx.global_mut::<PluginRegistry>();
+    registry.namespaces.push(plugin.namespace);
+    registry.components.extend(plugin.components);
+    registry.colors.extend(
+        plugin
+            .colors
+            .into_iter()
+            .map(|(id, light, dark)| (id, (light, dark))),
+    );
+    registry.commands.extend(plugin.commands);
+    Ok(())
+}
+
+/// Returns the namespaces of the registered plugins.
+pub fn plugins(cx: &App) -> Vec<SharedString> {
+    cx.try_global::<PluginRegistry>()
+        .map(|registry| registry.namespaces.clone())
+        .unwrap_or_default()
+}
+
+/// Returns the ids of the registered components.
+pub fn components(cx: &App) -> Vec<SharedString> {
+    let mut ids = cx
+        .try_global::<PluginRegistry>()
+        .map(|registry| registry.components.keys().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    ids.sort();
+    ids
+}
+
+/// Render the component by the namespaced id, `None` if it is not registered.
+pub fn render_component(id: &str, window: &mut Window, cx: &mut App) -> Option<AnyElement> {
+    let build = cx
+        .try_global::<PluginRegistry>()?
+        .components
+        .get(&SharedString::from(id.to_string()))?
+        .clone();
+    Some(build(window, cx))
+}
+
+/// Returns the theme color by the namespaced id for the current theme mode.
+pub fn theme_color(id: &str, cx: &App) -> Option<Hsla> {
+    let (light, dark) = *cx
+        .try_global::<PluginRegistry>()?
+        .colors
+        .get(&SharedString::from(id.to_string()))?;
+    Some(if cx.theme().is_dark() { dark } else { light })
+}
+
+/// Returns the commands of the plugins, in the registered order.
+pub fn commands(cx: &App) -> Vec<PluginCommand> {
+    cx.try_global::<PluginRegistry>()
+        .map(|registry| registry.commands.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{qualified_id, validate_namespace};
+
+    #[test]
+    fn test_namespace() {
+        assert_eq!(&*qualified_id("acme", "chart"), "acme:chart");
+
+        assert!(validate_namespace("acme").is_ok());
+        assert!(validate_namespace("acme-charts").is_ok());
+        assert!(validate_namespace("").is_err());
+        assert!(validate_namespace("acme:ui").is_err());
+        assert!(validate_namespace("acme ui").is_err());
+    }
+}
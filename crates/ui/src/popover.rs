@@ -5,19 +5,31 @@ use gpui::{
     ManagedView, MouseButton, MouseDownEvent, ParentElement, Pixels, Point, Render, Style,
     StyleRefinement, Styled, Window,
 };
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
-use crate::{actions::Cancel, Selectable, StyledExt as _};
+use crate::{
+    actions::Cancel,
+    overlay::{self, OverlayId, OverlayLayer},
+    FocusNext, FocusPrev, FocusRestore, FocusTrap, FocusTrapExt as _, Selectable, StyledExt as _,
+};
 
 const CONTEXT: &str = "Popover";
 
 pub fn init(cx: &mut App) {
-    cx.bind_keys([KeyBinding::new("escape", Cancel, Some(CONTEXT))])
+    cx.bind_keys([
+        KeyBinding::new("escape", Cancel, Some(CONTEXT)),
+        KeyBinding::new("tab", FocusNext, Some(CONTEXT)),
+        KeyBinding::new("shift-tab", FocusPrev, Some(CONTEXT)),
+    ])
 }
 
 pub struct PopoverContent {
     style: StyleRefinement,
     focus_handle: FocusHandle,
+    focus_handles: Vec<FocusHandle>,
     content: Rc<dyn Fn(&mut Window, &mut Context<Self>) -> AnyElement>,
 }
 
@@ -31,9 +43,18 @@ impl PopoverContent {
         Self {
             style: StyleRefinement::default(),
             focus_handle,
+            focus_handles: vec![],
             content: Rc::new(content),
         }
     }
+
+    /// Set the focus handles to cycle by Tab / Shift-Tab, default is empty.
+    ///
+    /// The focus is trapped inside the popover until it is dismissed.
+    pub fn focus_handles(mut self, handles: impl IntoIterator<Item = FocusHandle>) -> Self {
+        self.focus_handles = handles.into_iter().collect();
+        self
+    }
 }
 impl EventEmitter<DismissEvent> for PopoverContent {}
 
@@ -51,11 +72,14 @@ impl Styled for PopoverContent {
 
 impl Render for PopoverContent {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_trap = FocusTrap::new().handles(self.focus_handles.iter().cloned());
+
         div()
             .p_2()
             .refine_style(&self.style)
             .track_focus(&self.focus_handle)
             .key_context(CONTEXT)
+            .focus_trap(&focus_trap)
             .on_action(cx.listener(|_, _: &Cancel, _, cx| {
                 cx.propagate();
                 cx.emit(DismissEvent);
@@ -194,6 +218,8 @@ pub struct PopoverElementState<M> {
     popover_element: Option<AnyElement>,
     trigger_element: Option<AnyElement>,
     content_view: Rc<RefCell<Option<Entity<M>>>>,
+    /// The overlay id of the opened popover.
+    overlay_id: Rc<Cell<Option<OverlayId>>>,
     /// Trigger bounds for positioning the popover.
     trigger_bounds: Option<Bounds<Pixels>>,
 }
@@ -206,6 +232,7 @@ impl<M> Default for PopoverElementState<M> {
             popover_element: None,
             trigger_element: None,
             content_view: Rc::new(RefCell::new(None)),
+            overlay_id: Rc::new(Cell::new(None)),
             trigger_bounds: None,
         }
     }
@@ -272,6 +299,7 @@ impl<M: ManagedView> Element for Popover<M> {
 
                     let mut element = {
                         let content_view_mut = element_state.content_view.clone();
+                        let overlay_id = element_state.overlay_id.clone();
                         let anchor = view.anchor;
                         let no_style = view.no_style;
                         deferred(
@@ -287,8 +315,26 @@ impl<M: ManagedView> Element for Popover<M> {
                                         }
                                     })
                                     .child(content_view.clone())
+                                    .when_some(overlay_id.get(), |this, id| {
+                                        this.child(overlay::bounds_tracker(id))
+                                    })
                                     .when(!no_style, |this| {
-                                        this.on_mouse_down_out(move |_, window, _| {
+                                        this.on_mouse_down_out(move |event, window, cx| {
+                                            if let Some(id) = overlay_id.get() {
+                                                // Keep open if click in the overlays above,
+                                                // e.g.: a dropdown menu in the popover.
+                                                if overlay::contains_above(
+                                                    id,
+                                                    event.position,
+                                                    window,
+                                                    cx,
+                                                ) {
+                                                    return;
+                                                }
+                                                overlay::close(id, cx);
+                                                overlay_id.set(None);
+                                            }
+
                                             // Update the element_state.content_view to `None`,
                                             // so that the `paint`` method will not paint it.
                                             *content_view_mut.borrow_mut() = None;
@@ -297,7 +343,7 @@ impl<M: ManagedView> Element for Popover<M> {
                                     }),
                             ),
                         )
-                        .with_priority(1)
+                        .with_priority(OverlayLayer::Popover.priority())
                         .into_any()
                     };
 
@@ -395,6 +441,7 @@ impl<M: ManagedView> Element for Popover<M> {
                     return;
                 };
                 let old_content_view = element_state.content_view.clone();
+                let overlay_id = element_state.overlay_id.clone();
                 let hitbox_id = prepaint.hitbox.id;
                 let mouse_button = this.mouse_button;
                 window.on_mouse_event(move |event: &MouseDownEvent, phase, window, cx| {
@@ -408,19 +455,29 @@ impl<M: ManagedView> Element for Popover<M> {
                         let new_content_view = (content_build)(window, cx);
                         let old_content_view1 = old_content_view.clone();
 
-                        let previous_focus_handle = window.focused(cx);
+                        let focus_restore = FocusRestore::save(window, cx);
 
+                        if let Some(id) = overlay_id.take() {
+                            overlay::close(id, cx);
+                        }
+                        overlay_id.set(Some(overlay::open(OverlayLayer::Popover, window, cx, {
+                            let content_view = new_content_view.downgrade();
+                            move |_, cx| {
+                                _ = content_view.update(cx, |_, cx| cx.emit(DismissEvent));
+                            }
+                        })));
+
+                        let overlay_id = overlay_id.clone();
                         window
                             .subscribe(
                                 &new_content_view,
                                 cx,
                                 move |modal, _: &DismissEvent, window, cx| {
                                     if modal.focus_handle(cx).contains_focused(window, cx) {
-                                        if let Some(previous_focus_handle) =
-                                            previous_focus_handle.as_ref()
-                                        {
-                                            window.focus(previous_focus_handle);
-                                        }
+                                        focus_restore.restore(window);
+                                    }
+                                    if let Some(id) = overlay_id.take() {
+                                        overlay::close(id, cx);
                                     }
                                     *old_content_view1.borrow_mut() = None;
 
@@ -0,0 +1,220 @@
+//! The indicators of the presence and the activity of the users, e.g.: in the collaboration
+//! and chat apps.
+use std::time::Duration;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, Animation, AnimationExt as _, App, ElementId, Hsla,
+    IntoElement, ParentElement as _, Pixels, RenderOnce, SharedString, Styled as _, Window,
+};
+
+use crate::{animation::cubic_bezier, h_flex, i18n::t, ActiveTheme as _, Sizable, Size};
+
+/// The presence status of a user.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PresenceStatus {
+    Online,
+    Away,
+    Busy,
+    #[default]
+    Offline,
+}
+
+impl PresenceStatus {
+    /// Returns the color of the status from the theme.
+    pub fn color(&self, cx: &App) -> Hsla {
+        match self {
+            Self::Online => cx.theme().success,
+            Self::Away => cx.theme().warning,
+            Self::Busy => cx.theme().danger,
+            Self::Offline => cx.theme().muted_foreground,
+        }
+    }
+
+    /// Returns the name of the status.
+    pub fn label(&self) -> SharedString {
+        match self {
+            Self::Online => t!("Presence.online"),
+            Self::Away => t!("Presence.away"),
+            Self::Busy => t!("Presence.busy"),
+            Self::Offline => t!("Presence.offline"),
+        }
+        .into()
+    }
+}
+
+/// A dot of the [`PresenceStatus`], e.g.: at the corner of an avatar.
+#[derive(IntoElement)]
+pub struct PresenceDot {
+    id: ElementId,
+    status: PresenceStatus,
+    pulse: bool,
+    size: Size,
+}
+
+impl PresenceDot {
+    pub fn new(id: impl Into<ElementId>, status: PresenceStatus) -> Self {
+        Self {
+            id: id.into(),
+            status,
+            pulse: false,
+            size: Size::Medium,
+        }
+    }
+
+    /// Set to show the pulse animation, default is false.
+    ///
+    /// The offline status is never pulsed, and the animation is disabled by the reduce motion.
+    pub fn pulse(mut self, pulse: bool) -> Self {
+        self.pulse = pulse;
+        self
+    }
+
+    fn dot_size(&self) -> Pixels {
+        match self.size {
+            Size::XSmall => px(6.),
+            Size::Small => px(8.),
+            Size::Large => px(12.),
+            Size::Size(size) => size,
+            _ => px(10.),
+        }
+    }
+}
+
+impl Sizable for PresenceDot {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl RenderOnce for PresenceDot {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let size = self.dot_size();
+        let color = self.status.color(cx);
+        let pulse =
+            self.pulse && self.status != PresenceStatus::Offline && !cx.theme().reduce_motion;
+
+        div()
+            .id(self.id)
+            .relative()
+            .flex_shrink_0()
+            .size(size)
+            .when(pulse, |this| {
+                this.child(
+                    div().absolute().rounded_full().bg(color).with_animation(
+                        "pulse",
+                        Animation::new(Duration::from_secs(2))
+                            .repeat()
+                            .with_easing(cubic_bezier(0., 0., 0.2, 1.)),
+                        move |this, delta| {
+                            let offset = size * delta / 2.;
+                            this.top(-offset)
+                                .left(-offset)
+                                .size(size + size * delta)
+                                .opacity(0.6 * (1. - delta))
+                        },
+                    ),
+                )
+            })
+            .child(
+                div()
+                    .absolute()
+                    .size_full()
+                    .rounded_full()
+                    .bg(color)
+                    .border_1()
+                    .border_color(cx.theme().background),
+            )
+    }
+}
+
+/// The animated dots to indicate someone is typing.
+#[derive(IntoElement)]
+pub struct TypingIndicator {
+    id: ElementId,
+    label: Option<SharedString>,
+    size: Size,
+}
+
+impl TypingIndicator {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            label: None,
+            size: Size::Medium,
+        }
+    }
+
+    /// Set the label after the dots, e.g.: `Alice is typing`.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    fn dot_size(&self) -> Pixels {
+        match self.size {
+            Size::XSmall | Size::Small => px(4.),
+            Size::Large => px(8.),
+            Size::Size(size) => size,
+            _ => px(6.),
+        }
+    }
+}
+
+impl Sizable for TypingIndicator {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+/// Returns the opacity of the `ix` dot at the `delta` of the animation, the dots fade in and
+/// out one by one.
+fn typing_dot_opacity(ix: usize, delta: f32) -> f32 {
+    let phase = (delta - ix as f32 / 3.).rem_euclid(1.);
+    let wave = 1. - (phase * 2. - 1.).abs();
+    0.3 + 0.7 * wave
+}
+
+impl RenderOnce for TypingIndicator {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let size = self.dot_size();
+        let color = cx.theme().muted_foreground;
+        let reduce_motion = cx.theme().reduce_motion;
+
+        h_flex()
+            .id(self.id)
+            .gap_2()
+            .text_sm()
+            .text_color(color)
+            .child(h_flex().gap(size / 2.).children((0..3).map(|ix| {
+                let dot = div().size(size).rounded_full().bg(color);
+                if reduce_motion {
+                    dot.opacity(0.6).into_any_element()
+                } else {
+                    dot.with_animation(
+                        ("typing-dot", ix),
+                        Animation::new(Duration::from_millis(1200)).repeat(),
+                        move |this, delta| this.opacity(typing_dot_opacity(ix, delta)),
+                    )
+                    .into_any_element()
+                }
+            })))
+            .when_some(self.label, |this, label| this.child(label))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::typing_dot_opacity;
+
+    #[test]
+    fn test_typing_dot_opacity() {
+        // The first dot is brightest at the middle of the animation.
+        assert_eq!(typing_dot_opacity(0, 0.5), 1.);
+        assert_eq!(typing_dot_opacity(0, 0.), 0.3);
+        // The next dots follow with a third of the cycle.
+        assert!((typing_dot_opacity(1, 0.5 + 1. / 3.) - 1.).abs() < 1e-5);
+        assert!((typing_dot_opacity(2, 0.5 + 2. / 3.) - 1.).abs() < 1e-5);
+    }
+}
@@ -1,12 +1,16 @@
 use std::rc::Rc;
 
 use crate::{
-    checkbox::checkbox_check_icon, h_flex, text::Text, v_flex, ActiveTheme, AxisExt, Sizable, Size,
-    StyledExt,
+    accessibility::{self, text_label, AccessibilityProperties, AccessibilityRole, Accessible},
+    checkbox::checkbox_check_icon,
+    h_flex,
+    text::Text,
+    v_flex, ActiveTheme, AxisExt, LayoutDirection, ReadOnly, RovingFocus, RovingFocusExt as _,
+    Sizable, Size, StyledExt,
 };
 use gpui::{
-    div, prelude::FluentBuilder, relative, rems, AnyElement, App, Axis, Div, ElementId,
-    InteractiveElement, IntoElement, ParentElement, RenderOnce, SharedString,
+    div, prelude::FluentBuilder, px, relative, rems, AnyElement, App, Axis, Div, ElementId,
+    FocusHandle, InteractiveElement, IntoElement, ParentElement, RenderOnce, SharedString,
     StatefulInteractiveElement, StyleRefinement, Styled, Window,
 };
 
@@ -22,8 +26,10 @@ pub struct Radio {
     children: Vec<AnyElement>,
     checked: bool,
     disabled: bool,
+    read_only: bool,
     size: Size,
     on_click: Option<Box<dyn Fn(&bool, &mut Window, &mut App) + 'static>>,
+    accessibility: AccessibilityProperties,
 }
 
 impl Radio {
@@ -36,8 +42,10 @@ impl Radio {
             children: Vec::new(),
             checked: false,
             disabled: false,
+            read_only: false,
             size: Size::default(),
             on_click: None,
+            accessibility: AccessibilityProperties::default(),
         }
     }
 
@@ -62,6 +70,29 @@ impl Radio {
     }
 }
 
+impl Accessible for Radio {
+    fn accessibility_mut(&mut self) -> &mut AccessibilityProperties {
+        &mut self.accessibility
+    }
+
+    fn accessibility(&self, _: &App) -> AccessibilityProperties {
+        AccessibilityProperties::new(AccessibilityRole::Radio)
+            .label(text_label(self.label.as_ref()))
+            .checked(self.checked)
+            .disabled(self.disabled)
+            .read_only(self.read_only)
+            .merge(&self.accessibility)
+    }
+}
+
+impl ReadOnly for Radio {
+    /// Set the read-only state of the radio, the checked state can't be changed by the user.
+    fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+}
+
 impl Sizable for Radio {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
         self.size = size.into();
@@ -89,6 +120,8 @@ impl ParentElement for Radio {
 
 impl RenderOnce for Radio {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        accessibility::record_rendered(self.accessibility(cx), window, cx);
+
         let checked = self.checked;
         let disabled = self.disabled;
 
@@ -163,7 +196,7 @@ impl RenderOnce for Radio {
                         .children(self.children),
                 )
                 .when_some(
-                    self.on_click.filter(|_| !self.disabled),
+                    self.on_click.filter(|_| !self.disabled && !self.read_only),
                     |this, on_click| {
                         this.on_click(move |_event, window, cx| {
                             on_click(&!self.checked, window, cx);
@@ -183,7 +216,10 @@ pub struct RadioGroup {
     layout: Axis,
     selected_index: Option<usize>,
     disabled: bool,
+    read_only: bool,
+    focus_handle: Option<FocusHandle>,
     on_change: Option<Rc<dyn Fn(&usize, &mut Window, &mut App) + 'static>>,
+    accessibility: AccessibilityProperties,
 }
 
 impl RadioGroup {
@@ -195,7 +231,10 @@ impl RadioGroup {
             layout: Axis::Vertical,
             selected_index: None,
             disabled: false,
+            read_only: false,
+            focus_handle: None,
             radios: vec![],
+            accessibility: AccessibilityProperties::default(),
         }
     }
 
@@ -233,6 +272,14 @@ impl RadioGroup {
         self
     }
 
+    /// Set the focus handle of the Radio group.
+    ///
+    /// When set, the group is a single Tab stop, and the arrow keys will change the selection.
+    pub fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
+
     /// Add a child Radio element.
     pub fn child(mut self, child: impl Into<Radio>) -> Self {
         self.radios.push(child.into());
@@ -246,6 +293,33 @@ impl RadioGroup {
     }
 }
 
+impl Accessible for RadioGroup {
+    fn accessibility_mut(&mut self) -> &mut AccessibilityProperties {
+        &mut self.accessibility
+    }
+
+    fn accessibility(&self, _: &App) -> AccessibilityProperties {
+        let mut properties = AccessibilityProperties::new(AccessibilityRole::RadioGroup)
+            .disabled(self.disabled)
+            .read_only(self.read_only);
+        if let Some(radio) = self.selected_index.and_then(|ix| self.radios.get(ix)) {
+            properties.value = text_label(radio.label.as_ref());
+        }
+
+        properties.merge(&self.accessibility)
+    }
+}
+
+impl ReadOnly for RadioGroup {
+    /// Set the read-only state of the group, the selection can't be changed by the user.
+    ///
+    /// Unlike the disabled state, the group is still focusable.
+    fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+}
+
 impl Styled for RadioGroup {
     fn style(&mut self) -> &mut StyleRefinement {
         &mut self.style
@@ -271,35 +345,73 @@ impl From<String> for Radio {
 }
 
 impl RenderOnce for RadioGroup {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        accessibility::record_rendered(self.accessibility(cx), window, cx);
+
         let on_change = self.on_change;
         let disabled = self.disabled;
+        let read_only = self.read_only;
         let selected_ix = self.selected_index;
+        let disabled_ixs = self
+            .radios
+            .iter()
+            .enumerate()
+            .filter(|(_, radio)| radio.disabled)
+            .map(|(ix, _)| ix)
+            .collect::<Vec<_>>();
+        let direction = LayoutDirection::of(window, cx);
+        let roving_focus = self
+            .focus_handle
+            .filter(|_| !disabled)
+            .zip(on_change.clone())
+            .map(|(focus_handle, on_change)| {
+                let is_focused = focus_handle.is_focused(window);
+                let roving =
+                    RovingFocus::new(&focus_handle, self.radios.len(), move |ix, window, cx| {
+                        if !read_only {
+                            on_change(ix, window, cx)
+                        }
+                    })
+                    .active_ix(selected_ix)
+                    .direction(direction)
+                    .disabled(disabled_ixs);
+
+                (roving, is_focused)
+            });
 
         let base = if self.layout.is_vertical() {
             v_flex()
         } else {
-            h_flex().w_full().flex_wrap()
+            h_flex().flex_row_with(direction).w_full().flex_wrap()
         };
 
         let mut container = div().id(self.id);
         *container.style() = self.style;
 
-        container.child(
-            base.gap_3()
-                .children(self.radios.into_iter().enumerate().map(|(ix, mut radio)| {
-                    let checked = selected_ix == Some(ix);
-
-                    radio.id = ix.into();
-                    radio.disabled(disabled).checked(checked).when_some(
-                        on_change.clone(),
-                        |this, on_change| {
-                            this.on_click(move |_, window, cx| {
-                                on_change(&ix, window, cx);
-                            })
-                        },
-                    )
-                })),
-        )
+        container
+            .when_some(roving_focus, |this, (roving, is_focused)| {
+                this.roving_focus(roving)
+                    .rounded(cx.theme().radius)
+                    .border_snapped(px(1.), window)
+                    .border_color(cx.theme().transparent)
+                    .when(is_focused, |this| this.focused_border(cx))
+            })
+            .child(
+                base.gap_3()
+                    .children(self.radios.into_iter().enumerate().map(|(ix, mut radio)| {
+                        let checked = selected_ix == Some(ix);
+
+                        radio.id = ix.into();
+                        radio.read_only |= read_only;
+                        radio.disabled(disabled).checked(checked).when_some(
+                            on_change.clone().filter(|_| !read_only),
+                            |this, on_change| {
+                                this.on_click(move |_, window, cx| {
+                                    on_change(&ix, window, cx);
+                                })
+                            },
+                        )
+                    })),
+            )
     }
 }
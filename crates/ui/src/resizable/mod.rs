@@ -2,9 +2,11 @@ use std::ops::Range;
 
 use gpui::{
     px, Along, App, AppContext, Axis, Bounds, Context, ElementId, Entity, EventEmitter, Pixels,
-    Window,
+    SharedString, Window,
 };
 
+use crate::state;
+
 mod panel;
 mod resize_handle;
 pub use panel::*;
@@ -36,6 +38,10 @@ pub struct ResizableState {
     sizes: Vec<Pixels>,
     pub(crate) resizing_panel_ix: Option<usize>,
     bounds: Bounds<Pixels>,
+    /// The key to persist the sizes, see [`ResizableState::persist_sizes`].
+    sizes_key: Option<SharedString>,
+    /// The persisted sizes to restore when the panels are added.
+    restored_sizes: Vec<Pixels>,
 }
 
 impl ResizableState {
@@ -46,9 +52,34 @@ impl ResizableState {
             sizes: vec![],
             resizing_panel_ix: None,
             bounds: Bounds::default(),
+            sizes_key: None,
+            restored_sizes: vec![],
         })
     }
 
+    /// Persist the sizes of the panels to the [`state`](crate::state) storage with the `key`.
+    ///
+    /// The persisted sizes are restored now, and the sizes are saved when the panels are resized.
+    pub fn persist_sizes(&mut self, key: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let key = key.into();
+        if let Some(sizes) = state::get::<Vec<f32>>(&key, cx) {
+            self.restored_sizes = sizes.into_iter().map(px).collect();
+            for ix in 0..self.panels.len() {
+                self.restore_size(ix);
+            }
+            cx.notify();
+        }
+        self.sizes_key = Some(key);
+    }
+
+    /// Apply the persisted size to the panel at `ix`.
+    fn restore_size(&mut self, ix: usize) {
+        if let Some(size) = self.restored_sizes.get(ix).copied() {
+            self.panels[ix].size = Some(size);
+            self.sizes[ix] = size;
+        }
+    }
+
     pub fn insert_panel(
         &mut self,
         size: Option<Pixels>,
@@ -74,9 +105,13 @@ impl ResizableState {
         self.axis = axis;
         if panels_count > self.panels.len() {
             let diff = panels_count - self.panels.len();
+            let start = self.panels.len();
             self.panels
                 .extend(vec![ResizablePanelState::default(); diff]);
             self.sizes.extend(vec![PANEL_MIN_SIZE; diff]);
+            for ix in start..panels_count {
+                self.restore_size(ix);
+            }
         }
     }
 
@@ -135,6 +170,10 @@ impl ResizableState {
 
     pub(crate) fn done_resizing(&mut self, cx: &mut Context<Self>) {
         self.resizing_panel_ix = None;
+        if let Some(key) = &self.sizes_key {
+            let sizes = self.sizes.iter().map(|size| size.0).collect::<Vec<_>>();
+            state::set(key.to_string(), &sizes, cx);
+        }
         cx.emit(ResizablePanelEvent::Resized);
     }
 
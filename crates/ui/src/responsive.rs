@@ -0,0 +1,205 @@
+//! The container queries, to adapt the layout of an element by its own measured width
+//! instead of the window size, e.g.: a table in a narrow resizable panel switches to
+//! a card list.
+//!
+//! ```ignore
+//! let table = self.table.clone();
+//! let cards = self.orders.iter().map(|order| OrderCard::new(order)).collect::<Vec<_>>();
+//!
+//! container("orders")
+//!     .size_full()
+//!     .when_width_above(px(480.), move |this| this.child(OrdersTable::new(&table)))
+//!     .when_width_below(px(480.), move |this| this.children(cards))
+//! ```
+//!
+//! The width is measured in the last frame, so the queries of the first frame use the width
+//! of the window, and the element is rendered again when the measured width changed.
+//!
+//! A matched query is kept until the width crosses its breakpoint by the [`Container::hysteresis`],
+//! so the layout doesn't flicker when the new layout changes the width, e.g.: shows a scrollbar.
+use gpui::{
+    canvas, div, px, AnyElement, App, Div, ElementId, IntoElement, ParentElement, Pixels,
+    RenderOnce, StyleRefinement, Styled, Window,
+};
+
+struct WidthQuery {
+    width: Pixels,
+    below: bool,
+    f: Box<dyn FnOnce(Div) -> Div>,
+}
+
+/// Returns true if the `width` is below the `breakpoint`, the `was_below` is the result of the
+/// last frame to apply the `hysteresis`.
+fn below_breakpoint(
+    width: Pixels,
+    breakpoint: Pixels,
+    was_below: Option<bool>,
+    hysteresis: Pixels,
+) -> bool {
+    match was_below {
+        Some(true) => width < breakpoint + hysteresis,
+        Some(false) => width < breakpoint - hysteresis,
+        None => width < breakpoint,
+    }
+}
+
+#[derive(Default)]
+struct ContainerState {
+    /// The measured width in the last frame.
+    width: Option<Pixels>,
+    /// The breakpoints and whether the width was below them in the last frame.
+    below: Vec<(Pixels, bool)>,
+}
+
+/// Create a [`Container`] with the `id` to keep the measured width.
+pub fn container(id: impl Into<ElementId>) -> Container {
+    Container {
+        id: id.into(),
+        base: div(),
+        children: vec![],
+        queries: vec![],
+        hysteresis: px(8.),
+    }
+}
+
+/// An element to apply the width queries by its own measured width.
+#[derive(IntoElement)]
+pub struct Container {
+    id: ElementId,
+    base: Div,
+    children: Vec<AnyElement>,
+    queries: Vec<WidthQuery>,
+    hysteresis: Pixels,
+}
+
+impl Container {
+    /// Set the hysteresis of the breakpoints, default is 8px.
+    ///
+    /// A matched query is kept until the width crosses its breakpoint by this amount.
+    pub fn hysteresis(mut self, hysteresis: Pixels) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Apply the `f` when the width of this element is less than `width`.
+    ///
+    /// The `f` is applied after the children added before, so it can append the children or
+    /// override the styles.
+    pub fn when_width_below(mut self, width: Pixels, f: impl FnOnce(Div) -> Div + 'static) -> Self {
+        self.queries.push(WidthQuery {
+            width,
+            below: true,
+            f: Box::new(f),
+        });
+        self
+    }
+
+    /// Apply the `f` when the width of this element is greater than or equal to `width`.
+    pub fn when_width_above(mut self, width: Pixels, f: impl FnOnce(Div) -> Div + 'static) -> Self {
+        self.queries.push(WidthQuery {
+            width,
+            below: false,
+            f: Box::new(f),
+        });
+        self
+    }
+}
+
+impl Styled for Container {
+    fn style(&mut self) -> &mut StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl ParentElement for Container {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements);
+    }
+}
+
+impl RenderOnce for Container {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id, cx, |_, _| ContainerState::default());
+        let width = state
+            .read(cx)
+            .width
+            .unwrap_or_else(|| window.viewport_size().width);
+
+        // The queries of the same breakpoint share the result, to keep the `when_width_below`
+        // and `when_width_above` exclusive.
+        let last_below = state.read(cx).below.clone();
+        let mut below: Vec<(Pixels, bool)> = Vec::with_capacity(self.queries.len());
+        let mut base = self.base.children(self.children);
+        for query in self.queries {
+            let is_below = match below.iter().find(|(bp, _)| *bp == query.width) {
+                Some((_, is_below)) => *is_below,
+                None => {
+                    let was_below = last_below
+                        .iter()
+                        .find(|(bp, _)| *bp == query.width)
+                        .map(|(_, below)| *below);
+                    let is_below = below_breakpoint(width, query.width, was_below, self.hysteresis);
+                    below.push((query.width, is_below));
+                    is_below
+                }
+            };
+
+            if is_below == query.below {
+                base = (query.f)(base);
+            }
+        }
+        state.update(cx, |state, _| state.below = below);
+
+        base.child(
+            canvas(
+                move |bounds, window, cx| {
+                    let width = bounds.size.width;
+                    if state.read(cx).width != Some(width) {
+                        state.update(cx, |state, _| state.width = Some(width));
+                        window.refresh();
+                    }
+                },
+                |_, _, _, _| {},
+            )
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::below_breakpoint;
+
+    #[test]
+    fn test_below_breakpoint() {
+        let hysteresis = px(8.);
+        assert!(below_breakpoint(px(479.), px(480.), None, hysteresis));
+        assert!(!below_breakpoint(px(480.), px(480.), None, hysteresis));
+
+        // The last result is kept within the hysteresis.
+        assert!(below_breakpoint(px(485.), px(480.), Some(true), hysteresis));
+        assert!(!below_breakpoint(
+            px(488.),
+            px(480.),
+            Some(true),
+            hysteresis
+        ));
+        assert!(!below_breakpoint(
+            px(475.),
+            px(480.),
+            Some(false),
+            hysteresis
+        ));
+        assert!(below_breakpoint(
+            px(471.),
+            px(480.),
+            Some(false),
+            hysteresis
+        ));
+    }
+}
@@ -1,17 +1,57 @@
 use crate::{
+    accessibility,
+    actions::Cancel,
+    debug_overlay::{self, DebugOverlay, ToggleDebugOverlay},
     drawer::Drawer,
+    i18n::t,
     input::InputState,
     modal::Modal,
     notification::{Notification, NotificationList},
-    window_border, ActiveTheme, Placement,
+    notification_center::NotificationCenter,
+    overlay, state, window_border, ActiveTheme, FocusRestore, LayoutDirection, Placement, Theme,
+    ThemeMode,
 };
 use gpui::{
-    canvas, div, prelude::FluentBuilder as _, AnyView, App, AppContext, Context, DefiniteLength,
-    Entity, FocusHandle, InteractiveElement, IntoElement, ParentElement as _, Render, Styled,
-    Window,
+    actions, canvas, div, prelude::FluentBuilder as _, AnyView, App, AppContext, Bounds, Context,
+    DefiniteLength, Entity, FocusHandle, InteractiveElement, IntoElement, KeyBinding,
+    ParentElement as _, Pixels, Render, SharedString, Styled, Subscription, Window, WindowBounds,
 };
+use serde::{Deserialize, Serialize};
 use std::{any::TypeId, rc::Rc};
 
+actions!(root, [ZoomIn, ZoomOut, ResetZoom]);
+
+const CONTEXT: &str = "Root";
+const MIN_UI_SCALE: f32 = 0.5;
+const MAX_UI_SCALE: f32 = 2.0;
+const UI_SCALE_STEP: f32 = 0.1;
+/// The key of the theme mode in the [`state`] storage, it is shared by all the windows.
+const THEME_MODE_KEY: &str = "theme.mode";
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("escape", Cancel, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-=", ZoomIn, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd--", ZoomOut, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-0", ResetZoom, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-=", ZoomIn, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl--", ZoomOut, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-0", ResetZoom, Some(CONTEXT)),
+    ]);
+}
+
+/// Returns the UI scale after zoom by `steps`, clamped in 0.5 ~ 2.0.
+fn zoom_ui_scale(scale: f32, steps: i32) -> f32 {
+    let scale = scale + UI_SCALE_STEP * steps as f32;
+    ((scale * 10.).round() / 10.).clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+}
+
 /// Extension trait for [`WindowContext`] and [`ViewContext`] to add drawer functionality.
 pub trait ContextModal: Sized {
     /// Opens a Drawer at right placement.
@@ -56,6 +96,9 @@ pub trait ContextModal: Sized {
     /// Returns number of notifications.
     fn notifications(&mut self, cx: &mut App) -> Rc<Vec<Entity<Notification>>>;
 
+    /// Opens the [`NotificationCenter`] of the past notifications in a Drawer.
+    fn open_notification_center(&mut self, cx: &mut App);
+
     /// Return current focused Input entity.
     fn focused_input(&mut self, cx: &mut App) -> Option<Entity<InputState>>;
     /// Returns true if there is a focused Input entity.
@@ -76,7 +119,7 @@ impl ContextModal for Window {
     {
         Root::update(self, cx, move |root, window, cx| {
             if root.active_drawer.is_none() {
-                root.previous_focus_handle = window.focused(cx);
+                root.focus_restore = FocusRestore::save(window, cx);
             }
 
             let focus_handle = cx.focus_handle();
@@ -112,7 +155,7 @@ impl ContextModal for Window {
             // Only save focus handle if there are no active modals.
             // This is used to restore focus when all modals are closed.
             if root.active_modals.len() == 0 {
-                root.previous_focus_handle = window.focused(cx);
+                root.focus_restore = FocusRestore::save(window, cx);
             }
 
             let focus_handle = cx.focus_handle();
@@ -187,6 +230,14 @@ impl ContextModal for Window {
         Rc::new(entity.read(cx).notifications())
     }
 
+    fn open_notification_center(&mut self, cx: &mut App) {
+        self.open_drawer(cx, |drawer, _, _| {
+            drawer
+                .title(t!("NotificationCenter.title"))
+                .child(NotificationCenter::new())
+        })
+    }
+
     fn has_focused_input(&mut self, cx: &mut App) -> bool {
         Root::read(self, cx).focused_input.is_some()
     }
@@ -202,13 +253,55 @@ impl ContextModal for Window {
 pub struct Root {
     /// Used to store the focus handle of the previous view.
     /// When the Modal, Drawer closes, we will focus back to the previous view.
-    previous_focus_handle: Option<FocusHandle>,
+    focus_restore: FocusRestore,
     active_drawer: Option<ActiveDrawer>,
     pub(crate) active_modals: Vec<ActiveModal>,
     pub(super) focused_input: Option<Entity<InputState>>,
     pub notification: Entity<NotificationList>,
     drawer_size: Option<DefiniteLength>,
+    pub(crate) direction: Option<LayoutDirection>,
+    ui_scale: f32,
     view: AnyView,
+    /// The key to persist the state of this window, see [`Root::persist_state`].
+    state_key: Option<SharedString>,
+    _subscriptions: Vec<Subscription>,
+}
+
+/// The window bounds saved by [`Root::persist_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PersistedBounds {
+    Windowed(Bounds<Pixels>),
+    Maximized(Bounds<Pixels>),
+    Fullscreen(Bounds<Pixels>),
+}
+
+impl From<WindowBounds> for PersistedBounds {
+    fn from(bounds: WindowBounds) -> Self {
+        match bounds {
+            WindowBounds::Windowed(bounds) => Self::Windowed(bounds),
+            WindowBounds::Maximized(bounds) => Self::Maximized(bounds),
+            WindowBounds::Fullscreen(bounds) => Self::Fullscreen(bounds),
+        }
+    }
+}
+
+impl From<PersistedBounds> for WindowBounds {
+    fn from(bounds: PersistedBounds) -> Self {
+        match bounds {
+            PersistedBounds::Windowed(bounds) => Self::Windowed(bounds),
+            PersistedBounds::Maximized(bounds) => Self::Maximized(bounds),
+            PersistedBounds::Fullscreen(bounds) => Self::Fullscreen(bounds),
+        }
+    }
+}
+
+fn window_bounds_key(key: &str) -> String {
+    format!("{}.bounds", key)
+}
+
+fn ui_scale_key(key: &str) -> String {
+    format!("{}.ui_scale", key)
 }
 
 #[derive(Clone)]
@@ -227,16 +320,57 @@ pub(crate) struct ActiveModal {
 impl Root {
     pub fn new(view: AnyView, window: &mut Window, cx: &mut Context<Self>) -> Self {
         Self {
-            previous_focus_handle: None,
+            focus_restore: FocusRestore::default(),
             active_drawer: None,
             active_modals: Vec::new(),
             focused_input: None,
             notification: cx.new(|cx| NotificationList::new(window, cx)),
             drawer_size: None,
+            direction: None,
+            ui_scale: 1.,
             view,
+            state_key: None,
+            _subscriptions: vec![],
         }
     }
 
+    /// Persist the state of this window to the [`state`] storage with the `key`.
+    ///
+    /// The window bounds, the UI scale and the theme mode are saved when they are changed, and
+    /// the UI scale and the theme mode are restored now. The window bounds must be restored
+    /// when opening the window, see [`Root::persisted_window_bounds`].
+    pub fn persist_state(
+        &mut self,
+        key: impl Into<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let key = key.into();
+        if let Some(mode) = state::get::<ThemeMode>(THEME_MODE_KEY, cx) {
+            Theme::change(mode, Some(window), cx);
+        }
+        if let Some(scale) = state::get::<f32>(&ui_scale_key(&key), cx) {
+            self.set_ui_scale(scale, window, cx);
+        }
+        self.state_key = Some(key.clone());
+
+        self._subscriptions = vec![
+            cx.observe_window_bounds(window, move |_, window, cx| {
+                let bounds = PersistedBounds::from(window.window_bounds());
+                state::set(window_bounds_key(&key), &bounds, cx);
+            }),
+            cx.observe_global_in::<Theme>(window, |_, _, cx| {
+                state::set(THEME_MODE_KEY, &cx.theme().mode, cx);
+            }),
+        ];
+    }
+
+    /// Returns the window bounds saved by [`Root::persist_state`] with the `key`, use it
+    /// as the `window_bounds` of the `WindowOptions` to restore the window.
+    pub fn persisted_window_bounds(key: &str, cx: &App) -> Option<WindowBounds> {
+        state::get::<PersistedBounds>(&window_bounds_key(key), cx).map(Into::into)
+    }
+
     pub fn update<F>(window: &mut Window, cx: &mut App, f: F)
     where
         F: FnOnce(&mut Self, &mut Window, &mut Context<Self>) + 'static,
@@ -254,10 +388,75 @@ impl Root {
             .read(cx)
     }
 
-    fn focus_back(&mut self, window: &mut Window, _: &mut App) {
-        if let Some(handle) = self.previous_focus_handle.clone() {
-            window.focus(&handle);
+    /// Set the layout direction of this window, `None` to use the direction of the [`Theme`](crate::theme::Theme).
+    pub fn set_direction(
+        &mut self,
+        direction: Option<LayoutDirection>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.direction = direction;
+        window.refresh();
+        cx.notify();
+    }
+
+    /// Returns the UI scale of this window, default is 1.0.
+    pub fn ui_scale(&self) -> f32 {
+        self.ui_scale
+    }
+
+    /// Set the UI scale of this window, the rem based sizes will be multiplied by it.
+    ///
+    /// The scale is clamped in 0.5 ~ 2.0, and saved if the state is persisted by
+    /// [`Root::persist_state`].
+    pub fn set_ui_scale(&mut self, scale: f32, window: &mut Window, cx: &mut Context<Self>) {
+        self.ui_scale = scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+        if let Some(key) = &self.state_key {
+            state::set(ui_scale_key(key), &self.ui_scale, cx);
         }
+        window.refresh();
+        cx.notify();
+    }
+
+    /// Returns the UI scale of the window, 1.0 if the window root is not a [`Root`].
+    pub(crate) fn window_ui_scale(window: &Window, cx: &App) -> f32 {
+        window
+            .root::<Root>()
+            .flatten()
+            .map(|root| root.read(cx).ui_scale)
+            .unwrap_or(1.)
+    }
+
+    fn on_action_zoom_in(&mut self, _: &ZoomIn, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_ui_scale(zoom_ui_scale(self.ui_scale, 1), window, cx);
+    }
+
+    fn on_action_zoom_out(&mut self, _: &ZoomOut, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_ui_scale(zoom_ui_scale(self.ui_scale, -1), window, cx);
+    }
+
+    fn on_action_reset_zoom(&mut self, _: &ResetZoom, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_ui_scale(1., window, cx);
+    }
+
+    /// Dismiss the topmost overlay, if the focused element does not handle the `escape`.
+    fn on_action_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if !overlay::dismiss_topmost(window, cx) {
+            cx.propagate();
+        }
+    }
+
+    fn on_action_toggle_debug_overlay(
+        &mut self,
+        _: &ToggleDebugOverlay,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        debug_overlay::set_visible(!debug_overlay::is_visible(cx), cx);
+    }
+
+    fn focus_back(&mut self, window: &mut Window, _: &mut App) {
+        self.focus_restore.restore(window);
     }
 
     // Render Notification layer.
@@ -370,18 +569,46 @@ impl Render for Root {
         window: &mut gpui::Window,
         cx: &mut gpui::Context<Self>,
     ) -> impl IntoElement {
+        accessibility::clear_rendered(window, cx);
+        #[cfg(any(test, feature = "test-support"))]
+        crate::test_support::clear_texts(window, cx);
+
         let base_font_size = cx.theme().font_size;
-        window.set_rem_size(base_font_size);
+        window.set_rem_size(base_font_size * self.ui_scale);
 
         window_border().child(
             div()
                 .id("root")
+                .key_context(CONTEXT)
+                .on_action(cx.listener(Self::on_action_zoom_in))
+                .on_action(cx.listener(Self::on_action_zoom_out))
+                .on_action(cx.listener(Self::on_action_reset_zoom))
+                .on_action(cx.listener(Self::on_action_toggle_debug_overlay))
+                .on_action(cx.listener(Self::on_action_cancel))
                 .relative()
                 .size_full()
                 .font_family(".SystemUIFont")
                 .bg(cx.theme().background)
                 .text_color(cx.theme().foreground)
-                .child(self.view.clone()),
+                .child(self.view.clone())
+                .when(debug_overlay::is_visible(cx), |this| {
+                    this.child(DebugOverlay)
+                }),
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::zoom_ui_scale;
+
+    #[test]
+    fn test_zoom_ui_scale() {
+        assert_eq!(zoom_ui_scale(1., 1), 1.1);
+        assert_eq!(zoom_ui_scale(1.1, 1), 1.2);
+        assert_eq!(zoom_ui_scale(1., -1), 0.9);
+        assert_eq!(zoom_ui_scale(1.25, 0), 1.3);
+        assert_eq!(zoom_ui_scale(2., 1), 2.);
+        assert_eq!(zoom_ui_scale(0.5, -1), 0.5);
+    }
+}
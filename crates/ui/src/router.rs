@@ -0,0 +1,534 @@
+//! The app level URL router, the links of the components (e.g.: [`Link`](crate::link::Link),
+//! the links in the [`TextView`](crate::text::TextView) and the link items of the
+//! [`PopupMenu`](crate::popup_menu::PopupMenu)) are opened by [`open_url`].
+//!
+//! The URL matches a route is handled by the app, the others are opened by the system.
+//!
+//! ```ignore
+//! router::route("app://settings/:section", cx, |route, window, cx| {
+//!     let section = route.params.get("section");
+//!     // ...
+//! });
+//!
+//! router::open_url("app://settings/appearance?tab=theme", window, cx);
+//! ```
+//!
+//! The in-app navigation of the multipage app is done by the [`Router`] with the named
+//! routes, and the view of the active route is rendered by the [`RouterOutlet`].
+//!
+//! ```ignore
+//! let router = cx.new(|_| {
+//!     Router::new()
+//!         .route("home", "/", |_, window, cx| cx.new(|cx| HomeView::new(window, cx)).into())
+//!         .route("user", "/users/:id", |params, window, cx| {
+//!             let id: u64 = params.get("id").unwrap_or_default();
+//!             cx.new(|cx| UserView::new(id, window, cx)).into()
+//!         })
+//! });
+//!
+//! router.update(cx, |router, cx| router.push("user", [("id", "42")], window, cx));
+//! // Or by the path.
+//! router.update(cx, |router, cx| router.navigate("/users/42", window, cx));
+//!
+//! div().child(RouterOutlet::new(&router))
+//! ```
+use std::{collections::HashMap, rc::Rc, str::FromStr};
+
+use gpui::{
+    actions, div, AnyView, App, Context, Entity, EventEmitter, Global, InteractiveElement as _,
+    IntoElement, KeyBinding, ParentElement as _, RenderOnce, SharedString, Styled as _, Window,
+};
+
+actions!(router, [Back, Forward]);
+
+const CONTEXT: &str = "Router";
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-[", Back, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-]", Forward, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("alt-left", Back, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("alt-right", Forward, Some(CONTEXT)),
+    ]);
+}
+
+/// The max number of the URLs kept in the history.
+const MAX_HISTORY: usize = 100;
+
+/// The matched route of a URL.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteMatch {
+    pub url: SharedString,
+    /// The params captured by the `:name` and `*name` segments of the pattern.
+    pub params: HashMap<SharedString, SharedString>,
+    /// The query params of the URL.
+    pub query: HashMap<SharedString, SharedString>,
+}
+
+type RouteHandler = Rc<dyn Fn(&RouteMatch, &mut Window, &mut App)>;
+
+#[derive(Default)]
+struct UrlRoutes {
+    routes: Vec<(SharedString, RouteHandler)>,
+    history: Vec<SharedString>,
+}
+
+impl Global for UrlRoutes {}
+
+/// Register a route handler of the URL `pattern`.
+///
+/// The pattern is matched by the segments split by `/`:
+///
+/// - `:name` matches one segment, e.g.: `app://users/:id`.
+/// - `*name` matches the rest segments, it must be the last, e.g.: `app://docs/*path`.
+///
+/// The query of the URL is ignored on matching, the routes are matched in the registered order.
+pub fn route(
+    pattern: impl Into<SharedString>,
+    cx: &mut App,
+    handler: impl Fn(&RouteMatch, &mut Window, &mut App) + 'static,
+) {
+    cx.default_global::<UrlRoutes>()
+        .routes
+        .push((pattern.into(), Rc::new(handler)));
+}
+
+/// Open the URL by the matched route and push it to the history, or open it by the system
+/// if no route matches.
+pub fn open_url(url: &str, window: &mut Window, cx: &mut App) {
+    if dispatch(url, window, cx) {
+        let history = &mut cx.default_global::<UrlRoutes>().history;
+        history.push(url.to_string().into());
+        if history.len() > MAX_HISTORY {
+            history.remove(0);
+        }
+    } else {
+        cx.open_url(url);
+    }
+}
+
+/// Go back to the previous URL in the history, returns false if there is no previous URL.
+pub fn back(window: &mut Window, cx: &mut App) -> bool {
+    let Some(router) = cx.try_global::<UrlRoutes>() else {
+        return false;
+    };
+    if router.history.len() < 2 {
+        return false;
+    }
+
+    let history = &mut cx.global_mut::<UrlRoutes>().history;
+    history.pop();
+    let Some(url) = history.last().cloned() else {
+        return false;
+    };
+    dispatch(&url, window, cx)
+}
+
+/// Returns the history of the routed URLs, the current one is the last.
+pub fn history(cx: &App) -> &[SharedString] {
+    match cx.try_global::<UrlRoutes>() {
+        Some(router) => &router.history,
+        None => &[],
+    }
+}
+
+/// Call the handler of the first matched route, returns false if no route matches.
+fn dispatch(url: &str, window: &mut Window, cx: &mut App) -> bool {
+    let Some(router) = cx.try_global::<UrlRoutes>() else {
+        return false;
+    };
+    let Some((route, handler)) = router.routes.iter().find_map(|(pattern, handler)| {
+        match_route(pattern, url).map(|route| (route, handler.clone()))
+    }) else {
+        return false;
+    };
+
+    handler(&route, window, cx);
+    true
+}
+
+/// Match the `url` by the route `pattern`.
+fn match_route(pattern: &str, url: &str) -> Option<RouteMatch> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let path = path.split_once('#').map_or(path, |(path, _)| path);
+    let query = query.split_once('#').map_or(query, |(query, _)| query);
+
+    let mut params = HashMap::new();
+    let mut segments = path.trim_end_matches('/').split('/');
+    let mut pattern_segments = pattern.trim_end_matches('/').split('/');
+    loop {
+        match (pattern_segments.next(), segments.next()) {
+            (None, None) => break,
+            (Some(pattern), Some(segment)) if pattern.starts_with('*') => {
+                let rest = std::iter::once(segment)
+                    .chain(segments.by_ref())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                params.insert(pattern[1..].to_string().into(), rest.into());
+                if pattern_segments.next().is_some() {
+                    return None;
+                }
+                break;
+            }
+            (Some(pattern), Some(segment)) if pattern.starts_with(':') => {
+                if segment.is_empty() {
+                    return None;
+                }
+                params.insert(pattern[1..].to_string().into(), segment.to_string().into());
+            }
+            (Some(pattern), Some(segment)) if pattern == segment => {}
+            _ => return None,
+        }
+    }
+
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_string().into(), value.to_string().into())
+        })
+        .collect();
+
+    Some(RouteMatch {
+        url: url.to_string().into(),
+        params,
+        query,
+    })
+}
+
+/// The params of the route, the values are parsed to the typed values by [`Self::get`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteParams(HashMap<SharedString, SharedString>);
+
+impl RouteParams {
+    /// Returns the param parsed as `T`, `None` if the param is missing or failed to parse.
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.get_str(key).and_then(|value| value.parse().ok())
+    }
+
+    /// Returns the raw string of the param.
+    pub fn get_str(&self, key: &str) -> Option<&SharedString> {
+        self.0.get(&SharedString::from(key.to_string()))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for RouteParams
+where
+    K: Into<SharedString>,
+    V: Into<SharedString>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+/// A location of the [`Router`], the route name with the params.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub name: SharedString,
+    pub params: RouteParams,
+}
+
+/// The event emitted by the [`Router`] after the active location is changed.
+#[derive(Debug, Clone)]
+pub enum RouterEvent {
+    Navigate(Location),
+}
+
+type ViewBuilder = Rc<dyn Fn(&RouteParams, &mut Window, &mut App) -> AnyView>;
+
+struct NamedRoute {
+    name: SharedString,
+    pattern: SharedString,
+    build: ViewBuilder,
+}
+
+struct HistoryEntry {
+    location: Location,
+    view: AnyView,
+}
+
+/// The in-app router with the named routes and the back/forward history.
+///
+/// The views of the locations in the history are kept, so the state of the view is
+/// restored after going back or forward.
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<NamedRoute>,
+    history: Vec<HistoryEntry>,
+    /// The index of the active location in the history.
+    ix: usize,
+}
+
+impl EventEmitter<RouterEvent> for Router {}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a named route with the path `pattern`, see [`route`] for the pattern syntax.
+    ///
+    /// The `build` is called to build the view when the route is navigated to.
+    pub fn route(
+        mut self,
+        name: impl Into<SharedString>,
+        pattern: impl Into<SharedString>,
+        build: impl Fn(&RouteParams, &mut Window, &mut App) -> AnyView + 'static,
+    ) -> Self {
+        self.routes.push(NamedRoute {
+            name: name.into(),
+            pattern: pattern.into(),
+            build: Rc::new(build),
+        });
+        self
+    }
+
+    /// Returns the active location.
+    pub fn location(&self) -> Option<&Location> {
+        self.history.get(self.ix).map(|entry| &entry.location)
+    }
+
+    /// Returns the view of the active location.
+    pub fn view(&self) -> Option<&AnyView> {
+        self.history.get(self.ix).map(|entry| &entry.view)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.ix > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.ix + 1 < self.history.len()
+    }
+
+    fn build_entry(
+        &self,
+        name: SharedString,
+        params: RouteParams,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<HistoryEntry> {
+        let Some(route) = self.routes.iter().find(|route| route.name == name) else {
+            tracing::error!("no route named: {}", name);
+            return None;
+        };
+
+        let view = (route.build)(&params, window, cx);
+        Some(HistoryEntry {
+            location: Location { name, params },
+            view,
+        })
+    }
+
+    fn navigated(&mut self, cx: &mut Context<Self>) {
+        if let Some(location) = self.location().cloned() {
+            cx.emit(RouterEvent::Navigate(location));
+        }
+        cx.notify();
+    }
+
+    /// Push the location of the named route, the forward history is cleared.
+    ///
+    /// Returns false if there is no route named `name`.
+    pub fn push<K, V>(
+        &mut self,
+        name: impl Into<SharedString>,
+        params: impl IntoIterator<Item = (K, V)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool
+    where
+        K: Into<SharedString>,
+        V: Into<SharedString>,
+    {
+        let params = params.into_iter().collect();
+        let Some(entry) = self.build_entry(name.into(), params, window, cx) else {
+            return false;
+        };
+
+        if !self.history.is_empty() {
+            self.history.truncate(self.ix + 1);
+            self.ix += 1;
+        }
+        self.history.push(entry);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+            self.ix -= 1;
+        }
+        self.navigated(cx);
+        true
+    }
+
+    /// Replace the active location by the location of the named route.
+    ///
+    /// Returns false if there is no route named `name`.
+    pub fn replace<K, V>(
+        &mut self,
+        name: impl Into<SharedString>,
+        params: impl IntoIterator<Item = (K, V)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool
+    where
+        K: Into<SharedString>,
+        V: Into<SharedString>,
+    {
+        let params = params.into_iter().collect();
+        let Some(entry) = self.build_entry(name.into(), params, window, cx) else {
+            return false;
+        };
+
+        match self.history.get_mut(self.ix) {
+            Some(active) => *active = entry,
+            None => self.history.push(entry),
+        }
+        self.navigated(cx);
+        true
+    }
+
+    /// Push the location of the first route matches the `path`.
+    ///
+    /// Returns false if no route matches.
+    pub fn navigate(&mut self, path: &str, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Some((name, route)) = self.routes.iter().find_map(|route| {
+            match_route(&route.pattern, path).map(|matched| (route.name.clone(), matched))
+        }) else {
+            return false;
+        };
+
+        self.push(name, route.params, window, cx)
+    }
+
+    /// Remove the active location and go back to the previous one, the forward history
+    /// is cleared.
+    ///
+    /// Returns false if there is no previous location.
+    pub fn pop(&mut self, cx: &mut Context<Self>) -> bool {
+        if !self.can_go_back() {
+            return false;
+        }
+
+        self.history.truncate(self.ix);
+        self.ix -= 1;
+        self.navigated(cx);
+        true
+    }
+
+    /// Go back to the previous location, returns false if there is no previous location.
+    pub fn back(&mut self, cx: &mut Context<Self>) -> bool {
+        if !self.can_go_back() {
+            return false;
+        }
+
+        self.ix -= 1;
+        self.navigated(cx);
+        true
+    }
+
+    /// Go forward to the next location, returns false if there is no next location.
+    pub fn forward(&mut self, cx: &mut Context<Self>) -> bool {
+        if !self.can_go_forward() {
+            return false;
+        }
+
+        self.ix += 1;
+        self.navigated(cx);
+        true
+    }
+}
+
+/// An element to render the view of the active location of the [`Router`].
+///
+/// The [`Back`] and [`Forward`] actions are handled when the focus is in the outlet.
+#[derive(IntoElement)]
+pub struct RouterOutlet {
+    router: Entity<Router>,
+}
+
+impl RouterOutlet {
+    pub fn new(router: &Entity<Router>) -> Self {
+        Self {
+            router: router.clone(),
+        }
+    }
+}
+
+impl RenderOnce for RouterOutlet {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let view = self.router.read(cx).view().cloned();
+
+        div()
+            .key_context(CONTEXT)
+            .size_full()
+            .on_action({
+                let router = self.router.clone();
+                move |_: &Back, _, cx| {
+                    router.update(cx, |router, cx| router.back(cx));
+                }
+            })
+            .on_action({
+                let router = self.router.clone();
+                move |_: &Forward, _, cx| {
+                    router.update(cx, |router, cx| router.forward(cx));
+                }
+            })
+            .children(view)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::SharedString;
+
+    use super::{match_route, RouteParams};
+
+    #[test]
+    fn test_route_params() {
+        let params = RouteParams::from_iter([("id", "42"), ("name", "jason")]);
+        assert_eq!(params.get::<u64>("id"), Some(42));
+        assert_eq!(params.get::<String>("name"), Some("jason".to_string()));
+        assert_eq!(params.get::<u64>("name"), None);
+        assert_eq!(params.get::<u64>("missing"), None);
+    }
+
+    #[test]
+    fn test_match_route() {
+        let param = |route: &super::RouteMatch, key: &str| {
+            route
+                .params
+                .get(&SharedString::from(key.to_string()))
+                .cloned()
+        };
+
+        let route = match_route("app://settings", "app://settings/").unwrap();
+        assert!(route.params.is_empty());
+        assert!(match_route("app://settings", "app://settings/general").is_none());
+        assert!(match_route("app://settings", "https://settings").is_none());
+
+        let route = match_route("app://users/:id", "app://users/42?tab=info&edit").unwrap();
+        assert_eq!(param(&route, "id"), Some("42".into()));
+        assert_eq!(
+            route.query.get(&SharedString::from("tab")).cloned(),
+            Some("info".into())
+        );
+        assert_eq!(
+            route.query.get(&SharedString::from("edit")).cloned(),
+            Some("".into())
+        );
+        assert!(match_route("app://users/:id", "app://users/").is_none());
+
+        let route = match_route("app://docs/*path", "app://docs/guide/intro#install").unwrap();
+        assert_eq!(param(&route, "path"), Some("guide/intro".into()));
+        assert!(match_route("app://docs/*path", "app://docs").is_none());
+    }
+}
@@ -1,7 +1,11 @@
+mod scroll_target;
 mod scrollable;
 mod scrollable_mask;
 mod scrollbar;
+mod sticky;
 
+pub use scroll_target::*;
 pub use scrollable::*;
 pub use scrollable_mask::*;
 pub use scrollbar::*;
+pub use sticky::*;
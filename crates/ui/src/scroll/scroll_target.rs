@@ -0,0 +1,558 @@
+use std::{
+    cell::RefCell,
+    ops::Deref,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    canvas, point, px, Along, App, Axis, Bounds, Div, ElementId, ParentElement, Pixels, Point,
+    ScrollHandle, Stateful, StatefulInteractiveElement, Styled, Window,
+};
+
+use crate::animation::cubic_bezier;
+
+const SCROLL_ANIMATION_DURATION: Duration = Duration::from_millis(200);
+
+/// The alignment of the element when scroll it into view.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollAlignment {
+    /// Align the element to the start of the viewport.
+    Start,
+    /// Align the element to the center of the viewport.
+    Center,
+    /// Align the element to the end of the viewport.
+    End,
+    /// Scroll the minimum distance to make the element visible, do nothing if it is already visible.
+    #[default]
+    Nearest,
+}
+
+/// Returns the new scroll offset (along an axis) to make the item visible with the alignment.
+///
+/// - `item` is the start and size of the item in window coordinates.
+/// - `viewport` is the start and size of the scroll container in window coordinates.
+/// - `offset` is the current scroll offset (<= 0), `max_offset` is the max scrollable distance (>= 0).
+pub(crate) fn scroll_offset_for(
+    item: (Pixels, Pixels),
+    viewport: (Pixels, Pixels),
+    offset: Pixels,
+    max_offset: Pixels,
+    alignment: ScrollAlignment,
+) -> Pixels {
+    let (item_start, item_size) = item;
+    let (view_start, view_size) = viewport;
+    let item_end = item_start + item_size;
+    let view_end = view_start + view_size;
+
+    let new_offset = match alignment {
+        ScrollAlignment::Start => offset - (item_start - view_start),
+        ScrollAlignment::End => offset - (item_end - view_end),
+        ScrollAlignment::Center => {
+            offset - ((item_start + item_size / 2.) - (view_start + view_size / 2.))
+        }
+        ScrollAlignment::Nearest => {
+            if item_start < view_start || item_size > view_size {
+                offset - (item_start - view_start)
+            } else if item_end > view_end {
+                offset - (item_end - view_end)
+            } else {
+                offset
+            }
+        }
+    };
+
+    new_offset.min(px(0.)).max(-max_offset)
+}
+
+/// Returns the index of the section to pin the header, and the offset of the header.
+///
+/// - `sections` are the start and size of the sections relative to the viewport start.
+/// - `header_size` is the size of the header.
+pub(crate) fn sticky_header_for(
+    sections: &[(Pixels, Pixels)],
+    header_size: Pixels,
+) -> Option<(usize, Pixels)> {
+    let ix = sections
+        .iter()
+        .rposition(|(start, size)| *start <= px(0.) && *start + *size > px(0.))?;
+    let (start, size) = sections[ix];
+    Some((ix, (start + size - header_size).min(px(0.))))
+}
+
+/// Returns the scroll offset (along an axis) to keep the anchor element at the same position,
+/// `None` if the anchor is not moved.
+///
+/// - `anchor` is the start of the anchor element in the content when it was chosen.
+/// - `start` is the start of the anchor element in the content of the current layout.
+/// - `offset` is the current scroll offset (<= 0), `max_offset` is the max scrollable distance (>= 0).
+pub(crate) fn anchored_offset(
+    anchor: Pixels,
+    start: Pixels,
+    offset: Pixels,
+    max_offset: Pixels,
+) -> Option<Pixels> {
+    let diff = start - anchor;
+    if diff == px(0.) {
+        return None;
+    }
+
+    Some((offset - diff).min(px(0.)).max(-max_offset))
+}
+
+/// Returns the index of the first visible item as the anchor.
+///
+/// - `items` are the start and size of the items in the content.
+/// - `offset` is the scroll offset (<= 0).
+pub(crate) fn first_visible(items: &[(Pixels, Pixels)], offset: Pixels) -> Option<usize> {
+    items
+        .iter()
+        .position(|(start, size)| *start + *size > -offset)
+}
+
+struct ScrollAnimation {
+    from: Point<Pixels>,
+    to: Point<Pixels>,
+    started_at: Instant,
+}
+
+struct ScrollTargetState {
+    axis: Axis,
+    anchoring: bool,
+    /// The bounds of the targets in the last frame, in window coordinates.
+    targets: Vec<(ElementId, Bounds<Pixels>)>,
+    /// The anchor element and the start of it in the content.
+    anchor: Option<(ElementId, Pixels)>,
+    animation: Option<ScrollAnimation>,
+    /// The bounds of the sticky sections in the last frame, in window coordinates.
+    sections: Vec<(ElementId, Bounds<Pixels>)>,
+    /// The scroll offset of the last frame, that is used to layout the targets.
+    frame_offset: Point<Pixels>,
+    /// The size of the sticky header in the last frame.
+    sticky_header_size: Pixels,
+}
+
+/// A scroll handle to scroll the children elements into view by their id,
+/// and keep the scroll anchoring when the content before the viewport changes.
+///
+/// ```ignore
+/// div()
+///     .id("messages")
+///     .overflow_y_scroll()
+///     .track_scroll_targets(&self.scroll_handle)
+///     .children(messages.iter().map(|msg| {
+///         div().child(msg.text.clone()).scroll_target(msg.id, &self.scroll_handle)
+///     }))
+/// ```
+#[derive(Clone)]
+pub struct ScrollTargetHandle {
+    base_handle: ScrollHandle,
+    state: Rc<RefCell<ScrollTargetState>>,
+}
+
+impl Default for ScrollTargetHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for ScrollTargetHandle {
+    type Target = ScrollHandle;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base_handle
+    }
+}
+
+impl ScrollTargetHandle {
+    /// Create a new vertical scroll target handle.
+    pub fn new() -> Self {
+        Self {
+            base_handle: ScrollHandle::new(),
+            state: Rc::new(RefCell::new(ScrollTargetState {
+                axis: Axis::Vertical,
+                anchoring: false,
+                targets: vec![],
+                anchor: None,
+                animation: None,
+                sections: vec![],
+                frame_offset: Point::default(),
+                sticky_header_size: px(0.),
+            })),
+        }
+    }
+
+    /// Set the scroll axis, default is [`Axis::Vertical`].
+    pub fn set_axis(&self, axis: Axis) {
+        self.state.borrow_mut().axis = axis;
+    }
+
+    /// Set to enable the scroll anchoring, default is `false`.
+    ///
+    /// When enabled, if the elements are inserted or resized before the viewport (e.g. prepend
+    /// the chat history), the scroll offset will be adjusted to keep the first visible element
+    /// at the same position.
+    pub fn set_anchoring(&self, anchoring: bool) {
+        let mut state = self.state.borrow_mut();
+        state.anchoring = anchoring;
+        state.anchor = None;
+    }
+
+    /// Returns the bounds of the target element (in window coordinates) in the last frame.
+    pub fn target_bounds(&self, id: &ElementId) -> Option<Bounds<Pixels>> {
+        self.state
+            .borrow()
+            .targets
+            .iter()
+            .find(|(target_id, _)| target_id == id)
+            .map(|(_, bounds)| *bounds)
+    }
+
+    /// Scroll the element with the `id` into view.
+    ///
+    /// The element must be marked by [`ScrollTargetExt::scroll_target`] and rendered in last frame.
+    ///
+    /// Returns `false` if the element was not found.
+    pub fn scroll_to_element(
+        &self,
+        id: impl Into<ElementId>,
+        alignment: ScrollAlignment,
+        animated: bool,
+        window: &mut Window,
+    ) -> bool {
+        let id = id.into();
+        let Some(bounds) = self.target_bounds(&id) else {
+            return false;
+        };
+
+        let axis = self.state.borrow().axis;
+        let container = self.base_handle.bounds();
+        let offset = self.base_handle.offset();
+        let mut to = offset;
+        let new_offset = scroll_offset_for(
+            (bounds.origin.along(axis), bounds.size.along(axis)),
+            (container.origin.along(axis), container.size.along(axis)),
+            offset.along(axis),
+            self.base_handle.max_offset().along(axis),
+            alignment,
+        );
+        match axis {
+            Axis::Vertical => to.y = new_offset,
+            Axis::Horizontal => to.x = new_offset,
+        }
+
+        if to == offset {
+            return true;
+        }
+
+        if animated {
+            self.state.borrow_mut().animation = Some(ScrollAnimation {
+                from: offset,
+                to,
+                started_at: Instant::now(),
+            });
+        } else {
+            self.base_handle.set_offset(to);
+        }
+        window.refresh();
+
+        true
+    }
+
+    /// Record the bounds of the target, and keep the anchor element at the same position if
+    /// it is moved by the changed content before it.
+    ///
+    /// This is called in the prepaint of the target, so the anchor is corrected in the same
+    /// frame the content changes, the new offset is applied in the next frame.
+    fn record_target(&self, id: ElementId, bounds: Bounds<Pixels>, window: &mut Window) {
+        let mut state = self.state.borrow_mut();
+        let axis = state.axis;
+        if state.anchoring && state.animation.is_none() {
+            if let Some((anchor_id, anchor)) = state.anchor.clone() {
+                if anchor_id == id {
+                    let view_start = self.base_handle.bounds().origin.along(axis);
+                    let frame_offset = state.frame_offset.along(axis);
+                    let start = bounds.origin.along(axis) - view_start - frame_offset;
+                    if let Some(new_offset) = anchored_offset(
+                        anchor,
+                        start,
+                        frame_offset,
+                        self.base_handle.max_offset().along(axis),
+                    ) {
+                        let mut offset = self.base_handle.offset();
+                        match axis {
+                            Axis::Vertical => offset.y = new_offset,
+                            Axis::Horizontal => offset.x = new_offset,
+                        }
+                        self.base_handle.set_offset(offset);
+                        state.anchor = Some((anchor_id, start));
+                        window.refresh();
+                    }
+                }
+            }
+        }
+
+        state.targets.push((id, bounds));
+    }
+
+    pub(super) fn record_section(&self, id: ElementId, bounds: Bounds<Pixels>) {
+        self.state.borrow_mut().sections.push((id, bounds));
+    }
+
+    pub(super) fn record_sticky_header_size(&self, size: Pixels) {
+        self.state.borrow_mut().sticky_header_size = size;
+    }
+
+    /// Returns the id of the section to pin the header, and the offset of the header to the
+    /// viewport start, the offset is negative when the header is pushed out by the next section.
+    pub(super) fn sticky_header(&self) -> Option<(ElementId, Pixels)> {
+        let state = self.state.borrow();
+        let axis = state.axis;
+        let view_start = self.base_handle.bounds().origin.along(axis);
+        // The sections are recorded in the last frame, adjust them by the scrolled distance.
+        let scrolled = self.base_handle.offset().along(axis) - state.frame_offset.along(axis);
+
+        let sections = state
+            .sections
+            .iter()
+            .map(|(_, bounds)| {
+                (
+                    bounds.origin.along(axis) + scrolled - view_start,
+                    bounds.size.along(axis),
+                )
+            })
+            .collect::<Vec<_>>();
+        let (ix, offset) = sticky_header_for(&sections, state.sticky_header_size)?;
+        Some((state.sections[ix].0.clone(), offset))
+    }
+
+    /// Step the animation and choose the anchor with the targets recorded in the last frame,
+    /// then clear the targets to record them again.
+    fn prepaint(&self, window: &mut Window) {
+        // The children of this frame are laid out with the offset before it is updated.
+        let layout_offset = self.base_handle.offset();
+        self.update_offset(window);
+        let mut state = self.state.borrow_mut();
+        state.targets.clear();
+        state.sections.clear();
+        state.frame_offset = layout_offset;
+    }
+
+    fn update_offset(&self, window: &mut Window) {
+        let mut state = self.state.borrow_mut();
+        let axis = state.axis;
+
+        if let Some(animation) = state.animation.as_ref() {
+            let elapsed = animation.started_at.elapsed();
+            let progress =
+                (elapsed.as_secs_f32() / SCROLL_ANIMATION_DURATION.as_secs_f32()).min(1.);
+            let delta = cubic_bezier(0.25, 0.1, 0.25, 1.0)(progress);
+            let (from, to) = (animation.from, animation.to);
+            let offset = point(
+                from.x + (to.x - from.x) * delta,
+                from.y + (to.y - from.y) * delta,
+            );
+            self.base_handle.set_offset(offset);
+
+            if progress < 1. {
+                window.request_animation_frame();
+            } else {
+                state.animation = None;
+            }
+
+            // Do not anchor during the animation, the anchor will be updated after it.
+            state.anchor = None;
+            return;
+        }
+
+        if !state.anchoring {
+            return;
+        }
+
+        // Use the first visible element at the current offset as the anchor, the targets are
+        // converted to the content positions by the offset they were laid out with.
+        let view_start = self.base_handle.bounds().origin.along(axis);
+        let frame_offset = state.frame_offset.along(axis);
+        let items = state
+            .targets
+            .iter()
+            .map(|(_, bounds)| {
+                (
+                    bounds.origin.along(axis) - view_start - frame_offset,
+                    bounds.size.along(axis),
+                )
+            })
+            .collect::<Vec<_>>();
+        let offset = self.base_handle.offset().along(axis);
+        state.anchor =
+            first_visible(&items, offset).map(|ix| (state.targets[ix].0.clone(), items[ix].0));
+    }
+}
+
+/// Extension trait to track the scroll targets on a scroll container.
+pub trait ScrollTargetContainerExt: StatefulInteractiveElement + ParentElement + Sized {
+    /// Track the scroll of this container with the [`ScrollTargetHandle`].
+    ///
+    /// This must be called before adding the children that marked by [`ScrollTargetExt::scroll_target`].
+    fn track_scroll_targets(self, handle: &ScrollTargetHandle) -> Self {
+        let handle = handle.clone();
+        self.track_scroll(&handle.base_handle).child(
+            canvas(
+                move |_, window, _: &mut App| handle.prepaint(window),
+                |_, _, _, _| {},
+            )
+            .absolute()
+            .size_0(),
+        )
+    }
+}
+
+impl ScrollTargetContainerExt for Stateful<Div> {}
+
+/// Extension trait to mark an element as a scroll target.
+pub trait ScrollTargetExt: ParentElement + Sized {
+    /// Mark this element as a scroll target with the `id`, for [`ScrollTargetHandle::scroll_to_element`].
+    ///
+    /// The element should be `relative` positioned.
+    fn scroll_target(self, id: impl Into<ElementId>, handle: &ScrollTargetHandle) -> Self {
+        let id = id.into();
+        let handle = handle.clone();
+        self.child(
+            canvas(
+                move |bounds, window, _: &mut App| handle.record_target(id.clone(), bounds, window),
+                |_, _, _, _| {},
+            )
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full(),
+        )
+    }
+}
+
+impl ScrollTargetExt for Div {}
+impl ScrollTargetExt for Stateful<Div> {}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::{
+        anchored_offset, first_visible, scroll_offset_for, sticky_header_for, ScrollAlignment,
+    };
+
+    #[test]
+    fn test_anchoring() {
+        // The items of 100px, scrolled to the third item.
+        let items = [
+            (px(0.), px(100.)),
+            (px(100.), px(100.)),
+            (px(200.), px(100.)),
+        ];
+        let offset = px(-250.);
+        let ix = first_visible(&items, offset).unwrap();
+        assert_eq!(ix, 2);
+        let anchor = items[ix].0;
+        assert_eq!(first_visible(&items, px(-300.)), None);
+
+        // Not moved.
+        assert_eq!(anchored_offset(anchor, px(200.), offset, px(1000.)), None);
+
+        // Prepend 2 items of 100px, the anchor is moved to 400px in the content.
+        let offset = anchored_offset(anchor, px(400.), offset, px(1000.)).unwrap();
+        assert_eq!(offset, px(-450.));
+        // The next frame is laid out with the new offset, the anchor is not moved again.
+        assert_eq!(anchored_offset(px(400.), px(400.), offset, px(1000.)), None);
+
+        // Remove the items before, clamp to the top.
+        assert_eq!(
+            anchored_offset(anchor, px(0.), px(-150.), px(1000.)),
+            Some(px(0.))
+        );
+        // Clamp to the max offset.
+        assert_eq!(
+            anchored_offset(anchor, px(1200.), offset, px(1000.)),
+            Some(px(-1000.))
+        );
+    }
+
+    #[test]
+    fn test_sticky_header_for() {
+        let sections = [
+            (px(-300.), px(200.)),
+            (px(-100.), px(200.)),
+            (px(100.), px(200.)),
+        ];
+        // The second section is at the viewport start.
+        assert_eq!(sticky_header_for(&sections, px(30.)), Some((1, px(0.))));
+        // The header is pushed out by the next section.
+        assert_eq!(sticky_header_for(&sections, px(120.)), Some((1, px(-20.))));
+        // The sections are below the viewport start.
+        assert_eq!(sticky_header_for(&sections[2..], px(30.)), None);
+        assert_eq!(sticky_header_for(&[], px(30.)), None);
+    }
+
+    #[test]
+    fn test_scroll_offset_for() {
+        let viewport = (px(100.), px(200.));
+        let max_offset = px(1000.);
+
+        // Item is visible
+        let item = (px(150.), px(50.));
+        assert_eq!(
+            scroll_offset_for(
+                item,
+                viewport,
+                px(-10.),
+                max_offset,
+                ScrollAlignment::Nearest
+            ),
+            px(-10.)
+        );
+        assert_eq!(
+            scroll_offset_for(item, viewport, px(-10.), max_offset, ScrollAlignment::Start),
+            px(-60.)
+        );
+        assert_eq!(
+            scroll_offset_for(item, viewport, px(-10.), max_offset, ScrollAlignment::End),
+            px(0.)
+        );
+        assert_eq!(
+            scroll_offset_for(
+                item,
+                viewport,
+                px(-100.),
+                max_offset,
+                ScrollAlignment::Center
+            ),
+            px(-75.)
+        );
+
+        // Item is below the viewport
+        let item = (px(400.), px(50.));
+        assert_eq!(
+            scroll_offset_for(item, viewport, px(0.), max_offset, ScrollAlignment::Nearest),
+            px(-150.)
+        );
+
+        // Item is above the viewport
+        let item = (px(20.), px(50.));
+        assert_eq!(
+            scroll_offset_for(
+                item,
+                viewport,
+                px(-300.),
+                max_offset,
+                ScrollAlignment::Nearest
+            ),
+            px(-220.)
+        );
+
+        // Clamp to the max offset
+        let item = (px(2000.), px(50.));
+        assert_eq!(
+            scroll_offset_for(item, viewport, px(0.), max_offset, ScrollAlignment::Start),
+            px(-1000.)
+        );
+    }
+}
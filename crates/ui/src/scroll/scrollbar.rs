@@ -268,6 +268,49 @@ impl ScrollbarAxis {
     }
 }
 
+/// The min length of the [`ScrollbarMarker`] on the track.
+const MIN_MARKER_LENGTH: Pixels = px(2.);
+
+/// A marker painted along the vertical scrollbar track, e.g.: a search match or an error line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollbarMarker {
+    /// The start position of the marker in the content.
+    pub position: Pixels,
+    /// The length of the marker in the content, the marker is at least 2px on the track.
+    pub length: Pixels,
+    pub color: Hsla,
+}
+
+impl ScrollbarMarker {
+    pub fn new(position: Pixels, color: Hsla) -> Self {
+        Self {
+            position,
+            length: px(0.),
+            color,
+        }
+    }
+
+    /// Set the length of the marker in the content, e.g.: the height of the matched lines.
+    pub fn length(mut self, length: Pixels) -> Self {
+        self.length = length;
+        self
+    }
+}
+
+/// Returns the start and length of the marker on the track, by the content size.
+fn marker_on_track(
+    marker: &ScrollbarMarker,
+    scroll_size: Pixels,
+    track_size: Pixels,
+) -> (Pixels, Pixels) {
+    let ratio = track_size / scroll_size;
+    let length = (marker.length * ratio).max(MIN_MARKER_LENGTH);
+    let start = (marker.position * ratio)
+        .min(track_size - length)
+        .max(px(0.));
+    (start, length)
+}
+
 /// Scrollbar control for scroll-area or a uniform-list.
 pub struct Scrollbar {
     axis: ScrollbarAxis,
@@ -279,6 +322,7 @@ pub struct Scrollbar {
     /// This is used to limit the update rate of the scrollbar when it is
     /// being dragged for some complex interactions for reducing CPU usage.
     max_fps: usize,
+    markers: Vec<ScrollbarMarker>,
 }
 
 impl Scrollbar {
@@ -293,6 +337,7 @@ impl Scrollbar {
             scroll_handle: Rc::new(Box::new(scroll_handle.clone())),
             max_fps: 120,
             scroll_size: None,
+            markers: vec![],
         }
     }
 
@@ -342,6 +387,12 @@ impl Scrollbar {
         self
     }
 
+    /// Set the markers to paint along the vertical track, e.g.: the search matches.
+    pub fn markers(mut self, markers: impl IntoIterator<Item = ScrollbarMarker>) -> Self {
+        self.markers = markers.into_iter().collect();
+        self
+    }
+
     /// Set maximum frames per second for scrolling by drag. Default is 120 FPS.
     ///
     /// If you have very high CPU usage, consider reducing this value to improve performance.
@@ -712,9 +763,25 @@ impl Element for Scrollbar {
 
                     window.set_cursor_style(CursorStyle::default(), &state.bar_hitbox);
 
+                    let markers = &self.markers;
                     window.paint_layer(hitbox_bounds, |cx| {
                         cx.paint_quad(fill(state.bounds, state.bg));
 
+                        if is_vertical {
+                            for marker in markers.iter() {
+                                let (start, length) =
+                                    marker_on_track(marker, scroll_area_size, container_size);
+                                cx.paint_quad(fill(
+                                    Bounds::from_corner_and_size(
+                                        Corner::TopRight,
+                                        bounds.top_right() + point(-THUMB_INSET, start),
+                                        size(THUMB_ACTIVE_WIDTH, length),
+                                    ),
+                                    marker.color,
+                                ));
+                            }
+                        }
+
                         cx.paint_quad(PaintQuad {
                             bounds,
                             corner_radii: (0.).into(),
@@ -911,3 +978,33 @@ impl Element for Scrollbar {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::{px, red};
+
+    use super::{marker_on_track, ScrollbarMarker};
+
+    #[test]
+    fn test_marker_on_track() {
+        let marker = ScrollbarMarker::new(px(500.), red()).length(px(100.));
+        assert_eq!(
+            marker_on_track(&marker, px(1000.), px(200.)),
+            (px(100.), px(20.))
+        );
+
+        // The min length.
+        let marker = ScrollbarMarker::new(px(500.), red());
+        assert_eq!(
+            marker_on_track(&marker, px(1000.), px(200.)),
+            (px(100.), px(2.))
+        );
+
+        // Keep in the track.
+        let marker = ScrollbarMarker::new(px(1000.), red());
+        assert_eq!(
+            marker_on_track(&marker, px(1000.), px(200.)),
+            (px(198.), px(2.))
+        );
+    }
+}
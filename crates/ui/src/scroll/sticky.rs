@@ -0,0 +1,109 @@
+use std::rc::Rc;
+
+use gpui::{
+    canvas, div, AnyElement, App, Div, ElementId, IntoElement, ParentElement, RenderOnce, Stateful,
+    Styled as _, Window,
+};
+
+use super::ScrollTargetHandle;
+
+/// Extension trait to mark an element as a section with the sticky header.
+pub trait StickySectionExt: ParentElement + Sized {
+    /// Mark this element as a sticky section with the `id`, the header of it is pinned by
+    /// the [`StickyHeader`] while the section is at the viewport start.
+    ///
+    /// The element should be `relative` positioned.
+    fn sticky_section(self, id: impl Into<ElementId>, handle: &ScrollTargetHandle) -> Self {
+        let id = id.into();
+        let handle = handle.clone();
+        self.child(
+            canvas(
+                move |bounds, _, _: &mut App| handle.record_section(id.clone(), bounds),
+                |_, _, _, _| {},
+            )
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full(),
+        )
+    }
+}
+
+impl StickySectionExt for Div {}
+impl StickySectionExt for Stateful<Div> {}
+
+/// The pinned header of the section at the viewport start of the scroll container tracked by
+/// [`ScrollTargetContainerExt::track_scroll_targets`](super::ScrollTargetContainerExt::track_scroll_targets),
+/// the header is pushed out by the next section.
+///
+/// It should be the sibling after the scroll container, in a `relative` and `overflow_hidden`
+/// parent with the same bounds, only the vertical scroll is supported.
+///
+/// ```ignore
+/// div()
+///     .relative()
+///     .size_full()
+///     .overflow_hidden()
+///     .child(
+///         div()
+///             .id("settings")
+///             .size_full()
+///             .overflow_y_scroll()
+///             .track_scroll_targets(&self.scroll_handle)
+///             .children(self.groups.iter().map(|group| {
+///                 v_flex()
+///                     .child(render_group_header(group))
+///                     .children(group.items.iter().map(render_item))
+///                     .sticky_section(group.id.clone(), &self.scroll_handle)
+///             })),
+///     )
+///     .child(StickyHeader::new(&self.scroll_handle, |id, _, _| {
+///         render_group_header(find_group(id)).into_any_element()
+///     }))
+/// ```
+#[derive(IntoElement)]
+pub struct StickyHeader {
+    handle: ScrollTargetHandle,
+    render: Rc<dyn Fn(&ElementId, &mut Window, &mut App) -> AnyElement>,
+}
+
+impl StickyHeader {
+    /// Create a sticky header, the `render` renders the header of the section by the id.
+    pub fn new(
+        handle: &ScrollTargetHandle,
+        render: impl Fn(&ElementId, &mut Window, &mut App) -> AnyElement + 'static,
+    ) -> Self {
+        Self {
+            handle: handle.clone(),
+            render: Rc::new(render),
+        }
+    }
+}
+
+impl RenderOnce for StickyHeader {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let Some((id, offset)) = self.handle.sticky_header() else {
+            return div().into_any_element();
+        };
+
+        let handle = self.handle.clone();
+        div()
+            .absolute()
+            .top(offset)
+            .left_0()
+            .right_0()
+            .overflow_hidden()
+            .child((self.render)(&id, window, cx))
+            .child(
+                canvas(
+                    move |bounds, _, _: &mut App| {
+                        handle.record_sticky_header_size(bounds.size.height)
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .into_any_element()
+    }
+}
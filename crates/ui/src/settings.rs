@@ -0,0 +1,625 @@
+//! A settings screen built by the declarative sections and items.
+//!
+//! ```ignore
+//! let settings = cx.new(|cx| {
+//!     SettingsView::new(window, cx)
+//!         .section(
+//!             SettingSection::new("Editor")
+//!                 .item(SettingItem::switch("editor.line_numbers", "Line Numbers", true))
+//!                 .item(
+//!                     SettingItem::slider("editor.font_size", "Font Size", 10.0..=32.0, 14.)
+//!                         .description("The font size of the editor."),
+//!                 ),
+//!             window,
+//!             cx,
+//!         )
+//!         .section(
+//!             SettingSection::new("Appearance").item(SettingItem::select(
+//!                 "theme.mode",
+//!                 "Theme",
+//!                 ["Light", "Dark"],
+//!                 "Light",
+//!             )),
+//!             window,
+//!             cx,
+//!         )
+//! });
+//! ```
+//!
+//! The values are persisted by the [`state`](crate::state) storage with the key of the item,
+//! and the changes are emitted as [`SettingsEvent`].
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use gpui::{
+    div, prelude::FluentBuilder as _, App, AppContext as _, Context, Entity, EventEmitter,
+    FocusHandle, Focusable, InteractiveElement as _, IntoElement, Keystroke, ParentElement as _,
+    Render, SharedString, Styled as _, Subscription, Window,
+};
+use serde_json::Value;
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    dropdown::{Dropdown, DropdownEvent, DropdownState},
+    h_flex,
+    i18n::t,
+    input::{InputEvent, InputState, TextInput},
+    kbd::Kbd,
+    slider::{Slider, SliderEvent, SliderState},
+    state,
+    switch::Switch,
+    v_flex, ActiveTheme as _, IconName, IndexPath, Sizable as _, StyledExt as _,
+};
+
+/// The kind of the [`SettingItem`] with the default value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingKind {
+    Switch(bool),
+    Select {
+        options: Vec<SharedString>,
+        default: SharedString,
+    },
+    Slider {
+        range: RangeInclusive<f32>,
+        step: f32,
+        default: f32,
+    },
+    Text(SharedString),
+    /// The key binding, e.g.: `cmd-s` or `ctrl-k ctrl-c`.
+    KeyBinding(SharedString),
+}
+
+impl SettingKind {
+    fn default_value(&self) -> Value {
+        match self {
+            Self::Switch(default) => Value::from(*default),
+            Self::Select { default, .. } | Self::Text(default) | Self::KeyBinding(default) => {
+                Value::from(default.to_string())
+            }
+            Self::Slider { default, .. } => Value::from(*default),
+        }
+    }
+}
+
+/// An item of the [`SettingSection`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingItem {
+    /// The key to persist the value, e.g.: `editor.font_size`.
+    pub key: SharedString,
+    pub label: SharedString,
+    pub description: Option<SharedString>,
+    pub kind: SettingKind,
+}
+
+impl SettingItem {
+    fn new(
+        key: impl Into<SharedString>,
+        label: impl Into<SharedString>,
+        kind: SettingKind,
+    ) -> Self {
+        Self {
+            key: key.into(),
+            label: label.into(),
+            description: None,
+            kind,
+        }
+    }
+
+    /// Create a switch item.
+    pub fn switch(
+        key: impl Into<SharedString>,
+        label: impl Into<SharedString>,
+        default: bool,
+    ) -> Self {
+        Self::new(key, label, SettingKind::Switch(default))
+    }
+
+    /// Create a select item with the options.
+    pub fn select(
+        key: impl Into<SharedString>,
+        label: impl Into<SharedString>,
+        options: impl IntoIterator<Item = impl Into<SharedString>>,
+        default: impl Into<SharedString>,
+    ) -> Self {
+        Self::new(
+            key,
+            label,
+            SettingKind::Select {
+                options: options.into_iter().map(Into::into).collect(),
+                default: default.into(),
+            },
+        )
+    }
+
+    /// Create a slider item, the step is 1.0 by default.
+    pub fn slider(
+        key: impl Into<SharedString>,
+        label: impl Into<SharedString>,
+        range: RangeInclusive<f32>,
+        default: f32,
+    ) -> Self {
+        Self::new(
+            key,
+            label,
+            SettingKind::Slider {
+                range,
+                step: 1.,
+                default,
+            },
+        )
+    }
+
+    /// Create a text item.
+    pub fn text(
+        key: impl Into<SharedString>,
+        label: impl Into<SharedString>,
+        default: impl Into<SharedString>,
+    ) -> Self {
+        Self::new(key, label, SettingKind::Text(default.into()))
+    }
+
+    /// Create a key binding item, the value is saved only if the keystrokes are valid.
+    pub fn key_binding(
+        key: impl Into<SharedString>,
+        label: impl Into<SharedString>,
+        default: impl Into<SharedString>,
+    ) -> Self {
+        Self::new(key, label, SettingKind::KeyBinding(default.into()))
+    }
+
+    /// Set the description of the item.
+    pub fn description(mut self, description: impl Into<SharedString>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the step of the slider item.
+    pub fn step(mut self, step: f32) -> Self {
+        if let SettingKind::Slider { step: s, .. } = &mut self.kind {
+            *s = step;
+        }
+        self
+    }
+
+    /// Returns true if the label, description or key contains the `query` (case-insensitive).
+    fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.label.to_lowercase().contains(&query)
+            || self.key.to_lowercase().contains(&query)
+            || self.description.as_ref().map_or(false, |description| {
+                description.to_lowercase().contains(&query)
+            })
+    }
+}
+
+/// A section of the [`SettingsView`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingSection {
+    pub title: SharedString,
+    pub items: Vec<SettingItem>,
+}
+
+impl SettingSection {
+    pub fn new(title: impl Into<SharedString>) -> Self {
+        Self {
+            title: title.into(),
+            items: vec![],
+        }
+    }
+
+    /// Add an item to the section.
+    pub fn item(mut self, item: SettingItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Add items to the section.
+    pub fn items(mut self, items: impl IntoIterator<Item = SettingItem>) -> Self {
+        self.items.extend(items);
+        self
+    }
+}
+
+/// Returns the keystrokes of the key binding, `None` if any of them is invalid.
+fn parse_keystrokes(binding: &str) -> Option<Vec<Keystroke>> {
+    let keystrokes = binding
+        .split_whitespace()
+        .map(|stroke| Keystroke::parse(stroke).ok())
+        .collect::<Option<Vec<_>>>()?;
+    (!keystrokes.is_empty()).then_some(keystrokes)
+}
+
+pub enum SettingsEvent {
+    /// The value of the item is changed.
+    Change { key: SharedString, value: Value },
+}
+
+enum Control {
+    Select(Entity<DropdownState<Vec<SharedString>>>),
+    Slider(Entity<SliderState>),
+    Input(Entity<InputState>),
+}
+
+/// The settings screen, with a search input to filter the items.
+pub struct SettingsView {
+    focus_handle: FocusHandle,
+    sections: Vec<SettingSection>,
+    search_input: Entity<InputState>,
+    values: HashMap<SharedString, Value>,
+    controls: HashMap<SharedString, Control>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<SettingsEvent> for SettingsView {}
+
+impl SettingsView {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let search_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(t!("Settings.search_placeholder")));
+        let _subscriptions = vec![cx.subscribe(&search_input, |_, _, event: &InputEvent, cx| {
+            if let InputEvent::Change(_) = event {
+                cx.notify();
+            }
+        })];
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            sections: vec![],
+            search_input,
+            values: HashMap::new(),
+            controls: HashMap::new(),
+            _subscriptions,
+        }
+    }
+
+    /// Add a section, the values of the items are restored from the [`state`] storage.
+    pub fn section(
+        mut self,
+        section: SettingSection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        for item in section.items.iter() {
+            let value = state::get::<Value>(&item.key, cx)
+                .filter(|value| Self::is_valid(&item.kind, value))
+                .unwrap_or_else(|| item.kind.default_value());
+            self.add_control(item, &value, window, cx);
+            self.values.insert(item.key.clone(), value);
+        }
+        self.sections.push(section);
+        self
+    }
+
+    fn is_valid(kind: &SettingKind, value: &Value) -> bool {
+        match kind {
+            SettingKind::Switch(_) => value.is_boolean(),
+            SettingKind::Select { options, .. } => value
+                .as_str()
+                .map_or(false, |value| options.iter().any(|option| option == value)),
+            SettingKind::Slider { .. } => value.is_number(),
+            SettingKind::Text(_) => value.is_string(),
+            SettingKind::KeyBinding(_) => value.as_str().and_then(parse_keystrokes).is_some(),
+        }
+    }
+
+    fn add_control(
+        &mut self,
+        item: &SettingItem,
+        value: &Value,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let key = item.key.clone();
+        let control = match &item.kind {
+            SettingKind::Switch(_) => return,
+            SettingKind::Select { options, .. } => {
+                let selected = value.as_str().unwrap_or_default();
+                let selected_index = options
+                    .iter()
+                    .position(|option| option == selected)
+                    .map(IndexPath::new);
+                let dropdown =
+                    cx.new(|cx| DropdownState::new(options.clone(), selected_index, window, cx));
+                self._subscriptions.push(cx.subscribe(
+                    &dropdown,
+                    move |this, _, event: &DropdownEvent<Vec<SharedString>>, cx| {
+                        let DropdownEvent::Confirm(Some(value)) = event else {
+                            return;
+                        };
+                        this.update_value(key.clone(), Value::from(value.to_string()), cx);
+                    },
+                ));
+                Control::Select(dropdown)
+            }
+            SettingKind::Slider { range, step, .. } => {
+                let slider = cx.new(|_| {
+                    SliderState::new()
+                        .min(*range.start())
+                        .max(*range.end())
+                        .step(*step)
+                        .default_value(value.as_f64().unwrap_or_default() as f32)
+                });
+                self._subscriptions.push(cx.subscribe(
+                    &slider,
+                    move |this, _, event: &SliderEvent, cx| {
+                        let SliderEvent::Change(value) = event;
+                        this.update_value(key.clone(), Value::from(*value), cx);
+                    },
+                ));
+                Control::Slider(slider)
+            }
+            SettingKind::Text(_) | SettingKind::KeyBinding(_) => {
+                let is_key_binding = matches!(item.kind, SettingKind::KeyBinding(_));
+                let input = cx.new(|cx| {
+                    InputState::new(window, cx).default_value(value.as_str().unwrap_or_default())
+                });
+                self._subscriptions.push(cx.subscribe(
+                    &input,
+                    move |this, _, event: &InputEvent, cx| {
+                        let InputEvent::Change(text) = event else {
+                            return;
+                        };
+                        if is_key_binding && parse_keystrokes(text).is_none() {
+                            return;
+                        }
+                        this.update_value(key.clone(), Value::from(text.to_string()), cx);
+                    },
+                ));
+                Control::Input(input)
+            }
+        };
+        self.controls.insert(item.key.clone(), control);
+    }
+
+    fn item(&self, key: &str) -> Option<&SettingItem> {
+        self.sections
+            .iter()
+            .flat_map(|section| section.items.iter())
+            .find(|item| item.key == key)
+    }
+
+    fn update_value(&mut self, key: SharedString, value: Value, cx: &mut Context<Self>) {
+        if self.values.get(&key) == Some(&value) {
+            return;
+        }
+
+        state::set(key.to_string(), &value, cx);
+        self.values.insert(key.clone(), value.clone());
+        cx.emit(SettingsEvent::Change { key, value });
+        cx.notify();
+    }
+
+    /// Returns the value of the item.
+    pub fn value(&self, key: &str) -> Option<&Value> {
+        self.values.get(key)
+    }
+
+    /// Returns true if the value of the item is different from the default.
+    pub fn is_modified(&self, key: &str) -> bool {
+        match (self.item(key), self.values.get(key)) {
+            (Some(item), Some(value)) => item.kind.default_value() != *value,
+            _ => false,
+        }
+    }
+
+    /// Returns the keys of the modified items.
+    pub fn modified_keys(&self) -> Vec<SharedString> {
+        self.sections
+            .iter()
+            .flat_map(|section| section.items.iter())
+            .filter(|item| self.is_modified(&item.key))
+            .map(|item| item.key.clone())
+            .collect()
+    }
+
+    /// Reset the item to the default value.
+    pub fn reset(&mut self, key: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(item) = self.item(key) else {
+            return;
+        };
+        let key = item.key.clone();
+        let value = item.kind.default_value();
+
+        match self.controls.get(&key) {
+            Some(Control::Select(dropdown)) => {
+                let selected = SharedString::from(value.as_str().unwrap_or_default().to_string());
+                dropdown.update(cx, |dropdown, cx| {
+                    dropdown.set_selected_value(&selected, window, cx);
+                });
+            }
+            Some(Control::Slider(slider)) => {
+                let value = value.as_f64().unwrap_or_default() as f32;
+                slider.update(cx, |slider, cx| slider.set_value(value, window, cx));
+            }
+            Some(Control::Input(input)) => {
+                let text = value.as_str().unwrap_or_default().to_string();
+                input.update(cx, |input, cx| input.set_value(text, window, cx));
+            }
+            None => {}
+        }
+        self.update_value(key, value, cx);
+    }
+
+    fn render_control(&self, item: &SettingItem, cx: &mut Context<Self>) -> impl IntoElement {
+        let key = item.key.clone();
+        let value = self.values.get(&key);
+
+        div()
+            .w_64()
+            .flex()
+            .justify_end()
+            .map(|this| match (&item.kind, self.controls.get(&key)) {
+                (SettingKind::Switch(_), _) => {
+                    let checked = value.and_then(|value| value.as_bool()).unwrap_or_default();
+                    let view = cx.entity();
+                    this.child(
+                        Switch::new(SharedString::from(format!("setting-{}", key)))
+                            .checked(checked)
+                            .on_click(move |checked, _, cx| {
+                                let key = key.clone();
+                                view.update(cx, |this, cx| {
+                                    this.update_value(key, Value::from(*checked), cx);
+                                });
+                            }),
+                    )
+                }
+                (_, Some(Control::Select(dropdown))) => this.child(Dropdown::new(dropdown).small()),
+                (_, Some(Control::Slider(slider))) => this.child(
+                    h_flex().w_full().gap_2().child(Slider::new(slider)).child(
+                        div()
+                            .w_10()
+                            .text_right()
+                            .text_sm()
+                            .child(format!("{}", slider.read(cx).value())),
+                    ),
+                ),
+                (SettingKind::KeyBinding(_), Some(Control::Input(input))) => {
+                    let keystrokes = parse_keystrokes(input.read(cx).value());
+                    this.child(TextInput::new(input).small().when_some(
+                        keystrokes,
+                        |this, keystrokes| {
+                            this.suffix(
+                                h_flex()
+                                    .gap_1()
+                                    .children(keystrokes.into_iter().map(Kbd::new)),
+                            )
+                        },
+                    ))
+                }
+                (_, Some(Control::Input(input))) => this.child(TextInput::new(input).small()),
+                _ => this,
+            })
+    }
+
+    fn render_item(&self, item: &SettingItem, cx: &mut Context<Self>) -> impl IntoElement {
+        let key = item.key.clone();
+        let modified = self.is_modified(&key);
+
+        h_flex()
+            .id(SharedString::from(format!("setting-item-{}", key)))
+            .py_3()
+            .gap_4()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .gap_1()
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .text_sm()
+                            .font_medium()
+                            .child(item.label.clone())
+                            .when(modified, |this| {
+                                this.child(
+                                    Button::new(SharedString::from(format!("reset-{}", key)))
+                                        .label(t!("Settings.reset"))
+                                        .ghost()
+                                        .xsmall()
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            this.reset(&key, window, cx);
+                                        })),
+                                )
+                            }),
+                    )
+                    .when_some(item.description.clone(), |this, description| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(description),
+                        )
+                    }),
+            )
+            .child(self.render_control(item, cx))
+    }
+}
+
+impl Focusable for SettingsView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for SettingsView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let query = self.search_input.read(cx).value().clone();
+        let sections = self
+            .sections
+            .iter()
+            .filter_map(|section| {
+                let items = section
+                    .items
+                    .iter()
+                    .filter(|item| item.matches(&query))
+                    .collect::<Vec<_>>();
+                if items.is_empty() {
+                    return None;
+                }
+
+                Some(
+                    v_flex()
+                        .gap_1()
+                        .child(div().text_lg().font_semibold().child(section.title.clone()))
+                        .children(items.into_iter().map(|item| self.render_item(item, cx))),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .id("settings")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .gap_4()
+            .child(
+                TextInput::new(&self.search_input)
+                    .prefix(IconName::Search)
+                    .cleanable(),
+            )
+            .child(
+                v_flex()
+                    .id("settings-sections")
+                    .flex_1()
+                    .gap_6()
+                    .overflow_y_scroll()
+                    .when(sections.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(t!("Settings.no_results")),
+                        )
+                    })
+                    .children(sections),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_keystrokes, SettingItem};
+
+    #[test]
+    fn test_item_matches() {
+        let item = SettingItem::switch("editor.line_numbers", "Line Numbers", true)
+            .description("Show the line numbers in the gutter.");
+        assert!(item.matches(""));
+        assert!(item.matches("line"));
+        assert!(item.matches("EDITOR"));
+        assert!(item.matches("gutter"));
+        assert!(!item.matches("theme"));
+    }
+
+    #[test]
+    fn test_parse_keystrokes() {
+        assert_eq!(parse_keystrokes("cmd-s").map(|keys| keys.len()), Some(1));
+        assert_eq!(
+            parse_keystrokes("ctrl-k ctrl-c").map(|keys| keys.len()),
+            Some(2)
+        );
+        assert!(parse_keystrokes("").is_none());
+        assert!(parse_keystrokes("   ").is_none());
+    }
+}
@@ -0,0 +1,321 @@
+//! An input to record a keyboard shortcut, e.g.: for the keymap settings.
+//!
+//! When focused, the pressed chord is recorded and shown by the platform symbols, `escape`
+//! without the modifiers stops recording.
+//!
+//! ```ignore
+//! let state = cx.new(|cx| {
+//!     ShortcutInputState::new(window, cx)
+//!         .default_value(Keystroke::parse("cmd-shift-p").ok())
+//!         .action(Box::new(ToggleCommandPalette))
+//! });
+//!
+//! // In render
+//! ShortcutInput::new(&state)
+//! ```
+use gpui::{
+    div, prelude::FluentBuilder as _, Action, App, AppContext as _, Context, Entity, EventEmitter,
+    FocusHandle, Focusable, InteractiveElement as _, IntoElement, KeyDownEvent, Keystroke,
+    ParentElement as _, Render, RenderOnce, SharedString, StyleRefinement, Styled, Window,
+};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    i18n::t,
+    v_flex, ActiveTheme as _, IconName, Kbd, Sizable, Size, StyleSized as _, StyledExt as _,
+};
+
+/// The events of the [`ShortcutInputState`].
+#[derive(Debug, Clone)]
+pub enum ShortcutInputEvent {
+    /// The shortcut is recorded, cleared or reset, `None` if it is cleared.
+    Change(Option<Keystroke>),
+}
+
+/// Returns true if the keystrokes are the same chord.
+fn is_same_chord(a: &Keystroke, b: &Keystroke) -> bool {
+    a.modifiers == b.modifiers && a.key.eq_ignore_ascii_case(&b.key)
+}
+
+fn is_same_value(a: Option<&Keystroke>, b: Option<&Keystroke>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => is_same_chord(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// State of the [`ShortcutInput`].
+pub struct ShortcutInputState {
+    focus_handle: FocusHandle,
+    value: Option<Keystroke>,
+    default_value: Option<Keystroke>,
+    /// The action of the shortcut, its own bindings are not the conflicts.
+    action: Option<Box<dyn Action>>,
+}
+
+impl EventEmitter<ShortcutInputEvent> for ShortcutInputState {}
+
+impl ShortcutInputState {
+    pub fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            value: None,
+            default_value: None,
+            action: None,
+        }
+    }
+
+    /// Set the default shortcut, it is also the initial value.
+    pub fn default_value(mut self, value: Option<Keystroke>) -> Self {
+        self.value = value.clone();
+        self.default_value = value;
+        self
+    }
+
+    /// Set the action of the shortcut, the bindings of the action are not the conflicts.
+    pub fn action(mut self, action: Box<dyn Action>) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    /// Returns the recorded shortcut.
+    pub fn value(&self) -> Option<&Keystroke> {
+        self.value.as_ref()
+    }
+
+    /// Set the shortcut, the change event is not emitted.
+    pub fn set_value(&mut self, value: Option<Keystroke>, _: &mut Window, cx: &mut Context<Self>) {
+        self.value = value;
+        cx.notify();
+    }
+
+    /// Clear the shortcut.
+    pub fn clear(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        self.update_value(None, cx);
+    }
+
+    /// Reset the shortcut to the default value.
+    pub fn reset(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        self.update_value(self.default_value.clone(), cx);
+    }
+
+    /// Returns true if the shortcut is recording.
+    pub fn is_recording(&self, window: &Window) -> bool {
+        self.focus_handle.is_focused(window)
+    }
+
+    /// Returns the names of the other actions bound to the shortcut.
+    pub fn conflicts(&self, cx: &App) -> Vec<SharedString> {
+        let Some(value) = &self.value else {
+            return vec![];
+        };
+
+        let keymap = cx.key_bindings();
+        let keymap = keymap.borrow();
+        let mut names: Vec<SharedString> = vec![];
+        for binding in keymap.bindings() {
+            let [keystroke] = binding.keystrokes() else {
+                continue;
+            };
+            if !is_same_chord(keystroke, value) {
+                continue;
+            }
+            if let Some(action) = &self.action {
+                if binding.action().partial_eq(action.as_ref()) {
+                    continue;
+                }
+            }
+
+            let name = SharedString::from(binding.action().name());
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    fn update_value(&mut self, value: Option<Keystroke>, cx: &mut Context<Self>) {
+        if is_same_value(self.value.as_ref(), value.as_ref()) {
+            return;
+        }
+
+        self.value = value.clone();
+        cx.emit(ShortcutInputEvent::Change(value));
+        cx.notify();
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let keystroke = &event.keystroke;
+        window.prevent_default();
+        cx.stop_propagation();
+
+        if keystroke.key == "escape" && !keystroke.modifiers.modified() {
+            window.blur();
+            cx.notify();
+            return;
+        }
+
+        self.update_value(Some(keystroke.clone()), cx);
+    }
+}
+
+impl Focusable for ShortcutInputState {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ShortcutInputState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        ShortcutInput::new(&cx.entity())
+    }
+}
+
+/// An input to record a keyboard shortcut of the [`ShortcutInputState`].
+///
+/// The chords bound to the actions of the focused contexts are dispatched as the actions
+/// before recording, so they can not be recorded.
+#[derive(IntoElement)]
+pub struct ShortcutInput {
+    state: Entity<ShortcutInputState>,
+    style: StyleRefinement,
+    size: Size,
+    cleanable: bool,
+}
+
+impl ShortcutInput {
+    pub fn new(state: &Entity<ShortcutInputState>) -> Self {
+        Self {
+            state: state.clone(),
+            style: StyleRefinement::default(),
+            size: Size::default(),
+            cleanable: true,
+        }
+    }
+
+    /// Set to show the buttons to clear and reset the shortcut, default is true.
+    pub fn cleanable(mut self, cleanable: bool) -> Self {
+        self.cleanable = cleanable;
+        self
+    }
+}
+
+impl Sizable for ShortcutInput {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Styled for ShortcutInput {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl Focusable for ShortcutInput {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.state.read(cx).focus_handle.clone()
+    }
+}
+
+impl RenderOnce for ShortcutInput {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let is_recording = state.is_recording(window);
+        let conflicts = state.conflicts(cx);
+        let value = state.value.clone();
+        let is_default = is_same_value(state.value.as_ref(), state.default_value.as_ref());
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .id(("shortcut-input", self.state.entity_id()))
+                    .track_focus(&state.focus_handle)
+                    .on_key_down(window.listener_for(&self.state, ShortcutInputState::on_key_down))
+                    .gap_1()
+                    .input_size(self.size)
+                    .input_text_size(self.size)
+                    .border_1()
+                    .border_color(cx.theme().input)
+                    .rounded(cx.theme().radius)
+                    .bg(cx.theme().background)
+                    .when(is_recording, |this| this.border_color(cx.theme().ring))
+                    .when(!conflicts.is_empty(), |this| {
+                        this.border_color(cx.theme().danger)
+                    })
+                    .refine_style(&self.style)
+                    .child(
+                        h_flex()
+                            .flex_1()
+                            .overflow_hidden()
+                            .map(|this| match &value {
+                                Some(keystroke) => this.child(Kbd::new(keystroke.clone())),
+                                None => this.text_color(cx.theme().muted_foreground).child(
+                                    if is_recording {
+                                        t!("ShortcutInput.recording")
+                                    } else {
+                                        t!("ShortcutInput.placeholder")
+                                    },
+                                ),
+                            }),
+                    )
+                    .when(self.cleanable && !is_default, |this| {
+                        this.child(
+                            Button::new("reset")
+                                .ghost()
+                                .xsmall()
+                                .label(t!("ShortcutInput.reset"))
+                                .on_click(
+                                    window.listener_for(&self.state, |state, _, window, cx| {
+                                        state.reset(window, cx)
+                                    }),
+                                ),
+                        )
+                    })
+                    .when(self.cleanable && value.is_some(), |this| {
+                        this.child(
+                            Button::new("clear")
+                                .ghost()
+                                .xsmall()
+                                .icon(IconName::Close)
+                                .tooltip(t!("ShortcutInput.clear"))
+                                .on_click(
+                                    window.listener_for(&self.state, |state, _, window, cx| {
+                                        state.clear(window, cx)
+                                    }),
+                                ),
+                        )
+                    }),
+            )
+            .when(!conflicts.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().danger)
+                        .child(t!("ShortcutInput.conflict", actions = conflicts.join(", "))),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::Keystroke;
+
+    use super::{is_same_chord, is_same_value};
+
+    #[test]
+    fn test_is_same_chord() {
+        let parse = |source| Keystroke::parse(source).unwrap();
+
+        assert!(is_same_chord(&parse("cmd-shift-p"), &parse("shift-cmd-p")));
+        assert!(!is_same_chord(&parse("cmd-shift-p"), &parse("cmd-p")));
+        assert!(!is_same_chord(&parse("ctrl-a"), &parse("ctrl-b")));
+        assert!(is_same_value(None, None));
+        assert!(!is_same_value(Some(&parse("ctrl-a")), None));
+    }
+}
@@ -34,7 +34,7 @@ impl Styled for Skeleton {
 
 impl RenderOnce for Skeleton {
     fn render(self, _: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
-        div()
+        let el = div()
             .w_full()
             .h_4()
             .bg(if self.secondary {
@@ -42,16 +42,22 @@ impl RenderOnce for Skeleton {
             } else {
                 cx.theme().skeleton
             })
-            .refine_style(&self.style)
-            .with_animation(
-                "skeleton",
-                Animation::new(Duration::from_secs(2))
-                    .repeat()
-                    .with_easing(bounce(ease_in_out)),
-                move |this, delta| {
-                    let v = 1.0 - delta * 0.5;
-                    this.opacity(v)
-                },
-            )
+            .refine_style(&self.style);
+
+        if cx.theme().reduce_motion {
+            return el.into_any_element();
+        }
+
+        el.with_animation(
+            "skeleton",
+            Animation::new(Duration::from_secs(2))
+                .repeat()
+                .with_easing(bounce(ease_in_out)),
+            move |this, delta| {
+                let v = 1.0 - delta * 0.5;
+                this.opacity(v)
+            },
+        )
+        .into_any_element()
     }
 }
@@ -1,4 +1,9 @@
-use crate::{h_flex, tooltip::Tooltip, ActiveTheme, AxisExt};
+use crate::{
+    accessibility::{self, AccessibilityProperties, AccessibilityRole, Accessible},
+    h_flex,
+    tooltip::Tooltip,
+    ActiveTheme, AxisExt, LayoutDirection, ReadOnly,
+};
 use gpui::{
     canvas, div, prelude::FluentBuilder as _, px, App, AppContext as _, Axis, Bounds, Context,
     DragMoveEvent, Empty, Entity, EntityId, EventEmitter, InteractiveElement, IntoElement,
@@ -147,6 +152,9 @@ pub struct Slider {
     axis: Axis,
     reverse: bool,
     disabled: bool,
+    read_only: bool,
+    accessibility: AccessibilityProperties,
+    direction: Option<LayoutDirection>,
 }
 
 impl Slider {
@@ -157,6 +165,9 @@ impl Slider {
             reverse: false,
             state: state.clone(),
             disabled: false,
+            read_only: false,
+            accessibility: AccessibilityProperties::default(),
+            direction: None,
         }
     }
 
@@ -184,49 +195,55 @@ impl Slider {
         self
     }
 
+    /// Set the layout direction, default is the direction of the window.
+    ///
+    /// The horizontal slider is reversed in [`LayoutDirection::RightToLeft`].
+    pub fn direction(mut self, direction: LayoutDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
     fn render_thumb(
         &self,
         thumb_bar_size: Pixels,
+        reverse: bool,
         window: &mut Window,
         cx: &mut App,
     ) -> impl gpui::IntoElement {
         let state = self.state.read(cx);
         let entity_id = self.state.entity_id();
         let value = state.value;
-        let reverse = self.reverse;
         let axis = self.axis;
 
-        if self.disabled {
-            return div().id("slider-thumb");
-        }
-
         div()
             .id("slider-thumb")
-            .on_drag(DragThumb(entity_id), |drag, _, _, cx| {
-                cx.stop_propagation();
-                cx.new(|_| drag.clone())
-            })
-            .on_drag_move(window.listener_for(
-                &self.state,
-                move |view, e: &DragMoveEvent<DragThumb>, window, cx| {
-                    match e.drag(cx) {
-                        DragThumb(id) => {
-                            if *id != entity_id {
-                                return;
-                            }
+            .when(self.is_interactive(), |this| {
+                this.on_drag(DragThumb(entity_id), |drag, _, _, cx| {
+                    cx.stop_propagation();
+                    cx.new(|_| drag.clone())
+                })
+                .on_drag_move(window.listener_for(
+                    &self.state,
+                    move |view, e: &DragMoveEvent<DragThumb>, window, cx| {
+                        match e.drag(cx) {
+                            DragThumb(id) => {
+                                if *id != entity_id {
+                                    return;
+                                }
 
-                            // set value by mouse position
-                            view.update_value_by_position(
-                                axis,
-                                reverse,
-                                e.event.position,
-                                window,
-                                cx,
-                            )
+                                // set value by mouse position
+                                view.update_value_by_position(
+                                    axis,
+                                    reverse,
+                                    e.event.position,
+                                    window,
+                                    cx,
+                                )
+                            }
                         }
-                    }
-                },
-            ))
+                    },
+                ))
+            })
             .absolute()
             .map(|this| match reverse {
                 true => this
@@ -250,15 +267,51 @@ impl Slider {
             .border_color(cx.theme().slider_bar.opacity(0.9))
             .when(cx.theme().shadow, |this| this.shadow_md())
             .bg(cx.theme().slider_thumb)
-            .tooltip(move |window, cx| Tooltip::new(format!("{}", value)).build(window, cx))
+            .when(!self.disabled, |this| {
+                this.tooltip(move |window, cx| Tooltip::new(format!("{}", value)).build(window, cx))
+            })
+    }
+
+    /// Returns true if the value can be changed by the user.
+    fn is_interactive(&self) -> bool {
+        !self.disabled && !self.read_only
+    }
+}
+
+impl ReadOnly for Slider {
+    /// Set the read-only state of the slider, the value can't be changed by the user.
+    fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+}
+
+impl Accessible for Slider {
+    fn accessibility_mut(&mut self) -> &mut AccessibilityProperties {
+        &mut self.accessibility
+    }
+
+    fn accessibility(&self, cx: &App) -> AccessibilityProperties {
+        let value = self.state.read(cx).value;
+
+        AccessibilityProperties::new(AccessibilityRole::Slider)
+            .value(value.to_string())
+            .disabled(self.disabled)
+            .read_only(self.read_only)
+            .merge(&self.accessibility)
     }
 }
 
 impl RenderOnce for Slider {
     fn render(self, window: &mut Window, cx: &mut gpui::App) -> impl IntoElement {
+        accessibility::record_rendered(self.accessibility(cx), window, cx);
+
         let state = self.state.read(cx);
         let axis = self.axis;
-        let reverse = self.reverse;
+        let direction = self
+            .direction
+            .unwrap_or_else(|| LayoutDirection::of(window, cx));
+        let reverse = self.reverse ^ (axis.is_horizontal() && direction.is_rtl());
         let thumb_bar_size = match axis {
             Axis::Horizontal => state.percentage * state.bounds.size.width,
             Axis::Vertical => state.percentage * state.bounds.size.height,
@@ -272,9 +325,10 @@ impl RenderOnce for Slider {
                 this.items_center().justify_center()
             })
             .when(axis.is_horizontal(), |this| this.w_full())
+            .when(self.disabled, |this| this.opacity(0.5))
             .child(
                 h_flex()
-                    .when(!self.disabled, |this| {
+                    .when(self.is_interactive(), |this| {
                         this.on_mouse_down(
                             MouseButton::Left,
                             window.listener_for(
@@ -317,7 +371,7 @@ impl RenderOnce for Slider {
                                     .bg(cx.theme().slider_bar)
                                     .rounded_full(),
                             )
-                            .child(self.render_thumb(thumb_bar_size, window, cx))
+                            .child(self.render_thumb(thumb_bar_size, reverse, window, cx))
                             .child({
                                 let state = self.state.clone();
                                 canvas(
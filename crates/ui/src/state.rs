@@ -0,0 +1,176 @@
+//! The key-value storage to persist the UI state (e.g.: the dock layout, the sizes of the
+//! resizable panels, the column widths of the table, the window bounds and the theme) as a
+//! JSON file on disk.
+//!
+//! ```ignore
+//! gpui_component::state::load("target/state.json", cx)?;
+//!
+//! let mode = state::get::<ThemeMode>("theme.mode", cx).unwrap_or_default();
+//! state::set("theme.mode", &ThemeMode::Dark, cx);
+//! ```
+//!
+//! The changes are saved in the background after a short delay, and saved on app quit.
+//!
+//! The components are persisted with a key by:
+//!
+//! - [`DockArea::persist_layout`](crate::dock::DockArea::persist_layout)
+//! - [`ResizableState::persist_sizes`](crate::resizable::ResizableState::persist_sizes)
+//! - [`Table::persist_layout`](crate::table::Table::persist_layout)
+//! - [`Root::persist_state`](crate::Root::persist_state), the window bounds, the UI scale and
+//!   the theme mode.
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Result;
+use gpui::{App, Global, Task};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+/// The delay to save the changes, the changes in the delay are saved together.
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Default)]
+struct StateStorage {
+    path: Option<PathBuf>,
+    values: Map<String, Value>,
+    _save_task: Option<Task<()>>,
+}
+
+impl Global for StateStorage {}
+
+impl StateStorage {
+    fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.values.get(key)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// Set the value of the key, returns true if the value is changed.
+    fn set(&mut self, key: String, value: Value) -> bool {
+        if self.values.get(&key) == Some(&value) {
+            return false;
+        }
+        self.values.insert(key, value);
+        true
+    }
+}
+
+/// Load the state from the JSON file at `path`, the changes are saved to the file.
+///
+/// The file is created on the first save if it does not exist.
+pub fn load(path: impl Into<PathBuf>, cx: &mut App) -> Result<()> {
+    let path = path.into();
+    let values = match std::fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json)?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Map::new(),
+        Err(err) => return Err(err.into()),
+    };
+
+    let first_load = !cx.has_global::<StateStorage>();
+    cx.set_global(StateStorage {
+        path: Some(path),
+        values,
+        _save_task: None,
+    });
+
+    if first_load {
+        cx.on_app_quit(|cx| save(cx)).detach();
+    }
+    Ok(())
+}
+
+/// Returns the value of the key, `None` if the key is not found or the value can not
+/// be deserialized to `T`.
+pub fn get<T: DeserializeOwned>(key: &str, cx: &App) -> Option<T> {
+    cx.try_global::<StateStorage>()?.get(key)
+}
+
+/// Set the value of the key, it is saved after a short delay.
+pub fn set<T: Serialize>(key: impl Into<String>, value: &T, cx: &mut App) {
+    let value = match serde_json::to_value(value) {
+        Ok(value) => value,
+        Err(err) => {
+            tracing::error!("failed to serialize the state: {:?}", err);
+            return;
+        }
+    };
+
+    if cx.default_global::<StateStorage>().set(key.into(), value) {
+        schedule_save(cx);
+    }
+}
+
+/// Remove the value of the key.
+pub fn remove(key: &str, cx: &mut App) {
+    if cx
+        .default_global::<StateStorage>()
+        .values
+        .remove(key)
+        .is_some()
+    {
+        schedule_save(cx);
+    }
+}
+
+fn schedule_save(cx: &mut App) {
+    let task = cx.spawn(async move |cx| {
+        cx.background_executor().timer(SAVE_DELAY).await;
+        let Ok(task) = cx.update(|cx| save(cx)) else {
+            return;
+        };
+        task.await;
+    });
+    cx.global_mut::<StateStorage>()._save_task = Some(task);
+}
+
+/// Save the state to the file now, it is not saved if the state is not loaded by [`load`].
+pub fn save(cx: &mut App) -> Task<()> {
+    let Some(storage) = cx.try_global::<StateStorage>() else {
+        return Task::ready(());
+    };
+    let Some(path) = storage.path.clone() else {
+        return Task::ready(());
+    };
+    let values = storage.values.clone();
+
+    cx.background_executor().spawn(async move {
+        let result = (|| -> Result<()> {
+            let json = serde_json::to_string_pretty(&values)?;
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            // Write to a temporary file and rename it, to not corrupt the file on crash.
+            let tmp_path = path.with_extension("tmp");
+            std::fs::write(&tmp_path, json)?;
+            std::fs::rename(&tmp_path, &path)?;
+            Ok(())
+        })();
+
+        if let Err(err) = result {
+            tracing::error!("failed to save the state to {:?}: {:?}", path, err);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::StateStorage;
+
+    #[test]
+    fn test_state_storage() {
+        let mut storage = StateStorage::default();
+        assert_eq!(storage.get::<f32>("sidebar.width"), None);
+
+        assert!(storage.set("sidebar.width".into(), json!(240.)));
+        assert!(!storage.set("sidebar.width".into(), json!(240.)));
+        assert_eq!(storage.get::<f32>("sidebar.width"), Some(240.));
+        // The value can not be deserialized to the type.
+        assert_eq!(storage.get::<String>("sidebar.width"), None);
+
+        assert!(storage.set("table.widths".into(), json!([100, 120])));
+        assert_eq!(
+            storage.get::<Vec<u32>>("table.widths"),
+            Some(vec![100, 120])
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! A status bar at the bottom of the window, with the items in the left, center and right slots.
+//!
+//! ```ignore
+//! StatusBar::new()
+//!     .left(StatusItem::new("branch").icon(IconName::GitBranch).label("main"))
+//!     .left(
+//!         StatusItem::new("errors")
+//!             .icon(IconName::CircleX)
+//!             .label("3")
+//!             .tooltip("3 errors")
+//!             .prominent(true)
+//!             .on_click(|_, window, cx| window.dispatch_action(Box::new(ShowProblems), cx)),
+//!     )
+//!     .center(StatusItem::new("cursor").label("Ln 12, Col 8"))
+//!     .progress("Indexing...", Some(42.))
+//!     .right(
+//!         StatusItem::new("encoding")
+//!             .label("UTF-8")
+//!             .popup_menu(|menu, _, _| menu.menu("UTF-16", Box::new(ReopenWithUtf16))),
+//!     )
+//! ```
+use std::rc::Rc;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, AnyElement, App, ClickEvent, Context, Corner, ElementId,
+    IntoElement, ParentElement, RenderOnce, SharedString, StyleRefinement, Styled, Window,
+};
+
+use crate::{
+    button::{Button, ButtonCustomVariant, ButtonVariants as _},
+    h_flex,
+    indicator::Indicator,
+    popup_menu::{PopupMenu, PopupMenuExt as _},
+    progress::Progress,
+    task_progress, ActiveTheme as _, Icon, Sizable as _, StyledExt as _,
+};
+
+type MenuBuilder = Rc<dyn Fn(PopupMenu, &mut Window, &mut Context<PopupMenu>) -> PopupMenu>;
+
+/// A compact item of the [`StatusBar`] with the icon and the text.
+#[derive(IntoElement)]
+pub struct StatusItem {
+    id: ElementId,
+    icon: Option<Icon>,
+    label: Option<SharedString>,
+    tooltip: Option<SharedString>,
+    prominent: bool,
+    on_click: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
+    popup_menu: Option<MenuBuilder>,
+}
+
+impl StatusItem {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            icon: None,
+            label: None,
+            tooltip: None,
+            prominent: false,
+            on_click: None,
+            popup_menu: None,
+        }
+    }
+
+    pub fn icon(mut self, icon: impl Into<Icon>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn tooltip(mut self, tooltip: impl Into<SharedString>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Set to highlight the item with the danger color, e.g.: the errors count.
+    pub fn prominent(mut self, prominent: bool) -> Self {
+        self.prominent = prominent;
+        self
+    }
+
+    pub fn on_click(
+        mut self,
+        handler: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+
+    /// Set the menu to open by the click, the menu is opened above the item.
+    pub fn popup_menu(
+        mut self,
+        f: impl Fn(PopupMenu, &mut Window, &mut Context<PopupMenu>) -> PopupMenu + 'static,
+    ) -> Self {
+        self.popup_menu = Some(Rc::new(f));
+        self
+    }
+}
+
+impl RenderOnce for StatusItem {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let button = Button::new(self.id)
+            .xsmall()
+            .compact()
+            .map(|this| {
+                if self.prominent {
+                    this.custom(
+                        ButtonCustomVariant::new(cx)
+                            .color(cx.theme().danger)
+                            .foreground(cx.theme().danger_foreground)
+                            .hover(cx.theme().danger_hover)
+                            .active(cx.theme().danger_active),
+                    )
+                } else {
+                    this.ghost()
+                }
+            })
+            .when_some(self.icon, |this, icon| this.icon(icon))
+            .when_some(self.label, |this, label| this.label(label))
+            .when_some(self.tooltip, |this, tooltip| this.tooltip(tooltip))
+            .when_some(self.on_click, |this, on_click| {
+                this.on_click(move |event, window, cx| on_click(event, window, cx))
+            });
+
+        match self.popup_menu {
+            Some(f) => button
+                .popup_menu_with_anchor(Corner::BottomLeft, move |menu, window, cx| {
+                    f(menu, window, cx)
+                })
+                .into_any_element(),
+            None => button.into_any_element(),
+        }
+    }
+}
+
+/// A status bar with the left, center and right slots, and a background progress slot before
+/// the right items.
+#[derive(IntoElement, Default)]
+pub struct StatusBar {
+    style: StyleRefinement,
+    left: Vec<AnyElement>,
+    center: Vec<AnyElement>,
+    right: Vec<AnyElement>,
+    progress: Option<(SharedString, Option<f32>)>,
+}
+
+impl StatusBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item to the left slot.
+    pub fn left(mut self, item: impl IntoElement) -> Self {
+        self.left.push(item.into_any_element());
+        self
+    }
+
+    /// Add an item to the center slot.
+    pub fn center(mut self, item: impl IntoElement) -> Self {
+        self.center.push(item.into_any_element());
+        self
+    }
+
+    /// Add an item to the right slot.
+    pub fn right(mut self, item: impl IntoElement) -> Self {
+        self.right.push(item.into_any_element());
+        self
+    }
+
+    /// Show the background progress with the `label`, the `value` is in 0 ~ 100,
+    /// `None` to show the indeterminate indicator.
+    pub fn progress(mut self, label: impl Into<SharedString>, value: Option<f32>) -> Self {
+        self.progress = Some((label.into(), value));
+        self
+    }
+
+    /// Show the aggregate progress of the running tasks of the [`task_progress`].
+    pub fn task_progress(mut self, cx: &App) -> Self {
+        if let Some(progress) = task_progress::status_bar_progress(cx) {
+            self.progress = Some(progress);
+        }
+        self
+    }
+}
+
+impl Styled for StatusBar {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for StatusBar {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .h_6()
+            .flex_none()
+            .px_1()
+            .gap_2()
+            .justify_between()
+            .text_xs()
+            .border_t_1()
+            .border_color(cx.theme().title_bar_border)
+            .bg(cx.theme().title_bar)
+            .text_color(cx.theme().muted_foreground)
+            .refine_style(&self.style)
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .gap_0p5()
+                    .children(self.left),
+            )
+            .when(!self.center.is_empty(), |this| {
+                this.child(h_flex().flex_none().gap_0p5().children(self.center))
+            })
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .justify_end()
+                    .gap_0p5()
+                    .when_some(self.progress, |this, (label, value)| {
+                        this.child(
+                            h_flex()
+                                .flex_none()
+                                .gap_1()
+                                .px_1()
+                                .map(|this| match value {
+                                    Some(value) => this.child(
+                                        div().w(px(64.)).child(Progress::new().value(value)),
+                                    ),
+                                    None => this.child(Indicator::new().xsmall()),
+                                })
+                                .child(label),
+                        )
+                    })
+                    .children(self.right),
+            )
+    }
+}
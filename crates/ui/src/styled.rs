@@ -5,9 +5,10 @@ use crate::{
     ActiveTheme,
 };
 use gpui::{
-    div, point, px, App, Axis, BoxShadow, DefiniteLength, Div, Edges, Element, FocusHandle, Hsla,
-    Pixels, Refineable, StyleRefinement, Styled, Window,
+    div, point, px, AbsoluteLength, App, Axis, Bounds, BoxShadow, DefiniteLength, Div, Edges,
+    Element, FocusHandle, Hsla, Pixels, Point, Refineable, StyleRefinement, Styled, Window,
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Returns a `Div` as horizontal flex layout.
@@ -45,6 +46,43 @@ pub fn box_shadow(
     }
 }
 
+/// Returns the effective scale factor of the window, the number of physical pixels per logical pixel.
+///
+/// It may be fractional (e.g.: 1.25, 1.5), and changes when the window is moved to another monitor.
+#[inline]
+pub fn scale_factor(window: &Window) -> f32 {
+    window.scale_factor()
+}
+
+/// Snap the `value` to the nearest physical pixel, e.g.: 14px is 17.5 physical pixels
+/// at 1.25x, it will be snapped to 18 physical pixels (14.4px).
+pub fn snap_to_pixels(value: Pixels, scale_factor: f32) -> Pixels {
+    if scale_factor <= 0. {
+        return value;
+    }
+
+    px((value.0 * scale_factor).round() / scale_factor)
+}
+
+/// Snap the stroke `width` (e.g.: border, divider) to the physical pixels, keep at least 1 physical pixel.
+///
+/// So the hairline is not blurred or disappeared at the fractional scale factors.
+pub fn snap_stroke(width: Pixels, scale_factor: f32) -> Pixels {
+    if scale_factor <= 0. || width.0 <= 0. {
+        return width;
+    }
+
+    px((width.0 * scale_factor).round().max(1.) / scale_factor)
+}
+
+/// Returns the bounds of the rect by the two corners, e.g.: the marquee of the selection.
+pub(crate) fn rect_from_points(a: Point<Pixels>, b: Point<Pixels>) -> Bounds<Pixels> {
+    Bounds::from_corners(
+        point(a.x.min(b.x), a.y.min(b.y)),
+        point(a.x.max(b.x), a.y.max(b.y)),
+    )
+}
+
 macro_rules! font_weight {
     ($fn:ident, $const:ident) => {
         /// [docs](https://tailwindcss.com/docs/font-weight)
@@ -69,6 +107,16 @@ pub trait StyledExt: Styled + Sized {
         self.flex().flex_row().items_center()
     }
 
+    /// Set the flex direction to row, or row reverse for [`LayoutDirection::RightToLeft`].
+    #[inline]
+    fn flex_row_with(self, direction: LayoutDirection) -> Self {
+        if direction.is_rtl() {
+            self.flex_row_reverse()
+        } else {
+            self.flex_row()
+        }
+    }
+
     /// Apply self into a vertical flex layout.
     #[inline]
     fn v_flex(self) -> Self {
@@ -99,6 +147,17 @@ pub trait StyledExt: Styled + Sized {
             .mr(margins.right.into())
     }
 
+    /// Set the border width snapped to the physical pixels of the window, see [`snap_stroke`].
+    fn border_snapped(mut self, width: Pixels, window: &Window) -> Self {
+        let width: AbsoluteLength = snap_stroke(width, window.scale_factor()).into();
+        let widths = &mut self.style().border_widths;
+        widths.top = Some(width);
+        widths.right = Some(width);
+        widths.bottom = Some(width);
+        widths.left = Some(width);
+        self
+    }
+
     /// Render a border with a width of 1px, color red
     fn debug_red(self) -> Self {
         if cfg!(debug_assertions) {
@@ -351,11 +410,22 @@ pub trait Selectable: Sized {
 }
 
 /// A trait for defining element that can be disabled.
+///
+/// The disabled element is not focusable and ignores all the user interactions.
 pub trait Disableable {
     /// Set the disabled state of the element.
     fn disabled(self, disabled: bool) -> Self;
 }
 
+/// A trait for defining form element that can be read-only.
+///
+/// Unlike [`Disableable`], the read-only element is still focusable, and the content
+/// can be selected and copied, but the value can't be changed by the user.
+pub trait ReadOnly {
+    /// Set the read-only state of the element.
+    fn read_only(self, read_only: bool) -> Self;
+}
+
 /// A trait for setting the size of an element.
 /// Size::Medium is use by default.
 pub trait Sizable: Sized {
@@ -589,6 +659,33 @@ impl Side {
     }
 }
 
+/// The horizontal layout direction, used to mirror the layouts for the RTL locales.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum LayoutDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl LayoutDirection {
+    /// Returns true if the direction is right to left.
+    #[inline]
+    pub fn is_rtl(&self) -> bool {
+        matches!(self, Self::RightToLeft)
+    }
+
+    /// Returns the direction of the window.
+    ///
+    /// This is the [`Root`](crate::Root) direction if set, otherwise the [`Theme`](crate::theme::Theme) direction.
+    pub fn of(window: &Window, cx: &App) -> Self {
+        window
+            .root::<crate::Root>()
+            .flatten()
+            .and_then(|root| root.read(cx).direction)
+            .unwrap_or(cx.theme().direction)
+    }
+}
+
 /// A trait for defining element that can be collapsed.
 pub trait Collapsible {
     fn collapsed(self, collapsed: bool) -> Self;
@@ -597,9 +694,33 @@ pub trait Collapsible {
 
 #[cfg(test)]
 mod tests {
-    use gpui::px;
+    use gpui::{point, px};
 
-    use crate::Size;
+    use crate::{snap_stroke, snap_to_pixels, Size};
+
+    use super::rect_from_points;
+
+    #[test]
+    fn test_snap_to_pixels() {
+        assert_eq!(snap_to_pixels(px(14.), 1.), px(14.));
+        assert_eq!(snap_to_pixels(px(14.), 2.), px(14.));
+        assert_eq!(snap_to_pixels(px(14.), 1.25), px(14.4));
+        assert_eq!(snap_to_pixels(px(10.2), 1.5), px(10.));
+
+        assert_eq!(snap_stroke(px(1.), 1.), px(1.));
+        assert_eq!(snap_stroke(px(1.), 1.5), px(4. / 3.));
+        assert_eq!(snap_stroke(px(2.), 1.25), px(2.4));
+        assert_eq!(snap_stroke(px(0.5), 1.), px(1.));
+        assert_eq!(snap_stroke(px(0.), 1.5), px(0.));
+    }
+
+    #[test]
+    fn test_rect_from_points() {
+        let rect = rect_from_points(point(px(10.), px(20.)), point(px(0.), px(5.)));
+        assert_eq!(rect.origin, point(px(0.), px(5.)));
+        assert_eq!(rect.size.width, px(10.));
+        assert_eq!(rect.size.height, px(15.));
+    }
 
     #[test]
     fn test_size_max_min() {
@@ -1,5 +1,9 @@
 use crate::{
-    h_flex, text::Text, tooltip::Tooltip, ActiveTheme, Disableable, Side, Sizable, Size, StyledExt,
+    accessibility::{self, text_label, AccessibilityProperties, AccessibilityRole, Accessible},
+    h_flex,
+    text::Text,
+    tooltip::Tooltip,
+    ActiveTheme, Disableable, ReadOnly, Side, Sizable, Size, StyledExt,
 };
 use gpui::{
     div, prelude::FluentBuilder as _, px, Animation, AnimationExt as _, App, ElementId,
@@ -15,11 +19,13 @@ pub struct Switch {
     style: StyleRefinement,
     checked: bool,
     disabled: bool,
+    read_only: bool,
     label: Option<Text>,
     label_side: Side,
     on_click: Option<Rc<dyn Fn(&bool, &mut Window, &mut App)>>,
     size: Size,
     tooltip: Option<SharedString>,
+    accessibility: AccessibilityProperties,
 }
 
 impl Switch {
@@ -30,11 +36,13 @@ impl Switch {
             style: StyleRefinement::default(),
             checked: false,
             disabled: false,
+            read_only: false,
             label: None,
             on_click: None,
             label_side: Side::Right,
             size: Size::Medium,
             tooltip: None,
+            accessibility: AccessibilityProperties::default(),
         }
     }
 
@@ -67,6 +75,21 @@ impl Switch {
     }
 }
 
+impl Accessible for Switch {
+    fn accessibility_mut(&mut self) -> &mut AccessibilityProperties {
+        &mut self.accessibility
+    }
+
+    fn accessibility(&self, _: &App) -> AccessibilityProperties {
+        AccessibilityProperties::new(AccessibilityRole::Switch)
+            .label(text_label(self.label.as_ref()).or_else(|| self.tooltip.clone()))
+            .checked(self.checked)
+            .disabled(self.disabled)
+            .read_only(self.read_only)
+            .merge(&self.accessibility)
+    }
+}
+
 impl Styled for Switch {
     fn style(&mut self) -> &mut gpui::StyleRefinement {
         &mut self.style
@@ -87,11 +110,22 @@ impl Disableable for Switch {
     }
 }
 
+impl ReadOnly for Switch {
+    /// Set the read-only state of the switch, the checked state can't be changed by the user.
+    fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+}
+
 impl RenderOnce for Switch {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        accessibility::record_rendered(self.accessibility(cx), window, cx);
+
         let checked = self.checked;
         let on_click = self.on_click.clone();
         let toggle_state = window.use_keyed_state(self.id.clone(), cx, |_, _| checked);
+        let reduce_motion = cx.theme().reduce_motion;
 
         let (bg, toggle_bg) = match checked {
             true => (cx.theme().primary, cx.theme().background),
@@ -170,6 +204,7 @@ impl RenderOnce for Switch {
                                             ElementId::NamedInteger("move".into(), checked as u64),
                                             Animation::new(duration),
                                             move |this, delta| {
+                                                let delta = if reduce_motion { 1. } else { delta };
                                                 let max_x = bg_width - bar_width - inset * 2;
                                                 let x = if checked {
                                                     max_x * delta
@@ -200,7 +235,7 @@ impl RenderOnce for Switch {
                     on_click
                         .as_ref()
                         .map(|c| c.clone())
-                        .filter(|_| !self.disabled),
+                        .filter(|_| !self.disabled && !self.read_only),
                     |this, on_click| {
                         let toggle_state = toggle_state.clone();
                         this.on_mouse_down(gpui::MouseButton::Left, move |_, window, cx| {
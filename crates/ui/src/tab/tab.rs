@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use crate::{h_flex, ActiveTheme, Icon, IconName, Selectable, Sizable, Size, StyledExt};
+use crate::{
+    accessibility::{self, AccessibilityProperties, AccessibilityRole, Accessible},
+    h_flex, ActiveTheme, Icon, IconName, Selectable, Sizable, Size, StyledExt,
+};
 use gpui::prelude::FluentBuilder as _;
 use gpui::{
     div, px, relative, AnyElement, App, ClickEvent, Div, Edges, ElementId, Hsla,
@@ -388,6 +391,7 @@ pub struct Tab {
     pub(super) disabled: bool,
     pub(super) selected: bool,
     on_click: Option<Arc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
+    accessibility: AccessibilityProperties,
 }
 
 impl From<&'static str> for Tab {
@@ -437,6 +441,7 @@ impl Default for Tab {
             variant: TabVariant::default(),
             size: Size::default(),
             on_click: None,
+            accessibility: AccessibilityProperties::default(),
         }
     }
 }
@@ -531,6 +536,20 @@ impl ParentElement for Tab {
     }
 }
 
+impl Accessible for Tab {
+    fn accessibility_mut(&mut self) -> &mut AccessibilityProperties {
+        &mut self.accessibility
+    }
+
+    fn accessibility(&self, _: &App) -> AccessibilityProperties {
+        AccessibilityProperties::new(AccessibilityRole::Tab)
+            .label(self.label.clone())
+            .selected(self.selected)
+            .disabled(self.disabled)
+            .merge(&self.accessibility)
+    }
+}
+
 impl Selectable for Tab {
     fn selected(mut self, selected: bool) -> Self {
         self.selected = selected;
@@ -564,7 +583,9 @@ impl Sizable for Tab {
 }
 
 impl RenderOnce for Tab {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        accessibility::record_rendered(self.accessibility(cx), window, cx);
+
         let mut tab_style = if self.selected {
             self.variant.selected(cx)
         } else {
@@ -2,12 +2,17 @@ use std::sync::Arc;
 
 use crate::button::{Button, ButtonVariants as _};
 use crate::popup_menu::PopupMenuExt as _;
-use crate::{h_flex, ActiveTheme, IconName, Selectable, Sizable, Size, StyledExt};
+use crate::{
+    accessibility::{self, AccessibilityProperties, AccessibilityRole, Accessible},
+    h_flex,
+    telemetry::{self, Interaction, InteractionKind},
+    ActiveTheme, IconName, RovingFocus, RovingFocusExt as _, Selectable, Sizable, Size, StyledExt,
+};
 use gpui::prelude::FluentBuilder as _;
 use gpui::{
-    div, Action, AnyElement, App, Corner, Div, Edges, ElementId, IntoElement, ParentElement,
-    Pixels, RenderOnce, ScrollHandle, Stateful, StatefulInteractiveElement as _, StyleRefinement,
-    Styled, Window,
+    div, Action, AnyElement, App, Corner, Div, Edges, ElementId, FocusHandle, IntoElement,
+    ParentElement, Pixels, RenderOnce, ScrollHandle, Stateful, StatefulInteractiveElement as _,
+    StyleRefinement, Styled, Window,
 };
 use gpui::{px, InteractiveElement};
 use smallvec::SmallVec;
@@ -30,9 +35,11 @@ pub struct TabBar {
     variant: TabVariant,
     size: Size,
     menu: bool,
+    focus_handle: Option<FocusHandle>,
     on_click: Option<Arc<dyn Fn(&usize, &mut Window, &mut App) + 'static>>,
     /// Special for internal TabPanel to remove the top border.
     tab_item_top_offset: Pixels,
+    accessibility: AccessibilityProperties,
 }
 
 impl TabBar {
@@ -49,8 +56,10 @@ impl TabBar {
             last_empty_space: div().w_3().into_any_element(),
             selected_index: None,
             on_click: None,
+            focus_handle: None,
             menu: false,
             tab_item_top_offset: px(0.),
+            accessibility: AccessibilityProperties::default(),
         }
     }
 
@@ -96,6 +105,15 @@ impl TabBar {
         self
     }
 
+    /// Set the focus handle of the TabBar.
+    ///
+    /// When set, the TabBar is a single Tab stop, and the arrow keys will select the tabs,
+    /// this requires the [`TabBar::on_click`] to be set.
+    pub fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
+
     /// Set the prefix element of the TabBar
     pub fn prefix(mut self, prefix: impl IntoElement) -> Self {
         self.prefix = Some(prefix.into_any_element());
@@ -137,7 +155,14 @@ impl TabBar {
     ///
     /// When this is set, the children's on_click will be ignored.
     pub fn on_click(mut self, on_click: impl Fn(&usize, &mut Window, &mut App) + 'static) -> Self {
-        self.on_click = Some(Arc::new(on_click));
+        let id = self.base.interactivity().element_id.clone();
+        self.on_click = Some(Arc::new(move |ix, window, cx| {
+            telemetry::record(cx, || {
+                Interaction::new(InteractionKind::TabSwitch, id.clone())
+                    .metadata("index", ix.to_string())
+            });
+            on_click(ix, window, cx);
+        }));
         self
     }
 
@@ -147,6 +172,21 @@ impl TabBar {
     }
 }
 
+impl Accessible for TabBar {
+    fn accessibility_mut(&mut self) -> &mut AccessibilityProperties {
+        &mut self.accessibility
+    }
+
+    fn accessibility(&self, _: &App) -> AccessibilityProperties {
+        let mut properties = AccessibilityProperties::new(AccessibilityRole::TabList);
+        if let Some(tab) = self.selected_index.and_then(|ix| self.children.get(ix)) {
+            properties.value = tab.label.clone();
+        }
+
+        properties.merge(&self.accessibility)
+    }
+}
+
 impl Styled for TabBar {
     fn style(&mut self) -> &mut StyleRefinement {
         self.base.style()
@@ -161,7 +201,9 @@ impl Sizable for TabBar {
 }
 
 impl RenderOnce for TabBar {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        accessibility::record_rendered(self.accessibility(cx), window, cx);
+
         let default_gap = match self.size {
             Size::Small | Size::XSmall => px(8.),
             Size::Large => px(16.),
@@ -215,9 +257,27 @@ impl RenderOnce for TabBar {
 
         let mut item_labels = Vec::new();
         let selected_index = self.selected_index;
+        let roving_focus =
+            self.focus_handle
+                .zip(self.on_click.clone())
+                .map(|(focus_handle, on_click)| {
+                    let disabled_ixs = self
+                        .children
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, tab)| tab.disabled)
+                        .map(|(ix, _)| ix);
+
+                    RovingFocus::new(&focus_handle, self.children.len(), move |ix, window, cx| {
+                        on_click(ix, window, cx)
+                    })
+                    .active_ix(selected_index)
+                    .disabled(disabled_ixs)
+                });
 
         self.base
             .group("tab-bar")
+            .when_some(roving_focus, |this, roving| this.roving_focus(roving))
             .on_action({
                 let on_click = self.on_click.clone();
                 move |action: &SelectTab, window: &mut Window, cx: &mut App| {
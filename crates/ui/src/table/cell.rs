@@ -0,0 +1,115 @@
+use std::ops::Range;
+
+use serde::Deserialize;
+
+/// The movement of the active cell, see [`MoveCell`](super::MoveCell).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Movement {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// The first visible column of the row.
+    RowStart,
+    /// The last visible column of the row.
+    RowEnd,
+    PageUp,
+    PageDown,
+}
+
+/// The selected cells of the table, the range from the `anchor` to the `active` cell in the
+/// display order, the cells are the (`row_ix`, `col_ix`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CellSelection {
+    pub(crate) active: Option<(usize, usize)>,
+    /// The start of the range, the same as the `active` cell if not extended.
+    pub(crate) anchor: Option<(usize, usize)>,
+}
+
+impl CellSelection {
+    /// Select the cell, or extend the range from the anchor to the cell if `extend`.
+    pub(crate) fn select(&mut self, cell: (usize, usize), extend: bool) {
+        if !extend || self.anchor.is_none() {
+            self.anchor = Some(cell);
+        }
+        self.active = Some(cell);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.active = None;
+        self.anchor = None;
+    }
+}
+
+/// Returns the position (row, column) moved from the `pos` by the `movement`, the positions are
+/// the indices in the navigable rows and the visible columns.
+///
+/// The position is clamped to the `rows` and `cols` counts, and the `page` is the number of the rows
+/// to move by the page up and down.
+pub(super) fn move_position(
+    pos: (usize, usize),
+    movement: Movement,
+    rows: usize,
+    cols: usize,
+    page: usize,
+) -> (usize, usize) {
+    let (row, col) = pos;
+    let last_row = rows.saturating_sub(1);
+    let last_col = cols.saturating_sub(1);
+    let page = page.max(1);
+
+    let (row, col) = match movement {
+        Movement::Up => (row.saturating_sub(1), col),
+        Movement::Down => (row + 1, col),
+        Movement::Left => (row, col.saturating_sub(1)),
+        Movement::Right => (row, col + 1),
+        Movement::RowStart => (row, 0),
+        Movement::RowEnd => (row, last_col),
+        Movement::PageUp => (row.saturating_sub(page), col),
+        Movement::PageDown => (row + page, col),
+    };
+    (row.min(last_row), col.min(last_col))
+}
+
+/// Returns the range of the positions between the `a` and the `b`, inclusive.
+pub(super) fn span(a: usize, b: usize) -> Range<usize> {
+    a.min(b)..a.max(b) + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{move_position, span, CellSelection, Movement};
+
+    #[test]
+    fn test_move_position() {
+        assert_eq!(move_position((0, 0), Movement::Up, 10, 5, 4), (0, 0));
+        assert_eq!(move_position((0, 0), Movement::Down, 10, 5, 4), (1, 0));
+        assert_eq!(move_position((9, 4), Movement::Down, 10, 5, 4), (9, 4));
+        assert_eq!(move_position((3, 0), Movement::Left, 10, 5, 4), (3, 0));
+        assert_eq!(move_position((3, 4), Movement::Right, 10, 5, 4), (3, 4));
+        assert_eq!(move_position((3, 2), Movement::RowStart, 10, 5, 4), (3, 0));
+        assert_eq!(move_position((3, 2), Movement::RowEnd, 10, 5, 4), (3, 4));
+        assert_eq!(move_position((3, 2), Movement::PageUp, 10, 5, 4), (0, 2));
+        assert_eq!(move_position((3, 2), Movement::PageDown, 10, 5, 4), (7, 2));
+        assert_eq!(move_position((7, 2), Movement::PageDown, 10, 5, 4), (9, 2));
+        // The empty page moves by one row.
+        assert_eq!(move_position((3, 2), Movement::PageDown, 10, 5, 0), (4, 2));
+    }
+
+    #[test]
+    fn test_cell_selection() {
+        let mut selection = CellSelection::default();
+        selection.select((2, 1), true);
+        assert_eq!(selection.anchor, Some((2, 1)));
+
+        selection.select((4, 3), true);
+        assert_eq!(selection.anchor, Some((2, 1)));
+        assert_eq!(selection.active, Some((4, 3)));
+
+        selection.select((0, 0), false);
+        assert_eq!(selection.anchor, Some((0, 0)));
+
+        assert_eq!(span(4, 2), 2..5);
+        assert_eq!(span(1, 1), 1..2);
+    }
+}
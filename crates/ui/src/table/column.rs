@@ -1,10 +1,14 @@
+use std::rc::Rc;
+
 use gpui::{
-    div, prelude::FluentBuilder, px, Bounds, Context, Edges, Empty, EntityId, IntoElement,
-    ParentElement as _, Pixels, Render, SharedString, Styled as _, TextAlign, Window,
+    div, prelude::FluentBuilder, px, AnyElement, App, Bounds, Context, Edges, Empty, EntityId,
+    IntoElement, ParentElement as _, Pixels, Render, SharedString, Styled as _, TextAlign, Window,
 };
 
 use crate::ActiveTheme as _;
 
+use super::{Aggregate, CellValue, ColumnFormat};
+
 /// Represents a column in a table, used for initializing table columns.
 #[derive(Debug, Clone)]
 pub struct Column {
@@ -18,6 +22,12 @@ pub struct Column {
     pub resizable: bool,
     pub movable: bool,
     pub selectable: bool,
+    /// The format to render the cells, see [`TableDelegate::cell_value`](super::TableDelegate::cell_value).
+    pub format: ColumnFormat,
+    /// The aggregate to show in the group rows, see [`Table::set_group_by`](super::Table::set_group_by).
+    pub aggregate: Option<Aggregate>,
+    /// Whether the column is hidden by default, it can be shown in the column chooser.
+    pub hidden: bool,
 }
 
 impl Default for Column {
@@ -33,6 +43,9 @@ impl Default for Column {
             resizable: true,
             movable: true,
             selectable: true,
+            format: ColumnFormat::default(),
+            aggregate: None,
+            hidden: false,
         }
     }
 }
@@ -129,6 +142,38 @@ impl Column {
         self.selectable = selectable;
         self
     }
+
+    /// Set the format to render the cells, default is [`ColumnFormat::Text`].
+    ///
+    /// Only used if [`TableDelegate::render_td`](super::TableDelegate::render_td) is not implemented.
+    pub fn format(mut self, format: ColumnFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the closure to render the cells with the row index and the cell value.
+    ///
+    /// This is a shortcut of [`ColumnFormat::Custom`].
+    pub fn render(
+        mut self,
+        render: impl Fn(usize, &CellValue, &mut Window, &mut App) -> AnyElement + 'static,
+    ) -> Self {
+        self.format = ColumnFormat::Custom(Rc::new(render));
+        self
+    }
+
+    /// Set the aggregate of the [`CellValue::Number`] values to show in the group rows,
+    /// default is None.
+    pub fn aggregate(mut self, aggregate: Aggregate) -> Self {
+        self.aggregate = Some(aggregate);
+        self
+    }
+
+    /// Set whether the column is hidden by default, default is false.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
 }
 
 impl FluentBuilder for Column {}
@@ -148,6 +193,8 @@ pub(crate) struct ColGroup {
     pub(crate) width: Pixels,
     /// The bounds of the column in the table after it renders.
     pub(crate) bounds: Bounds<Pixels>,
+    /// Whether the column is visible, changed by the column chooser.
+    pub(crate) visible: bool,
 }
 
 impl ColGroup {
@@ -1,14 +1,14 @@
 use std::ops::Range;
 
 use gpui::{
-    div, App, Context, Div, InteractiveElement as _, IntoElement, ParentElement as _, Stateful,
-    Styled as _, Window,
+    div, App, Context, Div, Empty, InteractiveElement as _, IntoElement, ParentElement as _,
+    Pixels, Stateful, Styled as _, Window,
 };
 
 use crate::{
     h_flex,
     popup_menu::PopupMenu,
-    table::{loading::Loading, Column, ColumnSort, Table},
+    table::{loading::Loading, CellValue, Column, ColumnSort, Table},
     ActiveTheme as _, Icon, IconName, Size,
 };
 
@@ -61,14 +61,68 @@ pub trait TableDelegate: Sized + 'static {
         menu
     }
 
-    /// Render cell at the given row and column.
+    /// Returns the value of the cell at the given row and column, default is empty.
+    ///
+    /// The value is rendered by the [`Column::format`] in the default [`TableDelegate::render_td`].
+    fn cell_value(&self, row_ix: usize, col_ix: usize, cx: &App) -> CellValue {
+        CellValue::Empty
+    }
+
+    /// Render cell at the given row and column, default to render the
+    /// [`TableDelegate::cell_value`] by the [`Column::format`].
     fn render_td(
         &self,
         row_ix: usize,
         col_ix: usize,
         window: &mut Window,
         cx: &mut Context<Table<Self>>,
-    ) -> impl IntoElement;
+    ) -> impl IntoElement {
+        let value = self.cell_value(row_ix, col_ix, cx);
+        let format = self.column(col_ix, cx).format.clone();
+        format.render(row_ix, &value, window, cx)
+    }
+
+    /// Returns the height of the detail of the row, `None` if the row can not be expanded,
+    /// default is None.
+    ///
+    /// The rows are expanded by the expander column, see [`Table::row_expandable`].
+    fn row_detail_height(&self, row_ix: usize, cx: &App) -> Option<Pixels> {
+        None
+    }
+
+    /// Render the detail of the expanded row beneath the row, spanning all the columns.
+    ///
+    /// This is only called for the expanded rows in the view.
+    fn render_row_detail(
+        &self,
+        row_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Table<Self>>,
+    ) -> impl IntoElement {
+        Empty
+    }
+
+    /// Returns the parent row of the row in the tree mode, default is None (the top level).
+    ///
+    /// See [`Table::tree`].
+    fn row_parent(&self, row_ix: usize, cx: &App) -> Option<usize> {
+        None
+    }
+
+    /// Return true if the row has the children to load by the [`TableDelegate::load_children`],
+    /// default is false.
+    ///
+    /// The rows with the loaded children (by the [`TableDelegate::row_parent`]) are always expandable.
+    fn row_has_children(&self, row_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Load the children of the row when it is expanded the first time in the tree mode.
+    ///
+    /// The children are appended to the rows with the [`TableDelegate::row_parent`], the table is
+    /// updated when the rows count is changed, so this can load them in the background task.
+    fn load_children(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Table<Self>>) {
+    }
 
     /// Move the column at the given `col_ix` to insert before the column at the given `to_ix`.
     fn move_column(
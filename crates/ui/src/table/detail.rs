@@ -0,0 +1,146 @@
+use std::collections::BTreeMap;
+
+use gpui::{App, Window};
+
+use crate::animation::{Spring, SpringValue};
+
+use super::group::DisplayRow;
+
+/// The detail of an expanded row.
+struct RowDetail {
+    /// The number of the list items taken by the detail.
+    items: usize,
+    /// The progress of the expand animation, from 0 to 1.
+    progress: SpringValue,
+}
+
+/// The expanded rows of the table, see [`TableDelegate::render_row_detail`](super::TableDelegate::render_row_detail).
+///
+/// The detail takes the placeholder items after the row in the list, because all the items
+/// of the list have the same height.
+#[derive(Default)]
+pub(crate) struct RowDetails {
+    expanded: BTreeMap<usize, RowDetail>,
+    /// The number of the delegate rows when the details were updated.
+    pub(crate) rows_count: usize,
+}
+
+impl RowDetails {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.expanded.is_empty()
+    }
+
+    pub(crate) fn is_expanded(&self, row_ix: usize) -> bool {
+        self.expanded.contains_key(&row_ix)
+    }
+
+    /// Returns the number of the list items taken by the detail of the row.
+    pub(crate) fn items(&self, row_ix: usize) -> Option<usize> {
+        self.expanded.get(&row_ix).map(|detail| detail.items)
+    }
+
+    /// Expand the row with the detail taking the `items`, the detail is animated to expand
+    /// unless the `reduce_motion`.
+    pub(crate) fn expand(&mut self, row_ix: usize, items: usize, reduce_motion: bool) {
+        if self.is_expanded(row_ix) {
+            return;
+        }
+
+        let mut progress = SpringValue::new(Spring::new(0.).stiffness(300.).damping(35.));
+        if reduce_motion {
+            progress.spring_mut().jump_to(1.);
+        } else {
+            progress.set_target(1.);
+        }
+        self.expanded.insert(
+            row_ix,
+            RowDetail {
+                items: items.max(1),
+                progress,
+            },
+        );
+    }
+
+    /// Collapse the row, returns false if the row is not expanded.
+    pub(crate) fn collapse(&mut self, row_ix: usize) -> bool {
+        self.expanded.remove(&row_ix).is_some()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.expanded.clear();
+    }
+
+    /// Collapse the rows out of the `rows_count`, e.g.: the rows are removed.
+    pub(crate) fn retain(&mut self, rows_count: usize) {
+        self.expanded.retain(|row_ix, _| *row_ix < rows_count);
+        self.rows_count = rows_count;
+    }
+
+    /// Returns the progress of the expand animation of the row in the current frame.
+    pub(crate) fn progress(&mut self, row_ix: usize, window: &mut Window, cx: &App) -> f32 {
+        self.expanded
+            .get_mut(&row_ix)
+            .map_or(1., |detail| detail.progress.value(window, cx).clamp(0., 1.))
+    }
+
+    /// Returns the `rows` with the detail items after the expanded rows.
+    pub(crate) fn insert_into(
+        &self,
+        rows: impl IntoIterator<Item = DisplayRow>,
+    ) -> Vec<DisplayRow> {
+        let mut out = vec![];
+        for row in rows {
+            out.push(row);
+            if let DisplayRow::Row(row_ix) = row {
+                if let Some(items) = self.items(row_ix) {
+                    out.extend(std::iter::repeat_n(DisplayRow::Detail(row_ix), items));
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DisplayRow, RowDetails};
+
+    #[test]
+    fn test_row_details() {
+        let mut details = RowDetails::default();
+        assert!(details.is_empty());
+
+        details.expand(1, 2, true);
+        details.expand(3, 0, true);
+        assert!(details.is_expanded(1));
+        assert_eq!(details.items(3), Some(1));
+        assert_eq!(
+            details.insert_into((0..4).map(DisplayRow::Row)),
+            vec![
+                DisplayRow::Row(0),
+                DisplayRow::Row(1),
+                DisplayRow::Detail(1),
+                DisplayRow::Detail(1),
+                DisplayRow::Row(2),
+                DisplayRow::Row(3),
+                DisplayRow::Detail(3),
+            ]
+        );
+
+        // The group rows are kept.
+        assert_eq!(
+            details.insert_into([DisplayRow::Group(0), DisplayRow::Row(3)]),
+            vec![
+                DisplayRow::Group(0),
+                DisplayRow::Row(3),
+                DisplayRow::Detail(3)
+            ]
+        );
+
+        assert!(details.collapse(1));
+        assert!(!details.collapse(1));
+        details.retain(3);
+        assert!(details.is_empty());
+        assert_eq!(details.rows_count, 3);
+    }
+}
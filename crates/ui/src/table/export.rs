@@ -0,0 +1,71 @@
+use std::borrow::Cow;
+
+/// Returns the field of the CSV, quoted if it has the delimiter, the quote or the line break.
+fn csv_field(text: &str) -> Cow<'_, str> {
+    if text.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Returns the field of the TSV, the tabs and the line breaks are replaced by spaces to keep
+/// the cells in place when pasting into the spreadsheets.
+fn tsv_field(text: &str) -> Cow<'_, str> {
+    if text.contains(['\t', '\n', '\r']) {
+        Cow::Owned(text.replace("\r\n", " ").replace(['\t', '\n', '\r'], " "))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Append a line of the CSV (RFC 4180) fields to the `out`.
+pub(super) fn push_csv_line<S: AsRef<str>>(out: &mut String, fields: impl IntoIterator<Item = S>) {
+    push_line(out, fields, ',', "\r\n", csv_field);
+}
+
+/// Append a line of the TSV fields to the `out`.
+pub(super) fn push_tsv_line<S: AsRef<str>>(out: &mut String, fields: impl IntoIterator<Item = S>) {
+    push_line(out, fields, '\t', "\n", tsv_field);
+}
+
+fn push_line<S: AsRef<str>>(
+    out: &mut String,
+    fields: impl IntoIterator<Item = S>,
+    delimiter: char,
+    line_end: &str,
+    field: impl Fn(&str) -> Cow<'_, str>,
+) {
+    for (ix, text) in fields.into_iter().enumerate() {
+        if ix > 0 {
+            out.push(delimiter);
+        }
+        out.push_str(&field(text.as_ref()));
+    }
+    out.push_str(line_end);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{push_csv_line, push_tsv_line};
+
+    #[test]
+    fn test_push_csv_line() {
+        let mut out = String::new();
+        push_csv_line(&mut out, ["Name", "Note"]);
+        push_csv_line(&mut out, ["Alice", "says \"hi\", twice"]);
+        push_csv_line(&mut out, ["Bob", "line 1\nline 2"]);
+        push_csv_line(&mut out, ["", ""]);
+        assert_eq!(
+            out,
+            "Name,Note\r\nAlice,\"says \"\"hi\"\", twice\"\r\nBob,\"line 1\nline 2\"\r\n,\r\n"
+        );
+    }
+
+    #[test]
+    fn test_push_tsv_line() {
+        let mut out = String::new();
+        push_tsv_line(&mut out, ["a\tb", "c\r\nd", "\"e\""]);
+        assert_eq!(out, "a b\tc d\t\"e\"\n");
+    }
+}
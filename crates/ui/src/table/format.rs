@@ -0,0 +1,272 @@
+use std::{fmt::Debug, rc::Rc};
+
+use chrono::{NaiveDate, NaiveDateTime};
+use gpui::{
+    canvas, div, AnyElement, App, IntoElement, ParentElement as _, SharedString, Styled as _,
+    Window,
+};
+
+use crate::{
+    locale::{format_date, format_datetime, format_number},
+    plot::{
+        scale::{Scale as _, ScaleLinear},
+        shape::Line,
+    },
+    tag::Tag,
+    ActiveTheme as _, Sizable as _,
+};
+
+/// The value of a table cell, returned by [`TableDelegate::cell_value`](super::TableDelegate::cell_value)
+/// to render the cell by the [`ColumnFormat`] of the column.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum CellValue {
+    #[default]
+    Empty,
+    Text(SharedString),
+    Number(f64),
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+    /// A series of numbers, e.g.: for the [`ColumnFormat::Sparkline`].
+    Series(Vec<f64>),
+}
+
+impl From<&'static str> for CellValue {
+    fn from(value: &'static str) -> Self {
+        Self::Text(value.into())
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(value: String) -> Self {
+        Self::Text(value.into())
+    }
+}
+
+impl From<SharedString> for CellValue {
+    fn from(value: SharedString) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<f64> for CellValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<i64> for CellValue {
+    fn from(value: i64) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<u64> for CellValue {
+    fn from(value: u64) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<NaiveDate> for CellValue {
+    fn from(value: NaiveDate) -> Self {
+        Self::Date(value)
+    }
+}
+
+impl From<NaiveDateTime> for CellValue {
+    fn from(value: NaiveDateTime) -> Self {
+        Self::DateTime(value)
+    }
+}
+
+impl From<Vec<f64>> for CellValue {
+    fn from(value: Vec<f64>) -> Self {
+        Self::Series(value)
+    }
+}
+
+impl<T: Into<CellValue>> From<Option<T>> for CellValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Empty, Into::into)
+    }
+}
+
+type CellRenderer = Rc<dyn Fn(usize, &CellValue, &mut Window, &mut App) -> AnyElement>;
+
+/// The format to render the [`CellValue`] of a column, set by [`Column::format`](super::Column::format).
+#[derive(Clone, Default)]
+pub enum ColumnFormat {
+    /// Display the value as is, the numbers and the dates are formatted by the current locale.
+    #[default]
+    Text,
+    /// Display the number with the group separators and the `precision` decimal places.
+    Number { precision: usize },
+    /// Display the date (and time) by the current locale.
+    Date,
+    /// Display the number of bytes in the human readable size, e.g.: `1.5 MB`.
+    Bytes,
+    /// Display the ratio (`0.0` to `1.0`) in percentage with the `precision` decimal places.
+    Percentage { precision: usize },
+    /// Display the text in a [`Tag`].
+    Badge,
+    /// Display the [`CellValue::Series`] in a line chart.
+    Sparkline,
+    /// Render the cell by the closure with the row index and the value.
+    Custom(CellRenderer),
+}
+
+impl Debug for ColumnFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text => write!(f, "Text"),
+            Self::Number { precision } => write!(f, "Number({})", precision),
+            Self::Date => write!(f, "Date"),
+            Self::Bytes => write!(f, "Bytes"),
+            Self::Percentage { precision } => write!(f, "Percentage({})", precision),
+            Self::Badge => write!(f, "Badge"),
+            Self::Sparkline => write!(f, "Sparkline"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+const BYTE_UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+/// Returns the size in the largest unit (base 1024) that the size is at least 1, and the unit.
+fn bytes_unit(bytes: f64) -> (f64, &'static str) {
+    let mut size = bytes;
+    let mut unit = 0;
+    while size.abs() >= 1024. && unit < BYTE_UNITS.len() - 1 {
+        size /= 1024.;
+        unit += 1;
+    }
+    (size, BYTE_UNITS[unit])
+}
+
+/// Format the `value` by the current locale, with the decimal places of the shortest representation.
+fn format_plain_number(value: f64) -> SharedString {
+    let precision = value
+        .to_string()
+        .split_once('.')
+        .map_or(0, |(_, frac)| frac.len());
+    format_number(value, precision)
+}
+
+impl ColumnFormat {
+    /// Returns the text of the `value` in this format.
+    pub fn format(&self, value: &CellValue) -> SharedString {
+        match (self, value) {
+            (_, CellValue::Empty) => SharedString::default(),
+            (Self::Number { precision }, CellValue::Number(value)) => {
+                format_number(*value, *precision)
+            }
+            (Self::Bytes, CellValue::Number(value)) => match bytes_unit(*value) {
+                (size, "B") => format!("{} B", format_number(size, 0)).into(),
+                (size, unit) => format!("{} {}", format_number(size, 1), unit).into(),
+            },
+            (Self::Percentage { precision }, CellValue::Number(value)) => {
+                format!("{}%", format_number(value * 100., *precision)).into()
+            }
+            (_, CellValue::Text(text)) => text.clone(),
+            (_, CellValue::Number(value)) => format_plain_number(*value),
+            (_, CellValue::Date(date)) => format_date(date),
+            (_, CellValue::DateTime(datetime)) => format_datetime(datetime),
+            (_, CellValue::Series(values)) => values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+                .into(),
+        }
+    }
+
+    /// Render the `value` of the cell at the `row_ix` in this format.
+    pub(crate) fn render(
+        &self,
+        row_ix: usize,
+        value: &CellValue,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> AnyElement {
+        match self {
+            Self::Custom(render) => render(row_ix, value, window, cx),
+            Self::Badge => {
+                let text = self.format(value);
+                if text.is_empty() {
+                    return div().into_any_element();
+                }
+                Tag::secondary().small().child(text).into_any_element()
+            }
+            Self::Sparkline => {
+                let CellValue::Series(values) = value else {
+                    return div().into_any_element();
+                };
+                let values = values.clone();
+                let stroke = cx.theme().chart_2;
+
+                canvas(
+                    |_, _, _| {},
+                    move |bounds, _, window, _| {
+                        let width = bounds.size.width.0;
+                        let height = bounds.size.height.0;
+                        let y = ScaleLinear::new(values.clone(), vec![2., height - 2.]);
+                        let step = width / values.len().saturating_sub(1).max(1) as f32;
+                        // The scale has no ticks for the constant series, draw a midline.
+                        let is_flat = values.windows(2).all(|pair| pair[0] == pair[1]);
+
+                        Line::new()
+                            .data(values.iter().copied().enumerate())
+                            .x(move |(ix, _)| Some(*ix as f32 * step))
+                            .y(move |(_, value)| {
+                                if is_flat {
+                                    Some(height / 2.)
+                                } else {
+                                    y.tick(value)
+                                }
+                            })
+                            .stroke(stroke)
+                            .stroke_width(1.5)
+                            .paint(&bounds, window);
+                    },
+                )
+                .w_full()
+                .h_5()
+                .into_any_element()
+            }
+            _ => div().child(self.format(value)).into_any_element(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bytes_unit, CellValue, ColumnFormat};
+
+    #[test]
+    fn test_bytes_unit() {
+        assert_eq!(bytes_unit(0.), (0., "B"));
+        assert_eq!(bytes_unit(1023.), (1023., "B"));
+        assert_eq!(bytes_unit(1536.), (1.5, "KB"));
+        assert_eq!(bytes_unit(5. * 1024. * 1024.), (5., "MB"));
+        assert_eq!(bytes_unit(2048. * 1024f64.powi(4)), (2048., "TB"));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(ColumnFormat::Text.format(&CellValue::Empty), "");
+        assert_eq!(ColumnFormat::Text.format(&1234.5.into()), "1,234.5");
+        assert_eq!(ColumnFormat::Text.format(&42u64.into()), "42");
+        assert_eq!(ColumnFormat::Badge.format(&"Active".into()), "Active");
+        assert_eq!(
+            ColumnFormat::Number { precision: 2 }.format(&1234.5.into()),
+            "1,234.50"
+        );
+        assert_eq!(ColumnFormat::Bytes.format(&512u64.into()), "512 B");
+        assert_eq!(ColumnFormat::Bytes.format(&1536u64.into()), "1.5 KB");
+        assert_eq!(
+            ColumnFormat::Percentage { precision: 1 }.format(&0.256.into()),
+            "25.6%"
+        );
+        // The format is ignored if it does not match the value.
+        assert_eq!(ColumnFormat::Bytes.format(&CellValue::from("n/a")), "n/a");
+    }
+}
@@ -0,0 +1,404 @@
+use std::collections::{HashMap, HashSet};
+
+use gpui::SharedString;
+
+/// The aggregate of the column values in a group of rows, set by [`Column::aggregate`](super::Column::aggregate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    /// The number of the rows.
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    /// Returns the aggregate of the number `values` of the `count` rows, `None` if there is no
+    /// number (except for the [`Aggregate::Count`]).
+    pub(super) fn apply(&self, values: &[f64], count: usize) -> Option<f64> {
+        if *self != Self::Count && values.is_empty() {
+            return None;
+        }
+
+        Some(match self {
+            Self::Count => count as f64,
+            Self::Sum => values.iter().sum(),
+            Self::Avg => values.iter().sum::<f64>() / values.len() as f64,
+            Self::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            Self::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+}
+
+/// A group of the rows with the same values of the grouped columns.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RowGroup {
+    /// The keys of the parent groups and this group, from the top level.
+    pub(crate) path: Vec<SharedString>,
+    /// The rows in this group (including the sub groups).
+    pub(crate) rows: Vec<usize>,
+    /// The aggregates of each column.
+    pub(crate) aggregates: Vec<Option<f64>>,
+}
+
+impl RowGroup {
+    pub(crate) fn key(&self) -> &SharedString {
+        self.path.last().expect("BUG: empty group path")
+    }
+
+    /// Returns the level of the group, 0 is the top level.
+    pub(crate) fn level(&self) -> usize {
+        self.path.len() - 1
+    }
+}
+
+/// A row in the display order of the grouped table or the table with the expanded rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DisplayRow {
+    /// The header row of the group at the index.
+    Group(usize),
+    /// The row of the delegate at the index.
+    Row(usize),
+    /// The placeholder of the detail of the expanded row at the index.
+    Detail(usize),
+}
+
+/// Returns the indices of the delegate rows in the display `rows`, the rows not displayed
+/// (e.g.: in a collapsed group) are not included.
+pub(crate) fn display_ixs(rows: &[DisplayRow]) -> HashMap<usize, usize> {
+    rows.iter()
+        .enumerate()
+        .filter_map(|(ix, row)| match row {
+            DisplayRow::Row(row_ix) => Some((*row_ix, ix)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The groups of the rows by the columns.
+#[derive(Debug, Default)]
+pub(crate) struct RowGroups {
+    /// The column indices to group by, the first is the top level.
+    group_by: Vec<usize>,
+    /// The groups in the display order, the sub groups follow the parent group.
+    pub(crate) groups: Vec<RowGroup>,
+    /// The visible rows in the display order.
+    pub(crate) rows: Vec<DisplayRow>,
+    /// The paths of the collapsed groups.
+    collapsed: HashSet<Vec<SharedString>>,
+    /// The number of the delegate rows when the groups were built.
+    pub(crate) rows_count: usize,
+}
+
+impl RowGroups {
+    /// Returns true if the rows are not grouped.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.group_by.is_empty()
+    }
+
+    pub(crate) fn group_by(&self) -> &[usize] {
+        &self.group_by
+    }
+
+    /// Set the column indices to group by, the collapsed groups are reset.
+    pub(crate) fn set_group_by(&mut self, group_by: Vec<usize>) {
+        self.group_by = group_by;
+        self.collapsed.clear();
+        self.groups.clear();
+        self.rows.clear();
+    }
+
+    /// Update the grouped column indices after the column at `col_ix` is moved to `to_ix`.
+    pub(crate) fn move_column(&mut self, col_ix: usize, to_ix: usize) {
+        for ix in self.group_by.iter_mut() {
+            if *ix == col_ix {
+                *ix = to_ix;
+            } else {
+                let moved_ix = if *ix > col_ix { *ix - 1 } else { *ix };
+                *ix = if moved_ix >= to_ix {
+                    moved_ix + 1
+                } else {
+                    moved_ix
+                };
+            }
+        }
+    }
+
+    /// Build the groups of the `rows_count` rows.
+    ///
+    /// The `key` returns the group key of the cell at the row and column, the `value` returns
+    /// the number of the cell to aggregate by the `aggregates` of the columns. The groups are
+    /// ordered by their first rows, to keep the order (e.g.: sorted) of the rows.
+    pub(crate) fn build(
+        &mut self,
+        rows_count: usize,
+        key: impl Fn(usize, usize) -> SharedString,
+        value: impl Fn(usize, usize) -> Option<f64>,
+        aggregates: &[Option<Aggregate>],
+    ) {
+        self.groups.clear();
+        self.rows_count = rows_count;
+        if !self.is_empty() {
+            self.build_level((0..rows_count).collect(), vec![], &key, &value, aggregates);
+        }
+        self.update_rows();
+    }
+
+    fn build_level(
+        &mut self,
+        rows: Vec<usize>,
+        parent: Vec<SharedString>,
+        key: &impl Fn(usize, usize) -> SharedString,
+        value: &impl Fn(usize, usize) -> Option<f64>,
+        aggregates: &[Option<Aggregate>],
+    ) {
+        let level = parent.len();
+        let col_ix = self.group_by[level];
+
+        let mut groups: Vec<(SharedString, Vec<usize>)> = vec![];
+        let mut group_ixs: HashMap<SharedString, usize> = HashMap::new();
+        for row_ix in rows {
+            let key = key(row_ix, col_ix);
+            match group_ixs.get(&key) {
+                Some(ix) => groups[*ix].1.push(row_ix),
+                None => {
+                    group_ixs.insert(key.clone(), groups.len());
+                    groups.push((key, vec![row_ix]));
+                }
+            }
+        }
+
+        for (key, rows) in groups {
+            let mut path = parent.clone();
+            path.push(key);
+
+            let values = aggregates
+                .iter()
+                .enumerate()
+                .map(|(col_ix, aggregate)| {
+                    let aggregate = (*aggregate)?;
+                    let values = rows
+                        .iter()
+                        .filter_map(|row_ix| value(*row_ix, col_ix))
+                        .collect::<Vec<_>>();
+                    aggregate.apply(&values, rows.len())
+                })
+                .collect();
+
+            self.groups.push(RowGroup {
+                path: path.clone(),
+                rows: rows.clone(),
+                aggregates: values,
+            });
+            if level + 1 < self.group_by.len() {
+                self.build_level(rows, path, key, value, aggregates);
+            }
+        }
+    }
+
+    /// Update the visible rows by the collapsed groups.
+    fn update_rows(&mut self) {
+        self.rows.clear();
+        let leaf_level = self.group_by.len().saturating_sub(1);
+        for (group_ix, group) in self.groups.iter().enumerate() {
+            // Hidden if any parent is collapsed.
+            if (1..group.path.len()).any(|len| self.collapsed.contains(&group.path[..len])) {
+                continue;
+            }
+
+            self.rows.push(DisplayRow::Group(group_ix));
+            if group.level() == leaf_level && !self.collapsed.contains(&group.path) {
+                self.rows
+                    .extend(group.rows.iter().map(|row_ix| DisplayRow::Row(*row_ix)));
+            }
+        }
+    }
+
+    pub(crate) fn is_collapsed(&self, group_ix: usize) -> bool {
+        self.groups
+            .get(group_ix)
+            .is_some_and(|group| self.collapsed.contains(&group.path))
+    }
+
+    /// Toggle the group at the index to collapse or expand.
+    pub(crate) fn toggle(&mut self, group_ix: usize) {
+        let Some(group) = self.groups.get(group_ix) else {
+            return;
+        };
+
+        if !self.collapsed.remove(&group.path) {
+            self.collapsed.insert(group.path.clone());
+        }
+        self.update_rows();
+    }
+
+    pub(crate) fn expand_all(&mut self) {
+        self.collapsed.clear();
+        self.update_rows();
+    }
+
+    pub(crate) fn collapse_all(&mut self) {
+        self.collapsed = self.groups.iter().map(|group| group.path.clone()).collect();
+        self.update_rows();
+    }
+}
+
+/// Returns the next (or the previous if `forward` is false) delegate row of the `row_ix` in the
+/// display `rows`.
+pub(crate) fn next_row(
+    rows: &[DisplayRow],
+    row_ix: Option<usize>,
+    forward: bool,
+    loop_selection: bool,
+) -> Option<usize> {
+    let rows = rows
+        .iter()
+        .filter_map(|row| match row {
+            DisplayRow::Row(row_ix) => Some(*row_ix),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let Some(ix) = row_ix.and_then(|row_ix| rows.iter().position(|r| *r == row_ix)) else {
+        return rows.first().copied();
+    };
+
+    let ix = if forward {
+        if ix + 1 < rows.len() {
+            ix + 1
+        } else if loop_selection {
+            0
+        } else {
+            ix
+        }
+    } else if ix > 0 {
+        ix - 1
+    } else if loop_selection {
+        rows.len() - 1
+    } else {
+        ix
+    };
+    rows.get(ix).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::SharedString;
+
+    use super::{display_ixs, next_row, Aggregate, DisplayRow, RowGroups};
+
+    /// The rows of (team, role, score).
+    const ROWS: [(&str, &str, f64); 5] = [
+        ("A", "dev", 1.),
+        ("B", "dev", 2.),
+        ("A", "qa", 3.),
+        ("A", "dev", 4.),
+        ("B", "qa", 5.),
+    ];
+
+    fn build(group_by: Vec<usize>) -> RowGroups {
+        let mut groups = RowGroups::default();
+        groups.set_group_by(group_by);
+        groups.build(
+            ROWS.len(),
+            |row_ix, col_ix| {
+                let (team, role, _) = ROWS[row_ix];
+                SharedString::from(if col_ix == 0 { team } else { role })
+            },
+            |row_ix, col_ix| (col_ix == 2).then(|| ROWS[row_ix].2),
+            &[None, Some(Aggregate::Count), Some(Aggregate::Sum)],
+        );
+        groups
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let values = [1., 4., 2.5];
+        assert_eq!(Aggregate::Count.apply(&values, 5), Some(5.));
+        assert_eq!(Aggregate::Sum.apply(&values, 3), Some(7.5));
+        assert_eq!(Aggregate::Avg.apply(&values, 3), Some(2.5));
+        assert_eq!(Aggregate::Min.apply(&values, 3), Some(1.));
+        assert_eq!(Aggregate::Max.apply(&values, 3), Some(4.));
+        assert_eq!(Aggregate::Sum.apply(&[], 3), None);
+        assert_eq!(Aggregate::Count.apply(&[], 3), Some(3.));
+    }
+
+    #[test]
+    fn test_group_rows() {
+        let groups = build(vec![0]);
+        assert_eq!(groups.groups.len(), 2);
+        assert_eq!(groups.groups[0].key(), "A");
+        assert_eq!(groups.groups[0].rows, vec![0, 2, 3]);
+        assert_eq!(groups.groups[0].aggregates, vec![None, Some(3.), Some(8.)]);
+        assert_eq!(groups.groups[1].aggregates, vec![None, Some(2.), Some(7.)]);
+        assert_eq!(
+            groups.rows,
+            vec![
+                DisplayRow::Group(0),
+                DisplayRow::Row(0),
+                DisplayRow::Row(2),
+                DisplayRow::Row(3),
+                DisplayRow::Group(1),
+                DisplayRow::Row(1),
+                DisplayRow::Row(4),
+            ]
+        );
+        assert_eq!(display_ixs(&groups.rows).get(&1), Some(&5));
+    }
+
+    #[test]
+    fn test_multi_level_group_rows() {
+        let mut groups = build(vec![0, 1]);
+        let paths = groups
+            .groups
+            .iter()
+            .map(|group| group.path.join("/"))
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["A", "A/dev", "A/qa", "B", "B/dev", "B/qa"]);
+        assert_eq!(groups.groups[1].rows, vec![0, 3]);
+        assert_eq!(groups.groups[1].level(), 1);
+
+        // Collapse the group `A`, the sub groups are hidden.
+        groups.toggle(0);
+        assert!(groups.is_collapsed(0));
+        assert_eq!(
+            groups.rows[..3],
+            [
+                DisplayRow::Group(0),
+                DisplayRow::Group(3),
+                DisplayRow::Group(4)
+            ]
+        );
+        assert_eq!(display_ixs(&groups.rows).get(&0), None);
+
+        groups.collapse_all();
+        assert_eq!(
+            groups.rows,
+            vec![DisplayRow::Group(0), DisplayRow::Group(3)]
+        );
+        groups.expand_all();
+        assert_eq!(groups.rows.len(), 6 + 5);
+    }
+
+    #[test]
+    fn test_move_column() {
+        let mut groups = RowGroups::default();
+        groups.set_group_by(vec![0, 1, 2]);
+        groups.move_column(0, 2);
+        assert_eq!(groups.group_by(), &[2, 0, 1]);
+        groups.move_column(2, 0);
+        assert_eq!(groups.group_by(), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn test_next_row() {
+        let groups = build(vec![0]);
+        assert_eq!(next_row(&groups.rows, None, true, true), Some(0));
+        assert_eq!(next_row(&groups.rows, Some(3), true, true), Some(1));
+        assert_eq!(next_row(&groups.rows, Some(4), true, true), Some(0));
+        assert_eq!(next_row(&groups.rows, Some(4), true, false), Some(4));
+        assert_eq!(next_row(&groups.rows, Some(0), false, true), Some(4));
+        assert_eq!(next_row(&groups.rows, Some(1), false, true), Some(3));
+    }
+}
@@ -0,0 +1,81 @@
+use gpui::{Pixels, SharedString};
+use serde::{Deserialize, Serialize};
+
+/// The layout of the columns of a [`Table`](super::Table), used to persist the column widths,
+/// order and visibility changed by the user.
+///
+/// See [`Table::layout`](super::Table::layout) and [`Table::persist_layout`](super::Table::persist_layout).
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableLayout {
+    /// The columns in the display order.
+    pub columns: Vec<ColumnLayout>,
+}
+
+/// The layout of a column in the [`TableLayout`], the column is matched by the [`Column::key`](super::Column::key).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnLayout {
+    pub key: SharedString,
+    pub width: Pixels,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+/// Returns the moves (`from`, `to`) to sort the `keys` in the order of the `order`, each move
+/// removes the item at `from` and inserts it at `to`.
+///
+/// The keys not in the `order` are kept after the ordered keys, and the unknown keys in the
+/// `order` are ignored.
+pub(super) fn reorder_moves(keys: &[SharedString], order: &[SharedString]) -> Vec<(usize, usize)> {
+    let mut keys = keys.to_vec();
+    let mut moves = vec![];
+    let mut to = 0;
+    for key in order {
+        let Some(from) = keys.iter().skip(to).position(|k| k == key) else {
+            continue;
+        };
+        let from = from + to;
+        if from != to {
+            let key = keys.remove(from);
+            keys.insert(to, key);
+            moves.push((from, to));
+        }
+        to += 1;
+    }
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::SharedString;
+
+    use super::reorder_moves;
+
+    fn apply(keys: &[&str], order: &[&str]) -> Vec<String> {
+        let mut keys: Vec<SharedString> = keys.iter().map(|k| SharedString::from(*k)).collect();
+        let order: Vec<SharedString> = order.iter().map(|k| SharedString::from(*k)).collect();
+        for (from, to) in reorder_moves(&keys, &order) {
+            let key = keys.remove(from);
+            keys.insert(to, key);
+        }
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn test_reorder_moves() {
+        assert_eq!(apply(&["a", "b", "c"], &["a", "b", "c"]), ["a", "b", "c"]);
+        assert_eq!(apply(&["a", "b", "c"], &["c", "a", "b"]), ["c", "a", "b"]);
+        assert_eq!(apply(&["a", "b", "c"], &["b", "c", "a"]), ["b", "c", "a"]);
+        // The keys not in the order are kept at the end.
+        assert_eq!(
+            apply(&["a", "b", "c", "d"], &["c", "a"]),
+            ["c", "a", "b", "d"]
+        );
+        // The unknown keys are ignored.
+        assert_eq!(apply(&["a", "b"], &["x", "b", "y"]), ["b", "a"]);
+        assert!(reorder_moves(&["a".into(), "b".into()], &["a".into(), "b".into()]).is_empty());
+    }
+}
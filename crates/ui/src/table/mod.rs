@@ -1,39 +1,123 @@
-use std::{ops::Range, rc::Rc, time::Duration};
+use std::{collections::HashMap, ops::Range, rc::Rc, time::Duration};
 
 use crate::{
-    actions::{Cancel, SelectNext, SelectPrev},
+    actions::{Cancel, Confirm, SelectNext, SelectPrev},
     context_menu::ContextMenuExt,
     h_flex,
+    i18n::t,
+    indicator::Indicator,
+    locale::format_number,
     popup_menu::PopupMenu,
     scroll::{self, ScrollableMask, Scrollbar, ScrollbarState},
-    v_flex, ActiveTheme, Icon, IconName, Sizable, Size, StyleSized as _, StyledExt,
-    VirtualListScrollHandle,
+    state, v_flex, ActiveTheme, Icon, IconName, LayoutDirection, Sizable, Size, StyleSized as _,
+    StyledExt, VirtualListScrollHandle,
 };
 use gpui::{
-    actions, canvas, div, prelude::FluentBuilder, px, uniform_list, App, AppContext, Axis, Bounds,
-    Context, Div, DragMoveEvent, Edges, EventEmitter, FocusHandle, Focusable, InteractiveElement,
-    IntoElement, KeyBinding, ListSizingBehavior, MouseButton, MouseDownEvent, ParentElement,
-    Pixels, Point, Render, ScrollStrategy, ScrollWheelEvent, SharedString,
-    StatefulInteractiveElement as _, Styled, Task, UniformListScrollHandle, Window,
+    actions, canvas, div, prelude::FluentBuilder, px, uniform_list, Action, AnyElement, App,
+    AppContext, Axis, Bounds, ClipboardItem, Context, Div, DragMoveEvent, Edges, Entity,
+    EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding,
+    ListSizingBehavior, MouseButton, MouseDownEvent, ParentElement, Pixels, Point, Render,
+    ScrollStrategy, ScrollWheelEvent, SharedString, StatefulInteractiveElement as _, Styled, Task,
+    UniformListScrollHandle, Window,
 };
+use serde::Deserialize;
 
+mod cell;
 mod column;
 mod delegate;
+mod detail;
+mod export;
+mod format;
+mod group;
+mod layout;
 mod loading;
+mod tree;
 
+use cell::CellSelection;
+pub use cell::Movement;
 pub use column::*;
 pub use delegate::*;
-
-actions!(table, [SelectPrevColumn, SelectNextColumn]);
+use detail::RowDetails;
+pub use format::*;
+pub use group::Aggregate;
+use group::{display_ixs, next_row, DisplayRow, RowGroups};
+pub use layout::{ColumnLayout, TableLayout};
+use tree::RowTree;
+
+actions!(
+    table,
+    [
+        SelectPrevColumn,
+        SelectNextColumn,
+        ExpandAllGroups,
+        CollapseAllGroups,
+        Copy,
+        ResetColumns
+    ]
+);
+
+/// Show or hide the column at the index.
+#[derive(Action, Debug, Clone, Copy, PartialEq, Eq)]
+#[action(namespace = table, no_json)]
+pub struct ToggleColumn(usize);
+
+/// Move the column at the index before the previous visible column.
+#[derive(Action, Debug, Clone, Copy, PartialEq, Eq)]
+#[action(namespace = table, no_json)]
+pub struct MoveColumnLeft(usize);
+
+/// Move the column at the index after the next visible column.
+#[derive(Action, Debug, Clone, Copy, PartialEq, Eq)]
+#[action(namespace = table, no_json)]
+pub struct MoveColumnRight(usize);
+
+/// Move the active cell, see [`Table::cell_selectable`].
+#[derive(Clone, Action, PartialEq, Eq, Deserialize)]
+#[action(namespace = table, no_json)]
+pub struct MoveCell {
+    pub movement: Movement,
+    /// Extend the selected range from the anchor, e.g.: with `shift`.
+    pub extend: bool,
+}
 
 pub fn init(cx: &mut App) {
     let context = Some("Table");
+    let bindings = [
+        ("up", Movement::Up, true),
+        ("down", Movement::Down, true),
+        ("left", Movement::Left, true),
+        ("right", Movement::Right, true),
+        ("home", Movement::RowStart, false),
+        ("end", Movement::RowEnd, false),
+        ("pageup", Movement::PageUp, false),
+        ("pagedown", Movement::PageDown, false),
+    ]
+    .into_iter()
+    .flat_map(|(key, movement, shift_only)| {
+        // The arrows without `shift` are bound to select the row or the column.
+        let extends: &[bool] = if shift_only { &[true] } else { &[false, true] };
+        extends.iter().map(move |&extend| {
+            let keystroke = if extend {
+                format!("shift-{}", key)
+            } else {
+                key.to_string()
+            };
+            KeyBinding::new(&keystroke, MoveCell { movement, extend }, context)
+        })
+    });
+
+    cx.bind_keys(bindings);
     cx.bind_keys([
+        KeyBinding::new("enter", Confirm { secondary: false }, context),
         KeyBinding::new("escape", Cancel, context),
         KeyBinding::new("up", SelectPrev, context),
         KeyBinding::new("down", SelectNext, context),
         KeyBinding::new("left", SelectPrevColumn, context),
         KeyBinding::new("right", SelectNextColumn, context),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-c", Copy, context),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-c", Copy, context),
     ]);
 }
 
@@ -41,6 +125,7 @@ pub fn init(cx: &mut App) {
 enum SelectionState {
     Column,
     Row,
+    Cell,
 }
 
 #[derive(Clone)]
@@ -52,6 +137,10 @@ pub enum TableEvent {
     SelectColumn(usize),
     ColumnWidthsChanged(Vec<Pixels>),
     MoveColumn(usize, usize),
+    /// The active cell (`row_ix`, `col_ix`) is changed, see [`Table::cell_selectable`].
+    SelectCell(usize, usize),
+    /// Enter pressed or double click on the active cell (`row_ix`, `col_ix`), e.g.: to edit the cell in place.
+    EditCell(usize, usize),
 }
 
 /// The visible range of the rows and columns.
@@ -84,6 +173,21 @@ pub struct Table<D: TableDelegate> {
     fixed_head_cols_bounds: Bounds<Pixels>,
 
     col_groups: Vec<ColGroup>,
+    /// The groups of the rows, see [`Table::set_group_by`].
+    row_groups: RowGroups,
+    /// The hierarchy of the rows, see [`Table::tree`].
+    row_tree: RowTree,
+    /// The expanded rows, see [`Table::row_expandable`].
+    row_details: RowDetails,
+    /// The rows in the list with the group rows and the details, `None` to display the rows
+    /// of the delegate as is.
+    display_rows: Option<Vec<DisplayRow>>,
+    /// The indices of the delegate rows in the [`Self::display_rows`].
+    display_ixs: HashMap<usize, usize>,
+    /// The layout of the columns when the table is created, to reset the columns.
+    initial_layout: TableLayout,
+    /// The key to persist the layout, see [`Table::persist_layout`].
+    layout_key: Option<SharedString>,
 
     /// Whether the table can loop selection, default is true.
     ///
@@ -101,6 +205,10 @@ pub struct Table<D: TableDelegate> {
     pub col_movable: bool,
     /// Enable/disable fixed columns feature.
     pub col_fixed: bool,
+    /// Whether to show the expander column to expand the row details.
+    pub row_expandable: bool,
+    /// Whether the table can select the cells like a spreadsheet.
+    pub cell_selectable: bool,
 
     pub vertical_scroll_handle: UniformListScrollHandle,
     pub vertical_scroll_state: ScrollbarState,
@@ -111,7 +219,12 @@ pub struct Table<D: TableDelegate> {
     selected_row: Option<usize>,
     selection_state: SelectionState,
     right_clicked_row: Option<usize>,
+    /// The column of the right clicked header.
+    right_clicked_col: Option<usize>,
     selected_col: Option<usize>,
+    cell_selection: CellSelection,
+    /// The display indices of the rows and the column indices of the selected cells, updated on render.
+    selected_cells: Option<(Range<usize>, Range<usize>)>,
 
     /// The column index that is being resized.
     resizing_col: Option<usize>,
@@ -124,6 +237,10 @@ pub struct Table<D: TableDelegate> {
     size: Size,
     /// The visible range of the rows and columns.
     visible_range: VisibleRangeState,
+    /// The layout direction of the window, the columns are mirrored in right to left.
+    direction: LayoutDirection,
+    /// Set when the direction is changed, to scroll to the first column on the next paint.
+    scroll_to_start: bool,
 
     _measure: Vec<Duration>,
     _load_more_task: Task<()>,
@@ -138,6 +255,13 @@ where
             focus_handle: cx.focus_handle(),
             delegate,
             col_groups: Vec::new(),
+            row_groups: RowGroups::default(),
+            row_tree: RowTree::default(),
+            row_details: RowDetails::default(),
+            display_rows: None,
+            display_ixs: HashMap::new(),
+            initial_layout: TableLayout::default(),
+            layout_key: None,
             horizontal_scroll_handle: VirtualListScrollHandle::new(),
             vertical_scroll_handle: UniformListScrollHandle::new(),
             vertical_scroll_state: ScrollbarState::default(),
@@ -145,7 +269,10 @@ where
             selection_state: SelectionState::Row,
             selected_row: None,
             right_clicked_row: None,
+            right_clicked_col: None,
             selected_col: None,
+            cell_selection: CellSelection::default(),
+            selected_cells: None,
             resizing_col: None,
             bounds: Bounds::default(),
             fixed_head_cols_bounds: Bounds::default(),
@@ -154,6 +281,8 @@ where
             size: Size::default(),
             scrollbar_visible: Edges::all(true),
             visible_range: VisibleRangeState::default(),
+            direction: LayoutDirection::default(),
+            scroll_to_start: false,
             loop_selection: true,
             col_selectable: true,
             row_selectable: true,
@@ -161,11 +290,14 @@ where
             col_movable: true,
             col_resizable: true,
             col_fixed: true,
+            row_expandable: false,
+            cell_selectable: false,
             _load_more_task: Task::ready(()),
             _measure: Vec::new(),
         };
 
         this.prepare_col_groups(cx);
+        this.initial_layout = this.layout();
         this
     }
 
@@ -230,6 +362,33 @@ where
         self
     }
 
+    /// Set to show the expander column to expand the row details, default false.
+    ///
+    /// See [`TableDelegate::row_detail_height`] and [`TableDelegate::render_row_detail`].
+    pub fn row_expandable(mut self, row_expandable: bool) -> Self {
+        self.row_expandable = row_expandable;
+        self
+    }
+
+    /// Set to show the rows in a tree by the [`TableDelegate::row_parent`], default false.
+    ///
+    /// The rows with the children are expanded by the toggle in the first visible column, and
+    /// show the [`Column::aggregate`] of the leaf descendants in the columns. The tree is not
+    /// shown if the rows are grouped by [`Table::set_group_by`].
+    pub fn tree(mut self, tree: bool) -> Self {
+        self.row_tree.set_enabled(tree);
+        self
+    }
+
+    /// Set to select the cells like a spreadsheet, default false.
+    ///
+    /// The arrows, Home/End and PageUp/PageDown move the active cell, with `shift` to select the
+    /// range, and Enter emits the [`TableEvent::EditCell`].
+    pub fn cell_selectable(mut self, cell_selectable: bool) -> Self {
+        self.cell_selectable = cell_selectable;
+        self
+    }
+
     /// Set the size to the table.
     pub fn set_size(&mut self, size: Size, cx: &mut Context<Self>) {
         self.size = size;
@@ -254,22 +413,383 @@ where
     /// When we update columns or rows, we need to refresh the table.
     pub fn refresh(&mut self, cx: &mut Context<Self>) {
         self.prepare_col_groups(cx);
+        self.rebuild_row_groups(cx);
+    }
+
+    /// Group the rows by the values of the columns at the indices, the first column is the top
+    /// level group, empty to ungroup.
+    ///
+    /// The group keys are the [`TableDelegate::cell_value`] formatted by the column format, and
+    /// the group rows show the [`Column::aggregate`] of each column.
+    pub fn set_group_by(&mut self, col_ixs: impl Into<Vec<usize>>, cx: &mut Context<Self>) {
+        self.row_groups.set_group_by(col_ixs.into());
+        self.rebuild_row_groups(cx);
+        cx.notify();
+    }
+
+    /// Returns the column indices that the rows are grouped by.
+    pub fn group_by(&self) -> &[usize] {
+        self.row_groups.group_by()
+    }
+
+    /// Expand all the groups of the rows.
+    pub fn expand_all_groups(&mut self, cx: &mut Context<Self>) {
+        self.row_groups.expand_all();
+        self.update_display_rows(cx);
+        cx.notify();
+    }
+
+    /// Collapse all the groups of the rows.
+    pub fn collapse_all_groups(&mut self, cx: &mut Context<Self>) {
+        self.row_groups.collapse_all();
+        self.update_display_rows(cx);
+        cx.notify();
+    }
+
+    fn toggle_row_group(&mut self, group_ix: usize, cx: &mut Context<Self>) {
+        self.row_groups.toggle(group_ix);
+        self.update_display_rows(cx);
+        cx.notify();
+    }
+
+    /// Returns true if the children of the row are expanded in the tree mode.
+    pub fn is_tree_row_expanded(&self, row_ix: usize) -> bool {
+        self.row_tree.is_expanded(row_ix)
+    }
+
+    /// Expand or collapse the children of the row in the tree mode, the
+    /// [`TableDelegate::load_children`] is called if the children are not loaded.
+    pub fn set_tree_row_expanded(
+        &mut self,
+        row_ix: usize,
+        expanded: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.row_tree.set_expanded(row_ix, expanded) {
+            return;
+        }
+        if expanded && self.row_tree.is_lazy(row_ix) {
+            self.delegate.load_children(row_ix, window, cx);
+        }
+
+        self.update_display_rows(cx);
+        cx.notify();
+    }
+
+    /// Returns the selected row in the tree mode, the left and right keys collapse and expand it.
+    fn selected_tree_row(&self) -> Option<usize> {
+        if !self.row_tree.is_enabled() || self.selection_state != SelectionState::Row {
+            return None;
+        }
+        self.selected_row
+    }
+
+    /// Toggle the children of the row to expand or collapse in the tree mode.
+    pub fn toggle_tree_row(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_tree_row_expanded(row_ix, !self.is_tree_row_expanded(row_ix), window, cx);
+    }
+
+    /// Expand all the rows with the loaded children in the tree mode.
+    pub fn expand_all_tree_rows(&mut self, cx: &mut Context<Self>) {
+        self.row_tree.expand_all();
+        self.update_display_rows(cx);
+        cx.notify();
+    }
+
+    /// Collapse all the rows in the tree mode.
+    pub fn collapse_all_tree_rows(&mut self, cx: &mut Context<Self>) {
+        self.row_tree.collapse_all();
+        self.update_display_rows(cx);
+        cx.notify();
+    }
+
+    /// Returns true if the detail of the row is expanded.
+    pub fn is_row_expanded(&self, row_ix: usize) -> bool {
+        self.row_details.is_expanded(row_ix)
+    }
+
+    /// Expand or collapse the detail of the row, the row without the
+    /// [`TableDelegate::row_detail_height`] can not be expanded.
+    pub fn set_row_expanded(&mut self, row_ix: usize, expanded: bool, cx: &mut Context<Self>) {
+        if expanded {
+            let Some(height) = self.delegate.row_detail_height(row_ix, cx) else {
+                return;
+            };
+            let items = (height / self.size.table_row_height()).ceil() as usize;
+            self.row_details
+                .expand(row_ix, items, cx.theme().reduce_motion);
+        } else if !self.row_details.collapse(row_ix) {
+            return;
+        }
+
+        self.update_display_rows(cx);
+        cx.notify();
+    }
+
+    /// Toggle the detail of the row to expand or collapse.
+    pub fn toggle_row_detail(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        self.set_row_expanded(row_ix, !self.is_row_expanded(row_ix), cx);
+    }
+
+    /// Collapse the details of all the rows.
+    pub fn collapse_all_details(&mut self, cx: &mut Context<Self>) {
+        self.row_details.clear();
+        self.update_display_rows(cx);
+        cx.notify();
+    }
+
+    /// Update the rows in the list by the groups and the expanded rows.
+    fn update_display_rows(&mut self, cx: &App) {
+        let rows_count = self.delegate.rows_count(cx);
+        self.row_details.retain(rows_count);
+
+        self.display_rows = if !self.row_groups.is_empty() {
+            Some(
+                self.row_details
+                    .insert_into(self.row_groups.rows.iter().copied()),
+            )
+        } else if self.row_tree.is_enabled() {
+            Some(
+                self.row_details
+                    .insert_into(self.row_tree.rows.iter().copied()),
+            )
+        } else if !self.row_details.is_empty() {
+            Some(
+                self.row_details
+                    .insert_into((0..rows_count).map(DisplayRow::Row)),
+            )
+        } else {
+            None
+        };
+        self.display_ixs = self
+            .display_rows
+            .as_deref()
+            .map(display_ixs)
+            .unwrap_or_default();
+    }
+
+    fn rebuild_row_groups(&mut self, cx: &mut Context<Self>) {
+        self.rebuild_row_tree(cx);
+        if self.row_groups.is_empty() {
+            self.update_display_rows(cx);
+            return;
+        }
+
+        let rows_count = self.delegate.rows_count(cx);
+        let aggregates = self
+            .col_groups
+            .iter()
+            .map(|col_group| col_group.column.aggregate)
+            .collect::<Vec<_>>();
+        let delegate = &self.delegate;
+        let col_groups = &self.col_groups;
+        let cx: &App = cx;
+
+        self.row_groups.build(
+            rows_count,
+            |row_ix, col_ix| {
+                let value = delegate.cell_value(row_ix, col_ix, cx);
+                col_groups
+                    .get(col_ix)
+                    .map(|col_group| col_group.column.format.format(&value))
+                    .unwrap_or_default()
+            },
+            |row_ix, col_ix| match delegate.cell_value(row_ix, col_ix, cx) {
+                CellValue::Number(value) => Some(value),
+                _ => None,
+            },
+            &aggregates,
+        );
+        self.update_display_rows(cx);
+    }
+
+    fn rebuild_row_tree(&mut self, cx: &App) {
+        if !self.row_tree.is_enabled() {
+            return;
+        }
+
+        let aggregates = self
+            .col_groups
+            .iter()
+            .map(|col_group| col_group.column.aggregate)
+            .collect::<Vec<_>>();
+        let delegate = &self.delegate;
+        self.row_tree.build(
+            delegate.rows_count(cx),
+            |row_ix| delegate.row_parent(row_ix, cx),
+            |row_ix| delegate.row_has_children(row_ix, cx),
+            |row_ix, col_ix| match delegate.cell_value(row_ix, col_ix, cx) {
+                CellValue::Number(value) => Some(value),
+                _ => None,
+            },
+            &aggregates,
+        );
     }
 
     fn prepare_col_groups(&mut self, cx: &mut Context<Self>) {
         self.col_groups = (0..self.delegate.columns_count(cx))
             .map(|col_ix| {
                 let column = self.delegate().column(col_ix, cx);
+                // Keep the visibility changed by the user.
+                let visible = self
+                    .col_groups
+                    .iter()
+                    .find(|col_group| col_group.column.key == column.key)
+                    .map_or(!column.hidden, |col_group| col_group.visible);
                 ColGroup {
                     width: column.width,
                     bounds: Bounds::default(),
                     column: column.clone(),
+                    visible,
                 }
             })
             .collect();
         cx.notify();
     }
 
+    /// Returns true if the column at the index is visible.
+    pub fn is_column_visible(&self, col_ix: usize) -> bool {
+        self.col_groups
+            .get(col_ix)
+            .is_some_and(|col_group| col_group.visible)
+    }
+
+    /// Show or hide the column at the index, the last visible column can not be hidden.
+    pub fn set_column_visible(&mut self, col_ix: usize, visible: bool, cx: &mut Context<Self>) {
+        if !visible && self.visible_cols_count() <= 1 {
+            return;
+        }
+        let Some(col_group) = self.col_groups.get_mut(col_ix) else {
+            return;
+        };
+        if col_group.visible == visible {
+            return;
+        }
+
+        col_group.visible = visible;
+        if !visible && self.selected_col == Some(col_ix) {
+            self.selected_col = None;
+        }
+        self.save_layout(cx);
+        cx.notify();
+    }
+
+    fn visible_cols_count(&self) -> usize {
+        self.col_groups
+            .iter()
+            .filter(|col_group| col_group.visible)
+            .count()
+    }
+
+    /// Returns the index of the previous (or next if `forward`) visible column of the `col_ix`.
+    fn adjacent_visible_col(&self, col_ix: usize, forward: bool) -> Option<usize> {
+        if forward {
+            (col_ix + 1..self.col_groups.len()).find(|ix| self.col_groups[*ix].visible)
+        } else {
+            (0..col_ix.min(self.col_groups.len()))
+                .rev()
+                .find(|ix| self.col_groups[*ix].visible)
+        }
+    }
+
+    /// Returns the layout of the columns in the current order.
+    pub fn layout(&self) -> TableLayout {
+        TableLayout {
+            columns: self
+                .col_groups
+                .iter()
+                .map(|col_group| ColumnLayout {
+                    key: col_group.column.key.clone(),
+                    width: col_group.width,
+                    visible: col_group.visible,
+                })
+                .collect(),
+        }
+    }
+
+    /// Apply the layout to the columns, the columns are matched by the [`Column::key`].
+    ///
+    /// The columns not in the layout are kept after the others.
+    pub fn set_layout(
+        &mut self,
+        layout: &TableLayout,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let keys = self
+            .col_groups
+            .iter()
+            .map(|col_group| col_group.column.key.clone())
+            .collect::<Vec<_>>();
+        let order = layout
+            .columns
+            .iter()
+            .map(|column| column.key.clone())
+            .collect::<Vec<_>>();
+        for (col_ix, to_ix) in layout::reorder_moves(&keys, &order) {
+            self.move_column(col_ix, to_ix, window, cx);
+        }
+
+        for column in &layout.columns {
+            let Some(col_group) = self
+                .col_groups
+                .iter_mut()
+                .find(|col_group| col_group.column.key == column.key)
+            else {
+                continue;
+            };
+            if col_group.is_resizable() {
+                col_group.width = column.width;
+            }
+            col_group.visible = column.visible;
+        }
+        if self.visible_cols_count() == 0 {
+            if let Some(col_group) = self.col_groups.first_mut() {
+                col_group.visible = true;
+            }
+        }
+
+        self.save_layout(cx);
+        cx.emit(TableEvent::ColumnWidthsChanged(
+            self.col_groups.iter().map(|g| g.width).collect(),
+        ));
+        cx.notify();
+    }
+
+    /// Reset the columns to the layout when the table is created, the persisted layout is
+    /// removed.
+    pub fn reset_layout(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let layout = self.initial_layout.clone();
+        self.set_layout(&layout, window, cx);
+        if let Some(key) = &self.layout_key {
+            state::remove(key, cx);
+        }
+    }
+
+    /// Persist the layout of the columns to the [`state`](crate::state) storage with the `key`.
+    ///
+    /// The persisted layout is restored now, and the layout is saved when the columns are
+    /// resized, moved, shown or hidden.
+    pub fn persist_layout(
+        &mut self,
+        key: impl Into<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let key = key.into();
+        if let Some(layout) = state::get::<TableLayout>(&key, cx) {
+            self.set_layout(&layout, window, cx);
+        }
+        self.layout_key = Some(key);
+    }
+
+    fn save_layout(&self, cx: &mut App) {
+        if let Some(key) = &self.layout_key {
+            state::set(key.to_string(), &self.layout(), cx);
+        }
+    }
+
     fn fixed_left_cols_count(&self) -> usize {
         if !self.col_fixed {
             return 0;
@@ -283,20 +803,47 @@ where
 
     /// Scroll to the row at the given index.
     pub fn scroll_to_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
-        self.vertical_scroll_handle
-            .scroll_to_item(row_ix, ScrollStrategy::Top);
+        if let Some(ix) = self.display_ix(row_ix) {
+            self.vertical_scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Top);
+        }
         cx.notify();
     }
 
+    /// Returns the index of the row in the list, `None` if the row is in a collapsed group.
+    fn display_ix(&self, row_ix: usize) -> Option<usize> {
+        match &self.display_rows {
+            Some(_) => self.display_ixs.get(&row_ix).copied(),
+            None => Some(row_ix),
+        }
+    }
+
     // Scroll to the column at the given index.
     pub fn scroll_to_col(&mut self, col_ix: usize, cx: &mut Context<Self>) {
-        let col_ix = col_ix.saturating_sub(self.fixed_left_cols_count());
+        let left_columns_count = self.fixed_left_cols_count();
+        let col_ix = col_ix.max(left_columns_count);
 
+        let ix = if self.direction.is_rtl() {
+            self.col_groups.len().saturating_sub(col_ix + 1)
+        } else {
+            col_ix - left_columns_count
+        };
         self.horizontal_scroll_handle
-            .scroll_to_item(col_ix, ScrollStrategy::Top);
+            .scroll_to_item(ix, ScrollStrategy::Top);
         cx.notify();
     }
 
+    /// Returns the column index at the position `ix` of the scrollable columns.
+    ///
+    /// The scrollable columns are laid out from the last column in right to left.
+    fn scrollable_col_ix(&self, ix: usize, left_columns_count: usize) -> usize {
+        if self.direction.is_rtl() {
+            self.col_groups.len() - 1 - ix
+        } else {
+            left_columns_count + ix
+        }
+    }
+
     /// Returns the selected row index.
     pub fn selected_row(&self) -> Option<usize> {
         self.selected_row
@@ -307,9 +854,9 @@ where
         self.selection_state = SelectionState::Row;
         self.right_clicked_row = None;
         self.selected_row = Some(row_ix);
-        if let Some(row_ix) = self.selected_row {
+        if let Some(ix) = self.display_ix(row_ix) {
             self.vertical_scroll_handle
-                .scroll_to_item(row_ix, ScrollStrategy::Top);
+                .scroll_to_item(ix, ScrollStrategy::Top);
         }
         cx.emit(TableEvent::SelectRow(row_ix));
         cx.notify();
@@ -331,14 +878,203 @@ where
         cx.notify();
     }
 
+    /// Returns the active cell (`row_ix`, `col_ix`), see [`Table::cell_selectable`].
+    pub fn selected_cell(&self) -> Option<(usize, usize)> {
+        match self.selection_state {
+            SelectionState::Cell => self.cell_selection.active,
+            _ => None,
+        }
+    }
+
+    /// Select the cell, or extend the selected range from the anchor to the cell if `extend`.
+    pub fn select_cell(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        extend: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let extend = extend && self.selection_state == SelectionState::Cell;
+        self.selection_state = SelectionState::Cell;
+        self.right_clicked_row = None;
+        self.cell_selection.select((row_ix, col_ix), extend);
+        self.scroll_to_row(row_ix, cx);
+        // The fixed columns are always visible.
+        if col_ix >= self.fixed_left_cols_count() {
+            self.scroll_to_col(col_ix, cx);
+        }
+        cx.emit(TableEvent::SelectCell(row_ix, col_ix));
+        cx.notify();
+    }
+
+    /// Returns the selected rows and columns, the rows are in the display order and the
+    /// columns are visible, `None` if no cell is selected.
+    fn selected_cells_list(&self, cx: &App) -> Option<(Vec<usize>, Vec<usize>)> {
+        if self.selection_state != SelectionState::Cell {
+            return None;
+        }
+        let (anchor, active) = (self.cell_selection.anchor?, self.cell_selection.active?);
+
+        let rows = self.navigable_rows(cx);
+        let row_pos = |row_ix| rows.iter().position(|ix| *ix == row_ix);
+        let rows = rows[cell::span(row_pos(anchor.0)?, row_pos(active.0)?)].to_vec();
+        let cols = self
+            .visible_cols()
+            .filter(|col_ix| cell::span(anchor.1, active.1).contains(col_ix))
+            .collect();
+        Some((rows, cols))
+    }
+
+    /// Update the `selected_cells` to render the selected cells.
+    fn update_selected_cells(&mut self) {
+        self.selected_cells = None;
+        if self.selection_state != SelectionState::Cell {
+            return;
+        }
+        let (Some(anchor), Some(active)) = (self.cell_selection.anchor, self.cell_selection.active)
+        else {
+            return;
+        };
+        let (Some(anchor_ix), Some(active_ix)) =
+            (self.display_ix(anchor.0), self.display_ix(active.0))
+        else {
+            return;
+        };
+
+        self.selected_cells = Some((
+            cell::span(anchor_ix, active_ix),
+            cell::span(anchor.1, active.1),
+        ));
+    }
+
+    /// Returns the rows in the display order, without the rows in the collapsed groups.
+    fn navigable_rows(&self, cx: &App) -> Vec<usize> {
+        match &self.display_rows {
+            Some(rows) => rows
+                .iter()
+                .filter_map(|row| match row {
+                    DisplayRow::Row(row_ix) => Some(*row_ix),
+                    _ => None,
+                })
+                .collect(),
+            None => (0..self.delegate.rows_count(cx)).collect(),
+        }
+    }
+
+    /// Move the active cell, the first cell is selected if no cell is selected.
+    fn move_cell(&mut self, movement: Movement, extend: bool, cx: &mut Context<Self>) {
+        let rows = self.navigable_rows(cx);
+        let cols = self.visible_cols().collect::<Vec<_>>();
+        if rows.is_empty() || cols.is_empty() {
+            return;
+        }
+
+        let (row_ix, col_ix) = match self.selected_cell() {
+            Some((row_ix, col_ix)) => {
+                let pos = (
+                    rows.iter().position(|ix| *ix == row_ix).unwrap_or(0),
+                    cols.iter().position(|ix| *ix == col_ix).unwrap_or(0),
+                );
+                let page = self.visible_range.rows.len().saturating_sub(1);
+                let (row, col) = cell::move_position(pos, movement, rows.len(), cols.len(), page);
+                (rows[row], cols[col])
+            }
+            None => (rows[0], cols[0]),
+        };
+        self.select_cell(row_ix, col_ix, extend, cx);
+    }
+
     /// Clear the selection of the table.
     pub fn clear_selection(&mut self, cx: &mut Context<Self>) {
         self.selection_state = SelectionState::Row;
         self.selected_row = None;
         self.selected_col = None;
+        self.cell_selection.clear();
         cx.notify();
     }
 
+    /// Returns the text of the cell, the [`TableDelegate::cell_value`] formatted by the column format.
+    fn cell_text(&self, row_ix: usize, col_ix: usize, cx: &App) -> SharedString {
+        let value = self.delegate.cell_value(row_ix, col_ix, cx);
+        self.col_groups
+            .get(col_ix)
+            .map(|col_group| col_group.column.format.format(&value))
+            .unwrap_or_default()
+    }
+
+    /// Returns the indices of the visible columns.
+    fn visible_cols(&self) -> impl Iterator<Item = usize> + '_ {
+        self.col_groups
+            .iter()
+            .enumerate()
+            .filter(|(_, col_group)| col_group.visible)
+            .map(|(col_ix, _)| col_ix)
+    }
+
+    /// Export all the rows to CSV, with the column names as the header.
+    ///
+    /// The rows and the visible columns are in the current order (e.g.: sorted or moved), and
+    /// the cells are the [`TableDelegate::cell_value`] formatted by the column format.
+    pub fn export_csv(&self, cx: &App) -> String {
+        let mut out = String::new();
+        export::push_csv_line(
+            &mut out,
+            self.visible_cols()
+                .map(|col_ix| &self.col_groups[col_ix].column.name),
+        );
+        for row_ix in 0..self.delegate.rows_count(cx) {
+            export::push_csv_line(
+                &mut out,
+                self.visible_cols()
+                    .map(|col_ix| self.cell_text(row_ix, col_ix, cx)),
+            );
+        }
+        out
+    }
+
+    /// Returns the selection in TSV to paste into the spreadsheets, `None` if nothing is selected.
+    ///
+    /// The selected row is the cells of the visible columns, the selected column is the column name
+    /// with the cells of all the rows, and the selected cells are the range without the header.
+    pub fn selection_to_tsv(&self, cx: &App) -> Option<String> {
+        let mut out = String::new();
+        match self.selection_state {
+            SelectionState::Cell => {
+                let (rows, cols) = self.selected_cells_list(cx)?;
+                for row_ix in rows {
+                    export::push_tsv_line(
+                        &mut out,
+                        cols.iter()
+                            .map(|col_ix| self.cell_text(row_ix, *col_ix, cx)),
+                    );
+                }
+            }
+            SelectionState::Row => {
+                let row_ix = self.selected_row?;
+                export::push_tsv_line(
+                    &mut out,
+                    self.visible_cols()
+                        .map(|col_ix| self.cell_text(row_ix, col_ix, cx)),
+                );
+            }
+            SelectionState::Column => {
+                let col_ix = self.selected_col?;
+                export::push_tsv_line(&mut out, [&self.col_groups.get(col_ix)?.column.name]);
+                for row_ix in 0..self.delegate.rows_count(cx) {
+                    export::push_tsv_line(&mut out, [self.cell_text(row_ix, col_ix, cx)]);
+                }
+            }
+        }
+        Some(out)
+    }
+
+    /// Copy the selection to the clipboard in TSV, see [`Table::selection_to_tsv`].
+    pub fn copy_selection(&self, cx: &mut App) {
+        if let Some(text) = self.selection_to_tsv(cx) {
+            cx.write_to_clipboard(ClipboardItem::new_string(text));
+        }
+    }
+
     /// Returns the visible range of the rows and columns.
     pub fn visible_range(&self) -> &VisibleRangeState {
         &self.visible_range
@@ -353,8 +1089,12 @@ where
     ) {
         if ev.button == MouseButton::Right {
             self.right_clicked_row = Some(row_ix);
+            self.right_clicked_col = None;
         } else {
-            self.set_selected_row(row_ix, cx);
+            // The cell is selected by `on_cell_click`.
+            if !self.cell_selectable {
+                self.set_selected_row(row_ix, cx);
+            }
 
             if ev.click_count == 2 {
                 cx.emit(TableEvent::DoubleClickedRow(row_ix));
@@ -362,6 +1102,20 @@ where
         }
     }
 
+    fn on_cell_click(
+        &mut self,
+        ev: &MouseDownEvent,
+        row_ix: usize,
+        col_ix: usize,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_cell(row_ix, col_ix, ev.modifiers.shift, cx);
+        if ev.click_count == 2 {
+            cx.emit(TableEvent::EditCell(row_ix, col_ix));
+        }
+    }
+
     fn on_col_head_click(&mut self, col_ix: usize, _: &mut Window, cx: &mut Context<Self>) {
         if !self.col_selectable {
             return;
@@ -383,11 +1137,24 @@ where
     }
 
     fn action_select_prev(&mut self, _: &SelectPrev, _: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_selectable {
+            self.move_cell(Movement::Up, false, cx);
+            return;
+        }
+
         let rows_count = self.delegate.rows_count(cx);
         if rows_count < 1 {
             return;
         }
 
+        if !self.row_groups.is_empty() || self.row_tree.is_enabled() {
+            let rows = self.display_rows.as_deref().unwrap_or_default();
+            if let Some(row_ix) = next_row(rows, self.selected_row, false, self.loop_selection) {
+                self.set_selected_row(row_ix, cx);
+            }
+            return;
+        }
+
         let mut selected_row = self.selected_row.unwrap_or(0);
         if selected_row > 0 {
             selected_row = selected_row.saturating_sub(1);
@@ -401,11 +1168,24 @@ where
     }
 
     fn action_select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_selectable {
+            self.move_cell(Movement::Down, false, cx);
+            return;
+        }
+
         let rows_count = self.delegate.rows_count(cx);
         if rows_count < 1 {
             return;
         }
 
+        if !self.row_groups.is_empty() || self.row_tree.is_enabled() {
+            let rows = self.display_rows.as_deref().unwrap_or_default();
+            if let Some(row_ix) = next_row(rows, self.selected_row, true, self.loop_selection) {
+                self.set_selected_row(row_ix, cx);
+            }
+            return;
+        }
+
         let selected_row = match self.selected_row {
             Some(selected_row) if selected_row < rows_count.saturating_sub(1) => selected_row + 1,
             Some(selected_row) => {
@@ -421,36 +1201,172 @@ where
         self.set_selected_row(selected_row, cx);
     }
 
+    fn action_copy(&mut self, _: &Copy, _: &mut Window, cx: &mut Context<Self>) {
+        self.copy_selection(cx);
+    }
+
+    fn action_move_cell(&mut self, action: &MoveCell, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.cell_selectable {
+            cx.propagate();
+            return;
+        }
+
+        // The left and right keys are mirrored in right to left.
+        let movement = match action.movement {
+            Movement::Left if self.direction.is_rtl() => Movement::Right,
+            Movement::Right if self.direction.is_rtl() => Movement::Left,
+            movement => movement,
+        };
+        self.move_cell(movement, action.extend, cx);
+    }
+
+    fn action_confirm(&mut self, _: &Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        let Some((row_ix, col_ix)) = self.selected_cell() else {
+            cx.propagate();
+            return;
+        };
+
+        cx.emit(TableEvent::EditCell(row_ix, col_ix));
+    }
+
+    fn action_expand_all_groups(
+        &mut self,
+        _: &ExpandAllGroups,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.expand_all_groups(cx);
+    }
+
+    fn action_collapse_all_groups(
+        &mut self,
+        _: &CollapseAllGroups,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.collapse_all_groups(cx);
+    }
+
+    fn action_toggle_column(
+        &mut self,
+        action: &ToggleColumn,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let col_ix = action.0;
+        self.set_column_visible(col_ix, !self.is_column_visible(col_ix), cx);
+    }
+
+    fn action_move_column_left(
+        &mut self,
+        action: &MoveColumnLeft,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let forward = self.direction.is_rtl();
+        if let Some(to_ix) = self.adjacent_visible_col(action.0, forward) {
+            self.move_column(action.0, to_ix, window, cx);
+        }
+    }
+
+    fn action_move_column_right(
+        &mut self,
+        action: &MoveColumnRight,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let forward = !self.direction.is_rtl();
+        if let Some(to_ix) = self.adjacent_visible_col(action.0, forward) {
+            self.move_column(action.0, to_ix, window, cx);
+        }
+    }
+
+    fn action_reset_columns(
+        &mut self,
+        _: &ResetColumns,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.reset_layout(window, cx);
+    }
+
     fn action_select_prev_col(
         &mut self,
         _: &SelectPrevColumn,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let mut selected_col = self.selected_col.unwrap_or(0);
-        let columns_count = self.delegate.columns_count(cx);
-        if selected_col > 0 {
-            selected_col = selected_col.saturating_sub(1);
+        // The left and right keys are mirrored in right to left.
+        if self.direction.is_rtl() {
+            self.select_next_col(window, cx);
         } else {
-            if self.loop_selection {
-                selected_col = columns_count.saturating_sub(1);
-            }
+            self.select_prev_col(window, cx);
         }
-        self.set_selected_col(selected_col, cx);
     }
 
     fn action_select_next_col(
         &mut self,
         _: &SelectNextColumn,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.direction.is_rtl() {
+            self.select_prev_col(window, cx);
+        } else {
+            self.select_next_col(window, cx);
+        }
+    }
+
+    fn select_prev_col(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_selectable {
+            self.move_cell(Movement::Left, false, cx);
+            return;
+        }
+
+        // Collapse the selected row in the tree, or select the parent row.
+        if let Some(row_ix) = self.selected_tree_row() {
+            if self.is_tree_row_expanded(row_ix) {
+                self.set_tree_row_expanded(row_ix, false, window, cx);
+            } else if let Some(parent_ix) = self.row_tree.parent(row_ix) {
+                self.set_selected_row(parent_ix, cx);
+            }
+            return;
+        }
+
+        let mut selected_col = self.selected_col.unwrap_or(0);
+        if let Some(col_ix) = self.adjacent_visible_col(selected_col, false) {
+            selected_col = col_ix;
+        } else {
+            if self.loop_selection {
+                selected_col = self
+                    .adjacent_visible_col(self.col_groups.len(), false)
+                    .unwrap_or(selected_col);
+            }
+        }
+        self.set_selected_col(selected_col, cx);
+    }
+
+    fn select_next_col(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_selectable {
+            self.move_cell(Movement::Right, false, cx);
+            return;
+        }
+
+        if let Some(row_ix) = self.selected_tree_row() {
+            self.set_tree_row_expanded(row_ix, true, window, cx);
+            return;
+        }
+
         let mut selected_col = self.selected_col.unwrap_or(0);
-        if selected_col < self.delegate.columns_count(cx).saturating_sub(1) {
-            selected_col += 1;
+        if let Some(col_ix) = self.adjacent_visible_col(selected_col, true) {
+            selected_col = col_ix;
         } else {
             if self.loop_selection {
-                selected_col = 0;
+                selected_col = self
+                    .col_groups
+                    .iter()
+                    .position(|col_group| col_group.visible)
+                    .unwrap_or(0);
             }
         }
 
@@ -463,13 +1379,28 @@ where
         mouse_position: Point<Pixels>,
         col_group: &ColGroup,
     ) {
-        // Do nothing if pos out of the table bounds right for avoid scroll to the right.
-        if mouse_position.x > self.bounds.right() {
+        let mut offset = self.horizontal_scroll_handle.offset();
+        let col_bounds = col_group.bounds;
+
+        // The resize handle is on the left of the column in right to left.
+        if self.direction.is_rtl() {
+            if mouse_position.x < self.bounds.left() {
+                return;
+            }
+
+            if mouse_position.x > self.bounds.right()
+                && col_bounds.left() > self.bounds.right() - px(20.)
+            {
+                offset.x -= px(1.);
+            }
+            self.horizontal_scroll_handle.set_offset(offset);
             return;
         }
 
-        let mut offset = self.horizontal_scroll_handle.offset();
-        let col_bounds = col_group.bounds;
+        // Do nothing if pos out of the table bounds right for avoid scroll to the right.
+        if mouse_position.x > self.bounds.right() {
+            return;
+        }
 
         if mouse_position.x < self.bounds.left()
             && col_bounds.right() < self.bounds.left() + px(20.)
@@ -545,6 +1476,10 @@ where
         }
 
         self.delegate_mut().perform_sort(col_ix, sort, window, cx);
+        // The rows are changed after sorting.
+        self.row_details.clear();
+        self.row_tree.collapse_all();
+        self.rebuild_row_groups(cx);
 
         cx.notify();
     }
@@ -563,6 +1498,9 @@ where
         self.delegate.move_column(col_ix, to_ix, window, cx);
         let col_group = self.col_groups.remove(col_ix);
         self.col_groups.insert(to_ix, col_group);
+        self.row_groups.move_column(col_ix, to_ix);
+        self.rebuild_row_groups(cx);
+        self.save_layout(cx);
 
         cx.emit(TableEvent::MoveColumn(col_ix, to_ix));
         cx.notify();
@@ -612,6 +1550,13 @@ where
                 .visible_rows_changed(visible_range.clone(), window, cx);
             self.visible_range.rows = visible_range;
         } else {
+            // The columns are laid out from the last column in right to left.
+            let visible_range = if self.direction.is_rtl() {
+                let count = self.col_groups.len() - self.fixed_left_cols_count();
+                count.saturating_sub(visible_range.end)..count.saturating_sub(visible_range.start)
+            } else {
+                visible_range
+            };
             if self.visible_range.cols == visible_range {
                 return;
             }
@@ -625,6 +1570,9 @@ where
         let Some(col_group) = self.col_groups.get(col_ix) else {
             return div();
         };
+        if !col_group.visible {
+            return div();
+        }
 
         let col_width = col_group.width;
         let col_padding = col_group.column.paddings;
@@ -666,6 +1614,24 @@ where
         }
     }
 
+    /// Render the border between the fixed columns and the scrollable columns.
+    fn render_fixed_cols_border(&self, cx: &mut Context<Self>) -> Div {
+        div()
+            .absolute()
+            .top_0()
+            .bottom_0()
+            .w_0()
+            .flex_shrink_0()
+            .map(|this| {
+                if self.direction.is_rtl() {
+                    this.left_0().border_l_1()
+                } else {
+                    this.right_0().border_r_1()
+                }
+            })
+            .border_color(cx.theme().border)
+    }
+
     fn render_vertical_scrollbar(
         &self,
         _: &mut Window,
@@ -695,11 +1661,18 @@ where
     ) -> impl IntoElement {
         let state = self.horizontal_scroll_state.clone();
 
+        let fixed_width = self.fixed_head_cols_bounds.size.width + self.expander_width();
+
         div()
             .occlude()
             .absolute()
-            .left(self.fixed_head_cols_bounds.size.width)
-            .right_0()
+            .map(|this| {
+                if self.direction.is_rtl() {
+                    this.left_0().right(fixed_width)
+                } else {
+                    this.left(fixed_width).right_0()
+                }
+            })
             .bottom_0()
             .h(scroll::WIDTH)
             .on_scroll_wheel(cx.listener(|_, _: &ScrollWheelEvent, _, cx| {
@@ -730,6 +1703,7 @@ where
         }
 
         let group_id = SharedString::from(format!("resizable-handle:{}", ix));
+        let rtl = self.direction.is_rtl();
 
         h_flex()
             .id(("resizable-handle", ix))
@@ -738,8 +1712,13 @@ where
             .cursor_col_resize()
             .h_full()
             .w(HANDLE_SIZE)
-            .ml(-(HANDLE_SIZE))
-            .justify_end()
+            .map(|this| {
+                if rtl {
+                    this.mr(-(HANDLE_SIZE)).justify_start()
+                } else {
+                    this.ml(-(HANDLE_SIZE)).justify_end()
+                }
+            })
             .items_center()
             .child(
                 div()
@@ -772,12 +1751,28 @@ where
                                 .expect("BUG: invalid col index")
                                 .clone();
 
-                            view.resize_cols(
-                                ix,
-                                e.event.position.x - HANDLE_SIZE - col_group.bounds.left(),
-                                window,
-                                cx,
-                            );
+                            if view.direction.is_rtl() {
+                                view.resize_cols(
+                                    ix,
+                                    col_group.bounds.right() - HANDLE_SIZE - e.event.position.x,
+                                    window,
+                                    cx,
+                                );
+
+                                // Keep the right edge of the scrollable column in place.
+                                if ix >= view.fixed_left_cols_count() {
+                                    let mut offset = view.horizontal_scroll_handle.offset();
+                                    offset.x -= view.col_groups[ix].width - col_group.width;
+                                    view.horizontal_scroll_handle.set_offset(offset);
+                                }
+                            } else {
+                                view.resize_cols(
+                                    ix,
+                                    e.event.position.x - HANDLE_SIZE - col_group.bounds.left(),
+                                    window,
+                                    cx,
+                                );
+                            }
 
                             // scroll the table if the drag is near the edge
                             view.scroll_table_by_col_resizing(e.event.position, &col_group);
@@ -800,6 +1795,7 @@ where
 
                     let new_widths = view.col_groups.iter().map(|g| g.width).collect();
                     cx.emit(TableEvent::ColumnWidthsChanged(new_widths));
+                    view.save_layout(cx);
                     cx.notify();
                 }),
             )
@@ -853,21 +1849,22 @@ where
     /// The children must be one by one items.
     /// Because the horizontal scroll handle will use the child_item_bounds to
     /// calculate the item position for itself's `scroll_to_item` method.
-    fn render_th(
-        &self,
-        col_ix: usize,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) -> impl IntoElement {
+    fn render_th(&self, col_ix: usize, window: &mut Window, cx: &mut Context<Self>) -> AnyElement {
         let entity_id = cx.entity_id();
         let col_group = self.col_groups.get(col_ix).expect("BUG: invalid col index");
+        // Keep an empty item for the hidden column, to keep the index of the items.
+        if !col_group.visible {
+            return div().into_any_element();
+        }
 
         let movable = self.col_movable && col_group.column.movable;
         let paddings = col_group.column.paddings;
         let name = col_group.column.name.clone();
+        let rtl = self.direction.is_rtl();
 
         h_flex()
             .h_full()
+            .flex_row_with(self.direction)
             .child(
                 self.render_cell(col_ix, window, cx)
                     .id(("col-header", col_ix))
@@ -877,6 +1874,14 @@ where
                             this.on_col_head_click(col_ix, window, cx);
                         }),
                     )
+                    .on_mouse_down(
+                        MouseButton::Right,
+                        cx.listener(move |this, _, _, cx| {
+                            this.right_clicked_col = Some(col_ix);
+                            this.right_clicked_row = None;
+                            cx.notify();
+                        }),
+                    )
                     .child(
                         h_flex()
                             .size_full()
@@ -904,11 +1909,13 @@ where
                                 cx.new(|_| drag.clone())
                             },
                         )
-                        .drag_over::<DragColumn>(|this, _, _, cx| {
-                            this.rounded_l_none()
-                                .border_l_2()
-                                .border_r_0()
-                                .border_color(cx.theme().drag_border)
+                        .drag_over::<DragColumn>(move |this, _, _, cx| {
+                            if rtl {
+                                this.rounded_r_none().border_r_2().border_l_0()
+                            } else {
+                                this.rounded_l_none().border_l_2().border_r_0()
+                            }
+                            .border_color(cx.theme().drag_border)
                         })
                         .on_drop(cx.listener(
                             move |table, drag: &DragColumn, window, cx| {
@@ -936,6 +1943,7 @@ where
                 .absolute()
                 .size_full()
             })
+            .into_any_element()
     }
 
     fn render_table_head(
@@ -955,10 +1963,14 @@ where
         h_flex()
             .w_full()
             .h(self.size.table_row_height())
+            .flex_row_with(self.direction)
             .flex_shrink_0()
             .border_b_1()
             .border_color(cx.theme().border)
             .text_color(cx.theme().table_head_foreground)
+            .when(self.row_expandable, |this| {
+                this.child(self.render_expander(None, cx).bg(cx.theme().table_head))
+            })
             .when(left_columns_count > 0, |this| {
                 let view = view.clone();
                 // Render left fixed columns
@@ -966,6 +1978,7 @@ where
                     h_flex()
                         .relative()
                         .h_full()
+                        .flex_row_with(self.direction)
                         .bg(cx.theme().table_head)
                         .children(
                             self.col_groups
@@ -974,18 +1987,7 @@ where
                                 .enumerate()
                                 .map(|(col_ix, _)| self.render_th(col_ix, window, cx)),
                         )
-                        .child(
-                            // Fixed columns border
-                            div()
-                                .absolute()
-                                .top_0()
-                                .right_0()
-                                .bottom_0()
-                                .w_0()
-                                .flex_shrink_0()
-                                .border_r_1()
-                                .border_color(cx.theme().border),
-                        )
+                        .child(self.render_fixed_cols_border(cx))
                         .child(
                             canvas(
                                 move |bounds, _, cx| {
@@ -1010,15 +2012,10 @@ where
                     .child(
                         h_flex()
                             .relative()
-                            .children(
-                                self.col_groups
-                                    .iter()
-                                    .skip(left_columns_count)
-                                    .enumerate()
-                                    .map(|(col_ix, _)| {
-                                        self.render_th(left_columns_count + col_ix, window, cx)
-                                    }),
-                            )
+                            .children((0..self.col_groups.len() - left_columns_count).map(|ix| {
+                                let col_ix = self.scrollable_col_ix(ix, left_columns_count);
+                                self.render_th(col_ix, window, cx)
+                            }))
                             .child(self.delegate.render_last_empty_col(window, cx)),
                     ),
             )
@@ -1040,6 +2037,11 @@ where
         let is_stripe_row = self.stripe && row_ix % 2 != 0;
         let is_selected = self.selected_row == Some(row_ix);
         let view = cx.entity().clone();
+        // The columns of the selected cells in the row.
+        let selected_cols = self.selected_cells.as_ref().and_then(|(rows, cols)| {
+            let ix = self.display_ix(row_ix)?;
+            rows.contains(&ix).then(|| cols.clone())
+        });
 
         if row_ix < rows_count {
             let is_last_row = row_ix == rows_count - 1;
@@ -1057,6 +2059,7 @@ where
             self.delegate
                 .render_tr(row_ix, window, cx)
                 .h_flex()
+                .flex_row_with(self.direction)
                 .w_full()
                 .h(self.size.table_row_height())
                 .when(need_render_border, |this| {
@@ -1070,37 +2073,30 @@ where
                         this.bg(cx.theme().table_hover)
                     }
                 })
+                .when(self.row_expandable, |this| {
+                    this.child(self.render_expander(Some(row_ix), cx))
+                })
                 .when(left_columns_count > 0, |this| {
                     // Left fixed columns
                     this.child(
                         h_flex()
                             .relative()
                             .h_full()
+                            .flex_row_with(self.direction)
                             .children({
                                 let mut items = Vec::with_capacity(left_columns_count);
 
                                 (0..left_columns_count).for_each(|col_ix| {
-                                    items.push(self.render_col_wrap(col_ix, window, cx).child(
-                                        self.render_cell(col_ix, window, cx).child(
-                                            self.measure_render_td(row_ix, col_ix, window, cx),
-                                        ),
-                                    ));
+                                    let selected = selected_cols
+                                        .as_ref()
+                                        .is_some_and(|cols| cols.contains(&col_ix));
+                                    items
+                                        .push(self.render_td(row_ix, col_ix, selected, window, cx));
                                 });
 
                                 items
                             })
-                            .child(
-                                // Fixed columns border
-                                div()
-                                    .absolute()
-                                    .top_0()
-                                    .right_0()
-                                    .bottom_0()
-                                    .w_0()
-                                    .flex_shrink_0()
-                                    .border_r_1()
-                                    .border_color(cx.theme().border),
-                            ),
+                            .child(self.render_fixed_cols_border(cx)),
                     )
                 })
                 .child(
@@ -1116,6 +2112,7 @@ where
                                 Axis::Horizontal,
                                 col_sizes,
                                 {
+                                    let selected_cols = selected_cols.clone();
                                     move |table, visible_range: Range<usize>, window, cx| {
                                         table.update_visible_range_if_need(
                                             visible_range.clone(),
@@ -1128,18 +2125,17 @@ where
                                             visible_range.end - visible_range.start,
                                         );
 
-                                        visible_range.for_each(|col_ix| {
-                                            let col_ix = col_ix + left_columns_count;
-                                            let el =
-                                                table.render_col_wrap(col_ix, window, cx).child(
-                                                    table.render_cell(col_ix, window, cx).child(
-                                                        table.measure_render_td(
-                                                            row_ix, col_ix, window, cx,
-                                                        ),
-                                                    ),
-                                                );
-
-                                            items.push(el);
+                                        visible_range.for_each(|ix| {
+                                            let col_ix =
+                                                table.scrollable_col_ix(ix, left_columns_count);
+                                            let selected = selected_cols
+                                                .as_ref()
+                                                .is_some_and(|cols| cols.contains(&col_ix));
+                                            items.push(
+                                                table.render_td(
+                                                    row_ix, col_ix, selected, window, cx,
+                                                ),
+                                            );
                                         });
 
                                         items
@@ -1196,23 +2192,421 @@ where
                 )
         } else {
             // Render fake rows to fill the rest table space
+            let offset_x = if self.direction.is_rtl() {
+                horizontal_scroll_handle.offset().x + horizontal_scroll_handle.max_offset().width
+            } else {
+                horizontal_scroll_handle.offset().x
+            };
+
             self.delegate
                 .render_tr(row_ix, window, cx)
                 .h_flex()
+                .flex_row_with(self.direction)
                 .w_full()
                 .h_full()
                 .border_t_1()
                 .border_color(cx.theme().table_row_border)
                 .when(is_stripe_row, |this| this.bg(cx.theme().table_even))
+                .when(self.row_expandable, |this| {
+                    this.child(self.render_expander(None, cx))
+                })
                 .children((0..columns_count).map(|col_ix| {
                     h_flex()
-                        .left(horizontal_scroll_handle.offset().x)
+                        .left(offset_x)
                         .child(self.render_cell(col_ix, window, cx))
                 }))
                 .child(self.delegate.render_last_empty_col(window, cx))
         }
     }
 
+    /// Render the cell at the row and the column, the hidden column is an empty item.
+    ///
+    /// The `selected` is true if the cell is in the selected cells.
+    fn render_td(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        selected: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        if !self.is_column_visible(col_ix) {
+            return div();
+        }
+
+        let is_active = selected && self.cell_selection.active == Some((row_ix, col_ix));
+        let content = match self.row_tree.aggregate(row_ix, col_ix) {
+            Some(value) if self.row_tree.is_enabled() => {
+                let column = &self.col_groups[col_ix].column;
+                if column.aggregate == Some(Aggregate::Count) {
+                    format_number(value, 0).into_any_element()
+                } else {
+                    column
+                        .format
+                        .format(&CellValue::Number(value))
+                        .into_any_element()
+                }
+            }
+            _ => self
+                .measure_render_td(row_ix, col_ix, window, cx)
+                .into_any_element(),
+        };
+        // The toggle of the tree is in the first visible column.
+        let content = if self.row_tree.is_enabled() && self.visible_cols().next() == Some(col_ix) {
+            self.render_tree_toggle(row_ix, cx)
+                .child(div().flex_1().min_w_0().child(content))
+                .into_any_element()
+        } else {
+            content
+        };
+
+        self.render_col_wrap(col_ix, window, cx)
+            .relative()
+            .when(selected, |this| this.bg(cx.theme().table_active))
+            .child(self.render_cell(col_ix, window, cx).child(content))
+            .when(is_active, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .size_full()
+                        .border_1()
+                        .border_color(cx.theme().table_active_border),
+                )
+            })
+            .when(self.cell_selectable, |this| {
+                this.on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, ev, window, cx| {
+                        this.on_cell_click(ev, row_ix, col_ix, window, cx);
+                    }),
+                )
+            })
+    }
+
+    /// Render the indent and the toggle of the row in the tree, the toggle is a loading indicator
+    /// when the children are loading.
+    fn render_tree_toggle(&self, row_ix: usize, cx: &mut Context<Self>) -> Div {
+        let expanded = self.row_tree.is_expanded(row_ix);
+        let toggle = if !self.row_tree.has_children(row_ix) {
+            div().into_any_element()
+        } else if expanded && self.row_tree.is_lazy(row_ix) {
+            Indicator::new().xsmall().into_any_element()
+        } else {
+            div()
+                .id(("table-tree-toggle", row_ix))
+                .cursor_pointer()
+                .child(
+                    Icon::new(if expanded {
+                        IconName::ChevronDown
+                    } else {
+                        IconName::ChevronRight
+                    })
+                    .size_4()
+                    .text_color(cx.theme().muted_foreground),
+                )
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _, window, cx| {
+                        cx.stop_propagation();
+                        this.toggle_tree_row(row_ix, window, cx);
+                    }),
+                )
+                .into_any_element()
+        };
+
+        h_flex()
+            .size_full()
+            .gap_1()
+            .pl(px(16.) * self.row_tree.depth(row_ix) as f32)
+            .child(h_flex().size_4().flex_shrink_0().child(toggle))
+    }
+
+    /// Returns the width of the expander column, zero if the rows are not expandable.
+    fn expander_width(&self) -> Pixels {
+        if self.row_expandable {
+            self.size.table_row_height()
+        } else {
+            px(0.)
+        }
+    }
+
+    /// Render the cell of the expander column, with the toggle of the detail if the row can be
+    /// expanded, `None` for the header or the rows without the detail.
+    fn render_expander(&self, row_ix: Option<usize>, cx: &mut Context<Self>) -> Div {
+        let row_ix = match row_ix {
+            Some(row_ix) if self.delegate.row_detail_height(row_ix, cx).is_some() => Some(row_ix),
+            _ => None,
+        };
+
+        h_flex()
+            .w(self.expander_width())
+            .h_full()
+            .flex_shrink_0()
+            .justify_center()
+            .when_some(row_ix, |this, row_ix| {
+                let expanded = self.row_details.is_expanded(row_ix);
+                this.child(
+                    div()
+                        .id(("table-expander", row_ix))
+                        .cursor_pointer()
+                        .child(
+                            Icon::new(if expanded {
+                                IconName::ChevronDown
+                            } else {
+                                IconName::ChevronRight
+                            })
+                            .size_4()
+                            .text_color(cx.theme().muted_foreground),
+                        )
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(move |this, _, _, cx| {
+                                cx.stop_propagation();
+                                this.toggle_row_detail(row_ix, cx);
+                            }),
+                        ),
+                )
+            })
+    }
+
+    /// Render the details of the expanded rows in the view, over the placeholder items after
+    /// the rows in the list.
+    fn render_row_details(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        if self.row_details.is_empty() {
+            return vec![];
+        }
+        let Some(display_rows) = &self.display_rows else {
+            return vec![];
+        };
+
+        let row_height = self.size.table_row_height();
+        let (offset_y, view_height) = {
+            let state = self.vertical_scroll_handle.0.borrow();
+            (
+                state.base_handle.offset().y,
+                state.base_handle.bounds().size.height,
+            )
+        };
+        let rows = display_rows
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, row)| match row {
+                DisplayRow::Row(row_ix) => Some((ix, *row_ix, self.row_details.items(*row_ix)?)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut details = Vec::with_capacity(rows.len());
+        for (ix, row_ix, items) in rows {
+            let top = offset_y + row_height * (ix + 1) as f32;
+            let height = row_height * items as f32;
+            if top >= view_height || top + height <= px(0.) {
+                continue;
+            }
+
+            let progress = self.row_details.progress(row_ix, window, cx);
+            details.push(
+                div()
+                    .id(("table-detail", row_ix))
+                    .absolute()
+                    .top(top)
+                    .left_0()
+                    .right_0()
+                    .h(height * progress)
+                    .overflow_hidden()
+                    .border_b_1()
+                    .border_color(cx.theme().table_row_border)
+                    .bg(cx.theme().table)
+                    .child(
+                        div()
+                            .h(height)
+                            .child(self.delegate.render_row_detail(row_ix, window, cx)),
+                    )
+                    .into_any_element(),
+            );
+        }
+        details
+    }
+
+    /// Render the header row of the group, with the key and the rows count in the first column,
+    /// and the aggregates in the other columns.
+    fn render_group_row(
+        &self,
+        group_ix: usize,
+        left_columns_count: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let group = &self.row_groups.groups[group_ix];
+        let collapsed = self.row_groups.is_collapsed(group_ix);
+        let offset_x = self.horizontal_scroll_handle.offset().x;
+
+        // The key is shown in the first visible column.
+        let key_col_ix = self.visible_cols().next();
+
+        let mut left_cells = Vec::with_capacity(self.col_groups.len());
+        for (col_ix, col_group) in self.col_groups.iter().enumerate() {
+            let cell = self.render_cell(col_ix, window, cx);
+            if !col_group.visible {
+                left_cells.push(cell);
+                continue;
+            }
+            if Some(col_ix) == key_col_ix {
+                left_cells.push(
+                    cell.child(
+                        h_flex()
+                            .gap_1()
+                            .pl(px(16.) * group.level() as f32)
+                            .child(
+                                Icon::new(if collapsed {
+                                    IconName::ChevronRight
+                                } else {
+                                    IconName::ChevronDown
+                                })
+                                .size_4()
+                                .text_color(cx.theme().muted_foreground),
+                            )
+                            .child(group.key().clone())
+                            .child(
+                                div()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(format!("({})", group.rows.len())),
+                            ),
+                    ),
+                );
+                continue;
+            }
+
+            let aggregate = col_group.column.aggregate;
+            left_cells.push(cell.when_some(
+                group.aggregates.get(col_ix).copied().flatten(),
+                |this, value| {
+                    this.child(if aggregate == Some(Aggregate::Count) {
+                        format_number(value, 0)
+                    } else {
+                        col_group.column.format.format(&CellValue::Number(value))
+                    })
+                },
+            ));
+        }
+        let mut cells = left_cells.split_off(left_columns_count.min(left_cells.len()));
+        if self.direction.is_rtl() {
+            cells.reverse();
+        }
+
+        h_flex()
+            .id(("table-group", group_ix))
+            .flex_row_with(self.direction)
+            .w_full()
+            .h(self.size.table_row_height())
+            .overflow_hidden()
+            .border_b_1()
+            .border_color(cx.theme().table_row_border)
+            .bg(cx.theme().table_head)
+            .font_semibold()
+            .cursor_pointer()
+            .when(self.row_expandable, |this| {
+                this.child(self.render_expander(None, cx))
+            })
+            .when(left_columns_count > 0, |this| {
+                this.child(
+                    h_flex()
+                        .h_full()
+                        .flex_row_with(self.direction)
+                        .map(|this| {
+                            if self.direction.is_rtl() {
+                                this.border_l_1()
+                            } else {
+                                this.border_r_1()
+                            }
+                        })
+                        .border_color(cx.theme().border)
+                        .children(left_cells),
+                )
+            })
+            .child(
+                h_flex()
+                    .flex_1()
+                    .h_full()
+                    .overflow_hidden()
+                    .child(h_flex().h_full().left(offset_x).children(cells)),
+            )
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _, cx| this.toggle_row_group(group_ix, cx)),
+            )
+    }
+
+    /// Append the menu items of the right clicked column, and the column chooser to show or hide
+    /// the columns.
+    fn column_menu(
+        view: &Entity<Self>,
+        menu: PopupMenu,
+        window: &mut Window,
+        cx: &mut Context<PopupMenu>,
+    ) -> PopupMenu {
+        let table = view.read(cx);
+        let can_hide = table.visible_cols_count() > 1;
+        let columns = table
+            .col_groups
+            .iter()
+            .map(|col_group| (col_group.column.name.clone(), col_group.visible))
+            .collect::<Vec<_>>();
+
+        let menu = match table.right_clicked_col {
+            Some(col_ix) if table.is_column_visible(col_ix) => {
+                let movable = table.col_movable
+                    && table
+                        .col_groups
+                        .get(col_ix)
+                        .is_some_and(|col_group| col_group.column.movable);
+                let can_move_left = movable && table.adjacent_visible_col(col_ix, false).is_some();
+                let can_move_right = movable && table.adjacent_visible_col(col_ix, true).is_some();
+
+                menu.separator()
+                    .menu_with_disabled(
+                        t!("Table.move_column_left"),
+                        Box::new(MoveColumnLeft(col_ix)),
+                        !can_move_left,
+                    )
+                    .menu_with_disabled(
+                        t!("Table.move_column_right"),
+                        Box::new(MoveColumnRight(col_ix)),
+                        !can_move_right,
+                    )
+                    .menu_with_disabled(
+                        t!("Table.hide_column"),
+                        Box::new(ToggleColumn(col_ix)),
+                        !can_hide,
+                    )
+            }
+            _ => menu,
+        };
+
+        menu.separator()
+            .submenu(t!("Table.columns"), window, cx, move |menu, _, _| {
+                columns
+                    .iter()
+                    .enumerate()
+                    .fold(menu, |menu, (col_ix, (name, visible))| {
+                        menu.menu_with_check_and_disabled(
+                            name.clone(),
+                            *visible,
+                            Box::new(ToggleColumn(col_ix)),
+                            *visible && !can_hide,
+                        )
+                    })
+                    .separator()
+                    .menu(t!("Table.reset_columns"), Box::new(ResetColumns))
+            })
+    }
+
     /// Calculate the extra rows needed to fill the table empty space when `stripe` is true.
     fn calculate_extra_rows_needed(&self, rows_count: usize) -> usize {
         let mut extra_rows_needed = 0;
@@ -1307,6 +2701,12 @@ where
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.measure(window, cx);
 
+        let direction = LayoutDirection::of(window, cx);
+        if self.direction != direction {
+            self.direction = direction;
+            self.scroll_to_start = true;
+        }
+
         let view = cx.entity().clone();
         let vertical_scroll_handle = self.vertical_scroll_handle.clone();
         let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
@@ -1317,12 +2717,25 @@ where
             .filter(|col| self.col_fixed && col.column.fixed == Some(ColumnFixed::Left))
             .count();
         let rows_count = self.delegate.rows_count(cx);
+        if (!self.row_groups.is_empty() && self.row_groups.rows_count != rows_count)
+            || (self.row_tree.is_enabled() && self.row_tree.rows_count != rows_count)
+            || (!self.row_details.is_empty() && self.row_details.rows_count != rows_count)
+        {
+            self.rebuild_row_groups(cx);
+        }
+        self.update_selected_cells();
+        // The number of the rows in the list, including the group rows and the details.
+        let display_rows_count = self
+            .display_rows
+            .as_ref()
+            .map_or(rows_count, |rows| rows.len());
+        let row_details = self.render_row_details(window, cx);
         let loading = self.delegate.loading(cx);
-        let extra_rows_count = self.calculate_extra_rows_needed(rows_count);
+        let extra_rows_count = self.calculate_extra_rows_needed(display_rows_count);
         let render_rows_count = if self.stripe {
-            rows_count + extra_rows_count
+            display_rows_count + extra_rows_count
         } else {
-            rows_count
+            display_rows_count
         };
 
         let inner_table = v_flex()
@@ -1334,19 +2747,38 @@ where
             .on_action(cx.listener(Self::action_select_prev))
             .on_action(cx.listener(Self::action_select_next_col))
             .on_action(cx.listener(Self::action_select_prev_col))
+            .on_action(cx.listener(Self::action_copy))
+            .on_action(cx.listener(Self::action_move_cell))
+            .on_action(cx.listener(Self::action_confirm))
+            .on_action(cx.listener(Self::action_expand_all_groups))
+            .on_action(cx.listener(Self::action_collapse_all_groups))
+            .on_action(cx.listener(Self::action_toggle_column))
+            .on_action(cx.listener(Self::action_move_column_left))
+            .on_action(cx.listener(Self::action_move_column_right))
+            .on_action(cx.listener(Self::action_reset_columns))
             .size_full()
             .overflow_hidden()
             .child(self.render_table_head(left_columns_count, window, cx))
             .context_menu({
                 let view = view.clone();
                 move |this, window: &mut Window, cx: &mut Context<PopupMenu>| {
-                    if let Some(row_ix) = view.read(cx).right_clicked_row {
+                    let menu = if let Some(row_ix) = view.read(cx).right_clicked_row {
                         view.read(cx)
                             .delegate
                             .context_menu(row_ix, this, window, cx)
                     } else {
                         this
-                    }
+                    };
+
+                    let menu = if view.read(cx).row_groups.is_empty() {
+                        menu
+                    } else {
+                        menu.separator()
+                            .menu(t!("Table.expand_all_groups"), Box::new(ExpandAllGroups))
+                            .menu(t!("Table.collapse_all_groups"), Box::new(CollapseAllGroups))
+                    };
+
+                    Self::column_menu(&view, menu, window, cx)
                 }
             })
             .map(|this| {
@@ -1358,75 +2790,130 @@ where
                     )
                 } else {
                     this.child(
-                        h_flex().id("table-body").flex_grow().size_full().child(
-                            uniform_list(
-                                "table-uniform-list",
-                                render_rows_count,
-                                cx.processor(
-                                    move |table, visible_range: Range<usize>, window, cx| {
-                                        // We must calculate the col sizes here, because the col sizes
-                                        // need render_th first, then that method will set the bounds of each col.
-                                        let col_sizes: Rc<Vec<gpui::Size<Pixels>>> = Rc::new(
-                                            table
-                                                .col_groups
-                                                .iter()
-                                                .skip(left_columns_count)
-                                                .map(|col| col.bounds.size)
-                                                .collect(),
-                                        );
-
-                                        table.load_more_if_need(
-                                            rows_count,
-                                            visible_range.end,
-                                            window,
-                                            cx,
-                                        );
-                                        table.update_visible_range_if_need(
-                                            visible_range.clone(),
-                                            Axis::Vertical,
-                                            window,
-                                            cx,
-                                        );
+                        h_flex()
+                            .id("table-body")
+                            .flex_grow()
+                            .size_full()
+                            .relative()
+                            .overflow_hidden()
+                            .child(
+                                uniform_list(
+                                    "table-uniform-list",
+                                    render_rows_count,
+                                    cx.processor(
+                                        move |table, visible_range: Range<usize>, window, cx| {
+                                            // We must calculate the col sizes here, because the col sizes
+                                            // need render_th first, then that method will set the bounds of each col.
+                                            let col_sizes: Rc<Vec<gpui::Size<Pixels>>> = Rc::new(
+                                                (0..table.col_groups.len() - left_columns_count)
+                                                    .map(|ix| {
+                                                        let col = &table.col_groups[table
+                                                            .scrollable_col_ix(
+                                                                ix,
+                                                                left_columns_count,
+                                                            )];
+                                                        if col.visible {
+                                                            col.bounds.size
+                                                        } else {
+                                                            gpui::Size::default()
+                                                        }
+                                                    })
+                                                    .collect(),
+                                            );
 
-                                        if visible_range.end > rows_count {
-                                            table.scroll_to_row(
-                                                std::cmp::min(
-                                                    visible_range.start,
-                                                    rows_count.saturating_sub(1),
-                                                ),
+                                            table.load_more_if_need(
+                                                display_rows_count,
+                                                visible_range.end,
+                                                window,
                                                 cx,
                                             );
-                                        }
-
-                                        let mut items = Vec::with_capacity(
-                                            visible_range.end.saturating_sub(visible_range.start),
-                                        );
-
-                                        // Render fake rows to fill the table
-                                        visible_range.for_each(|row_ix| {
-                                            // Render real rows for available data
-                                            items.push(table.render_table_row(
-                                                row_ix,
-                                                rows_count,
-                                                left_columns_count,
-                                                col_sizes.clone(),
-                                                columns_count,
-                                                extra_rows_count,
+                                            table.update_visible_range_if_need(
+                                                visible_range.clone(),
+                                                Axis::Vertical,
                                                 window,
                                                 cx,
-                                            ));
-                                        });
+                                            );
 
-                                        items
-                                    },
-                                ),
+                                            if visible_range.end > display_rows_count {
+                                                table.vertical_scroll_handle.scroll_to_item(
+                                                    std::cmp::min(
+                                                        visible_range.start,
+                                                        display_rows_count.saturating_sub(1),
+                                                    ),
+                                                    ScrollStrategy::Top,
+                                                );
+                                                cx.notify();
+                                            }
+
+                                            let mut items = Vec::with_capacity(
+                                                visible_range
+                                                    .end
+                                                    .saturating_sub(visible_range.start),
+                                            );
+
+                                            // Render fake rows to fill the table
+                                            visible_range.for_each(|ix| {
+                                                let row_ix = match table
+                                                    .display_rows
+                                                    .as_ref()
+                                                    .and_then(|rows| rows.get(ix))
+                                                {
+                                                    Some(DisplayRow::Group(group_ix)) => {
+                                                        items.push(
+                                                            table
+                                                                .render_group_row(
+                                                                    *group_ix,
+                                                                    left_columns_count,
+                                                                    window,
+                                                                    cx,
+                                                                )
+                                                                .into_any_element(),
+                                                        );
+                                                        return;
+                                                    }
+                                                    Some(DisplayRow::Detail(_)) => {
+                                                        // The detail is rendered over the items.
+                                                        items.push(
+                                                            div()
+                                                                .w_full()
+                                                                .h(table.size.table_row_height())
+                                                                .into_any_element(),
+                                                        );
+                                                        return;
+                                                    }
+                                                    Some(DisplayRow::Row(row_ix)) => *row_ix,
+                                                    // The fake rows follow the rows count.
+                                                    None => ix + rows_count - display_rows_count,
+                                                };
+
+                                                // Render real rows for available data
+                                                items.push(
+                                                    table
+                                                        .render_table_row(
+                                                            row_ix,
+                                                            rows_count,
+                                                            left_columns_count,
+                                                            col_sizes.clone(),
+                                                            columns_count,
+                                                            extra_rows_count,
+                                                            window,
+                                                            cx,
+                                                        )
+                                                        .into_any_element(),
+                                                );
+                                            });
+
+                                            items
+                                        },
+                                    ),
+                                )
+                                .flex_grow()
+                                .size_full()
+                                .with_sizing_behavior(ListSizingBehavior::Auto)
+                                .track_scroll(vertical_scroll_handle)
+                                .into_any_element(),
                             )
-                            .flex_grow()
-                            .size_full()
-                            .with_sizing_behavior(ListSizingBehavior::Auto)
-                            .track_scroll(vertical_scroll_handle)
-                            .into_any_element(),
-                        ),
+                            .children(row_details),
                     )
                 }
             });
@@ -1450,15 +2937,36 @@ where
                         Axis::Horizontal,
                         &horizontal_scroll_handle,
                     ))
-                    .when(self.right_clicked_row.is_some(), |this| {
-                        this.on_mouse_down_out(cx.listener(|this, _, _, cx| {
-                            this.right_clicked_row = None;
-                            cx.notify();
-                        }))
-                    })
+                    .when(
+                        self.right_clicked_row.is_some() || self.right_clicked_col.is_some(),
+                        |this| {
+                            this.on_mouse_down_out(cx.listener(|this, _, _, cx| {
+                                this.right_clicked_row = None;
+                                this.right_clicked_col = None;
+                                cx.notify();
+                            }))
+                        },
+                    )
             })
             .child(canvas(
-                move |bounds, _, cx| view.update(cx, |r, _| r.bounds = bounds),
+                move |bounds, window, cx| {
+                    view.update(cx, |r, _| {
+                        r.bounds = bounds;
+
+                        // Scroll to the first column, it is on the right in right to left.
+                        if r.scroll_to_start {
+                            r.scroll_to_start = false;
+                            let mut offset = r.horizontal_scroll_handle.offset();
+                            offset.x = if r.direction.is_rtl() {
+                                -r.horizontal_scroll_handle.max_offset().width
+                            } else {
+                                px(0.)
+                            };
+                            r.horizontal_scroll_handle.set_offset(offset);
+                            window.refresh();
+                        }
+                    })
+                },
                 |_, _, _, _| {},
             ))
             .when(!window.is_inspector_picking(cx), |this| {
@@ -0,0 +1,288 @@
+use std::collections::{HashMap, HashSet};
+
+use super::group::{Aggregate, DisplayRow};
+
+/// The hierarchy of the rows in the tree mode, see [`Table::tree`](super::Table::tree).
+///
+/// The rows are the delegate rows with the [`TableDelegate::row_parent`](super::TableDelegate::row_parent),
+/// the children are in the order of the rows, so the sorted rows are sorted in each level.
+#[derive(Debug, Default)]
+pub(crate) struct RowTree {
+    enabled: bool,
+    /// The parent of each row.
+    parents: Vec<Option<usize>>,
+    /// The children of each row.
+    children: Vec<Vec<usize>>,
+    /// The depth of each row, `None` if the row can not be reached from the roots, e.g.: the
+    /// parents are a cycle.
+    depths: Vec<Option<usize>>,
+    roots: Vec<usize>,
+    /// The rows have the children to load, see [`TableDelegate::row_has_children`](super::TableDelegate::row_has_children).
+    lazy: HashSet<usize>,
+    /// The aggregates of each column of the descendants, for the rows with the children.
+    aggregates: HashMap<usize, Vec<Option<f64>>>,
+    expanded: HashSet<usize>,
+    /// The visible rows in the display order.
+    pub(crate) rows: Vec<DisplayRow>,
+    /// The number of the delegate rows when the tree was built.
+    pub(crate) rows_count: usize,
+}
+
+impl RowTree {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Build the tree of the `rows_count` rows.
+    ///
+    /// The `parent` returns the parent row of the row, the `has_children` returns true if the row
+    /// has the children to load. The `value` returns the number of the cell at the row and column,
+    /// to aggregate the leaf descendants of each row by the `aggregates` of the columns.
+    pub(crate) fn build(
+        &mut self,
+        rows_count: usize,
+        parent: impl Fn(usize) -> Option<usize>,
+        has_children: impl Fn(usize) -> bool,
+        value: impl Fn(usize, usize) -> Option<f64>,
+        aggregates: &[Option<Aggregate>],
+    ) {
+        self.rows_count = rows_count;
+        self.parents = (0..rows_count)
+            .map(|row_ix| parent(row_ix).filter(|ix| *ix < rows_count && *ix != row_ix))
+            .collect();
+        self.children = vec![vec![]; rows_count];
+        self.roots.clear();
+        for (row_ix, parent) in self.parents.iter().enumerate() {
+            match parent {
+                Some(parent) => self.children[*parent].push(row_ix),
+                None => self.roots.push(row_ix),
+            }
+        }
+
+        self.depths = vec![None; rows_count];
+        let mut stack = self
+            .roots
+            .iter()
+            .rev()
+            .map(|ix| (*ix, 0))
+            .collect::<Vec<_>>();
+        while let Some((row_ix, depth)) = stack.pop() {
+            self.depths[row_ix] = Some(depth);
+            stack.extend(
+                self.children[row_ix]
+                    .iter()
+                    .rev()
+                    .map(|ix| (*ix, depth + 1)),
+            );
+        }
+
+        self.lazy = (0..rows_count)
+            .filter(|row_ix| self.children[*row_ix].is_empty() && has_children(*row_ix))
+            .collect();
+        self.expanded.retain(|row_ix| *row_ix < rows_count);
+        self.build_aggregates(value, aggregates);
+        self.update_rows();
+    }
+
+    fn build_aggregates(
+        &mut self,
+        value: impl Fn(usize, usize) -> Option<f64>,
+        aggregates: &[Option<Aggregate>],
+    ) {
+        self.aggregates.clear();
+        if aggregates.iter().all(Option::is_none) {
+            return;
+        }
+
+        let mut leaves: HashMap<usize, Vec<usize>> = HashMap::new();
+        for row_ix in 0..self.rows_count {
+            if self.depths[row_ix].is_none() || !self.children[row_ix].is_empty() {
+                continue;
+            }
+
+            let mut parent = self.parents[row_ix];
+            while let Some(ix) = parent {
+                leaves.entry(ix).or_default().push(row_ix);
+                parent = self.parents[ix];
+            }
+        }
+
+        for (row_ix, leaves) in leaves {
+            let values = aggregates
+                .iter()
+                .enumerate()
+                .map(|(col_ix, aggregate)| {
+                    let aggregate = (*aggregate)?;
+                    let values = leaves
+                        .iter()
+                        .filter_map(|row_ix| value(*row_ix, col_ix))
+                        .collect::<Vec<_>>();
+                    aggregate.apply(&values, leaves.len())
+                })
+                .collect();
+            self.aggregates.insert(row_ix, values);
+        }
+    }
+
+    /// Update the visible rows by the expanded rows.
+    fn update_rows(&mut self) {
+        self.rows.clear();
+        let mut stack = self.roots.iter().rev().copied().collect::<Vec<_>>();
+        while let Some(row_ix) = stack.pop() {
+            self.rows.push(DisplayRow::Row(row_ix));
+            if self.expanded.contains(&row_ix) {
+                stack.extend(self.children[row_ix].iter().rev());
+            }
+        }
+    }
+
+    /// Returns the depth of the row, 0 is the top level.
+    pub(crate) fn depth(&self, row_ix: usize) -> usize {
+        self.depths.get(row_ix).copied().flatten().unwrap_or(0)
+    }
+
+    pub(crate) fn parent(&self, row_ix: usize) -> Option<usize> {
+        self.parents.get(row_ix).copied().flatten()
+    }
+
+    /// Returns true if the row has the children, or the children to load.
+    pub(crate) fn has_children(&self, row_ix: usize) -> bool {
+        self.children
+            .get(row_ix)
+            .is_some_and(|children| !children.is_empty())
+            || self.lazy.contains(&row_ix)
+    }
+
+    /// Returns true if the children of the row are not loaded yet.
+    pub(crate) fn is_lazy(&self, row_ix: usize) -> bool {
+        self.lazy.contains(&row_ix)
+    }
+
+    pub(crate) fn is_expanded(&self, row_ix: usize) -> bool {
+        self.expanded.contains(&row_ix)
+    }
+
+    /// Expand or collapse the row, returns false if the row has no children or is not changed.
+    pub(crate) fn set_expanded(&mut self, row_ix: usize, expanded: bool) -> bool {
+        if !self.has_children(row_ix) {
+            return false;
+        }
+
+        let changed = if expanded {
+            self.expanded.insert(row_ix)
+        } else {
+            self.expanded.remove(&row_ix)
+        };
+        if changed {
+            self.update_rows();
+        }
+        changed
+    }
+
+    pub(crate) fn expand_all(&mut self) {
+        self.expanded = (0..self.rows_count)
+            .filter(|row_ix| !self.children[*row_ix].is_empty())
+            .collect();
+        self.update_rows();
+    }
+
+    pub(crate) fn collapse_all(&mut self) {
+        self.expanded.clear();
+        self.update_rows();
+    }
+
+    /// Returns the aggregate of the descendants of the row at the column, `None` if the row has
+    /// no children or the column has no aggregate.
+    pub(crate) fn aggregate(&self, row_ix: usize, col_ix: usize) -> Option<f64> {
+        self.aggregates.get(&row_ix)?.get(col_ix).copied().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Aggregate, DisplayRow, RowTree};
+
+    /// The rows of (parent, size), e.g.: the folders and the files.
+    const ROWS: [(Option<usize>, Option<f64>); 6] = [
+        (None, None),
+        (Some(0), Some(1.)),
+        (Some(0), None),
+        (Some(2), Some(2.)),
+        (Some(2), Some(4.)),
+        (None, None),
+    ];
+
+    fn build() -> RowTree {
+        let mut tree = RowTree::default();
+        tree.set_enabled(true);
+        tree.build(
+            ROWS.len(),
+            |row_ix| ROWS[row_ix].0,
+            |row_ix| row_ix == 5,
+            |row_ix, _| ROWS[row_ix].1,
+            &[Some(Aggregate::Sum), Some(Aggregate::Count)],
+        );
+        tree
+    }
+
+    fn rows(tree: &RowTree) -> Vec<usize> {
+        tree.rows
+            .iter()
+            .filter_map(|row| match row {
+                DisplayRow::Row(row_ix) => Some(*row_ix),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_row_tree() {
+        let mut tree = build();
+        assert_eq!(rows(&tree), vec![0, 5]);
+        assert_eq!(tree.depth(4), 2);
+        assert_eq!(tree.parent(4), Some(2));
+        assert!(tree.has_children(0));
+        assert!(!tree.has_children(1));
+        // The children of the row 5 are not loaded.
+        assert!(tree.has_children(5));
+        assert!(tree.is_lazy(5));
+
+        assert!(tree.set_expanded(0, true));
+        assert!(!tree.set_expanded(0, true));
+        assert!(!tree.set_expanded(1, true));
+        assert_eq!(rows(&tree), vec![0, 1, 2, 5]);
+
+        tree.expand_all();
+        assert_eq!(rows(&tree), vec![0, 1, 2, 3, 4, 5]);
+        tree.collapse_all();
+        assert_eq!(rows(&tree), vec![0, 5]);
+    }
+
+    #[test]
+    fn test_row_tree_aggregates() {
+        let tree = build();
+        assert_eq!(tree.aggregate(0, 0), Some(7.));
+        assert_eq!(tree.aggregate(0, 1), Some(3.));
+        assert_eq!(tree.aggregate(2, 0), Some(6.));
+        assert_eq!(tree.aggregate(3, 0), None);
+        assert_eq!(tree.aggregate(5, 0), None);
+    }
+
+    #[test]
+    fn test_row_tree_cycle() {
+        let mut tree = RowTree::default();
+        tree.build(
+            3,
+            |row_ix| [None, Some(2), Some(1)][row_ix],
+            |_| false,
+            |_, _| None,
+            &[],
+        );
+        // The rows in the cycle can not be reached.
+        assert_eq!(rows(&tree), vec![0]);
+    }
+}
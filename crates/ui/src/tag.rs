@@ -1,4 +1,4 @@
-use crate::{theme::ActiveTheme as _, ColorName, Sizable, Size, StyledExt};
+use crate::{auto_foreground, theme::ActiveTheme as _, ColorName, Sizable, Size, StyledExt};
 use gpui::{
     div, prelude::FluentBuilder as _, relative, rems, transparent_white, AbsoluteLength,
     AnyElement, App, Hsla, InteractiveElement as _, IntoElement, ParentElement, RenderOnce,
@@ -191,6 +191,13 @@ impl Tag {
         })
     }
 
+    /// Create a new tag with [`TagVariant::Custom`] by a background color,
+    /// the foreground is black or white to keep readable on the color.
+    pub fn fill(color: impl Into<Hsla>) -> Self {
+        let color = color.into();
+        Self::custom(color, auto_foreground(color), color)
+    }
+
     /// Create a new tag with default variant ([`TagVariant::Color`]).
     pub fn color(color: impl Into<ColorName>) -> Self {
         Self::new().with_variant(TagVariant::Color(color.into()))
@@ -0,0 +1,368 @@
+//! The progress of the long-running background tasks, with the task rows to cancel, the
+//! aggregate progress of the [`StatusBar`](crate::status_bar::StatusBar) and the notifications
+//! on completion or failure.
+//!
+//! ```ignore
+//! task_progress::spawn("Indexing", cx, async move |task, cx| {
+//!     for (ix, file) in files.iter().enumerate() {
+//!         index(file).await?;
+//!         task.set_progress((ix + 1) as f32 / files.len() as f32, cx)?;
+//!     }
+//!     Ok(())
+//! });
+//!
+//! // Show the notifications of the tasks in the window.
+//! self._subscriptions.push(task_progress::notify_in(window, cx));
+//!
+//! // In render
+//! StatusBar::new().task_progress(cx);
+//! TaskProgressList::new();
+//! ```
+use std::collections::HashMap;
+
+use anyhow::Result;
+use gpui::{
+    prelude::FluentBuilder as _, App, AppContext as _, AsyncApp, Entity, EventEmitter, Global,
+    IntoElement, ParentElement as _, RenderOnce, SharedString, Styled as _, Subscription, Task,
+    Window,
+};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    i18n::t,
+    indicator::Indicator,
+    notification::Notification,
+    progress::Progress,
+    v_flex, ActiveTheme as _, ContextModal as _, IconName, Sizable as _,
+};
+
+/// The id of a task, returned by [`start`] and [`spawn`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(usize);
+
+/// The status of a task.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskStatus {
+    Running,
+    Completed,
+    Failed(SharedString),
+    Cancelled,
+}
+
+/// A task in the [`TaskProgress`].
+#[derive(Debug, Clone)]
+pub struct TaskInfo {
+    pub id: TaskId,
+    pub title: SharedString,
+    pub message: Option<SharedString>,
+    /// The progress in 0 ~ 1, `None` if it is indeterminate.
+    pub progress: Option<f32>,
+    pub status: TaskStatus,
+    pub cancellable: bool,
+}
+
+/// The events of the [`TaskProgress`].
+#[derive(Debug, Clone)]
+pub enum TaskProgressEvent {
+    Started(TaskId),
+    /// The task is finished with the status, it is removed from the running tasks.
+    Finished(TaskInfo),
+}
+
+/// The running tasks of the app, the global entity is returned by [`TaskProgress::global`].
+pub struct TaskProgress {
+    next_id: usize,
+    tasks: Vec<TaskInfo>,
+    /// The spawned tasks, dropped to cancel.
+    handles: HashMap<TaskId, Task<()>>,
+}
+
+struct GlobalTaskProgress(Entity<TaskProgress>);
+
+impl Global for GlobalTaskProgress {}
+
+impl EventEmitter<TaskProgressEvent> for TaskProgress {}
+
+impl TaskProgress {
+    /// Returns the global task progress entity.
+    pub fn global(cx: &mut App) -> Entity<TaskProgress> {
+        if let Some(global) = cx.try_global::<GlobalTaskProgress>() {
+            return global.0.clone();
+        }
+
+        let entity = cx.new(|_| TaskProgress {
+            next_id: 0,
+            tasks: vec![],
+            handles: HashMap::new(),
+        });
+        cx.set_global(GlobalTaskProgress(entity.clone()));
+        entity
+    }
+
+    /// Returns the running tasks, in the started order.
+    pub fn tasks(&self) -> &[TaskInfo] {
+        &self.tasks
+    }
+
+    /// Returns the aggregate progress of the running tasks, `None` if any task is indeterminate.
+    pub fn aggregate_progress(&self) -> Option<f32> {
+        aggregate_progress(&self.tasks)
+    }
+
+    fn task_mut(&mut self, id: TaskId) -> Option<&mut TaskInfo> {
+        self.tasks.iter_mut().find(|task| task.id == id)
+    }
+
+    fn finish(&mut self, id: TaskId, status: TaskStatus, cx: &mut gpui::Context<Self>) {
+        let Some(ix) = self.tasks.iter().position(|task| task.id == id) else {
+            return;
+        };
+
+        let mut task = self.tasks.remove(ix);
+        if let Some(handle) = self.handles.remove(&id) {
+            // Drop the spawned task to cancel it, or detach it to finish by itself.
+            if status != TaskStatus::Cancelled {
+                handle.detach();
+            }
+        }
+        task.status = status;
+        cx.emit(TaskProgressEvent::Finished(task));
+        cx.notify();
+    }
+}
+
+fn aggregate_progress(tasks: &[TaskInfo]) -> Option<f32> {
+    if tasks.is_empty() {
+        return None;
+    }
+
+    let mut total = 0.;
+    for task in tasks {
+        total += task.progress?;
+    }
+    Some(total / tasks.len() as f32)
+}
+
+/// The handle to report the progress of a task.
+#[derive(Clone)]
+pub struct TaskHandle {
+    id: TaskId,
+    progress: Entity<TaskProgress>,
+}
+
+impl TaskHandle {
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Set the progress in 0 ~ 1.
+    pub fn set_progress(&self, progress: f32, cx: &mut AsyncApp) -> Result<()> {
+        self.update(cx, |task| task.progress = Some(progress.clamp(0., 1.)))
+    }
+
+    /// Set the message of the current step.
+    pub fn set_message(&self, message: impl Into<SharedString>, cx: &mut AsyncApp) -> Result<()> {
+        let message = message.into();
+        self.update(cx, |task| task.message = Some(message))
+    }
+
+    fn update(&self, cx: &mut AsyncApp, f: impl FnOnce(&mut TaskInfo)) -> Result<()> {
+        let id = self.id;
+        self.progress.update(cx, |this, cx| {
+            if let Some(task) = this.task_mut(id) {
+                f(task);
+                cx.notify();
+            }
+        })
+    }
+
+    /// Finish the task with the result, for the task started by [`start`].
+    pub fn finish(&self, result: Result<()>, cx: &mut App) {
+        let status = match result {
+            Ok(()) => TaskStatus::Completed,
+            Err(err) => TaskStatus::Failed(err.to_string().into()),
+        };
+        self.progress
+            .update(cx, |this, cx| this.finish(self.id, status, cx));
+    }
+}
+
+/// Start a task to report the progress by the returned handle, the task must be finished by
+/// [`TaskHandle::finish`].
+pub fn start(title: impl Into<SharedString>, cx: &mut App) -> TaskHandle {
+    let progress = TaskProgress::global(cx);
+    let id = progress.update(cx, |this, cx| {
+        let id = TaskId(this.next_id);
+        this.next_id += 1;
+        this.tasks.push(TaskInfo {
+            id,
+            title: title.into(),
+            message: None,
+            progress: None,
+            status: TaskStatus::Running,
+            cancellable: false,
+        });
+        cx.emit(TaskProgressEvent::Started(id));
+        cx.notify();
+        id
+    });
+
+    TaskHandle { id, progress }
+}
+
+/// Spawn a cancellable task, the task is finished by the result of the `f`.
+pub fn spawn<F>(title: impl Into<SharedString>, cx: &mut App, f: F) -> TaskId
+where
+    F: AsyncFnOnce(TaskHandle, &mut AsyncApp) -> Result<()> + 'static,
+{
+    let handle = start(title, cx);
+    let id = handle.id;
+    let task = cx.spawn({
+        let handle = handle.clone();
+        async move |cx| {
+            let result = f(handle.clone(), cx).await;
+            _ = cx.update(|cx| handle.finish(result, cx));
+        }
+    });
+
+    handle.progress.update(cx, |this, _| {
+        if let Some(info) = this.task_mut(id) {
+            info.cancellable = true;
+            this.handles.insert(id, task);
+        }
+    });
+    id
+}
+
+/// Cancel the task spawned by [`spawn`].
+pub fn cancel(id: TaskId, cx: &mut App) {
+    TaskProgress::global(cx).update(cx, |this, cx| this.finish(id, TaskStatus::Cancelled, cx));
+}
+
+/// Push the notifications of the completed and failed tasks to the window.
+///
+/// The returned subscription must be kept, e.g.: in the root view of the window.
+pub fn notify_in(window: &mut Window, cx: &mut App) -> Subscription {
+    let window_handle = window.window_handle();
+    let progress = TaskProgress::global(cx);
+    cx.subscribe(&progress, move |_, event, cx| {
+        let TaskProgressEvent::Finished(task) = event else {
+            return;
+        };
+
+        let notification = match &task.status {
+            TaskStatus::Completed => {
+                Notification::success(t!("TaskProgress.completed", title = task.title))
+            }
+            TaskStatus::Failed(error) => {
+                Notification::error(t!("TaskProgress.failed", title = task.title, error = error))
+            }
+            _ => return,
+        };
+        _ = window_handle.update(cx, |_, window, cx| {
+            window.push_notification(notification, cx)
+        });
+    })
+}
+
+/// Returns the label and the progress (in 0 ~ 100) of the running tasks to show in the
+/// status bar, `None` if there is no running task.
+pub(crate) fn status_bar_progress(cx: &App) -> Option<(SharedString, Option<f32>)> {
+    let progress = cx.try_global::<GlobalTaskProgress>()?.0.read(cx);
+    let count = progress.tasks.len();
+    match count {
+        0 => None,
+        1 => {
+            let task = &progress.tasks[0];
+            Some((
+                task.message.clone().unwrap_or_else(|| task.title.clone()),
+                task.progress.map(|value| value * 100.),
+            ))
+        }
+        _ => Some((
+            t!("TaskProgress.running", count = count),
+            progress.aggregate_progress().map(|value| value * 100.),
+        )),
+    }
+}
+
+/// The rows of the running tasks with the cancel buttons.
+///
+/// The view should observe the [`TaskProgress::global`] to render the changes.
+#[derive(IntoElement, Default)]
+pub struct TaskProgressList {}
+
+impl TaskProgressList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RenderOnce for TaskProgressList {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let tasks = TaskProgress::global(cx).read(cx).tasks.clone();
+
+        v_flex().gap_2().children(tasks.into_iter().map(|task| {
+            let id = task.id;
+            v_flex()
+                .gap_1()
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .text_sm()
+                        .when(task.progress.is_none(), |this| {
+                            this.child(Indicator::new().xsmall())
+                        })
+                        .child(h_flex().flex_1().overflow_hidden().child(task.title))
+                        .when(task.cancellable, |this| {
+                            this.child(
+                                Button::new(("cancel-task", id.0))
+                                    .ghost()
+                                    .xsmall()
+                                    .icon(IconName::Close)
+                                    .tooltip(t!("Modal.cancel"))
+                                    .on_click(move |_, _, cx| cancel(id, cx)),
+                            )
+                        }),
+                )
+                .when_some(task.progress, |this, value| {
+                    this.child(Progress::new().value(value * 100.))
+                })
+                .when_some(task.message, |this, message| {
+                    this.child(
+                        h_flex()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(message),
+                    )
+                })
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{aggregate_progress, TaskId, TaskInfo, TaskStatus};
+
+    fn task(progress: Option<f32>) -> TaskInfo {
+        TaskInfo {
+            id: TaskId(0),
+            title: "Task".into(),
+            message: None,
+            progress,
+            status: TaskStatus::Running,
+            cancellable: false,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_progress() {
+        assert_eq!(aggregate_progress(&[]), None);
+        assert_eq!(
+            aggregate_progress(&[task(Some(0.5)), task(Some(1.))]),
+            Some(0.75)
+        );
+        assert_eq!(aggregate_progress(&[task(Some(0.5)), task(None)]), None);
+    }
+}
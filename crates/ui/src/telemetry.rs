@@ -0,0 +1,113 @@
+//! The instrumentation hooks of the significant interactions of the components, to plug in
+//! the analytics of the app.
+//!
+//! ```ignore
+//! gpui_component::telemetry::set_instrumentation(cx, |interaction: &Interaction, _: &mut App| {
+//!     analytics::track(interaction.kind.as_str(), &interaction.id, &interaction.metadata);
+//! });
+//! ```
+use std::rc::Rc;
+
+use gpui::{App, ElementId, Global, SharedString};
+
+/// The kind of the [`Interaction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InteractionKind {
+    /// A [`Button`](crate::button::Button) is clicked.
+    Click,
+    /// A [`PopupMenu`](crate::popup_menu::PopupMenu) is opened.
+    MenuOpen,
+    /// An item of the [`PopupMenu`](crate::popup_menu::PopupMenu) is selected.
+    MenuSelect,
+    /// A tab of the [`TabBar`](crate::tab::TabBar) is selected.
+    TabSwitch,
+    /// A form is submitted, recorded by the app by [`record`].
+    Submit,
+}
+
+impl InteractionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Click => "click",
+            Self::MenuOpen => "menu_open",
+            Self::MenuSelect => "menu_select",
+            Self::TabSwitch => "tab_switch",
+            Self::Submit => "submit",
+        }
+    }
+}
+
+/// An interaction of a component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interaction {
+    pub kind: InteractionKind,
+    /// The element id of the component, `None` if the component has no id.
+    pub id: Option<ElementId>,
+    pub metadata: Vec<(SharedString, SharedString)>,
+}
+
+impl Interaction {
+    pub fn new(kind: InteractionKind, id: Option<ElementId>) -> Self {
+        Self {
+            kind,
+            id,
+            metadata: vec![],
+        }
+    }
+
+    /// Add a metadata of the interaction, e.g.: the label of the selected menu item.
+    pub fn metadata(
+        mut self,
+        key: impl Into<SharedString>,
+        value: impl Into<SharedString>,
+    ) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+}
+
+/// The instrumentation to receive the [`Interaction`]s of the components.
+pub trait Instrumentation: 'static {
+    fn on_interaction(&self, interaction: &Interaction, cx: &mut App);
+}
+
+impl<F> Instrumentation for F
+where
+    F: Fn(&Interaction, &mut App) + 'static,
+{
+    fn on_interaction(&self, interaction: &Interaction, cx: &mut App) {
+        self(interaction, cx)
+    }
+}
+
+#[derive(Default)]
+struct Telemetry {
+    instrumentation: Option<Rc<dyn Instrumentation>>,
+}
+
+impl Global for Telemetry {}
+
+/// Set the instrumentation of the interactions, the interactions are not recorded if it is not set.
+pub fn set_instrumentation(cx: &mut App, instrumentation: impl Instrumentation) {
+    cx.default_global::<Telemetry>().instrumentation = Some(Rc::new(instrumentation));
+}
+
+/// Returns true if the instrumentation is set.
+pub fn is_enabled(cx: &App) -> bool {
+    cx.try_global::<Telemetry>()
+        .map_or(false, |telemetry| telemetry.instrumentation.is_some())
+}
+
+/// Record the interaction by the instrumentation.
+///
+/// The interaction is built by `f` only if the instrumentation is set.
+pub fn record(cx: &mut App, f: impl FnOnce() -> Interaction) {
+    let Some(instrumentation) = cx
+        .try_global::<Telemetry>()
+        .and_then(|telemetry| telemetry.instrumentation.clone())
+    else {
+        return;
+    };
+
+    instrumentation.on_interaction(&f(), cx);
+}
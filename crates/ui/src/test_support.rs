@@ -0,0 +1,228 @@
+//! The helpers to test the components in the headless GPUI test context, enabled by the
+//! `test-support` feature.
+//!
+//! The elements are queried by the debug selectors, e.g.: `.debug_selector(|| "save".into())`,
+//! the rendered texts by [`ComponentTest::texts`], and the element tree by
+//! [`ComponentTest::elements`].
+//!
+//! ```ignore
+//! #[gpui::test]
+//! fn test_counter(cx: &mut TestAppContext) {
+//!     let mut test = ComponentTest::new(cx, |_, _| Counter::default());
+//!     test.click("increment");
+//!     test.keys("cmd-z");
+//!     assert_eq!(test.read(|counter, _| counter.count), 0);
+//!     assert!(test.has_text("Count: 0"));
+//! }
+//! ```
+//!
+//! The snapshot rendering to the images is not supported, the test platform of GPUI
+//! does not render the pixels.
+use std::collections::HashMap;
+
+use gpui::{
+    App, AppContext as _, Bounds, Context, Entity, Global, Modifiers, Pixels, Point, Render,
+    ScrollDelta, ScrollWheelEvent, SharedString, TestAppContext, VisualTestContext, Window,
+    WindowId,
+};
+
+use crate::{
+    accessibility::{rendered_accessibility, AccessibilityProperties, AccessibilityRole},
+    Root,
+};
+
+/// The texts of the labels and the text view paragraphs rendered in the windows.
+#[derive(Default)]
+struct RenderedTexts(HashMap<WindowId, Vec<SharedString>>);
+
+impl Global for RenderedTexts {}
+
+/// Clear the rendered texts of the window, called by the [`Root`] at the start of each frame.
+pub(crate) fn clear_texts(window: &Window, cx: &mut App) {
+    if let Some(texts) = cx
+        .default_global::<RenderedTexts>()
+        .0
+        .get_mut(&window.window_handle().window_id())
+    {
+        texts.clear();
+    }
+}
+
+/// Record the text rendered in the window.
+pub(crate) fn record_text(text: SharedString, window: &Window, cx: &mut App) {
+    cx.default_global::<RenderedTexts>()
+        .0
+        .entry(window.window_handle().window_id())
+        .or_default()
+        .push(text);
+}
+
+/// Initialize the components in the test context.
+pub fn init(cx: &mut TestAppContext) {
+    cx.update(|cx| crate::init(cx));
+}
+
+/// A component rendered in a test window, the window root is the [`Root`].
+pub struct ComponentTest<'a, V: 'static> {
+    pub view: Entity<V>,
+    pub cx: &'a mut VisualTestContext,
+}
+
+impl<'a, V: Render> ComponentTest<'a, V> {
+    /// Initialize the components and open a test window with the view built by `build`.
+    pub fn new(
+        cx: &'a mut TestAppContext,
+        build: impl FnOnce(&mut Window, &mut Context<V>) -> V,
+    ) -> Self {
+        init(cx);
+
+        let mut view = None;
+        let (_, cx) = cx.add_window_view(|window, cx| {
+            let entity = cx.new(|cx| build(window, cx));
+            view = Some(entity.clone());
+            Root::new(entity.into(), window, cx)
+        });
+        cx.run_until_parked();
+
+        Self {
+            view: view.expect("the view is built"),
+            cx,
+        }
+    }
+
+    /// Returns the bounds of the element with the debug selector in the last frame.
+    pub fn bounds(&mut self, selector: &'static str) -> Option<Bounds<Pixels>> {
+        self.cx.debug_bounds(selector)
+    }
+
+    fn center(&mut self, selector: &'static str) -> Point<Pixels> {
+        self.bounds(selector)
+            .unwrap_or_else(|| panic!("no element with the debug selector: {}", selector))
+            .center()
+    }
+
+    /// Click the center of the element with the debug selector.
+    pub fn click(&mut self, selector: &'static str) {
+        let position = self.center(selector);
+        self.cx.simulate_click(position, Modifiers::none());
+        self.cx.run_until_parked();
+    }
+
+    /// Dispatch the keystrokes to the focused element, e.g.: `cmd-a backspace`.
+    pub fn keys(&mut self, keystrokes: &str) {
+        self.cx.simulate_keystrokes(keystrokes);
+        self.cx.run_until_parked();
+    }
+
+    /// Input the text to the focused element.
+    pub fn input(&mut self, text: &str) {
+        self.cx.simulate_input(text);
+        self.cx.run_until_parked();
+    }
+
+    /// Scroll the element with the debug selector by the `delta`.
+    pub fn scroll(&mut self, selector: &'static str, delta: Point<Pixels>) {
+        let position = self.center(selector);
+        self.cx.simulate_event(ScrollWheelEvent {
+            position,
+            delta: ScrollDelta::Pixels(delta),
+            ..Default::default()
+        });
+        self.cx.run_until_parked();
+    }
+
+    /// Returns the texts of the labels and the text view paragraphs in the last frame,
+    /// in the render order.
+    pub fn texts(&mut self) -> Vec<SharedString> {
+        self.cx.update(|window, cx| {
+            cx.try_global::<RenderedTexts>()
+                .and_then(|texts| texts.0.get(&window.window_handle().window_id()))
+                .cloned()
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns true if any text in the last frame contains the `text`.
+    pub fn has_text(&mut self, text: &str) -> bool {
+        self.texts().iter().any(|rendered| rendered.contains(text))
+    }
+
+    /// Returns the accessibility properties of the elements in the last frame,
+    /// in the render order.
+    pub fn elements(&mut self) -> Vec<AccessibilityProperties> {
+        self.cx
+            .update(|window, cx| rendered_accessibility(window, cx).to_vec())
+    }
+
+    /// Returns the first element with the role and the accessible name in the last frame.
+    pub fn find(
+        &mut self,
+        role: AccessibilityRole,
+        label: &str,
+    ) -> Option<AccessibilityProperties> {
+        self.elements().into_iter().find(|properties| {
+            properties.role == Some(role) && properties.label.as_deref() == Some(label)
+        })
+    }
+
+    /// Update the view, the window is redrawn after the update.
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut V, &mut Window, &mut Context<V>) -> R) -> R {
+        let result = self.view.update_in(self.cx, f);
+        self.cx.run_until_parked();
+        result
+    }
+
+    /// Read the view.
+    pub fn read<R>(&mut self, f: impl FnOnce(&V, &App) -> R) -> R {
+        let view = self.view.clone();
+        self.cx.read(|cx| f(view.read(cx), cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{
+        Context, InteractiveElement as _, IntoElement, ParentElement as _, Render, TestAppContext,
+        Window,
+    };
+
+    use super::ComponentTest;
+    use crate::{accessibility::AccessibilityRole, button::Button, label::Label, v_flex};
+
+    #[derive(Default)]
+    struct Counter {
+        count: usize,
+    }
+
+    impl Render for Counter {
+        fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+            v_flex()
+                .child(Label::new(format!("Count: {}", self.count)))
+                .child(
+                    Button::new("increment")
+                        .label("Increment")
+                        .debug_selector(|| "increment".into())
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.count += 1;
+                            cx.notify();
+                        })),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_component_test(cx: &mut TestAppContext) {
+        let mut test = ComponentTest::new(cx, |_, _| Counter::default());
+        assert!(test.has_text("Count: 0"));
+
+        let button = test
+            .find(AccessibilityRole::Button, "Increment")
+            .expect("the button is rendered");
+        assert!(!button.disabled);
+
+        test.click("increment");
+        assert_eq!(test.read(|counter, _| counter.count), 1);
+        assert!(test.has_text("Count: 1"));
+        assert!(!test.has_text("Count: 0"));
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    fmt::Debug,
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+use gpui::{App, HighlightStyle, Hsla, SharedString};
+
+use crate::ActiveTheme as _;
+
+use super::element::LinkMark;
+
+type Highlights = Arc<Vec<(Range<usize>, HighlightStyle)>>;
+
+/// The prepared text of the paragraph (after the CJK spacing and hyphenation).
+///
+/// It is prepared once after parsing, the theme colors of the inline code and links
+/// are resolved on render and cached until the theme is changed.
+///
+/// It is `Send` to keep the node tree `Send`, the HTML is parsed in the background thread.
+pub(super) struct PreparedText {
+    pub(super) text: SharedString,
+    /// The highlights without the theme colors.
+    highlights: Vec<(Range<usize>, HighlightStyle)>,
+    /// The ranges of the inline code, highlighted by the theme accent color.
+    code_ranges: Vec<Range<usize>>,
+    pub(super) links: Arc<Vec<(Range<usize>, LinkMark)>>,
+    /// The ranges of the soft hyphens and CJK spacing inserted into the text, sorted.
+    pub(super) inserted: Arc<Vec<Range<usize>>>,
+    /// The locale used to insert the hyphens, `None` if not hyphenated.
+    hyphens: Option<String>,
+    cjk_spacing: bool,
+    /// The highlights resolved by the theme colors (accent, link).
+    resolved: Mutex<Option<((Hsla, Hsla), Highlights)>>,
+}
+
+impl PreparedText {
+    pub(super) fn new(
+        text: SharedString,
+        highlights: Vec<(Range<usize>, HighlightStyle)>,
+        code_ranges: Vec<Range<usize>>,
+        links: Vec<(Range<usize>, LinkMark)>,
+        inserted: Vec<Range<usize>>,
+        hyphens: Option<String>,
+        cjk_spacing: bool,
+    ) -> Self {
+        Self {
+            text,
+            highlights,
+            code_ranges,
+            links: Arc::new(links),
+            inserted: Arc::new(inserted),
+            hyphens,
+            cjk_spacing,
+            resolved: Mutex::new(None),
+        }
+    }
+
+    /// Returns the highlights with the theme colors.
+    pub(super) fn highlights(&self, cx: &App) -> Highlights {
+        let theme = (cx.theme().accent, cx.theme().link);
+        let mut resolved = self.resolved.lock().unwrap();
+        if let Some((resolved_theme, highlights)) = resolved.as_ref() {
+            if *resolved_theme == theme {
+                return highlights.clone();
+            }
+        }
+
+        let (accent, link) = theme;
+        let mut theme_highlights = self
+            .code_ranges
+            .iter()
+            .map(|range| {
+                let highlight = HighlightStyle {
+                    background_color: Some(accent),
+                    ..Default::default()
+                };
+                (range.clone(), highlight)
+            })
+            .chain(self.links.iter().map(|(range, _)| {
+                let highlight = HighlightStyle {
+                    color: Some(link),
+                    ..Default::default()
+                };
+                (range.clone(), highlight)
+            }))
+            .collect::<Vec<_>>();
+        theme_highlights.sort_by_key(|(range, _)| range.start);
+
+        let highlights = Arc::new(
+            gpui::combine_highlights(self.highlights.iter().cloned(), theme_highlights)
+                .collect::<Vec<_>>(),
+        );
+        *resolved = Some((theme, highlights.clone()));
+        highlights
+    }
+}
+
+/// The [`PreparedText`] of the paragraph, shared by the clones of the node.
+///
+/// It is derived from the text nodes, so it is ignored on the comparison.
+#[derive(Default, Clone)]
+pub(crate) struct PreparedCell(Arc<Mutex<Option<Arc<PreparedText>>>>);
+
+impl PreparedCell {
+    /// Returns the prepared text, or prepare it by `prepare` if it is not prepared with the
+    /// `hyphens` and `cjk_spacing`.
+    pub(super) fn get_or_prepare(
+        &self,
+        hyphens: Option<&str>,
+        cjk_spacing: bool,
+        prepare: impl FnOnce() -> PreparedText,
+    ) -> Arc<PreparedText> {
+        let mut cell = self.0.lock().unwrap();
+        if let Some(prepared) = cell.as_ref() {
+            if prepared.hyphens.as_deref() == hyphens && prepared.cjk_spacing == cjk_spacing {
+                return prepared.clone();
+            }
+        }
+
+        let prepared = Arc::new(prepare());
+        *cell = Some(prepared.clone());
+        prepared
+    }
+}
+
+impl PartialEq for PreparedCell {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Debug for PreparedCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PreparedCell").finish()
+    }
+}
@@ -0,0 +1,165 @@
+/// The space inserted between the CJK and Latin characters.
+///
+/// The six-per-em space is about 1/4 width of the CJK character, that is the common spacing
+/// used in the Chinese and Japanese typesetting.
+const AUTOSPACE: char = '\u{2006}';
+
+/// Returns true if the character is a CJK ideograph, Kana, Bopomofo or Hangul.
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}'
+        | '\u{2E80}'..='\u{2FDF}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{3100}'..='\u{318F}'
+        | '\u{31F0}'..='\u{31FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FA1F}'
+    )
+}
+
+/// Returns true if the character is a closing punctuation that should not start a line,
+/// e.g.: `。`, `，`, `」`.
+pub(crate) fn is_closing_punctuation(c: char) -> bool {
+    matches!(
+        c,
+        '、' | '。'
+            | '，'
+            | '．'
+            | '：'
+            | '；'
+            | '！'
+            | '？'
+            | '）'
+            | '」'
+            | '』'
+            | '】'
+            | '〉'
+            | '》'
+            | '〕'
+            | '］'
+            | '｝'
+            | '〙'
+            | '〛'
+            | '・'
+            | 'ー'
+            | '々'
+            | 'ゝ'
+            | 'ゞ'
+            | 'ヽ'
+            | 'ヾ'
+    )
+}
+
+fn is_latin(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '\u{00C0}'..='\u{024F}')
+}
+
+/// Insert a narrow space between the CJK and Latin characters for the mixed text,
+/// e.g.: `使用Rust开发` to `使用 Rust 开发`, this also gives a break opportunity between them.
+///
+/// Returns None if the text is not changed, otherwise the text and the inserted
+/// `(offset, len)` in the original text, use [`remap_range`](super::utils::remap_range) to map the ranges.
+pub(crate) fn prepare_text(text: &str) -> Option<(String, Vec<(usize, usize)>)> {
+    let mut out = String::new();
+    let mut inserted = vec![];
+    let mut prev: Option<char> = None;
+
+    for (ix, c) in text.char_indices() {
+        if let Some(prev) = prev {
+            let insert = if (is_cjk(prev) && is_latin(c)) || (is_latin(prev) && is_cjk(c)) {
+                Some(AUTOSPACE)
+            } else {
+                None
+            };
+
+            if let Some(insert) = insert {
+                if inserted.is_empty() {
+                    out.push_str(&text[..ix]);
+                }
+                out.push(insert);
+                inserted.push((ix, insert.len_utf8()));
+            }
+        }
+
+        if !inserted.is_empty() {
+            out.push(c);
+        }
+        prev = Some(c);
+    }
+
+    if inserted.is_empty() {
+        None
+    } else {
+        Some((out, inserted))
+    }
+}
+
+/// Adjust the soft wrap position `ix` of the line to avoid starting the next line with
+/// a closing punctuation, the previous character will be moved to the next line together.
+///
+/// This is used by the [`TextInput`](crate::input::TextInput) soft wrap only, the `StyledText` of the
+/// TextView and Label is wrapped inside GPUI, and there is no hook to adjust its line breaks.
+pub(crate) fn adjust_line_break(line: &str, ix: usize) -> usize {
+    let mut adjusted = ix;
+    while line[adjusted..]
+        .chars()
+        .next()
+        .map_or(false, is_closing_punctuation)
+    {
+        match line[..adjusted].char_indices().next_back() {
+            Some((prev_ix, _)) if prev_ix > 0 => adjusted = prev_ix,
+            // Keep the original position, if all the line is punctuation.
+            _ => return ix,
+        }
+    }
+
+    adjusted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adjust_line_break, is_cjk, is_closing_punctuation, prepare_text};
+
+    #[test]
+    fn test_is_cjk() {
+        assert!(is_cjk('中'));
+        assert!(is_cjk('か'));
+        assert!(is_cjk('カ'));
+        assert!(is_cjk('한'));
+        assert!(!is_cjk('a'));
+        assert!(!is_cjk('。'));
+        assert!(is_closing_punctuation('。'));
+        assert!(!is_closing_punctuation('「'));
+    }
+
+    #[test]
+    fn test_prepare_text() {
+        assert_eq!(prepare_text("Hello World"), None);
+        assert_eq!(prepare_text("你好世界"), None);
+        assert_eq!(prepare_text("你好，世界。"), None);
+
+        let (text, inserted) = prepare_text("使用Rust开发").unwrap();
+        assert_eq!(text, "使用\u{2006}Rust\u{2006}开发");
+        assert_eq!(inserted, vec![(6, 3), (10, 3)]);
+
+        let (text, _) = prepare_text("版本1.0发布").unwrap();
+        assert_eq!(text, "版本\u{2006}1.0\u{2006}发布");
+    }
+
+    #[test]
+    fn test_adjust_line_break() {
+        let line = "你好世界。再见";
+        assert_eq!(adjust_line_break(line, 6), 6);
+        // Break before `。`, move `界` to the next line.
+        assert_eq!(adjust_line_break(line, 12), 9);
+        assert_eq!(adjust_line_break(line, 15), 15);
+
+        let line = "他说：「好。」";
+        // Break before `」`, move `好。` to the next line.
+        assert_eq!(adjust_line_break(line, 18), 12);
+        assert_eq!(adjust_line_break("。。", 3), 3);
+    }
+}
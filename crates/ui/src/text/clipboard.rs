@@ -0,0 +1,57 @@
+//! The rich text clipboard, the content is written with both the plain text and the HTML.
+//!
+//! NOTE: The GPUI clipboard only has the plain text flavor, it can't write a `text/html`
+//! entry. So the HTML is kept in the metadata of the clipboard item, it is only read back by
+//! the GPUI applications (e.g.: [`read_html_from_clipboard`]), the other applications get the
+//! plain text, that is the Markdown source.
+use gpui::{App, ClipboardEntry, ClipboardItem, SharedString};
+use serde::{Deserialize, Serialize};
+
+use super::{element::Node, html::parse_html, markdown::markdown_to_node};
+
+#[derive(Serialize, Deserialize)]
+struct RichTextMetadata {
+    html: String,
+}
+
+fn node_clipboard_item(node: &Node, text: String) -> ClipboardItem {
+    let metadata = RichTextMetadata {
+        html: node.to_html(),
+    };
+    ClipboardItem::new_string_with_json_metadata(text, metadata)
+}
+
+/// Returns the clipboard item of the Markdown as the rich text, the plain text is the Markdown
+/// source, used by the Markdown editor to copy.
+pub(crate) fn markdown_clipboard_item(markdown: &str, cx: &mut App) -> ClipboardItem {
+    match markdown_to_node(markdown, cx) {
+        Ok(node) => node_clipboard_item(&node, markdown.to_string()),
+        Err(_) => ClipboardItem::new_string(markdown.to_string()),
+    }
+}
+
+/// Write the Markdown to the clipboard as the rich text, the plain text is the Markdown source.
+pub fn write_markdown_to_clipboard(markdown: &str, cx: &mut App) {
+    let item = markdown_clipboard_item(markdown, cx);
+    cx.write_to_clipboard(item);
+}
+
+/// Write the HTML to the clipboard as the rich text, the plain text is converted from the HTML.
+pub fn write_html_to_clipboard(html: &str, cx: &mut App) {
+    let item = match parse_html(html) {
+        Ok(node) => node_clipboard_item(&node, node.to_markdown()),
+        Err(_) => ClipboardItem::new_string(html.to_string()),
+    };
+    cx.write_to_clipboard(item);
+}
+
+/// Returns the HTML of the rich text in the clipboard, `None` if the clipboard has no rich text.
+pub fn read_html_from_clipboard(cx: &App) -> Option<SharedString> {
+    let item = cx.read_from_clipboard()?;
+    item.entries().iter().find_map(|entry| match entry {
+        ClipboardEntry::String(string) => string
+            .metadata_json::<RichTextMetadata>()
+            .map(|metadata| metadata.html.into()),
+        _ => None,
+    })
+}
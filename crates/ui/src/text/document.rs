@@ -0,0 +1,122 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{App, IntoElement, ParentElement as _, SharedString, Styled as _, Task, Window};
+
+use crate::{skeleton::Skeleton, v_flex};
+
+use super::element::Node;
+
+/// The documents larger than this (in bytes) are parsed in the background thread.
+const BACKGROUND_PARSE_THRESHOLD: usize = 64 * 1024;
+
+pub(super) type ParseResult = Result<Node, SharedString>;
+
+/// The parsed node tree of the [`super::TextView`] source.
+///
+/// The large documents are parsed in the background thread to keep the render path fast,
+/// the last result (or a placeholder) is rendered until the new result arrives.
+#[derive(Default)]
+pub(super) struct ParsedDocument {
+    root: Option<ParseResult>,
+    /// The result of the background parsing, taken on the next render.
+    pending: Rc<RefCell<Option<ParseResult>>>,
+    last_parsed: Option<Instant>,
+    _parse_task: Option<Task<()>>,
+    /// The task to render again after the debounce, to parse the latest source.
+    _debounce_task: Option<Task<()>>,
+}
+
+impl ParsedDocument {
+    /// Returns true if the source has been parsed at least once.
+    pub(super) fn is_parsed(&self) -> bool {
+        self.last_parsed.is_some()
+    }
+
+    /// Returns true if the changed source can be parsed now.
+    ///
+    /// If the last parsing is within the `debounce` duration, returns false and the view is
+    /// rendered again after the duration, so the latest source is parsed at the end.
+    pub(super) fn should_parse(
+        &mut self,
+        debounce: Duration,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> bool {
+        let elapsed = self
+            .last_parsed
+            .map_or(Duration::MAX, |last_parsed| last_parsed.elapsed());
+        if elapsed >= debounce {
+            self._debounce_task = None;
+            return true;
+        }
+
+        if self._debounce_task.is_none() {
+            let delay = debounce - elapsed;
+            let entity = window.current_view();
+            self._debounce_task = Some(window.spawn(cx, async move |cx| {
+                cx.background_executor().timer(delay).await;
+                _ = cx.update(|_, cx| cx.notify(entity));
+            }));
+        }
+        false
+    }
+
+    /// Parse the `raw` source.
+    ///
+    /// - `parse` is the part runs in the background thread, it must not depend on the [`App`].
+    /// - `build` converts the parsed result to the [`Node`] tree in the main thread.
+    pub(super) fn parse<T: Send + 'static>(
+        &mut self,
+        raw: SharedString,
+        parse: fn(&str) -> Result<T, SharedString>,
+        build: impl FnOnce(T, &mut App) -> Node + 'static,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.last_parsed = Some(Instant::now());
+        if raw.len() < BACKGROUND_PARSE_THRESHOLD {
+            // Cancel the parsing of the previous source.
+            self._parse_task = None;
+            self.pending.borrow_mut().take();
+            self.root = Some(parse(&raw).map(|value| build(value, cx)));
+            return;
+        }
+
+        let pending = Rc::new(RefCell::new(None));
+        self.pending = pending.clone();
+
+        let task = cx.background_executor().spawn(async move { parse(&raw) });
+        let entity = window.current_view();
+        self._parse_task = Some(window.spawn(cx, async move |cx| {
+            let result = task.await;
+            _ = cx.update(|_, cx| {
+                *pending.borrow_mut() = Some(result.map(|value| build(value, cx)));
+                cx.notify(entity);
+            });
+        }));
+    }
+
+    /// Returns the node tree to render, `None` if the first parsing is not finished yet.
+    pub(super) fn root(&mut self) -> Option<&ParseResult> {
+        if let Some(result) = self.pending.borrow_mut().take() {
+            self.root = Some(result);
+            self._parse_task = None;
+        }
+
+        self.root.as_ref()
+    }
+}
+
+/// A lightweight placeholder to render while the document is parsing.
+pub(super) fn render_placeholder() -> impl IntoElement {
+    v_flex()
+        .gap_2()
+        .child(Skeleton::new().w_1_2())
+        .child(Skeleton::new())
+        .child(Skeleton::new())
+        .child(Skeleton::new().w_3_4())
+}
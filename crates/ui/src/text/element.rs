@@ -1,16 +1,43 @@
-use std::ops::Range;
+use std::{
+    ops::Range,
+    sync::{Arc, OnceLock},
+};
 
 use gpui::{
-    div, img, prelude::FluentBuilder as _, px, relative, rems, AnyElement, App, DefiniteLength,
-    Div, ElementId, FontStyle, FontWeight, Half, HighlightStyle, InteractiveElement as _,
-    InteractiveText, IntoElement, Length, ObjectFit, ParentElement, Rems, RenderOnce, SharedString,
-    SharedUri, Styled, StyledImage as _, StyledText, Window,
+    canvas, div, img, prelude::FluentBuilder as _, px, relative, rems, uniform_list, AnyElement,
+    App, DefiniteLength, Div, ElementId, FontStyle, FontWeight, Half, HighlightStyle,
+    InteractiveElement as _, InteractiveText, IntoElement, Length, MouseButton, ObjectFit,
+    ParentElement, Pixels, Rems, RenderOnce, Resource, SharedString, SharedUri, Size,
+    StatefulInteractiveElement as _, Styled, StyledImage as _, StyledText, Window,
 };
 use markdown::mdast;
 
-use crate::{h_flex, highlighter::SyntaxHighlighter, v_flex, ActiveTheme as _, Icon, IconName};
+use crate::{
+    accessibility::{self, AccessibilityProperties, AccessibilityRole},
+    clipboard::Clipboard,
+    h_flex,
+    highlighter::SyntaxHighlighter,
+    i18n::t,
+    image_cache::{cached_image, ImageCache},
+    indicator::Indicator,
+    skeleton::Skeleton,
+    snap_stroke, v_flex, ActiveTheme as _, Icon, IconName, Root,
+};
 
-use super::{utils::list_item_prefix, TextViewStyle};
+use super::{
+    cache::{PreparedCell, PreparedText},
+    cjk,
+    footnote::{Footnotes, FOOTNOTE_URL_PREFIX},
+    hyphenation,
+    lightbox::Lightbox,
+    link::{self, OnLinkClick},
+    math::{self, MathBlock},
+    mermaid::MermaidBlock,
+    selection::Selection,
+    task_list::OnCheckboxToggle,
+    utils::{self, escape_html, list_item_prefix},
+    ImagePlaceholder, TextViewStyle,
+};
 
 #[allow(unused)]
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -56,10 +83,20 @@ impl PartialEq for ImageNode {
     }
 }
 
+/// Returns the display pixels of the length, used to decode the image to the display size.
+///
+/// The relative length is resolved by the viewport width.
+fn display_pixels(length: DefiniteLength, window: &Window) -> Pixels {
+    match length {
+        DefiniteLength::Absolute(length) => length.to_pixels(window.rem_size()),
+        DefiniteLength::Fraction(fraction) => window.viewport_size().width * fraction,
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct TextNode {
     /// The text content.
-    pub text: String,
+    pub text: SharedString,
     /// The text styles, each tuple contains the range of the text and the style.
     pub marks: Vec<(Range<usize>, InlineTextStyle)>,
 }
@@ -69,6 +106,8 @@ pub enum Paragraph {
     Texts {
         span: Option<Span>,
         children: Vec<TextNode>,
+        /// The text and highlights prepared from the children.
+        prepared: PreparedCell,
     },
     Image {
         span: Option<Span>,
@@ -81,6 +120,7 @@ impl Default for Paragraph {
         Self::Texts {
             span: None,
             children: vec![],
+            prepared: PreparedCell::default(),
         }
     }
 }
@@ -90,9 +130,10 @@ impl From<String> for Paragraph {
         Self::Texts {
             span: None,
             children: vec![TextNode {
-                text: value.clone(),
+                text: value.into(),
                 marks: vec![],
             }],
+            prepared: PreparedCell::default(),
         }
     }
 }
@@ -101,12 +142,34 @@ impl From<String> for Paragraph {
 pub struct Table {
     pub children: Vec<TableRow>,
     pub column_aligns: Vec<ColumnumnAlign>,
+    /// The text length of the longest cell of each column, see [`Table::update_col_lens`].
+    col_lens: Vec<usize>,
 }
 
 impl Table {
     pub(crate) fn column_align(&self, index: usize) -> ColumnumnAlign {
         self.column_aligns.get(index).copied().unwrap_or_default()
     }
+
+    /// Update the length of the columns to layout the widths, it should be called after the
+    /// rows are parsed, so the rendering does not scan the cells.
+    pub(crate) fn update_col_lens(&mut self) {
+        const DEFAULT_LENGTH: usize = 5;
+
+        self.col_lens.clear();
+        for row in self.children.iter() {
+            for (ix, cell) in row.children.iter().enumerate() {
+                if self.col_lens.len() <= ix {
+                    self.col_lens.push(DEFAULT_LENGTH);
+                }
+
+                let len = cell.children.text_len();
+                if len > self.col_lens[ix] {
+                    self.col_lens[ix] = len;
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
@@ -142,7 +205,12 @@ pub struct TableCell {
 impl Paragraph {
     pub fn clear(&mut self) {
         match self {
-            Self::Texts { children, .. } => children.clear(),
+            Self::Texts {
+                children, prepared, ..
+            } => {
+                children.clear();
+                *prepared = PreparedCell::default();
+            }
             Self::Image { .. } => *self = Self::default(),
         }
     }
@@ -151,6 +219,38 @@ impl Paragraph {
         matches!(self, Self::Image { .. })
     }
 
+    /// Insert the soft hyphens into the text nodes by the patterns of the current locale.
+    ///
+    /// Returns the ranges of the soft hyphens in each text node.
+    fn hyphenate(children: &mut [TextNode]) -> Vec<Vec<Range<usize>>> {
+        let Some(patterns) = hyphenation::current_patterns() else {
+            return vec![];
+        };
+
+        children
+            .iter_mut()
+            .map(|node| {
+                let Some((text, inserted)) = hyphenation::hyphenate_text(&node.text, &patterns)
+                else {
+                    return vec![];
+                };
+                node.text = text.into();
+                for (range, _) in node.marks.iter_mut() {
+                    *range = utils::remap_range(range, &inserted);
+                }
+                utils::inserted_ranges(&inserted)
+            })
+            .collect()
+    }
+
+    fn shift_span(&mut self, delta: isize) {
+        let (Self::Texts { span, .. } | Self::Image { span, .. }) = self;
+        if let Some(span) = span {
+            span.start = span.start.saturating_add_signed(delta);
+            span.end = span.end.saturating_add_signed(delta);
+        }
+    }
+
     pub fn set_span(&mut self, span: Span) {
         match self {
             Self::Texts { span: s, .. } => *s = Some(span),
@@ -159,17 +259,19 @@ impl Paragraph {
     }
 
     pub fn push_str(&mut self, text: &str) {
-        if let Self::Texts { children, .. } = self {
-            children.push(TextNode {
-                text: text.to_string(),
-                marks: vec![(0..text.len(), InlineTextStyle::default())],
-            });
-        }
+        self.push(TextNode {
+            text: text.to_string().into(),
+            marks: vec![(0..text.len(), InlineTextStyle::default())],
+        });
     }
 
     pub fn push(&mut self, text: TextNode) {
-        if let Self::Texts { children, .. } = self {
+        if let Self::Texts {
+            children, prepared, ..
+        } = self
+        {
             children.push(text);
+            *prepared = PreparedCell::default();
         }
     }
 
@@ -203,13 +305,17 @@ impl Paragraph {
     /// - Returns `true` if other have merge into self.
     /// - Returns `false` if not able to merge.
     pub fn try_merge(&mut self, other: &Self) -> bool {
-        if let Self::Texts { children, .. } = self {
+        if let Self::Texts {
+            children, prepared, ..
+        } = self
+        {
             if let Self::Texts {
                 children: other_children,
                 ..
             } = other
             {
                 children.extend(other_children.clone());
+                *prepared = PreparedCell::default();
                 return true;
             }
         }
@@ -218,11 +324,16 @@ impl Paragraph {
     }
 }
 
+/// The code blocks larger than this (in bytes) are highlighted in the background thread.
+const BACKGROUND_HIGHLIGHT_THRESHOLD: usize = 16 * 1024;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct CodeBlock {
     code: SharedString,
     lang: Option<SharedString>,
-    styles: Vec<(Range<usize>, HighlightStyle)>,
+    /// The highlight styles, the large code is rendered without highlights until
+    /// the background highlighting is finished.
+    styles: Arc<OnceLock<Vec<(Range<usize>, HighlightStyle)>>>,
 }
 
 impl CodeBlock {
@@ -233,11 +344,27 @@ impl CodeBlock {
         cx: &mut App,
     ) -> Self {
         let theme = cx.theme().highlight_theme.clone();
-        let mut styles = vec![];
+        let styles = Arc::new(OnceLock::new());
         if let Some(lang) = &lang {
             let mut highlighter = SyntaxHighlighter::new(&lang, cx);
-            highlighter.update(&(0..0), &code, "", cx);
-            styles = highlighter.styles(&(0..code.len()), &theme);
+            if code.len() < BACKGROUND_HIGHLIGHT_THRESHOLD {
+                highlighter.update(&(0..0), &code, "", cx);
+                _ = styles.set(highlighter.styles(&(0..code.len()), &theme));
+            } else {
+                let task = cx.background_executor().spawn({
+                    let code = code.clone();
+                    async move {
+                        highlighter.update_text(&(0..0), &code, "");
+                        highlighter.styles(&(0..code.len()), &theme)
+                    }
+                });
+                let styles = styles.clone();
+                cx.spawn(async move |cx| {
+                    _ = styles.set(task.await);
+                    _ = cx.update(|cx| cx.refresh_windows());
+                })
+                .detach();
+            }
         };
 
         Self { code, lang, styles }
@@ -270,16 +397,71 @@ pub enum Node {
         spread: bool,
         /// Whether the list item is checked, if None, it's not a checkbox
         checked: Option<bool>,
+        /// The span of the checkbox marker (e.g.: `[ ]`) in the source.
+        span: Option<Span>,
     },
     CodeBlock(CodeBlock),
-    Table(Table),
+    /// The block math, e.g.: `$$...$$`.
+    Math(MathBlock),
+    /// The diagram of the ` ```mermaid ` code block.
+    Mermaid(MermaidBlock),
+    /// Shared by the clones of the node, the rows of the large table are rendered lazily.
+    Table(Arc<Table>),
     Break {
         html: bool,
     },
     Divider,
+    /// The footnote definitions at the end of the document.
+    Footnotes(Vec<Footnote>),
+    /// The glossary-style list of the terms and the definitions, e.g.: `<dl>` or `Term\n: Definition`.
+    DefinitionList(Vec<DefinitionItem>),
+    /// The collapsible section of the `<details>` and `<summary>`.
+    Details(DetailsBlock),
     Unknown,
 }
 
+/// The collapsible section of the [`Node::Details`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetailsBlock {
+    /// The position in the document order to key the open state, so it is kept after the source
+    /// is re-parsed, see [`Node::number_details`].
+    pub(super) id: usize,
+    /// The summary, empty to show the default "Details".
+    pub summary: Paragraph,
+    pub children: Vec<Node>,
+    /// Whether the section is open by default, the `open` attribute.
+    pub open: bool,
+}
+
+impl DetailsBlock {
+    pub(super) fn new(summary: Paragraph, children: Vec<Node>, open: bool) -> Self {
+        Self {
+            id: 0,
+            summary,
+            children,
+            open,
+        }
+    }
+}
+
+/// A term of the [`Node::DefinitionList`] with the definitions.
+///
+/// The `definitions` is empty if the term shares the definitions of the next term.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefinitionItem {
+    pub term: Paragraph,
+    pub definitions: Vec<Node>,
+}
+
+/// A footnote definition, referenced by `[^label]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Footnote {
+    /// The normalized identifier of the label, used as the id of the reference url.
+    pub id: SharedString,
+    pub label: SharedString,
+    pub children: Vec<Node>,
+}
+
 impl Node {
     pub(super) fn is_list_item(&self) -> bool {
         matches!(self, Self::ListItem { .. })
@@ -289,6 +471,164 @@ impl Node {
         matches!(self, Self::Break { .. })
     }
 
+    /// Returns true if the node is a list item with only one paragraph (not a todo item).
+    fn is_simple_list_item(&self) -> bool {
+        match self {
+            Self::ListItem {
+                children,
+                spread: false,
+                checked: None,
+                ..
+            } => matches!(children.as_slice(), [Self::Paragraph(_)]),
+            _ => false,
+        }
+    }
+
+    /// Visit the paragraphs of the node, the `bool` is false for the heading and table cell.
+    pub(super) fn for_each_paragraph(&mut self, f: &mut impl FnMut(&mut Paragraph, bool)) {
+        match self {
+            Self::Root { children }
+            | Self::Blockquote { children }
+            | Self::List { children, .. }
+            | Self::ListItem { children, .. } => {
+                for child in children.iter_mut() {
+                    child.for_each_paragraph(f);
+                }
+            }
+            Self::Details(details) => {
+                f(&mut details.summary, false);
+                for child in details.children.iter_mut() {
+                    child.for_each_paragraph(f);
+                }
+            }
+            Self::Footnotes(footnotes) => {
+                for child in footnotes
+                    .iter_mut()
+                    .flat_map(|footnote| footnote.children.iter_mut())
+                {
+                    child.for_each_paragraph(f);
+                }
+            }
+            Self::DefinitionList(items) => {
+                for item in items.iter_mut() {
+                    f(&mut item.term, false);
+                    for child in item.definitions.iter_mut() {
+                        child.for_each_paragraph(f);
+                    }
+                }
+            }
+            Self::Paragraph(paragraph) => f(paragraph, true),
+            Self::Heading { children, .. } => f(children, false),
+            Self::Table(table) => {
+                for cell in Arc::make_mut(table)
+                    .children
+                    .iter_mut()
+                    .flat_map(|row| row.children.iter_mut())
+                {
+                    f(&mut cell.children, false);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Visit the checkbox spans of the task list items of the node.
+    pub(super) fn for_each_checkbox_span(&mut self, f: &mut impl FnMut(&mut Option<Span>)) {
+        match self {
+            Self::ListItem { children, span, .. } => {
+                f(span);
+                for child in children.iter_mut() {
+                    child.for_each_checkbox_span(f);
+                }
+            }
+            Self::Root { children }
+            | Self::Blockquote { children }
+            | Self::List { children, .. }
+            | Self::Details(DetailsBlock { children, .. }) => {
+                for child in children.iter_mut() {
+                    child.for_each_checkbox_span(f);
+                }
+            }
+            Self::Footnotes(footnotes) => {
+                for child in footnotes
+                    .iter_mut()
+                    .flat_map(|footnote| footnote.children.iter_mut())
+                {
+                    child.for_each_checkbox_span(f);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Number the [`Node::Details`] in the document order from the `next`, it should be called
+    /// on the whole document after it is parsed.
+    pub(super) fn number_details(&mut self, next: &mut usize) {
+        let children = match self {
+            Self::Details(details) => {
+                details.id = *next;
+                *next += 1;
+                &mut details.children
+            }
+            Self::Root { children }
+            | Self::Blockquote { children }
+            | Self::List { children, .. }
+            | Self::ListItem { children, .. } => children,
+            Self::Footnotes(footnotes) => {
+                for child in footnotes
+                    .iter_mut()
+                    .flat_map(|footnote| footnote.children.iter_mut())
+                {
+                    child.number_details(next);
+                }
+                return;
+            }
+            Self::DefinitionList(items) => {
+                for child in items
+                    .iter_mut()
+                    .flat_map(|item| item.definitions.iter_mut())
+                {
+                    child.number_details(next);
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        for child in children.iter_mut() {
+            child.number_details(next);
+        }
+    }
+
+    /// Move the spans of the paragraphs by `delta` bytes, used to reuse the node after the source is changed.
+    pub(super) fn shift_spans(&mut self, delta: isize) {
+        self.for_each_paragraph(&mut |paragraph, _| paragraph.shift_span(delta));
+        self.for_each_checkbox_span(&mut |span| {
+            if let Some(span) = span {
+                span.start = span.start.saturating_add_signed(delta);
+                span.end = span.end.saturating_add_signed(delta);
+            }
+        });
+    }
+
+    /// Prepare the text and highlights of the paragraphs after parsing, to avoid building them on render.
+    pub(super) fn prepare(&mut self, style: &TextViewStyle) {
+        self.for_each_paragraph(&mut |paragraph, is_block| {
+            if let Paragraph::Texts {
+                children, prepared, ..
+            } = paragraph
+            {
+                // Only the block paragraphs are hyphenated, see `Node::render`.
+                Paragraph::prepared_text(
+                    children,
+                    prepared,
+                    style.hyphens && is_block,
+                    style.cjk_spacing,
+                );
+            }
+        });
+    }
+
     /// Combine all children, omitting the empt parent nodes.
     pub(super) fn compact(&self) -> Node {
         match self {
@@ -307,95 +647,362 @@ impl Node {
 
 impl RenderOnce for Paragraph {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
-        match self {
-            Self::Texts { span, children } => {
-                let mut text = String::new();
-                let mut highlights: Vec<(Range<usize>, HighlightStyle)> = vec![];
-                let mut links: Vec<(Range<usize>, LinkMark)> = vec![];
-                let mut offset = 0;
-
-                for text_node in children.into_iter() {
-                    let text_len = text_node.text.len();
-                    let part = if text.len() == 0 {
-                        // trim start for first text
-                        text_node.text.trim_start()
-                    } else {
-                        text_node.text.as_str()
-                    };
-                    text.push_str(&part);
-
-                    let mut node_highlights = vec![];
-                    for (range, style) in text_node.marks {
-                        let inner_range = (offset + range.start)..(offset + range.end);
+        self.render_with(false, &RenderContext::default(), window, cx)
+    }
+}
 
-                        let mut highlight = HighlightStyle::default();
-                        if style.bold {
-                            highlight.font_weight = Some(FontWeight::BOLD);
-                        }
-                        if style.italic {
-                            highlight.font_style = Some(FontStyle::Italic);
-                        }
-                        if style.strikethrough {
-                            highlight.strikethrough = Some(gpui::StrikethroughStyle {
-                                thickness: gpui::px(1.),
-                                ..Default::default()
-                            });
-                        }
-                        if style.code {
-                            highlight.background_color = Some(cx.theme().accent);
-                        }
+impl Paragraph {
+    /// Build the text and highlights of the text nodes, the theme colors are resolved on render.
+    fn prepare(children: &[TextNode], hyphens: Option<&str>, cjk_spacing: bool) -> PreparedText {
+        let mut text = String::new();
+        let mut highlights: Vec<(Range<usize>, HighlightStyle)> = vec![];
+        let mut code_ranges: Vec<Range<usize>> = vec![];
+        let mut links: Vec<(Range<usize>, LinkMark)> = vec![];
+        let mut inserted: Vec<Range<usize>> = vec![];
+        let mut offset = 0;
+
+        let mut children = children.to_vec();
+        let hyphenated = if hyphens.is_some() {
+            Self::hyphenate(&mut children)
+        } else {
+            vec![]
+        };
 
-                        if let Some(link_mark) = style.link {
-                            highlight.color = Some(cx.theme().link);
-                            highlight.underline = Some(gpui::UnderlineStyle {
-                                thickness: gpui::px(1.),
-                                ..Default::default()
-                            });
+        for (ix, text_node) in children.into_iter().enumerate() {
+            let text_len = text_node.text.len();
+            let part = if text.len() == 0 {
+                // trim start for first text
+                text_node.text.trim_start()
+            } else {
+                &text_node.text[..]
+            };
+
+            // The soft hyphens of the node in the paragraph text.
+            let trimmed = text_len - part.len();
+            for range in hyphenated.get(ix).into_iter().flatten() {
+                if range.start >= trimmed {
+                    inserted.push(
+                        (text.len() + range.start - trimmed)..(text.len() + range.end - trimmed),
+                    );
+                }
+            }
+            text.push_str(&part);
 
-                            links.push((inner_range.clone(), link_mark));
-                        }
+            let mut node_highlights = vec![];
+            for (range, style) in text_node.marks {
+                let inner_range = (offset + range.start)..(offset + range.end);
 
-                        node_highlights.push((inner_range, highlight));
-                    }
+                let mut highlight = HighlightStyle::default();
+                if style.bold {
+                    highlight.font_weight = Some(FontWeight::BOLD);
+                }
+                if style.italic {
+                    highlight.font_style = Some(FontStyle::Italic);
+                }
+                if style.strikethrough {
+                    highlight.strikethrough = Some(gpui::StrikethroughStyle {
+                        thickness: gpui::px(1.),
+                        ..Default::default()
+                    });
+                }
+                if style.code {
+                    code_ranges.push(inner_range.clone());
+                }
 
-                    highlights = gpui::combine_highlights(highlights, node_highlights).collect();
+                if let Some(link_mark) = style.link {
+                    highlight.underline = Some(gpui::UnderlineStyle {
+                        thickness: gpui::px(1.),
+                        ..Default::default()
+                    });
 
-                    offset += text_len;
+                    links.push((inner_range.clone(), link_mark));
                 }
 
+                node_highlights.push((inner_range, highlight));
+            }
+
+            highlights = gpui::combine_highlights(highlights, node_highlights).collect();
+
+            offset += text_len;
+        }
+
+        // Add the spacing between the CJK and Latin characters.
+        if let Some((prepared, spacing)) = cjk_spacing.then(|| cjk::prepare_text(&text)).flatten() {
+            text = prepared;
+            for (range, _) in highlights.iter_mut() {
+                *range = utils::remap_range(range, &spacing);
+            }
+            for range in code_ranges.iter_mut() {
+                *range = utils::remap_range(range, &spacing);
+            }
+            for (range, _) in links.iter_mut() {
+                *range = utils::remap_range(range, &spacing);
+            }
+            for range in inserted.iter_mut() {
+                *range = utils::remap_range(range, &spacing);
+            }
+            inserted.extend(utils::inserted_ranges(&spacing));
+            inserted.sort_by_key(|range| range.start);
+        }
+
+        PreparedText::new(
+            text.into(),
+            highlights,
+            code_ranges,
+            links,
+            inserted,
+            hyphens.map(|locale| locale.to_string()),
+            cjk_spacing,
+        )
+    }
+
+    /// Prepare the text of the text nodes if it is not prepared yet.
+    ///
+    /// The hyphens are inserted by the patterns of the current locale if `hyphens` is true,
+    /// and the spacing between the CJK and Latin characters if `cjk_spacing` is true.
+    fn prepared_text(
+        children: &[TextNode],
+        prepared: &PreparedCell,
+        hyphens: bool,
+        cjk_spacing: bool,
+    ) -> Arc<PreparedText> {
+        let locale = hyphens.then(|| crate::locale().to_string());
+        let hyphens = locale.as_deref();
+        prepared.get_or_prepare(hyphens, cjk_spacing, || {
+            Self::prepare(children, hyphens, cjk_spacing)
+        })
+    }
+
+    /// Returns the builder of the fallback of the image failed to load, it shows the alt text
+    /// and retries to load the image on click.
+    fn image_fallback(
+        id: ElementId,
+        image: &ImageNode,
+        resource: &Resource,
+        cx: &App,
+    ) -> impl Fn() -> AnyElement + 'static {
+        let label: SharedString = image
+            .alt
+            .clone()
+            .filter(|alt| !alt.is_empty())
+            .unwrap_or_else(|| image.url.to_string().into());
+        let resource = resource.clone();
+        let (border, foreground, hover) = (
+            cx.theme().border,
+            cx.theme().muted_foreground,
+            cx.theme().accent,
+        );
+        let radius = cx.theme().radius;
+
+        move || {
+            let resource = resource.clone();
+            h_flex()
+                .id(id.clone())
+                .gap_2()
+                .px_3()
+                .py_2()
+                .max_w_full()
+                .border_1()
+                .border_color(border)
+                .rounded(radius)
+                .text_sm()
+                .text_color(foreground)
+                .cursor_pointer()
+                .hover(|this| this.bg(hover))
+                .child(Icon::new(IconName::ImageOff).flex_shrink_0())
+                .child(div().truncate().child(label.clone()))
+                .child(
+                    div()
+                        .flex_shrink_0()
+                        .text_xs()
+                        .child(t!("TextView.image_retry")),
+                )
+                .on_click(move |_, _, cx| {
+                    cx.stop_propagation();
+                    ImageCache::invalidate(resource.clone(), cx);
+                })
+                .into_any_element()
+        }
+    }
+
+    /// Render the paragraph, the hyphens are inserted if `hyphens` is true.
+    fn render_with(
+        &self,
+        hyphens: bool,
+        ctx: &RenderContext,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> AnyElement {
+        match self {
+            Self::Texts {
+                span,
+                children,
+                prepared,
+            } => {
+                let prepared =
+                    Self::prepared_text(children, prepared, hyphens, ctx.style.cjk_spacing);
+                #[cfg(any(test, feature = "test-support"))]
+                crate::test_support::record_text(prepared.text.clone(), window, cx);
+
                 let text_style = window.text_style();
                 let element_id: ElementId = span.unwrap_or_default().into();
-                let styled_text =
-                    StyledText::new(text).with_default_highlights(&text_style, highlights);
+                let styled_text = Selection::styled_text(
+                    ctx.selection.as_ref(),
+                    prepared.text.clone(),
+                    prepared.inserted.clone(),
+                    cx,
+                    |selected| {
+                        let highlights = prepared.highlights(cx);
+                        StyledText::new(prepared.text.clone()).with_default_highlights(
+                            &text_style,
+                            gpui::combine_highlights(highlights.iter().cloned(), selected),
+                        )
+                    },
+                );
+                let text_layout = styled_text.layout().clone();
+                let links = prepared.links.clone();
+                for (range, link) in links.iter() {
+                    let label = prepared.text[range.clone()].replace(hyphenation::SOFT_HYPHEN, "");
+                    let mut properties = AccessibilityProperties::new(AccessibilityRole::Link)
+                        .label(Some(label))
+                        .value(link.url.clone());
+                    properties.description = link.title.clone();
+                    accessibility::record_rendered(properties, window, cx);
+                }
+
+                let footnotes = ctx.footnotes.clone();
+                let on_link_click = ctx.on_link_click.clone();
                 let link_ranges = links
                     .iter()
                     .map(|(range, _)| range.clone())
                     .collect::<Vec<_>>();
 
-                InteractiveText::new(element_id, styled_text)
-                    .on_click(link_ranges, {
-                        let links = links.clone();
-                        move |ix, _, cx| {
-                            if let Some((_, link)) = &links.get(ix) {
-                                // Stop propagation to prevent the parent element from handling the event.
-                                //
-                                // For example the text in a checkbox label, click link need avoid toggle check state.
-                                cx.stop_propagation();
-                                cx.open_url(&link.url);
+                let text = InteractiveText::new(element_id, styled_text).on_click(
+                    link_ranges,
+                    move |ix, window, cx| {
+                        if let Some((_, link)) = &links.get(ix) {
+                            // Stop propagation to prevent the parent element from handling the event.
+                            //
+                            // For example the text in a checkbox label, click link need avoid toggle check state.
+                            cx.stop_propagation();
+                            if let Some(id) = link.url.strip_prefix(FOOTNOTE_URL_PREFIX) {
+                                if let Some(footnotes) = &footnotes {
+                                    footnotes.scroll_to(id, window);
+                                }
+                                return;
                             }
+                            link::open(on_link_click.as_ref(), &link.url, window, cx);
                         }
-                    })
+                    },
+                );
+
+                if !prepared.text.contains(hyphenation::SOFT_HYPHEN) {
+                    return text.into_any_element();
+                }
+
+                // Draw the hyphens at the line breaks of the soft hyphens.
+                div()
+                    .relative()
+                    .child(text)
+                    .child(hyphenation::hyphen_overlay(
+                        prepared.text.clone(),
+                        text_layout,
+                    ))
                     .into_any_element()
             }
-            Self::Image { image, .. } => img(image.url)
-                .object_fit(ObjectFit::Contain)
-                .max_w(relative(1.))
-                .when_some(image.width, |this, width| this.w(width))
-                .into_any_element(),
+            Self::Image { span, image } => {
+                // The image is never wider than the viewport (`max_w(relative(1.))`).
+                let viewport_width = window.viewport_size().width;
+                let max_width = image.width.map_or(viewport_width, |width| {
+                    display_pixels(width, window).min(viewport_width)
+                });
+                let max_height = image.height.map(|height| display_pixels(height, window));
+                let resource = Resource::Uri(image.url.clone());
+
+                // The placeholders are in the size of the image, or in the default size if
+                // the size is unknown.
+                let placeholder_width = image.width.unwrap_or(IMAGE_PLACEHOLDER_SIZE.width.into());
+                let placeholder_height =
+                    image.height.unwrap_or(IMAGE_PLACEHOLDER_SIZE.height.into());
+                let placeholder = ctx.style.image_placeholder;
+                let fallback =
+                    Self::image_fallback(span.unwrap_or_default().into(), image, &resource, cx);
+
+                let lightbox = ctx
+                    .lightbox
+                    .clone()
+                    .filter(|_| ctx.style.image_lightbox)
+                    .map(|lightbox| (lightbox, resource.clone()));
+
+                let el = img(cached_image(resource, Some(max_width), max_height))
+                    .object_fit(ObjectFit::Contain)
+                    .max_w(relative(1.))
+                    .when_some(image.width, |this, width| this.w(width))
+                    .with_loading(move || match placeholder {
+                        ImagePlaceholder::Skeleton => Skeleton::new()
+                            .w(placeholder_width)
+                            .h(placeholder_height)
+                            .max_w_full()
+                            .into_any_element(),
+                        ImagePlaceholder::Spinner => div()
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .w(placeholder_width)
+                            .h(placeholder_height)
+                            .max_w_full()
+                            .child(Indicator::new())
+                            .into_any_element(),
+                        ImagePlaceholder::None => div().into_any_element(),
+                    })
+                    .with_fallback(fallback);
+
+                match lightbox {
+                    Some((lightbox, resource)) => div()
+                        .id(span.unwrap_or_default())
+                        .max_w(relative(1.))
+                        .cursor_pointer()
+                        .child(el)
+                        .on_click(move |_, window, cx| {
+                            cx.stop_propagation();
+                            lightbox.open(resource.clone(), window, cx);
+                        })
+                        .into_any_element(),
+                    None => el.into_any_element(),
+                }
+            }
         }
     }
 }
 
+/// The size of the image placeholders if the size of the image is unknown.
+const IMAGE_PLACEHOLDER_SIZE: Size<Pixels> = Size {
+    width: px(320.),
+    height: px(180.),
+};
+/// The tables with more rows than this are virtualized, only the visible rows are rendered.
+const VIRTUAL_TABLE_ROWS: usize = 100;
+/// The max height of the virtualized table body.
+const VIRTUAL_TABLE_MAX_HEIGHT: Pixels = px(480.);
+/// The lists with more items than this are laid out flat if all the items are simple.
+const FLAT_LIST_ITEMS: usize = 50;
+
+/// The style and the state of a document passed to the nodes in rendering.
+#[derive(Clone, Default)]
+pub(super) struct RenderContext {
+    pub(super) style: TextViewStyle,
+    /// The selection of the document, the texts are registered to it on render.
+    pub(super) selection: Option<Selection>,
+    /// The handler of the link clicks, the links are opened by the router if it is `None`.
+    pub(super) on_link_click: Option<OnLinkClick>,
+    /// The handler of the task list checkboxes, the checkboxes are read-only if it is `None`.
+    pub(super) on_checkbox_toggle: Option<OnCheckboxToggle>,
+    /// The lightbox to view the images of the document.
+    pub(super) lightbox: Option<Lightbox>,
+    /// The footnote definitions of the document, to scroll to the definition by the reference.
+    pub(super) footnotes: Option<Footnotes>,
+}
+
 #[derive(Default)]
 pub(crate) struct ListState {
     todo: bool,
@@ -405,18 +1012,19 @@ pub(crate) struct ListState {
 
 impl Node {
     fn render_list_item(
-        item: Node,
+        item: &Node,
         ix: usize,
         state: ListState,
-        text_view_style: &TextViewStyle,
+        ctx: &RenderContext,
         window: &mut Window,
         cx: &mut App,
     ) -> impl IntoElement {
-        match item {
+        match *item {
             Node::ListItem {
-                children,
+                ref children,
                 spread,
                 checked,
+                span,
             } => v_flex()
                 .when(spread, |this| this.child(div()))
                 .children({
@@ -427,7 +1035,7 @@ impl Node {
                                 let last_not_list = child_ix > 0
                                     && !matches!(children[child_ix - 1], Node::List { .. });
 
-                                let text = child.clone().render(
+                                let text = child.render(
                                     Some(ListState {
                                         depth: state.depth + 1,
                                         ordered: state.ordered,
@@ -435,7 +1043,7 @@ impl Node {
                                     }),
                                     false,
                                     true,
-                                    text_view_style,
+                                    ctx,
                                     window,
                                     cx,
                                 );
@@ -465,7 +1073,9 @@ impl Node {
                                             ))
                                         })
                                         .when_some(checked, |this, checked| {
-                                            // Todo list checkbox
+                                            // Todo list checkbox, toggled by the handler if any.
+                                            let on_toggle =
+                                                span.zip(ctx.on_checkbox_toggle.clone());
                                             this.child(
                                                 div()
                                                     .flex()
@@ -484,14 +1094,31 @@ impl Node {
                                                                 .size_2()
                                                                 .text_xs(),
                                                         )
-                                                    }),
+                                                    })
+                                                    .when_some(
+                                                        on_toggle,
+                                                        |this, (span, on_toggle)| {
+                                                            this.cursor_pointer().on_mouse_down(
+                                                                MouseButton::Left,
+                                                                move |_, window, cx| {
+                                                                    cx.stop_propagation();
+                                                                    on_toggle(
+                                                                        span.start..span.end,
+                                                                        !checked,
+                                                                        window,
+                                                                        cx,
+                                                                    );
+                                                                },
+                                                            )
+                                                        },
+                                                    ),
                                             )
                                         })
                                         .child(div().overflow_hidden().child(text)),
                                 );
                             }
                             Node::List { .. } => {
-                                items.push(div().ml(rems(1.)).child(child.clone().render(
+                                items.push(div().ml(rems(1.)).child(child.render(
                                     Some(ListState {
                                         depth: state.depth + 1,
                                         ordered: state.ordered,
@@ -499,7 +1126,7 @@ impl Node {
                                     }),
                                     true,
                                     true,
-                                    text_view_style,
+                                    ctx,
                                     window,
                                     cx,
                                 )))
@@ -514,99 +1141,177 @@ impl Node {
         }
     }
 
-    fn render_table(item: &Node, _: &mut Window, cx: &mut App) -> impl IntoElement {
-        const DEFAULT_LENGTH: usize = 5;
-        const MAX_LENGTH: usize = 150;
-        let col_lens = match item {
-            Node::Table(table) => {
-                let mut col_lens = vec![];
-                for row in table.children.iter() {
-                    for (ix, cell) in row.children.iter().enumerate() {
-                        if col_lens.len() <= ix {
-                            col_lens.push(DEFAULT_LENGTH);
-                        }
+    /// Lay out the long list of the simple items (see [`Self::is_simple_list_item`]) flat.
+    ///
+    /// The prefix width is measured once by the widest prefix, and each item is a single row
+    /// of the prefix and the paragraph, without the nested containers of the list item.
+    fn render_flat_list(
+        children: &[Node],
+        state: ListState,
+        ctx: &RenderContext,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<AnyElement> {
+        let prefixes = (0..children.len())
+            .map(|ix| (!state.todo).then(|| list_item_prefix(ix, state.ordered, state.depth)))
+            .collect::<Vec<_>>();
+        let prefix_width = prefixes
+            .iter()
+            .flatten()
+            .max_by_key(|prefix| prefix.chars().count())
+            .and_then(|prefix| {
+                let text_style = window.text_style();
+                let font_size = text_style.font_size.to_pixels(window.rem_size());
+                let run = text_style.to_run(prefix.len());
+                let lines = window
+                    .text_system()
+                    .shape_text(prefix.clone().into(), font_size, &[run], None, None)
+                    .ok()?;
+                lines.first().map(|line| line.size(font_size).width)
+            });
 
-                        let len = cell.children.text_len();
-                        if len > col_lens[ix] {
-                            col_lens[ix] = len;
-                        }
-                    }
-                }
-                col_lens
-            }
-            _ => vec![],
-        };
+        children
+            .iter()
+            .zip(prefixes)
+            .filter_map(|(item, prefix)| {
+                let Node::ListItem { children, .. } = item else {
+                    return None;
+                };
+                let Some(Node::Paragraph(paragraph)) = children.first() else {
+                    return None;
+                };
 
-        match item {
-            Node::Table(table) => div()
-                .id("table")
-                .mb(rems(1.))
-                .w_full()
-                .border_1()
-                .border_color(cx.theme().border)
-                .rounded(cx.theme().radius)
-                .children({
-                    let mut rows = Vec::with_capacity(table.children.len());
-                    for (row_ix, row) in table.children.iter().enumerate() {
-                        rows.push(
+                Some(
+                    h_flex()
+                        .items_start()
+                        .when_some(prefix, |this, prefix| {
+                            this.child(
+                                div()
+                                    .flex_none()
+                                    .when_some(prefix_width, |this, width| this.w(width))
+                                    .child(prefix),
+                            )
+                        })
+                        .child(
                             div()
-                                .id("row")
-                                .w_full()
-                                .when(row_ix < table.children.len() - 1, |this| this.border_b_1())
-                                .border_color(cx.theme().border)
-                                .flex()
-                                .flex_row()
-                                .children({
-                                    let mut cells = Vec::with_capacity(row.children.len());
-                                    for (ix, cell) in row.children.iter().enumerate() {
-                                        let align = table.column_align(ix);
-                                        let is_last_col = ix == row.children.len() - 1;
-                                        let len = col_lens
-                                            .get(ix)
-                                            .copied()
-                                            .unwrap_or(MAX_LENGTH)
-                                            .min(MAX_LENGTH);
-
-                                        cells.push(
-                                            div()
-                                                .id("cell")
-                                                .flex()
-                                                .when(align == ColumnumnAlign::Center, |this| {
-                                                    this.justify_center()
-                                                })
-                                                .when(align == ColumnumnAlign::Right, |this| {
-                                                    this.justify_end()
-                                                })
-                                                .w(Length::Definite(relative(len as f32)))
-                                                .px_2()
-                                                .py_1()
-                                                .when(!is_last_col, |this| {
-                                                    this.border_r_1()
-                                                        .border_color(cx.theme().border)
-                                                })
-                                                .truncate()
-                                                .child(cell.children.clone()),
-                                        )
-                                    }
-                                    cells
-                                }),
+                                .flex_1()
+                                .overflow_hidden()
+                                .child(paragraph.render_with(ctx.style.hyphens, ctx, window, cx)),
                         )
-                    }
-                    rows
-                })
-                .into_any_element(),
-            _ => div().into_any_element(),
+                        .into_any_element(),
+                )
+            })
+            .collect()
+    }
+
+    fn render_table(
+        item: &Node,
+        ctx: &RenderContext,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> impl IntoElement {
+        let Node::Table(table) = item else {
+            return div().into_any_element();
+        };
+
+        let table_el = div()
+            .id("table")
+            .mb(rems(1.))
+            .w_full()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius);
+
+        // Only the visible rows of the large tables are rendered, the header row is kept on the top.
+        if table.children.len() > VIRTUAL_TABLE_ROWS {
+            let table = Arc::clone(table);
+            let rows_count = table.children.len() - 1;
+            let ctx = ctx.clone();
+
+            return table_el
+                .child(Self::render_table_row(&table, 0, &ctx, window, cx))
+                .child(
+                    uniform_list("rows", rows_count, move |visible_range, window, cx| {
+                        visible_range
+                            .map(|ix| Self::render_table_row(&table, ix + 1, &ctx, window, cx))
+                            .collect()
+                    })
+                    .h(VIRTUAL_TABLE_MAX_HEIGHT),
+                )
+                .into_any_element();
         }
+
+        table_el
+            .children(
+                (0..table.children.len())
+                    .map(|row_ix| Self::render_table_row(table, row_ix, ctx, window, cx)),
+            )
+            .into_any_element()
+    }
+
+    fn render_table_row(
+        table: &Table,
+        row_ix: usize,
+        ctx: &RenderContext,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> impl IntoElement {
+        const MAX_LENGTH: usize = 150;
+
+        let row = &table.children[row_ix];
+        div()
+            .id("row")
+            .w_full()
+            .when(row_ix < table.children.len() - 1, |this| this.border_b_1())
+            .border_color(cx.theme().border)
+            .flex()
+            .flex_row()
+            .children({
+                let mut cells = Vec::with_capacity(row.children.len());
+                for (ix, cell) in row.children.iter().enumerate() {
+                    let align = table.column_align(ix);
+                    let is_last_col = ix == row.children.len() - 1;
+                    let len = table
+                        .col_lens
+                        .get(ix)
+                        .copied()
+                        .unwrap_or(MAX_LENGTH)
+                        .min(MAX_LENGTH);
+
+                    cells.push(
+                        div()
+                            .id("cell")
+                            .flex()
+                            .when(align == ColumnumnAlign::Center, |this| {
+                                this.justify_center()
+                            })
+                            .when(align == ColumnumnAlign::Right, |this| this.justify_end())
+                            .w(Length::Definite(relative(len as f32)))
+                            .px_2()
+                            .py_1()
+                            .when(!is_last_col, |this| {
+                                this.border_r_1().border_color(cx.theme().border)
+                            })
+                            .truncate()
+                            .child(cell.children.render_with(false, ctx, window, cx)),
+                    )
+                }
+                cells
+            })
     }
 
     fn render_codeblock(
-        code_block: CodeBlock,
+        code_block: &CodeBlock,
         mb: Rems,
-        _: &TextViewStyle,
+        ctx: &RenderContext,
         _: &mut Window,
         cx: &mut App,
     ) -> AnyElement {
+        // The node is kept by the cache of the view, so the address of the styles is stable.
+        let id = Arc::as_ptr(&code_block.styles) as usize;
+
         div()
+            .group("code-block")
             .mb(mb)
             .p_3()
             .rounded(cx.theme().radius)
@@ -614,16 +1319,192 @@ impl Node {
             .font_family("Menlo, Monaco, Consolas, monospace")
             .text_size(rems(0.875))
             .relative()
-            .child(StyledText::new(code_block.code.clone()).with_highlights(code_block.styles))
+            .child(Selection::styled_text(
+                ctx.selection.as_ref(),
+                code_block.code.clone(),
+                Default::default(),
+                cx,
+                |selected| {
+                    let highlights = code_block.styles.get().into_iter().flatten().cloned();
+                    StyledText::new(code_block.code.clone())
+                        .with_highlights(gpui::combine_highlights(highlights, selected))
+                },
+            ))
+            .when(ctx.style.code_block_copy_button, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_2()
+                        .right_2()
+                        .invisible()
+                        .group_hover("code-block", |this| this.visible())
+                        .child(
+                            Clipboard::new(("code-block-copy", id)).value(code_block.code.clone()),
+                        ),
+                )
+            })
             .into_any_element()
     }
 
-    pub(crate) fn render(
-        self,
+    fn render_footnotes(
+        footnotes: &[Footnote],
+        ctx: &RenderContext,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> AnyElement {
+        let current = ctx.footnotes.clone();
+        let mut items = Vec::with_capacity(footnotes.len());
+        for footnote in footnotes {
+            let len = footnote.children.len();
+            let children = footnote
+                .children
+                .iter()
+                .enumerate()
+                .map(|(ix, child)| {
+                    child
+                        .render(None, false, ix + 1 == len, ctx, window, cx)
+                        .into_any_element()
+                })
+                .collect::<Vec<_>>();
+            let current = current.clone();
+            let id = footnote.id.clone();
+
+            items.push(
+                h_flex()
+                    .relative()
+                    .items_start()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_none()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(format!("{}.", footnote.label)),
+                    )
+                    .child(v_flex().flex_1().min_w_0().children(children))
+                    .child(
+                        canvas(
+                            move |bounds, _, _| {
+                                if let Some(current) = &current {
+                                    current.set_bounds(id.clone(), bounds);
+                                }
+                            },
+                            |_, _, _, _| {},
+                        )
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .size_full(),
+                    ),
+            );
+        }
+
+        v_flex()
+            .gap_1()
+            .pt_2()
+            .border_t_1()
+            .border_color(cx.theme().border)
+            .text_sm()
+            .children(items)
+            .into_any_element()
+    }
+
+    fn render_details(
+        details: &DetailsBlock,
+        mb: Rems,
+        ctx: &RenderContext,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> AnyElement {
+        let id = details.id;
+        let open_state = window.use_keyed_state(("details", id), cx, |_, _| details.open);
+        let open = *open_state.read(cx);
+
+        let summary = if details.summary.is_empty() {
+            SharedString::from(t!("TextView.details")).into_any_element()
+        } else {
+            details.summary.render_with(false, ctx, window, cx)
+        };
+        let children_len = details.children.len();
+
+        v_flex()
+            .mb(mb)
+            .child(
+                h_flex()
+                    .id(("details-summary", id))
+                    .gap_1()
+                    .cursor_pointer()
+                    .child(
+                        Icon::new(if open {
+                            IconName::ChevronDown
+                        } else {
+                            IconName::ChevronRight
+                        })
+                        .size_4()
+                        .flex_none()
+                        .text_color(cx.theme().muted_foreground),
+                    )
+                    .child(div().flex_1().min_w_0().child(summary))
+                    .on_click(move |_, _, cx| {
+                        open_state.update(cx, |open, cx| {
+                            *open = !*open;
+                            cx.notify();
+                        });
+                    }),
+            )
+            .when(open, |this| {
+                this.child(v_flex().pt_2().pl_5().children(
+                    details.children.iter().enumerate().map(|(ix, child)| {
+                        child
+                            .render(None, false, ix + 1 == children_len, ctx, window, cx)
+                            .into_any_element()
+                    }),
+                ))
+            })
+            .into_any_element()
+    }
+
+    fn render_definition_list(
+        items: &[DefinitionItem],
+        mb: Rems,
+        ctx: &RenderContext,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> AnyElement {
+        let mut children = Vec::with_capacity(items.len());
+        for (ix, item) in items.iter().enumerate() {
+            // Keep the gap between the items, but not between the terms sharing the definitions.
+            let gap = ix > 0 && !items[ix - 1].definitions.is_empty();
+            children.push(
+                div()
+                    .when(gap, |this| this.mt_2())
+                    .font_semibold()
+                    .child(item.term.render_with(false, ctx, window, cx))
+                    .into_any_element(),
+            );
+
+            for definition in item.definitions.iter() {
+                children.push(
+                    div()
+                        .pl_6()
+                        .child(definition.render(None, false, true, ctx, window, cx))
+                        .into_any_element(),
+                );
+            }
+        }
+
+        v_flex()
+            .mb(mb)
+            .gap_0p5()
+            .children(children)
+            .into_any_element()
+    }
+
+    pub(super) fn render(
+        &self,
         list_state: Option<ListState>,
         is_root: bool,
         is_last_child: bool,
-        style: &TextViewStyle,
+        ctx: &RenderContext,
         window: &mut Window,
         cx: &mut App,
     ) -> impl IntoElement {
@@ -631,20 +1512,23 @@ impl Node {
         let mb = if in_list || is_last_child {
             rems(0.)
         } else {
-            style.paragraph_gap
+            ctx.style.paragraph_gap
         };
 
         match self {
             Node::Root { children } => div()
                 .children({
                     let children_len = children.len();
-                    children.into_iter().enumerate().map(move |(index, c)| {
+                    children.iter().enumerate().map(move |(index, c)| {
                         let is_last_child = is_root && index == children_len - 1;
-                        c.render(None, false, is_last_child, style, window, cx)
+                        c.render(None, false, is_last_child, ctx, window, cx)
                     })
                 })
                 .into_any_element(),
-            Node::Paragraph(paragraph) => div().mb(mb).child(paragraph).into_any_element(),
+            Node::Paragraph(paragraph) => div()
+                .mb(mb)
+                .child(paragraph.render_with(ctx.style.hyphens, ctx, window, cx))
+                .into_any_element(),
             Node::Heading { level, children } => {
                 let (text_size, font_weight) = match level {
                     1 => (rems(2.), FontWeight::BOLD),
@@ -656,14 +1540,16 @@ impl Node {
                     _ => (rems(1.), FontWeight::NORMAL),
                 };
 
-                let text_size = text_size.to_pixels(style.heading_base_font_size);
+                let text_size = text_size.to_pixels(
+                    ctx.style.heading_base_font_size * Root::window_ui_scale(window, cx),
+                );
 
                 h_flex()
                     .mb(rems(0.3))
                     .whitespace_normal()
                     .text_size(text_size)
                     .font_weight(font_weight)
-                    .child(children)
+                    .child(children.render_with(false, ctx, window, cx))
                     .into_any_element()
             }
             Node::Blockquote { children } => div()
@@ -675,30 +1561,50 @@ impl Node {
                 .px_4()
                 .children({
                     let children_len = children.len();
-                    children.into_iter().enumerate().map(move |(index, c)| {
+                    children.iter().enumerate().map(move |(index, c)| {
                         let is_last_child = is_root && index == children_len - 1;
-                        c.render(None, false, is_last_child, style, window, cx)
+                        c.render(None, false, is_last_child, ctx, window, cx)
                     })
                 })
                 .into_any_element(),
+            Node::List { children, ordered }
+                if children.len() > FLAT_LIST_ITEMS
+                    && children.iter().all(Node::is_simple_list_item) =>
+            {
+                let list_state = list_state.unwrap_or_default();
+                v_flex()
+                    .mb(mb)
+                    .children(Self::render_flat_list(
+                        children,
+                        ListState {
+                            ordered: *ordered,
+                            todo: list_state.todo,
+                            depth: list_state.depth,
+                        },
+                        ctx,
+                        window,
+                        cx,
+                    ))
+                    .into_any_element()
+            }
             Node::List { children, ordered } => v_flex()
                 .mb(mb)
                 .children({
                     let mut items = Vec::with_capacity(children.len());
                     let list_state = list_state.unwrap_or_default();
                     let mut ix = 0;
-                    for item in children.into_iter() {
+                    for item in children.iter() {
                         let is_item = item.is_list_item();
 
                         items.push(Self::render_list_item(
                             item,
                             ix,
                             ListState {
-                                ordered,
+                                ordered: *ordered,
                                 todo: list_state.todo,
                                 depth: list_state.depth,
                             },
-                            style,
+                            ctx,
                             window,
                             cx,
                         ));
@@ -710,16 +1616,31 @@ impl Node {
                     items
                 })
                 .into_any_element(),
-            Node::CodeBlock(code_block) => {
-                Self::render_codeblock(code_block, mb, style, window, cx)
-            }
-            Node::Table { .. } => Self::render_table(&self, window, cx).into_any_element(),
+            Node::CodeBlock(code_block) => Self::render_codeblock(code_block, mb, ctx, window, cx),
+            Node::Math(math) => div()
+                .id(("math", Arc::as_ptr(&math.node) as usize))
+                .mb(mb)
+                .py_2()
+                .overflow_x_scroll()
+                .child(math.render(cx))
+                .into_any_element(),
+            Node::Mermaid(mermaid) => div()
+                .id(("mermaid", mermaid.id()))
+                .mb(mb)
+                .py_2()
+                .overflow_x_scroll()
+                .child(mermaid.render(cx))
+                .into_any_element(),
+            Node::Table { .. } => Self::render_table(self, ctx, window, cx).into_any_element(),
             Node::Divider => div()
                 .bg(cx.theme().border)
-                .h(px(2.))
+                .h(snap_stroke(px(2.), window.scale_factor()))
                 .mb(mb)
                 .into_any_element(),
             Node::Break { .. } => div().into_any_element(),
+            Node::Footnotes(footnotes) => Self::render_footnotes(footnotes, ctx, window, cx),
+            Node::DefinitionList(items) => Self::render_definition_list(items, mb, ctx, window, cx),
+            Node::Details(details) => Self::render_details(details, mb, ctx, window, cx),
             _ => {
                 if cfg!(debug_assertions) {
                     tracing::warn!("unknown implementation: {:?}", self);
@@ -737,7 +1658,7 @@ impl Paragraph {
             Paragraph::Texts { children, .. } => children
                 .iter()
                 .map(|text_node| {
-                    let mut text = text_node.text.clone();
+                    let mut text = text_node.text.to_string();
                     for (range, style) in &text_node.marks {
                         if style.bold {
                             text = format!("**{}**", &text_node.text[range.clone()]);
@@ -752,7 +1673,12 @@ impl Paragraph {
                             text = format!("`{}`", &text_node.text[range.clone()]);
                         }
                         if let Some(link) = &style.link {
-                            text = format!("[{}]({})", &text_node.text[range.clone()], link.url);
+                            text = match link.url.strip_prefix(FOOTNOTE_URL_PREFIX) {
+                                Some(id) => format!("[^{}]", id),
+                                None => {
+                                    format!("[{}]({})", &text_node.text[range.clone()], link.url)
+                                }
+                            };
                         }
                     }
                     text
@@ -772,6 +1698,77 @@ impl Paragraph {
         text.push_str("\n\n");
         text
     }
+
+    fn to_html(&self) -> String {
+        match self {
+            Paragraph::Texts { children, .. } => children.iter().map(TextNode::to_html).collect(),
+            Paragraph::Image { image, .. } => {
+                let mut html = format!(r#"<img src="{}""#, escape_html(&image.url));
+                if let Some(alt) = &image.alt {
+                    html.push_str(&format!(r#" alt="{}""#, escape_html(alt)));
+                }
+                if let Some(title) = &image.title {
+                    html.push_str(&format!(r#" title="{}""#, escape_html(title)));
+                }
+                html.push_str(" />");
+                html
+            }
+        }
+    }
+}
+
+impl TextNode {
+    /// Converts the text node to HTML, the text is split by the boundaries of the marks.
+    fn to_html(&self) -> String {
+        let len = self.text.len();
+        let mut bounds = vec![0, len];
+        for (range, _) in &self.marks {
+            bounds.push(range.start.min(len));
+            bounds.push(range.end.min(len));
+        }
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let mut html = String::new();
+        for part in bounds.windows(2) {
+            let range = part[0]..part[1];
+            let Some(text) = self.text.get(range.clone()) else {
+                continue;
+            };
+
+            let mut style = InlineTextStyle::default();
+            for (mark_range, mark) in &self.marks {
+                if mark_range.start <= range.start && range.end <= mark_range.end {
+                    style.bold |= mark.bold;
+                    style.italic |= mark.italic;
+                    style.strikethrough |= mark.strikethrough;
+                    style.code |= mark.code;
+                    if style.link.is_none() {
+                        style.link = mark.link.clone();
+                    }
+                }
+            }
+
+            let mut part = escape_html(text);
+            if style.code {
+                part = format!("<code>{}</code>", part);
+            }
+            if style.strikethrough {
+                part = format!("<del>{}</del>", part);
+            }
+            if style.italic {
+                part = format!("<em>{}</em>", part);
+            }
+            if style.bold {
+                part = format!("<strong>{}</strong>", part);
+            }
+            if let Some(link) = &style.link {
+                part = format!(r#"<a href="{}">{}</a>"#, escape_html(&link.url), part);
+            }
+            html.push_str(&part);
+        }
+        html
+    }
 }
 
 impl Node {
@@ -846,6 +1843,8 @@ impl Node {
                     code_block.code
                 )
             }
+            Node::Math(math) => format!("$$\n{}\n$$", math.source),
+            Node::Mermaid(mermaid) => format!("```mermaid\n{}\n```", mermaid.source),
             Node::Table(table) => {
                 let header = table
                     .children
@@ -894,9 +1893,157 @@ impl Node {
                 }
             }
             Node::Divider => "---".to_string(),
+            Node::Details(details) => {
+                let open = if details.open { " open" } else { "" };
+                let children = details
+                    .children
+                    .iter()
+                    .map(|child| child.to_markdown())
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                format!(
+                    "<details{}>\n<summary>{}</summary>\n\n{}\n\n</details>",
+                    open,
+                    details.summary.to_markdown(),
+                    children
+                )
+            }
+            Node::DefinitionList(items) => items
+                .iter()
+                .map(|item| {
+                    let mut text = item.term.to_markdown();
+                    for definition in item.definitions.iter() {
+                        text.push_str("\n: ");
+                        text.push_str(&definition.to_markdown().replace("\n", "\n  "));
+                    }
+                    text
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Node::Footnotes(footnotes) => footnotes
+                .iter()
+                .map(|footnote| {
+                    format!(
+                        "[^{}]: {}",
+                        footnote.label,
+                        footnote
+                            .children
+                            .iter()
+                            .map(|child| child.to_markdown())
+                            .collect::<Vec<_>>()
+                            .join("\n\n    ")
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
             Node::Unknown => "".to_string(),
         }
         .trim()
         .to_string()
     }
+
+    /// Converts the node to HTML, used to copy the rich text to the clipboard.
+    pub(crate) fn to_html(&self) -> String {
+        let children_html =
+            |children: &[Node]| children.iter().map(Node::to_html).collect::<String>();
+
+        match self {
+            Node::Root { children } => children_html(children),
+            Node::Paragraph(paragraph) => format!("<p>{}</p>", paragraph.to_html()),
+            Node::Heading { level, children } => {
+                format!("<h{}>{}</h{}>", level, children.to_html(), level)
+            }
+            Node::Blockquote { children } => {
+                format!("<blockquote>{}</blockquote>", children_html(children))
+            }
+            Node::List { children, ordered } => {
+                let tag = if *ordered { "ol" } else { "ul" };
+                format!("<{}>{}</{}>", tag, children_html(children), tag)
+            }
+            Node::ListItem {
+                children, checked, ..
+            } => {
+                let checkbox = match checked {
+                    Some(true) => r#"<input type="checkbox" checked disabled /> "#,
+                    Some(false) => r#"<input type="checkbox" disabled /> "#,
+                    None => "",
+                };
+                format!("<li>{}{}</li>", checkbox, children_html(children))
+            }
+            Node::CodeBlock(code_block) => {
+                let class = code_block.lang.as_ref().map_or(String::new(), |lang| {
+                    format!(r#" class="language-{}""#, escape_html(lang))
+                });
+                format!(
+                    "<pre><code{}>{}</code></pre>",
+                    class,
+                    escape_html(&code_block.code)
+                )
+            }
+            Node::Mermaid(mermaid) => format!(
+                r#"<pre class="mermaid">{}</pre>"#,
+                escape_html(&mermaid.source)
+            ),
+            Node::Math(math) => format!(
+                r#"<p class="math">{}</p>"#,
+                escape_html(&math::to_unicode(&math.source))
+            ),
+            Node::Table(table) => {
+                let rows = table
+                    .children
+                    .iter()
+                    .enumerate()
+                    .map(|(row_ix, row)| {
+                        let tag = if row_ix == 0 { "th" } else { "td" };
+                        let cells = row
+                            .children
+                            .iter()
+                            .map(|cell| format!("<{}>{}</{}>", tag, cell.children.to_html(), tag))
+                            .collect::<String>();
+                        format!("<tr>{}</tr>", cells)
+                    })
+                    .collect::<String>();
+                format!("<table>{}</table>", rows)
+            }
+            Node::Break { .. } => "<br />".to_string(),
+            Node::Divider => "<hr />".to_string(),
+            Node::Details(details) => format!(
+                "<details{}><summary>{}</summary>{}</details>",
+                if details.open { " open" } else { "" },
+                details.summary.to_html(),
+                children_html(&details.children)
+            ),
+            Node::DefinitionList(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| {
+                        let definitions = item
+                            .definitions
+                            .iter()
+                            .map(|definition| format!("<dd>{}</dd>", definition.to_html()))
+                            .collect::<String>();
+                        format!("<dt>{}</dt>{}", item.term.to_html(), definitions)
+                    })
+                    .collect::<String>();
+                format!("<dl>{}</dl>", items)
+            }
+            Node::Footnotes(footnotes) => {
+                let items = footnotes
+                    .iter()
+                    .map(|footnote| {
+                        format!(
+                            r#"<li id="fn-{}">{}</li>"#,
+                            escape_html(&footnote.id),
+                            children_html(&footnote.children)
+                        )
+                    })
+                    .collect::<String>();
+                format!(
+                    r#"<section class="footnotes"><hr /><ol>{}</ol></section>"#,
+                    items
+                )
+            }
+            Node::Unknown => String::new(),
+        }
+    }
 }
@@ -0,0 +1,56 @@
+use std::borrow::Cow;
+
+use crate::emoji::shortcode_emoji;
+
+fn is_shortcode_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-')
+}
+
+/// Replace the `:shortcode:` in the `text` with the emoji of the [`crate::emoji`] registry,
+/// the unknown and the custom shortcodes are kept.
+pub(super) fn expand_shortcodes(text: &str) -> Cow<'_, str> {
+    let mut out = String::new();
+    // The end of the text pushed to the `out`.
+    let mut last = 0;
+    let mut pos = 0;
+    while let Some(start) = text[pos..].find(':').map(|ix| ix + pos) {
+        let rest = &text[start + 1..];
+        let Some(len) = rest.find(|c| !is_shortcode_char(c)) else {
+            break;
+        };
+
+        if len > 0 && rest[len..].starts_with(':') {
+            if let Some(emoji) = shortcode_emoji(&rest[..len]) {
+                out.push_str(&text[last..start]);
+                out.push_str(emoji);
+                last = start + len + 2;
+                pos = last;
+                continue;
+            }
+        }
+        // The closing colon may start the next shortcode, e.g.: `10:30:smile:`.
+        pos = start + 1 + len;
+    }
+
+    if last == 0 {
+        return Cow::Borrowed(text);
+    }
+    out.push_str(&text[last..]);
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand_shortcodes;
+
+    #[test]
+    fn test_expand_shortcodes() {
+        assert_eq!(expand_shortcodes("Ship it :rocket:"), "Ship it 🚀");
+        assert_eq!(expand_shortcodes(":smile::tada:"), "😄🎉");
+        assert_eq!(expand_shortcodes("at 10:30:smile:"), "at 10:30😄");
+        // The unknown or unclosed shortcodes are kept.
+        assert_eq!(expand_shortcodes("a :foo: b :smile"), "a :foo: b :smile");
+        assert_eq!(expand_shortcodes("key: value"), "key: value");
+        assert_eq!(expand_shortcodes("::"), "::");
+    }
+}
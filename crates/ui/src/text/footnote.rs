@@ -0,0 +1,66 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use gpui::{px, Bounds, Pixels, ScrollHandle, SharedString, Window};
+
+/// The url prefix of the footnote references, e.g.: `#fn-1` for `[^1]`.
+pub(super) const FOOTNOTE_URL_PREFIX: &str = "#fn-";
+
+/// The footnote definitions of a rendered document, to scroll to the definition by clicking
+/// the reference.
+#[derive(Clone, Default)]
+pub(super) struct Footnotes {
+    scroll_handle: Option<ScrollHandle>,
+    bounds: Rc<RefCell<HashMap<SharedString, Bounds<Pixels>>>>,
+}
+
+impl Footnotes {
+    /// Set the scroll handle of the container of the document.
+    pub(super) fn set_scroll_handle(&mut self, scroll_handle: Option<ScrollHandle>) {
+        self.scroll_handle = scroll_handle;
+    }
+
+    /// Set the bounds of the definition in the last frame.
+    pub(super) fn set_bounds(&self, id: SharedString, bounds: Bounds<Pixels>) {
+        self.bounds.borrow_mut().insert(id, bounds);
+    }
+
+    /// Scroll the definition of the `id` to the top, returns false if it can not be scrolled.
+    pub(super) fn scroll_to(&self, id: &str, window: &mut Window) -> bool {
+        let Some(scroll_handle) = &self.scroll_handle else {
+            return false;
+        };
+        let Some(bounds) = self.bounds.borrow().get(id).copied() else {
+            return false;
+        };
+
+        let mut offset = scroll_handle.offset();
+        offset.y = (offset.y - (bounds.top() - scroll_handle.bounds().top()))
+            .clamp(-scroll_handle.max_offset().height, px(0.));
+        scroll_handle.set_offset(offset);
+        window.refresh();
+        true
+    }
+}
+
+/// Returns the text of the reference, the numeric labels are shown in the superscript digits.
+pub(super) fn superscript(label: &str) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+    if label.is_empty() || !label.chars().all(|c| c.is_ascii_digit()) {
+        return format!("[{}]", label);
+    }
+
+    label.bytes().map(|b| DIGITS[(b - b'0') as usize]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::superscript;
+
+    #[test]
+    fn test_superscript() {
+        assert_eq!(superscript("1"), "¹");
+        assert_eq!(superscript("20"), "²⁰");
+        assert_eq!(superscript("note"), "[note]");
+    }
+}
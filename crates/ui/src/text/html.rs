@@ -4,22 +4,29 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::ops::Range;
 use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
 
 use gpui::prelude::FluentBuilder as _;
 use gpui::{
-    div, px, relative, AnyElement, DefiniteLength, Element, ElementId, IntoElement,
-    ParentElement as _, SharedString, Styled as _, Window,
+    px, relative, AnyElement, App, DefiniteLength, Element, ElementId, Hitbox, HitboxBehavior,
+    IntoElement, ParentElement as _, SharedString, Styled as _, Window,
 };
 use html5ever::tendril::TendrilSink;
 use html5ever::{local_name, parse_document, LocalName, ParseOpts};
 use markup5ever_rcdom::{Node, NodeData, RcDom};
 
-use crate::v_flex;
+use crate::{i18n::t, v_flex};
 
+use super::document::{render_placeholder, ParsedDocument};
 use super::element::{
-    self, ImageNode, InlineTextStyle, LinkMark, Paragraph, Table, TableRow, TextNode,
+    self, DefinitionItem, DetailsBlock, ImageNode, InlineTextStyle, LinkMark, Paragraph,
+    RenderContext, Table, TableRow, TextNode,
 };
+use super::intern::Interner;
+use super::link::OnLinkClick;
 use super::TextViewStyle;
+use super::{lightbox::Lightbox, selection::Selection};
 
 const BLOCK_ELEMENTS: [&str; 35] = [
     "html",
@@ -77,7 +84,8 @@ pub(super) fn parse_html(source: &str) -> Result<element::Node, SharedString> {
     // NOTE: The outer paragraph is not used.
     let node: element::Node =
         parse_node(&dom.document, &mut paragraph).unwrap_or(element::Node::Unknown);
-    let node = node.compact();
+    let mut node = node.compact();
+    node.number_details(&mut 0);
 
     Ok(node)
 }
@@ -93,6 +101,9 @@ pub(super) struct HtmlElement {
     id: ElementId,
     pub(super) text: SharedString,
     style: TextViewStyle,
+    debounce: Duration,
+    on_link_click: Option<OnLinkClick>,
+    read_error: Option<SharedString>,
 }
 
 impl HtmlElement {
@@ -101,6 +112,9 @@ impl HtmlElement {
             id: id.into(),
             text: raw.into(),
             style: TextViewStyle::default(),
+            debounce: Duration::ZERO,
+            on_link_click: None,
+            read_error: None,
         }
     }
 
@@ -115,24 +129,66 @@ impl HtmlElement {
         self.style = style.into();
         self
     }
+
+    /// Set the debounce of the source changes, default is no debounce.
+    pub(crate) fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Set the error of reading the source file, it is shown instead of the document.
+    pub(crate) fn read_error(mut self, error: Option<SharedString>) -> Self {
+        self.read_error = error;
+        self
+    }
+
+    /// Set the handler of the link clicks, instead of opening by the router.
+    pub(crate) fn on_link_click(mut self, handler: OnLinkClick) -> Self {
+        self.on_link_click = Some(handler);
+        self
+    }
 }
 
 #[derive(Default)]
 pub struct HtmlState {
     raw: SharedString,
-    root: Option<Result<element::Node, SharedString>>,
+    document: ParsedDocument,
+    selection: Selection,
+    lightbox: Lightbox,
 }
 
 impl HtmlState {
-    fn parse_if_needed(&mut self, new_text: SharedString) {
+    fn parse_if_needed(
+        &mut self,
+        new_text: SharedString,
+        style: &TextViewStyle,
+        debounce: Duration,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
         let is_changed = self.raw != new_text;
 
-        if self.root.is_some() && !is_changed {
+        if self.document.is_parsed() && !is_changed {
+            return;
+        }
+
+        if !self.document.should_parse(debounce, window, cx) {
             return;
         }
 
         self.raw = new_text;
-        self.root = Some(parse_html(&self.raw));
+        let style = style.clone();
+        self.document.parse(
+            self.raw.clone(),
+            parse_html,
+            move |mut node, _| {
+                Interner::default().intern_node(&mut node);
+                node.prepare(&style);
+                node
+            },
+            window,
+            cx,
+        );
     }
 }
 
@@ -145,8 +201,8 @@ impl IntoElement for HtmlElement {
 }
 
 impl Element for HtmlElement {
-    type RequestLayoutState = AnyElement;
-    type PrepaintState = ();
+    type RequestLayoutState = (AnyElement, Selection);
+    type PrepaintState = Hitbox;
 
     fn id(&self) -> Option<gpui::ElementId> {
         Some(self.id.clone())
@@ -165,28 +221,45 @@ impl Element for HtmlElement {
     ) -> (gpui::LayoutId, Self::RequestLayoutState) {
         window.with_element_state(id.unwrap(), |state, window| {
             let mut state: HtmlState = state.unwrap_or_default();
-            state.parse_if_needed(self.text.clone());
-
-            let root = state
-                .root
-                .clone()
-                .expect("BUG: root should not None, maybe parse_if_needed issue.");
-
-            let mut el = div()
-                .map(|this| match root {
-                    Ok(node) => this.child(node.render(None, true, true, &self.style, window, cx)),
-                    Err(err) => this.child(
+            state.parse_if_needed(self.text.clone(), &self.style, self.debounce, window, cx);
+
+            let selection = state.selection.clone();
+            let lightbox = state.lightbox.clone();
+            let root = state.document.root();
+            let mut el = selection
+                .container(cx)
+                .map(|this| match (&self.read_error, root) {
+                    (Some(err), _) => this.child(
+                        v_flex()
+                            .gap_1()
+                            .child(t!("TextView.read_file_error"))
+                            .child(err.clone()),
+                    ),
+                    (None, Some(Ok(node))) => {
+                        selection.clear();
+                        let ctx = RenderContext {
+                            style: self.style.clone(),
+                            selection: Some(selection.clone()),
+                            on_link_click: self.on_link_click.clone(),
+                            lightbox: Some(lightbox.clone()),
+                            ..Default::default()
+                        };
+                        this.child(node.render(None, true, true, &ctx, window, cx))
+                    }
+                    (None, Some(Err(err))) => this.child(
                         v_flex()
                             .gap_1()
-                            .child("Error parsing HTML")
+                            .child(t!("TextView.parse_html_error"))
                             .child(err.to_string()),
                     ),
+                    (None, None) => this.child(render_placeholder()),
                 })
+                .children(lightbox.render(window, cx))
                 .into_any_element();
 
             let layout_id = el.request_layout(window, cx);
 
-            ((layout_id, el), state)
+            ((layout_id, (el, selection)), state)
         })
     }
 
@@ -194,12 +267,14 @@ impl Element for HtmlElement {
         &mut self,
         _: Option<&gpui::GlobalElementId>,
         _: Option<&gpui::InspectorElementId>,
-        _: gpui::Bounds<gpui::Pixels>,
+        bounds: gpui::Bounds<gpui::Pixels>,
         request_layout: &mut Self::RequestLayoutState,
         window: &mut Window,
         cx: &mut gpui::App,
     ) -> Self::PrepaintState {
-        request_layout.prepaint(window, cx);
+        let hitbox = window.insert_hitbox(bounds, HitboxBehavior::Normal);
+        request_layout.0.prepaint(window, cx);
+        hitbox
     }
 
     fn paint(
@@ -208,11 +283,12 @@ impl Element for HtmlElement {
         _: Option<&gpui::InspectorElementId>,
         _: gpui::Bounds<gpui::Pixels>,
         request_layout: &mut Self::RequestLayoutState,
-        _: &mut Self::PrepaintState,
+        hitbox: &mut Self::PrepaintState,
         window: &mut Window,
         cx: &mut gpui::App,
     ) {
-        request_layout.paint(window, cx);
+        request_layout.0.paint(window, cx);
+        request_layout.1.paint(hitbox, window);
     }
 }
 
@@ -408,7 +484,7 @@ fn parse_paragraph(
                     },
                 ));
                 paragraph.push(element::TextNode {
-                    text: text.clone(),
+                    text: text.clone().into(),
                     marks: marks.clone(),
                 });
             }
@@ -426,7 +502,7 @@ fn parse_paragraph(
                     },
                 ));
                 paragraph.push(TextNode {
-                    text: text.clone(),
+                    text: text.clone().into(),
                     marks: marks.clone(),
                 });
             }
@@ -444,7 +520,7 @@ fn parse_paragraph(
                     },
                 ));
                 paragraph.push(TextNode {
-                    text: text.clone(),
+                    text: text.clone().into(),
                     marks: marks.clone(),
                 });
             }
@@ -462,7 +538,7 @@ fn parse_paragraph(
                     },
                 ));
                 paragraph.push(TextNode {
-                    text: text.clone(),
+                    text: text.clone().into(),
                     marks: marks.clone(),
                 });
             }
@@ -486,7 +562,7 @@ fn parse_paragraph(
                     },
                 ));
                 paragraph.push(TextNode {
-                    text: text.clone(),
+                    text: text.clone().into(),
                     marks: marks.clone(),
                 });
             }
@@ -518,7 +594,7 @@ fn parse_paragraph(
                     merge_child_text(&mut text, &mut marks, &child_text, &child_marks);
                 }
                 paragraph.push(element::TextNode {
-                    text: text.clone(),
+                    text: text.clone().into(),
                     marks: marks.clone(),
                 });
             }
@@ -530,7 +606,7 @@ fn parse_paragraph(
                 merge_child_text(&mut text, &mut marks, &child_text, &child_marks);
             }
             paragraph.push(TextNode {
-                text: text.clone(),
+                text: text.clone().into(),
                 marks: marks.clone(),
             });
         }
@@ -656,6 +732,7 @@ fn parse_node(node: &Rc<Node>, paragraph: &mut Paragraph) -> Option<element::Nod
                     children,
                     spread: false,
                     checked: None,
+                    span: None,
                 })
             }
             local_name!("table") => {
@@ -679,8 +756,9 @@ fn parse_node(node: &Rc<Node>, paragraph: &mut Paragraph) -> Option<element::Nod
                     }
                 }
                 consume_paragraph(&mut children, paragraph);
+                table.update_col_lens();
 
-                let table = element::Node::Table(table);
+                let table = element::Node::Table(Arc::new(table));
                 if children.len() > 0 {
                     children.push(table);
                     Some(element::Node::Root { children })
@@ -692,6 +770,31 @@ fn parse_node(node: &Rc<Node>, paragraph: &mut Paragraph) -> Option<element::Nod
                 let children = consume_children_nodes(node, paragraph);
                 Some(element::Node::Blockquote { children })
             }
+            local_name!("dl") => {
+                let mut children = vec![];
+                consume_paragraph(&mut children, paragraph);
+
+                let list = element::Node::DefinitionList(parse_definition_items(node));
+                if children.len() > 0 {
+                    children.push(list);
+                    Some(element::Node::Root { children })
+                } else {
+                    Some(list)
+                }
+            }
+            local_name!("details") => {
+                let mut children = vec![];
+                consume_paragraph(&mut children, paragraph);
+
+                let open = attr_value(attrs, local_name!("open")).is_some();
+                let details = element::Node::Details(parse_details(node, open));
+                if children.len() > 0 {
+                    children.push(details);
+                    Some(element::Node::Root { children })
+                } else {
+                    Some(details)
+                }
+            }
             local_name!("style") | local_name!("script") => None,
             _ => {
                 if BLOCK_ELEMENTS.contains(&name.local.trim()) {
@@ -741,6 +844,80 @@ fn parse_node(node: &Rc<Node>, paragraph: &mut Paragraph) -> Option<element::Nod
     }
 }
 
+/// Parse the `<dt>` and `<dd>` children of the `<dl>`, the `<dd>` before any `<dt>` is added
+/// to an empty term.
+fn parse_definition_items(node: &Rc<Node>) -> Vec<DefinitionItem> {
+    let mut items: Vec<DefinitionItem> = vec![];
+    for child in node.children.borrow().iter() {
+        let NodeData::Element { ref name, .. } = child.data else {
+            continue;
+        };
+
+        match name.local {
+            local_name!("dt") => {
+                let mut term = Paragraph::default();
+                for sub_child in child.children.borrow().iter() {
+                    parse_paragraph(&mut term, sub_child);
+                }
+                items.push(DefinitionItem {
+                    term,
+                    definitions: vec![],
+                });
+            }
+            local_name!("dd") => {
+                let mut paragraph = Paragraph::default();
+                let mut children = consume_children_nodes(child, &mut paragraph);
+                let definition = match children.len() {
+                    0 => continue,
+                    1 => children.remove(0),
+                    _ => element::Node::Root { children },
+                };
+
+                if items.is_empty() {
+                    items.push(DefinitionItem {
+                        term: Paragraph::default(),
+                        definitions: vec![],
+                    });
+                }
+                if let Some(item) = items.last_mut() {
+                    item.definitions.push(definition);
+                }
+            }
+            _ => {}
+        }
+    }
+    items
+}
+
+/// Parse the `<details>` with the first `<summary>` as the summary, and the rest as the children.
+fn parse_details(node: &Rc<Node>, open: bool) -> DetailsBlock {
+    let mut summary = None;
+    let mut children = vec![];
+    let mut paragraph = Paragraph::default();
+    for child in node.children.borrow().iter() {
+        if summary.is_none() {
+            if let NodeData::Element { ref name, .. } = child.data {
+                if name.local == local_name!("summary") {
+                    let mut text = Paragraph::default();
+                    for sub_child in child.children.borrow().iter() {
+                        parse_paragraph(&mut text, sub_child);
+                    }
+                    summary = Some(text);
+                    continue;
+                }
+            }
+        }
+
+        if let Some(child_node) = parse_node(child, &mut paragraph) {
+            children.push(child_node);
+        }
+        consume_paragraph(&mut children, &mut paragraph);
+    }
+    consume_paragraph(&mut children, &mut paragraph);
+
+    DetailsBlock::new(summary.unwrap_or_default(), children, open)
+}
+
 fn consume_children_nodes(node: &Node, paragraph: &mut Paragraph) -> Vec<element::Node> {
     let mut children = vec![];
     consume_paragraph(&mut children, paragraph);
@@ -839,6 +1016,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_soft_hyphen() {
+        // The `&shy;` is kept as the soft hyphen, it is the manual hyphenation.
+        let node = super::parse_html("<p>hy&shy;phen&shy;ation</p>").unwrap();
+        assert_eq!(node.to_markdown(), "hy\u{AD}phen\u{AD}ation");
+    }
+
+    #[test]
+    fn test_to_html() {
+        let html =
+            r#"<p>Hello <strong>world</strong> &amp; <a href="https://example.com">link</a></p>"#;
+        let node = super::parse_html(html).unwrap();
+        assert_eq!(node.to_html(), html);
+    }
+
+    #[test]
+    fn test_definition_list() {
+        let html =
+            r#"<dl><dt>Term</dt><dt><b>Alias</b></dt><dd>Definition</dd><dd>Another</dd></dl>"#;
+        let node = super::parse_html(html).unwrap();
+        let Node::DefinitionList(items) = &node else {
+            panic!("expected the definition list, got {:?}", node);
+        };
+        assert_eq!(items.len(), 2);
+        assert!(items[0].definitions.is_empty());
+        assert_eq!(items[1].definitions.len(), 2);
+        assert_eq!(
+            node.to_markdown(),
+            "Term\n**Alias**\n: Definition\n: Another"
+        );
+        assert_eq!(
+            node.to_html(),
+            "<dl><dt>Term</dt><dt><strong>Alias</strong></dt><dd><p>Definition</p></dd><dd><p>Another</p></dd></dl>"
+        );
+    }
+
+    #[test]
+    fn test_details() {
+        let html = r#"<details open><summary>More <b>info</b></summary><p>Body</p>Text</details>"#;
+        let node = super::parse_html(html).unwrap();
+        let Node::Details(details) = &node else {
+            panic!("expected the details, got {:?}", node);
+        };
+        assert!(details.open);
+        assert_eq!(details.children.len(), 2);
+        assert_eq!(
+            node.to_html(),
+            "<details open><summary>More <strong>info</strong></summary><p>Body</p><p>Text</p></details>"
+        );
+
+        let node = super::parse_html("<details><p>Body</p></details>").unwrap();
+        let Node::Details(details) = &node else {
+            panic!("expected the details, got {:?}", node);
+        };
+        assert!(!details.open);
+        assert!(details.summary.is_empty());
+    }
+
     #[test]
     fn test_value_to_length() {
         assert_eq!(super::value_to_length("100px"), Some(px(100.).into()));
@@ -881,4 +1116,24 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_details_ids() {
+        // The same details are numbered by the document order, including the nested.
+        let html = "<details><summary>A</summary><details><summary>B</summary><p>b</p></details></details><details><summary>A</summary><p>a</p></details>";
+        let node = super::parse_html(html).unwrap();
+        let mut ids = vec![];
+        fn collect(node: &Node, ids: &mut Vec<usize>) {
+            match node {
+                Node::Root { children } => children.iter().for_each(|c| collect(c, ids)),
+                Node::Details(details) => {
+                    ids.push(details.id);
+                    details.children.iter().for_each(|c| collect(c, ids));
+                }
+                _ => {}
+            }
+        }
+        collect(&node, &mut ids);
+        assert_eq!(ids, vec![0, 1, 2]);
+    }
 }
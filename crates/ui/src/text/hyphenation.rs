@@ -0,0 +1,249 @@
+//! Hyphenation by the Liang's algorithm (the TeX hyphenation patterns).
+//!
+//! There are no built-in patterns, use [`register_hyphenation_patterns`] to register the
+//! patterns for the languages, e.g.: the `hyph-en-us.pat.txt` from the [hyph-utf8] project.
+//!
+//! The soft hyphens add the break opportunities to the long words, the line wrapping of GPUI
+//! does not draw a hyphen at the break, so the [`hyphen_overlay`] paints a hyphen at the soft
+//! hyphens that the lines are broken at.
+//!
+//! [hyph-utf8]: https://github.com/hyphenation/tex-hyphen
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use gpui::{canvas, App, IntoElement, SharedString, Styled as _, TextAlign, TextLayout, Window};
+
+/// The soft hyphen, it is a break opportunity in the word, and only drawn at the line break.
+pub(crate) const SOFT_HYPHEN: char = '\u{00AD}';
+
+/// The minimum number of characters to keep before and after the hyphen.
+const LEFT_MIN: usize = 2;
+const RIGHT_MIN: usize = 3;
+
+static PATTERNS: RwLock<Option<HashMap<String, Arc<HyphenationPatterns>>>> = RwLock::new(None);
+
+/// Register the hyphenation patterns of the language (e.g.: `en`, `de`, `fr`).
+///
+/// The `patterns` is in the TeX format, the patterns are separated by whitespaces, e.g.: `.ach4 4ab. a1b`.
+///
+/// The patterns of the current locale will be used to hyphenate the text, the language part
+/// of the locale is used as fallback, e.g.: `en` for `en-US`.
+pub fn register_hyphenation_patterns(language: &str, patterns: &str) {
+    let patterns = Arc::new(HyphenationPatterns::parse(patterns));
+    if let Ok(mut registry) = PATTERNS.write() {
+        registry
+            .get_or_insert_with(HashMap::new)
+            .insert(language.to_lowercase(), patterns);
+    }
+}
+
+/// Returns the patterns of the current locale.
+pub(crate) fn current_patterns() -> Option<Arc<HyphenationPatterns>> {
+    let locale = crate::locale().to_lowercase();
+    let registry = PATTERNS.read().ok()?;
+    let registry = registry.as_ref()?;
+
+    registry.get(&locale).cloned().or_else(|| {
+        let language = locale.split(['-', '_']).next()?;
+        registry.get(language).cloned()
+    })
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct HyphenationPatterns {
+    /// The letters of the pattern and the values between the letters.
+    patterns: HashMap<String, Vec<u8>>,
+    max_len: usize,
+}
+
+impl HyphenationPatterns {
+    pub(crate) fn parse(source: &str) -> Self {
+        let mut patterns = HashMap::new();
+        let mut max_len = 0;
+
+        for pattern in source.split_whitespace() {
+            let mut letters = String::new();
+            let mut values = vec![0];
+            for c in pattern.chars() {
+                if let Some(value) = c.to_digit(10) {
+                    *values.last_mut().unwrap() = value as u8;
+                } else {
+                    letters.extend(c.to_lowercase());
+                    values.push(0);
+                }
+            }
+
+            max_len = max_len.max(letters.chars().count());
+            patterns.insert(letters, values);
+        }
+
+        Self { patterns, max_len }
+    }
+
+    /// Returns the byte offsets of the word to insert the hyphens.
+    pub(crate) fn hyphenate(&self, word: &str) -> Vec<usize> {
+        let chars = word.chars().collect::<Vec<_>>();
+        if chars.len() < LEFT_MIN + RIGHT_MIN {
+            return vec![];
+        }
+
+        let dotted = std::iter::once('.')
+            .chain(chars.iter().flat_map(|c| c.to_lowercase()))
+            .chain(std::iter::once('.'))
+            .collect::<Vec<_>>();
+        // Keep the positions aligned with the word, if the lowercase changes the length.
+        if dotted.len() != chars.len() + 2 {
+            return vec![];
+        }
+
+        let mut points = vec![0u8; dotted.len() + 1];
+        for start in 0..dotted.len() {
+            let end_max = (start + self.max_len).min(dotted.len());
+            for end in start + 1..=end_max {
+                let key = dotted[start..end].iter().collect::<String>();
+                if let Some(values) = self.patterns.get(&key) {
+                    for (ix, value) in values.iter().enumerate() {
+                        points[start + ix] = points[start + ix].max(*value);
+                    }
+                }
+            }
+        }
+
+        let mut offsets = vec![];
+        let mut offset = 0;
+        for (ix, c) in chars.iter().enumerate() {
+            // The point before the char `ix` of the word, the `.` is at the start.
+            if ix >= LEFT_MIN && chars.len() - ix >= RIGHT_MIN && points[ix + 1] % 2 == 1 {
+                offsets.push(offset);
+            }
+            offset += c.len_utf8();
+        }
+
+        offsets
+    }
+}
+
+/// Insert the soft hyphens into the words of the text.
+///
+/// The words that already have the soft hyphens (e.g.: `&shy;` in HTML) are kept as is.
+///
+/// Returns None if the text is not changed, otherwise the text and the inserted
+/// `(offset, len)` in the original text, use [`remap_range`](super::utils::remap_range) to map the ranges.
+pub(crate) fn hyphenate_text(
+    text: &str,
+    patterns: &HyphenationPatterns,
+) -> Option<(String, Vec<(usize, usize)>)> {
+    let mut inserted = vec![];
+    let mut word_start: Option<usize> = None;
+
+    let mut hyphenate_word = |start: usize, end: usize| {
+        let word = &text[start..end];
+        if word.contains(SOFT_HYPHEN) {
+            return;
+        }
+        for offset in patterns.hyphenate(word) {
+            inserted.push((start + offset, SOFT_HYPHEN.len_utf8()));
+        }
+    };
+
+    for (ix, c) in text.char_indices() {
+        if c.is_alphabetic() || c == SOFT_HYPHEN {
+            word_start.get_or_insert(ix);
+        } else if let Some(start) = word_start.take() {
+            hyphenate_word(start, ix);
+        }
+    }
+    if let Some(start) = word_start {
+        hyphenate_word(start, text.len());
+    }
+
+    if inserted.is_empty() {
+        return None;
+    }
+
+    let mut out = String::with_capacity(text.len() + inserted.len() * SOFT_HYPHEN.len_utf8());
+    let mut last = 0;
+    for (offset, _) in inserted.iter() {
+        out.push_str(&text[last..*offset]);
+        out.push(SOFT_HYPHEN);
+        last = *offset;
+    }
+    out.push_str(&text[last..]);
+
+    Some((out, inserted))
+}
+
+/// Returns an overlay to paint a hyphen at the soft hyphens of the `text` that the lines are
+/// broken at, the `layout` is the layout of the text, e.g.: [`gpui::StyledText::layout`].
+///
+/// The overlay must be placed after the text in the same relative container.
+pub(crate) fn hyphen_overlay(text: SharedString, layout: TextLayout) -> impl IntoElement {
+    canvas(
+        |_, _, _| {},
+        move |_, _, window, cx| paint_hyphens(&text, &layout, window, cx),
+    )
+    .absolute()
+    .top_0()
+    .left_0()
+    .size_full()
+}
+
+fn paint_hyphens(text: &str, layout: &TextLayout, window: &mut Window, cx: &mut App) {
+    let text_style = window.text_style();
+    let font_size = text_style.font_size.to_pixels(window.rem_size());
+    let line_height = layout.line_height();
+
+    for (ix, _) in text.match_indices(SOFT_HYPHEN) {
+        let next_ix = ix + SOFT_HYPHEN.len_utf8();
+        let (Some(position), Some(next_position)) = (
+            layout.position_for_index(ix),
+            layout.position_for_index(next_ix),
+        ) else {
+            continue;
+        };
+        // The line is broken at the soft hyphen if the next character is on the next line.
+        if next_position.y <= position.y {
+            continue;
+        }
+
+        let run = text_style.to_run(1);
+        let Ok(lines) = window
+            .text_system()
+            .shape_text("-".into(), font_size, &[run], None, None)
+        else {
+            continue;
+        };
+        for line in lines {
+            _ = line.paint(position, line_height, TextAlign::Left, None, window, cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hyphenate_text, HyphenationPatterns};
+
+    // A subset of the `hyph-en-us` patterns.
+    const PATTERNS: &str = "1na hy3ph he2n hena4 hen5at n2at 1tio";
+
+    #[test]
+    fn test_hyphenate() {
+        let patterns = HyphenationPatterns::parse(PATTERNS);
+        assert_eq!(patterns.hyphenate("hyphenation"), vec![2, 6]);
+        assert_eq!(patterns.hyphenate("Hyphenation"), vec![2, 6]);
+        assert_eq!(patterns.hyphenate("hen"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_hyphenate_text() {
+        let patterns = HyphenationPatterns::parse(PATTERNS);
+        let (text, inserted) = hyphenate_text("The hyphenation.", &patterns).unwrap();
+        assert_eq!(text, "The hy\u{AD}phen\u{AD}ation.");
+        assert_eq!(inserted, vec![(6, 2), (10, 2)]);
+
+        assert_eq!(hyphenate_text("hy\u{AD}phenation", &patterns), None);
+        assert_eq!(hyphenate_text("The end", &patterns), None);
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+use gpui::SharedString;
+
+use super::element::{Node, Paragraph};
+
+/// The strings longer than this (in bytes) are not interned, they are rarely repeated.
+const MAX_INTERN_LEN: usize = 128;
+/// The max number of the strings in the table, the table is cleared when it is full.
+const MAX_STRINGS: usize = 64 * 1024;
+
+/// The table of the interned strings of a document.
+///
+/// The repeated strings (e.g.: the link urls and the small text runs) of the parsed document
+/// share the same [`SharedString`], to reduce the memory of the documents with many repeated
+/// tokens (e.g.: the logs).
+#[derive(Default)]
+pub(super) struct Interner {
+    strings: HashSet<SharedString>,
+}
+
+impl Interner {
+    /// Intern the texts and the link urls of the paragraphs of the parsed `node`.
+    pub(super) fn intern_node(&mut self, node: &mut Node) {
+        node.for_each_paragraph(&mut |paragraph, _| {
+            let Paragraph::Texts { children, .. } = paragraph else {
+                return;
+            };
+
+            for child in children.iter_mut() {
+                child.text = self.intern(&child.text);
+                for (_, style) in child.marks.iter_mut() {
+                    if let Some(link) = style.link.as_mut() {
+                        link.url = self.intern(&link.url);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Returns the interned [`SharedString`] of `s`, the long strings are not interned.
+    fn intern(&mut self, s: &SharedString) -> SharedString {
+        if s.len() > MAX_INTERN_LEN {
+            return s.clone();
+        }
+
+        if let Some(interned) = self.strings.get(s) {
+            return interned.clone();
+        }
+
+        if self.strings.len() >= MAX_STRINGS {
+            self.strings.clear();
+        }
+        self.strings.insert(s.clone());
+        s.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::SharedString;
+
+    use super::Interner;
+
+    #[test]
+    fn test_intern() {
+        let mut interner = Interner::default();
+        let a = interner.intern(&SharedString::from("rust".to_string()));
+        let b = interner.intern(&SharedString::from("rust".to_string()));
+        assert_eq!(a, b);
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_eq!(interner.strings.len(), 1);
+
+        // The long strings are not interned.
+        let long = SharedString::from("a".repeat(200));
+        assert_eq!(interner.intern(&long).as_ptr(), long.as_ptr());
+        assert_eq!(interner.strings.len(), 1);
+    }
+}
@@ -0,0 +1,281 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gpui::{
+    anchored, deferred, div, hsla, img, point, px, AnyElement, App, AssetLogger, FocusHandle,
+    ImageAssetLoader, InteractiveElement as _, IntoElement, MouseButton, MouseDownEvent,
+    MouseMoveEvent, ParentElement as _, Pixels, Point, Resource, ScrollWheelEvent, Styled as _,
+    Window,
+};
+
+use crate::{
+    indicator::Indicator,
+    overlay::{self, OverlayId, OverlayLayer},
+    Icon, IconName,
+};
+
+/// The zoom range of the image.
+const MIN_ZOOM: f32 = 0.1;
+const MAX_ZOOM: f32 = 10.;
+
+struct LightboxState {
+    resource: Resource,
+    zoom: f32,
+    /// The offset of the image center from the window center.
+    offset: Point<Pixels>,
+    /// The mouse position minus the offset when the panning started.
+    drag_origin: Option<Point<Pixels>>,
+    overlay_id: OverlayId,
+    focus_handle: FocusHandle,
+    /// The focused element before opening, to focus back on closing.
+    prev_focus: Option<FocusHandle>,
+}
+
+/// Returns the zoom and the offset after zooming by `factor` at the `position`, the point of
+/// the image under the `position` is kept in place.
+///
+/// The `center` is the center of the window, the image is centered at `center + offset`.
+fn zoom_at(
+    zoom: f32,
+    offset: Point<Pixels>,
+    center: Point<Pixels>,
+    position: Point<Pixels>,
+    factor: f32,
+) -> (f32, Point<Pixels>) {
+    let new_zoom = (zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+    let scale = 1. - new_zoom / zoom;
+    let delta = position - (center + offset);
+    (
+        new_zoom,
+        point(offset.x + delta.x * scale, offset.y + delta.y * scale),
+    )
+}
+
+/// The full window overlay to view an image of the document at the natural size.
+///
+/// The image is zoomed by scrolling, panned by dragging, and reset by double click. The
+/// overlay is dismissed by `escape` or clicking outside the image.
+#[derive(Clone, Default)]
+pub(super) struct Lightbox(Rc<RefCell<Option<LightboxState>>>);
+
+impl Lightbox {
+    /// Open the image of the `resource`.
+    pub(super) fn open(&self, resource: Resource, window: &mut Window, cx: &mut App) {
+        self.close(window, cx);
+
+        let overlay_id = overlay::open(OverlayLayer::Popover, window, cx, {
+            let this = self.clone();
+            move |window, cx| this.close(window, cx)
+        });
+        let focus_handle = cx.focus_handle();
+        let prev_focus = window.focused(cx);
+        focus_handle.focus(window);
+
+        *self.0.borrow_mut() = Some(LightboxState {
+            resource,
+            zoom: 1.,
+            offset: Point::default(),
+            drag_origin: None,
+            overlay_id,
+            focus_handle,
+            prev_focus,
+        });
+        window.refresh();
+    }
+
+    fn close(&self, window: &mut Window, cx: &mut App) {
+        let Some(state) = self.0.borrow_mut().take() else {
+            return;
+        };
+
+        overlay::close(state.overlay_id, cx);
+        if let Some(prev_focus) = state.prev_focus {
+            prev_focus.focus(window);
+        }
+        window.refresh();
+    }
+
+    fn on_scroll_wheel(&self, event: &ScrollWheelEvent, window: &mut Window) {
+        let mut state = self.0.borrow_mut();
+        let Some(state) = state.as_mut() else {
+            return;
+        };
+
+        let delta = event.delta.pixel_delta(window.line_height());
+        let viewport = window.viewport_size();
+        let center = point(viewport.width / 2., viewport.height / 2.);
+        (state.zoom, state.offset) = zoom_at(
+            state.zoom,
+            state.offset,
+            center,
+            event.position,
+            (delta.y.0 / 200.).exp(),
+        );
+        window.refresh();
+    }
+
+    fn on_image_mouse_down(&self, event: &MouseDownEvent, window: &mut Window) {
+        let mut state = self.0.borrow_mut();
+        let Some(state) = state.as_mut() else {
+            return;
+        };
+
+        if event.click_count == 2 {
+            state.zoom = 1.;
+            state.offset = Point::default();
+            window.refresh();
+        } else {
+            state.drag_origin = Some(event.position - state.offset);
+        }
+    }
+
+    fn on_mouse_move(&self, event: &MouseMoveEvent, window: &mut Window) {
+        let mut state = self.0.borrow_mut();
+        let Some(state) = state.as_mut() else {
+            return;
+        };
+        let Some(origin) = state.drag_origin else {
+            return;
+        };
+
+        if event.pressed_button != Some(MouseButton::Left) {
+            state.drag_origin = None;
+            return;
+        }
+        state.offset = event.position - origin;
+        window.refresh();
+    }
+
+    /// Returns the overlay of the opened image, `None` if no image is opened.
+    pub(super) fn render(&self, window: &mut Window, cx: &mut App) -> Option<AnyElement> {
+        let (resource, zoom, offset, focus_handle) = {
+            let state = self.0.borrow();
+            let state = state.as_ref()?;
+            (
+                state.resource.clone(),
+                state.zoom,
+                state.offset,
+                state.focus_handle.clone(),
+            )
+        };
+
+        let viewport = window.viewport_size();
+        let center = point(viewport.width / 2., viewport.height / 2.);
+        let content = match window.use_asset::<AssetLogger<ImageAssetLoader>>(&resource, cx) {
+            Some(Ok(image)) => {
+                // The natural size of the image, in the same way as `img`.
+                let size = image.size(0);
+                let width = px(size.width.0 as f32) * zoom;
+                let height = px(size.height.0 as f32) * zoom;
+
+                div()
+                    .absolute()
+                    .left(center.x + offset.x - width / 2.)
+                    .top(center.y + offset.y - height / 2.)
+                    .w(width)
+                    .h(height)
+                    .cursor_grab()
+                    .on_mouse_down(MouseButton::Left, {
+                        let this = self.clone();
+                        move |event, window, cx| {
+                            cx.stop_propagation();
+                            this.on_image_mouse_down(event, window);
+                        }
+                    })
+                    .child(img(image).size_full())
+                    .into_any_element()
+            }
+            Some(Err(_)) => Icon::new(IconName::ImageOff)
+                .size_8()
+                .text_color(hsla(0., 0., 1., 0.8))
+                .into_any_element(),
+            None => Indicator::new()
+                .color(hsla(0., 0., 1., 0.8))
+                .into_any_element(),
+        };
+
+        Some(
+            deferred(
+                anchored()
+                    .position(point(px(0.), px(0.)))
+                    .snap_to_window()
+                    .child(
+                        div()
+                            .id("lightbox")
+                            .occlude()
+                            .relative()
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .w(viewport.width)
+                            .h(viewport.height)
+                            .overflow_hidden()
+                            .bg(hsla(0., 0., 0., 0.85))
+                            .track_focus(&focus_handle)
+                            .on_scroll_wheel({
+                                let this = self.clone();
+                                move |event, window, _| this.on_scroll_wheel(event, window)
+                            })
+                            .on_mouse_move({
+                                let this = self.clone();
+                                move |event, window, _| this.on_mouse_move(event, window)
+                            })
+                            .on_mouse_up(MouseButton::Left, {
+                                let this = self.clone();
+                                move |_, _, _| {
+                                    if let Some(state) = this.0.borrow_mut().as_mut() {
+                                        state.drag_origin = None;
+                                    }
+                                }
+                            })
+                            // Dismiss by clicking outside the image.
+                            .on_mouse_down(MouseButton::Left, {
+                                let this = self.clone();
+                                move |_, window, cx| this.close(window, cx)
+                            })
+                            .child(content),
+                    ),
+            )
+            .with_priority(OverlayLayer::Popover.priority())
+            .into_any_element(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px};
+
+    use super::{zoom_at, MAX_ZOOM};
+
+    #[test]
+    fn test_zoom_at() {
+        let center = point(px(100.), px(100.));
+
+        // Zoom at the center of the image.
+        let (zoom, offset) = zoom_at(1., point(px(0.), px(0.)), center, center, 2.);
+        assert_eq!(zoom, 2.);
+        assert_eq!(offset, point(px(0.), px(0.)));
+
+        // The point under the mouse is kept in place.
+        let (zoom, offset) = zoom_at(
+            1.,
+            point(px(0.), px(0.)),
+            center,
+            point(px(150.), px(80.)),
+            2.,
+        );
+        assert_eq!(zoom, 2.);
+        assert_eq!(offset, point(px(-50.), px(20.)));
+
+        // The zoom is clamped.
+        let (zoom, offset) = zoom_at(
+            8.,
+            point(px(0.), px(0.)),
+            center,
+            point(px(150.), px(100.)),
+            2.,
+        );
+        assert_eq!(zoom, MAX_ZOOM);
+        assert_eq!(offset, point(px(-12.5), px(0.)));
+    }
+}
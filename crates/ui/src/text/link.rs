@@ -0,0 +1,16 @@
+use std::rc::Rc;
+
+use gpui::{App, Window};
+
+use crate::router;
+
+/// The handler of the link clicks of a [`TextView`](super::TextView).
+pub(super) type OnLinkClick = Rc<dyn Fn(&str, &mut Window, &mut App)>;
+
+/// Open the `url` by the `handler`, or by the [`router::open_url`] if it is not set.
+pub(super) fn open(handler: Option<&OnLinkClick>, url: &str, window: &mut Window, cx: &mut App) {
+    match handler {
+        Some(handler) => handler(url, window, cx),
+        None => router::open_url(url, window, cx),
+    }
+}
@@ -1,21 +1,32 @@
-use std::time::Instant;
+use std::{cell::RefCell, ops::Range, rc::Rc, sync::Arc, time::Duration};
 
 use gpui::{
-    div, prelude::FluentBuilder as _, AnyElement, App, Element, ElementId, IntoElement,
-    ParentElement, SharedString, Styled, Window,
+    prelude::FluentBuilder as _, AnyElement, App, Element, ElementId, Hitbox, HitboxBehavior,
+    IntoElement, ParentElement, ScrollHandle, SharedString, Styled, Window,
 };
 use markdown::{
     mdast::{self, Node},
-    ParseOptions,
+    Constructs, ParseOptions,
 };
 
-use crate::v_flex;
+use crate::{i18n::t, v_flex};
 
 use super::{
+    document::{render_placeholder, ParsedDocument},
     element::{
-        self, CodeBlock, ImageNode, InlineTextStyle, LinkMark, Paragraph, Span, Table, TableRow,
+        self, CodeBlock, DefinitionItem, DetailsBlock, Footnote, ImageNode, InlineTextStyle,
+        LinkMark, Paragraph, RenderContext, Span, Table, TableRow,
     },
+    emoji,
+    footnote::{superscript, Footnotes, FOOTNOTE_URL_PREFIX},
     html::parse_html,
+    intern::Interner,
+    lightbox::Lightbox,
+    link::OnLinkClick,
+    math::{self, MathBlock},
+    mermaid::MermaidBlock,
+    selection::Selection,
+    task_list::{self, OnCheckboxToggle},
     TextViewStyle,
 };
 
@@ -30,6 +41,11 @@ pub(super) struct MarkdownElement {
     id: ElementId,
     pub(super) text: SharedString,
     style: TextViewStyle,
+    debounce: Duration,
+    scroll_handle: Option<ScrollHandle>,
+    on_link_click: Option<OnLinkClick>,
+    on_checkbox_toggle: Option<OnCheckboxToggle>,
+    read_error: Option<SharedString>,
 }
 
 impl MarkdownElement {
@@ -38,6 +54,11 @@ impl MarkdownElement {
             id: id.into(),
             text: raw.into(),
             style: TextViewStyle::default(),
+            debounce: Duration::ZERO,
+            scroll_handle: None,
+            on_link_click: None,
+            on_checkbox_toggle: None,
+            read_error: None,
         }
     }
 
@@ -52,40 +73,316 @@ impl MarkdownElement {
         self.style = style.into();
         self
     }
+
+    /// Set the debounce of the source changes, default is no debounce.
+    pub(crate) fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Set the scroll handle of the container, to scroll to the footnotes by the references.
+    pub(crate) fn scroll_handle(mut self, scroll_handle: &ScrollHandle) -> Self {
+        self.scroll_handle = Some(scroll_handle.clone());
+        self
+    }
+
+    /// Set the error of reading the source file, it is shown instead of the document.
+    pub(crate) fn read_error(mut self, error: Option<SharedString>) -> Self {
+        self.read_error = error;
+        self
+    }
+
+    /// Set the handler of the link clicks, instead of opening by the router.
+    pub(crate) fn on_link_click(mut self, handler: OnLinkClick) -> Self {
+        self.on_link_click = Some(handler);
+        self
+    }
+
+    /// Set the handler of the task list checkbox toggles, the checkboxes are read-only if not set.
+    pub(crate) fn on_checkbox_toggle(mut self, handler: OnCheckboxToggle) -> Self {
+        self.on_checkbox_toggle = Some(handler);
+        self
+    }
 }
 
 #[derive(Default)]
 pub struct MarkdownState {
     raw: SharedString,
-    root: Option<Result<element::Node, SharedString>>,
+    document: ParsedDocument,
+    blocks: Rc<RefCell<Blocks>>,
     style: TextViewStyle,
-    _last_parsed: Option<Instant>,
+    footnotes: Footnotes,
+    selection: Selection,
+    lightbox: Lightbox,
 }
 
 impl MarkdownState {
-    fn parse_if_needed(&mut self, new_text: SharedString, style: &TextViewStyle, cx: &mut App) {
+    fn parse_if_needed(
+        &mut self,
+        new_text: SharedString,
+        style: &TextViewStyle,
+        debounce: Duration,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
         let is_changed = self.raw != new_text || self.style != *style;
 
-        if self.root.is_some() && !is_changed {
+        if self.document.is_parsed() && !is_changed {
             return;
         }
 
-        if let Some(last_parsed) = self._last_parsed {
-            if last_parsed.elapsed().as_millis() < 500 {
-                return;
-            }
+        if !self.document.should_parse(debounce, window, cx) {
+            return;
         }
 
+        // The code blocks are highlighted by the style, so parse the whole source if it is changed.
+        let reparse = if self.style == *style {
+            let blocks = self.blocks.borrow();
+            blocks
+                .incremental
+                .then(|| reparse_range(&blocks.raw, &new_text, &blocks.ranges))
+                .flatten()
+        } else {
+            None
+        };
+
         self.raw = new_text;
-        // NOTE: About 100ms
-        // let measure = crate::Measure::new("parse_markdown");
-        self.root = Some(parse_markdown(&self.raw, &style, cx));
-        // measure.end();
-        self._last_parsed = Some(Instant::now());
         self.style = style.clone();
+        let style = style.clone();
+        let raw = self.raw.clone();
+        let blocks = self.blocks.clone();
+        let source = match &reparse {
+            Some(reparse) => raw[reparse.range.clone()].to_string().into(),
+            None => raw.clone(),
+        };
+
+        self.document.parse(
+            source,
+            parse_markdown,
+            move |ast, cx| {
+                let mut blocks = blocks.borrow_mut();
+                // The reused blocks share the strings of the previous table.
+                let mut interner = match reparse {
+                    Some(_) => std::mem::take(&mut blocks.interner),
+                    None => Interner::default(),
+                };
+                let mut new_blocks =
+                    Blocks::build(ast, raw, reparse, &blocks, &mut interner, &style, cx);
+                new_blocks.interner = interner;
+                *blocks = new_blocks;
+                element::Node::Root {
+                    children: blocks.nodes.clone(),
+                }
+            },
+            window,
+            cx,
+        );
     }
 }
 
+/// The top level blocks of the last parsed source, used to re-parse only the changed blocks.
+#[derive(Default)]
+struct Blocks {
+    raw: SharedString,
+    /// The source ranges of the blocks.
+    ranges: Vec<Range<usize>>,
+    nodes: Vec<element::Node>,
+    /// False if the source can not be parsed incrementally, e.g.: it has the link definitions
+    /// that are referenced by the other blocks.
+    incremental: bool,
+    /// The interned strings of the blocks.
+    interner: Interner,
+}
+
+impl Blocks {
+    /// Build the blocks of the parsed `ast`, that is the `reparse` range of the `raw` if given,
+    /// the other blocks are reused from the `prev` blocks.
+    ///
+    /// The strings of the built blocks are interned into the `interner`.
+    fn build(
+        ast: mdast::Node,
+        raw: SharedString,
+        reparse: Option<Reparse>,
+        prev: &Blocks,
+        interner: &mut Interner,
+        style: &TextViewStyle,
+        cx: &mut App,
+    ) -> Self {
+        let offset = reparse.as_ref().map_or(0, |reparse| reparse.range.start);
+        let children = match ast {
+            Node::Root(root) => root.children,
+            ast => vec![ast],
+        };
+
+        let mut incremental = true;
+        let mut ranges = vec![];
+        let mut nodes = vec![];
+        let mut footnotes = vec![];
+        for mut group in group_details(children) {
+            let start = group.first().and_then(|child| child.position());
+            let end = group.last().and_then(|child| child.position());
+            match (start, end) {
+                (Some(start), Some(end)) => {
+                    ranges.push(offset + start.start.offset..offset + end.end.offset)
+                }
+                _ => incremental = false,
+            }
+            // The `<details>` html can be closed by the other blocks, so the changed block is
+            // not enough to re-parse.
+            if group.iter().any(|child| {
+                matches!(child, Node::Definition(_) | Node::FootnoteDefinition(_))
+                    || details_tag(child).is_some()
+            }) {
+                incremental = false;
+            }
+
+            let mut node = if group.len() == 1 {
+                let child = group.remove(0);
+                if let Node::FootnoteDefinition(definition) = child {
+                    footnotes.push(definition);
+                    continue;
+                }
+                ast_to_node(child, style, cx)
+            } else {
+                details_node(group, style, cx)
+            };
+            resolve_checkbox_spans(&mut node, &raw[offset..]);
+            node.shift_spans(offset as isize);
+            interner.intern_node(&mut node);
+            node.prepare(style);
+            nodes.push(node);
+        }
+        if let Some(mut node) = footnotes_node(footnotes, style, cx) {
+            resolve_checkbox_spans(&mut node, &raw[offset..]);
+            node.shift_spans(offset as isize);
+            interner.intern_node(&mut node);
+            node.prepare(style);
+            nodes.push(node);
+        }
+
+        if let Some(reparse) = reparse {
+            let delta = raw.len() as isize - prev.raw.len() as isize;
+            let suffix_ix = prev.nodes.len() - reparse.keep_suffix;
+
+            let suffix_nodes = prev.nodes[suffix_ix..].iter().map(|node| {
+                let mut node = node.clone();
+                node.shift_spans(delta);
+                node
+            });
+            nodes = prev.nodes[..reparse.keep_prefix]
+                .iter()
+                .cloned()
+                .chain(nodes)
+                .chain(suffix_nodes)
+                .collect();
+
+            let suffix_ranges = prev.ranges[suffix_ix..].iter().map(|range| {
+                range.start.saturating_add_signed(delta)..range.end.saturating_add_signed(delta)
+            });
+            ranges = prev.ranges[..reparse.keep_prefix]
+                .iter()
+                .cloned()
+                .chain(ranges)
+                .chain(suffix_ranges)
+                .collect();
+        }
+
+        let mut details_ix = 0;
+        for node in nodes.iter_mut() {
+            node.number_details(&mut details_ix);
+        }
+
+        Self {
+            incremental: incremental && ranges.len() == nodes.len(),
+            raw,
+            ranges,
+            nodes,
+            interner: Interner::default(),
+        }
+    }
+}
+
+/// The range of the changed source to re-parse, the blocks before and after it are reused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Reparse {
+    /// The number of the blocks reused from the start.
+    keep_prefix: usize,
+    /// The number of the blocks reused from the end.
+    keep_suffix: usize,
+    /// The range of the new source to parse.
+    range: Range<usize>,
+}
+
+/// Returns the [`Reparse`] for the `old` source changed to the `new`, `None` to parse the whole source.
+///
+/// The `blocks` are the ranges of the top level blocks in the `old` source.
+fn reparse_range(old: &str, new: &str, blocks: &[Range<usize>]) -> Option<Reparse> {
+    let mut prefix = old
+        .bytes()
+        .zip(new.bytes())
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !old.is_char_boundary(prefix) || !new.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let mut suffix = old
+        .bytes()
+        .rev()
+        .zip(new.bytes().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !old.is_char_boundary(old.len() - suffix) || !new.is_char_boundary(new.len() - suffix) {
+        suffix -= 1;
+    }
+
+    let old_end = old.len() - suffix;
+    let new_end = new.len() - suffix;
+
+    // The fenced code and math blocks may contain the following blocks, and the footnote
+    // definitions are moved to the end of the document.
+    let changed = [&old[prefix..old_end], &new[prefix..new_end]];
+    if changed.iter().any(|text| {
+        text.contains("```") || text.contains("~~~") || text.contains("$$") || text.contains("[^")
+    }) {
+        return None;
+    }
+
+    // The block next to the change is also re-parsed, it may be merged with the changed text,
+    // e.g.: the lazy continuation of a paragraph, or a setext heading underline.
+    let keep_prefix = blocks
+        .iter()
+        .take_while(|range| range.end <= prefix)
+        .count()
+        .saturating_sub(1);
+    let keep_suffix = blocks
+        .iter()
+        .rev()
+        .take_while(|range| range.start >= old_end)
+        .count()
+        .saturating_sub(1);
+    if keep_prefix == 0 && keep_suffix == 0 {
+        return None;
+    }
+
+    let start = match keep_prefix {
+        0 => 0,
+        ix => blocks[ix - 1].end,
+    };
+    let end = match keep_suffix {
+        0 => new.len(),
+        count => blocks[blocks.len() - count].start - old_end + new_end,
+    };
+
+    Some(Reparse {
+        keep_prefix,
+        keep_suffix,
+        range: start..end,
+    })
+}
+
 impl IntoElement for MarkdownElement {
     type Element = Self;
 
@@ -95,8 +392,8 @@ impl IntoElement for MarkdownElement {
 }
 
 impl Element for MarkdownElement {
-    type RequestLayoutState = AnyElement;
-    type PrepaintState = ();
+    type RequestLayoutState = (AnyElement, Selection);
+    type PrepaintState = Hitbox;
 
     fn id(&self) -> Option<gpui::ElementId> {
         Some(self.id.clone())
@@ -115,28 +412,50 @@ impl Element for MarkdownElement {
     ) -> (gpui::LayoutId, Self::RequestLayoutState) {
         window.with_element_state(id.unwrap(), |state, window| {
             let mut state: MarkdownState = state.unwrap_or_default();
-            state.parse_if_needed(self.text.clone(), &self.style, cx);
+            state.parse_if_needed(self.text.clone(), &self.style, self.debounce, window, cx);
 
-            let root = state
-                .root
-                .clone()
-                .expect("BUG: root should not None, maybe parse_if_needed issue.");
-
-            let mut el = div()
-                .map(|this| match root {
-                    Ok(node) => this.child(node.render(None, true, true, &self.style, window, cx)),
-                    Err(err) => this.child(
+            state
+                .footnotes
+                .set_scroll_handle(self.scroll_handle.clone());
+            let footnotes = state.footnotes.clone();
+            let selection = state.selection.clone();
+            let lightbox = state.lightbox.clone();
+            let root = state.document.root();
+            let mut el = selection
+                .container(cx)
+                .map(|this| match (&self.read_error, root) {
+                    (Some(err), _) => this.child(
+                        v_flex()
+                            .gap_1()
+                            .child(t!("TextView.read_file_error"))
+                            .child(err.clone()),
+                    ),
+                    (None, Some(Ok(node))) => {
+                        selection.clear();
+                        let ctx = RenderContext {
+                            style: self.style.clone(),
+                            selection: Some(selection.clone()),
+                            on_link_click: self.on_link_click.clone(),
+                            on_checkbox_toggle: self.on_checkbox_toggle.clone(),
+                            lightbox: Some(lightbox.clone()),
+                            footnotes: Some(footnotes),
+                        };
+                        this.child(node.render(None, true, true, &ctx, window, cx))
+                    }
+                    (None, Some(Err(err))) => this.child(
                         v_flex()
                             .gap_1()
-                            .child("Error parsing Markdown")
+                            .child(t!("TextView.parse_markdown_error"))
                             .child(err.to_string()),
                     ),
+                    (None, None) => this.child(render_placeholder()),
                 })
+                .children(lightbox.render(window, cx))
                 .into_any_element();
 
             let layout_id = el.request_layout(window, cx);
 
-            ((layout_id, el), state)
+            ((layout_id, (el, selection)), state)
         })
     }
 
@@ -144,12 +463,14 @@ impl Element for MarkdownElement {
         &mut self,
         _: Option<&gpui::GlobalElementId>,
         _: Option<&gpui::InspectorElementId>,
-        _: gpui::Bounds<gpui::Pixels>,
+        bounds: gpui::Bounds<gpui::Pixels>,
         request_layout: &mut Self::RequestLayoutState,
         window: &mut Window,
         cx: &mut gpui::App,
     ) -> Self::PrepaintState {
-        request_layout.prepaint(window, cx);
+        let hitbox = window.insert_hitbox(bounds, HitboxBehavior::Normal);
+        request_layout.0.prepaint(window, cx);
+        hitbox
     }
 
     fn paint(
@@ -158,23 +479,49 @@ impl Element for MarkdownElement {
         _: Option<&gpui::InspectorElementId>,
         _: gpui::Bounds<gpui::Pixels>,
         request_layout: &mut Self::RequestLayoutState,
-        _: &mut Self::PrepaintState,
+        hitbox: &mut Self::PrepaintState,
         window: &mut Window,
         cx: &mut gpui::App,
     ) {
-        request_layout.paint(window, cx);
+        request_layout.0.paint(window, cx);
+        request_layout.1.paint(hitbox, window);
     }
 }
 
-/// Parse Markdown into a tree of nodes.
-fn parse_markdown(
-    raw: &str,
-    style: &TextViewStyle,
-    cx: &mut App,
-) -> Result<element::Node, SharedString> {
-    markdown::to_mdast(&raw, &ParseOptions::gfm())
-        .map(|n| ast_to_node(n, style, cx))
-        .map_err(|e| e.to_string().into())
+/// Parse Markdown into the AST, it does not depend on the [`App`], so it can run in the background thread.
+fn parse_markdown(raw: &str) -> Result<mdast::Node, SharedString> {
+    let options = ParseOptions {
+        constructs: Constructs {
+            math_flow: true,
+            math_text: true,
+            ..Constructs::gfm()
+        },
+        ..ParseOptions::gfm()
+    };
+    markdown::to_mdast(&raw, &options).map_err(|e| e.to_string().into())
+}
+
+/// Parse the markdown to the node tree in the current thread.
+pub(super) fn markdown_to_node(raw: &str, cx: &mut App) -> Result<element::Node, SharedString> {
+    let style = TextViewStyle::default();
+    parse_markdown(raw).map(|ast| {
+        let mut node = ast_to_node(ast, &style, cx);
+        resolve_checkbox_spans(&mut node, raw);
+        node.number_details(&mut 0);
+        node
+    })
+}
+
+/// Narrow the spans of the task list items to the checkbox markers in the parsed `text`.
+fn resolve_checkbox_spans(node: &mut element::Node, text: &str) {
+    node.for_each_checkbox_span(&mut |span| {
+        *span = span
+            .and_then(|item| task_list::checkbox_range(text, item.start..item.end))
+            .map(|range| Span {
+                start: range.start,
+                end: range.end,
+            });
+    });
 }
 
 fn parse_table_row(table: &mut Table, node: &mdast::TableRow) {
@@ -220,8 +567,8 @@ fn parse_paragraph(paragraph: &mut Paragraph, node: &mdast::Node) -> String {
             });
         }
         Node::Text(val) => {
-            text = val.value.clone();
-            paragraph.push_str(&val.value)
+            text = emoji::expand_shortcodes(&val.value).into_owned();
+            paragraph.push_str(&text)
         }
         Node::Emphasis(val) => {
             let mut child_paragraph = Paragraph::default();
@@ -229,7 +576,7 @@ fn parse_paragraph(paragraph: &mut Paragraph, node: &mdast::Node) -> String {
                 text.push_str(&parse_paragraph(&mut child_paragraph, &child));
             }
             paragraph.push(element::TextNode {
-                text: text.clone(),
+                text: text.clone().into(),
                 marks: vec![(
                     0..text.len(),
                     InlineTextStyle {
@@ -245,7 +592,7 @@ fn parse_paragraph(paragraph: &mut Paragraph, node: &mdast::Node) -> String {
                 text.push_str(&parse_paragraph(&mut child_paragraph, &child));
             }
             paragraph.push(element::TextNode {
-                text: text.clone(),
+                text: text.clone().into(),
                 marks: vec![(
                     0..text.len(),
                     InlineTextStyle {
@@ -261,7 +608,7 @@ fn parse_paragraph(paragraph: &mut Paragraph, node: &mdast::Node) -> String {
                 text.push_str(&parse_paragraph(&mut child_paragraph, &child));
             }
             paragraph.push(element::TextNode {
-                text: text.clone(),
+                text: text.clone().into(),
                 marks: vec![(
                     0..text.len(),
                     InlineTextStyle {
@@ -274,7 +621,7 @@ fn parse_paragraph(paragraph: &mut Paragraph, node: &mdast::Node) -> String {
         Node::InlineCode(val) => {
             text = val.value.clone();
             paragraph.push(element::TextNode {
-                text: text.clone(),
+                text: text.clone().into(),
                 marks: vec![(
                     0..text.len(),
                     InlineTextStyle {
@@ -290,13 +637,13 @@ fn parse_paragraph(paragraph: &mut Paragraph, node: &mdast::Node) -> String {
                 text.push_str(&parse_paragraph(&mut child_paragraph, &child));
             }
             paragraph.push(element::TextNode {
-                text: text.clone(),
+                text: text.clone().into(),
                 marks: vec![(
                     0..text.len(),
                     InlineTextStyle {
                         link: Some(LinkMark {
                             url: val.url.clone().into(),
-                            title: val.title.clone().map(|s| s.into()),
+                            title: val.title.clone().map(Into::into),
                         }),
                         ..Default::default()
                     },
@@ -311,23 +658,33 @@ fn parse_paragraph(paragraph: &mut Paragraph, node: &mdast::Node) -> String {
                 ..Default::default()
             });
         }
-        Node::InlineMath(raw) => {
-            text = raw.value.clone();
+        Node::FootnoteReference(val) => {
+            text = superscript(val.label.as_deref().unwrap_or(&val.identifier));
             paragraph.push(element::TextNode {
-                text: text.clone(),
+                text: text.clone().into(),
                 marks: vec![(
                     0..text.len(),
                     InlineTextStyle {
-                        code: true,
+                        link: Some(LinkMark {
+                            url: format!("{}{}", FOOTNOTE_URL_PREFIX, val.identifier).into(),
+                            title: None,
+                        }),
                         ..Default::default()
                     },
                 )],
             });
         }
+        Node::InlineMath(raw) => {
+            text = math::to_unicode(&raw.value);
+            paragraph.push(element::TextNode {
+                text: text.clone().into(),
+                marks: vec![],
+            });
+        }
         Node::MdxTextExpression(raw) => {
             text = raw.value.clone();
             paragraph.push(element::TextNode {
-                text: text.clone(),
+                text: text.clone().into(),
                 marks: vec![(0..text.len(), InlineTextStyle::default())],
             });
         }
@@ -336,7 +693,7 @@ fn parse_paragraph(paragraph: &mut Paragraph, node: &mdast::Node) -> String {
                 if el.is_break() {
                     text = "\n".to_owned();
                     paragraph.push(element::TextNode {
-                        text: text.clone(),
+                        text: text.clone().into(),
                         marks: vec![(0..text.len(), InlineTextStyle::default())],
                     });
                 } else {
@@ -363,30 +720,248 @@ fn parse_paragraph(paragraph: &mut Paragraph, node: &mdast::Node) -> String {
     text
 }
 
+/// Returns the text node of the `range` of the `text`, `None` if the range is empty.
+fn slice_text(text: &mdast::Text, range: Range<usize>) -> Option<Node> {
+    if range.is_empty() {
+        return None;
+    }
+
+    let position = text.position.clone().map(|mut pos| {
+        pos.end.offset = pos.start.offset + range.end;
+        pos.start.offset += range.start;
+        pos
+    });
+    Some(Node::Text(mdast::Text {
+        value: text.value[range].to_string(),
+        position,
+    }))
+}
+
+/// The inline nodes of a term and of the definitions of the definition list.
+type DefinitionLines = (Vec<Node>, Vec<Vec<Node>>);
+
+/// Split the inline nodes of the paragraph to the terms and the definitions, `None` if the
+/// paragraph is not a definition list.
+///
+/// A definition is a line starting with `: ` after the term:
+///
+/// ```md
+/// Term
+/// : Definition
+/// : Another definition
+/// ```
+///
+/// The consecutive terms share the definitions, and the line after a definition continues it,
+/// unless the line is a term followed by a definition.
+fn split_definition_list(children: &[Node]) -> Option<Vec<DefinitionLines>> {
+    // The lines of the paragraph, `true` for the definitions.
+    let mut lines: Vec<(bool, Vec<Node>)> = vec![(false, vec![])];
+    for child in children {
+        let Node::Text(text) = child else {
+            lines.last_mut()?.1.push(child.clone());
+            continue;
+        };
+
+        let mut start = 0;
+        for (ix, _) in text.value.match_indices('\n') {
+            let is_definition = text.value[ix + 1..].starts_with(": ");
+            lines.last_mut()?.1.extend(slice_text(text, start..ix));
+            lines.push((is_definition, vec![]));
+            start = if is_definition { ix + 3 } else { ix + 1 };
+        }
+        lines
+            .last_mut()?
+            .1
+            .extend(slice_text(text, start..text.value.len()));
+    }
+
+    if lines[0].1.is_empty() || !lines.iter().any(|(is_definition, _)| *is_definition) {
+        return None;
+    }
+
+    let mut items: Vec<DefinitionLines> = vec![];
+    let mut lines = lines.into_iter().peekable();
+    while let Some((is_definition, line)) = lines.next() {
+        let next_is_definition = lines
+            .peek()
+            .is_some_and(|(is_definition, _)| *is_definition);
+        match items.last_mut() {
+            Some((_, definitions)) if is_definition => definitions.push(line),
+            Some((_, definitions)) if !next_is_definition && !definitions.is_empty() => {
+                let definition = definitions.last_mut()?;
+                definition.push(Node::Text(mdast::Text {
+                    value: "\n".to_string(),
+                    position: None,
+                }));
+                definition.extend(line);
+            }
+            _ => items.push((line, vec![])),
+        }
+    }
+    Some(items)
+}
+
+fn inline_paragraph(children: &[Node]) -> Paragraph {
+    let mut paragraph = Paragraph::default();
+    children.iter().for_each(|c| {
+        parse_paragraph(&mut paragraph, c);
+    });
+    paragraph
+}
+
+/// Returns the footnotes section of the definitions, `None` if there is no definition.
+fn footnotes_node(
+    definitions: Vec<mdast::FootnoteDefinition>,
+    style: &TextViewStyle,
+    cx: &mut App,
+) -> Option<element::Node> {
+    if definitions.is_empty() {
+        return None;
+    }
+
+    let footnotes = definitions
+        .into_iter()
+        .map(|definition| Footnote {
+            label: definition
+                .label
+                .unwrap_or_else(|| definition.identifier.clone())
+                .into(),
+            id: definition.identifier.into(),
+            children: blocks_to_nodes(definition.children, style, cx),
+        })
+        .collect();
+    Some(element::Node::Footnotes(footnotes))
+}
+
+/// Returns `Some(true)` if the html block opens a `<details>` section without closing it, e.g.:
+/// `<details>\n<summary>Title</summary>`, or `Some(false)` if the html block is the `</details>`.
+fn details_tag(node: &Node) -> Option<bool> {
+    let Node::Html(html) = node else {
+        return None;
+    };
+
+    let html = html.value.trim().to_ascii_lowercase();
+    if html == "</details>" {
+        return Some(false);
+    }
+
+    let rest = html.strip_prefix("<details")?;
+    let is_tag = rest.starts_with('>') || rest.starts_with(char::is_whitespace);
+    (is_tag && !rest.contains("</details>")).then_some(true)
+}
+
+/// Group the blocks by the `<details>` sections in the markdown, e.g.:
+///
+/// ```md
+/// <details>
+/// <summary>Title</summary>
+///
+/// The **content** of the section.
+///
+/// </details>
+/// ```
+///
+/// The blocks from the `<details>` html to the matched `</details>` html are in one group,
+/// the other blocks are in their own groups.
+fn group_details(children: Vec<Node>) -> Vec<Vec<Node>> {
+    let mut ends = vec![None; children.len()];
+    let mut opens = vec![];
+    for (ix, child) in children.iter().enumerate() {
+        match details_tag(child) {
+            Some(true) => opens.push(ix),
+            Some(false) => {
+                if let Some(start) = opens.pop() {
+                    ends[start] = Some(ix);
+                }
+            }
+            None => {}
+        }
+    }
+
+    let mut groups = vec![];
+    let mut children = children.into_iter();
+    let mut ix = 0;
+    while ix < ends.len() {
+        let end = ends[ix].unwrap_or(ix);
+        groups.push(children.by_ref().take(end - ix + 1).collect());
+        ix = end + 1;
+    }
+    groups
+}
+
+/// Convert the blocks of a `<details>` section grouped by [`group_details`].
+fn details_node(mut blocks: Vec<Node>, style: &TextViewStyle, cx: &mut App) -> element::Node {
+    // The `</details>` html.
+    blocks.pop();
+    let mut blocks = blocks.into_iter();
+    let Some(open) = blocks.next() else {
+        return element::Node::Unknown;
+    };
+    let children = blocks_to_nodes(blocks.collect(), style, cx);
+
+    match ast_to_node(open, style, cx) {
+        element::Node::Details(details) => {
+            let mut inner = details.children;
+            inner.extend(children);
+            element::Node::Details(DetailsBlock::new(details.summary, inner, details.open))
+        }
+        node => element::Node::Root {
+            children: std::iter::once(node).chain(children).collect(),
+        },
+    }
+}
+
+/// Convert the blocks with the `<details>` sections.
+fn blocks_to_nodes(children: Vec<Node>, style: &TextViewStyle, cx: &mut App) -> Vec<element::Node> {
+    group_details(children)
+        .into_iter()
+        .map(|mut blocks| {
+            if blocks.len() == 1 {
+                ast_to_node(blocks.remove(0), style, cx)
+            } else {
+                details_node(blocks, style, cx)
+            }
+        })
+        .collect()
+}
+
 fn ast_to_node(value: mdast::Node, style: &TextViewStyle, cx: &mut App) -> element::Node {
     match value {
         Node::Root(val) => {
-            let children = val
+            let (footnotes, children): (Vec<_>, Vec<_>) = val
                 .children
                 .into_iter()
-                .map(|c| ast_to_node(c, style, cx))
+                .partition(|c| matches!(c, Node::FootnoteDefinition(_)));
+            let footnotes = footnotes
+                .into_iter()
+                .filter_map(|c| match c {
+                    Node::FootnoteDefinition(definition) => Some(definition),
+                    _ => None,
+                })
                 .collect();
+            let mut children = blocks_to_nodes(children, style, cx);
+            children.extend(footnotes_node(footnotes, style, cx));
             element::Node::Root { children }
         }
-        Node::Paragraph(val) => {
-            let mut paragraph = Paragraph::default();
-            val.children.iter().for_each(|c| {
-                parse_paragraph(&mut paragraph, c);
-            });
-
-            element::Node::Paragraph(paragraph)
-        }
+        Node::Paragraph(val) => match split_definition_list(&val.children) {
+            Some(items) => element::Node::DefinitionList(
+                items
+                    .into_iter()
+                    .map(|(term, definitions)| DefinitionItem {
+                        term: inline_paragraph(&term),
+                        definitions: definitions
+                            .iter()
+                            .map(|definition| {
+                                element::Node::Paragraph(inline_paragraph(definition))
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            ),
+            None => element::Node::Paragraph(inline_paragraph(&val.children)),
+        },
         Node::Blockquote(val) => {
-            let children = val
-                .children
-                .into_iter()
-                .map(|c| ast_to_node(c, style, cx))
-                .collect();
+            let children = blocks_to_nodes(val.children, style, cx);
             element::Node::Blockquote { children }
         }
         Node::List(list) => {
@@ -401,21 +976,43 @@ fn ast_to_node(value: mdast::Node, style: &TextViewStyle, cx: &mut App) -> eleme
             }
         }
         Node::ListItem(val) => {
-            let children = val
-                .children
-                .into_iter()
-                .map(|c| ast_to_node(c, style, cx))
-                .collect();
+            let children = blocks_to_nodes(val.children, style, cx);
+            // The span of the whole item, see `resolve_checkbox_spans`.
+            let span = val
+                .position
+                .as_ref()
+                .filter(|_| val.checked.is_some())
+                .map(|pos| Span {
+                    start: pos.start.offset,
+                    end: pos.end.offset,
+                });
             element::Node::ListItem {
                 children,
                 spread: val.spread,
                 checked: val.checked,
+                span,
             }
         }
         Node::Break(_) => element::Node::Break { html: false },
+        Node::Code(raw) if raw.lang.as_deref() == Some("math") => {
+            element::Node::Math(MathBlock::new(raw.value.into()))
+        }
+        Node::Code(raw) if raw.lang.as_deref() == Some("mermaid") => {
+            let source = SharedString::from(raw.value);
+            match MermaidBlock::parse(source.clone()) {
+                Some(mermaid) => element::Node::Mermaid(mermaid),
+                // The unsupported diagrams are shown as the source.
+                None => element::Node::CodeBlock(CodeBlock::new(
+                    source,
+                    Some("mermaid".into()),
+                    style,
+                    cx,
+                )),
+            }
+        }
         Node::Code(raw) => element::Node::CodeBlock(CodeBlock::new(
             raw.value.into(),
-            raw.lang.map(|s| s.into()),
+            raw.lang.map(Into::into),
             style,
             cx,
         )),
@@ -430,9 +1027,7 @@ fn ast_to_node(value: mdast::Node, style: &TextViewStyle, cx: &mut App) -> eleme
                 children: paragraph,
             }
         }
-        Node::Math(val) => {
-            element::Node::CodeBlock(CodeBlock::new(val.value.into(), None, style, cx))
-        }
+        Node::Math(val) => element::Node::Math(MathBlock::new(val.value.into())),
         Node::Html(val) => match parse_html(&val.value) {
             Ok(el) => el,
             Err(err) => {
@@ -489,8 +1084,9 @@ fn ast_to_node(value: mdast::Node, style: &TextViewStyle, cx: &mut App) -> eleme
                     parse_table_row(&mut table, row);
                 }
             });
+            table.update_col_lens();
 
-            element::Node::Table(table)
+            element::Node::Table(Arc::new(table))
         }
         _ => {
             if cfg!(debug_assertions) {
@@ -500,3 +1096,162 @@ fn ast_to_node(value: mdast::Node, style: &TextViewStyle, cx: &mut App) -> eleme
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use markdown::mdast::Node;
+
+    use super::{
+        parse_markdown, parse_paragraph, reparse_range, split_definition_list, Paragraph, Reparse,
+    };
+
+    #[test]
+    fn test_reparse_range() {
+        let old = "# A\n\nB\n\nC";
+        let blocks = [0..3, 5..6, 8..9];
+        // Append to the last block.
+        assert_eq!(
+            reparse_range(old, "# A\n\nB\n\nCD", &blocks),
+            Some(Reparse {
+                keep_prefix: 2,
+                keep_suffix: 0,
+                range: 6..10,
+            })
+        );
+        // The first block is changed, the block after it is also re-parsed.
+        assert_eq!(
+            reparse_range(old, "# AX\n\nB\n\nC", &blocks),
+            Some(Reparse {
+                keep_prefix: 0,
+                keep_suffix: 1,
+                range: 0..9,
+            })
+        );
+
+        let old = "A\n\nB\n\nC\n\nD";
+        let blocks = [0..1, 3..4, 6..7, 9..10];
+        assert_eq!(
+            reparse_range(old, "A\n\nBX\n\nC\n\nD", &blocks),
+            Some(Reparse {
+                keep_prefix: 1,
+                keep_suffix: 1,
+                range: 1..10,
+            })
+        );
+        assert_eq!(
+            reparse_range(old, "A\n\nB\n\nCX\n\nD", &blocks),
+            Some(Reparse {
+                keep_prefix: 2,
+                keep_suffix: 0,
+                range: 4..11,
+            })
+        );
+
+        // No block to reuse.
+        assert_eq!(reparse_range("A", "AB", &[0..1]), None);
+        // The fenced code block may contain the following blocks.
+        assert_eq!(reparse_range(old, "A\n\nB\n\n```\nC\n\nD", &blocks), None);
+        // The footnote definitions are moved to the end.
+        assert_eq!(reparse_range(old, "A\n\nB\n\n[^1]: C\n\nD", &blocks), None);
+    }
+
+    #[test]
+    fn test_autolink_literal() {
+        let ast =
+            parse_markdown("Visit https://example.com, www.example.com or foo@bar.com.").unwrap();
+        let mut paragraph = Paragraph::default();
+        parse_paragraph(&mut paragraph, &ast.children().unwrap()[0]);
+        let Paragraph::Texts { children, .. } = paragraph else {
+            panic!("expected the texts");
+        };
+
+        let links = children
+            .iter()
+            .flat_map(|node| {
+                node.marks.iter().filter_map(|(_, style)| {
+                    let link = style.link.as_ref()?;
+                    Some((&*node.text, &*link.url))
+                })
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            [
+                ("https://example.com", "https://example.com"),
+                ("www.example.com", "http://www.example.com"),
+                ("foo@bar.com", "mailto:foo@bar.com"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_details() {
+        let source = "Intro\n\n<details>\n<summary>Outer</summary>\n\nBody\n\n<details>\n\nInner\n\n</details>\n\n</details>\n\n<details><summary>Inline</summary>Body</details>\n\n</details>";
+        let ast = parse_markdown(source).unwrap();
+        let children = ast.children().unwrap().clone();
+        assert_eq!(
+            children.iter().map(details_tag).collect::<Vec<_>>(),
+            [
+                None,
+                Some(true),
+                None,
+                Some(true),
+                None,
+                Some(false),
+                Some(false),
+                None,
+                Some(false)
+            ]
+        );
+
+        let groups = group_details(children);
+        assert_eq!(
+            groups.iter().map(|group| group.len()).collect::<Vec<_>>(),
+            [1, 6, 1, 1]
+        );
+    }
+
+    fn definition_list(source: &str) -> Option<Vec<(String, Vec<String>)>> {
+        let ast = parse_markdown(source).unwrap();
+        let Node::Paragraph(paragraph) = &ast.children().unwrap()[0] else {
+            panic!("expected the paragraph");
+        };
+        let text = |nodes: &[Node]| {
+            nodes
+                .iter()
+                .map(|node| node.to_string())
+                .collect::<String>()
+        };
+
+        split_definition_list(&paragraph.children).map(|items| {
+            items
+                .iter()
+                .map(|(term, definitions)| {
+                    (text(term), definitions.iter().map(|d| text(d)).collect())
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_split_definition_list() {
+        assert_eq!(definition_list("Term\nNot a definition"), None);
+        assert_eq!(definition_list("Term: not a definition"), None);
+        assert_eq!(
+            definition_list("Term\n: Definition\n: Another *one*"),
+            Some(vec![(
+                "Term".to_string(),
+                vec!["Definition".to_string(), "Another one".to_string()]
+            )])
+        );
+        // The consecutive terms share the definitions, the lazy line continues the definition.
+        assert_eq!(
+            definition_list("A\nB\n: AB\ncontinued\nC\n: C"),
+            Some(vec![
+                ("A".to_string(), vec![]),
+                ("B".to_string(), vec!["AB\ncontinued".to_string()]),
+                ("C".to_string(), vec!["C".to_string()]),
+            ])
+        );
+    }
+}
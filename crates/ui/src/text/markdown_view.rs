@@ -0,0 +1,206 @@
+use std::{rc::Rc, time::Duration};
+
+use gpui::{App, Context, IntoElement, Render, ScrollHandle, SharedString, Window};
+
+use super::{link::OnLinkClick, markdown::MarkdownElement, TextViewStyle};
+
+/// A Markdown view of the streaming source, e.g.: the responses of the LLM in a chat.
+///
+/// The source is extended by [`MarkdownView::append`] without recreating the view, only the
+/// blocks at the tail are parsed again, the other blocks are reused.
+///
+/// ```ignore
+/// let view = cx.new(|_| MarkdownView::new());
+///
+/// // For each chunk of the response
+/// view.update(cx, |view, cx| view.append(&chunk, cx));
+/// ```
+pub struct MarkdownView {
+    text: SharedString,
+    style: TextViewStyle,
+    debounce: Duration,
+    scroll_handle: Option<ScrollHandle>,
+    on_link_click: Option<OnLinkClick>,
+}
+
+impl MarkdownView {
+    pub fn new() -> Self {
+        Self {
+            text: SharedString::default(),
+            style: TextViewStyle::default(),
+            debounce: Duration::ZERO,
+            scroll_handle: None,
+            on_link_click: None,
+        }
+    }
+
+    /// Set the initial source.
+    pub fn text(mut self, text: impl Into<SharedString>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Set [`TextViewStyle`].
+    pub fn style(mut self, style: TextViewStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the debounce of the source changes, default is no debounce.
+    ///
+    /// See also [`super::TextView::debounce`].
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Set the scroll handle of the scrollable container of the view.
+    ///
+    /// See also [`super::TextView::scroll_handle`].
+    pub fn scroll_handle(mut self, scroll_handle: &ScrollHandle) -> Self {
+        self.scroll_handle = Some(scroll_handle.clone());
+        self
+    }
+
+    /// Set the handler of the link clicks.
+    ///
+    /// See also [`super::TextView::on_link_click`].
+    pub fn on_link_click(
+        mut self,
+        handler: impl Fn(&str, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_link_click = Some(Rc::new(handler));
+        self
+    }
+
+    /// Returns the source.
+    pub fn source(&self) -> &SharedString {
+        &self.text
+    }
+
+    /// Replace the whole source.
+    pub fn set_text(&mut self, text: impl Into<SharedString>, cx: &mut Context<Self>) {
+        self.text = text.into();
+        cx.notify();
+    }
+
+    /// Append the `text` to the end of the source.
+    pub fn append(&mut self, text: &str, cx: &mut Context<Self>) {
+        if text.is_empty() {
+            return;
+        }
+
+        self.text = format!("{}{}", self.text, text).into();
+        cx.notify();
+    }
+
+    /// Replace the last block of the source with the `text`, e.g.: to correct the partial
+    /// block of the stream.
+    ///
+    /// The blocks are separated by the blank lines, the headings and the fenced code or math
+    /// blocks, the whole source is replaced if it has only one block.
+    pub fn replace_last_block(&mut self, text: &str, cx: &mut Context<Self>) {
+        let start = last_block_start(&self.text);
+        self.text = format!("{}{}", &self.text[..start], text).into();
+        cx.notify();
+    }
+
+    /// Clear the source.
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.set_text("", cx);
+    }
+}
+
+impl Default for MarkdownView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for MarkdownView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut el = MarkdownElement::new(("markdown-view", cx.entity_id()), self.text.clone())
+            .style(self.style.clone())
+            .debounce(self.debounce);
+        if let Some(scroll_handle) = &self.scroll_handle {
+            el = el.scroll_handle(scroll_handle);
+        }
+        if let Some(handler) = &self.on_link_click {
+            el = el.on_link_click(handler.clone());
+        }
+        el
+    }
+}
+
+fn is_heading(line: &str) -> bool {
+    let rest = line.trim_start_matches('#');
+    let level = line.len() - rest.len();
+    (1..=6).contains(&level) && (rest.is_empty() || rest.starts_with([' ', '\t']))
+}
+
+/// Returns the byte offset of the start of the last top level block of the Markdown `text`.
+fn last_block_start(text: &str) -> usize {
+    let mut start = 0;
+    let mut offset = 0;
+    let mut fence: Option<&str> = None;
+    // The next line starts a new block.
+    let mut boundary = true;
+
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim();
+        match fence {
+            Some(marker) => {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                    boundary = true;
+                }
+            }
+            None if trimmed.is_empty() => boundary = true,
+            None => {
+                let marker = ["```", "~~~", "$$"]
+                    .into_iter()
+                    .find(|marker| trimmed.starts_with(marker));
+                let heading = is_heading(trimmed);
+                if boundary || heading || marker.is_some() {
+                    start = offset;
+                }
+                boundary = heading;
+
+                if let Some(marker) = marker {
+                    // The math block in a single line, e.g.: `$$x^2$$`.
+                    if marker == "$$" && trimmed.len() > 4 && trimmed.ends_with("$$") {
+                        boundary = true;
+                    } else {
+                        fence = Some(marker);
+                    }
+                }
+            }
+        }
+        offset += line.len();
+    }
+
+    start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::last_block_start;
+
+    #[test]
+    fn test_last_block_start() {
+        assert_eq!(last_block_start(""), 0);
+        assert_eq!(last_block_start("Hello"), 0);
+        assert_eq!(last_block_start("Hello\n\nWorld"), 7);
+        assert_eq!(last_block_start("Hello\n\nWorld\n"), 7);
+        assert_eq!(last_block_start("Hello\nWorld\n\n"), 0);
+        assert_eq!(last_block_start("# Title\nHello"), 8);
+        assert_eq!(last_block_start("#hashtag\nHello"), 0);
+        // The blank lines in the fenced code are not the boundaries.
+        assert_eq!(
+            last_block_start("Hello\n```rust\nfn a() {}\n\nfn b() {}"),
+            6
+        );
+        assert_eq!(last_block_start("```\na\n\nb\n```\nHello"), 13);
+        assert_eq!(last_block_start("$$x^2$$\nHello"), 8);
+    }
+}
@@ -0,0 +1,721 @@
+//! A subset of the LaTeX math, e.g.: `\frac{a}{b}`, `x^2`, `a_{ij}`, `\sqrt{x}` and the
+//! Greek letters and operators.
+//!
+//! The block math is laid out by the elements, and the inline math is converted to the
+//! Unicode text (e.g.: `x^2` to `x²`), because it is a part of the text of the paragraph.
+use std::{iter::Peekable, str::Chars, sync::Arc};
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, rems, AnyElement, App, IntoElement, ParentElement as _,
+    SharedString, Styled as _,
+};
+
+use crate::{h_flex, v_flex, ActiveTheme as _};
+
+/// The scale of the super/subscripts to the base.
+const SCRIPT_SCALE: f32 = 0.7;
+/// The minimum text size (in rems) of the nested scripts.
+const MIN_SIZE: f32 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum MathNode {
+    /// The variables, in italic.
+    Ident(String),
+    /// The numbers, operators and functions.
+    Text(String),
+    Row(Vec<MathNode>),
+    Frac(Box<MathNode>, Box<MathNode>),
+    Sqrt {
+        index: Option<Box<MathNode>>,
+        radicand: Box<MathNode>,
+    },
+    Scripts {
+        base: Box<MathNode>,
+        sub: Option<Box<MathNode>>,
+        sup: Option<Box<MathNode>>,
+    },
+    /// The line break by `\\`.
+    Break,
+}
+
+/// A block of the math, e.g.: `$$...$$` or the ` ```math ` code block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MathBlock {
+    pub(super) source: SharedString,
+    pub(super) node: Arc<MathNode>,
+}
+
+impl MathBlock {
+    pub(super) fn new(source: SharedString) -> Self {
+        let node = Arc::new(parse(&source));
+        Self { source, node }
+    }
+
+    pub(super) fn render(&self, cx: &App) -> AnyElement {
+        let lines = match self.node.as_ref() {
+            MathNode::Row(children) => children
+                .split(|child| *child == MathNode::Break)
+                .map(|line| render_row(line, 1.2, cx))
+                .collect(),
+            node => vec![node.render(1.2, cx)],
+        };
+
+        v_flex()
+            .w_full()
+            .items_center()
+            .gap_2()
+            .children(lines)
+            .into_any_element()
+    }
+}
+
+/// Parse the LaTeX math, the unsupported commands are kept as the source.
+pub(super) fn parse(source: &str) -> MathNode {
+    let mut parser = Parser {
+        chars: source.chars().peekable(),
+    };
+    MathNode::row(parser.parse_row(None))
+}
+
+/// Convert the LaTeX math to the Unicode text, used for the inline math.
+pub(super) fn to_unicode(source: &str) -> String {
+    parse(source).to_unicode().trim().to_string()
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    /// Parse the nodes until the `end` char (consumed) or the end of the source.
+    fn parse_row(&mut self, end: Option<char>) -> Vec<MathNode> {
+        let mut nodes = vec![];
+        loop {
+            self.skip_whitespace();
+            let Some(c) = self.chars.peek().copied() else {
+                break;
+            };
+            if Some(c) == end {
+                self.chars.next();
+                break;
+            }
+
+            match c {
+                '^' | '_' => {
+                    self.chars.next();
+                    let base = nodes.pop().unwrap_or(MathNode::Text(String::new()));
+                    let script = self.parse_atom();
+                    nodes.push(base.attach(c == '^', script));
+                }
+                '0'..='9' => {
+                    let mut number = String::new();
+                    while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+                        number.push(c);
+                    }
+                    nodes.push(MathNode::Text(number));
+                }
+                // The alignment and the unmatched braces.
+                '&' | '}' => {
+                    self.chars.next();
+                }
+                _ => {
+                    if let Some(node) = self.parse_atom() {
+                        nodes.push(node);
+                    }
+                }
+            }
+        }
+        nodes
+    }
+
+    /// Parse a char, a command or a group.
+    fn parse_atom(&mut self) -> Option<MathNode> {
+        self.skip_whitespace();
+        match self.chars.next()? {
+            '{' => Some(MathNode::row(self.parse_row(Some('}')))),
+            '\\' => self.parse_command(),
+            c if c.is_alphabetic() => Some(MathNode::Ident(c.to_string())),
+            c => Some(MathNode::Text(
+                match c {
+                    '-' => '−',
+                    '*' => '∗',
+                    '\'' => '′',
+                    c => c,
+                }
+                .to_string(),
+            )),
+        }
+    }
+
+    /// Returns the raw text of the `{...}` group, or the next char.
+    fn parse_raw_group(&mut self) -> String {
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&'{').is_none() {
+            return self.chars.next().map(String::from).unwrap_or_default();
+        }
+
+        let mut text = String::new();
+        let mut depth = 0;
+        for c in self.chars.by_ref() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            text.push(c);
+        }
+        text
+    }
+
+    fn parse_command(&mut self) -> Option<MathNode> {
+        let mut name = String::new();
+        while let Some(c) = self.chars.next_if(|c| c.is_ascii_alphabetic()) {
+            name.push(c);
+        }
+        if name.is_empty() {
+            let c = self.chars.next()?;
+            return match c {
+                '\\' => Some(MathNode::Break),
+                ',' | ':' | ';' | '>' | ' ' => Some(MathNode::Text(" ".into())),
+                '!' => None,
+                '|' => Some(MathNode::Text("‖".into())),
+                c => Some(MathNode::Text(c.to_string())),
+            };
+        }
+
+        let node = match name.as_str() {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let num = self.parse_atom().unwrap_or(MathNode::Row(vec![]));
+                let den = self.parse_atom().unwrap_or(MathNode::Row(vec![]));
+                MathNode::Frac(Box::new(num), Box::new(den))
+            }
+            "sqrt" => {
+                self.skip_whitespace();
+                let index = self
+                    .chars
+                    .next_if_eq(&'[')
+                    .map(|_| Box::new(MathNode::row(self.parse_row(Some(']')))));
+                let radicand = self.parse_atom().unwrap_or(MathNode::Row(vec![]));
+                MathNode::Sqrt {
+                    index,
+                    radicand: Box::new(radicand),
+                }
+            }
+            "text" | "textrm" | "textit" | "mbox" | "operatorname" => {
+                MathNode::Text(self.parse_raw_group())
+            }
+            "mathbb" => {
+                let text = self.parse_raw_group();
+                MathNode::Text(text.chars().map(double_struck).collect())
+            }
+            "mathrm" | "mathbf" | "mathit" | "mathcal" | "mathsf" | "boldsymbol" => {
+                self.parse_atom()?
+            }
+            "left" | "right" | "big" | "Big" | "bigg" | "Bigg" | "displaystyle" => {
+                self.skip_whitespace();
+                self.chars.next_if_eq(&'.');
+                return None;
+            }
+            "begin" | "end" => {
+                self.parse_raw_group();
+                return None;
+            }
+            "quad" => MathNode::Text("\u{2003}".into()),
+            "qquad" => MathNode::Text("\u{2003}\u{2003}".into()),
+            "sin" | "cos" | "tan" | "cot" | "sec" | "csc" | "arcsin" | "arccos" | "arctan"
+            | "sinh" | "cosh" | "tanh" | "log" | "ln" | "lg" | "exp" | "lim" | "min" | "max"
+            | "sup" | "inf" | "det" | "gcd" | "deg" | "dim" | "ker" | "arg" | "mod" => {
+                MathNode::Text(name)
+            }
+            _ => match symbol(&name) {
+                Some(symbol) if is_greek_lowercase(symbol) => MathNode::Ident(symbol.to_string()),
+                Some(symbol) => MathNode::Text(symbol.to_string()),
+                None => MathNode::Text(format!("\\{}", name)),
+            },
+        };
+        Some(node)
+    }
+}
+
+impl MathNode {
+    fn row(mut nodes: Vec<MathNode>) -> Self {
+        if nodes.len() == 1 {
+            nodes.remove(0)
+        } else {
+            Self::Row(nodes)
+        }
+    }
+
+    /// Attach the superscript (`sup` is true) or subscript to the node.
+    fn attach(self, sup: bool, script: Option<MathNode>) -> Self {
+        let script = script.map(Box::new);
+        match self {
+            Self::Scripts {
+                base,
+                sub,
+                sup: None,
+            } if sup => Self::Scripts {
+                base,
+                sub,
+                sup: script,
+            },
+            Self::Scripts {
+                base,
+                sub: None,
+                sup: sup_script,
+            } if !sup => Self::Scripts {
+                base,
+                sub: script,
+                sup: sup_script,
+            },
+            base if sup => Self::Scripts {
+                base: Box::new(base),
+                sub: None,
+                sup: script,
+            },
+            base => Self::Scripts {
+                base: Box::new(base),
+                sub: script,
+                sup: None,
+            },
+        }
+    }
+
+    /// Returns true if the node is shown as a single char.
+    fn is_single_char(&self) -> bool {
+        match self {
+            Self::Ident(text) | Self::Text(text) => text.chars().count() <= 1,
+            _ => false,
+        }
+    }
+
+    pub(super) fn to_unicode(&self) -> String {
+        let wrap = |node: &MathNode| match node.is_single_char() {
+            true => node.to_unicode(),
+            false => format!("({})", node.to_unicode()),
+        };
+        let script = |node: &MathNode, map: fn(char) -> Option<char>, mark: char| {
+            let text = node.to_unicode();
+            match text
+                .chars()
+                .filter(|c| *c != ' ')
+                .map(map)
+                .collect::<Option<String>>()
+            {
+                Some(script) => script,
+                None => format!("{}{}", mark, wrap(node)),
+            }
+        };
+
+        match self {
+            Self::Text(text) if text.chars().all(is_operator) => format!(" {} ", text),
+            Self::Ident(text) | Self::Text(text) => text.clone(),
+            Self::Row(children) => children.iter().map(Self::to_unicode).collect(),
+            Self::Frac(num, den) => format!("{}/{}", wrap(num), wrap(den)),
+            Self::Sqrt { index, radicand } => format!(
+                "{}√{}",
+                index
+                    .as_ref()
+                    .map(|index| script(index, superscript, '^'))
+                    .unwrap_or_default(),
+                wrap(radicand)
+            ),
+            Self::Scripts { base, sub, sup } => format!(
+                "{}{}{}",
+                base.to_unicode(),
+                sub.as_ref()
+                    .map(|sub| script(sub, subscript, '_'))
+                    .unwrap_or_default(),
+                sup.as_ref()
+                    .map(|sup| script(sup, superscript, '^'))
+                    .unwrap_or_default(),
+            ),
+            Self::Break => " ".to_string(),
+        }
+    }
+
+    /// Render the node with the text size in rems.
+    fn render(&self, size: f32, cx: &App) -> AnyElement {
+        let script_size = (size * SCRIPT_SCALE).max(MIN_SIZE);
+
+        match self {
+            Self::Ident(text) => div()
+                .text_size(rems(size))
+                .italic()
+                .child(text.clone())
+                .into_any_element(),
+            Self::Text(text) => div()
+                .text_size(rems(size))
+                .when(text.chars().any(is_operator), |this| {
+                    this.mx(rems(size * 0.2))
+                })
+                .child(text.clone())
+                .into_any_element(),
+            Self::Row(children) => render_row(children, size, cx),
+            Self::Frac(num, den) => v_flex()
+                .items_center()
+                .mx(rems(size * 0.15))
+                .child(num.render(size * 0.9, cx))
+                .child(
+                    div()
+                        .w_full()
+                        .h(px(1.))
+                        .my(rems(size * 0.1))
+                        .bg(cx.theme().foreground),
+                )
+                .child(den.render(size * 0.9, cx))
+                .into_any_element(),
+            Self::Sqrt { index, radicand } => h_flex()
+                .items_end()
+                .when_some(index.as_ref(), |this, index| {
+                    this.child(
+                        div()
+                            .mb(rems(size * 0.5))
+                            .mr(rems(-size * 0.2))
+                            .child(index.render(script_size, cx)),
+                    )
+                })
+                .child(div().text_size(rems(size * 1.1)).child("√"))
+                .child(
+                    div()
+                        .border_t_1()
+                        .border_color(cx.theme().foreground)
+                        .pl(rems(size * 0.1))
+                        .child(radicand.render(size, cx)),
+                )
+                .into_any_element(),
+            Self::Scripts { base, sub, sup } => {
+                // Keep the space of the missing script, so the script is aligned to the top or
+                // the bottom of the base.
+                let script = |node: &Option<Box<MathNode>>| match node {
+                    Some(node) => node.render(script_size, cx),
+                    None => div().h(rems(script_size)).into_any_element(),
+                };
+
+                h_flex()
+                    .items_center()
+                    .child(base.render(size, cx))
+                    .child(
+                        v_flex()
+                            .ml(rems(size * 0.05))
+                            .child(script(sup))
+                            .child(script(sub)),
+                    )
+                    .into_any_element()
+            }
+            Self::Break => div().into_any_element(),
+        }
+    }
+}
+
+fn render_row(children: &[MathNode], size: f32, cx: &App) -> AnyElement {
+    h_flex()
+        .items_center()
+        .flex_wrap()
+        .children(children.iter().map(|child| child.render(size, cx)))
+        .into_any_element()
+}
+
+fn is_operator(c: char) -> bool {
+    matches!(
+        c,
+        '+' | '−'
+            | '='
+            | '<'
+            | '>'
+            | '±'
+            | '∓'
+            | '×'
+            | '÷'
+            | '⋅'
+            | '≤'
+            | '≥'
+            | '≠'
+            | '≈'
+            | '≡'
+            | '→'
+            | '⇒'
+            | '⇔'
+            | '∈'
+    )
+}
+
+fn is_greek_lowercase(symbol: &str) -> bool {
+    symbol
+        .chars()
+        .all(|c| ('α'..='ω').contains(&c) || matches!(c, 'ϵ' | 'ϑ' | 'ϕ' | 'ϖ' | 'ϱ' | 'ς'))
+}
+
+/// Returns the symbol of the command, e.g.: `α` for `\alpha`.
+fn symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" => "ϵ",
+        "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "vartheta" => "ϑ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "omicron" => "ο",
+        "pi" => "π",
+        "varpi" => "ϖ",
+        "rho" => "ρ",
+        "varrho" => "ϱ",
+        "sigma" => "σ",
+        "varsigma" => "ς",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" => "ϕ",
+        "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Upsilon" => "Υ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "times" => "×",
+        "cdot" => "⋅",
+        "div" => "÷",
+        "pm" => "±",
+        "mp" => "∓",
+        "ast" => "∗",
+        "circ" => "∘",
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "simeq" => "≃",
+        "cong" => "≅",
+        "propto" => "∝",
+        "ll" => "≪",
+        "gg" => "≫",
+        "infty" => "∞",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "sum" => "∑",
+        "prod" => "∏",
+        "coprod" => "∐",
+        "int" => "∫",
+        "iint" => "∬",
+        "iiint" => "∭",
+        "oint" => "∮",
+        "to" | "rightarrow" => "→",
+        "leftarrow" | "gets" => "←",
+        "leftrightarrow" => "↔",
+        "Rightarrow" => "⇒",
+        "Leftarrow" => "⇐",
+        "Leftrightarrow" | "iff" => "⇔",
+        "implies" => "⟹",
+        "mapsto" => "↦",
+        "in" => "∈",
+        "notin" => "∉",
+        "ni" => "∋",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "supset" => "⊃",
+        "supseteq" => "⊇",
+        "cup" => "∪",
+        "cap" => "∩",
+        "setminus" => "∖",
+        "emptyset" | "varnothing" => "∅",
+        "forall" => "∀",
+        "exists" => "∃",
+        "neg" | "lnot" => "¬",
+        "land" | "wedge" => "∧",
+        "lor" | "vee" => "∨",
+        "oplus" => "⊕",
+        "otimes" => "⊗",
+        "perp" => "⊥",
+        "parallel" => "∥",
+        "angle" => "∠",
+        "prime" => "′",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "vdots" => "⋮",
+        "ddots" => "⋱",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "mid" => "∣",
+        "ell" => "ℓ",
+        "hbar" => "ℏ",
+        "Re" => "ℜ",
+        "Im" => "ℑ",
+        "aleph" => "ℵ",
+        "degree" => "°",
+        _ => return None,
+    })
+}
+
+fn double_struck(c: char) -> char {
+    match c {
+        'C' => 'ℂ',
+        'H' => 'ℍ',
+        'N' => 'ℕ',
+        'P' => 'ℙ',
+        'Q' => 'ℚ',
+        'R' => 'ℝ',
+        'Z' => 'ℤ',
+        c => c,
+    }
+}
+
+fn superscript(c: char) -> Option<char> {
+    Some(match c {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '-' | '−' => '⁻',
+        '=' => '⁼',
+        '(' => '⁽',
+        ')' => '⁾',
+        'a' => 'ᵃ',
+        'b' => 'ᵇ',
+        'c' => 'ᶜ',
+        'd' => 'ᵈ',
+        'e' => 'ᵉ',
+        'f' => 'ᶠ',
+        'g' => 'ᵍ',
+        'h' => 'ʰ',
+        'i' => 'ⁱ',
+        'j' => 'ʲ',
+        'k' => 'ᵏ',
+        'l' => 'ˡ',
+        'm' => 'ᵐ',
+        'n' => 'ⁿ',
+        'o' => 'ᵒ',
+        'p' => 'ᵖ',
+        'r' => 'ʳ',
+        's' => 'ˢ',
+        't' => 'ᵗ',
+        'u' => 'ᵘ',
+        'v' => 'ᵛ',
+        'w' => 'ʷ',
+        'x' => 'ˣ',
+        'y' => 'ʸ',
+        'z' => 'ᶻ',
+        'T' => 'ᵀ',
+        '′' => '′',
+        '∗' => '*',
+        _ => return None,
+    })
+}
+
+fn subscript(c: char) -> Option<char> {
+    Some(match c {
+        '0' => '₀',
+        '1' => '₁',
+        '2' => '₂',
+        '3' => '₃',
+        '4' => '₄',
+        '5' => '₅',
+        '6' => '₆',
+        '7' => '₇',
+        '8' => '₈',
+        '9' => '₉',
+        '+' => '₊',
+        '-' | '−' => '₋',
+        '=' => '₌',
+        '(' => '₍',
+        ')' => '₎',
+        'a' => 'ₐ',
+        'e' => 'ₑ',
+        'h' => 'ₕ',
+        'i' => 'ᵢ',
+        'j' => 'ⱼ',
+        'k' => 'ₖ',
+        'l' => 'ₗ',
+        'm' => 'ₘ',
+        'n' => 'ₙ',
+        'o' => 'ₒ',
+        'p' => 'ₚ',
+        'r' => 'ᵣ',
+        's' => 'ₛ',
+        't' => 'ₜ',
+        'u' => 'ᵤ',
+        'v' => 'ᵥ',
+        'x' => 'ₓ',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, to_unicode, MathNode};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse(r"\frac{1}{x}"),
+            MathNode::Frac(
+                Box::new(MathNode::Text("1".into())),
+                Box::new(MathNode::Ident("x".into()))
+            )
+        );
+        assert_eq!(
+            parse("x_i^2"),
+            MathNode::Scripts {
+                base: Box::new(MathNode::Ident("x".into())),
+                sub: Some(Box::new(MathNode::Ident("i".into()))),
+                sup: Some(Box::new(MathNode::Text("2".into()))),
+            }
+        );
+        assert_eq!(
+            parse(r"a \\ b"),
+            MathNode::Row(vec![
+                MathNode::Ident("a".into()),
+                MathNode::Break,
+                MathNode::Ident("b".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_to_unicode() {
+        assert_eq!(to_unicode("x^2 + y^2 = z^2"), "x² + y² = z²");
+        assert_eq!(to_unicode(r"a_{n+1}"), "aₙ₊₁");
+        assert_eq!(to_unicode(r"\alpha + \beta \leq \pi"), "α + β ≤ π");
+        assert_eq!(to_unicode(r"\frac{a+b}{2}"), "(a + b)/2");
+        assert_eq!(to_unicode(r"\sqrt{x}"), "√x");
+        assert_eq!(to_unicode(r"\sqrt[3]{x+1}"), "³√(x + 1)");
+        assert_eq!(to_unicode(r"e^{i\theta}"), "e^(iθ)");
+        assert_eq!(to_unicode(r"x \in \mathbb{R}"), "x ∈ ℝ");
+        assert_eq!(to_unicode(r"\text{if } x > 0"), "if x > 0");
+        assert_eq!(to_unicode(r"\foo"), r"\foo");
+    }
+}
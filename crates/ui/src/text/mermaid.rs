@@ -0,0 +1,1271 @@
+//! A subset of the Mermaid diagrams in the ` ```mermaid ` code blocks, the flowcharts
+//! (`graph` or `flowchart`) and the sequence diagrams (`sequenceDiagram`).
+//!
+//! The diagrams are laid out once on parsing, and drawn by the elements and the paths. The
+//! unsupported diagrams are rendered as the code blocks.
+use std::sync::Arc;
+
+use gpui::{
+    canvas, div, point, prelude::FluentBuilder as _, px, size, AnyElement, App, Bounds, Hsla,
+    IntoElement, ParentElement as _, PathBuilder, Pixels, Point, SharedString, Size, Styled as _,
+    Window,
+};
+
+use crate::{ActiveTheme as _, StyledExt as _};
+
+/// The font size of the labels, the text width is estimated by it.
+const FONT_SIZE: f32 = 14.;
+const NODE_PADDING: f32 = 12.;
+const NODE_HEIGHT: f32 = 36.;
+const NODE_GAP: f32 = 32.;
+const RANK_GAP: f32 = 48.;
+const ARROW_SIZE: f32 = 8.;
+const MESSAGE_HEIGHT: f32 = 40.;
+const SELF_MESSAGE_WIDTH: f32 = 32.;
+
+/// Returns the estimated width of the `text` in the [`FONT_SIZE`], the wide chars (e.g.: CJK)
+/// are twice as wide as the others.
+fn text_width(text: &str) -> f32 {
+    text.chars()
+        .map(|c| if c.is_ascii() { 0.6 } else { 1.1 })
+        .sum::<f32>()
+        * FONT_SIZE
+}
+
+fn bounds(x: f32, y: f32, width: f32, height: f32) -> Bounds<Pixels> {
+    Bounds::new(point(px(x), px(y)), size(px(width), px(height)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    TopDown,
+    BottomUp,
+    LeftRight,
+    RightLeft,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeShape {
+    Rect,
+    Round,
+    Circle,
+    Diamond,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineStyle {
+    Solid,
+    Dotted,
+    Thick,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FlowNode {
+    label: SharedString,
+    shape: NodeShape,
+    bounds: Bounds<Pixels>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FlowEdge {
+    label: Option<SharedString>,
+    style: LineStyle,
+    arrow: bool,
+    /// The points of the line from the border of the `from` node to the `to` node.
+    start: Point<Pixels>,
+    end: Point<Pixels>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Flowchart {
+    nodes: Vec<FlowNode>,
+    edges: Vec<FlowEdge>,
+    size: Size<Pixels>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Participant {
+    label: SharedString,
+    bounds: Bounds<Pixels>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SequenceItem {
+    Message {
+        from: usize,
+        to: usize,
+        label: SharedString,
+        dashed: bool,
+        arrow: bool,
+        /// The y of the line.
+        y: Pixels,
+    },
+    Note {
+        label: SharedString,
+        bounds: Bounds<Pixels>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Sequence {
+    participants: Vec<Participant>,
+    items: Vec<SequenceItem>,
+    size: Size<Pixels>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Diagram {
+    Flowchart(Flowchart),
+    Sequence(Sequence),
+}
+
+/// A block of the Mermaid diagram, the ` ```mermaid ` code block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MermaidBlock {
+    pub(super) source: SharedString,
+    diagram: Arc<Diagram>,
+}
+
+impl MermaidBlock {
+    /// Parse and lay out the diagram, `None` if the diagram is not supported.
+    pub(super) fn parse(source: SharedString) -> Option<Self> {
+        let diagram = parse(&source)?;
+        Some(Self {
+            source,
+            diagram: Arc::new(diagram),
+        })
+    }
+
+    /// Returns the id of the block, used as the element id.
+    pub(super) fn id(&self) -> usize {
+        Arc::as_ptr(&self.diagram) as usize
+    }
+
+    pub(super) fn render(&self, cx: &App) -> AnyElement {
+        match self.diagram.as_ref() {
+            Diagram::Flowchart(chart) => render_flowchart(chart, cx),
+            Diagram::Sequence(sequence) => render_sequence(sequence, cx),
+        }
+    }
+}
+
+fn parse(source: &str) -> Option<Diagram> {
+    let mut lines = source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("%%"));
+    let header = lines.next()?;
+    let mut words = header.split_whitespace();
+
+    match words.next()? {
+        "graph" | "flowchart" => {
+            let direction = match words.next().unwrap_or("TD").trim_end_matches(';') {
+                "TD" | "TB" => Direction::TopDown,
+                "BT" => Direction::BottomUp,
+                "LR" => Direction::LeftRight,
+                "RL" => Direction::RightLeft,
+                _ => return None,
+            };
+            let mut parser = FlowchartParser::default();
+            for statement in lines.flat_map(|line| line.split(';')) {
+                parser.parse_statement(statement.trim());
+            }
+            if parser.nodes.is_empty() {
+                return None;
+            }
+            Some(Diagram::Flowchart(parser.layout(direction)))
+        }
+        "sequenceDiagram" => {
+            let mut parser = SequenceParser::default();
+            for line in lines {
+                parser.parse_line(line);
+            }
+            if parser.participants.is_empty() {
+                return None;
+            }
+            Some(Diagram::Sequence(parser.layout()))
+        }
+        _ => None,
+    }
+}
+
+/// Strip the quotes of the label, e.g.: `"A label"`.
+fn unquote(text: &str) -> &str {
+    let text = text.trim();
+    text.strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .unwrap_or(text)
+}
+
+#[derive(Default)]
+struct FlowchartParser {
+    /// The nodes of (id, label, shape).
+    nodes: Vec<(String, SharedString, NodeShape)>,
+    /// The edges of (from, to, label, style, arrow).
+    edges: Vec<(usize, usize, Option<SharedString>, LineStyle, bool)>,
+}
+
+/// The links of the flowchart, the longest first to match, with the style and the arrow.
+const LINKS: [(&str, LineStyle, bool); 8] = [
+    ("-.->", LineStyle::Dotted, true),
+    ("-->", LineStyle::Solid, true),
+    ("==>", LineStyle::Thick, true),
+    ("---", LineStyle::Solid, false),
+    ("===", LineStyle::Thick, false),
+    ("-.-", LineStyle::Dotted, false),
+    // The start of the links with the text, e.g.: `-- text -->`.
+    ("--", LineStyle::Solid, true),
+    ("==", LineStyle::Thick, true),
+];
+
+/// The shapes of the nodes by the open and close brackets, the longest first to match.
+const SHAPES: [(&str, &str, NodeShape); 9] = [
+    ("((", "))", NodeShape::Circle),
+    ("([", "])", NodeShape::Round),
+    ("[[", "]]", NodeShape::Rect),
+    ("[(", ")]", NodeShape::Rect),
+    ("{{", "}}", NodeShape::Diamond),
+    ("[", "]", NodeShape::Rect),
+    ("(", ")", NodeShape::Round),
+    ("{", "}", NodeShape::Diamond),
+    (">", "]", NodeShape::Rect),
+];
+
+impl FlowchartParser {
+    fn parse_statement(&mut self, statement: &str) {
+        const SKIPPED: [&str; 8] = [
+            "subgraph",
+            "end",
+            "direction",
+            "classDef",
+            "class",
+            "style",
+            "linkStyle",
+            "click",
+        ];
+        let keyword = statement.split_whitespace().next().unwrap_or_default();
+        if statement.starts_with("%%") || SKIPPED.contains(&keyword) {
+            return;
+        }
+
+        // The nodes before the link in parsing, e.g.: `A & B -->`.
+        let mut pending: Option<(Vec<usize>, Link)> = None;
+        let mut rest = statement;
+        loop {
+            let (nodes, after) = self.parse_nodes(rest);
+            if nodes.is_empty() {
+                return;
+            }
+            if let Some((from_nodes, (label, style, arrow))) = pending.take() {
+                for from in &from_nodes {
+                    for to in &nodes {
+                        self.edges.push((*from, *to, label.clone(), style, arrow));
+                    }
+                }
+            }
+
+            let Some((link, after)) = parse_link(after) else {
+                return;
+            };
+            pending = Some((nodes, link));
+            rest = after;
+        }
+    }
+
+    /// Parse the nodes separated by `&`, returns the node indices and the rest.
+    fn parse_nodes<'a>(&mut self, text: &'a str) -> (Vec<usize>, &'a str) {
+        let mut nodes = vec![];
+        let mut rest = text.trim_start();
+        loop {
+            let Some((node, after)) = self.parse_node(rest) else {
+                break;
+            };
+            nodes.push(node);
+            rest = after.trim_start();
+            match rest.strip_prefix('&') {
+                Some(after) => rest = after.trim_start(),
+                None => break,
+            }
+        }
+        (nodes, rest)
+    }
+
+    /// Parse a node, e.g.: `A`, `A[Label]` or `A{Label}:::class`.
+    fn parse_node<'a>(&mut self, text: &'a str) -> Option<(usize, &'a str)> {
+        let id_len = text
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(text.len());
+        if id_len == 0 {
+            return None;
+        }
+        let id = &text[..id_len];
+        let mut rest = &text[id_len..];
+
+        let mut shape = None;
+        for (open, close, node_shape) in SHAPES {
+            let Some(after) = rest.strip_prefix(open) else {
+                continue;
+            };
+            let Some(end) = after.find(close) else {
+                continue;
+            };
+            shape = Some((unquote(&after[..end]).to_string(), node_shape));
+            rest = &after[end + close.len()..];
+            break;
+        }
+        // The class of the node, e.g.: `:::important`.
+        if let Some(after) = rest.strip_prefix(":::") {
+            let len = after
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                .unwrap_or(after.len());
+            rest = &after[len..];
+        }
+
+        let ix = match self.nodes.iter().position(|(node_id, ..)| node_id == id) {
+            Some(ix) => ix,
+            None => {
+                self.nodes
+                    .push((id.to_string(), id.to_string().into(), NodeShape::Rect));
+                self.nodes.len() - 1
+            }
+        };
+        if let Some((label, shape)) = shape {
+            self.nodes[ix].1 = label.into();
+            self.nodes[ix].2 = shape;
+        }
+        Some((ix, rest))
+    }
+}
+
+/// The link in parsing, the label, the style and the arrow.
+type Link = (Option<SharedString>, LineStyle, bool);
+
+/// Parse a link, e.g.: `-->`, `-->|text|` or `-- text -->`, returns the link and the rest.
+fn parse_link(text: &str) -> Option<(Link, &str)> {
+    let text = text.trim_start();
+    let (token, style, mut arrow) = LINKS
+        .iter()
+        .find(|(token, ..)| text.starts_with(token))
+        .copied()?;
+    let mut rest = &text[token.len()..];
+    // The longer links, e.g.: `---->`.
+    let extra = rest
+        .find(|c: char| !matches!(c, '-' | '=' | '.'))
+        .unwrap_or(rest.len());
+    rest = &rest[extra..];
+    if let Some(after) = rest.strip_prefix('>') {
+        arrow = true;
+        rest = after;
+    }
+
+    let mut label = None;
+    if matches!(token, "--" | "==") && extra == 0 && !rest.starts_with('>') {
+        // The text between the link, e.g.: `-- text -->`.
+        let (end, link) = ["-->", "---", "==>", "==="]
+            .iter()
+            .filter_map(|link| rest.find(link).map(|end| (end, link)))
+            .min()?;
+        label = Some(unquote(&rest[..end]).to_string().into());
+        arrow = link.ends_with('>');
+        rest = &rest[end + link.len()..];
+    }
+    if let Some(after) = rest.trim_start().strip_prefix('|') {
+        let end = after.find('|')?;
+        label = Some(unquote(&after[..end]).to_string().into());
+        rest = &after[end + 1..];
+    }
+
+    Some(((label, style, arrow), rest))
+}
+
+impl FlowchartParser {
+    /// Returns the rank of each node by the longest path from the sources, the edges of the
+    /// cycles are ignored.
+    fn ranks(&self) -> Vec<usize> {
+        fn visit(
+            node: usize,
+            edges: &[(usize, usize, Option<SharedString>, LineStyle, bool)],
+            state: &mut [u8],
+            order: &mut Vec<usize>,
+        ) {
+            // 0: not visited, 1: visiting, 2: visited.
+            state[node] = 1;
+            for (from, to, ..) in edges {
+                if *from == node && state[*to] == 0 {
+                    visit(*to, edges, state, order);
+                }
+            }
+            state[node] = 2;
+            order.push(node);
+        }
+
+        let mut state = vec![0; self.nodes.len()];
+        let mut order = vec![];
+        for node in 0..self.nodes.len() {
+            if state[node] == 0 {
+                visit(node, &self.edges, &mut state, &mut order);
+            }
+        }
+
+        // The position in the topological order, the edges backward are in the cycles.
+        let mut position = vec![0; self.nodes.len()];
+        for (ix, node) in order.iter().rev().enumerate() {
+            position[*node] = ix;
+        }
+
+        let mut ranks = vec![0; self.nodes.len()];
+        for node in order.iter().rev() {
+            for (from, to, ..) in &self.edges {
+                if from == node && position[*to] > position[*from] {
+                    ranks[*to] = ranks[*to].max(ranks[*from] + 1);
+                }
+            }
+        }
+        ranks
+    }
+
+    fn layout(self, direction: Direction) -> Flowchart {
+        let ranks = self.ranks();
+        let ranks_count = ranks.iter().max().map_or(0, |rank| rank + 1);
+
+        // The nodes of each rank, ordered by the average position of the previous rank.
+        let mut layers: Vec<Vec<usize>> = vec![vec![]; ranks_count];
+        for (node, rank) in ranks.iter().enumerate() {
+            layers[*rank].push(node);
+        }
+        for rank in 1..ranks_count {
+            let (prev, layer) = layers.split_at_mut(rank);
+            let prev = &prev[rank - 1];
+            let order = |node: usize| {
+                let positions = self
+                    .edges
+                    .iter()
+                    .filter(|(_, to, ..)| *to == node)
+                    .filter_map(|(from, ..)| prev.iter().position(|n| n == from))
+                    .collect::<Vec<_>>();
+                if positions.is_empty() {
+                    f32::MAX
+                } else {
+                    positions.iter().sum::<usize>() as f32 / positions.len() as f32
+                }
+            };
+            layer[0].sort_by(|a, b| order(*a).total_cmp(&order(*b)));
+        }
+
+        let node_size = |(_, label, shape): &(String, SharedString, NodeShape)| {
+            let width = text_width(label) + NODE_PADDING * 2.;
+            match shape {
+                NodeShape::Circle => {
+                    let size = width.max(NODE_HEIGHT);
+                    (size, size)
+                }
+                NodeShape::Diamond => (width * 1.5, NODE_HEIGHT * 1.5),
+                _ => (width, NODE_HEIGHT),
+            }
+        };
+        let sizes = self.nodes.iter().map(node_size).collect::<Vec<_>>();
+        let horizontal = matches!(direction, Direction::LeftRight | Direction::RightLeft);
+
+        // The layers are laid out in the main axis, and the nodes of a layer in the cross axis.
+        let main = |(width, height): (f32, f32)| if horizontal { width } else { height };
+        let cross = |(width, height): (f32, f32)| if horizontal { height } else { width };
+        let layer_cross = layers
+            .iter()
+            .map(|layer| {
+                layer.iter().map(|node| cross(sizes[*node])).sum::<f32>()
+                    + NODE_GAP * layer.len().saturating_sub(1) as f32
+            })
+            .collect::<Vec<_>>();
+        let total_cross = layer_cross.iter().copied().fold(0., f32::max);
+
+        let mut positions = vec![(0., 0.); self.nodes.len()];
+        let mut main_offset = 0.;
+        for (layer, layer_cross) in layers.iter().zip(&layer_cross) {
+            let layer_main = layer
+                .iter()
+                .map(|node| main(sizes[*node]))
+                .fold(0., f32::max);
+            let mut cross_offset = (total_cross - layer_cross) / 2.;
+            for node in layer {
+                let size = sizes[*node];
+                // Center the node in the layer.
+                let main_pos = main_offset + (layer_main - main(size)) / 2.;
+                positions[*node] = (main_pos, cross_offset);
+                cross_offset += cross(size) + NODE_GAP;
+            }
+            main_offset += layer_main + RANK_GAP;
+        }
+        let total_main = (main_offset - RANK_GAP).max(0.);
+
+        let nodes = self
+            .nodes
+            .into_iter()
+            .enumerate()
+            .map(|(ix, (_, label, shape))| {
+                let (width, height) = sizes[ix];
+                let (main_pos, cross_pos) = positions[ix];
+                let (x, y) = match direction {
+                    Direction::TopDown => (cross_pos, main_pos),
+                    Direction::BottomUp => (cross_pos, total_main - main_pos - height),
+                    Direction::LeftRight => (main_pos, cross_pos),
+                    Direction::RightLeft => (total_main - main_pos - width, cross_pos),
+                };
+                FlowNode {
+                    label,
+                    shape,
+                    bounds: bounds(x, y, width, height),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let edges = self
+            .edges
+            .into_iter()
+            .map(|(from, to, label, style, arrow)| {
+                let from_node = &nodes[from];
+                let to_node = &nodes[to];
+                FlowEdge {
+                    label,
+                    style,
+                    arrow,
+                    start: border_point(from_node, to_node.bounds.center()),
+                    end: border_point(to_node, from_node.bounds.center()),
+                }
+            })
+            .collect();
+
+        let (width, height) = if horizontal {
+            (total_main, total_cross)
+        } else {
+            (total_cross, total_main)
+        };
+        Flowchart {
+            nodes,
+            edges,
+            size: size(px(width), px(height)),
+        }
+    }
+}
+
+/// Returns the point on the border of the `node` in the direction to the `target`.
+fn border_point(node: &FlowNode, target: Point<Pixels>) -> Point<Pixels> {
+    let center = node.bounds.center();
+    let dx = (target.x - center.x).0;
+    let dy = (target.y - center.y).0;
+    if dx == 0. && dy == 0. {
+        return center;
+    }
+
+    let half_width = node.bounds.size.width.0 / 2.;
+    let half_height = node.bounds.size.height.0 / 2.;
+    let scale = match node.shape {
+        NodeShape::Diamond => 1. / (dx.abs() / half_width + dy.abs() / half_height),
+        NodeShape::Circle => half_width / (dx * dx + dy * dy).sqrt(),
+        _ => (half_width / dx.abs()).min(half_height / dy.abs()),
+    };
+    point(center.x + px(dx * scale), center.y + px(dy * scale))
+}
+
+#[derive(Default)]
+struct SequenceParser {
+    /// The participants of (id, label).
+    participants: Vec<(String, SharedString)>,
+    /// The messages of (from, to, label, dashed, arrow), and the notes of (from, to, label)
+    /// with the `None` style.
+    items: Vec<(usize, usize, SharedString, Option<(bool, bool)>)>,
+}
+
+impl SequenceParser {
+    fn participant(&mut self, id: &str) -> usize {
+        let id = id.trim();
+        match self.participants.iter().position(|(p, _)| p == id) {
+            Some(ix) => ix,
+            None => {
+                self.participants
+                    .push((id.to_string(), id.to_string().into()));
+                self.participants.len() - 1
+            }
+        }
+    }
+
+    fn parse_line(&mut self, line: &str) {
+        const SKIPPED: [&str; 17] = [
+            "autonumber",
+            "activate",
+            "deactivate",
+            "loop",
+            "alt",
+            "else",
+            "opt",
+            "par",
+            "and",
+            "critical",
+            "break",
+            "rect",
+            "box",
+            "end",
+            "title",
+            "create",
+            "destroy",
+        ];
+        let keyword = line.split_whitespace().next().unwrap_or_default();
+        if SKIPPED.contains(&keyword) {
+            return;
+        }
+
+        if let Some(rest) = line
+            .strip_prefix("participant ")
+            .or_else(|| line.strip_prefix("actor "))
+        {
+            let (id, label) = match rest.split_once(" as ") {
+                Some((id, label)) => (id, unquote(label)),
+                None => (rest, unquote(rest)),
+            };
+            let ix = self.participant(id);
+            self.participants[ix].1 = label.to_string().into();
+            return;
+        }
+
+        if let Some(rest) = line
+            .strip_prefix("Note ")
+            .or_else(|| line.strip_prefix("note "))
+        {
+            let Some((target, label)) = rest.split_once(':') else {
+                return;
+            };
+            let target = target.trim();
+            let ids = target
+                .strip_prefix("over ")
+                .or_else(|| target.strip_prefix("left of "))
+                .or_else(|| target.strip_prefix("right of "))
+                .unwrap_or(target);
+            let (from, to) = match ids.split_once(',') {
+                Some((from, to)) => (self.participant(from), self.participant(to)),
+                None => {
+                    let ix = self.participant(ids);
+                    (ix, ix)
+                }
+            };
+            self.items
+                .push((from, to, label.trim().to_string().into(), None));
+            return;
+        }
+
+        // The message, e.g.: `Alice->>Bob: Hello`.
+        let Some(arrow_start) = line.find('-') else {
+            return;
+        };
+        let (from, rest) = line.split_at(arrow_start);
+        let arrow_len = rest
+            .find(|c: char| !matches!(c, '-' | '>' | ')'))
+            .unwrap_or(rest.len());
+        let (arrow, rest) = rest.split_at(arrow_len);
+        let (to, label) = rest.split_once(':').unwrap_or((rest, ""));
+        // The activation of the target, e.g.: `->>+Bob`.
+        let to = to.trim().trim_start_matches(['+', '-']);
+        if from.trim().is_empty() || to.is_empty() {
+            return;
+        }
+
+        let dashed = arrow.starts_with("--");
+        let has_arrow = arrow.contains(['>', ')']);
+        let from = self.participant(from);
+        let to = self.participant(to);
+        self.items.push((
+            from,
+            to,
+            label.trim().to_string().into(),
+            Some((dashed, has_arrow)),
+        ));
+    }
+
+    fn layout(self) -> Sequence {
+        let widths = self
+            .participants
+            .iter()
+            .map(|(_, label)| text_width(label) + NODE_PADDING * 2.)
+            .collect::<Vec<_>>();
+
+        // The distance of the centers of the adjacent participants, enough for the labels of
+        // the messages between them.
+        let mut gaps = widths
+            .windows(2)
+            .map(|pair| (pair[0] + pair[1]) / 2. + NODE_GAP)
+            .collect::<Vec<_>>();
+        for (from, to, label, style) in &self.items {
+            let width = text_width(label) + NODE_PADDING * 2.;
+            if style.is_some() && from.abs_diff(*to) == 1 {
+                let gap = &mut gaps[(*from).min(*to)];
+                *gap = gap.max(width);
+            }
+        }
+
+        let mut centers = vec![widths.first().copied().unwrap_or_default() / 2.];
+        for gap in &gaps {
+            centers.push(centers.last().copied().unwrap_or_default() + gap);
+        }
+
+        let mut y = NODE_HEIGHT + NODE_GAP / 2.;
+        let mut items = vec![];
+        let mut right = centers.last().copied().unwrap_or_default()
+            + widths.last().copied().unwrap_or_default() / 2.;
+        for (from, to, label, style) in self.items {
+            match style {
+                Some((dashed, arrow)) => {
+                    let height = if from == to {
+                        right = right.max(centers[from] + SELF_MESSAGE_WIDTH + text_width(&label));
+                        MESSAGE_HEIGHT + NODE_GAP / 2.
+                    } else {
+                        MESSAGE_HEIGHT
+                    };
+                    items.push(SequenceItem::Message {
+                        from,
+                        to,
+                        label,
+                        dashed,
+                        arrow,
+                        y: px(y + MESSAGE_HEIGHT * 0.75),
+                    });
+                    y += height;
+                }
+                None => {
+                    let (left, right_center) = if from <= to {
+                        (centers[from], centers[to])
+                    } else {
+                        (centers[to], centers[from])
+                    };
+                    let width = (right_center - left + NODE_PADDING * 2.)
+                        .max(text_width(&label) + NODE_PADDING * 2.);
+                    let x = (left + right_center - width) / 2.;
+                    items.push(SequenceItem::Note {
+                        label,
+                        bounds: bounds(x, y + NODE_GAP / 4., width, NODE_HEIGHT),
+                    });
+                    y += NODE_HEIGHT + NODE_GAP / 2.;
+                }
+            }
+        }
+
+        // Keep the notes over the first participant in the view.
+        let left = items
+            .iter()
+            .filter_map(|item| match item {
+                SequenceItem::Note { bounds, .. } => Some(bounds.origin.x.0),
+                _ => None,
+            })
+            .fold(0., f32::min);
+        let offset = -left;
+        for item in items.iter_mut() {
+            if let SequenceItem::Note { bounds, .. } = item {
+                bounds.origin.x += px(offset);
+            }
+        }
+
+        let participants = self
+            .participants
+            .into_iter()
+            .zip(widths)
+            .zip(&centers)
+            .map(|(((_, label), width), center)| Participant {
+                label,
+                bounds: bounds(center + offset - width / 2., 0., width, NODE_HEIGHT),
+            })
+            .collect();
+        let right = items
+            .iter()
+            .filter_map(|item| match item {
+                SequenceItem::Note { bounds, .. } => Some(bounds.right().0),
+                _ => None,
+            })
+            .fold(right + offset, f32::max);
+
+        Sequence {
+            participants,
+            items,
+            size: size(px(right), px(y + NODE_GAP / 2.)),
+        }
+    }
+}
+
+/// Paint the line from `start` to `end`, with the arrow head at the `end` if `arrow`.
+fn paint_line(
+    start: Point<Pixels>,
+    end: Point<Pixels>,
+    style: LineStyle,
+    arrow: bool,
+    color: Hsla,
+    window: &mut Window,
+) {
+    let width = match style {
+        LineStyle::Thick => px(2.5),
+        _ => px(1.5),
+    };
+    let mut builder = PathBuilder::stroke(width);
+    if style == LineStyle::Dotted {
+        builder = builder.dash_array(&[px(4.), px(3.)]);
+    }
+    builder.move_to(start);
+    builder.line_to(end);
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, color);
+    }
+
+    if arrow {
+        paint_arrow_head(start, end, color, window);
+    }
+}
+
+/// Paint the arrow head at the `end` in the direction from the `start`.
+fn paint_arrow_head(start: Point<Pixels>, end: Point<Pixels>, color: Hsla, window: &mut Window) {
+    let dx = (end.x - start.x).0;
+    let dy = (end.y - start.y).0;
+    let len = (dx * dx + dy * dy).sqrt();
+    if len == 0. {
+        return;
+    }
+
+    let (ux, uy) = (dx / len, dy / len);
+    let base = point(end.x - px(ux * ARROW_SIZE), end.y - px(uy * ARROW_SIZE));
+    let side = point(px(-uy * ARROW_SIZE / 2.), px(ux * ARROW_SIZE / 2.));
+
+    let mut builder = PathBuilder::fill();
+    builder.move_to(end);
+    builder.line_to(base + side);
+    builder.line_to(base - side);
+    builder.close();
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, color);
+    }
+}
+
+/// The label at the `center`, with the background to cover the lines.
+fn render_label(label: SharedString, center: Point<Pixels>, cx: &App) -> impl IntoElement {
+    let width = px(text_width(&label) + 8.);
+    div()
+        .absolute()
+        .left(center.x - width / 2.)
+        .top(center.y - px(10.))
+        .w(width)
+        .h(px(20.))
+        .flex()
+        .items_center()
+        .justify_center()
+        .whitespace_nowrap()
+        .text_xs()
+        .bg(cx.theme().background)
+        .text_color(cx.theme().muted_foreground)
+        .child(label)
+}
+
+fn render_flowchart(chart: &Flowchart, cx: &App) -> AnyElement {
+    let line_color = cx.theme().muted_foreground;
+    let node_bg = cx.theme().secondary;
+    let node_border = cx.theme().border;
+    let lines = chart
+        .edges
+        .iter()
+        .map(|edge| (edge.start, edge.end, edge.style, edge.arrow))
+        .collect::<Vec<_>>();
+    let diamonds = chart
+        .nodes
+        .iter()
+        .filter(|node| node.shape == NodeShape::Diamond)
+        .map(|node| node.bounds)
+        .collect::<Vec<_>>();
+
+    div()
+        .relative()
+        .flex_shrink_0()
+        .w(chart.size.width)
+        .h(chart.size.height)
+        .text_sm()
+        .child(
+            canvas(
+                |_, _, _| {},
+                move |bounds, _, window, _| {
+                    let origin = bounds.origin;
+                    for node in &diamonds {
+                        let center = node.center() + origin;
+                        let half_width = node.size.width / 2.;
+                        let half_height = node.size.height / 2.;
+                        let corners = [
+                            point(center.x, center.y - half_height),
+                            point(center.x + half_width, center.y),
+                            point(center.x, center.y + half_height),
+                            point(center.x - half_width, center.y),
+                        ];
+
+                        let mut builder = PathBuilder::fill();
+                        builder.move_to(corners[0]);
+                        for corner in &corners[1..] {
+                            builder.line_to(*corner);
+                        }
+                        builder.close();
+                        if let Ok(path) = builder.build() {
+                            window.paint_path(path, node_bg);
+                        }
+
+                        let mut builder = PathBuilder::stroke(px(1.));
+                        builder.move_to(corners[0]);
+                        for corner in corners[1..].iter().chain(&corners[..1]) {
+                            builder.line_to(*corner);
+                        }
+                        if let Ok(path) = builder.build() {
+                            window.paint_path(path, node_border);
+                        }
+                    }
+
+                    for (start, end, style, arrow) in &lines {
+                        paint_line(
+                            *start + origin,
+                            *end + origin,
+                            *style,
+                            *arrow,
+                            line_color,
+                            window,
+                        );
+                    }
+                },
+            )
+            .absolute()
+            .size_full(),
+        )
+        .children(chart.nodes.iter().map(|node| {
+            div()
+                .absolute()
+                .left(node.bounds.origin.x)
+                .top(node.bounds.origin.y)
+                .w(node.bounds.size.width)
+                .h(node.bounds.size.height)
+                .flex()
+                .items_center()
+                .justify_center()
+                .whitespace_nowrap()
+                .map(|this| match node.shape {
+                    NodeShape::Diamond => this,
+                    shape => {
+                        this.bg(node_bg).border_1().border_color(node_border).map(
+                            |this| match shape {
+                                NodeShape::Rect => this.rounded(px(4.)),
+                                _ => this.rounded_full(),
+                            },
+                        )
+                    }
+                })
+                .child(node.label.clone())
+        }))
+        .children(chart.edges.iter().filter_map(|edge| {
+            let label = edge.label.clone()?;
+            let center = point(
+                (edge.start.x + edge.end.x) / 2.,
+                (edge.start.y + edge.end.y) / 2.,
+            );
+            Some(render_label(label, center, cx))
+        }))
+        .into_any_element()
+}
+
+fn render_sequence(sequence: &Sequence, cx: &App) -> AnyElement {
+    let line_color = cx.theme().muted_foreground;
+    let lifeline_color = cx.theme().border;
+    let height = sequence.size.height;
+    let centers = sequence
+        .participants
+        .iter()
+        .map(|participant| participant.bounds.center().x)
+        .collect::<Vec<_>>();
+    let messages = sequence
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            SequenceItem::Message {
+                from,
+                to,
+                dashed,
+                arrow,
+                y,
+                ..
+            } => Some((centers[*from], centers[*to], *y, *dashed, *arrow)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut labels = vec![];
+    for item in &sequence.items {
+        if let SequenceItem::Message {
+            from, to, label, y, ..
+        } = item
+        {
+            if label.is_empty() {
+                continue;
+            }
+            let (x, y) = if from == to {
+                (
+                    centers[*from] + px(SELF_MESSAGE_WIDTH + 4. + text_width(label) / 2.),
+                    *y,
+                )
+            } else {
+                ((centers[*from] + centers[*to]) / 2., *y - px(12.))
+            };
+            labels.push(render_label(label.clone(), point(x, y), cx));
+        }
+    }
+
+    div()
+        .relative()
+        .flex_shrink_0()
+        .w(sequence.size.width)
+        .h(height)
+        .text_sm()
+        .child(
+            canvas(
+                |_, _, _| {},
+                move |bounds, _, window, _| {
+                    let origin = bounds.origin;
+                    for x in &centers {
+                        paint_line(
+                            origin + point(*x, px(NODE_HEIGHT)),
+                            origin + point(*x, height),
+                            LineStyle::Solid,
+                            false,
+                            lifeline_color,
+                            window,
+                        );
+                    }
+
+                    for (from, to, y, dashed, arrow) in &messages {
+                        let style = if *dashed {
+                            LineStyle::Dotted
+                        } else {
+                            LineStyle::Solid
+                        };
+                        let start = origin + point(*from, *y);
+                        if from == to {
+                            // The loop to the participant itself.
+                            let right = *from + px(SELF_MESSAGE_WIDTH);
+                            let bottom = *y + px(NODE_GAP / 2.);
+                            paint_line(
+                                start,
+                                origin + point(right, *y),
+                                style,
+                                false,
+                                line_color,
+                                window,
+                            );
+                            paint_line(
+                                origin + point(right, *y),
+                                origin + point(right, bottom),
+                                style,
+                                false,
+                                line_color,
+                                window,
+                            );
+                            paint_line(
+                                origin + point(right, bottom),
+                                origin + point(*from, bottom),
+                                style,
+                                *arrow,
+                                line_color,
+                                window,
+                            );
+                        } else {
+                            paint_line(
+                                start,
+                                origin + point(*to, *y),
+                                style,
+                                *arrow,
+                                line_color,
+                                window,
+                            );
+                        }
+                    }
+                },
+            )
+            .absolute()
+            .size_full(),
+        )
+        .children(sequence.participants.iter().map(|participant| {
+            div()
+                .absolute()
+                .left(participant.bounds.origin.x)
+                .top(participant.bounds.origin.y)
+                .w(participant.bounds.size.width)
+                .h(participant.bounds.size.height)
+                .flex()
+                .items_center()
+                .justify_center()
+                .whitespace_nowrap()
+                .rounded(px(4.))
+                .border_1()
+                .border_color(cx.theme().border)
+                .bg(cx.theme().secondary)
+                .font_semibold()
+                .child(participant.label.clone())
+        }))
+        .children(sequence.items.iter().filter_map(|item| {
+            match item {
+                SequenceItem::Note { label, bounds } => Some(
+                    div()
+                        .absolute()
+                        .left(bounds.origin.x)
+                        .top(bounds.origin.y)
+                        .w(bounds.size.width)
+                        .h(bounds.size.height)
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .whitespace_nowrap()
+                        .rounded(px(4.))
+                        .border_1()
+                        .border_color(cx.theme().warning)
+                        .bg(cx.theme().warning.opacity(0.15))
+                        .child(label.clone()),
+                ),
+                _ => None,
+            }
+        }))
+        .children(labels)
+        .into_any_element()
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::Pixels;
+
+    use super::{
+        parse, parse_link, Diagram, Direction, FlowchartParser, LineStyle, NodeShape,
+        SequenceParser,
+    };
+
+    #[test]
+    fn test_parse_link() {
+        let link = |text| {
+            parse_link(text).map(|((label, style, arrow), rest)| {
+                (label.map(|label| label.to_string()), style, arrow, rest)
+            })
+        };
+
+        assert_eq!(link("--> B"), Some((None, LineStyle::Solid, true, " B")));
+        assert_eq!(link("--- B"), Some((None, LineStyle::Solid, false, " B")));
+        assert_eq!(link("-.-> B"), Some((None, LineStyle::Dotted, true, " B")));
+        assert_eq!(link("==> B"), Some((None, LineStyle::Thick, true, " B")));
+        assert_eq!(link("----> B"), Some((None, LineStyle::Solid, true, " B")));
+        assert_eq!(
+            link("-->|Yes| B"),
+            Some((Some("Yes".into()), LineStyle::Solid, true, " B"))
+        );
+        assert_eq!(
+            link("-- No --> B"),
+            Some((Some("No".into()), LineStyle::Solid, true, " B"))
+        );
+        assert_eq!(link("B"), None);
+    }
+
+    #[test]
+    fn test_parse_flowchart() {
+        let mut parser = FlowchartParser::default();
+        for statement in [
+            "A[Start] --> B{Is it?}",
+            "B -->|Yes| C(OK) & D((End))",
+            "C --> A",
+            "style A fill:#f9f",
+        ] {
+            parser.parse_statement(statement);
+        }
+
+        let nodes = parser
+            .nodes
+            .iter()
+            .map(|(id, label, shape)| (id.as_str(), label.as_ref(), *shape))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            nodes,
+            vec![
+                ("A", "Start", NodeShape::Rect),
+                ("B", "Is it?", NodeShape::Diamond),
+                ("C", "OK", NodeShape::Round),
+                ("D", "End", NodeShape::Circle),
+            ]
+        );
+        let edges = parser
+            .edges
+            .iter()
+            .map(|(from, to, label, ..)| (*from, *to, label.as_ref().map(|l| l.to_string())))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            edges,
+            vec![
+                (0, 1, None),
+                (1, 2, Some("Yes".into())),
+                (1, 3, Some("Yes".into())),
+                (2, 0, None)
+            ]
+        );
+
+        // The ranks are from left to right, the cycle is ignored.
+        assert_eq!(parser.ranks(), vec![0, 1, 2, 2]);
+        let chart = parser.layout(Direction::LeftRight);
+        let center = |ix: usize| chart.nodes[ix].bounds.center();
+        let approx_eq = |a: Pixels, b: Pixels| (a - b).0.abs() < 0.01;
+        assert!(center(0).x < center(1).x);
+        assert!(center(1).x < center(2).x);
+        assert!(approx_eq(center(2).x, center(3).x));
+        assert!(approx_eq(center(0).y, center(1).y));
+
+        // The lines start and end at the borders of the nodes.
+        let edge = &chart.edges[0];
+        assert!(approx_eq(edge.start.x, chart.nodes[0].bounds.right()));
+        assert!(approx_eq(edge.end.x, chart.nodes[1].bounds.left()));
+    }
+
+    #[test]
+    fn test_parse_sequence() {
+        let mut parser = SequenceParser::default();
+        for line in [
+            "participant A as Alice",
+            "A->>B: Hello",
+            "loop Every-minute",
+            "B-->>+A: Hi",
+            "Note over A,B: Done",
+            "B->>B: Think",
+        ] {
+            parser.parse_line(line);
+        }
+
+        let participants = parser
+            .participants
+            .iter()
+            .map(|(id, label)| (id.as_str(), label.as_ref()))
+            .collect::<Vec<_>>();
+        assert_eq!(participants, vec![("A", "Alice"), ("B", "B")]);
+        let items = parser
+            .items
+            .iter()
+            .map(|(from, to, label, style)| (*from, *to, label.as_ref(), *style))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            vec![
+                (0, 1, "Hello", Some((false, true))),
+                (1, 0, "Hi", Some((true, true))),
+                (0, 1, "Done", None),
+                (1, 1, "Think", Some((false, true))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(matches!(
+            parse("graph TD\n  A --> B; %% comment"),
+            Some(Diagram::Flowchart(_))
+        ));
+        assert!(matches!(
+            parse("sequenceDiagram\n  A->>B: Hi"),
+            Some(Diagram::Sequence(_))
+        ));
+        assert_eq!(parse("classDiagram\n  A <|-- B"), None);
+        assert_eq!(parse("graph XY\n  A --> B"), None);
+        assert_eq!(parse(""), None);
+    }
+}
@@ -1,7 +1,30 @@
+mod cache;
+pub(crate) mod cjk;
+mod clipboard;
+mod document;
 mod element;
+mod emoji;
+mod footnote;
 mod html;
+pub(crate) mod hyphenation;
+mod intern;
+mod lightbox;
+mod link;
 mod markdown;
+mod markdown_view;
+mod math;
+mod mermaid;
+mod selection;
+mod task_list;
 mod text_view;
-mod utils;
+pub(crate) mod utils;
+mod watch;
 
+pub(crate) use clipboard::markdown_clipboard_item;
+pub use clipboard::{
+    read_html_from_clipboard, write_html_to_clipboard, write_markdown_to_clipboard,
+};
+pub use hyphenation::register_hyphenation_patterns;
+pub use markdown_view::MarkdownView;
+pub(crate) use selection::init;
 pub use text_view::*;
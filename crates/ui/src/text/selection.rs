@@ -0,0 +1,385 @@
+use std::{cell::RefCell, ops::Range, rc::Rc, sync::Arc};
+
+use gpui::{
+    actions, div, px, App, Bounds, ClipboardItem, DispatchPhase, Div, FocusHandle, HighlightStyle,
+    Hitbox, InteractiveElement as _, KeyBinding, MouseButton, MouseDownEvent, MouseMoveEvent,
+    MouseUpEvent, Pixels, Point, SharedString, StyledText, TextLayout, Window,
+};
+
+use crate::ActiveTheme as _;
+
+use super::utils;
+
+const CONTEXT: &str = "TextView";
+
+actions!(text_view, [Copy]);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-c", Copy, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-c", Copy, Some(CONTEXT)),
+    ]);
+}
+
+/// A position in the texts of a document, `text` is the index of the text in the rendering
+/// order, and `offset` is the byte offset in the text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct TextPoint {
+    text: usize,
+    offset: usize,
+}
+
+#[derive(Clone)]
+struct SelectableText {
+    text: SharedString,
+    /// The ranges of the text inserted on preparing, removed from the copied text.
+    inserted: Arc<Vec<Range<usize>>>,
+    layout: TextLayout,
+}
+
+/// The texts on the same line of the document, e.g.: the cells of a table row.
+#[derive(Debug, Clone, PartialEq)]
+struct TextRow {
+    top: Pixels,
+    bottom: Pixels,
+    /// The indices of the texts in the row.
+    texts: Vec<usize>,
+}
+
+/// Build the rows of the texts by the `bounds`, the texts overlapping vertically are in the
+/// same row, and the rows are sorted by the top to binary search the position.
+fn build_rows(bounds: &[Bounds<Pixels>]) -> Vec<TextRow> {
+    let mut sorted = (0..bounds.len()).collect::<Vec<_>>();
+    sorted.sort_by(|a, b| bounds[*a].top().0.total_cmp(&bounds[*b].top().0));
+
+    let mut rows: Vec<TextRow> = vec![];
+    for ix in sorted {
+        let (top, bottom) = (bounds[ix].top(), bounds[ix].bottom());
+        match rows.last_mut() {
+            Some(row) if top < row.bottom => {
+                row.bottom = row.bottom.max(bottom);
+                row.texts.push(ix);
+            }
+            _ => rows.push(TextRow {
+                top,
+                bottom,
+                texts: vec![ix],
+            }),
+        }
+    }
+    for row in rows.iter_mut() {
+        row.texts.sort();
+    }
+    rows
+}
+
+#[derive(Default)]
+struct SelectionState {
+    anchor: TextPoint,
+    head: TextPoint,
+    selecting: bool,
+    /// The texts of the last rendering, in the document order.
+    texts: Vec<SelectableText>,
+    /// The rows of the texts by the bounds of the last layout, built on the first hit-test.
+    rows: Option<Vec<TextRow>>,
+    focus_handle: Option<FocusHandle>,
+}
+
+impl SelectionState {
+    fn range(&self) -> Range<TextPoint> {
+        if self.anchor <= self.head {
+            self.anchor..self.head
+        } else {
+            self.head..self.anchor
+        }
+    }
+
+    /// Returns the selected range of the `ix` text, `None` if nothing is selected in it.
+    fn selected_range(&self, ix: usize, len: usize) -> Option<Range<usize>> {
+        let range = self.range();
+        if ix < range.start.text || ix > range.end.text {
+            return None;
+        }
+
+        let start = if ix == range.start.text {
+            range.start.offset.min(len)
+        } else {
+            0
+        };
+        let end = if ix == range.end.text {
+            range.end.offset.min(len)
+        } else {
+            len
+        };
+        (start < end).then_some(start..end)
+    }
+
+    /// Returns the nearest text point of the `position`, the texts above the position are
+    /// before it, and the texts below are after it.
+    fn point_for_position(&mut self, position: Point<Pixels>) -> TextPoint {
+        let texts = &self.texts;
+        let rows = self.rows.get_or_insert_with(|| {
+            let bounds = texts
+                .iter()
+                .map(|text| text.layout.bounds())
+                .collect::<Vec<_>>();
+            build_rows(&bounds)
+        });
+
+        let row_ix = rows.partition_point(|row| row.bottom <= position.y);
+        let Some(row) = rows.get(row_ix) else {
+            return match self.texts.last() {
+                Some(text) => TextPoint {
+                    text: self.texts.len() - 1,
+                    offset: text.text.len(),
+                },
+                None => TextPoint::default(),
+            };
+        };
+        if position.y < row.top {
+            return TextPoint {
+                text: row.texts[0],
+                offset: 0,
+            };
+        }
+
+        // The text under the position, or the nearest text in the row.
+        let distance = |ix: &usize| {
+            let bounds = self.texts[*ix].layout.bounds();
+            let dy = (bounds.top() - position.y).max(position.y - bounds.bottom());
+            let dx = (bounds.left() - position.x).max(position.x - bounds.right());
+            (dy.max(px(0.)).0, dx.max(px(0.)).0)
+        };
+        let ix = row
+            .texts
+            .iter()
+            .copied()
+            .find(|ix| self.texts[*ix].layout.bounds().contains(&position))
+            .or_else(|| {
+                row.texts.iter().copied().min_by(|a, b| {
+                    let (a, b) = (distance(a), distance(b));
+                    a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1))
+                })
+            })
+            .unwrap_or(row.texts[0]);
+
+        let offset = self.texts[ix]
+            .layout
+            .index_for_position(position)
+            .unwrap_or_else(|ix| ix);
+        TextPoint { text: ix, offset }
+    }
+
+    fn selected_text(&self) -> String {
+        let range = self.range();
+        let mut lines = vec![];
+        for (ix, text) in self.texts.iter().enumerate() {
+            if let Some(selected) = self.selected_range(ix, text.text.len()) {
+                // Remove the soft hyphens of the hyphenation and the CJK spacing.
+                lines.push(utils::remove_inserted(&text.text, selected, &text.inserted));
+            } else if ix > range.end.text {
+                break;
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// The mouse selection of the texts of a rendered document, the selected text is copied by
+/// the [`Copy`] action.
+///
+/// The texts are registered in rendering, so the texts rendered lazily (e.g.: the rows of the
+/// large tables) are not selectable.
+#[derive(Clone, Default)]
+pub(super) struct Selection(Rc<RefCell<SelectionState>>);
+
+impl Selection {
+    /// Clear the texts of the last render, called before rendering the document.
+    pub(super) fn clear(&self) {
+        let mut state = self.0.borrow_mut();
+        state.texts.clear();
+        state.rows = None;
+    }
+
+    fn focus_handle(&self, cx: &mut App) -> FocusHandle {
+        self.0
+            .borrow_mut()
+            .focus_handle
+            .get_or_insert_with(|| cx.focus_handle())
+            .clone()
+    }
+
+    /// Returns the container of the document, it is focused to copy the selected text.
+    pub(super) fn container(&self, cx: &mut App) -> Div {
+        let selection = self.clone();
+        div()
+            .track_focus(&self.focus_handle(cx))
+            .key_context(CONTEXT)
+            .on_action(move |_: &Copy, _, cx| selection.copy(cx))
+    }
+
+    /// Copy the selected text to the clipboard.
+    pub(super) fn copy(&self, cx: &mut App) {
+        let text = self.0.borrow().selected_text();
+        if !text.is_empty() {
+            cx.write_to_clipboard(ClipboardItem::new_string(text));
+        }
+    }
+
+    /// Build the [`StyledText`] of a text by `build` with the highlight of the selected range,
+    /// and register it to the `selection` of the document.
+    ///
+    /// The `inserted` ranges of the text are removed from the copied text.
+    pub(super) fn styled_text(
+        selection: Option<&Selection>,
+        text: SharedString,
+        inserted: Arc<Vec<Range<usize>>>,
+        cx: &App,
+        build: impl FnOnce(Option<(Range<usize>, HighlightStyle)>) -> StyledText,
+    ) -> StyledText {
+        let Some(selection) = selection else {
+            return build(None);
+        };
+
+        let highlight = {
+            let state = selection.0.borrow();
+            state
+                .selected_range(state.texts.len(), text.len())
+                .map(|range| {
+                    let style = HighlightStyle {
+                        background_color: Some(cx.theme().selection),
+                        ..Default::default()
+                    };
+                    (range, style)
+                })
+        };
+        let styled_text = build(highlight);
+        selection.0.borrow_mut().texts.push(SelectableText {
+            text,
+            inserted,
+            layout: styled_text.layout().clone(),
+        });
+        styled_text
+    }
+
+    /// Handle the mouse events to select the texts in the `hitbox` of the document.
+    pub(super) fn paint(&self, hitbox: &Hitbox, window: &mut Window) {
+        // The texts are laid out again, e.g.: scrolled.
+        self.0.borrow_mut().rows = None;
+
+        window.on_mouse_event({
+            let state = self.0.clone();
+            let hitbox = hitbox.clone();
+            move |event: &MouseDownEvent, phase, window, _| {
+                if phase != DispatchPhase::Bubble || event.button != MouseButton::Left {
+                    return;
+                }
+
+                let mut state = state.borrow_mut();
+                if !hitbox.is_hovered(window) {
+                    if state.anchor != state.head {
+                        state.head = state.anchor;
+                        window.refresh();
+                    }
+                    return;
+                }
+
+                let point = state.point_for_position(event.position);
+                if !event.modifiers.shift {
+                    state.anchor = point;
+                }
+                state.head = point;
+                state.selecting = true;
+                window.refresh();
+            }
+        });
+
+        window.on_mouse_event({
+            let state = self.0.clone();
+            move |event: &MouseMoveEvent, phase, window, _| {
+                if phase != DispatchPhase::Bubble {
+                    return;
+                }
+
+                let mut state = state.borrow_mut();
+                if !state.selecting {
+                    return;
+                }
+                if event.pressed_button != Some(MouseButton::Left) {
+                    state.selecting = false;
+                    return;
+                }
+
+                let point = state.point_for_position(event.position);
+                if point != state.head {
+                    state.head = point;
+                    window.refresh();
+                }
+            }
+        });
+
+        window.on_mouse_event({
+            let state = self.0.clone();
+            move |_: &MouseUpEvent, phase, _, _| {
+                if phase == DispatchPhase::Bubble {
+                    state.borrow_mut().selecting = false;
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, Bounds, Pixels};
+
+    use super::{build_rows, SelectionState, TextPoint};
+
+    fn text_bounds(x: f32, y: f32, width: f32, height: f32) -> Bounds<Pixels> {
+        Bounds::new(point(px(x), px(y)), size(px(width), px(height)))
+    }
+
+    #[test]
+    fn test_build_rows() {
+        let rows = build_rows(&[
+            text_bounds(0., 0., 100., 20.),
+            // The cells of a table row.
+            text_bounds(0., 30., 50., 20.),
+            text_bounds(50., 30., 50., 40.),
+            text_bounds(100., 35., 50., 20.),
+            text_bounds(0., 80., 100., 20.),
+        ]);
+        assert_eq!(
+            rows.iter()
+                .map(|row| (row.top, row.bottom, row.texts.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (px(0.), px(20.), vec![0]),
+                (px(30.), px(70.), vec![1, 2, 3]),
+                (px(80.), px(100.), vec![4]),
+            ]
+        );
+        assert_eq!(rows.partition_point(|row| row.bottom <= px(25.)), 1);
+        assert_eq!(rows.partition_point(|row| row.bottom <= px(60.)), 1);
+        assert_eq!(rows.partition_point(|row| row.bottom <= px(120.)), 3);
+    }
+
+    #[test]
+    fn test_selected_range() {
+        let state = SelectionState {
+            anchor: TextPoint { text: 3, offset: 2 },
+            head: TextPoint { text: 1, offset: 4 },
+            ..Default::default()
+        };
+
+        assert_eq!(state.selected_range(0, 10), None);
+        assert_eq!(state.selected_range(1, 10), Some(4..10));
+        assert_eq!(state.selected_range(1, 3), None);
+        assert_eq!(state.selected_range(2, 10), Some(0..10));
+        assert_eq!(state.selected_range(3, 10), Some(0..2));
+        assert_eq!(state.selected_range(4, 10), None);
+        assert_eq!(SelectionState::default().selected_range(0, 10), None);
+    }
+}
@@ -0,0 +1,32 @@
+use std::{ops::Range, rc::Rc};
+
+use gpui::{App, Window};
+
+/// The handler of the task list checkbox toggles of a [`TextView`](super::TextView), with the
+/// source range of the checkbox marker (e.g.: `[ ]`) and the new checked state.
+pub(super) type OnCheckboxToggle = Rc<dyn Fn(Range<usize>, bool, &mut Window, &mut App)>;
+
+/// Returns the range of the checkbox marker (`[ ]`, `[x]` or `[X]`) in the `text` of the
+/// task list item at the `item` range.
+pub(super) fn checkbox_range(text: &str, item: Range<usize>) -> Option<Range<usize>> {
+    let start = item.start + text.get(item.clone())?.find('[')?;
+    match text.get(start..start + 3)? {
+        "[ ]" | "[x]" | "[X]" => Some(start..start + 3),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::checkbox_range;
+
+    #[test]
+    fn test_checkbox_range() {
+        let text = "- [ ] todo\n- [x] done\n  1. [X] nested";
+        assert_eq!(checkbox_range(text, 0..10), Some(2..5));
+        assert_eq!(checkbox_range(text, 11..21), Some(13..16));
+        assert_eq!(&text[checkbox_range(text, 24..37).unwrap()], "[X]");
+        assert_eq!(checkbox_range("- [link](url)", 0..13), None);
+        assert_eq!(checkbox_range("- [ ]", 0..20), None);
+    }
+}
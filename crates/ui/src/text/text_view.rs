@@ -1,10 +1,20 @@
-use std::rc::Rc;
+use std::{ops::Range, path::Path, rc::Rc, time::Duration};
 
-use gpui::{px, rems, App, ElementId, IntoElement, Pixels, Rems, RenderOnce, SharedString, Window};
+use gpui::{
+    px, rems, App, ElementId, IntoElement, Pixels, Rems, RenderOnce, ScrollHandle, SharedString,
+    Window,
+};
 
 use crate::highlighter::HighlightTheme;
 
-use super::{html::HtmlElement, markdown::MarkdownElement};
+use super::{
+    clipboard::{read_html_from_clipboard, write_html_to_clipboard, write_markdown_to_clipboard},
+    html::HtmlElement,
+    link::OnLinkClick,
+    markdown::MarkdownElement,
+    task_list::OnCheckboxToggle,
+    watch::WatchedFile,
+};
 
 /// A text view that can render Markdown or HTML.
 ///
@@ -90,6 +100,40 @@ pub struct TextViewStyle {
     /// Highlight theme for code blocks. Default: [`HighlightTheme::default_light()`]
     pub highlight_theme: Rc<HighlightTheme>,
     pub is_dark: bool,
+    /// Insert the soft hyphens into the long words of the paragraphs for the narrow containers,
+    /// default is false.
+    ///
+    /// A hyphen is drawn at the end of the line when the line breaks at a soft hyphen.
+    ///
+    /// The patterns of the current locale are registered by [`register_hyphenation_patterns`](super::register_hyphenation_patterns).
+    pub hyphens: bool,
+    /// Insert a narrow space between the CJK and Latin characters of the paragraphs,
+    /// default is false.
+    ///
+    /// The spaces are removed from the copied text.
+    ///
+    /// The paragraphs are wrapped by the GPUI line wrapper, it breaks between any CJK characters,
+    /// but the closing punctuation rule (e.g.: no `。` at the line start) is only applied in the
+    /// [`TextInput`](crate::input::TextInput) soft wrap.
+    pub cjk_spacing: bool,
+    /// Show the copy button in the top-right corner of the code blocks on hover, default is true.
+    pub code_block_copy_button: bool,
+    /// The placeholder of the images while loading, default is [`ImagePlaceholder::Skeleton`].
+    pub image_placeholder: ImagePlaceholder,
+    /// Open the image in a full window lightbox on click, default is false.
+    pub image_lightbox: bool,
+}
+
+/// The placeholder of the images in [`TextView`] while loading.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImagePlaceholder {
+    /// A skeleton in the size of the image.
+    #[default]
+    Skeleton,
+    /// A spinner in the size of the image.
+    Spinner,
+    /// Show nothing.
+    None,
 }
 
 impl PartialEq for TextViewStyle {
@@ -97,6 +141,11 @@ impl PartialEq for TextViewStyle {
         self.paragraph_gap == other.paragraph_gap
             && self.heading_base_font_size == other.heading_base_font_size
             && self.highlight_theme == other.highlight_theme
+            && self.hyphens == other.hyphens
+            && self.cjk_spacing == other.cjk_spacing
+            && self.code_block_copy_button == other.code_block_copy_button
+            && self.image_placeholder == other.image_placeholder
+            && self.image_lightbox == other.image_lightbox
     }
 }
 
@@ -107,6 +156,11 @@ impl Default for TextViewStyle {
             heading_base_font_size: px(14.),
             highlight_theme: Rc::new(HighlightTheme::default_light().clone()),
             is_dark: false,
+            hyphens: false,
+            cjk_spacing: false,
+            code_block_copy_button: true,
+            image_placeholder: ImagePlaceholder::default(),
+            image_lightbox: false,
         }
     }
 }
@@ -117,6 +171,38 @@ impl TextViewStyle {
         self.paragraph_gap = gap;
         self
     }
+
+    /// Set to enable the hyphenation, default is false.
+    pub fn hyphens(mut self, hyphens: bool) -> Self {
+        self.hyphens = hyphens;
+        self
+    }
+
+    /// Set to add the spacing between the CJK and Latin characters, default is false.
+    pub fn cjk_spacing(mut self, cjk_spacing: bool) -> Self {
+        self.cjk_spacing = cjk_spacing;
+        self
+    }
+
+    /// Set to show the copy button of the code blocks on hover, default is true.
+    pub fn code_block_copy_button(mut self, show: bool) -> Self {
+        self.code_block_copy_button = show;
+        self
+    }
+
+    /// Set the placeholder of the images while loading, default is [`ImagePlaceholder::Skeleton`].
+    pub fn image_placeholder(mut self, placeholder: ImagePlaceholder) -> Self {
+        self.image_placeholder = placeholder;
+        self
+    }
+
+    /// Set to open the image in a full window lightbox on click, default is false.
+    ///
+    /// The image is zoomed by scrolling and panned by dragging in the lightbox.
+    pub fn image_lightbox(mut self, lightbox: bool) -> Self {
+        self.image_lightbox = lightbox;
+        self
+    }
 }
 
 impl TextView {
@@ -130,6 +216,57 @@ impl TextView {
         Self::Html(HtmlElement::new(id, raw))
     }
 
+    /// Create a text view of the file, the file is read in the background and reloaded when
+    /// it is changed.
+    ///
+    /// The `.html` and `.htm` files are rendered as HTML, the others as Markdown.
+    /// The file is watched until the text view is not rendered, and the read error is shown
+    /// in the view.
+    pub fn from_path(
+        id: impl Into<ElementId>,
+        path: impl AsRef<Path>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        let id = id.into();
+        let path = path.as_ref();
+        let file = window.use_keyed_state(id.clone(), cx, |_, cx| {
+            WatchedFile::new(path.to_path_buf(), cx)
+        });
+        let file = file.read(cx);
+        let (text, error) = (file.text.clone(), file.error.clone());
+        let is_html = path.extension().map_or(false, |ext| {
+            ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm")
+        });
+
+        if is_html {
+            Self::Html(HtmlElement::new(id, text).read_error(error))
+        } else {
+            Self::Markdown(MarkdownElement::new(id, text).read_error(error))
+        }
+    }
+
+    /// Create a text view from the clipboard for pasting.
+    ///
+    /// The rich text is created as a html text view, and the plain text as a markdown text view,
+    /// returns `None` if the clipboard has no text.
+    pub fn from_clipboard(id: impl Into<ElementId>, cx: &App) -> Option<Self> {
+        if let Some(html) = read_html_from_clipboard(cx) {
+            return Some(Self::html(id, html));
+        }
+
+        let text = cx.read_from_clipboard()?.text()?;
+        Some(Self::markdown(id, text))
+    }
+
+    /// Copy the content to the clipboard as the rich text (HTML with the plain text).
+    pub fn write_to_clipboard(&self, cx: &mut App) {
+        match self {
+            Self::Markdown(el) => write_markdown_to_clipboard(&el.text, cx),
+            Self::Html(el) => write_html_to_clipboard(&el.text, cx),
+        }
+    }
+
     /// Set the source text of the text view.
     pub fn text(self, raw: impl Into<SharedString>) -> Self {
         match self {
@@ -145,6 +282,58 @@ impl TextView {
             Self::Html(el) => Self::Html(el.style(style)),
         }
     }
+
+    /// Set the debounce of the [`Self::text`] changes, the source is parsed at most once
+    /// in the duration, and the latest source is parsed after it.
+    ///
+    /// Default is no debounce.
+    pub fn debounce(self, debounce: Duration) -> Self {
+        match self {
+            Self::Markdown(el) => Self::Markdown(el.debounce(debounce)),
+            Self::Html(el) => Self::Html(el.debounce(debounce)),
+        }
+    }
+
+    /// Set the scroll handle of the scrollable container of the view, clicking a footnote
+    /// reference scrolls the definition to the top of the container.
+    ///
+    /// Only for Markdown.
+    pub fn scroll_handle(self, scroll_handle: &ScrollHandle) -> Self {
+        match self {
+            Self::Markdown(el) => Self::Markdown(el.scroll_handle(scroll_handle)),
+            Self::Html(el) => Self::Html(el),
+        }
+    }
+
+    /// Set the handler of the link clicks with the url, e.g.: to handle the in-app links or
+    /// veto the navigation.
+    ///
+    /// Default is to open the url by the [`router::open_url`](crate::router::open_url), call it
+    /// in the handler to fall back to the default behavior.
+    pub fn on_link_click(self, handler: impl Fn(&str, &mut Window, &mut App) + 'static) -> Self {
+        let handler: OnLinkClick = Rc::new(handler);
+        match self {
+            Self::Markdown(el) => Self::Markdown(el.on_link_click(handler)),
+            Self::Html(el) => Self::Html(el.on_link_click(handler)),
+        }
+    }
+
+    /// Set the handler of the task list checkbox clicks, with the source range of the checkbox
+    /// marker (e.g.: `[ ]`) and the new checked state, the checkboxes are read-only if not set.
+    ///
+    /// Replace the range with `[x]` or `[ ]` in the handler to update the source.
+    ///
+    /// Only for Markdown.
+    pub fn on_checkbox_toggle(
+        self,
+        handler: impl Fn(Range<usize>, bool, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        let handler: OnCheckboxToggle = Rc::new(handler);
+        match self {
+            Self::Markdown(el) => Self::Markdown(el.on_checkbox_toggle(handler)),
+            Self::Html(el) => Self::Html(el),
+        }
+    }
 }
 
 impl RenderOnce for TextView {
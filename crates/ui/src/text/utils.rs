@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 const NUMBERED_PREFIXES_1: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 const NUMBERED_PREFIXES_2: &str = "abcdefghijklmnopqrstuvwxyz";
 
@@ -34,9 +36,84 @@ pub fn list_item_prefix(ix: usize, ordered: bool, depth: usize) -> String {
     }
 }
 
+/// Map the range of the original text to the range of the prepared text.
+pub(crate) fn remap_range(range: &Range<usize>, inserted: &[(usize, usize)]) -> Range<usize> {
+    let mut start = range.start;
+    let mut end = range.end;
+    for (offset, len) in inserted {
+        // The inserted text at the range start is outside of the range.
+        if *offset <= range.start {
+            start += len;
+        }
+        if *offset < range.end {
+            end += len;
+        }
+    }
+
+    start..end.max(start)
+}
+
+/// Returns the ranges of the `inserted` text in the prepared text.
+pub(crate) fn inserted_ranges(inserted: &[(usize, usize)]) -> Vec<Range<usize>> {
+    let mut shift = 0;
+    inserted
+        .iter()
+        .map(|(offset, len)| {
+            let start = offset + shift;
+            shift += len;
+            start..start + len
+        })
+        .collect()
+}
+
+/// Returns the `range` of the prepared `text` without the `inserted` ranges, to map the
+/// text back to the source.
+pub(crate) fn remove_inserted(
+    text: &str,
+    range: Range<usize>,
+    inserted: &[Range<usize>],
+) -> String {
+    let mut out = String::with_capacity(range.len());
+    let mut start = range.start;
+    for inserted in inserted {
+        if inserted.end <= start {
+            continue;
+        }
+        if inserted.start >= range.end {
+            break;
+        }
+        if inserted.start > start {
+            out.push_str(&text[start..inserted.start]);
+        }
+        start = inserted.end;
+    }
+    if start < range.end {
+        out.push_str(&text[start..range.end]);
+    }
+    out
+}
+
+/// Escape the special characters of the HTML text or attribute value.
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::text::utils::list_item_prefix;
+    use crate::text::utils::{
+        escape_html, inserted_ranges, list_item_prefix, remap_range, remove_inserted,
+    };
 
     #[test]
     fn test_list_item_prefix() {
@@ -58,4 +135,39 @@ mod tests {
         assert_eq!(list_item_prefix(0, false, 3), "‣ ");
         assert_eq!(list_item_prefix(0, false, 4), "⁃ ");
     }
+
+    #[test]
+    fn test_remap_range() {
+        // 使用Rust开发
+        let inserted = vec![(6, 3), (10, 3)];
+        assert_eq!(remap_range(&(0..6), &inserted), 0..6);
+        assert_eq!(remap_range(&(6..10), &inserted), 9..13);
+        assert_eq!(remap_range(&(3..13), &inserted), 3..19);
+        assert_eq!(remap_range(&(10..16), &inserted), 16..22);
+    }
+
+    #[test]
+    fn test_remove_inserted() {
+        // 使用 Rust 开发
+        let text = "使用\u{2006}Rust\u{2006}开发";
+        let inserted = inserted_ranges(&[(6, 3), (10, 3)]);
+        assert_eq!(inserted, vec![6..9, 13..16]);
+        assert_eq!(
+            remove_inserted(text, 0..text.len(), &inserted),
+            "使用Rust开发"
+        );
+        assert_eq!(remove_inserted(text, 6..13, &inserted), "Rust");
+        assert_eq!(remove_inserted(text, 9..11, &inserted), "Ru");
+        assert_eq!(remove_inserted(text, 11..22, &inserted), "st开发");
+        assert_eq!(remove_inserted(text, 6..9, &inserted), "");
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(escape_html("Hello"), "Hello");
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & 'Jerry'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+        );
+    }
 }
@@ -0,0 +1,81 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use gpui::{Context, SharedString, Task};
+
+/// The interval to check the modified time of the watched file.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The file loaded by [`super::TextView::from_path`], it is read in the background and
+/// reloaded when it is changed.
+///
+/// The file is watched until the state is released, that is the text view is not rendered.
+pub(super) struct WatchedFile {
+    pub(super) text: SharedString,
+    /// The error of the last read, the text of the previous read is kept.
+    pub(super) error: Option<SharedString>,
+    _watch: Task<()>,
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+impl WatchedFile {
+    pub(super) fn new(path: PathBuf, cx: &mut Context<Self>) -> Self {
+        let watch = cx.spawn(async move |this, cx| {
+            let mut last_modified: Option<Option<SystemTime>> = None;
+
+            loop {
+                let loaded = cx
+                    .background_executor()
+                    .spawn({
+                        let path = path.clone();
+                        async move {
+                            let modified = modified_time(&path);
+                            if last_modified == Some(modified) {
+                                return None;
+                            }
+
+                            Some((modified, std::fs::read_to_string(&path)))
+                        }
+                    })
+                    .await;
+
+                if let Some((modified, result)) = loaded {
+                    last_modified = Some(modified);
+
+                    let updated = this.update(cx, |this, cx| {
+                        match result {
+                            Ok(text) => {
+                                this.text = text.into();
+                                this.error = None;
+                            }
+                            Err(err) => {
+                                tracing::error!("failed to read {:?}: {:?}", path, err);
+                                this.error = Some(err.to_string().into());
+                            }
+                        }
+                        cx.notify();
+                    });
+
+                    if updated.is_err() {
+                        return;
+                    }
+                }
+
+                cx.background_executor().timer(POLL_INTERVAL).await;
+            }
+        });
+
+        Self {
+            text: SharedString::default(),
+            error: None,
+            _watch: watch,
+        }
+    }
+}
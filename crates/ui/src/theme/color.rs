@@ -478,6 +478,55 @@ pub fn white() -> Hsla {
     DEFAULT_COLORS.white.hsla
 }
 
+/// The minimum contrast ratio for the normal text (WCAG level AA).
+pub const MIN_CONTRAST_RATIO: f32 = 4.5;
+
+/// Returns the relative luminance of the color defined by WCAG 2.x, in range: 0.0 .. 1.0
+///
+/// The alpha channel is ignored.
+pub fn luminance(color: Hsla) -> f32 {
+    fn channel(c: f32) -> f32 {
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    let rgb = color.to_rgb();
+    0.2126 * channel(rgb.r) + 0.7152 * channel(rgb.g) + 0.0722 * channel(rgb.b)
+}
+
+/// Returns the WCAG contrast ratio between two colors, in range: 1.0 .. 21.0
+///
+/// The normal text needs at least 4.5, and the large text needs at least 3.0 (level AA).
+pub fn contrast_ratio(a: Hsla, b: Hsla) -> f32 {
+    let (l1, l2) = (luminance(a), luminance(b));
+    (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+}
+
+/// Returns the [`black`] or [`white`] color that has the higher contrast on the `background`.
+///
+/// This is useful to choose the text color for an arbitrary background color, e.g.: the user-supplied color.
+pub fn auto_foreground(background: Hsla) -> Hsla {
+    let (black, white) = (black(), white());
+    if contrast_ratio(background, black) >= contrast_ratio(background, white) {
+        black
+    } else {
+        white
+    }
+}
+
+/// Returns the `foreground` if it is readable on the `background` ([`MIN_CONTRAST_RATIO`]),
+/// otherwise fallback to [`auto_foreground`].
+pub fn readable_foreground(foreground: Hsla, background: Hsla) -> Hsla {
+    if contrast_ratio(foreground, background) >= MIN_CONTRAST_RATIO {
+        foreground
+    } else {
+        auto_foreground(background)
+    }
+}
+
 color_methods!(slate);
 color_methods!(gray);
 color_methods!(zinc);
@@ -580,6 +629,31 @@ mod tests {
         assert_eq!(blue.mix(yellow, 0.2).to_hex(), "#0098FF");
     }
 
+    #[test]
+    fn test_contrast_ratio() {
+        let black = Hsla::parse_hex("#000000").unwrap();
+        let white = Hsla::parse_hex("#FFFFFF").unwrap();
+        assert_eq!(luminance(black), 0.0);
+        assert!((luminance(white) - 1.0).abs() < 1e-3);
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 1e-3);
+        assert!((contrast_ratio(white, black) - 21.0).abs() < 1e-3);
+        assert!((contrast_ratio(white, white) - 1.0).abs() < 1e-3);
+
+        let gray = Hsla::parse_hex("#777777").unwrap();
+        assert!((contrast_ratio(gray, white) - 4.48).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_auto_foreground() {
+        let yellow = Hsla::parse_hex("#FFFF00").unwrap();
+        let navy = Hsla::parse_hex("#000080").unwrap();
+        assert_eq!(auto_foreground(yellow), black());
+        assert_eq!(auto_foreground(navy), white());
+
+        assert_eq!(readable_foreground(navy, yellow), navy);
+        assert_eq!(readable_foreground(white(), yellow), black());
+    }
+
     #[test]
     fn test_color_name() {
         assert_eq!(ColorName::Purple.to_string(), "Purple");
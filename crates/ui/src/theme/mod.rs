@@ -1,4 +1,4 @@
-use crate::{highlighter::HighlightTheme, scroll::ScrollbarShow};
+use crate::{highlighter::HighlightTheme, scroll::ScrollbarShow, IconWeight, LayoutDirection};
 use gpui::{px, App, Global, Hsla, Pixels, SharedString, Window, WindowAppearance};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -10,9 +10,11 @@ use std::{
 mod color;
 mod schema;
 mod theme_color;
+mod tokens;
 pub use color::*;
 pub use schema::*;
 pub use theme_color::*;
+pub use tokens::*;
 
 pub fn init(cx: &mut App) {
     Theme::sync_system_appearance(None, cx);
@@ -54,6 +56,14 @@ pub struct Theme {
     pub tile_grid_size: Pixels,
     /// The shadow of the tile panel.
     pub tile_shadow: bool,
+    /// The layout direction, default: LeftToRight
+    pub direction: LayoutDirection,
+    /// Reduce the motion of the animations, default: false
+    ///
+    /// When enabled, the animated components will use instant or fade-only transitions.
+    pub reduce_motion: bool,
+    /// The stroke weight of the icons, default: Regular
+    pub icon_weight: IconWeight,
 }
 
 impl Default for Theme {
@@ -134,6 +144,24 @@ impl Theme {
         }
     }
 
+    /// Sync the reduce motion preference with the system, if the system preference is available.
+    ///
+    /// The preference is read in the background, and applied to the theme after it is read.
+    pub fn sync_reduce_motion(cx: &mut App) {
+        let read = cx
+            .background_executor()
+            .spawn(async { system_reduce_motion() });
+        cx.spawn(async move |cx| {
+            if let Some(reduce_motion) = read.await {
+                _ = cx.update(|cx| {
+                    cx.global_mut::<Theme>().reduce_motion = reduce_motion;
+                    cx.refresh_windows();
+                });
+            }
+        })
+        .detach();
+    }
+
     pub fn change(mode: impl Into<ThemeMode>, window: Option<&mut Window>, cx: &mut App) {
         let mode = mode.into();
         if !cx.has_global::<Theme>() {
@@ -161,6 +189,36 @@ impl Theme {
     }
 }
 
+/// Returns the reduce motion preference of the system, `None` if not available.
+fn system_reduce_motion() -> Option<bool> {
+    let read = |program: &str, args: &[&str]| -> Option<String> {
+        let output = std::process::Command::new(program)
+            .args(args)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    if cfg!(target_os = "macos") {
+        read(
+            "defaults",
+            &["read", "com.apple.universalaccess", "reduceMotion"],
+        )
+        .map(|v| v == "1")
+    } else if cfg!(any(target_os = "linux", target_os = "freebsd")) {
+        read(
+            "gsettings",
+            &["get", "org.gnome.desktop.interface", "enable-animations"],
+        )
+        .map(|v| v == "false")
+    } else {
+        None
+    }
+}
+
 impl From<ThemeColor> for Theme {
     fn from(colors: ThemeColor) -> Self {
         let mode = ThemeMode::default();
@@ -184,6 +242,9 @@ impl From<ThemeColor> for Theme {
             scrollbar_show: ScrollbarShow::default(),
             tile_grid_size: px(8.),
             tile_shadow: true,
+            direction: LayoutDirection::default(),
+            reduce_motion: false,
+            icon_weight: IconWeight::default(),
             colors,
             light_theme: ThemeColor::light(),
             dark_theme: ThemeColor::dark(),
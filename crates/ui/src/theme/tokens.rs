@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use gpui::{px, SharedString};
+use serde_json::{Map, Value};
+
+use crate::{Theme, ThemeConfig, ThemeConfigColors, ThemeMode};
+
+/// The max depth to resolve the alias of the tokens, to avoid the cyclic aliases.
+const MAX_ALIAS_DEPTH: usize = 8;
+/// The pixels of `1rem` in the dimension tokens.
+const REM_SIZE: f32 = 16.;
+
+/// The design tokens of the [W3C Design Tokens] format, or the JSON exported from the
+/// Figma Tokens Studio.
+///
+/// The tokens are flattened by the dotted paths of the groups, e.g.: the token at
+/// `{ "primary": { "background": { "$value": "#0f172a" } } }` is `primary.background`,
+/// the tokens with the same paths of the theme config (e.g.: `primary.background`,
+/// `border`, `radius`, `font.size`) are applied to the theme.
+///
+/// [W3C Design Tokens]: https://design-tokens.github.io/community-group/format/
+///
+/// ```ignore
+/// let tokens = DesignTokens::parse(&json)?
+///     .map("primary.background", "color.brand.600")
+///     .map("radius", "radius.md");
+/// Theme::global_mut(cx).apply_tokens(&tokens, ThemeMode::Light);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DesignTokens {
+    tokens: BTreeMap<String, Value>,
+    /// The theme config keys mapped to the token paths.
+    mappings: BTreeMap<String, String>,
+}
+
+impl DesignTokens {
+    /// Parse the tokens from the JSON.
+    pub fn parse(json: &str) -> Result<Self> {
+        let value = serde_json::from_str::<Value>(json)?;
+        let Value::Object(root) = value else {
+            return Err(anyhow!("design tokens must be a JSON object"));
+        };
+
+        let mut tokens = BTreeMap::new();
+        flatten(&root, "", &mut tokens);
+        Ok(Self {
+            tokens,
+            mappings: BTreeMap::new(),
+        })
+    }
+
+    /// Map the theme config key (e.g.: `primary.background`) to the token path (e.g.: `color.brand.600`).
+    pub fn map(mut self, key: impl Into<String>, token: impl Into<String>) -> Self {
+        self.mappings.insert(key.into(), token.into());
+        self
+    }
+
+    /// Returns the resolved value of the token path, the aliases (e.g.: `{color.blue.500}`) are resolved.
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        let mut value = self.tokens.get(path)?;
+        for _ in 0..MAX_ALIAS_DEPTH {
+            match value.as_str().and_then(alias_path) {
+                Some(path) => value = self.tokens.get(path)?,
+                None => return Some(value),
+            }
+        }
+        None
+    }
+
+    /// Returns the resolved value of the theme config key, by the mapped token path or the key itself.
+    fn value(&self, key: &str) -> Option<&Value> {
+        match self.mappings.get(key) {
+            Some(path) => self.get(path),
+            None => self.get(key),
+        }
+    }
+
+    /// Returns the dimension of the theme config key in pixels, e.g.: `6px`, `0.5rem` or `6`.
+    fn dimension(&self, key: &str) -> Option<f32> {
+        match self.value(key)? {
+            Value::Number(number) => number.as_f64().map(|n| n as f32),
+            Value::String(value) => parse_dimension(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`ThemeConfig`] with the colors of the tokens.
+    pub fn theme_config(&self, name: impl Into<SharedString>, mode: ThemeMode) -> ThemeConfig {
+        let keys = self
+            .tokens
+            .keys()
+            .chain(self.mappings.keys())
+            .collect::<Vec<_>>();
+        let colors = keys
+            .into_iter()
+            .filter_map(|key| {
+                let value = self.value(key)?.as_str()?;
+                Some((key.clone(), Value::from(value)))
+            })
+            .collect::<Map<_, _>>();
+
+        ThemeConfig {
+            name: name.into(),
+            mode,
+            // The unknown keys are ignored, so only the theme colors are applied.
+            colors: serde_json::from_value::<ThemeConfigColors>(Value::Object(colors))
+                .unwrap_or_default(),
+            highlight: None,
+        }
+    }
+}
+
+impl Theme {
+    /// Apply the colors, the `radius`, `radius.lg` and `font.size` of the design tokens.
+    pub fn apply_tokens(&mut self, tokens: &DesignTokens, mode: ThemeMode) {
+        self.apply_config(&tokens.theme_config("Design Tokens", mode));
+
+        if let Some(radius) = tokens.dimension("radius") {
+            self.radius = px(radius);
+        }
+        if let Some(radius) = tokens.dimension("radius.lg") {
+            self.radius_lg = px(radius);
+        }
+        if let Some(font_size) = tokens.dimension("font.size") {
+            self.font_size = px(font_size);
+        }
+    }
+}
+
+/// Flatten the token groups into the dotted paths.
+///
+/// The token is the object with `$value` (W3C) or `value` (Tokens Studio), the keys
+/// start with `$` (e.g.: `$type`, `$description`) of the groups are ignored.
+fn flatten(group: &Map<String, Value>, prefix: &str, tokens: &mut BTreeMap<String, Value>) {
+    for (key, value) in group {
+        if key.starts_with('$') {
+            continue;
+        }
+        let Value::Object(object) = value else {
+            continue;
+        };
+
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match object.get("$value").or_else(|| object.get("value")) {
+            Some(value) if !value.is_object() => {
+                tokens.insert(path, value.clone());
+            }
+            _ => flatten(object, &path, tokens),
+        }
+    }
+}
+
+/// Returns the path of the alias, e.g.: `color.blue.500` of `{color.blue.500}`.
+fn alias_path(value: &str) -> Option<&str> {
+    value
+        .trim()
+        .strip_prefix('{')
+        .and_then(|value| value.strip_suffix('}'))
+}
+
+/// Parse the dimension in pixels, e.g.: `6px`, `0.5rem` or `6`.
+fn parse_dimension(value: &str) -> Option<f32> {
+    let value = value.trim();
+    if let Some(rem) = value.strip_suffix("rem") {
+        return rem.trim().parse::<f32>().ok().map(|rem| rem * REM_SIZE);
+    }
+    value
+        .strip_suffix("px")
+        .unwrap_or(value)
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{parse_dimension, DesignTokens};
+    use crate::ThemeMode;
+
+    const TOKENS: &str = r##"{
+        "color": {
+            "$type": "color",
+            "blue": { "500": { "$value": "#3b82f6" } },
+            "brand": { "value": "{color.blue.500}", "type": "color" }
+        },
+        "primary": {
+            "background": { "$value": "{color.brand}" },
+            "foreground": { "$value": "#ffffff", "$description": "Text on primary." }
+        },
+        "border": { "$value": "#e5e7eb" },
+        "radius": { "md": { "$value": "0.375rem", "$type": "dimension" } },
+        "cycle": { "a": { "$value": "{cycle.b}" }, "b": { "$value": "{cycle.a}" } }
+    }"##;
+
+    #[test]
+    fn test_parse_tokens() {
+        let tokens = DesignTokens::parse(TOKENS)
+            .unwrap()
+            .map("radius", "radius.md");
+        assert_eq!(tokens.get("color.blue.500"), Some(&Value::from("#3b82f6")));
+        assert_eq!(tokens.get("color.brand"), Some(&Value::from("#3b82f6")));
+        assert_eq!(
+            tokens.get("primary.background"),
+            Some(&Value::from("#3b82f6"))
+        );
+        assert_eq!(tokens.get("color.$type"), None);
+        assert_eq!(tokens.get("cycle.a"), None);
+        assert_eq!(tokens.dimension("radius"), Some(6.));
+
+        let config = tokens.theme_config("Test", ThemeMode::Dark);
+        assert_eq!(config.mode, ThemeMode::Dark);
+        assert_eq!(config.colors.primary.as_deref(), Some("#3b82f6"));
+        assert_eq!(config.colors.primary_foreground.as_deref(), Some("#ffffff"));
+        assert_eq!(config.colors.border.as_deref(), Some("#e5e7eb"));
+        assert_eq!(config.colors.background, None);
+
+        assert!(DesignTokens::parse("[]").is_err());
+    }
+
+    #[test]
+    fn test_parse_dimension() {
+        assert_eq!(parse_dimension("6px"), Some(6.));
+        assert_eq!(parse_dimension("0.5rem"), Some(8.));
+        assert_eq!(parse_dimension(" 4 "), Some(4.));
+        assert_eq!(parse_dimension("auto"), None);
+    }
+}
@@ -1,12 +1,12 @@
-use std::{borrow::Cow, rc::Rc};
+use std::rc::Rc;
 
+use crate::i18n::t;
 use chrono::{Datelike, Local, NaiveDate};
 use gpui::{
     prelude::FluentBuilder as _, px, relative, App, ClickEvent, Context, ElementId, Empty, Entity,
     EventEmitter, FocusHandle, InteractiveElement, IntoElement, ParentElement, Render, RenderOnce,
     SharedString, StatefulInteractiveElement, StyleRefinement, Styled, Window,
 };
-use rust_i18n::t;
 
 use crate::{
     button::{Button, ButtonVariants as _},
@@ -469,7 +469,7 @@ impl CalendarState {
             10 => t!("Calendar.month.October"),
             11 => t!("Calendar.month.November"),
             12 => t!("Calendar.month.December"),
-            _ => Cow::Borrowed(""),
+            _ => SharedString::default(),
         }
         .into()
     }
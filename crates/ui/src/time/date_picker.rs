@@ -1,20 +1,22 @@
 use std::rc::Rc;
 
+use crate::i18n::t;
 use chrono::NaiveDate;
 use gpui::{
     anchored, deferred, div, prelude::FluentBuilder as _, px, App, AppContext, Context, ElementId,
     Empty, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement,
-    KeyBinding, MouseButton, ParentElement as _, Render, RenderOnce, SharedString,
+    KeyBinding, MouseButton, MouseUpEvent, ParentElement as _, Render, RenderOnce, SharedString,
     StatefulInteractiveElement as _, StyleRefinement, Styled, Subscription, Window,
 };
-use rust_i18n::t;
 
 use crate::{
     actions::Cancel,
     button::{Button, ButtonVariants as _},
     h_flex,
     input::clear_button,
-    v_flex, ActiveTheme, Disableable, Icon, IconName, Sizable, Size, StyleSized as _,
+    locale,
+    overlay::{self, OverlayId, OverlayLayer},
+    v_flex, ActiveTheme, Disableable, Icon, IconName, ReadOnly, Sizable, Size, StyleSized as _,
     StyledExt as _,
 };
 
@@ -64,8 +66,10 @@ pub struct DatePickerState {
     focus_handle: FocusHandle,
     date: Date,
     open: bool,
+    /// The overlay of the opened calendar, see [`overlay::sync`].
+    overlay_id: Option<OverlayId>,
     calendar: Entity<CalendarState>,
-    date_format: SharedString,
+    date_format: Option<SharedString>,
     number_of_months: usize,
     disabled_matcher: Option<Rc<Matcher>>,
     _subscriptions: Vec<Subscription>,
@@ -118,16 +122,18 @@ impl DatePickerState {
             date,
             calendar,
             open: false,
-            date_format: "%Y/%m/%d".into(),
+            overlay_id: None,
+            date_format: None,
             number_of_months: 1,
             disabled_matcher: None,
             _subscriptions,
         }
     }
 
-    /// Set the date format of the date picker to display in Input, default: "%Y/%m/%d".
+    /// Set the date format of the date picker to display in Input,
+    /// default is the date format of the current locale, e.g.: "%m/%d/%Y" for `en`.
     pub fn date_format(mut self, format: impl Into<SharedString>) -> Self {
-        self.date_format = format.into();
+        self.date_format = Some(format.into());
         self
     }
 
@@ -247,6 +253,7 @@ pub struct DatePicker {
     presets: Option<Vec<DateRangePreset>>,
     appearance: bool,
     disabled: bool,
+    read_only: bool,
 }
 
 impl Sizable for DatePicker {
@@ -274,6 +281,14 @@ impl Disableable for DatePicker {
     }
 }
 
+impl ReadOnly for DatePicker {
+    /// Set the read-only state, the calendar can't be opened to change the date.
+    fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+}
+
 impl Render for DatePickerState {
     fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl gpui::IntoElement {
         Empty
@@ -293,6 +308,7 @@ impl DatePicker {
             presets: None,
             appearance: true,
             disabled: false,
+            read_only: false,
         }
     }
 
@@ -329,13 +345,29 @@ impl DatePicker {
 
 impl RenderOnce for DatePicker {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
-        self.state.update(cx, |state, cx| {
+        let overlay_id = self.state.update(cx, |state, cx| {
             state.set_canlendar_disabled_matcher(window, cx);
+
+            let weak_state = cx.entity().downgrade();
+            overlay::sync(
+                &mut state.overlay_id,
+                state.open,
+                OverlayLayer::Menu,
+                window,
+                cx,
+                move |_, cx| {
+                    _ = weak_state.update(cx, |state, cx| {
+                        state.open = false;
+                        cx.notify();
+                    });
+                },
+            )
         });
 
         // This for keep focus border style, when click on the popup.
         let is_focused = self.focus_handle(cx).contains_focused(window, cx);
         let state = self.state.read(cx);
+        let interactive = !(self.disabled || self.read_only);
         let show_clean = self.cleanable && state.date.is_some();
         let placeholder = self
             .placeholder
@@ -343,13 +375,20 @@ impl RenderOnce for DatePicker {
             .unwrap_or_else(|| t!("DatePicker.placeholder").into());
         let display_title = state
             .date
-            .format(&state.date_format)
+            .format(
+                &state
+                    .date_format
+                    .clone()
+                    .unwrap_or_else(locale::date_format),
+            )
             .unwrap_or(placeholder.clone());
 
         div()
             .id(self.id.clone())
             .key_context("DatePicker")
-            .track_focus(&self.focus_handle(cx))
+            .when(!self.disabled, |this| {
+                this.track_focus(&self.focus_handle(cx))
+            })
             .when(state.open, |this| {
                 this.on_action(window.listener_for(&self.state, DatePickerState::escape))
             })
@@ -367,11 +406,14 @@ impl RenderOnce for DatePicker {
                     .justify_between()
                     .when(self.appearance, |this| {
                         this.bg(cx.theme().background)
-                            .border_1()
+                            .border_snapped(px(1.), window)
                             .border_color(cx.theme().input)
                             .rounded(cx.theme().radius)
                             .when(cx.theme().shadow, |this| this.shadow_xs())
                             .when(is_focused, |this| this.focused_border(cx))
+                            .when(self.read_only, |this| {
+                                this.bg(cx.theme().muted.opacity(0.5))
+                            })
                             .when(self.disabled, |this| {
                                 this.bg(cx.theme().muted)
                                     .text_color(cx.theme().muted_foreground)
@@ -380,7 +422,7 @@ impl RenderOnce for DatePicker {
                     .overflow_hidden()
                     .input_text_size(self.size)
                     .input_size(self.size)
-                    .when(!state.open && !self.disabled, |this| {
+                    .when(!state.open && interactive, |this| {
                         this.on_click(
                             window.listener_for(&self.state, DatePickerState::toggle_calendar),
                         )
@@ -392,7 +434,7 @@ impl RenderOnce for DatePicker {
                             .justify_between()
                             .gap_1()
                             .child(div().w_full().overflow_hidden().child(display_title))
-                            .when(!self.disabled, |this| {
+                            .when(interactive, |this| {
                                 this.when(show_clean, |this| {
                                     this.child(clear_button(cx).on_click(
                                         window.listener_for(&self.state, DatePickerState::clean),
@@ -421,11 +463,28 @@ impl RenderOnce for DatePicker {
                                 .shadow_lg()
                                 .rounded((cx.theme().radius * 2.).min(px(8.)))
                                 .bg(cx.theme().background)
+                                .when_some(overlay_id, |this, id| {
+                                    this.child(overlay::bounds_tracker(id))
+                                })
                                 .on_mouse_up_out(
                                     MouseButton::Left,
-                                    window.listener_for(&self.state, |view, _, window, cx| {
-                                        view.escape(&Cancel, window, cx);
-                                    }),
+                                    window.listener_for(
+                                        &self.state,
+                                        move |view, event: &MouseUpEvent, window, cx| {
+                                            // Keep open if click in the overlays above.
+                                            if overlay_id.map_or(false, |id| {
+                                                overlay::contains_above(
+                                                    id,
+                                                    event.position,
+                                                    window,
+                                                    cx,
+                                                )
+                                            }) {
+                                                return;
+                                            }
+                                            view.escape(&Cancel, window, cx);
+                                        },
+                                    ),
                                 )
                                 .child(
                                     h_flex()
@@ -464,7 +523,7 @@ impl RenderOnce for DatePicker {
                                 ),
                         ),
                     )
-                    .with_priority(2),
+                    .with_priority(OverlayLayer::Menu.priority()),
                 )
             })
     }
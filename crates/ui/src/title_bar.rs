@@ -1,11 +1,15 @@
 use std::rc::Rc;
 
-use crate::{h_flex, ActiveTheme, Icon, IconName, InteractiveElementExt as _, Sizable as _};
+use crate::{
+    h_flex,
+    window_border::{resize_cursor, resize_edge, window_paddings, RESIZE_HANDLE_SIZE},
+    ActiveTheme, Icon, IconName, InteractiveElementExt as _, Sizable as _,
+};
 use gpui::{
-    div, prelude::FluentBuilder as _, px, relative, AnyElement, App, ClickEvent, Div, Element,
-    Hsla, InteractiveElement, IntoElement, MouseButton, ParentElement, Pixels, RenderOnce,
-    Stateful, StatefulInteractiveElement as _, Style, Styled, TitlebarOptions, Window,
-    WindowControlArea,
+    div, prelude::FluentBuilder as _, px, relative, size, AnyElement, App, Bounds, ClickEvent,
+    Decorations, Div, Element, Hitbox, HitboxBehavior, Hsla, InteractiveElement, IntoElement,
+    MouseButton, ParentElement, Pixels, Point, RenderOnce, Stateful,
+    StatefulInteractiveElement as _, Style, Styled, TitlebarOptions, Window, WindowControlArea,
 };
 
 pub const TITLE_BAR_HEIGHT: Pixels = px(34.);
@@ -21,6 +25,7 @@ const TITLE_BAR_LEFT_PADDING: Pixels = px(12.);
 pub struct TitleBar {
     base: Stateful<Div>,
     children: Vec<AnyElement>,
+    center: Vec<AnyElement>,
     on_close_window: Option<Rc<Box<dyn Fn(&ClickEvent, &mut Window, &mut App)>>>,
 }
 
@@ -29,6 +34,7 @@ impl TitleBar {
         Self {
             base: div().id("title-bar").pl(TITLE_BAR_LEFT_PADDING),
             children: Vec::new(),
+            center: Vec::new(),
             on_close_window: None,
         }
     }
@@ -53,6 +59,15 @@ impl TitleBar {
         }
         self
     }
+
+    /// Add an element to the center of the title bar, e.g.: the search box.
+    ///
+    /// The center slot is centered in the window regardless of the children, the empty space
+    /// around it is still draggable.
+    pub fn center(mut self, element: impl IntoElement) -> Self {
+        self.center.push(element.into_any_element());
+        self
+    }
 }
 
 // The Windows control buttons have a fixed width of 35px.
@@ -253,6 +268,7 @@ impl RenderOnce for TitleBar {
 
         div().flex_shrink_0().child(
             self.base
+                .relative()
                 .flex()
                 .flex_row()
                 .items_center()
@@ -290,6 +306,17 @@ impl RenderOnce for TitleBar {
                         })
                         .children(self.children),
                 )
+                .when(!self.center.is_empty(), |this| {
+                    this.child(
+                        h_flex()
+                            .absolute()
+                            .top_0()
+                            .left_0()
+                            .size_full()
+                            .justify_center()
+                            .children(self.center),
+                    )
+                })
                 .child(WindowControls {
                     on_close_window: self.on_close_window,
                 }),
@@ -298,6 +325,9 @@ impl RenderOnce for TitleBar {
 }
 
 /// A TitleBar Element that can be move the window.
+///
+/// The top edge of it resizes the window if the window is client decorated and not tiled
+/// at the top.
 pub struct TitleBarElement {}
 
 impl IntoElement for TitleBarElement {
@@ -311,7 +341,7 @@ impl IntoElement for TitleBarElement {
 impl Element for TitleBarElement {
     type RequestLayoutState = ();
 
-    type PrepaintState = ();
+    type PrepaintState = Option<Hitbox>;
 
     fn id(&self) -> Option<gpui::ElementId> {
         None
@@ -342,11 +372,22 @@ impl Element for TitleBarElement {
         &mut self,
         _: Option<&gpui::GlobalElementId>,
         _: Option<&gpui::InspectorElementId>,
-        _: gpui::Bounds<Pixels>,
+        bounds: gpui::Bounds<Pixels>,
         _: &mut Self::RequestLayoutState,
-        _window: &mut Window,
+        window: &mut Window,
         _cx: &mut App,
     ) -> Self::PrepaintState {
+        let Decorations::Client { tiling } = window.window_decorations() else {
+            return None;
+        };
+        if tiling.top {
+            return None;
+        }
+
+        Some(window.insert_hitbox(
+            Bounds::new(bounds.origin, size(bounds.size.width, RESIZE_HANDLE_SIZE)),
+            HitboxBehavior::Normal,
+        ))
     }
 
     #[allow(unused_variables)]
@@ -356,13 +397,47 @@ impl Element for TitleBarElement {
         _: Option<&gpui::InspectorElementId>,
         bounds: gpui::Bounds<Pixels>,
         _: &mut Self::RequestLayoutState,
-        _: &mut Self::PrepaintState,
+        resize_hitbox: &mut Self::PrepaintState,
         window: &mut Window,
         cx: &mut App,
     ) {
-        use gpui::{MouseButton, MouseMoveEvent, MouseUpEvent};
+        use gpui::{MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent};
+
+        let resize_edge_at = |position: Point<Pixels>, window: &Window| {
+            let size = window.window_bounds().get_bounds().size;
+            resize_edge(
+                position,
+                window_paddings(window).top + RESIZE_HANDLE_SIZE,
+                size,
+            )
+        };
+
+        let resize_bounds = resize_hitbox.as_ref().map(|hitbox| hitbox.bounds);
+        if let Some(hitbox) = resize_hitbox.take() {
+            if let Some(edge) = resize_edge_at(window.mouse_position(), window) {
+                window.set_cursor_style(resize_cursor(edge), &hitbox);
+            }
+
+            let resize_bounds = hitbox.bounds;
+            window.on_mouse_event(
+                move |ev: &MouseDownEvent, _, window: &mut Window, cx: &mut App| {
+                    if ev.button != MouseButton::Left || !resize_bounds.contains(&ev.position) {
+                        return;
+                    }
+                    if let Some(edge) = resize_edge_at(ev.position, window) {
+                        cx.stop_propagation();
+                        window.start_window_resize(edge);
+                    }
+                },
+            );
+        }
+
         window.on_mouse_event(
             move |ev: &MouseMoveEvent, _, window: &mut Window, cx: &mut App| {
+                if resize_bounds.map_or(false, |resize_bounds| resize_bounds.contains(&ev.position))
+                {
+                    return;
+                }
                 if bounds.contains(&ev.position) && ev.pressed_button == Some(MouseButton::Left) {
                     window.start_window_move();
                 }
@@ -0,0 +1,517 @@
+//! A toolbar to lay out the buttons, the toggles and the separators in a row, the items that
+//! don't fit are collapsed into a trailing overflow menu.
+//!
+//! The toolbar is a single Tab stop, the arrow keys move the active item, and `enter` or
+//! `space` dispatches the action of it.
+//!
+//! ```ignore
+//! Toolbar::new("editor-toolbar")
+//!     .item(ToolbarItem::button("bold", "Bold", Box::new(Bold)).icon(IconName::Bold).priority(2))
+//!     .item(ToolbarItem::toggle("wrap", "Soft Wrap", Box::new(ToggleWrap)).checked(self.wrap))
+//!     .item(ToolbarItem::separator())
+//!     .item(ToolbarItem::button("export", "Export", Box::new(Export)))
+//! ```
+use std::{collections::HashMap, rc::Rc};
+
+use gpui::{
+    canvas, div, prelude::FluentBuilder as _, px, Action, App, Corner, ElementId, FocusHandle,
+    InteractiveElement as _, IntoElement, KeyDownEvent, ParentElement as _, Pixels, RenderOnce,
+    SharedString, StyleRefinement, Styled, Window,
+};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    divider::Divider,
+    h_flex,
+    i18n::t,
+    popup_menu::PopupMenuExt as _,
+    ActiveTheme as _, Disableable as _, IconName, LayoutDirection, RovingFocus,
+    RovingFocusExt as _, Selectable as _, Sizable as _, StyledExt as _,
+};
+
+const OVERFLOW_BUTTON_WIDTH: Pixels = px(28.);
+/// The gap between the items, see `gap_0p5`.
+const ITEM_GAP: Pixels = px(2.);
+
+#[derive(Clone)]
+enum ToolbarItemKind {
+    Button {
+        label: SharedString,
+        icon: Option<IconName>,
+        action: Rc<Box<dyn Action>>,
+        checked: Option<bool>,
+        disabled: bool,
+    },
+    Separator,
+}
+
+/// An item of the [`Toolbar`].
+#[derive(Clone)]
+pub struct ToolbarItem {
+    id: ElementId,
+    kind: ToolbarItemKind,
+    priority: i32,
+}
+
+impl ToolbarItem {
+    /// Create a button to dispatch the `action`, the label is shown as the tooltip if the
+    /// button has an icon.
+    pub fn button(
+        id: impl Into<ElementId>,
+        label: impl Into<SharedString>,
+        action: Box<dyn Action>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            kind: ToolbarItemKind::Button {
+                label: label.into(),
+                icon: None,
+                action: Rc::new(action),
+                checked: None,
+                disabled: false,
+            },
+            priority: 0,
+        }
+    }
+
+    /// Create a toggle to dispatch the `action`, the checked state is set by [`Self::checked`].
+    pub fn toggle(
+        id: impl Into<ElementId>,
+        label: impl Into<SharedString>,
+        action: Box<dyn Action>,
+    ) -> Self {
+        Self::button(id, label, action).checked(false)
+    }
+
+    /// Create a separator, the separators next to the collapsed items are hidden.
+    pub fn separator() -> Self {
+        Self {
+            id: ElementId::Name("separator".into()),
+            kind: ToolbarItemKind::Separator,
+            priority: 0,
+        }
+    }
+
+    /// Set the icon of the button.
+    pub fn icon(mut self, icon: IconName) -> Self {
+        if let ToolbarItemKind::Button { icon: i, .. } = &mut self.kind {
+            *i = Some(icon);
+        }
+        self
+    }
+
+    /// Set the checked state, the button becomes a toggle.
+    pub fn checked(mut self, checked: bool) -> Self {
+        if let ToolbarItemKind::Button { checked: c, .. } = &mut self.kind {
+            *c = Some(checked);
+        }
+        self
+    }
+
+    /// Set the disabled state of the button.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        if let ToolbarItemKind::Button { disabled: d, .. } = &mut self.kind {
+            *d = disabled;
+        }
+        self
+    }
+
+    /// Set the priority, the items with the lower priority are collapsed first, default is 0.
+    ///
+    /// The items with the same priority are collapsed from the end.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// The measured widths of the toolbar in the last frame, and the active item of the keyboard.
+struct ToolbarState {
+    focus_handle: FocusHandle,
+    /// The index of the active item, moved by the arrow keys.
+    active_ix: Option<usize>,
+    width: Option<Pixels>,
+    item_widths: HashMap<usize, Pixels>,
+}
+
+impl ToolbarState {
+    fn new(cx: &mut App) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            active_ix: None,
+            width: None,
+            item_widths: HashMap::new(),
+        }
+    }
+}
+
+/// Returns the visibility of the items to fit in the `available` width, the items are
+/// separated by the `gap`.
+///
+/// The items are hidden by the lowest priority first, the overflow button width is reserved
+/// if any item is hidden.
+fn visible_items(
+    widths: &[Pixels],
+    priorities: &[i32],
+    available: Pixels,
+    gap: Pixels,
+    overflow_width: Pixels,
+) -> Vec<bool> {
+    let mut visible = vec![true; widths.len()];
+    let mut total = widths.iter().fold(px(0.), |total, width| total + *width)
+        + gap * widths.len().saturating_sub(1) as f32;
+    if total <= available {
+        return visible;
+    }
+
+    total += overflow_width + gap;
+    let mut order = (0..widths.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&ix| (priorities[ix], std::cmp::Reverse(ix)));
+    for ix in order {
+        if total <= available {
+            break;
+        }
+        visible[ix] = false;
+        total -= widths[ix] + gap;
+    }
+    visible
+}
+
+/// Returns the indices of the `items` without the leading, trailing and repeated separators.
+fn trim_separators(items: &[&ToolbarItem]) -> Vec<usize> {
+    let mut result: Vec<usize> = vec![];
+    for (ix, item) in items.iter().enumerate() {
+        if matches!(item.kind, ToolbarItemKind::Separator)
+            && result.last().map_or(true, |&last| {
+                matches!(items[last].kind, ToolbarItemKind::Separator)
+            })
+        {
+            continue;
+        }
+        result.push(ix);
+    }
+    if result.last().map_or(false, |&last| {
+        matches!(items[last].kind, ToolbarItemKind::Separator)
+    }) {
+        result.pop();
+    }
+    result
+}
+
+/// A toolbar of the buttons, the toggles and the separators, the items that don't fit are
+/// collapsed into the overflow menu by the priority.
+#[derive(IntoElement)]
+pub struct Toolbar {
+    id: ElementId,
+    style: StyleRefinement,
+    items: Vec<ToolbarItem>,
+}
+
+impl Toolbar {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            items: vec![],
+        }
+    }
+
+    /// Add an item.
+    pub fn item(mut self, item: ToolbarItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Add the items.
+    pub fn items(mut self, items: impl IntoIterator<Item = ToolbarItem>) -> Self {
+        self.items.extend(items);
+        self
+    }
+
+    fn render_item(item: &ToolbarItem) -> impl IntoElement {
+        match &item.kind {
+            ToolbarItemKind::Separator => Divider::vertical().h_4().mx_1().into_any_element(),
+            ToolbarItemKind::Button {
+                label,
+                icon,
+                action,
+                checked,
+                disabled,
+            } => {
+                let action = action.clone();
+                Button::new(item.id.clone())
+                    .ghost()
+                    .small()
+                    .map(|this| match icon {
+                        Some(icon) => this.icon(icon.clone()).tooltip(label.clone()),
+                        None => this.label(label.clone()),
+                    })
+                    .selected(checked.unwrap_or(false))
+                    .disabled(*disabled)
+                    .on_click(move |_, window, cx| {
+                        window.dispatch_action(action.boxed_clone(), cx);
+                    })
+                    .into_any_element()
+            }
+        }
+    }
+}
+
+impl Styled for Toolbar {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for Toolbar {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, cx| ToolbarState::new(cx));
+        let (available, widths, focus_handle, active_ix) = {
+            let state = state.read(cx);
+            let widths = (0..self.items.len())
+                .map(|ix| state.item_widths.get(&ix).copied().unwrap_or_default())
+                .collect::<Vec<_>>();
+            (
+                state.width,
+                widths,
+                state.focus_handle.clone(),
+                state.active_ix,
+            )
+        };
+        let priorities = self
+            .items
+            .iter()
+            .map(|item| item.priority)
+            .collect::<Vec<_>>();
+        let visible = match available {
+            Some(available) => visible_items(
+                &widths,
+                &priorities,
+                available,
+                ITEM_GAP,
+                OVERFLOW_BUTTON_WIDTH,
+            ),
+            None => vec![true; self.items.len()],
+        };
+
+        let (shown, hidden): (Vec<_>, Vec<_>) = self
+            .items
+            .iter()
+            .enumerate()
+            .partition(|(ix, _)| visible[*ix]);
+        let shown = {
+            let items = shown.iter().map(|(_, item)| *item).collect::<Vec<_>>();
+            trim_separators(&items)
+                .into_iter()
+                .map(|ix| shown[ix])
+                .collect::<Vec<_>>()
+        };
+        let hidden = {
+            let items = hidden.iter().map(|(_, item)| *item).collect::<Vec<_>>();
+            Rc::new(
+                trim_separators(&items)
+                    .into_iter()
+                    .map(|ix| items[ix].clone())
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        // The separators and the disabled buttons are skipped by the arrow keys.
+        let shown_ixs = shown.iter().map(|(ix, _)| *ix).collect::<Vec<_>>();
+        let disabled_ixs = shown
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, item))| match &item.kind {
+                ToolbarItemKind::Separator => true,
+                ToolbarItemKind::Button { disabled, .. } => *disabled,
+            })
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>();
+        let active_pos = active_ix.and_then(|ix| shown_ixs.iter().position(|&i| i == ix));
+        let direction = LayoutDirection::of(window, cx);
+        let roving = RovingFocus::new(&focus_handle, shown.len(), {
+            let state = state.clone();
+            move |pos, window, cx| {
+                let ix = shown_ixs[*pos];
+                state.update(cx, |state, _| state.active_ix = Some(ix));
+                window.refresh();
+            }
+        })
+        .active_ix(active_pos)
+        .direction(direction)
+        .disabled(disabled_ixs);
+        let active_action = active_pos.and_then(|pos| match &shown[pos].1.kind {
+            ToolbarItemKind::Button {
+                action,
+                disabled: false,
+                ..
+            } => Some(action.clone()),
+            _ => None,
+        });
+        let is_focused = focus_handle.is_focused(window);
+
+        h_flex()
+            .id(self.id)
+            .roving_focus(roving)
+            .on_key_down(move |event: &KeyDownEvent, window, cx| {
+                let key = event.keystroke.key.as_str();
+                if !matches!(key, "enter" | "space") || event.keystroke.modifiers.modified() {
+                    return;
+                }
+                if let Some(action) = &active_action {
+                    cx.stop_propagation();
+                    window.dispatch_action(action.boxed_clone(), cx);
+                }
+            })
+            .flex_row_with(direction)
+            .relative()
+            .w_full()
+            .min_w_0()
+            .overflow_hidden()
+            .gap_0p5()
+            .refine_style(&self.style)
+            .children(shown.into_iter().map(|(ix, item)| {
+                let state = state.clone();
+                let is_active = is_focused && active_ix == Some(ix);
+                div()
+                    .relative()
+                    .flex_none()
+                    .child(Self::render_item(item))
+                    .when(is_active, |this| {
+                        this.child(
+                            div()
+                                .absolute()
+                                .top_0()
+                                .left_0()
+                                .size_full()
+                                .rounded(cx.theme().radius)
+                                .border_1()
+                                .focused_border(cx),
+                        )
+                    })
+                    .child(
+                        canvas(
+                            move |bounds, window, cx| {
+                                let width = bounds.size.width;
+                                if state.read(cx).item_widths.get(&ix) != Some(&width) {
+                                    state.update(cx, |state, _| {
+                                        state.item_widths.insert(ix, width);
+                                    });
+                                    window.refresh();
+                                }
+                            },
+                            |_, _, _, _| {},
+                        )
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .size_full(),
+                    )
+            }))
+            .when(!hidden.is_empty(), |this| {
+                this.child(
+                    Button::new("overflow")
+                        .ghost()
+                        .small()
+                        .flex_none()
+                        .icon(IconName::Ellipsis)
+                        .tooltip(t!("Toolbar.more"))
+                        .popup_menu_with_anchor(Corner::TopRight, move |mut menu, _, _| {
+                            for item in hidden.iter() {
+                                menu = match &item.kind {
+                                    ToolbarItemKind::Separator => menu.separator(),
+                                    ToolbarItemKind::Button {
+                                        label,
+                                        icon: Some(icon),
+                                        action,
+                                        checked: None,
+                                        disabled,
+                                    } => menu.menu_with_icon_and_disabled(
+                                        label.clone(),
+                                        icon.clone(),
+                                        action.boxed_clone(),
+                                        *disabled,
+                                    ),
+                                    ToolbarItemKind::Button {
+                                        label,
+                                        action,
+                                        checked,
+                                        disabled,
+                                        ..
+                                    } => menu.menu_with_check_and_disabled(
+                                        label.clone(),
+                                        checked.unwrap_or(false),
+                                        action.boxed_clone(),
+                                        *disabled,
+                                    ),
+                                };
+                            }
+                            menu
+                        }),
+                )
+            })
+            .child(
+                canvas(
+                    move |bounds, window, cx| {
+                        let width = bounds.size.width;
+                        if state.read(cx).width != Some(width) {
+                            state.update(cx, |state, _| state.width = Some(width));
+                            window.refresh();
+                        }
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .top_0()
+                .left_0()
+                .size_full(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{px, Pixels};
+
+    use super::{trim_separators, visible_items, ToolbarItem};
+
+    #[test]
+    fn test_visible_items() {
+        let widths: Vec<Pixels> = vec![px(30.), px(30.), px(30.), px(30.)];
+        let priorities = vec![0, 1, 0, 0];
+
+        assert_eq!(
+            visible_items(&widths, &priorities, px(120.), px(0.), px(20.)),
+            vec![true; 4]
+        );
+        // Collapse from the end by the same priority, and reserve the overflow button.
+        assert_eq!(
+            visible_items(&widths, &priorities, px(100.), px(0.), px(20.)),
+            vec![true, true, false, false]
+        );
+        // The higher priority is kept.
+        assert_eq!(
+            visible_items(&widths, &priorities, px(60.), px(0.), px(20.)),
+            vec![false, true, false, false]
+        );
+        // The gaps between the items are counted.
+        assert_eq!(
+            visible_items(&widths, &priorities, px(120.), px(2.), px(20.)),
+            vec![true, true, true, false]
+        );
+        assert_eq!(
+            visible_items(&widths, &priorities, px(126.), px(2.), px(20.)),
+            vec![true; 4]
+        );
+    }
+
+    #[test]
+    fn test_trim_separators() {
+        let button = ToolbarItem::button("a", "A", Box::new(gpui::NoAction));
+        let separator = ToolbarItem::separator();
+        let items = vec![
+            &separator, &button, &separator, &separator, &button, &separator,
+        ];
+        assert_eq!(trim_separators(&items), vec![1, 2, 4]);
+    }
+}
@@ -0,0 +1,147 @@
+use std::time::{Duration, Instant};
+
+use gpui::KeyDownEvent;
+
+/// The typed characters will be reset after this timeout.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// The number of items to move for the PageUp / PageDown keys.
+pub(crate) const PAGE_SIZE: usize = 10;
+
+/// A buffer of the typed characters to jump to the item by the typing, like the native listbox.
+#[derive(Debug, Default)]
+pub(crate) struct TypeAhead {
+    query: String,
+    last_input_at: Option<Instant>,
+}
+
+impl TypeAhead {
+    /// Returns true if the user is typing, the Space key should be treated as a character.
+    pub(crate) fn is_active(&self) -> bool {
+        !self.query.is_empty()
+            && self
+                .last_input_at
+                .map_or(false, |at| at.elapsed() < TYPE_AHEAD_TIMEOUT)
+    }
+
+    /// Push a character into the buffer and return the query.
+    pub(crate) fn push(&mut self, c: char) -> &str {
+        if !self.is_active() {
+            self.query.clear();
+        }
+
+        self.query.push(c);
+        self.last_input_at = Some(Instant::now());
+        &self.query
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.query.clear();
+        self.last_input_at = None;
+    }
+}
+
+/// Returns the character typed by the key down event, ignore the shortcuts.
+pub(crate) fn typed_char(event: &KeyDownEvent) -> Option<char> {
+    let modifiers = &event.keystroke.modifiers;
+    if modifiers.control || modifiers.platform || modifiers.function {
+        return None;
+    }
+
+    let key_char = event.keystroke.key_char.as_ref()?;
+    let mut chars = key_char.chars();
+    let c = chars.next()?;
+    if chars.next().is_some() || c.is_control() {
+        return None;
+    }
+
+    Some(c)
+}
+
+/// Find the item that label starts with the query (case-insensitive).
+///
+/// - The search starts after the `current` item when typing a single character (or repeating it),
+///   so typing the same character cycles through the items with the same initial.
+/// - Otherwise starts at the `current` item, so keep the current item if it still matches.
+///
+/// The `None` labels are not matchable, e.g.: separators, disabled items.
+pub(crate) fn find_match<T: AsRef<str>>(
+    labels: &[Option<T>],
+    current: Option<usize>,
+    query: &str,
+) -> Option<usize> {
+    let len = labels.len();
+    if len == 0 || query.is_empty() {
+        return None;
+    }
+
+    let query = query.to_lowercase();
+    let mut chars = query.chars();
+    let first = chars.next()?;
+    let (query, start) = if chars.all(|c| c == first) {
+        (first.to_string(), current.map_or(0, |ix| ix + 1))
+    } else {
+        (query, current.unwrap_or(0))
+    };
+
+    (0..len).map(|offset| (start + offset) % len).find(|&ix| {
+        labels[ix].as_ref().map_or(false, |label| {
+            label
+                .as_ref()
+                .trim_start()
+                .to_lowercase()
+                .starts_with(&query)
+        })
+    })
+}
+
+/// Returns the index to move by the PageUp (`forward` is false) / PageDown, clamped to the items.
+pub(crate) fn page_index(current: Option<usize>, len: usize, forward: bool) -> usize {
+    let last_ix = len.saturating_sub(1);
+    match (current, forward) {
+        (None, true) => PAGE_SIZE.min(last_ix),
+        (None, false) => 0,
+        (Some(ix), true) => (ix + PAGE_SIZE).min(last_ix),
+        (Some(ix), false) => ix.saturating_sub(PAGE_SIZE),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_match, page_index};
+
+    #[test]
+    fn test_find_match() {
+        let labels = [
+            Some("Apple"),
+            None,
+            Some("Banana"),
+            Some("apricot"),
+            Some("Blueberry"),
+            Some("Cherry"),
+        ];
+
+        assert_eq!(find_match(&labels, None, "a"), Some(0));
+        assert_eq!(find_match(&labels, Some(0), "a"), Some(3));
+        assert_eq!(find_match(&labels, Some(3), "a"), Some(0));
+        assert_eq!(find_match(&labels, Some(0), "aa"), Some(3));
+        assert_eq!(find_match(&labels, Some(0), "ap"), Some(0));
+        assert_eq!(find_match(&labels, Some(0), "apr"), Some(3));
+        assert_eq!(find_match(&labels, Some(2), "BL"), Some(4));
+        assert_eq!(find_match(&labels, Some(5), "b"), Some(2));
+        assert_eq!(find_match(&labels, None, "x"), None);
+        assert_eq!(find_match(&labels, None, ""), None);
+        assert_eq!(find_match::<&str>(&[], None, "a"), None);
+    }
+
+    #[test]
+    fn test_page_index() {
+        assert_eq!(page_index(None, 30, true), 10);
+        assert_eq!(page_index(None, 5, true), 4);
+        assert_eq!(page_index(None, 30, false), 0);
+        assert_eq!(page_index(Some(5), 30, true), 15);
+        assert_eq!(page_index(Some(25), 30, true), 29);
+        assert_eq!(page_index(Some(15), 30, false), 5);
+        assert_eq!(page_index(Some(5), 30, false), 0);
+    }
+}
@@ -0,0 +1,432 @@
+//! A zoomable and pannable viewport of an infinite canvas, as the basis of the node editors
+//! and the large diagrams.
+//!
+//! The elements are not scaled by the viewport, the app positions and sizes its elements in
+//! the viewport by the [`ViewportTransform`].
+//!
+//! ```ignore
+//! let state = cx.new(|_| ViewportState::new());
+//!
+//! // In render
+//! let transform = self.state.read(cx).transform();
+//! Viewport::new("diagram", &self.state)
+//!     .minimap(true)
+//!     .children(self.nodes.iter().map(|node| {
+//!         let origin = transform.to_screen(node.origin);
+//!         div()
+//!             .absolute()
+//!             .left(origin.x)
+//!             .top(origin.y)
+//!             .w(transform.scale(node.size.width))
+//!             .h(transform.scale(node.size.height))
+//!     }))
+//! ```
+use gpui::{
+    canvas, div, prelude::FluentBuilder as _, px, size, AnyElement, App, Bounds, Context,
+    ElementId, Entity, InteractiveElement as _, IntoElement, MouseButton, ParentElement, Pixels,
+    Point, RenderOnce, ScrollWheelEvent, Size, Styled, Window,
+};
+
+use crate::ActiveTheme as _;
+
+const MINIMAP_SIZE: Size<Pixels> = Size {
+    width: px(160.),
+    height: px(100.),
+};
+const DEFAULT_ZOOM_RANGE: (f32, f32) = (0.1, 8.);
+
+/// Returns the valid zoom range, the invalid bound (NaN, infinite or not positive) is replaced
+/// by the default, and the bounds are swapped if `min` > `max`.
+fn sanitize_zoom_range(min: f32, max: f32) -> (f32, f32) {
+    let valid = |zoom: f32| zoom.is_finite() && zoom > 0.;
+    let min = if valid(min) {
+        min
+    } else {
+        DEFAULT_ZOOM_RANGE.0
+    };
+    let max = if valid(max) {
+        max
+    } else {
+        DEFAULT_ZOOM_RANGE.1
+    };
+    if min > max {
+        (max, min)
+    } else {
+        (min, max)
+    }
+}
+
+/// The transform from the canvas coordinates to the viewport coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewportTransform {
+    pub zoom: f32,
+    /// The viewport position of the canvas origin.
+    pub offset: Point<Pixels>,
+}
+
+impl Default for ViewportTransform {
+    fn default() -> Self {
+        Self {
+            zoom: 1.,
+            offset: Point::default(),
+        }
+    }
+}
+
+impl ViewportTransform {
+    /// Returns the viewport position of the canvas `point`.
+    pub fn to_screen(&self, point: Point<Pixels>) -> Point<Pixels> {
+        Point::new(
+            point.x * self.zoom + self.offset.x,
+            point.y * self.zoom + self.offset.y,
+        )
+    }
+
+    /// Returns the canvas position of the viewport `point`.
+    pub fn to_canvas(&self, point: Point<Pixels>) -> Point<Pixels> {
+        Point::new(
+            (point.x - self.offset.x) / self.zoom,
+            (point.y - self.offset.y) / self.zoom,
+        )
+    }
+
+    /// Returns the viewport length of the canvas `length`.
+    pub fn scale(&self, length: Pixels) -> Pixels {
+        length * self.zoom
+    }
+
+    /// Returns the transform zoomed to `zoom`, the canvas point at the viewport `anchor` is kept.
+    fn zoomed_at(&self, zoom: f32, anchor: Point<Pixels>) -> Self {
+        let point = self.to_canvas(anchor);
+        Self {
+            zoom,
+            offset: Point::new(anchor.x - point.x * zoom, anchor.y - point.y * zoom),
+        }
+    }
+
+    /// Returns the transform to fit the canvas `content` in the viewport of `size` with the
+    /// `padding`, the content is centered.
+    fn fit(
+        content: Bounds<Pixels>,
+        size: Size<Pixels>,
+        padding: Pixels,
+        zoom_range: (f32, f32),
+    ) -> Self {
+        let width = (size.width - padding * 2.).max(px(1.));
+        let height = (size.height - padding * 2.).max(px(1.));
+        let zoom = (width / content.size.width.max(px(1.)))
+            .min(height / content.size.height.max(px(1.)))
+            .clamp(zoom_range.0, zoom_range.1);
+
+        let center = content.center();
+        Self {
+            zoom,
+            offset: Point::new(
+                size.width / 2. - center.x * zoom,
+                size.height / 2. - center.y * zoom,
+            ),
+        }
+    }
+}
+
+/// The state of the [`Viewport`].
+#[derive(Default)]
+pub struct ViewportState {
+    transform: ViewportTransform,
+    min_zoom: f32,
+    max_zoom: f32,
+    /// The bounds of the viewport in the last frame, in window coordinates.
+    bounds: Bounds<Pixels>,
+    /// The bounds of the content in the canvas coordinates, for fit and minimap.
+    content_bounds: Option<Bounds<Pixels>>,
+    /// The last mouse position of the drag panning.
+    drag_position: Option<Point<Pixels>>,
+}
+
+impl ViewportState {
+    pub fn new() -> Self {
+        Self {
+            min_zoom: DEFAULT_ZOOM_RANGE.0,
+            max_zoom: DEFAULT_ZOOM_RANGE.1,
+            ..Default::default()
+        }
+    }
+
+    /// Set the range of the zoom, default is 0.1 ~ 8.
+    ///
+    /// The invalid bound (NaN, infinite or not positive) fallback to the default, and the
+    /// bounds are swapped if `min` > `max`.
+    pub fn zoom_range(mut self, min: f32, max: f32) -> Self {
+        (self.min_zoom, self.max_zoom) = sanitize_zoom_range(min, max);
+        self
+    }
+
+    pub fn transform(&self) -> ViewportTransform {
+        self.transform
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.transform.zoom
+    }
+
+    /// Returns the bounds of the viewport in the last frame, in window coordinates.
+    pub fn bounds(&self) -> Bounds<Pixels> {
+        self.bounds
+    }
+
+    /// Returns the canvas position of the window `position`, e.g.: the mouse position.
+    pub fn window_to_canvas(&self, position: Point<Pixels>) -> Point<Pixels> {
+        self.transform.to_canvas(position - self.bounds.origin)
+    }
+
+    /// Set the bounds of the content in the canvas coordinates, used to fit and the minimap.
+    pub fn set_content_bounds(&mut self, bounds: Bounds<Pixels>, cx: &mut Context<Self>) {
+        if self.content_bounds == Some(bounds) {
+            return;
+        }
+
+        self.content_bounds = Some(bounds);
+        cx.notify();
+    }
+
+    /// Set the zoom around the viewport `anchor`, `None` to zoom around the center.
+    pub fn set_zoom(&mut self, zoom: f32, anchor: Option<Point<Pixels>>, cx: &mut Context<Self>) {
+        if zoom.is_nan() {
+            return;
+        }
+
+        let zoom = zoom.clamp(self.min_zoom, self.max_zoom);
+        let anchor = anchor.unwrap_or_else(|| {
+            Point::new(self.bounds.size.width / 2., self.bounds.size.height / 2.)
+        });
+        self.transform = self.transform.zoomed_at(zoom, anchor);
+        cx.notify();
+    }
+
+    /// Move the canvas by the `delta` in the viewport coordinates.
+    pub fn pan(&mut self, delta: Point<Pixels>, cx: &mut Context<Self>) {
+        self.transform.offset = self.transform.offset + delta;
+        cx.notify();
+    }
+
+    /// Zoom and pan to fit the content bounds in the viewport.
+    pub fn fit_to_content(&mut self, padding: Pixels, cx: &mut Context<Self>) {
+        let Some(content_bounds) = self.content_bounds else {
+            return;
+        };
+
+        self.transform = ViewportTransform::fit(
+            content_bounds,
+            self.bounds.size,
+            padding,
+            (self.min_zoom, self.max_zoom),
+        );
+        cx.notify();
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let delta = event.delta.pixel_delta(window.line_height());
+        let factor = (delta.y / px(1.) * 0.002).exp();
+        let anchor = event.position - self.bounds.origin;
+        self.set_zoom(self.transform.zoom * factor, Some(anchor), cx);
+    }
+}
+
+/// A zoomable and pannable viewport, the wheel zooms around the cursor and the drag on the
+/// background pans the canvas.
+#[derive(IntoElement)]
+pub struct Viewport {
+    id: ElementId,
+    state: Entity<ViewportState>,
+    minimap: bool,
+    children: Vec<AnyElement>,
+}
+
+impl Viewport {
+    pub fn new(id: impl Into<ElementId>, state: &Entity<ViewportState>) -> Self {
+        Self {
+            id: id.into(),
+            state: state.clone(),
+            minimap: false,
+            children: vec![],
+        }
+    }
+
+    /// Set to show the minimap at the bottom right, default is false.
+    ///
+    /// The minimap requires the content bounds set by [`ViewportState::set_content_bounds`].
+    pub fn minimap(mut self, minimap: bool) -> Self {
+        self.minimap = minimap;
+        self
+    }
+
+    fn render_minimap(state: &ViewportState, cx: &App) -> Option<impl IntoElement> {
+        let content = state.content_bounds?;
+        let visible = Bounds::from_corners(
+            state.transform.to_canvas(Point::default()),
+            state.transform.to_canvas(Point::new(
+                state.bounds.size.width,
+                state.bounds.size.height,
+            )),
+        );
+        let area = content.union(&visible);
+        let scale = (MINIMAP_SIZE.width / area.size.width.max(px(1.)))
+            .min(MINIMAP_SIZE.height / area.size.height.max(px(1.)));
+        let rect = |bounds: Bounds<Pixels>| {
+            div()
+                .absolute()
+                .left((bounds.origin.x - area.origin.x) * scale)
+                .top((bounds.origin.y - area.origin.y) * scale)
+                .w(bounds.size.width * scale)
+                .h(bounds.size.height * scale)
+        };
+
+        Some(
+            div()
+                .absolute()
+                .right_2()
+                .bottom_2()
+                .size(size(MINIMAP_SIZE.width, MINIMAP_SIZE.height))
+                .overflow_hidden()
+                .rounded(cx.theme().radius)
+                .border_1()
+                .border_color(cx.theme().border)
+                .bg(cx.theme().popover)
+                .child(rect(content).bg(cx.theme().muted))
+                .child(rect(visible).border_1().border_color(cx.theme().primary)),
+        )
+    }
+}
+
+impl ParentElement for Viewport {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements);
+    }
+}
+
+impl RenderOnce for Viewport {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = self.state.clone();
+        let minimap = self
+            .minimap
+            .then(|| Self::render_minimap(state.read(cx), cx))
+            .flatten();
+
+        div()
+            .id(self.id)
+            .relative()
+            .size_full()
+            .overflow_hidden()
+            .on_scroll_wheel({
+                let state = state.clone();
+                move |event, window, cx| {
+                    state.update(cx, |state, cx| state.on_scroll_wheel(event, window, cx));
+                }
+            })
+            .on_mouse_down(MouseButton::Left, {
+                let state = state.clone();
+                move |event, _, cx| {
+                    state.update(cx, |state, _| state.drag_position = Some(event.position));
+                }
+            })
+            .on_mouse_move({
+                let state = state.clone();
+                move |event, _, cx| {
+                    state.update(cx, |state, cx| {
+                        let Some(last_position) = state.drag_position else {
+                            return;
+                        };
+                        if event.pressed_button != Some(MouseButton::Left) {
+                            state.drag_position = None;
+                            return;
+                        }
+
+                        state.drag_position = Some(event.position);
+                        state.pan(event.position - last_position, cx);
+                    });
+                }
+            })
+            .on_mouse_up(MouseButton::Left, {
+                let state = state.clone();
+                move |_, _, cx| {
+                    state.update(cx, |state, _| state.drag_position = None);
+                }
+            })
+            .child(
+                canvas(
+                    {
+                        let state = state.clone();
+                        move |bounds, _, cx| state.update(cx, |state, _| state.bounds = bounds)
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .children(self.children)
+            .when_some(minimap, |this, minimap| this.child(minimap))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, Bounds};
+
+    use super::{sanitize_zoom_range, ViewportTransform};
+
+    #[test]
+    fn test_viewport_transform() {
+        let transform = ViewportTransform {
+            zoom: 2.,
+            offset: point(px(10.), px(20.)),
+        };
+        assert_eq!(
+            transform.to_screen(point(px(5.), px(5.))),
+            point(px(20.), px(30.))
+        );
+        assert_eq!(
+            transform.to_canvas(point(px(20.), px(30.))),
+            point(px(5.), px(5.))
+        );
+        assert_eq!(transform.scale(px(10.)), px(20.));
+
+        // The canvas point under the anchor is kept.
+        let anchor = point(px(100.), px(100.));
+        let zoomed = transform.zoomed_at(4., anchor);
+        assert_eq!(zoomed.zoom, 4.);
+        assert_eq!(zoomed.to_canvas(anchor), transform.to_canvas(anchor));
+    }
+
+    #[test]
+    fn test_viewport_fit() {
+        let content = Bounds::new(point(px(100.), px(100.)), size(px(200.), px(100.)));
+        let transform =
+            ViewportTransform::fit(content, size(px(420.), px(420.)), px(10.), (0.1, 8.));
+        assert_eq!(transform.zoom, 2.);
+        // The content center is at the viewport center.
+        assert_eq!(
+            transform.to_screen(content.center()),
+            point(px(210.), px(210.))
+        );
+
+        let transform =
+            ViewportTransform::fit(content, size(px(420.), px(420.)), px(10.), (0.1, 1.5));
+        assert_eq!(transform.zoom, 1.5);
+    }
+
+    #[test]
+    fn test_sanitize_zoom_range() {
+        assert_eq!(sanitize_zoom_range(0.5, 4.), (0.5, 4.));
+        assert_eq!(sanitize_zoom_range(4., 0.5), (0.5, 4.));
+        assert_eq!(sanitize_zoom_range(f32::NAN, 4.), (0.1, 4.));
+        assert_eq!(sanitize_zoom_range(0.5, f32::INFINITY), (0.5, 8.));
+        assert_eq!(sanitize_zoom_range(-1., 0.), (0.1, 8.));
+        // The default bound is swapped too.
+        assert_eq!(sanitize_zoom_range(10., f32::NAN), (8., 10.));
+    }
+}
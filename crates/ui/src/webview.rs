@@ -1,21 +1,40 @@
 use std::{ops::Deref, rc::Rc};
 
+use smol::channel::{self, Receiver};
 use wry::{
     dpi::{self, LogicalSize},
-    Rect,
+    PageLoadEvent, Rect,
 };
 
 use gpui::{
-    canvas, div, App, Bounds, ContentMask, DismissEvent, Element, ElementId, Entity, EventEmitter,
-    FocusHandle, Focusable, GlobalElementId, Hitbox, InteractiveElement, IntoElement, LayoutId,
-    MouseDownEvent, ParentElement as _, Pixels, Render, Size, Style, Styled as _, Window,
+    canvas, div, App, Bounds, ContentMask, Context, DismissEvent, Element, ElementId, Entity,
+    EventEmitter, FocusHandle, Focusable, GlobalElementId, Hitbox, InteractiveElement, IntoElement,
+    LayoutId, MouseDownEvent, ParentElement as _, Pixels, Render, SharedString, Size, Style,
+    Styled as _, Task, Window,
 };
 
+/// The events of the [`WebView`], emitted after the events are bound by [`WebView::events`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebViewEvent {
+    /// The webview is going to navigate to the URL.
+    Navigate(SharedString),
+    /// The page of the URL is started to load.
+    LoadStarted(SharedString),
+    /// The page of the URL is loaded.
+    Loaded(SharedString),
+    /// The message posted by `window.ipc.postMessage` in the page.
+    Message(String),
+}
+
+/// The receiver of the [`WebViewEvent`]s, created by [`WebView::bind_events`].
+pub struct WebViewEvents(Receiver<WebViewEvent>);
+
 pub struct WebView {
     focus_handle: FocusHandle,
     webview: Rc<wry::WebView>,
     visible: bool,
     bounds: Bounds<Pixels>,
+    _events_task: Option<Task<()>>,
 }
 
 impl Drop for WebView {
@@ -33,9 +52,69 @@ impl WebView {
             visible: true,
             bounds: Bounds::default(),
             webview: Rc::new(webview),
+            _events_task: None,
         }
     }
 
+    /// Bind the navigation, page load and IPC handlers to the `builder`, the returned
+    /// [`WebViewEvents`] should be passed to [`WebView::events`] after the webview is built.
+    ///
+    /// ```ignore
+    /// let (builder, events) = WebView::bind_events(wry::WebViewBuilder::new());
+    /// let webview = builder.build_as_child(&window_handle)?;
+    /// WebView::new(webview, window, cx).events(events, cx)
+    /// ```
+    pub fn bind_events(
+        builder: wry::WebViewBuilder<'_>,
+    ) -> (wry::WebViewBuilder<'_>, WebViewEvents) {
+        let (tx, rx) = channel::unbounded();
+
+        let builder = builder
+            .with_navigation_handler({
+                let tx = tx.clone();
+                move |url| {
+                    _ = tx.try_send(WebViewEvent::Navigate(url.into()));
+                    true
+                }
+            })
+            .with_on_page_load_handler({
+                let tx = tx.clone();
+                move |event, url| {
+                    let event = match event {
+                        PageLoadEvent::Started => WebViewEvent::LoadStarted(url.into()),
+                        PageLoadEvent::Finished => WebViewEvent::Loaded(url.into()),
+                    };
+                    _ = tx.try_send(event);
+                }
+            })
+            .with_ipc_handler(move |request| {
+                _ = tx.try_send(WebViewEvent::Message(request.into_body()));
+            });
+
+        (builder, WebViewEvents(rx))
+    }
+
+    /// Emit the [`WebViewEvent`]s received from the `events`.
+    pub fn events(mut self, events: WebViewEvents, cx: &mut Context<Self>) -> Self {
+        self._events_task = Some(cx.spawn(async move |this, cx| {
+            while let Ok(event) = events.0.recv().await {
+                if this.update(cx, |_, cx| cx.emit(event)).is_err() {
+                    break;
+                }
+            }
+        }));
+        self
+    }
+
+    /// Post the message to the page, it is received by the `message` event listener of `window`.
+    pub fn post_message(&self, message: &str) -> anyhow::Result<()> {
+        let data = serde_json::to_string(message)?;
+        Ok(self.webview.evaluate_script(&format!(
+            "window.dispatchEvent(new MessageEvent('message', {{ data: {} }}));",
+            data
+        ))?)
+    }
+
     pub fn show(&mut self) {
         let _ = self.webview.set_visible(true);
         self.visible = true;
@@ -80,6 +159,7 @@ impl Focusable for WebView {
 }
 
 impl EventEmitter<DismissEvent> for WebView {}
+impl EventEmitter<WebViewEvent> for WebView {}
 
 impl Render for WebView {
     fn render(
@@ -14,6 +14,11 @@ const SHADOW_SIZE: Pixels = Pixels(0.0);
 const SHADOW_SIZE: Pixels = Pixels(12.0);
 const BORDER_SIZE: Pixels = Pixels(1.0);
 pub(crate) const BORDER_RADIUS: Pixels = Pixels(0.0);
+/// The size of the resize handle inside the window, e.g.: the top edge of the [`TitleBar`](crate::TitleBar).
+pub(crate) const RESIZE_HANDLE_SIZE: Pixels = Pixels(4.0);
+/// The length of the corner resize handles along the edges, larger than the edges to be
+/// easier to grab.
+const RESIZE_CORNER_SIZE: Pixels = Pixels(16.0);
 
 /// Create a new window border.
 pub fn window_border() -> WindowBorder {
@@ -92,23 +97,7 @@ impl RenderOnce for WindowBorder {
                                 let Some(edge) = resize_edge(mouse, SHADOW_SIZE, size) else {
                                     return;
                                 };
-                                window.set_cursor_style(
-                                    match edge {
-                                        ResizeEdge::Top | ResizeEdge::Bottom => {
-                                            CursorStyle::ResizeUpDown
-                                        }
-                                        ResizeEdge::Left | ResizeEdge::Right => {
-                                            CursorStyle::ResizeLeftRight
-                                        }
-                                        ResizeEdge::TopLeft | ResizeEdge::BottomRight => {
-                                            CursorStyle::ResizeUpLeftDownRight
-                                        }
-                                        ResizeEdge::TopRight | ResizeEdge::BottomLeft => {
-                                            CursorStyle::ResizeUpRightDownLeft
-                                        }
-                                    },
-                                    &hitbox,
-                                );
+                                window.set_cursor_style(resize_cursor(edge), &hitbox);
                             },
                         )
                         .size_full()
@@ -176,25 +165,79 @@ impl RenderOnce for WindowBorder {
     }
 }
 
-fn resize_edge(pos: Point<Pixels>, shadow_size: Pixels, size: Size<Pixels>) -> Option<ResizeEdge> {
-    let edge = if pos.y < shadow_size && pos.x < shadow_size {
+/// Returns the cursor style to resize the window by the `edge`.
+pub(crate) fn resize_cursor(edge: ResizeEdge) -> CursorStyle {
+    match edge {
+        ResizeEdge::Top | ResizeEdge::Bottom => CursorStyle::ResizeUpDown,
+        ResizeEdge::Left | ResizeEdge::Right => CursorStyle::ResizeLeftRight,
+        ResizeEdge::TopLeft | ResizeEdge::BottomRight => CursorStyle::ResizeUpLeftDownRight,
+        ResizeEdge::TopRight | ResizeEdge::BottomLeft => CursorStyle::ResizeUpRightDownLeft,
+    }
+}
+
+/// Returns the resize edge of the window position `pos`, the edges are in `edge_size` from
+/// the window bounds, and the corners extend [`RESIZE_CORNER_SIZE`] along the edges.
+pub(crate) fn resize_edge(
+    pos: Point<Pixels>,
+    edge_size: Pixels,
+    size: Size<Pixels>,
+) -> Option<ResizeEdge> {
+    let corner_size = edge_size + RESIZE_CORNER_SIZE;
+    let top = pos.y < edge_size;
+    let bottom = pos.y > size.height - edge_size;
+    let left = pos.x < edge_size;
+    let right = pos.x > size.width - edge_size;
+    if !(top || bottom || left || right) {
+        return None;
+    }
+
+    let near_top = pos.y < corner_size;
+    let near_bottom = pos.y > size.height - corner_size;
+    let near_left = pos.x < corner_size;
+    let near_right = pos.x > size.width - corner_size;
+
+    let edge = if (top && near_left) || (left && near_top) {
         ResizeEdge::TopLeft
-    } else if pos.y < shadow_size && pos.x > size.width - shadow_size {
+    } else if (top && near_right) || (right && near_top) {
         ResizeEdge::TopRight
-    } else if pos.y < shadow_size {
-        ResizeEdge::Top
-    } else if pos.y > size.height - shadow_size && pos.x < shadow_size {
+    } else if (bottom && near_left) || (left && near_bottom) {
         ResizeEdge::BottomLeft
-    } else if pos.y > size.height - shadow_size && pos.x > size.width - shadow_size {
+    } else if (bottom && near_right) || (right && near_bottom) {
         ResizeEdge::BottomRight
-    } else if pos.y > size.height - shadow_size {
+    } else if top {
+        ResizeEdge::Top
+    } else if bottom {
         ResizeEdge::Bottom
-    } else if pos.x < shadow_size {
+    } else if left {
         ResizeEdge::Left
-    } else if pos.x > size.width - shadow_size {
-        ResizeEdge::Right
     } else {
-        return None;
+        ResizeEdge::Right
     };
     Some(edge)
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, ResizeEdge};
+
+    use super::resize_edge;
+
+    #[test]
+    fn test_resize_edge() {
+        let window_size = size(px(800.), px(600.));
+        let edge = |x: f32, y: f32| resize_edge(point(px(x), px(y)), px(8.), window_size);
+
+        assert_eq!(edge(400., 300.), None);
+        assert_eq!(edge(400., 2.), Some(ResizeEdge::Top));
+        assert_eq!(edge(400., 598.), Some(ResizeEdge::Bottom));
+        assert_eq!(edge(2., 300.), Some(ResizeEdge::Left));
+        assert_eq!(edge(798., 300.), Some(ResizeEdge::Right));
+        // The corners extend along the edges.
+        assert_eq!(edge(2., 2.), Some(ResizeEdge::TopLeft));
+        assert_eq!(edge(20., 2.), Some(ResizeEdge::TopLeft));
+        assert_eq!(edge(798., 20.), Some(ResizeEdge::TopRight));
+        assert_eq!(edge(2., 590.), Some(ResizeEdge::BottomLeft));
+        assert_eq!(edge(780., 598.), Some(ResizeEdge::BottomRight));
+        assert_eq!(edge(30., 2.), Some(ResizeEdge::Top));
+    }
+}
@@ -0,0 +1,72 @@
+//! The helpers of the app with multiple windows, the windows are the windows with the
+//! [`Root`] as the root view.
+//!
+//! ```ignore
+//! // Change the theme and the UI scale of all windows.
+//! windows::change_theme(ThemeMode::Dark, cx);
+//! windows::set_ui_scale(1.2, cx);
+//!
+//! // Focus the settings window if it is opened.
+//! if let Some(window) = windows::find(cx, |root, _| root.view().entity_type() == TypeId::of::<Settings>()) {
+//!     windows::focus(window, cx);
+//! }
+//! ```
+//!
+//! The dock panels can be dragged to the [`DockArea`](crate::dock::DockArea) of the other
+//! window, when the platform delivers the drop to the other window.
+use gpui::{AnyWindowHandle, App, Context, Window, WindowHandle};
+
+use crate::{
+    theme::{Theme, ThemeMode},
+    LayoutDirection, Root,
+};
+
+/// Returns the windows with the [`Root`], in the order of the window stack if it is
+/// available, the front most is the first.
+pub fn windows(cx: &App) -> Vec<WindowHandle<Root>> {
+    let handles = cx.window_stack().unwrap_or_else(|| cx.windows());
+    handles
+        .into_iter()
+        .filter_map(|handle| handle.downcast::<Root>())
+        .collect()
+}
+
+/// Returns the first window that the `predicate` returns true.
+pub fn find(cx: &App, predicate: impl Fn(&Root, &App) -> bool) -> Option<WindowHandle<Root>> {
+    windows(cx)
+        .into_iter()
+        .find(|handle| handle.read(cx).map_or(false, |root| predicate(root, cx)))
+}
+
+/// Activate the window and bring it to the front, returns false if the window is closed.
+pub fn focus(handle: impl Into<AnyWindowHandle>, cx: &mut App) -> bool {
+    handle
+        .into()
+        .update(cx, |_, window, _| window.activate_window())
+        .is_ok()
+}
+
+/// Update the [`Root`] of all windows.
+pub fn update_all(cx: &mut App, f: impl Fn(&mut Root, &mut Window, &mut Context<Root>)) {
+    for handle in windows(cx) {
+        _ = handle.update(cx, |root, window, cx| f(root, window, cx));
+    }
+}
+
+/// Change the theme mode and refresh all windows.
+pub fn change_theme(mode: impl Into<ThemeMode>, cx: &mut App) {
+    Theme::change(mode, None, cx);
+    cx.refresh_windows();
+}
+
+/// Set the UI scale of all windows, see [`Root::set_ui_scale`].
+pub fn set_ui_scale(scale: f32, cx: &mut App) {
+    update_all(cx, |root, window, cx| root.set_ui_scale(scale, window, cx));
+}
+
+/// Set the layout direction of all windows, see [`Root::set_direction`].
+pub fn set_direction(direction: Option<LayoutDirection>, cx: &mut App) {
+    update_all(cx, |root, window, cx| {
+        root.set_direction(direction, window, cx)
+    });
+}